@@ -1,6 +1,6 @@
 //! Namada node CLI.
 
-use eyre::{Context, Result};
+use eyre::{eyre, Context, Result};
 use namada::types::time::{DateTimeUtc, Utc};
 use namada_apps::cli::{self, cmds};
 use namada_apps::config::ValidatorLocalConfig;
@@ -11,14 +11,23 @@ pub fn main() -> Result<()> {
     match cmd {
         cmds::NamadaNode::Ledger(sub) => match sub {
             cmds::Ledger::Run(cmds::LedgerRun(args)) => {
-                let chain_ctx = ctx.take_chain_or_exit();
+                let mut chain_ctx = ctx.take_chain_or_exit();
                 let wasm_dir = chain_ctx.wasm_dir();
+                if args.wasm_bundle.is_some() {
+                    chain_ctx.config.ledger.shell.wasm_bundle =
+                        args.wasm_bundle;
+                }
+                if args.trust_wasm_bundle {
+                    chain_ctx.config.ledger.shell.trust_wasm_bundle = true;
+                }
+                validate_config(&chain_ctx.config.ledger)?;
                 sleep_until(args.start_time);
                 ledger::run(chain_ctx.config.ledger, wasm_dir);
             }
             cmds::Ledger::RunUntil(cmds::LedgerRunUntil(args)) => {
                 let mut chain_ctx = ctx.take_chain_or_exit();
                 let wasm_dir = chain_ctx.wasm_dir();
+                validate_config(&chain_ctx.config.ledger)?;
                 sleep_until(args.time);
                 chain_ctx.config.ledger.shell.action_at_height =
                     Some(args.action_at_height);
@@ -38,6 +47,18 @@ pub fn main() -> Result<()> {
                 ledger::rollback(chain_ctx.config.ledger)
                     .wrap_err("Failed to rollback the Namada node")?;
             }
+            cmds::Ledger::BuildWasmBundle(cmds::LedgerBuildWasmBundle(
+                args,
+            )) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                let wasm_dir = chain_ctx.wasm_dir();
+                ledger::build_wasm_bundle(
+                    chain_ctx.config.ledger,
+                    wasm_dir,
+                    args,
+                )
+                .wrap_err("Failed to build the WASM bundle")?;
+            }
         },
         cmds::NamadaNode::Config(sub) => match sub {
             cmds::Config::Gen(cmds::ConfigGen) => {
@@ -80,6 +101,15 @@ pub fn main() -> Result<()> {
     Ok(())
 }
 
+/// Check the ledger configuration before starting the node.
+fn validate_config(config: &namada_apps::config::Ledger) -> Result<()> {
+    config
+        .ethereum_bridge
+        .validate()
+        .map_err(|e| eyre!(e))
+        .wrap_err("Invalid Ethereum bridge configuration")
+}
+
 /// Sleep until the given start time if necessary.
 fn sleep_until(time: Option<DateTimeUtc>) {
     // Sleep until start time if needed
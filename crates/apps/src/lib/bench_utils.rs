@@ -74,7 +74,7 @@ use namada::types::token::{Amount, DenominatedAmount, Transfer};
 use namada::vm::wasm::run;
 use namada::{proof_of_stake, tendermint};
 use namada_sdk::masp::{
-    self, ShieldedContext, ShieldedTransfer, ShieldedUtils,
+    self, ShieldedContext, ShieldedTransfer, ShieldedUtils, WitnessStore,
 };
 pub use namada_sdk::tx::{
     TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
@@ -115,6 +115,8 @@ const BERTHA_SPENDING_KEY: &str = "bertha_spending";
 
 const FILE_NAME: &str = "shielded.dat";
 const TMP_FILE_NAME: &str = "shielded.tmp";
+const WITNESSES_FILE_NAME: &str = "shielded_witnesses.dat";
+const WITNESSES_TMP_FILE_NAME: &str = "shielded_witnesses.tmp";
 
 /// For `tracing_subscriber`, which fails if called more than once in the same
 /// process
@@ -392,6 +394,7 @@ impl BenchShell {
             tx,
             &mut self.inner.vp_wasm_cache,
             &mut self.inner.tx_wasm_cache,
+            &mut None,
         )
         .unwrap();
     }
@@ -651,10 +654,17 @@ impl ShieldedUtils for BenchShieldedUtils {
         )?;
         let mut bytes = Vec::new();
         ctx_file.read_to_end(&mut bytes)?;
-        // Fill the supplied context with the deserialized object
+        let loaded = ShieldedContext::<U>::deserialize(&mut &bytes[..])?;
+        // The note witnesses are kept in their own file
+        let mut witnesses_file =
+            File::open(self.context_dir.0.path().join(WITNESSES_FILE_NAME))?;
+        let mut bytes = Vec::new();
+        witnesses_file.read_to_end(&mut bytes)?;
+        // Fill the supplied context with the deserialized objects
         *ctx = ShieldedContext {
             utils: ctx.utils.clone(),
-            ..ShieldedContext::deserialize(&mut &bytes[..])?
+            witnesses: WitnessStore::deserialize(&mut &bytes[..])?,
+            ..loaded
         };
         Ok(())
     }
@@ -663,35 +673,45 @@ impl ShieldedUtils for BenchShieldedUtils {
     async fn save<U: ShieldedUtils>(
         &self,
         ctx: &ShieldedContext<U>,
+    ) -> std::io::Result<()> {
+        self.write_file(
+            WITNESSES_FILE_NAME,
+            WITNESSES_TMP_FILE_NAME,
+            &ctx.witnesses.serialize_to_vec(),
+        )?;
+        self.write_file(FILE_NAME, TMP_FILE_NAME, &ctx.serialize_to_vec())
+    }
+}
+
+impl BenchShieldedUtils {
+    /// Atomically replace the given file of the context directory with the
+    /// given bytes
+    fn write_file(
+        &self,
+        file_name: &str,
+        tmp_file_name: &str,
+        bytes: &[u8],
     ) -> std::io::Result<()> {
         let tmp_path =
-            self.context_dir.0.path().to_path_buf().join(TMP_FILE_NAME);
+            self.context_dir.0.path().to_path_buf().join(tmp_file_name);
         {
-            // First serialize the shielded context into a temporary file.
-            // Inability to create this file implies a simultaneuous write is in
-            // progress. In this case, immediately fail. This is unproblematic
-            // because the data intended to be stored can always be re-fetched
-            // from the blockchain.
-            let mut ctx_file = OpenOptions::new()
+            // First write the data into a temporary file. Inability to create
+            // this file implies a simultaneuous write is in progress. In this
+            // case, immediately fail. This is unproblematic because the data
+            // intended to be stored can always be re-fetched from the
+            // blockchain.
+            let mut file = OpenOptions::new()
                 .write(true)
                 .create_new(true)
                 .open(tmp_path.clone())?;
-            let mut bytes = Vec::new();
-            ctx.serialize(&mut bytes)
-                .expect("cannot serialize shielded context");
-            ctx_file.write_all(&bytes[..])?;
+            file.write_all(bytes)?;
         }
-        // Atomically update the old shielded context file with new data.
-        // Atomicity is required to prevent other client instances from reading
-        // corrupt data.
+        // Atomically update the old file with new data. Atomicity is required
+        // to prevent other client instances from reading corrupt data.
         std::fs::rename(
-            tmp_path.clone(),
-            self.context_dir.0.path().to_path_buf().join(FILE_NAME),
-        )?;
-        // Finally, remove our temporary file to allow future saving of shielded
-        // contexts.
-        std::fs::remove_file(tmp_path)?;
-        Ok(())
+            tmp_path,
+            self.context_dir.0.path().to_path_buf().join(file_name),
+        )
     }
 }
 
@@ -725,7 +745,10 @@ impl Client for BenchShell {
             storage_read_past_height_limit: None,
         };
 
-        if request.path == "/shell/dry_run_tx" {
+        if matches!(
+            request.path.as_str(),
+            "/shell/dry_run_tx" | "/shell/dry_run_tx_trace"
+        ) {
             dry_run_tx(ctx, &request)
         } else {
             RPC.handle(ctx, &request)
@@ -215,6 +215,7 @@ pub mod cmds {
                 // Simple transactions
                 .subcommand(TxCustom::def().display_order(1))
                 .subcommand(TxTransfer::def().display_order(1))
+                .subcommand(TxMultiTransfer::def().display_order(1))
                 .subcommand(TxIbcTransfer::def().display_order(1))
                 .subcommand(TxUpdateAccount::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
@@ -238,15 +239,21 @@ pub mod cmds {
                 .subcommand(TxMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
+                .subcommand(CancelEthBridgePoolTransfer::def().display_order(3))
+                .subcommand(BumpEthBridgePoolFee::def().display_order(3))
                 // PGF transactions
                 .subcommand(TxUpdateStewardCommission::def().display_order(4))
                 .subcommand(TxResignSteward::def().display_order(4))
+                .subcommand(TxSubmitPgfBatch::def().display_order(4))
+                .subcommand(TxChallengePgfBatch::def().display_order(4))
                 // Queries
                 .subcommand(QueryEpoch::def().display_order(5))
                 .subcommand(QueryAccount::def().display_order(5))
                 .subcommand(QueryTransfers::def().display_order(5))
+                .subcommand(ExportShieldedHistory::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
+                .subcommand(QueryMaspStats::def().display_order(5))
                 .subcommand(QueryBlock::def().display_order(5))
                 .subcommand(QueryBalance::def().display_order(5))
                 .subcommand(QueryBonds::def().display_order(5))
@@ -261,10 +268,12 @@ pub mod cmds {
                 .subcommand(QueryProposalResult::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
+                .subcommand(QueryIbcRateLimit::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
                 .subcommand(QueryCommissionRate::def().display_order(5))
                 .subcommand(QueryRewards::def().display_order(5))
                 .subcommand(QueryMetaData::def().display_order(5))
+                .subcommand(LightClientInit::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
                 .subcommand(GenIbcShieldedTransafer::def().display_order(6))
@@ -276,6 +285,8 @@ pub mod cmds {
             use NamadaClientWithContext::*;
             let tx_custom = Self::parse_with_ctx(matches, TxCustom);
             let tx_transfer = Self::parse_with_ctx(matches, TxTransfer);
+            let tx_multi_transfer =
+                Self::parse_with_ctx(matches, TxMultiTransfer);
             let tx_ibc_transfer = Self::parse_with_ctx(matches, TxIbcTransfer);
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
@@ -299,6 +310,10 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, TxUpdateStewardCommission);
             let tx_resign_steward =
                 Self::parse_with_ctx(matches, TxResignSteward);
+            let tx_submit_pgf_batch =
+                Self::parse_with_ctx(matches, TxSubmitPgfBatch);
+            let tx_challenge_pgf_batch =
+                Self::parse_with_ctx(matches, TxChallengePgfBatch);
             let tx_commission_rate_change =
                 Self::parse_with_ctx(matches, TxCommissionRateChange);
             let tx_change_consensus_key =
@@ -313,10 +328,14 @@ pub mod cmds {
             let query_epoch = Self::parse_with_ctx(matches, QueryEpoch);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
             let query_transfers = Self::parse_with_ctx(matches, QueryTransfers);
+            let export_shielded_history =
+                Self::parse_with_ctx(matches, ExportShieldedHistory);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
             let query_masp_reward_tokens =
                 Self::parse_with_ctx(matches, QueryMaspRewardTokens);
+            let query_masp_stats =
+                Self::parse_with_ctx(matches, QueryMaspStats);
             let query_block = Self::parse_with_ctx(matches, QueryBlock);
             let query_balance = Self::parse_with_ctx(matches, QueryBalance);
             let query_bonds = Self::parse_with_ctx(matches, QueryBonds);
@@ -338,19 +357,28 @@ pub mod cmds {
             let query_protocol_parameters =
                 Self::parse_with_ctx(matches, QueryProtocolParameters);
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
+            let query_ibc_rate_limit =
+                Self::parse_with_ctx(matches, QueryIbcRateLimit);
             let query_validator_state =
                 Self::parse_with_ctx(matches, QueryValidatorState);
             let query_commission =
                 Self::parse_with_ctx(matches, QueryCommissionRate);
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
+            let light_client_init =
+                Self::parse_with_ctx(matches, LightClientInit);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
+            let cancel_eth_bridge_pool_transfer =
+                Self::parse_with_ctx(matches, CancelEthBridgePoolTransfer);
+            let bump_eth_bridge_pool_fee =
+                Self::parse_with_ctx(matches, BumpEthBridgePoolFee);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
             let gen_ibc_shielded =
                 Self::parse_with_ctx(matches, GenIbcShieldedTransafer);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
             tx_custom
                 .or(tx_transfer)
+                .or(tx_multi_transfer)
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
                 .or(tx_init_account)
@@ -371,12 +399,18 @@ pub mod cmds {
                 .or(redelegate)
                 .or(claim_rewards)
                 .or(add_to_eth_bridge_pool)
+                .or(cancel_eth_bridge_pool_transfer)
+                .or(bump_eth_bridge_pool_fee)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
+                .or(tx_submit_pgf_batch)
+                .or(tx_challenge_pgf_batch)
                 .or(query_epoch)
                 .or(query_transfers)
+                .or(export_shielded_history)
                 .or(query_conversions)
                 .or(query_masp_reward_tokens)
+                .or(query_masp_stats)
                 .or(query_block)
                 .or(query_balance)
                 .or(query_bonds)
@@ -392,10 +426,12 @@ pub mod cmds {
                 .or(query_proposal_result)
                 .or(query_protocol_parameters)
                 .or(query_pgf)
+                .or(query_ibc_rate_limit)
                 .or(query_validator_state)
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_account)
+                .or(light_client_init)
                 .or(sign_tx)
                 .or(gen_ibc_shielded)
                 .or(utils)
@@ -437,6 +473,7 @@ pub mod cmds {
         // Ledger cmds
         TxCustom(TxCustom),
         TxTransfer(TxTransfer),
+        TxMultiTransfer(TxMultiTransfer),
         TxIbcTransfer(TxIbcTransfer),
         QueryResult(QueryResult),
         TxUpdateAccount(TxUpdateAccount),
@@ -458,13 +495,19 @@ pub mod cmds {
         ClaimRewards(ClaimRewards),
         Redelegate(Redelegate),
         AddToEthBridgePool(AddToEthBridgePool),
+        CancelEthBridgePoolTransfer(CancelEthBridgePoolTransfer),
+        BumpEthBridgePoolFee(BumpEthBridgePoolFee),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
         TxResignSteward(TxResignSteward),
+        TxSubmitPgfBatch(TxSubmitPgfBatch),
+        TxChallengePgfBatch(TxChallengePgfBatch),
         QueryEpoch(QueryEpoch),
         QueryAccount(QueryAccount),
         QueryTransfers(QueryTransfers),
+        ExportShieldedHistory(ExportShieldedHistory),
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
+        QueryMaspStats(QueryMaspStats),
         QueryBlock(QueryBlock),
         QueryBalance(QueryBalance),
         QueryBonds(QueryBonds),
@@ -475,11 +518,13 @@ pub mod cmds {
         QueryDelegations(QueryDelegations),
         QueryFindValidator(QueryFindValidator),
         QueryRawBytes(QueryRawBytes),
+        LightClientInit(LightClientInit),
         QueryProposal(QueryProposal),
         QueryProposalVotes(QueryProposalVotes),
         QueryProposalResult(QueryProposalResult),
         QueryProtocolParameters(QueryProtocolParameters),
         QueryPgf(QueryPgf),
+        QueryIbcRateLimit(QueryIbcRateLimit),
         QueryValidatorState(QueryValidatorState),
         QueryRewards(QueryRewards),
         SignTx(SignTx),
@@ -507,6 +552,8 @@ pub mod cmds {
         KeyAddrAdd(WalletAddKeyAddress),
         /// Key / address remove
         KeyAddrRemove(WalletRemoveKeyAddress),
+        /// Whole store encryption
+        StoreEncrypt(WalletEncryptStore),
     }
 
     impl Cmd for NamadaWallet {
@@ -520,6 +567,7 @@ pub mod cmds {
                 .subcommand(WalletImportKey::def())
                 .subcommand(WalletAddKeyAddress::def())
                 .subcommand(WalletRemoveKeyAddress::def())
+                .subcommand(WalletEncryptStore::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
@@ -533,6 +581,7 @@ pub mod cmds {
             let key_addr_add = SubCmd::parse(matches).map(Self::KeyAddrAdd);
             let key_addr_remove =
                 SubCmd::parse(matches).map(Self::KeyAddrRemove);
+            let store_encrypt = SubCmd::parse(matches).map(Self::StoreEncrypt);
             gen.or(derive)
                 .or(pay_addr_gen)
                 .or(key_addr_list)
@@ -541,6 +590,7 @@ pub mod cmds {
                 .or(import)
                 .or(key_addr_add)
                 .or(key_addr_remove)
+                .or(store_encrypt)
         }
     }
 
@@ -770,6 +820,30 @@ pub mod cmds {
         }
     }
 
+    /// Encrypt the whole wallet store
+    #[derive(Clone, Debug)]
+    pub struct WalletEncryptStore(pub args::StoreEncrypt);
+
+    impl SubCmd for WalletEncryptStore {
+        const CMD: &'static str = "encrypt-store";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::StoreEncrypt::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Encrypt the whole wallet store, including its aliases, \
+                     addresses and viewing keys, with a single passphrase. \
+                     Running it on an encrypted store changes the passphrase.",
+                )
+                .add_args::<args::StoreEncrypt>()
+        }
+    }
+
     /// Generate a payment address from a viewing key or payment address
     #[derive(Clone, Debug)]
     pub struct WalletGenPaymentAddress(pub args::PayAddressGen<args::CliTypes>);
@@ -799,6 +873,7 @@ pub mod cmds {
         Reset(LedgerReset),
         DumpDb(LedgerDumpDb),
         RollBack(LedgerRollBack),
+        BuildWasmBundle(LedgerBuildWasmBundle),
     }
 
     impl SubCmd for Ledger {
@@ -811,13 +886,18 @@ pub mod cmds {
                 let dump_db = SubCmd::parse(matches).map(Self::DumpDb);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
+                let build_wasm_bundle =
+                    SubCmd::parse(matches).map(Self::BuildWasmBundle);
                 run.or(reset)
                     .or(dump_db)
                     .or(rollback)
                     .or(run_until)
+                    .or(build_wasm_bundle)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
                         start_time: None,
+                        wasm_bundle: None,
+                        trust_wasm_bundle: false,
                     }))))
             })
         }
@@ -833,6 +913,7 @@ pub mod cmds {
                 .subcommand(LedgerReset::def())
                 .subcommand(LedgerDumpDb::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerBuildWasmBundle::def())
         }
     }
 
@@ -934,6 +1015,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerBuildWasmBundle(pub args::LedgerBuildWasmBundle);
+
+    impl SubCmd for LedgerBuildWasmBundle {
+        const CMD: &'static str = "build-wasm-bundle";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::LedgerBuildWasmBundle::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Compile the whitelisted transaction and validity \
+                     predicate WASMs for this machine into a bundle that can \
+                     be loaded at node startup with `--wasm-bundle`.",
+                )
+                .add_args::<args::LedgerBuildWasmBundle>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1132,6 +1236,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryIbcRateLimit(pub args::QueryIbcRateLimit<args::CliTypes>);
+
+    impl SubCmd for QueryIbcRateLimit {
+        const CMD: &'static str = "query-ibc-rate-limit";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryIbcRateLimit(args::QueryIbcRateLimit::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the IBC rate limits and the current usage of the \
+                     quotas.",
+                )
+                .add_args::<args::QueryIbcRateLimit<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxCustom(pub args::TxCustom<args::CliTypes>);
 
@@ -1170,6 +1299,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxMultiTransfer(pub args::TxMultiTransfer<args::CliTypes>);
+
+    impl SubCmd for TxMultiTransfer {
+        const CMD: &'static str = "multi-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxMultiTransfer(args::TxMultiTransfer::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction applying a batch of \
+                     transparent transfers atomically.",
+                )
+                .add_args::<args::TxMultiTransfer<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxIbcTransfer(pub args::TxIbcTransfer<args::CliTypes>);
 
@@ -1526,6 +1677,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryMaspStats(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryMaspStats {
+        const CMD: &'static str = "masp-stats";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryMaspStats(args::Query::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the MASP anonymity set statistics: note \
+                     commitments, nullifiers revealed per epoch, shielded \
+                     supply and reward rate history.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryBlock(pub args::Query<args::CliTypes>);
 
@@ -1661,6 +1835,32 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ExportShieldedHistory(
+        pub args::ExportShieldedHistory<args::CliTypes>,
+    );
+
+    impl SubCmd for ExportShieldedHistory {
+        const CMD: &'static str = "export-shielded-history";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                ExportShieldedHistory(args::ExportShieldedHistory::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Export the chronological history of the shielded \
+                     transactions of a viewing key.",
+                )
+                .add_args::<args::ExportShieldedHistory<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryCommissionRate(
         pub args::QueryCommissionRate<args::CliTypes>,
@@ -1811,6 +2011,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LightClientInit(pub args::LightClientInit<args::CliTypes>);
+
+    impl SubCmd for LightClientInit {
+        const CMD: &'static str = "light-client-init";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                LightClientInit(args::LightClientInit::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Initialize the light client used by the `--light` mode \
+                     of `query-balance` and `query-bytes` from a trusted \
+                     checkpoint.",
+                )
+                .add_args::<args::LightClientInit<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxInitProposal(pub args::InitProposal<args::CliTypes>);
 
@@ -1881,6 +2104,57 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxSubmitPgfBatch(pub args::SubmitPgfBatch<args::CliTypes>);
+
+    impl SubCmd for TxSubmitPgfBatch {
+        const CMD: &'static str = "submit-pgf-batch";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxSubmitPgfBatch(args::SubmitPgfBatch::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Submit a batch of PGF payments as a steward. The batch \
+                     is paid at the end of the challenge window unless a \
+                     validator escalates it to a governance proposal.",
+                )
+                .add_args::<args::SubmitPgfBatch<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxChallengePgfBatch(pub args::ChallengePgfBatch<args::CliTypes>);
+
+    impl SubCmd for TxChallengePgfBatch {
+        const CMD: &'static str = "challenge-pgf-batch";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxChallengePgfBatch(args::ChallengePgfBatch::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Escalate a pending steward batch of PGF payments to a \
+                     governance proposal as a validator.",
+                )
+                .add_args::<args::ChallengePgfBatch<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxCommissionRateChange(
         pub args::CommissionRateChange<args::CliTypes>,
@@ -2496,6 +2770,57 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct CancelEthBridgePoolTransfer(
+        pub args::CancelEthBridgePoolTransfer<args::CliTypes>,
+    );
+
+    impl SubCmd for CancelEthBridgePoolTransfer {
+        const CMD: &'static str = "cancel-erc20-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::CancelEthBridgePoolTransfer::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Cancel a transfer pending in the Ethereum Bridge pool. \
+                     The escrowed tokens and gas fees are refunded once the \
+                     next batch of transfers is relayed to Ethereum.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::CancelEthBridgePoolTransfer<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct BumpEthBridgePoolFee(
+        pub args::BumpEthBridgePoolFee<args::CliTypes>,
+    );
+
+    impl SubCmd for BumpEthBridgePoolFee {
+        const CMD: &'static str = "bump-erc20-transfer-fee";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::BumpEthBridgePoolFee::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Add gas fees to a transfer pending in the Ethereum \
+                     Bridge pool.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::BumpEthBridgePoolFee<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct ConstructProof(pub args::BridgePoolProof<args::CliTypes>);
 
@@ -2823,7 +3148,7 @@ pub mod args {
     use std::convert::TryFrom;
     use std::env;
     use std::net::SocketAddr;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::str::FromStr;
 
     use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
@@ -2832,6 +3157,7 @@ pub mod args {
     use namada::types::chain::{ChainId, ChainIdPrefix};
     use namada::types::dec::Dec;
     use namada::types::ethereum_events::EthAddress;
+    use namada::types::hash::Hash;
     use namada::types::keccak::KeccakHash;
     use namada::types::key::*;
     use namada::types::masp::PaymentAddress;
@@ -2840,13 +3166,15 @@ pub mod args {
     use namada::types::token;
     use namada::types::token::NATIVE_MAX_DECIMAL_PLACES;
     pub use namada_sdk::args::*;
+    use namada_sdk::masp::ShieldedHistoryFormat;
     pub use namada_sdk::tx::{
-        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-        TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_UPDATE_WASM,
+        TX_BRIDGE_POOL_WASM, TX_CHALLENGE_PGF_BATCH, TX_CHANGE_COMMISSION_WASM,
+        TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+        TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_MULTI_TRANSFER_WASM,
+        TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+        TX_REVEAL_PK, TX_SUBMIT_PGF_BATCH, TX_TRANSFER_WASM, TX_UNBOND_WASM,
         TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
         TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
         VP_USER_WASM,
@@ -2903,6 +3231,7 @@ pub mod args {
     pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
     pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
     pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
+    pub const CHANNEL_ID_OPT: ArgOpt<ChannelId> = CHANNEL_ID.opt();
     pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
     pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
     pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
@@ -2978,6 +3307,7 @@ pub mod args {
     pub const HD_WALLET_DERIVATION_PATH: ArgDefault<String> =
         arg_default("hd-path", DefaultFn(|| "default".to_string()));
     pub const HISTORIC: ArgFlag = flag("historic");
+    pub const HISTORY: ArgFlag = flag("history");
     pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
     pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
     pub const LEDGER_ADDRESS_ABOUT: &str =
@@ -2989,6 +3319,7 @@ pub mod args {
             TendermintAddress::from_str(raw).unwrap()
         }));
     pub const LEDGER_ADDRESS: Arg<TendermintAddress> = arg("node");
+    pub const LIGHT: ArgFlag = flag("light");
     pub const LIST_FIND_ADDRESSES_ONLY: ArgFlag = flag("addr");
     pub const LIST_FIND_KEYS_ONLY: ArgFlag = flag("keys");
     pub const LOCALHOST: ArgFlag = flag("localhost");
@@ -3008,6 +3339,7 @@ pub mod args {
     pub const OWNER: Arg<WalletAddress> = arg("owner");
     pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
     pub const PATH: Arg<PathBuf> = arg("path");
+    pub const PAYOUTS: Arg<PathBuf> = arg("payouts");
     pub const PIN: ArgFlag = flag("pin");
     pub const PORT_ID: ArgDefault<PortId> = arg_default(
         "port-id",
@@ -3023,6 +3355,7 @@ pub mod args {
     pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
     pub const PUBLIC_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("public-keys");
+    pub const PGF_BATCH_ID: Arg<u64> = arg("batch-id");
     pub const PROPOSAL_ID: Arg<u64> = arg("proposal-id");
     pub const PROPOSAL_ID_OPT: ArgOpt<u64> = arg_opt("proposal-id");
     pub const PROPOSAL_VOTE_PGF_OPT: ArgOpt<String> = arg_opt("pgf");
@@ -3050,6 +3383,8 @@ pub mod args {
         arg("self-bond-amount");
     pub const SENDER: Arg<String> = arg("sender");
     pub const SHIELDED: ArgFlag = flag("shielded");
+    pub const SHIELDED_HISTORY_FORMAT: ArgDefault<ShieldedHistoryFormat> =
+        arg_default("format", DefaultFn(|| ShieldedHistoryFormat::Csv));
     pub const SIGNER: ArgOpt<WalletAddress> = arg_opt("signer");
     pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("signing-keys");
@@ -3067,9 +3402,14 @@ pub mod args {
     pub const TOKEN_OPT: ArgOpt<WalletAddress> = TOKEN.opt();
     pub const TOKEN: Arg<WalletAddress> = arg("token");
     pub const TOKEN_STR: Arg<String> = arg("token");
+    pub const TRANSFER_HASH: Arg<String> = arg("transfer-hash");
     pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
+    pub const TRACE: ArgFlag = flag("trace");
     pub const TRANSPARENT: ArgFlag = flag("transparent");
+    pub const TRUST_HASH: Arg<Hash> = arg("trust-hash");
+    pub const TRUST_HEIGHT: Arg<BlockHeight> = arg("trust-height");
+    pub const TRUST_WASM_BUNDLE: ArgFlag = flag("trust-wasm-bundle");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
     pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
     pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
@@ -3094,6 +3434,7 @@ pub mod args {
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VP: ArgOpt<String> = arg_opt("vp");
     pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
+    pub const WASM_BUNDLE_PATH: ArgOpt<PathBuf> = arg_opt("wasm-bundle");
     pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
     pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
     pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
@@ -3155,12 +3496,20 @@ pub mod args {
     #[derive(Clone, Debug)]
     pub struct LedgerRun {
         pub start_time: Option<DateTimeUtc>,
+        pub wasm_bundle: Option<PathBuf>,
+        pub trust_wasm_bundle: bool,
     }
 
     impl Args for LedgerRun {
         fn parse(matches: &ArgMatches) -> Self {
             let start_time = NAMADA_START_TIME.parse(matches);
-            Self { start_time }
+            let wasm_bundle = WASM_BUNDLE_PATH.parse(matches);
+            let trust_wasm_bundle = TRUST_WASM_BUNDLE.parse(matches);
+            Self {
+                start_time,
+                wasm_bundle,
+                trust_wasm_bundle,
+            }
         }
 
         fn def(app: App) -> App {
@@ -3172,6 +3521,40 @@ pub mod args {
                  equivalent:\n2023-01-20T12:12:12Z\n2023-01-20 \
                  12:12:12Z\n2023-  01-20T12:  12:12Z",
             ))
+            .arg(WASM_BUNDLE_PATH.def().help(
+                "Path to a bundle of precompiled WASMs to load into the \
+                 compilation cache before starting. Overrides the \
+                 `wasm_bundle` setting of the configuration. The node refuses \
+                 to start if the bundle was built with a different wasmer \
+                 version, target or CPU features.",
+            ))
+            .arg(TRUST_WASM_BUNDLE.def().help(
+                "Trust the source of the WASM bundle. A bundle contains \
+                 native code that runs on the node without validation, so it \
+                 is only loaded when trusted. Overrides the \
+                 `trust_wasm_bundle` setting of the configuration.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerBuildWasmBundle {
+        pub out_file_path: PathBuf,
+    }
+
+    impl Args for LedgerBuildWasmBundle {
+        fn parse(matches: &ArgMatches) -> Self {
+            let out_file_path = OUT_FILE_PATH_OPT
+                .parse(matches)
+                .unwrap_or_else(|| PathBuf::from("wasm_bundle".to_string()));
+            Self { out_file_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(OUT_FILE_PATH_OPT.def().help(
+                "Path for the output bundle file. Defaults to \"wasm_bundle\" \
+                 in the current working directory.",
+            ))
         }
     }
 
@@ -3411,6 +3794,93 @@ pub mod args {
         }
     }
 
+    /// Parse the keccak hash of a transfer in the Ethereum bridge pool.
+    fn parse_transfer_hash(matches: &ArgMatches) -> KeccakHash {
+        let hash = TRANSFER_HASH.parse(matches);
+        KeccakHash::try_from(hash.as_str()).unwrap_or_else(|_| {
+            tracing::info!("Could not parse '{}' as a Keccak hash.", hash);
+            safe_exit(1)
+        })
+    }
+
+    impl CliToSdk<CancelEthBridgePoolTransfer<SdkTypes>>
+        for CancelEthBridgePoolTransfer<CliTypes>
+    {
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> CancelEthBridgePoolTransfer<SdkTypes> {
+            CancelEthBridgePoolTransfer::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                transfer: self.transfer,
+                code_path: self.code_path,
+            }
+        }
+    }
+
+    impl Args for CancelEthBridgePoolTransfer<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let transfer = parse_transfer_hash(matches);
+            let code_path = PathBuf::from(TX_BRIDGE_POOL_UPDATE_WASM);
+            Self {
+                tx,
+                transfer,
+                code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(
+                TRANSFER_HASH
+                    .def()
+                    .help("The keccak hash of the transfer to cancel."),
+            )
+        }
+    }
+
+    impl CliToSdk<BumpEthBridgePoolFee<SdkTypes>>
+        for BumpEthBridgePoolFee<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> BumpEthBridgePoolFee<SdkTypes> {
+            BumpEthBridgePoolFee::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                transfer: self.transfer,
+                fee_amount: self.fee_amount,
+                code_path: self.code_path,
+            }
+        }
+    }
+
+    impl Args for BumpEthBridgePoolFee<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let transfer = parse_transfer_hash(matches);
+            let fee_amount =
+                InputAmount::Unvalidated(BRIDGE_POOL_GAS_AMOUNT.parse(matches));
+            let code_path = PathBuf::from(TX_BRIDGE_POOL_UPDATE_WASM);
+            Self {
+                tx,
+                transfer,
+                fee_amount,
+                code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    TRANSFER_HASH
+                        .def()
+                        .help("The keccak hash of the transfer to update."),
+                )
+                .arg(BRIDGE_POOL_GAS_AMOUNT.def().help(
+                    "The amount of gas to add to the transfer, paid by its \
+                     original gas payer in its original gas token.",
+                ))
+        }
+    }
+
     impl CliToSdk<RecommendBatch<SdkTypes>> for RecommendBatch<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> RecommendBatch<SdkTypes> {
             let chain_ctx = ctx.borrow_chain_or_exit();
@@ -3924,6 +4394,123 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<TxMultiTransfer<SdkTypes>> for TxMultiTransfer<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxMultiTransfer<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            TxMultiTransfer::<SdkTypes> {
+                tx,
+                legs: self
+                    .legs
+                    .into_iter()
+                    .map(|leg| TxTransferLeg::<SdkTypes> {
+                        source: chain_ctx.get(&leg.source),
+                        target: chain_ctx.get(&leg.target),
+                        token: chain_ctx.get(&leg.token),
+                        amount: leg.amount,
+                    })
+                    .collect(),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxMultiTransfer<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let legs = parse_payouts(&PAYOUTS.parse(matches));
+            let tx_code_path = PathBuf::from(TX_MULTI_TRANSFER_WASM);
+            Self {
+                tx,
+                legs,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(PAYOUTS.def().help(
+                "Path to the payouts file. Either a CSV file whose rows are \
+                 `source,target,token,amount`, optionally preceded by a \
+                 header row, or a JSON array of objects with the fields \
+                 `source`, `target`, `token` and `amount`. The sources' keys \
+                 may be used to produce the signature.",
+            ))
+        }
+    }
+
+    /// A row of a payouts file
+    #[derive(serde::Deserialize)]
+    struct Payout {
+        source: String,
+        target: String,
+        token: String,
+        amount: String,
+    }
+
+    /// Parse the legs of a multi-transfer from a CSV or JSON payouts file
+    fn parse_payouts(path: &Path) -> Vec<TxTransferLeg<CliTypes>> {
+        let contents = std::fs::read_to_string(path).unwrap_or_else(|err| {
+            eprintln!("Could not read the payouts file {:?}: {}", path, err);
+            safe_exit(1)
+        });
+        let payouts: Vec<Payout> = if contents.trim_start().starts_with('[') {
+            serde_json::from_str(&contents).unwrap_or_else(|err| {
+                eprintln!("Could not parse the JSON payouts file: {}", err);
+                safe_exit(1)
+            })
+        } else {
+            contents
+                .lines()
+                .map(str::trim)
+                .enumerate()
+                .filter(|(_, line)| !line.is_empty())
+                .filter_map(|(idx, line)| {
+                    let fields: Vec<_> =
+                        line.split(',').map(str::trim).collect();
+                    match fields.as_slice() {
+                        // Skip the header row, if any
+                        ["source", "target", "token", "amount"] if idx == 0 => {
+                            None
+                        }
+                        [source, target, token, amount] => Some(Payout {
+                            source: source.to_string(),
+                            target: target.to_string(),
+                            token: token.to_string(),
+                            amount: amount.to_string(),
+                        }),
+                        _ => {
+                            eprintln!(
+                                "Expected a `source,target,token,amount` row \
+                                 on line {} of the payouts file",
+                                idx + 1
+                            );
+                            safe_exit(1)
+                        }
+                    }
+                })
+                .collect()
+        };
+        payouts
+            .into_iter()
+            .map(|payout| {
+                let amount = token::DenominatedAmount::from_str(&payout.amount)
+                    .unwrap_or_else(|err| {
+                        eprintln!(
+                            "Could not parse payout amount {}: {}",
+                            payout.amount, err
+                        );
+                        safe_exit(1)
+                    });
+                TxTransferLeg {
+                    source: FromContext::new(payout.source),
+                    target: FromContext::new(payout.target),
+                    token: FromContext::new(payout.token),
+                    amount: InputAmount::Unvalidated(amount),
+                }
+            })
+            .collect()
+    }
+
     impl CliToSdk<TxIbcTransfer<SdkTypes>> for TxIbcTransfer<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> TxIbcTransfer<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -4560,6 +5147,85 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<SubmitPgfBatch<SdkTypes>> for SubmitPgfBatch<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> SubmitPgfBatch<SdkTypes> {
+            SubmitPgfBatch::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                steward: ctx.borrow_chain_or_exit().get(&self.steward),
+                payments: std::fs::read(self.payments).expect(""),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for SubmitPgfBatch<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let steward = STEWARD.parse(matches);
+            let payments = DATA_PATH.parse(matches);
+            let tx_code_path = PathBuf::from(TX_SUBMIT_PGF_BATCH);
+            Self {
+                tx,
+                steward,
+                payments,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(STEWARD.def().help("Steward address."))
+                .arg(DATA_PATH.def().help(
+                    "The path to the file that describes the batch. The file \
+                     must contain a list of PGF funding targets with their \
+                     amount.",
+                ))
+        }
+    }
+
+    impl CliToSdk<ChallengePgfBatch<SdkTypes>> for ChallengePgfBatch<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> ChallengePgfBatch<SdkTypes> {
+            ChallengePgfBatch::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                challenger: ctx.borrow_chain_or_exit().get(&self.challenger),
+                batch_id: self.batch_id,
+                content: std::fs::read(self.content).expect(""),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for ChallengePgfBatch<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let challenger = VALIDATOR.parse(matches);
+            let batch_id = PGF_BATCH_ID.parse(matches);
+            let content = DATA_PATH.parse(matches);
+            let tx_code_path = PathBuf::from(TX_CHALLENGE_PGF_BATCH);
+            Self {
+                tx,
+                challenger,
+                batch_id,
+                content,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(VALIDATOR.def().help("Validator address."))
+                .arg(
+                    PGF_BATCH_ID
+                        .def()
+                        .help("The id of the steward batch to challenge."),
+                )
+                .arg(DATA_PATH.def().help(
+                    "The path to the file that contains the content of the \
+                     governance proposal created for the batch.",
+                ))
+        }
+    }
+
     impl CliToSdk<Redelegate<SdkTypes>> for Redelegate<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> Redelegate<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -4959,12 +5625,30 @@ pub mod args {
     impl Args for QueryPgf<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
+            let history = HISTORY.parse(matches);
+            let epoch = EPOCH.parse(matches);
 
-            Self { query }
+            Self {
+                query,
+                history,
+                epoch,
+            }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
+                .arg(HISTORY.def().help(
+                    "Show the PGF payment history: the minted PGF inflation, \
+                     the payments to funding targets and the steward rewards \
+                     of each epoch. Nodes only retain the history of the last \
+                     1000 epochs.",
+                ))
+                .arg(
+                    EPOCH
+                        .def()
+                        .help("Only show the PGF history of the given epoch.")
+                        .requires(HISTORY.name),
+                )
         }
     }
 
@@ -4972,6 +5656,43 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> QueryPgf<SdkTypes> {
             QueryPgf::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
+                history: self.history,
+                epoch: self.epoch,
+            }
+        }
+    }
+
+    impl Args for QueryIbcRateLimit<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let channel_id = CHANNEL_ID_OPT.parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+
+            Self {
+                query,
+                channel_id,
+                token,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(CHANNEL_ID_OPT.def().help(
+                    "The channel ID on this chain for which to query the rate \
+                     limits.",
+                ))
+                .arg(TOKEN_OPT.def().help(
+                    "The token address for which to query the rate limits.",
+                ))
+        }
+    }
+
+    impl CliToSdk<QueryIbcRateLimit<SdkTypes>> for QueryIbcRateLimit<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryIbcRateLimit<SdkTypes> {
+            QueryIbcRateLimit::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                channel_id: self.channel_id,
+                token: self.token.map(|x| ctx.borrow_chain_or_exit().get(&x)),
             }
         }
     }
@@ -5123,6 +5844,7 @@ pub mod args {
                 owner: self.owner.map(|x| chain_ctx.get_cached(&x)),
                 token: self.token.map(|x| chain_ctx.get(&x)),
                 no_conversions: self.no_conversions,
+                light: self.light,
             }
         }
     }
@@ -5133,11 +5855,13 @@ pub mod args {
             let owner = BALANCE_OWNER.parse(matches);
             let token = TOKEN_OPT.parse(matches);
             let no_conversions = NO_CONVERSIONS.parse(matches);
+            let light = LIGHT.parse(matches);
             Self {
                 query,
                 owner,
                 token,
                 no_conversions,
+                light,
             }
         }
 
@@ -5158,6 +5882,11 @@ pub mod args {
                         "Whether not to automatically perform conversions.",
                     ),
                 )
+                .arg(LIGHT.def().help(
+                    "Verify the balance with the light client instead of \
+                     trusting the node. Requires a transparent owner and a \
+                     token.",
+                ))
         }
     }
 
@@ -5196,6 +5925,53 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<ExportShieldedHistory<SdkTypes>>
+        for ExportShieldedHistory<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> ExportShieldedHistory<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            ExportShieldedHistory::<SdkTypes> {
+                query,
+                viewing_key: chain_ctx.get_cached(&self.viewing_key),
+                format: self.format,
+                output: self.output,
+            }
+        }
+    }
+
+    impl Args for ExportShieldedHistory<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let viewing_key = VIEWING_KEY.parse(matches);
+            let format = SHIELDED_HISTORY_FORMAT.parse(matches);
+            let output = OUTPUT.parse(matches);
+            Self {
+                query,
+                viewing_key,
+                format,
+                output,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    VIEWING_KEY
+                        .def()
+                        .help("The viewing key whose history is exported."),
+                )
+                .arg(SHIELDED_HISTORY_FORMAT.def().help(
+                    "The format of the export, either \"csv\" (default) or \
+                     \"json\".",
+                ))
+                .arg(OUTPUT.def().help(
+                    "Save the export to the given file. When not supplied, \
+                     the export will be printed to stdout instead.",
+                ))
+        }
+    }
+
     impl CliToSdk<QueryBonds<SdkTypes>> for QueryBonds<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryBonds<SdkTypes> {
             let query = self.query.to_sdk(ctx);
@@ -5851,6 +6627,7 @@ pub mod args {
             QueryRawBytes::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
                 storage_key: self.storage_key,
+                light: self.light,
             }
         }
     }
@@ -5859,12 +6636,57 @@ pub mod args {
         fn parse(matches: &ArgMatches) -> Self {
             let storage_key = STORAGE_KEY.parse(matches);
             let query = Query::parse(matches);
-            Self { storage_key, query }
+            let light = LIGHT.parse(matches);
+            Self {
+                storage_key,
+                query,
+                light,
+            }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
                 .arg(STORAGE_KEY.def().help("Storage key"))
+                .arg(LIGHT.def().help(
+                    "Verify the value with the light client instead of \
+                     trusting the node.",
+                ))
+        }
+    }
+
+    impl CliToSdk<LightClientInit<SdkTypes>> for LightClientInit<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> LightClientInit<SdkTypes> {
+            LightClientInit::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                trust_height: self.trust_height,
+                trust_hash: self.trust_hash,
+            }
+        }
+    }
+
+    impl Args for LightClientInit<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let trust_height = TRUST_HEIGHT.parse(matches);
+            let trust_hash = TRUST_HASH.parse(matches);
+            Self {
+                query,
+                trust_height,
+                trust_hash,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    TRUST_HEIGHT
+                        .def()
+                        .help("The height of a block trusted by the client."),
+                )
+                .arg(TRUST_HASH.def().help(
+                    "The hex encoded hash of the block at the trusted height. \
+                     It must be obtained from a trusted source.",
+                ))
         }
     }
 
@@ -5893,6 +6715,7 @@ pub mod args {
             Tx::<SdkTypes> {
                 dry_run: self.dry_run,
                 dry_run_wrapper: self.dry_run_wrapper,
+                trace: self.trace,
                 dump_tx: self.dump_tx,
                 output_folder: self.output_folder,
                 force: self.force,
@@ -5947,6 +6770,10 @@ pub mod args {
                     )
                     .conflicts_with(DRY_RUN_TX.name),
             )
+            .arg(TRACE.def().help(
+                "Include a trace of the host calls made by the transaction \
+                 and its validity predicates in the dry-run result.",
+            ))
             .arg(DUMP_TX.def().help("Dump transaction bytes to a file."))
             .arg(FORCE.def().help(
                 "Submit the transaction even if it doesn't pass client checks.",
@@ -5986,7 +6813,9 @@ pub mod args {
             ))
             .arg(EXPIRATION_OPT.def().help(
                 "The expiration datetime of the transaction, after which the \
-                 tx won't be accepted anymore. All of these examples are \
+                 tx won't be accepted anymore. Defaults to one hour from now \
+                 and must fall within the chain's tx expiration window. All \
+                 of these examples are \
                  equivalent:\n2012-12-12T12:12:12Z\n2012-12-12 \
                  12:12:12Z\n2012-  12-12T12:  12:12Z",
             ))
@@ -6049,6 +6878,7 @@ pub mod args {
         fn parse(matches: &ArgMatches) -> Self {
             let dry_run = DRY_RUN_TX.parse(matches);
             let dry_run_wrapper = DRY_RUN_WRAPPER_TX.parse(matches);
+            let trace = TRACE.parse(matches);
             let dump_tx = DUMP_TX.parse(matches);
             let force = FORCE.parse(matches);
             let broadcast_only = BROADCAST_ONLY.parse(matches);
@@ -6075,6 +6905,7 @@ pub mod args {
             Self {
                 dry_run,
                 dry_run_wrapper,
+                trace,
                 dump_tx,
                 force,
                 broadcast_only,
@@ -6268,9 +7099,10 @@ pub mod args {
         fn def(app: App) -> App {
             app.arg(SCHEME.def().conflicts_with(SHIELDED.name).help(
                 "For the transparent pool, the type of key that should be \
-                 generated. Argument must be either ed25519 or secp256k1. If \
-                 none provided, the default key scheme is ed25519.\nNot \
-                 applicable for the shielded pool.",
+                 generated. Argument must be either ed25519, secp256k1 or \
+                 secp256r1 (P-256, only with `--raw`). If none provided, the \
+                 default key scheme is ed25519.\nNot applicable for the \
+                 shielded pool.",
             ))
             .arg(
                 SHIELDED
@@ -6478,6 +7310,20 @@ pub mod args {
         }
     }
 
+    impl Args for StoreEncrypt {
+        fn parse(matches: &ArgMatches) -> Self {
+            let decrypt = DECRYPT.parse(matches);
+            Self { decrypt }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(DECRYPT.def().help(
+                "Remove the encryption of the whole store and save it in \
+                 plaintext again. Individually encrypted keys stay encrypted.",
+            ))
+        }
+    }
+
     impl Args for KeyExport {
         fn parse(matches: &ArgMatches) -> Self {
             let alias = ALIAS.parse(matches);
@@ -59,6 +59,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_transfer(&namada, args).await?;
                     }
+                    Sub::TxMultiTransfer(TxMultiTransfer(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_multi_transfer(&namada, args).await?;
+                    }
                     Sub::TxIbcTransfer(TxIbcTransfer(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -311,6 +322,31 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_bridge_pool_tx(&namada, args).await?;
                     }
+                    Sub::CancelEthBridgePoolTransfer(args) => {
+                        let mut args = args.0;
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_cancel_bridge_pool_transfer(&namada, args)
+                            .await?;
+                    }
+                    Sub::BumpEthBridgePoolFee(args) => {
+                        let mut args = args.0;
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_bump_bridge_pool_fee(&namada, args).await?;
+                    }
                     Sub::TxUnjailValidator(TxUnjailValidator(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -373,6 +409,28 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_resign_steward(&namada, args).await?;
                     }
+                    Sub::TxSubmitPgfBatch(TxSubmitPgfBatch(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_pgf_batch(&namada, args).await?;
+                    }
+                    Sub::TxChallengePgfBatch(TxChallengePgfBatch(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_challenge_pgf_batch(&namada, args).await?;
+                    }
                     // Ledger queries
                     Sub::QueryEpoch(QueryEpoch(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -405,6 +463,19 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_transfers(&namada, args).await;
                     }
+                    Sub::ExportShieldedHistory(ExportShieldedHistory(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::export_shielded_history(&namada, args).await;
+                    }
                     Sub::QueryConversions(QueryConversions(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -426,6 +497,14 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_masp_reward_tokens(&namada).await;
                     }
+                    Sub::QueryMaspStats(QueryMaspStats(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&mut args.ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_masp_stats(&namada).await;
+                    }
                     Sub::QueryBlock(QueryBlock(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(&mut args.ledger_address)
@@ -435,6 +514,17 @@ impl CliApi {
                         rpc::query_block(&namada).await;
                     }
                     Sub::QueryBalance(QueryBalance(mut args)) => {
+                        let light_client = if args.light {
+                            Some(
+                                rpc::load_light_client(
+                                    &args.query.ledger_address,
+                                    &ctx.light_client_state_path(),
+                                )
+                                .await?,
+                            )
+                        } else {
+                            None
+                        };
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.query.ledger_address,
@@ -443,7 +533,17 @@ impl CliApi {
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
                         let namada = ctx.to_sdk(client, io);
-                        rpc::query_balance(&namada, args).await;
+                        match light_client {
+                            Some(light_client) => {
+                                rpc::query_verified_balance(
+                                    &namada,
+                                    &light_client,
+                                    args,
+                                )
+                                .await
+                            }
+                            None => rpc::query_balance(&namada, args).await,
+                        }
                     }
                     Sub::QueryBonds(QueryBonds(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -548,6 +648,17 @@ impl CliApi {
                         rpc::query_result(&namada, args).await;
                     }
                     Sub::QueryRawBytes(QueryRawBytes(mut args)) => {
+                        let light_client = if args.light {
+                            Some(
+                                rpc::load_light_client(
+                                    &args.query.ledger_address,
+                                    &ctx.light_client_state_path(),
+                                )
+                                .await?,
+                            )
+                        } else {
+                            None
+                        };
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.query.ledger_address,
@@ -556,7 +667,29 @@ impl CliApi {
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
                         let namada = ctx.to_sdk(client, io);
-                        rpc::query_raw_bytes(&namada, args).await;
+                        match light_client {
+                            Some(light_client) => {
+                                rpc::query_verified_raw_bytes(
+                                    &namada,
+                                    &light_client,
+                                    args,
+                                )
+                                .await
+                            }
+                            None => rpc::query_raw_bytes(&namada, args).await,
+                        }
+                    }
+                    Sub::LightClientInit(LightClientInit(args)) => {
+                        let state_path = ctx.light_client_state_path();
+                        let ledger_address = args.query.ledger_address.clone();
+                        let args = args.to_sdk(&mut ctx);
+                        rpc::init_light_client(
+                            &io,
+                            &ledger_address,
+                            args,
+                            &state_path,
+                        )
+                        .await?;
                     }
                     Sub::QueryProposal(QueryProposal(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -615,6 +748,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_pgf(&namada, args).await;
                     }
+                    Sub::QueryIbcRateLimit(QueryIbcRateLimit(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_ibc_rate_limit(&namada, args).await;
+                    }
                     Sub::QueryAccount(QueryAccount(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -14,6 +14,7 @@ use namada::types::ibc::is_ibc_denom;
 use namada::types::io::Io;
 use namada::types::key::*;
 use namada::types::masp::*;
+use namada_sdk::light_client::LIGHT_CLIENT_STATE_FILE;
 use namada_sdk::masp::fs::FsShieldedUtils;
 use namada_sdk::masp::ShieldedContext;
 use namada_sdk::wallet::Wallet;
@@ -166,6 +167,16 @@ impl Context {
             .unwrap_or_else(|| safe_exit_on_missing_chain_context())
     }
 
+    /// Get the path of the light client's trusted state, which is kept next
+    /// to the wallet of the configured chain.
+    pub fn light_client_state_path(&self) -> PathBuf {
+        let chain_id = &self.borrow_chain_or_exit().config.ledger.chain_id;
+        self.global_args
+            .base_dir
+            .join(chain_id.as_str())
+            .join(LIGHT_CLIENT_STATE_FILE)
+    }
+
     /// Try to borrow mutably chain context, or exit the process with an error
     /// if no chain is configured.
     pub fn borrow_mut_chain_or_exit(&mut self) -> &mut ChainContext {
@@ -63,6 +63,9 @@ impl CliApi {
             cmds::NamadaWallet::KeyAddrRemove(
                 cmds::WalletRemoveKeyAddress(args),
             ) => key_address_remove(ctx, io, args),
+            cmds::NamadaWallet::StoreEncrypt(cmds::WalletEncryptStore(
+                args,
+            )) => store_encrypt(ctx, io, args),
             cmds::NamadaWallet::PayAddrGen(cmds::WalletGenPaymentAddress(
                 args,
             )) => {
@@ -289,9 +292,13 @@ pub fn decode_derivation_path(
     scheme: SchemeType,
     derivation_path: String,
 ) -> Result<DerivationPath, DerivationPathError> {
+    // P-256 keys are meant to be generated by their secure enclave
+    if scheme == SchemeType::Secp256r1 {
+        return Err(DerivationPathError::UnsupportedScheme(scheme));
+    }
     let is_default = derivation_path.eq_ignore_ascii_case("DEFAULT");
     let parsed_derivation_path = if is_default {
-        DerivationPath::default_for_scheme(scheme)
+        DerivationPath::default_for_scheme(scheme)?
     } else {
         DerivationPath::from_path_str(scheme, &derivation_path)?
     };
@@ -717,6 +724,40 @@ fn key_address_remove(
     display_line!(io, "Successfully removed alias: \"{}\"", alias);
 }
 
+/// Encrypt the whole wallet store, change its passphrase or remove its
+/// encryption. Wallets in the plaintext format are migrated by encrypting
+/// them.
+fn store_encrypt(
+    ctx: Context,
+    io: &impl Io,
+    args::StoreEncrypt { decrypt }: args::StoreEncrypt,
+) {
+    let mut wallet = load_wallet(ctx);
+    let was_encrypted = wallet.is_store_encrypted();
+    if decrypt {
+        if !was_encrypted {
+            display_line!(io, "The wallet store is not encrypted.");
+            return;
+        }
+        wallet.remove_store_passphrase();
+    } else {
+        wallet.set_store_passphrase(wallet::read_new_store_passphrase(
+            was_encrypted,
+        ));
+    }
+    wallet.save().unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+    if decrypt {
+        display_line!(io, "Successfully removed the wallet store encryption.");
+    } else if was_encrypted {
+        display_line!(io, "Successfully changed the wallet store passphrase.");
+    } else {
+        display_line!(io, "Successfully encrypted the wallet store.");
+    }
+}
+
 /// Find a keypair in the wallet store.
 fn transparent_key_find(
     ctx: Context,
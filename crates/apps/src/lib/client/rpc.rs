@@ -5,6 +5,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::{self, read_dir};
 use std::io;
 use std::iter::Iterator;
+use std::path::Path;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
@@ -21,6 +22,10 @@ use namada::governance::cli::offline::{
 };
 use namada::governance::parameters::GovernanceParameters;
 use namada::governance::pgf::parameters::PgfParameters;
+use namada::governance::pgf::storage::batch::StewardPaymentBatch;
+use namada::governance::pgf::storage::history::{
+    PgfEpochRecord, HISTORY_RETENTION_EPOCHS, MAX_HISTORY_QUERY_EPOCHS,
+};
 use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::storage::keys as governance_storage;
 use namada::governance::storage::proposal::{
@@ -52,8 +57,10 @@ use namada::{state as storage, token};
 use namada_sdk::error::{
     is_pinned_error, Error, PinnedBalanceError, QueryError,
 };
-use namada_sdk::masp::{Conversions, MaspChange};
+use namada_sdk::light_client::{self, LightClient};
+use namada_sdk::masp::{render_shielded_history, Conversions, MaspChange};
 use namada_sdk::proof_of_stake::types::ValidatorMetaData;
+use namada_sdk::rpc::verified::{self, TrustedRoot};
 use namada_sdk::rpc::{
     self, enriched_bonds_and_unbonds, query_epoch, TxResponse,
 };
@@ -63,8 +70,12 @@ use namada_sdk::{display, display_line, edisplay_line, error, prompt, Namada};
 use tokio::time::Instant;
 
 use crate::cli::{self, args};
+use crate::facade::tendermint::block::Height;
 use crate::facade::tendermint::merkle::proof::ProofOps;
+use crate::facade::tendermint::Hash as TmHash;
+use crate::facade::tendermint_config::net::Address as TendermintAddress;
 use crate::facade::tendermint_rpc::error::Error as TError;
+use crate::facade::tendermint_rpc::HttpClient;
 
 /// Query the status of a given transaction.
 ///
@@ -257,6 +268,47 @@ pub async fn query_transfers(
     }
 }
 
+/// Export the shielded history of a viewing key
+pub async fn export_shielded_history(
+    context: &impl Namada,
+    args: args::ExportShieldedHistory,
+) {
+    let vk = ExtendedFullViewingKey::from(args.viewing_key).fvk.vk;
+    let mut shielded = context.shielded_mut().await;
+    let _ = shielded.load().await;
+    // Scan the shielded transactions with the requested viewing key
+    shielded
+        .fetch(context.client(), &[], &[vk])
+        .await
+        .expect("Failed to fetch the shielded transactions");
+    // Save the update state so that future fetches can be short-circuited
+    let _ = shielded.save().await;
+    let history = shielded
+        .shielded_history(context.client(), context.io(), &vk)
+        .await
+        .expect("Failed to compute the shielded history");
+    let rendered = render_shielded_history(&history, args.format)
+        .expect("Failed to render the shielded history");
+    match args.output {
+        Some(path) => {
+            if let Err(err) = std::fs::write(&path, rendered) {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to write the shielded history to {}: {err}",
+                    path.to_string_lossy()
+                );
+                cli::safe_exit(1)
+            }
+            display_line!(
+                context.io(),
+                "Shielded history written to {}",
+                path.to_string_lossy()
+            );
+        }
+        None => display!(context.io(), "{}", rendered),
+    }
+}
+
 /// Query the raw bytes of given storage key
 pub async fn query_raw_bytes<N: Namada>(
     context: &N,
@@ -288,6 +340,154 @@ pub async fn query_raw_bytes<N: Namada>(
     }
 }
 
+/// Resume the light client from its trusted state, following the chain
+/// through the given node
+pub async fn load_light_client(
+    ledger_address: &TendermintAddress,
+    state_path: &Path,
+) -> Result<LightClient, Error> {
+    let rpc_client = HttpClient::new(ledger_address.clone())
+        .map_err(|err| Error::Other(err.to_string()))?;
+    LightClient::load(rpc_client, light_client::default_options(), state_path)
+        .await
+}
+
+/// Initialize the light client from a trusted checkpoint, following the chain
+/// through the given node
+pub async fn init_light_client(
+    io: &impl Io,
+    ledger_address: &TendermintAddress,
+    args: args::LightClientInit,
+    state_path: &Path,
+) -> Result<(), Error> {
+    let rpc_client = HttpClient::new(ledger_address.clone())
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let height = Height::try_from(args.trust_height.0)
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let hash = TmHash::Sha256(args.trust_hash.0);
+    let light_client = LightClient::from_checkpoint(
+        rpc_client,
+        height,
+        hash,
+        light_client::default_options(),
+        state_path,
+    )
+    .await?;
+    let latest = light_client.verify_to_highest().await?;
+    display_line!(
+        io,
+        "Light client initialized at height {} and verified up to height {}. \
+         Its trusted state is kept in {}.",
+        args.trust_height,
+        latest.height(),
+        state_path.to_string_lossy()
+    );
+    Ok(())
+}
+
+/// Verify the latest block with the light client and get its state
+/// commitment, or exit the process on failure
+async fn light_client_root(
+    context: &impl Namada,
+    light_client: &LightClient,
+) -> TrustedRoot {
+    light_client.trusted_root().await.unwrap_or_else(|err| {
+        edisplay_line!(
+            context.io(),
+            "Failed to verify the chain with the light client: {err}"
+        );
+        cli::safe_exit(1)
+    })
+}
+
+/// Query the raw bytes of given storage key and verify them with the light
+/// client
+pub async fn query_verified_raw_bytes<N: Namada>(
+    context: &N,
+    light_client: &LightClient,
+    args: args::QueryRawBytes,
+) {
+    let root = light_client_root(context, light_client).await;
+    match verified::query_storage_value_bytes(
+        context.client(),
+        &root,
+        &args.storage_key,
+    )
+    .await
+    {
+        Ok(Some(value)) => display_line!(
+            context.io(),
+            "Found data verified at height {}: 0x{}",
+            root.height,
+            HEXLOWER.encode(&value)
+        ),
+        Ok(None) => display_line!(
+            context.io(),
+            "Verified that no data exists for key {} at height {}",
+            args.storage_key,
+            root.height
+        ),
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to verify the data of key {}: {err}",
+                args.storage_key
+            );
+            cli::safe_exit(1)
+        }
+    }
+}
+
+/// Query a transparent token balance and verify it with the light client
+pub async fn query_verified_balance(
+    context: &impl Namada,
+    light_client: &LightClient,
+    args: args::QueryBalance,
+) {
+    let (Some(token), Some(BalanceOwner::Address(owner))) =
+        (&args.token, &args.owner)
+    else {
+        edisplay_line!(
+            context.io(),
+            "Verifying a balance with the light client requires a transparent \
+             owner and a token."
+        );
+        cli::safe_exit(1)
+    };
+    let root = light_client_root(context, light_client).await;
+    let balance =
+        verified::get_token_balance(context.client(), &root, token, owner)
+            .await
+            .unwrap_or_else(|err| {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to verify the {token} balance of {owner}. If no \
+                     balance is stored, e.g. it has never held the token, its \
+                     absence can't be verified: {err}"
+                );
+                cli::safe_exit(1)
+            });
+    // The denomination is only used for display, so fall back to the raw
+    // amount if it can't be verified
+    let denom = verified::query_storage_value::<_, token::Denomination>(
+        context.client(),
+        &root,
+        &token::storage_key::denom_key(token),
+    )
+    .await;
+    let balance = match denom {
+        Ok(Some(denom)) => {
+            token::DenominatedAmount::new(balance, denom).to_string()
+        }
+        _ => format!("{balance} (in the smallest unit)"),
+    };
+    display_line!(
+        context.io(),
+        "{token}: {balance} (verified at height {})",
+        root.height
+    );
+}
+
 /// Query token balance(s)
 pub async fn query_balance(context: &impl Namada, args: args::QueryBalance) {
     // Query the balances of shielded or transparent account types depending on
@@ -1279,7 +1479,11 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     }
 }
 
-pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
+pub async fn query_pgf(context: &impl Namada, args: args::QueryPgf) {
+    if args.history {
+        return query_pgf_history(context, args.epoch).await;
+    }
+
     let stewards = query_pgf_stewards(context.client()).await;
     let fundings = query_pgf_fundings(context.client()).await;
 
@@ -1325,7 +1529,244 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
                     funding.detail.target(),
                     funding.detail.amount().to_string_native()
                 );
+                if let Some(start_epoch) = funding.start_epoch {
+                    display_line!(
+                        context.io(),
+                        "{:4}  Start epoch: {}",
+                        "",
+                        start_epoch
+                    );
+                }
+                if let Some(end_epoch) = funding.end_epoch {
+                    display_line!(
+                        context.io(),
+                        "{:4}  End epoch: {}",
+                        "",
+                        end_epoch
+                    );
+                }
+                display_line!(
+                    context.io(),
+                    "{:4}  Paid: {}",
+                    "",
+                    funding.paid.to_string_native()
+                );
+                if let Some(remaining) = funding.remaining_budget() {
+                    display_line!(
+                        context.io(),
+                        "{:4}  Remaining budget: {}",
+                        "",
+                        remaining.to_string_native()
+                    );
+                }
+            }
+        }
+    }
+
+    let current_epoch = rpc::query_epoch(context.client()).await.unwrap();
+    let pending_batches = query_pgf_batches(context.client())
+        .await
+        .into_iter()
+        .filter(|batch| batch.is_challengeable(current_epoch))
+        .collect::<Vec<_>>();
+
+    match pending_batches.is_empty() {
+        true => {
+            display_line!(
+                context.io(),
+                "Pgf steward batches: no batches are currently pending."
+            )
+        }
+        false => {
+            display_line!(context.io(), "Pgf pending steward batches:");
+            for batch in pending_batches {
+                display_line!(
+                    context.io(),
+                    "{:4}- Batch {} by {} for {}",
+                    "",
+                    batch.id,
+                    batch.steward,
+                    batch.total_amount().to_string_native()
+                );
+                display_line!(
+                    context.io(),
+                    "{:4}  Challengeable until epoch: {}",
+                    "",
+                    batch.challenge_end_epoch
+                );
+                for payment in batch.payments {
+                    display_line!(
+                        context.io(),
+                        "{:6}- {} for {}",
+                        "",
+                        payment.target(),
+                        payment.amount().to_string_native()
+                    );
+                }
+            }
+        }
+    }
+}
+
+async fn query_pgf_history(context: &impl Namada, epoch: Option<Epoch>) {
+    let history = match epoch {
+        Some(epoch) => query_pgf_epoch_history(context.client(), epoch)
+            .await
+            .map(|record| vec![(epoch, record)])
+            .unwrap_or_default(),
+        None => {
+            // Page through the retained history, up to the current epoch
+            let current_epoch =
+                rpc::query_epoch(context.client()).await.unwrap();
+            let mut from =
+                current_epoch.sub_or_default(Epoch(HISTORY_RETENTION_EPOCHS));
+            let mut history = vec![];
+            while from <= current_epoch {
+                history.append(
+                    &mut query_pgf_history_range(
+                        context.client(),
+                        from,
+                        current_epoch,
+                    )
+                    .await,
+                );
+                from = Epoch(from.0 + MAX_HISTORY_QUERY_EPOCHS);
             }
+            history
+        }
+    };
+
+    if history.is_empty() {
+        display_line!(context.io(), "Pgf history: no records found.");
+        return;
+    }
+
+    for (epoch, record) in history {
+        display_line!(context.io(), "Epoch {}:", epoch);
+        display_line!(
+            context.io(),
+            "{:2}Minted: {}",
+            "",
+            record.minted.to_string_native()
+        );
+        display_line!(
+            context.io(),
+            "{:2}Paid: {}",
+            "",
+            record.total_paid().to_string_native()
+        );
+        for payment in &record.payments {
+            let status = match &payment.error {
+                Some(error) => format!("failed: {}", error),
+                None => "paid".to_string(),
+            };
+            display_line!(
+                context.io(),
+                "{:4}- {} {} to {} from proposal {} ({})",
+                "",
+                payment.kind,
+                payment.amount.to_string_native(),
+                payment.target,
+                payment.proposal_id,
+                status
+            );
+        }
+        for reward in &record.steward_rewards {
+            let status = match &reward.error {
+                Some(error) => format!("failed: {}", error),
+                None => "minted".to_string(),
+            };
+            display_line!(
+                context.io(),
+                "{:4}- steward {} reward {} to {} ({})",
+                "",
+                reward.steward,
+                reward.amount.to_string_native(),
+                reward.recipient,
+                status
+            );
+        }
+    }
+}
+
+pub async fn query_ibc_rate_limit(
+    context: &impl Namada,
+    args: args::QueryIbcRateLimit,
+) {
+    let usages = match (&args.channel_id, &args.token) {
+        (Some(channel_id), Some(token)) => {
+            rpc::query_ibc_rate_limit(context.client(), channel_id, token)
+                .await
+                .map(|usage| usage.into_iter().collect())
+        }
+        _ => rpc::query_ibc_rate_limits(context.client()).await,
+    };
+    let usages: Vec<_> = match usages {
+        Ok(usages) => usages
+            .into_iter()
+            .filter(|usage| {
+                args.channel_id.as_ref().map_or(true, |channel_id| {
+                    usage.channel_id == channel_id.to_string()
+                }) && args
+                    .token
+                    .as_ref()
+                    .map_or(true, |token| usage.token == *token)
+            })
+            .collect(),
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Querying the IBC rate limits failed: {}",
+                err
+            );
+            cli::safe_exit(1)
+        }
+    };
+
+    if usages.is_empty() {
+        display_line!(context.io(), "No IBC rate limit has been found.");
+        return;
+    }
+    for usage in usages {
+        display_line!(
+            context.io(),
+            "Channel {}, Token {} (in the window of {} epochs up to epoch {}):",
+            usage.channel_id,
+            usage.token,
+            usage.limit.window,
+            usage.epoch,
+        );
+        for (name, used, quota) in [
+            ("Inflow", usage.inflow, usage.limit.inflow),
+            ("Outflow", usage.outflow, usage.limit.outflow),
+        ] {
+            let used = rpc::format_denominated_amount(
+                context.client(),
+                context.io(),
+                &usage.token,
+                used,
+            )
+            .await;
+            let quota = match quota {
+                Some(quota) => {
+                    rpc::format_denominated_amount(
+                        context.client(),
+                        context.io(),
+                        &usage.token,
+                        quota,
+                    )
+                    .await
+                }
+                None => "unlimited".to_string(),
+            };
+            display_line!(
+                context.io(),
+                "{:4}{}: {} / {}",
+                "",
+                name,
+                used,
+                quota
+            );
         }
     }
 }
@@ -1388,6 +1829,12 @@ pub async fn query_protocol_parameters(
         "",
         pgf_parameters.stewards_inflation_rate
     );
+    display_line!(
+        context.io(),
+        "{:4}Steward batch challenge epochs: {}",
+        "",
+        pgf_parameters.batch_challenge_epochs
+    );
 
     display_line!(context.io(), "Protocol parameters");
     let key = param_storage::get_epoch_duration_storage_key();
@@ -1419,6 +1866,17 @@ pub async fn query_protocol_parameters(
         max_block_duration
     );
 
+    let key = param_storage::get_tx_expiration_window_key();
+    let tx_expiration_window: u64 = query_storage_value(context.client(), &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Tx expiration window: {}",
+        "",
+        tx_expiration_window
+    );
+
     let key = param_storage::get_tx_whitelist_storage_key();
     let vp_whitelist: Vec<String> = query_storage_value(context.client(), &key)
         .await
@@ -1582,6 +2040,38 @@ pub async fn query_pgf_fundings<C: namada::ledger::queries::Client + Sync>(
     unwrap_client_response::<C, _>(RPC.vp().pgf().funding(client).await)
 }
 
+pub async fn query_pgf_batches<C: namada::ledger::queries::Client + Sync>(
+    client: &C,
+) -> Vec<StewardPaymentBatch> {
+    unwrap_client_response::<C, _>(RPC.vp().pgf().batches(client).await)
+}
+
+/// Query the pgf history of a range of epochs, inclusive of the bounds. The
+/// node returns at most `MAX_HISTORY_QUERY_EPOCHS` epochs starting from
+/// `from`.
+pub async fn query_pgf_history_range<
+    C: namada::ledger::queries::Client + Sync,
+>(
+    client: &C,
+    from: Epoch,
+    to: Epoch,
+) -> Vec<(Epoch, PgfEpochRecord)> {
+    unwrap_client_response::<C, _>(
+        RPC.vp().pgf().history(client, &from, &to).await,
+    )
+}
+
+pub async fn query_pgf_epoch_history<
+    C: namada::ledger::queries::Client + Sync,
+>(
+    client: &C,
+    epoch: Epoch,
+) -> Option<PgfEpochRecord> {
+    unwrap_client_response::<C, _>(
+        RPC.vp().pgf().epoch_history(client, &epoch).await,
+    )
+}
+
 pub async fn query_pgf_parameters<C: namada::ledger::queries::Client + Sync>(
     client: &C,
 ) -> PgfParameters {
@@ -2480,6 +2970,49 @@ pub async fn query_masp_reward_tokens(context: &impl Namada) {
     }
 }
 
+/// Query the MASP anonymity set statistics.
+pub async fn query_masp_stats(context: &impl Namada) {
+    let note_commitments =
+        namada_sdk::rpc::query_masp_note_commitments(context.client())
+            .await
+            .expect("The note commitment tree should be readable");
+    display_line!(context.io(), "Note commitments: {}", note_commitments);
+
+    let supply = namada_sdk::rpc::query_masp_shielded_supply(context.client())
+        .await
+        .expect("The shielded supply should be readable");
+    display_line!(context.io(), "Shielded supply:");
+    for (token, amount) in supply {
+        let amount = context.format_amount(&token, amount).await;
+        display_line!(context.io(), "  {}: {}", token, amount);
+    }
+
+    let nullifiers = namada_sdk::rpc::query_masp_nullifiers(context.client())
+        .await
+        .expect("The nullifier counts should be readable");
+    display_line!(context.io(), "Nullifiers revealed per epoch:");
+    for (epoch, count) in nullifiers {
+        display_line!(context.io(), "  {}: {}", epoch, count);
+    }
+
+    let tokens = namada_sdk::rpc::query_masp_reward_tokens(context.client())
+        .await
+        .expect("The tokens that may earn MASP rewards should be defined");
+    display_line!(context.io(), "Reward rate history:");
+    for (alias, address) in tokens {
+        let rates = namada_sdk::rpc::query_masp_reward_rates(
+            context.client(),
+            &address,
+        )
+        .await
+        .expect("The reward rate history should be readable");
+        display_line!(context.io(), "  {}:", alias);
+        for (epoch, rate) in rates {
+            display_line!(context.io(), "    {}: {}", epoch, rate);
+        }
+    }
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -252,6 +252,40 @@ pub async fn submit_bridge_pool_tx<N: Namada>(
     Ok(())
 }
 
+pub async fn submit_cancel_bridge_pool_transfer<N: Namada>(
+    namada: &N,
+    args: args::CancelEthBridgePoolTransfer,
+) -> Result<(), error::Error> {
+    let (mut tx, signing_data) = args.clone().build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_bump_bridge_pool_fee<N: Namada>(
+    namada: &N,
+    args: args::BumpEthBridgePoolFee,
+) -> Result<(), error::Error> {
+    let (mut tx, signing_data) = args.clone().build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_custom<N: Namada>(
     namada: &N,
     args: args::TxCustom,
@@ -357,7 +391,8 @@ pub async fn submit_change_consensus_key(
     let new_key = consensus_key
         .map(|key| match key {
             common::PublicKey::Ed25519(_) => key,
-            common::PublicKey::Secp256k1(_) => {
+            common::PublicKey::Secp256k1(_)
+            | common::PublicKey::Secp256r1(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Consensus key can only be ed25519"
@@ -574,7 +609,8 @@ pub async fn submit_become_validator(
     let consensus_key = consensus_key
         .map(|key| match key {
             common::PublicKey::Ed25519(_) => key,
-            common::PublicKey::Secp256k1(_) => {
+            common::PublicKey::Secp256k1(_)
+            | common::PublicKey::Secp256r1(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Consensus key can only be ed25519"
@@ -603,7 +639,7 @@ pub async fn submit_become_validator(
     let eth_cold_pk = eth_cold_key
         .map(|key| match key {
             common::PublicKey::Secp256k1(_) => key,
-            common::PublicKey::Ed25519(_) => {
+            common::PublicKey::Ed25519(_) | common::PublicKey::Secp256r1(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Eth cold key can only be secp256k1"
@@ -632,7 +668,7 @@ pub async fn submit_become_validator(
     let eth_hot_pk = eth_hot_key
         .map(|key| match key {
             common::PublicKey::Secp256k1(_) => key,
-            common::PublicKey::Ed25519(_) => {
+            common::PublicKey::Ed25519(_) | common::PublicKey::Secp256r1(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Eth hot key can only be secp256k1"
@@ -959,6 +995,30 @@ pub async fn submit_transfer(
     Ok(())
 }
 
+pub async fn submit_multi_transfer(
+    namada: &impl Namada,
+    mut args: args::TxMultiTransfer,
+) -> Result<(), error::Error> {
+    let mut revealed = HashSet::new();
+    for leg in &args.legs {
+        if revealed.insert(leg.source.clone()) {
+            submit_reveal_aux(namada, args.tx.clone(), &leg.source).await?;
+        }
+    }
+
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_ibc_transfer<N: Namada>(
     namada: &N,
     args: args::TxIbcTransfer,
@@ -1553,6 +1613,46 @@ where
     Ok(())
 }
 
+pub async fn submit_pgf_batch<N: Namada>(
+    namada: &N,
+    args: args::SubmitPgfBatch,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_challenge_pgf_batch<N: Namada>(
+    namada: &N,
+    args: args::ChallengePgfBatch,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 /// Save accounts initialized from a tx into the wallet, if any.
 pub async fn save_initialized_accounts(
     namada: &impl Namada,
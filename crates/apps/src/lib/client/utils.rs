@@ -387,6 +387,11 @@ pub fn id_from_pk(pk: &common::PublicKey) -> TendermintNodeId {
             let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
             bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
         }
+        common::PublicKey::Secp256r1(_) => {
+            let _pk: secp256r1::PublicKey = pk.try_to_pk().unwrap();
+            let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
+            bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
+        }
     }
     TendermintNodeId::new(bytes)
 }
@@ -980,6 +985,9 @@ pub fn write_tendermint_node_key(
         common::SecretKey::Secp256k1(sk) => {
             (sk.serialize_to_vec(), "Secp256k1")
         }
+        common::SecretKey::Secp256r1(_) => {
+            panic!("Tendermint node keys cannot be secp256r1 keys")
+        }
     };
 
     let tm_node_keypair_json = json!({
@@ -25,6 +25,43 @@ pub enum Mode {
     Off,
 }
 
+/// How the oracle combines the responses of its Ethereum JSON-RPC endpoints.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuorumPolicy {
+    /// Use the response of the first endpoint that answers, trying the
+    /// healthiest endpoints first.
+    #[default]
+    Failover,
+    /// Only accept block heights and event logs that at least `threshold`
+    /// of the endpoints agree on.
+    Agreement {
+        /// The minimum number of endpoints that must agree
+        threshold: usize,
+    },
+}
+
+impl QuorumPolicy {
+    /// Check that the policy can be met with the given number of endpoints.
+    pub fn validate(&self, num_endpoints: usize) -> Result<(), String> {
+        if num_endpoints == 0 {
+            return Err(
+                "At least one Ethereum RPC endpoint is required".to_string()
+            );
+        }
+        match self {
+            Self::Agreement { threshold }
+                if *threshold == 0 || *threshold > num_endpoints =>
+            {
+                Err(format!(
+                    "Invalid Ethereum RPC quorum: {threshold} of \
+                     {num_endpoints} endpoints"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
     /// The mode in which to run the Ethereum node and oracle setup of this
@@ -33,6 +70,13 @@ pub struct Config {
     /// The Ethereum JSON-RPC endpoint that the Ethereum event oracle will use
     /// to listen for events from the Ethereum bridge smart contracts
     pub oracle_rpc_endpoint: String,
+    /// Additional Ethereum JSON-RPC endpoints that the Ethereum event oracle
+    /// will use along with `oracle_rpc_endpoint`
+    #[serde(default)]
+    pub oracle_rpc_fallback_endpoints: Vec<String>,
+    /// How the responses of the Ethereum JSON-RPC endpoints are combined
+    #[serde(default)]
+    pub oracle_rpc_quorum: QuorumPolicy,
     /// The size of bounded channel between the Ethereum oracle and main
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
@@ -44,7 +88,53 @@ impl Default for Config {
         Self {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
+            oracle_rpc_fallback_endpoints: vec![],
+            oracle_rpc_quorum: QuorumPolicy::default(),
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
         }
     }
 }
+
+impl Config {
+    /// All the Ethereum JSON-RPC endpoints used by the oracle, starting with
+    /// the primary one.
+    pub fn oracle_rpc_endpoints(&self) -> Vec<String> {
+        std::iter::once(self.oracle_rpc_endpoint.clone())
+            .chain(self.oracle_rpc_fallback_endpoints.iter().cloned())
+            .collect()
+    }
+
+    /// Check that the quorum policy can be met with the configured Ethereum
+    /// JSON-RPC endpoints.
+    pub fn validate(&self) -> Result<(), String> {
+        self.oracle_rpc_quorum
+            .validate(self.oracle_rpc_endpoints().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_quorum() {
+        let mut config = Config {
+            oracle_rpc_fallback_endpoints: vec![
+                "http://127.0.0.1:8546".to_owned()
+            ],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.oracle_rpc_quorum = QuorumPolicy::Agreement { threshold: 2 };
+        assert!(config.validate().is_ok());
+
+        config.oracle_rpc_quorum = QuorumPolicy::Agreement { threshold: 3 };
+        assert!(config.validate().is_err());
+
+        config.oracle_rpc_quorum = QuorumPolicy::Agreement { threshold: 0 };
+        assert!(config.validate().is_err());
+
+        assert!(QuorumPolicy::Failover.validate(0).is_err());
+    }
+}
@@ -275,6 +275,8 @@ pub struct Parameters {
     pub epoch_duration: EpochDuration,
     /// Maximum expected time per block
     pub max_expected_time_per_block: DurationSecs,
+    /// Max time between a block and the expiration of its wrapper txs
+    pub tx_expiration_window: DurationSecs,
     /// Whitelisted validity predicate hashes
     pub vp_whitelist: Vec<String>,
     /// Whitelisted tx hashes
@@ -262,6 +262,7 @@ impl Finalized {
         let templates::ChainParams {
             min_num_of_blocks,
             max_expected_time_per_block,
+            tx_expiration_window,
             max_proposal_bytes,
             vp_whitelist,
             tx_whitelist,
@@ -297,6 +298,8 @@ impl Finalized {
         let max_expected_time_per_block =
             namada::types::time::Duration::seconds(max_expected_time_per_block)
                 .into();
+        let tx_expiration_window =
+            namada::types::time::Duration::seconds(tx_expiration_window).into();
         let vp_whitelist = vp_whitelist.unwrap_or_default();
         let tx_whitelist = tx_whitelist.unwrap_or_default();
         let staked_ratio = Dec::zero();
@@ -306,6 +309,7 @@ impl Finalized {
             max_tx_bytes,
             epoch_duration,
             max_expected_time_per_block,
+            tx_expiration_window,
             vp_whitelist,
             tx_whitelist,
             implicit_vp_code_hash,
@@ -704,6 +708,7 @@ impl FinalizedParameters {
             stewards: pgf_params.stewards,
             pgf_inflation_rate: pgf_params.pgf_inflation_rate,
             stewards_inflation_rate: pgf_params.stewards_inflation_rate,
+            batch_challenge_epochs: pgf_params.batch_challenge_epochs,
         };
         Self {
             parameters,
@@ -253,6 +253,9 @@ pub struct ChainParams<T: TemplateValidation> {
     /// Maximum duration per block (in seconds).
     // TODO: this is i64 because datetime wants it
     pub max_expected_time_per_block: i64,
+    /// Maximum time between a block and the expiration of the wrapper txs
+    /// it includes (in seconds).
+    pub tx_expiration_window: i64,
     /// Max payload size, in bytes, for a tx batch proposal.
     ///
     /// Block proposers may never return a `PrepareProposal`
@@ -298,6 +301,7 @@ impl ChainParams<Unvalidated> {
             native_token,
             min_num_of_blocks,
             max_expected_time_per_block,
+            tx_expiration_window,
             max_proposal_bytes,
             vp_whitelist,
             tx_whitelist,
@@ -343,6 +347,7 @@ impl ChainParams<Unvalidated> {
             native_token,
             min_num_of_blocks,
             max_expected_time_per_block,
+            tx_expiration_window,
             max_proposal_bytes,
             vp_whitelist,
             tx_whitelist,
@@ -450,6 +455,9 @@ pub struct PgfParams<T: TemplateValidation> {
     pub pgf_inflation_rate: Dec,
     /// The pgf stewards inflation rate
     pub stewards_inflation_rate: Dec,
+    /// The number of epochs during which a steward payment batch can be
+    /// challenged
+    pub batch_challenge_epochs: u64,
     #[serde(default)]
     #[serde(skip_serializing)]
     #[cfg(test)]
@@ -859,6 +867,7 @@ pub fn validate_parameters(
                 stewards: pgf_params.stewards,
                 pgf_inflation_rate: pgf_params.pgf_inflation_rate,
                 stewards_inflation_rate: pgf_params.stewards_inflation_rate,
+                batch_challenge_epochs: pgf_params.batch_challenge_epochs,
                 valid: Default::default(),
             },
             eth_bridge_params,
@@ -67,6 +67,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
     TxArgs {
         dry_run: false,
         dry_run_wrapper: false,
+        trace: false,
         dump_tx: false,
         output_folder: None,
         force: false,
@@ -847,7 +848,7 @@ impl<T> Signed<T> {
                 &None,
                 threshold,
                 None,
-                || Ok(()),
+                |_| Ok(()),
             )
             .map_err(|err| err.to_string())?;
         Ok(())
@@ -1326,6 +1327,17 @@ pub fn validate_validator_account(
 ) -> bool {
     let tx = &signed_tx.data;
 
+    // Check the consensus key is supported by Tendermint
+    if matches!(
+        &signed_tx.data.consensus_key.pk.raw,
+        common::PublicKey::Secp256r1(_)
+    ) {
+        panic!(
+            "The validator with address {} has a secp256r1 consensus key",
+            signed_tx.data.address
+        );
+    }
+
     // Check eth keys are secp256k1 keys
     if !matches!(
         &signed_tx.data.eth_cold_key.pk.raw,
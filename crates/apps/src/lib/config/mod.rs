@@ -65,6 +65,48 @@ impl TendermintMode {
     }
 }
 
+/// The database backend of the ledger's persistent storage.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum DbBackend {
+    /// RocksDB, with a column family per part of the storage tree.
+    #[default]
+    RocksDB,
+    /// LMDB, with a named database per part of the storage tree.
+    Lmdb,
+}
+
+/// Which historical states the ledger keeps in its storage.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum StatePruning {
+    /// Keep the diffs of all the block heights, so that the state can be
+    /// queried at any past height.
+    #[default]
+    Archive,
+    /// Prune the diffs of the heights older than the last `retained_heights`
+    /// in the background. The state can't be queried at the pruned heights.
+    Pruned {
+        /// The number of the last block heights whose state can be queried
+        retained_heights: u64,
+    },
+}
+
+impl StatePruning {
+    /// The number of the last block heights whose diffs are retained, if the
+    /// older ones are pruned
+    pub fn retained_heights(&self) -> Option<u64> {
+        match self {
+            StatePruning::Archive => None,
+            StatePruning::Pruned { retained_heights } => {
+                Some(*retained_heights)
+            }
+        }
+    }
+}
+
 /// An action to be performed at a
 /// certain block height.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +141,10 @@ pub struct Ledger {
 pub struct Shell {
     pub base_dir: PathBuf,
     // pub ledger_address: SocketAddr,
+    /// The database backend of the storage. It can't be changed for an
+    /// existing DB.
+    #[serde(default)]
+    pub db_backend: DbBackend,
     /// RocksDB block cache maximum size in bytes.
     /// When not set, defaults to 1/3 of the available memory.
     pub block_cache_bytes: Option<u64>,
@@ -111,6 +157,10 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// Whether the storage keeps the state of all the past block heights or
+    /// prunes the older ones.
+    #[serde(default)]
+    pub state_pruning: StatePruning,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -119,6 +169,13 @@ pub struct Shell {
     pub action_at_height: Option<ActionAtHeight>,
     /// Specify if tendermint is started as validator, fullnode or seednode
     pub tendermint_mode: TendermintMode,
+    /// When set, the bundle of precompiled WASMs at this path is verified
+    /// and loaded into the compilation cache on startup.
+    pub wasm_bundle: Option<PathBuf>,
+    /// Whether the operator trusts the source of the `wasm_bundle`, which
+    /// is required to load it as it contains native code.
+    #[serde(default)]
+    pub trust_wasm_bundle: bool,
 }
 
 impl Ledger {
@@ -138,15 +195,19 @@ impl Ledger {
             chain_id,
             shell: Shell {
                 base_dir: base_dir.as_ref().to_owned(),
+                db_backend: DbBackend::default(),
                 block_cache_bytes: None,
                 vp_wasm_compilation_cache_bytes: None,
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                state_pruning: StatePruning::default(),
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
                 tendermint_mode: mode,
+                wasm_bundle: None,
+                trust_wasm_bundle: false,
             },
             cometbft: tendermint_config,
             ethereum_bridge: ethereum_bridge::ledger::Config::default(),
@@ -167,6 +228,16 @@ impl Ledger {
     pub fn cometbft_dir(&self) -> PathBuf {
         self.shell.cometbft_dir(&self.chain_id)
     }
+
+    /// Get the directory path to the compiled VP WASM cache
+    pub fn vp_wasm_cache_dir(&self) -> PathBuf {
+        self.chain_dir().join("vp_wasm_cache")
+    }
+
+    /// Get the directory path to the compiled tx WASM cache
+    pub fn tx_wasm_cache_dir(&self) -> PathBuf {
+        self.chain_dir().join("tx_wasm_cache")
+    }
 }
 
 impl Shell {
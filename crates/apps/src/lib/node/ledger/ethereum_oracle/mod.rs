@@ -1,5 +1,6 @@
 pub mod control;
 pub mod events;
+pub mod quorum;
 pub mod test_tools;
 
 use std::ops::ControlFlow;
@@ -23,7 +24,9 @@ use tokio::sync::mpsc::Sender as BoundedSender;
 use tokio::task::LocalSet;
 
 use self::events::PendingEvent;
+use self::quorum::QuorumClient;
 use super::abortable::AbortableSpawner;
+use crate::config::ethereum_bridge::ledger::QuorumPolicy;
 use crate::node::ledger::oracle::control::Command;
 
 /// The default amount of time the oracle will wait between processing blocks
@@ -46,6 +49,10 @@ pub enum Error {
     MoreConfirmations,
     #[error("The Ethereum oracle timed out")]
     Timeout,
+    #[error("The Ethereum RPC endpoints did not reach a quorum: {0}")]
+    NoQuorum(String),
+    #[error("Invalid Ethereum RPC configuration: {0}")]
+    InvalidConfig(String),
 }
 
 /// Convert values to [`ethabi`] Ethereum event logs.
@@ -281,22 +288,37 @@ async fn await_initial_configuration(
 /// Set up an Oracle and run the process where the Oracle
 /// processes and forwards Ethereum events to the ledger
 pub fn run_oracle<C: RpcClient>(
-    url: impl AsRef<str>,
+    urls: Vec<String>,
+    quorum: QuorumPolicy,
     sender: BoundedSender<EthereumEvent>,
     control: control::Receiver,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
 ) -> tokio::task::JoinHandle<()> {
-    let url = url.as_ref().to_owned();
     let blocking_handle = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async move {
             LocalSet::new()
                 .run_until(async move {
-                    tracing::info!(?url, "Ethereum event oracle is starting");
+                    tracing::info!(
+                        ?urls,
+                        ?quorum,
+                        "Ethereum event oracle is starting"
+                    );
 
-                    let oracle = Oracle::<C>::new(
-                        Either::Right(&url),
+                    let client =
+                        match QuorumClient::<C>::from_urls(&urls, quorum) {
+                            Ok(client) => client,
+                            Err(error) => {
+                                tracing::error!(
+                                    %error,
+                                    "Ethereum event oracle couldn't start"
+                                );
+                                return;
+                            }
+                        };
+                    let oracle = Oracle::new(
+                        Either::Left(client),
                         sender,
                         last_processed_block,
                         DEFAULT_BACKOFF,
@@ -306,7 +328,7 @@ pub fn run_oracle<C: RpcClient>(
                     run_oracle_aux(oracle).await;
 
                     tracing::info!(
-                        ?url,
+                        ?urls,
                         "Ethereum event oracle is no longer running"
                     );
                 })
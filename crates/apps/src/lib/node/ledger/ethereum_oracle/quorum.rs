@@ -0,0 +1,473 @@
+//! An [`RpcClient`] backed by several Ethereum JSON-RPC endpoints, which
+//! either fails over between them or requires a quorum of them to agree on
+//! the block height and the event logs.
+
+use std::cell::RefCell;
+
+use async_trait::async_trait;
+use ethabi::Address;
+use futures::future::join_all;
+use namada::types::control_flow::time::{Duration, Instant};
+use namada::types::ethereum_structs;
+use namada_sdk::eth_bridge::SyncStatus;
+use num256::Uint256;
+
+use super::{Error, IntoEthAbiLog, RpcClient};
+use crate::config::ethereum_bridge::ledger::QuorumPolicy;
+
+/// The health of an Ethereum JSON-RPC endpoint.
+#[derive(Debug, Default, Clone)]
+pub struct EndpointHealth {
+    /// The number of successful requests
+    pub successes: u64,
+    /// The number of failed requests
+    pub failures: u64,
+    /// The number of failed requests since the last successful one
+    pub consecutive_failures: u64,
+    /// The error of the last failed request
+    pub last_error: Option<String>,
+}
+
+/// An Ethereum JSON-RPC endpoint with its health.
+struct Endpoint<C> {
+    url: String,
+    client: C,
+    health: RefCell<EndpointHealth>,
+}
+
+impl<C> Endpoint<C> {
+    /// Record the result of a request to this endpoint.
+    fn record<T>(&self, result: &Result<T, Error>) {
+        let mut health = self.health.borrow_mut();
+        match result {
+            Ok(_) => {
+                if health.consecutive_failures > 0 {
+                    tracing::info!(
+                        url = %self.url,
+                        failures = health.consecutive_failures,
+                        "Ethereum RPC endpoint has recovered"
+                    );
+                }
+                health.successes += 1;
+                health.consecutive_failures = 0;
+            }
+            Err(error) => {
+                health.failures += 1;
+                health.consecutive_failures += 1;
+                health.last_error = Some(error.to_string());
+                tracing::warn!(
+                    url = %self.url,
+                    %error,
+                    consecutive_failures = health.consecutive_failures,
+                    total_failures = health.failures,
+                    "Ethereum RPC endpoint request failed"
+                );
+            }
+        }
+    }
+}
+
+impl<C: RpcClient> Endpoint<C> {
+    /// Query the block for Ethereum events, recording the health.
+    async fn check_events_in_block(
+        &self,
+        block: ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+    ) -> Result<Vec<ethabi::RawLog>, Error> {
+        let result = self
+            .client
+            .check_events_in_block(block, address, abi_signature)
+            .await
+            .map(|logs| {
+                logs.into_iter()
+                    .map(IntoEthAbiLog::into_ethabi_log)
+                    .collect()
+            });
+        self.record(&result);
+        result
+    }
+
+    /// Check the sync status of the fullnode, recording the health.
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<SyncStatus, Error> {
+        let result = self
+            .client
+            .syncing(last_processed_block, backoff, deadline)
+            .await;
+        self.record(&result);
+        result
+    }
+}
+
+/// A client that talks to several Ethereum fullnodes.
+pub struct QuorumClient<C> {
+    endpoints: Vec<Endpoint<C>>,
+    policy: QuorumPolicy,
+}
+
+impl<C: RpcClient> QuorumClient<C> {
+    /// Instantiate a new client with the given endpoint clients.
+    ///
+    /// Returns an error if there is no endpoint or if the quorum threshold
+    /// can not be met with the given endpoints.
+    pub fn new(
+        clients: Vec<(String, C)>,
+        policy: QuorumPolicy,
+    ) -> Result<Self, Error> {
+        policy
+            .validate(clients.len())
+            .map_err(Error::InvalidConfig)?;
+        let endpoints = clients
+            .into_iter()
+            .map(|(url, client)| Endpoint {
+                url,
+                client,
+                health: Default::default(),
+            })
+            .collect();
+        Ok(Self { endpoints, policy })
+    }
+
+    /// Instantiate a new client pointing to the given RPC urls.
+    pub fn from_urls(
+        urls: &[String],
+        policy: QuorumPolicy,
+    ) -> Result<Self, Error> {
+        let clients = urls
+            .iter()
+            .map(|url| (url.clone(), C::new_client(url)))
+            .collect();
+        Self::new(clients, policy)
+    }
+
+    /// Get the health of every endpoint, along with its url.
+    pub fn health(&self) -> Vec<(String, EndpointHealth)> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                (endpoint.url.clone(), endpoint.health.borrow().clone())
+            })
+            .collect()
+    }
+
+    /// The endpoints ordered by their health, the healthiest first. The
+    /// configured order is kept among equally healthy endpoints.
+    fn by_health(&self) -> Vec<&Endpoint<C>> {
+        let mut endpoints: Vec<_> = self.endpoints.iter().collect();
+        endpoints.sort_by_key(|endpoint| {
+            endpoint.health.borrow().consecutive_failures
+        });
+        endpoints
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: RpcClient> RpcClient for QuorumClient<C> {
+    type Log = ethabi::RawLog;
+
+    fn new_client(rpc_url: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_urls(&[rpc_url.to_owned()], QuorumPolicy::Failover)
+            .expect("A single endpoint always meets the failover policy")
+    }
+
+    async fn check_events_in_block(
+        &self,
+        block: ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+    ) -> Result<Vec<Self::Log>, Error> {
+        match self.policy {
+            QuorumPolicy::Failover => {
+                let mut last_error = None;
+                for endpoint in self.by_health() {
+                    match endpoint
+                        .check_events_in_block(
+                            block.clone(),
+                            address,
+                            abi_signature,
+                        )
+                        .await
+                    {
+                        Ok(logs) => return Ok(logs),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+                Err(last_error.expect("There is at least one endpoint"))
+            }
+            QuorumPolicy::Agreement { threshold } => {
+                let results = join_all(self.endpoints.iter().map(|endpoint| {
+                    endpoint.check_events_in_block(
+                        block.clone(),
+                        address,
+                        abi_signature,
+                    )
+                }))
+                .await;
+                let mut responses: Vec<(Vec<ethabi::RawLog>, usize)> = vec![];
+                let mut first_error = None;
+                for result in results {
+                    match result {
+                        Ok(logs) => {
+                            match responses
+                                .iter_mut()
+                                .find(|(agreed, _)| *agreed == logs)
+                            {
+                                Some((_, votes)) => *votes += 1,
+                                None => responses.push((logs, 1)),
+                            }
+                        }
+                        Err(error) => {
+                            first_error.get_or_insert(error);
+                        }
+                    }
+                }
+                if let Some((logs, _)) =
+                    responses.iter().find(|(_, votes)| *votes >= threshold)
+                {
+                    return Ok(logs.clone());
+                }
+                match first_error {
+                    Some(error) if responses.is_empty() => Err(error),
+                    _ => Err(Error::NoQuorum(format!(
+                        "{} different event logs ({abi_signature} from \
+                         {address}) were returned, but {threshold} matching \
+                         responses are required",
+                        responses.len()
+                    ))),
+                }
+            }
+        }
+    }
+
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<SyncStatus, Error> {
+        match self.policy {
+            QuorumPolicy::Failover => {
+                let mut last_error = None;
+                for endpoint in self.by_health() {
+                    match endpoint
+                        .syncing(last_processed_block, backoff, deadline)
+                        .await
+                    {
+                        Ok(status) => return Ok(status),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+                Err(last_error.expect("There is at least one endpoint"))
+            }
+            QuorumPolicy::Agreement { threshold } => {
+                let results = join_all(self.endpoints.iter().map(|endpoint| {
+                    endpoint.syncing(last_processed_block, backoff, deadline)
+                }))
+                .await;
+                let mut heights: Vec<Uint256> = vec![];
+                let mut syncing = 0;
+                let mut first_error = None;
+                for result in results {
+                    match result {
+                        Ok(SyncStatus::AtHeight(height)) => {
+                            heights.push(height)
+                        }
+                        Ok(SyncStatus::Syncing) => syncing += 1,
+                        Err(error) => {
+                            first_error.get_or_insert(error);
+                        }
+                    }
+                }
+                // The highest height which at least `threshold` endpoints
+                // have reached
+                heights.sort_unstable_by(|a, b| b.cmp(a));
+                if let Some(height) = heights.get(threshold - 1) {
+                    return Ok(SyncStatus::AtHeight(height.clone()));
+                }
+                if heights.len() + syncing >= threshold {
+                    return Ok(SyncStatus::Syncing);
+                }
+                match first_error {
+                    Some(error) if heights.is_empty() && syncing == 0 => {
+                        Err(error)
+                    }
+                    _ => Err(Error::NoQuorum(format!(
+                        "Only {} endpoints returned their sync status, but \
+                         {threshold} are required",
+                        heights.len() + syncing
+                    ))),
+                }
+            }
+        }
+    }
+
+    fn may_recover(&self, error: &Error) -> bool {
+        match error {
+            // Some endpoints are still responsive
+            Error::NoQuorum(_) => true,
+            _ => self
+                .endpoints
+                .iter()
+                .any(|endpoint| endpoint.client.may_recover(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_quorum {
+    use super::*;
+    use crate::node::ledger::ethereum_oracle::test_tools::mock_web3_client::{
+        TestCmd, Web3Client, Web3Controller,
+    };
+
+    /// Set up a quorum client with the given number of mock endpoints
+    fn setup(
+        n: usize,
+        policy: QuorumPolicy,
+    ) -> (QuorumClient<Web3Client>, Vec<Web3Controller>) {
+        let mut controllers = vec![];
+        let clients = (0..n)
+            .map(|i| {
+                let (_, client) = Web3Client::setup();
+                controllers.push(client.controller());
+                (format!("http://127.0.0.1:{}", 8545 + i), client)
+            })
+            .collect();
+        let client = QuorumClient::new(clients, policy).expect("Test failed");
+        (client, controllers)
+    }
+
+    /// Test that an invalid quorum is rejected with an error
+    #[test]
+    fn test_invalid_quorum() {
+        let clients = (0..2)
+            .map(|i| {
+                let (_, client) = Web3Client::setup();
+                (format!("http://127.0.0.1:{}", 8545 + i), client)
+            })
+            .collect();
+        let result = QuorumClient::new(
+            clients,
+            QuorumPolicy::Agreement { threshold: 3 },
+        );
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+        let result =
+            QuorumClient::<Web3Client>::new(vec![], QuorumPolicy::Failover);
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+
+    async fn height(client: &QuorumClient<Web3Client>) -> Uint256 {
+        match client
+            .syncing(None, Duration::from_millis(1), Instant::now())
+            .await
+            .expect("Test failed")
+        {
+            SyncStatus::AtHeight(height) => height,
+            SyncStatus::Syncing => panic!("Test failed"),
+        }
+    }
+
+    fn log(data: u8) -> ethabi::RawLog {
+        ethabi::RawLog {
+            topics: vec![],
+            data: vec![data],
+        }
+    }
+
+    /// Test that the failover policy uses the next endpoint when one fails,
+    /// and records the health of the endpoints
+    #[tokio::test]
+    async fn test_failover() {
+        let (client, controllers) = setup(2, QuorumPolicy::Failover);
+        controllers[0].apply_cmd(TestCmd::NewHeight(Uint256::from(10u32)));
+        controllers[1].apply_cmd(TestCmd::NewHeight(Uint256::from(10u32)));
+        controllers[0].apply_cmd(TestCmd::Unresponsive);
+        let (seen, _seen_recv) = tokio::sync::oneshot::channel();
+        controllers[1].apply_cmd(TestCmd::NewEvent {
+            event_type: "event".into(),
+            log: log(1),
+            height: 1,
+            seen,
+        });
+
+        let logs = client
+            .check_events_in_block(1u64.into(), Address::zero(), "event")
+            .await
+            .expect("Test failed");
+        assert_eq!(logs, vec![log(1)]);
+        let health = client.health();
+        assert_eq!(health[0].1.consecutive_failures, 1);
+        assert_eq!(health[1].1.successes, 1);
+
+        // The unhealthy endpoint is tried last
+        assert_eq!(client.by_health()[0].url, health[1].0);
+
+        // All the endpoints fail
+        controllers[1].apply_cmd(TestCmd::Unresponsive);
+        assert!(client
+            .check_events_in_block(2u64.into(), Address::zero(), "event")
+            .await
+            .is_err());
+    }
+
+    /// Test that the agreement policy only accepts the block height reached
+    /// by the quorum
+    #[tokio::test]
+    async fn test_agreement_on_height() {
+        let (client, controllers) =
+            setup(3, QuorumPolicy::Agreement { threshold: 2 });
+        controllers[0].apply_cmd(TestCmd::NewHeight(Uint256::from(100u32)));
+        controllers[1].apply_cmd(TestCmd::NewHeight(Uint256::from(12u32)));
+        controllers[2].apply_cmd(TestCmd::NewHeight(Uint256::from(10u32)));
+        // The lying endpoint is ignored
+        assert_eq!(height(&client).await, Uint256::from(12u32));
+    }
+
+    /// Test that the agreement policy rejects event logs which not enough
+    /// endpoints agree on
+    #[tokio::test]
+    async fn test_agreement_on_events() {
+        let (client, controllers) =
+            setup(3, QuorumPolicy::Agreement { threshold: 2 });
+        let mut seen_recvs = vec![];
+        let mut add_event = |controller: &Web3Controller, data| {
+            let (seen, seen_recv) = tokio::sync::oneshot::channel();
+            seen_recvs.push(seen_recv);
+            controller.apply_cmd(TestCmd::NewEvent {
+                event_type: "event".into(),
+                log: log(data),
+                height: 1,
+                seen,
+            });
+        };
+
+        // Two endpoints agree
+        add_event(&controllers[0], 1);
+        add_event(&controllers[1], 1);
+        add_event(&controllers[2], 2);
+        let logs = client
+            .check_events_in_block(1u64.into(), Address::zero(), "event")
+            .await
+            .expect("Test failed");
+        assert_eq!(logs, vec![log(1)]);
+
+        // No endpoints agree
+        add_event(&controllers[0], 3);
+        add_event(&controllers[1], 4);
+        controllers[2].apply_cmd(TestCmd::Unresponsive);
+        let error = client
+            .check_events_in_block(2u64.into(), Address::zero(), "event")
+            .await
+            .expect_err("Test failed");
+        assert!(matches!(error, Error::NoQuorum(_)));
+        assert!(client.may_recover(&error));
+    }
+}
@@ -215,15 +215,145 @@ pub fn dump_db(
         historic,
     }: args::LedgerDumpDb,
 ) {
-    use namada::state::DB;
-
     let chain_id = config.chain_id;
     let db_path = config.shell.db_dir(&chain_id);
 
-    let db = storage::PersistentDB::open(db_path, None);
+    let db =
+        storage::PersistentDB::open_backend(db_path, config.shell.db_backend);
     db.dump_block(out_file_path, historic, block_height);
 }
 
+/// Read the whitelists of txs and VPs from the last committed state of the
+/// node or, if it has none yet, from the genesis files of the chain
+fn read_wasm_whitelists(
+    config: &config::Ledger,
+) -> eyre::Result<namada::vm::wasm::compilation_cache::bundle::Whitelists> {
+    use borsh::BorshDeserialize;
+    use namada::parameters::storage::{
+        get_tx_whitelist_storage_key, get_vp_whitelist_storage_key,
+    };
+    use namada::state::DB;
+    use namada::vm::wasm::compilation_cache::bundle::Whitelists;
+
+    let db_path = config.db_dir();
+    if db_path.exists() {
+        let db = storage::PersistentDB::open_backend(
+            db_path,
+            config.shell.db_backend,
+        );
+        if db.read_last_block()?.is_some() {
+            let read_whitelist = |key: &Key| -> eyre::Result<Vec<String>> {
+                Ok(db
+                    .read_subspace_val(key)?
+                    .map(|bytes| Vec::<String>::try_from_slice(&bytes))
+                    .transpose()?
+                    .unwrap_or_default())
+            };
+            return Ok(Whitelists {
+                tx: read_whitelist(&get_tx_whitelist_storage_key())?,
+                vp: read_whitelist(&get_vp_whitelist_storage_key())?,
+            });
+        }
+    }
+    tracing::info!(
+        "The node has no state yet, using the WASM whitelists from genesis"
+    );
+    let genesis = config::genesis::chain::Finalized::read_toml_files(
+        &config.chain_dir(),
+    )?;
+    let config::genesis::templates::ChainParams {
+        tx_whitelist,
+        vp_whitelist,
+        ..
+    } = genesis.parameters.parameters;
+    Ok(Whitelists {
+        tx: tx_whitelist.unwrap_or_default(),
+        vp: vp_whitelist.unwrap_or_default(),
+    })
+}
+
+/// Compile the whitelisted WASMs into a bundle of precompiled modules for
+/// the host
+pub fn build_wasm_bundle(
+    config: config::Ledger,
+    wasm_dir: PathBuf,
+    args::LedgerBuildWasmBundle { out_file_path }: args::LedgerBuildWasmBundle,
+) -> eyre::Result<()> {
+    use namada::types::hash::Hash;
+    use namada::vm::wasm::compilation_cache::bundle::{ArtifactKind, Bundle};
+
+    let whitelists = read_wasm_whitelists(&config)?;
+    let checksums = wasm_loader::Checksums::read_checksums(&wasm_dir)?;
+    let mut bundle = Bundle::default();
+    for name in checksums.0.keys() {
+        let kind = if name.starts_with("vp_") {
+            ArtifactKind::Vp
+        } else {
+            ArtifactKind::Tx
+        };
+        let code = wasm_loader::read_wasm(&wasm_dir, name)?;
+        if !whitelists.allows(kind, &Hash::sha256(&code)) {
+            tracing::debug!("Skipping non-whitelisted WASM {name}");
+            continue;
+        }
+        tracing::info!("Compiling {name}...");
+        bundle.compile(kind, code)?;
+    }
+    bundle.write(&out_file_path)?;
+    println!(
+        "Wrote {} precompiled WASMs for {} to {}",
+        bundle.artifacts.len(),
+        bundle.header.target,
+        out_file_path.to_string_lossy()
+    );
+    Ok(())
+}
+
+/// Verify a bundle of precompiled WASMs and load it into the compilation
+/// caches of the node, if the bundle is trusted by the operator
+fn load_wasm_bundle(config: &config::Ledger, bundle_path: &std::path::Path) {
+    use namada::vm::wasm::compilation_cache::bundle::Bundle;
+
+    if !config.shell.trust_wasm_bundle {
+        tracing::error!(
+            "Refusing to load the WASM bundle {}, as it contains native code \
+             that runs on the node without validation. Only load a bundle \
+             that you built or that comes from a source you trust, by setting \
+             `--trust-wasm-bundle` or the `trust_wasm_bundle` setting of the \
+             configuration.",
+            bundle_path.to_string_lossy()
+        );
+        crate::cli::safe_exit(1)
+    }
+    let whitelists = read_wasm_whitelists(config).unwrap_or_else(|err| {
+        tracing::error!("Failed to read the WASM whitelists: {err}");
+        crate::cli::safe_exit(1)
+    });
+    let loaded = Bundle::read(bundle_path).and_then(|bundle| {
+        // Safe, as the operator trusts the bundle
+        unsafe {
+            bundle.load(
+                &whitelists,
+                config.tx_wasm_cache_dir(),
+                config.vp_wasm_cache_dir(),
+            )
+        }
+    });
+    match loaded {
+        Ok(count) => tracing::info!(
+            "Loaded {count} precompiled WASMs from {}",
+            bundle_path.to_string_lossy()
+        ),
+        Err(err) => {
+            tracing::error!(
+                "Rejected the WASM bundle {}: {err}",
+                bundle_path.to_string_lossy()
+            );
+            crate::cli::safe_exit(1)
+        }
+    }
+}
+
 /// Roll Namada state back to the previous height
 pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
@@ -331,6 +461,11 @@ async fn run_aux_setup(
     // Prefetch needed wasm artifacts
     wasm_loader::pre_fetch_wasm(wasm_dir).await;
 
+    // Load the precompiled WASMs, if any
+    if let Some(bundle_path) = &config.shell.wasm_bundle {
+        load_wasm_bundle(config, bundle_path);
+    }
+
     // Find the system available memory
     let available_memory_bytes = Lazy::new(|| {
         let sys = System::new_with_specifics(RefreshKind::new().with_memory());
@@ -469,8 +604,10 @@ fn start_abci_broadcaster_shell(
     };
 
     // Setup DB cache, it must outlive the DB instance that's in the shell
-    let db_cache =
-        rocksdb::Cache::new_lru_cache(db_block_cache_size_bytes as usize);
+    let db_cache = storage::PersistentDBCache::new(
+        config.shell.db_backend,
+        db_block_cache_size_bytes,
+    );
 
     // Construct our ABCI application.
     let tendermint_mode = config.shell.tendermint_mode.clone();
@@ -678,7 +815,8 @@ async fn maybe_start_ethereum_oracle(
     match config.ethereum_bridge.mode {
         ethereum_bridge::ledger::Mode::RemoteEndpoint => {
             let handle = oracle::run_oracle::<Provider<Http>>(
-                ethereum_url,
+                config.ethereum_bridge.oracle_rpc_endpoints(),
+                config.ethereum_bridge.oracle_rpc_quorum.clone(),
                 eth_sender,
                 control_receiver,
                 last_processed_block_sender,
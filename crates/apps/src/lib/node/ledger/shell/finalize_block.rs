@@ -1,11 +1,14 @@
 //! Implementation of the `FinalizeBlock` ABCI++ method for the Shell
 
-use data_encoding::HEXUPPER;
+use data_encoding::{HEXLOWER, HEXUPPER};
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
 use namada::governance::pgf::inflation as pgf_inflation;
+use namada::ethereum_bridge::protocol::transactions::ethereum_events;
+use namada::ibc;
 use namada::ledger::events::EventType;
-use namada::ledger::gas::{GasMetering, TxGasMeter};
+use namada::ledger::gas::{Gas, GasMetering, TxGasMeter};
+use namada::ledger::pgf::utils::ProposalEvent as PgfEvent;
 use namada::ledger::pos::namada_proof_of_stake;
 use namada::ledger::protocol::{self, WrapperArgs};
 use namada::proof_of_stake::storage::{
@@ -19,6 +22,7 @@ use namada::state::{
 };
 use namada::token::conversion::update_allowed_conversions;
 use namada::tx::data::protocol::ProtocolTxType;
+use namada::tx::{Code, Data};
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::vote_ext::ethereum_events::MultiSignedEthEvent;
@@ -80,14 +84,36 @@ where
             self.wl_storage.storage.update_epoch_blocks_delay
         );
 
-        // Finalize the transactions' hashes from the previous block
-        for hash in self.wl_storage.storage.iter_replay_protection() {
+        // Finalize the transactions' hashes from the previous block. The
+        // hashes without a known expiration are never pruned
+        for (hash, expiration) in
+            self.wl_storage.storage.iter_replay_protection()
+        {
             self.wl_storage
                 .write_log
-                .finalize_tx_hash(hash)
+                .finalize_tx_hash(hash, expiration)
                 .expect("Failed tx hashes finalization")
         }
 
+        // Prune the hashes of the transactions that expired before the last
+        // committed block. Pruning against the last committed block rather
+        // than the current one is safe across a rollback, since the block
+        // replacing the current one can't be older than the last committed one
+        if let Some(last_block_time) =
+            self.wl_storage.storage.last_block.as_ref().map(|b| b.time)
+        {
+            for (hash, expiration) in self
+                .wl_storage
+                .storage
+                .iter_expired_replay_protection(last_block_time)
+            {
+                self.wl_storage
+                    .write_log
+                    .prune_tx_hash(hash, expiration)
+                    .expect("Failed tx hashes pruning")
+            }
+        }
+
         let pos_params =
             namada_proof_of_stake::storage::read_pos_params(&self.wl_storage)?;
 
@@ -493,7 +519,15 @@ where
                                         .eth_bridge_events
                                         .iter()
                                         .map(Event::from),
-                                ),
+                                )
+                                // multi-transfer leg events
+                                .chain(result.transfer_leg_events.iter().map(
+                                    |leg_event| {
+                                        let mut event = Event::from(leg_event);
+                                        event["height"] = height.to_string();
+                                        event
+                                    },
+                                )),
                         );
                     } else {
                         tracing::trace!(
@@ -596,6 +630,19 @@ where
         tracing::info!("{}", stats);
         tracing::info!("{}", stats.format_tx_executed());
 
+        // Execute the transactions received by the interchain accounts
+        self.execute_ica_host_txs(&mut response)?;
+
+        // Refund the Bridge pool transfers cancelled before being signed
+        let (_, eth_bridge_events) =
+            ethereum_events::refund_unsigned_cancelled_transfers(
+                &mut self.wl_storage,
+            )
+            .expect("Refunding the cancelled transfers shouldn't fail");
+        response
+            .events
+            .extend(eth_bridge_events.iter().map(Event::from));
+
         // Update the MASP commitment tree anchor if the tree was updated
         let tree_key = token::storage_key::masp_commitment_tree_key();
         if let Some(StorageModification::Write { value }) =
@@ -679,6 +726,87 @@ where
             .expect("Must be able to update validator set");
     }
 
+    /// Execute the transactions received in this block by the interchain
+    /// accounts hosted on this chain. Each transaction is applied under the
+    /// interchain account with its declared gas limit. When a transaction is
+    /// rejected, the rest of the transactions received with the packet are
+    /// skipped and the acknowledgement of the packet is overwritten with the
+    /// error.
+    fn execute_ica_host_txs(
+        &mut self,
+        response: &mut shim::response::FinalizeBlock,
+    ) -> Result<()> {
+        let pending = ibc::ica::pending_host_txs(&self.wl_storage)?;
+        for (pending_key, pending_txs) in pending {
+            let execution_key =
+                ibc::storage::ica_execution_key(&pending_txs.account);
+            self.wl_storage.write(&execution_key, ())?;
+
+            let mut error = None;
+            for (index, host_tx) in pending_txs.txs.iter().enumerate() {
+                let mut tx =
+                    Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+                tx.header.chain_id = self.chain_id.clone();
+                tx.set_data(Data::new(host_tx.data.clone()));
+                tx.set_code(Code::from_hash(host_tx.code_hash, None));
+
+                let tx_result = protocol::dispatch_tx(
+                    tx,
+                    &[], /*  this is used to compute the fee
+                          * based on the code size. We dont
+                          * need it here. */
+                    TxIndex::default(),
+                    &mut TxGasMeter::new_from_sub_limit(Gas::from_whole_units(
+                        host_tx.gas_limit,
+                    )),
+                    &mut self.wl_storage,
+                    &mut self.vp_wasm_cache,
+                    &mut self.tx_wasm_cache,
+                    None,
+                );
+                let reason = match tx_result {
+                    Ok(tx_result) if tx_result.is_accepted() => {
+                        self.wl_storage.commit_tx();
+                        continue;
+                    }
+                    Ok(tx_result) => format!(
+                        "was rejected by VPs: {:?}",
+                        tx_result.vps_result.rejected_vps
+                    ),
+                    Err(e) => format!("failed: {e}"),
+                };
+                self.wl_storage.drop_tx();
+                tracing::info!(
+                    "The transaction {} of the interchain account {} received \
+                     with the packet {} on {} {}",
+                    index,
+                    pending_txs.account,
+                    pending_txs.sequence,
+                    pending_txs.channel_id,
+                    reason,
+                );
+                error = Some(format!(
+                    "The transaction {index} {reason}. The preceding \
+                     transactions have been applied."
+                ));
+                break;
+            }
+
+            self.wl_storage.delete(&execution_key)?;
+            self.wl_storage.delete(&pending_key)?;
+
+            if let Some(error) = error {
+                let ack = ibc::ica::write_error_ack(
+                    &mut self.wl_storage,
+                    &pending_txs,
+                    error,
+                )?;
+                update_write_ack_event(response, &pending_txs, &ack);
+            }
+        }
+        Ok(())
+    }
+
     /// Calculate the new inflation rate, mint the new tokens to the PoS
     /// account, then update the reward products of the validators. This is
     /// executed while finalizing the first block of a new epoch and is applied
@@ -709,10 +837,15 @@ where
         )?;
 
         // Pgf inflation
-        pgf_inflation::apply_inflation(
+        let completed_fundings = pgf_inflation::apply_inflation(
             &mut self.wl_storage,
             namada::ibc::transfer_over_ibc,
         )?;
+        for funding in completed_fundings {
+            response
+                .events
+                .push(PgfEvent::pgf_funding_completed(&funding).into());
+        }
         for ibc_event in self.wl_storage.write_log_mut().take_ibc_events() {
             let mut event = Event::from(ibc_event.clone());
             // Add the height for IBC event query
@@ -771,7 +904,10 @@ where
     // both the hashes.
     fn commit_inner_tx_hash(&mut self, wrapper_tx: Tx) {
         self.wl_storage
-            .write_tx_hash(wrapper_tx.raw_header_hash())
+            .write_tx_hash(
+                wrapper_tx.raw_header_hash(),
+                wrapper_tx.header.expiration,
+            )
             .expect("Error while writing tx hash to storage");
 
         self.wl_storage
@@ -843,6 +979,27 @@ fn pos_votes_from_abci(
         .collect()
 }
 
+/// Replace the acknowledgement in the event written when receiving the packet
+/// of the interchain account transactions
+fn update_write_ack_event(
+    response: &mut shim::response::FinalizeBlock,
+    pending_txs: &ibc::ica::PendingHostTxs,
+    ack: &[u8],
+) {
+    let sequence = pending_txs.sequence.to_string();
+    let event = response.events.iter_mut().find(|event| {
+        event.event_type == EventType::Ibc("write_acknowledgement".to_string())
+            && event.get("packet_dst_port").map(String::as_str)
+                == Some(ibc::ica::HOST_PORT_ID_STR)
+            && event.get("packet_dst_channel") == Some(&pending_txs.channel_id)
+            && event.get("packet_sequence") == Some(&sequence)
+    });
+    if let Some(event) = event {
+        event["packet_ack"] = String::from_utf8_lossy(ack).into_owned();
+        event["packet_ack_hex"] = HEXLOWER.encode(ack);
+    }
+}
+
 /// We test the failure cases of [`finalize_block`]. The happy flows
 /// are covered by the e2e tests.
 #[cfg(test)]
@@ -933,6 +1090,8 @@ mod test_finalize_block {
                 None,
             ))));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx.set_data(Data::new(
             "Encrypted transaction data".as_bytes().to_owned(),
@@ -974,6 +1133,8 @@ mod test_finalize_block {
                 None,
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
+        outer_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         outer_tx.set_code(Code::new(tx_code, None));
         outer_tx.set_data(Data::new(
             "Decrypted transaction data".as_bytes().to_owned(),
@@ -1091,6 +1252,8 @@ mod test_finalize_block {
                 None,
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
+        outer_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         outer_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         outer_tx.set_data(Data::new(
             String::from("transaction data").as_bytes().to_owned(),
@@ -2927,6 +3090,8 @@ mod test_finalize_block {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new(tx_code, None));
         wrapper.set_data(Data::new(
             "Decrypted transaction data".as_bytes().to_owned(),
@@ -2967,7 +3132,7 @@ mod test_finalize_block {
             shell
                 .wl_storage
                 .storage
-                .write_replay_protection_entry(&mut batch, &hash_subkey)
+                .write_replay_protection_entry(&mut batch, &hash_subkey, b"")
                 .expect("Test failed");
         }
 
@@ -3054,6 +3219,8 @@ mod test_finalize_block {
                 None,
             ))));
         unsigned_wrapper.header.chain_id = shell.chain_id.clone();
+        unsigned_wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         let mut failing_wrapper = unsigned_wrapper.clone();
         unsigned_wrapper.set_code(Code::new(tx_code, None));
         let addr = Address::from(&keypair.to_public());
@@ -3107,7 +3274,7 @@ mod test_finalize_block {
             shell
                 .wl_storage
                 .storage
-                .write_replay_protection_entry(&mut batch, &hash_subkey)
+                .write_replay_protection_entry(&mut batch, &hash_subkey, b"")
                 .unwrap();
         }
 
@@ -3228,6 +3395,8 @@ mod test_finalize_block {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new(
             "Encrypted transaction data".as_bytes().to_owned(),
@@ -3307,6 +3476,8 @@ mod test_finalize_block {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new(
             "Encrypted transaction data".as_bytes().to_owned(),
@@ -3393,6 +3564,8 @@ mod test_finalize_block {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new(tx_code, None));
         wrapper.set_data(Data::new(
             "Enxrypted transaction data".as_bytes().to_owned(),
@@ -5144,4 +5317,139 @@ mod test_finalize_block {
             control_receiver.recv().await.expect("Test failed");
         assert_eq!(u64::from(cmd.min_confirmations), 42);
     }
+
+    /// Test that the transactions received by an interchain account are
+    /// executed with their gas limits, and that the acknowledgement of the
+    /// packet is overwritten with an error when a transaction fails
+    #[test]
+    fn test_ica_host_txs_execution() {
+        use namada::ibc::apps::transfer::types::ack_success_b64;
+        use namada::ibc::core::channel::types::acknowledgement::AcknowledgementStatus;
+        use namada::ibc::core::host::types::identifiers::{
+            ChannelId, ConnectionId, Sequence,
+        };
+        use namada::ibc::ica::{HostTx, PendingHostTxs};
+        use namada::ledger::events::EventLevel;
+
+        let (mut shell, _, _, _) = setup();
+
+        let tx_code = TestWasms::TxWriteStorageKey.read_bytes();
+        let code_hash = Hash::sha256(&tx_code);
+        let code_len = (tx_code.len() as u64).serialize_to_vec();
+        shell
+            .wl_storage
+            .write_bytes(&Key::wasm_code(&code_hash), tx_code)
+            .unwrap();
+        shell
+            .wl_storage
+            .write_bytes(&Key::wasm_code_len(&code_hash), code_len)
+            .unwrap();
+
+        let owner = namada::types::address::testing::established_address_1();
+        let controller_port_id = ibc::ica::controller_port_id(&owner).unwrap();
+        let account = ibc::storage::ica_account(
+            &ConnectionId::new(0),
+            &controller_port_id,
+        );
+        let channel_id = ChannelId::new(0);
+        let account_key = |name: &str| {
+            Key::from(account.to_db_key())
+                .push(&name.to_string().to_db_key())
+                .unwrap()
+        };
+        let host_tx = |name: &str, gas_limit: u64| HostTx {
+            code_hash,
+            data: TxWriteData {
+                key: account_key(name),
+                value: name.as_bytes().to_vec(),
+            }
+            .serialize_to_vec(),
+            gas_limit,
+        };
+        let success_ack: Vec<u8> =
+            AcknowledgementStatus::success(ack_success_b64()).into();
+        let mut response = shim::response::FinalizeBlock::default();
+        // The second packet has a transaction which runs out of gas
+        for (sequence, txs) in [
+            (1, vec![host_tx("a", 1_000_000_000)]),
+            (
+                2,
+                vec![
+                    host_tx("b", 1_000_000_000),
+                    host_tx("c", 1),
+                    host_tx("d", 1_000_000_000),
+                ],
+            ),
+        ] {
+            let pending = PendingHostTxs {
+                channel_id: channel_id.to_string(),
+                sequence,
+                account: account.clone(),
+                txs,
+            };
+            let sequence = Sequence::from(sequence);
+            let key = ibc::storage::ica_pending_tx_key(&channel_id, sequence);
+            shell.wl_storage.write(&key, pending).unwrap();
+            // The acknowledgement written when receiving the packet
+            let key = ibc::storage::ack_key(
+                &ibc::ica::host_port_id(),
+                &channel_id,
+                sequence,
+            );
+            shell
+                .wl_storage
+                .write_bytes(&key, Hash::sha256(&success_ack).to_vec())
+                .unwrap();
+            let mut event = Event {
+                event_type: EventType::Ibc("write_acknowledgement".to_string()),
+                level: EventLevel::Tx,
+                attributes: HashMap::new(),
+            };
+            event["packet_dst_port"] = ibc::ica::HOST_PORT_ID_STR.to_string();
+            event["packet_dst_channel"] = channel_id.to_string();
+            event["packet_sequence"] = sequence.to_string();
+            event["packet_ack_hex"] = HEXLOWER.encode(&success_ack);
+            response.events.push(event);
+        }
+
+        shell.execute_ica_host_txs(&mut response).unwrap();
+
+        // The transactions before the failed one have been applied
+        for (name, is_applied) in
+            [("a", true), ("b", true), ("c", false), ("d", false)]
+        {
+            assert_eq!(
+                shell.wl_storage.has_key(&account_key(name)).unwrap(),
+                is_applied,
+                "{name}"
+            );
+        }
+        // All the pending transactions have been consumed
+        let pending = ibc::ica::pending_host_txs(&shell.wl_storage).unwrap();
+        assert!(pending.is_empty());
+        assert!(
+            !shell
+                .wl_storage
+                .has_key(&ibc::storage::ica_execution_key(&account))
+                .unwrap()
+        );
+        // Only the acknowledgement of the second packet is overwritten
+        for (sequence, event) in [1, 2].into_iter().zip(&response.events) {
+            let key = ibc::storage::ack_key(
+                &ibc::ica::host_port_id(),
+                &channel_id,
+                Sequence::from(sequence),
+            );
+            let commitment = shell.wl_storage.read_bytes(&key).unwrap();
+            let ack =
+                HEXLOWER.decode(event["packet_ack_hex"].as_bytes()).unwrap();
+            assert_eq!(commitment, Some(Hash::sha256(&ack).to_vec()));
+            if sequence == 1 {
+                assert_eq!(ack, success_ack);
+            } else {
+                assert!(event["packet_ack"].contains("The transaction 1"));
+                assert_ne!(ack, success_ack);
+            }
+        }
+    }
 }
@@ -1,11 +1,15 @@
 use std::collections::HashMap;
 
+use namada::governance::pgf::storage::history::{
+    PgfPaymentKind, PgfPaymentRecord,
+};
 use namada::governance::pgf::storage::keys as pgf_storage;
 use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::pgf::{storage as pgf, ADDRESS};
 use namada::governance::storage::keys as gov_storage;
 use namada::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
+    AddRemove, PGFAction, PGFContinuousFunding, PGFTarget, ProposalType,
+    StoragePgfFunding,
 };
 use namada::governance::utils::{
     compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
@@ -367,11 +371,12 @@ where
     for payment in payments {
         match payment {
             PGFAction::Continuous(action) => match action {
-                AddRemove::Add(target) => {
+                AddRemove::Add(funding) => {
+                    let target = funding.target.clone();
                     pgf_storage::fundings_handle().insert(
                         storage,
                         target.target().clone(),
-                        StoragePgfFunding::new(target.clone(), proposal_id),
+                        StoragePgfFunding::new(funding, proposal_id),
                     )?;
                     tracing::info!(
                         "Execute ContinousPgf from proposal id {}: set {} to \
@@ -381,7 +386,7 @@ where
                         target.target()
                     );
                 }
-                AddRemove::Remove(target) => {
+                AddRemove::Remove(PGFContinuousFunding { target, .. }) => {
                     pgf_storage::fundings_handle()
                         .remove(storage, &target.target())?;
                     tracing::info!(
@@ -406,22 +411,39 @@ where
                         ibc::transfer_over_ibc(storage, token, &ADDRESS, target)
                     }
                 };
-                match result {
-                    Ok(()) => tracing::info!(
-                        "Execute RetroPgf from proposal id {}: sent {} to {}.",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target()
-                    ),
-                    Err(e) => tracing::warn!(
-                        "Error in RetroPgf transfer from proposal id {}, \
-                         amount {} to {}: {}",
+                let error = match result {
+                    Ok(()) => {
+                        tracing::info!(
+                            "Execute RetroPgf from proposal id {}: sent {} to \
+                             {}.",
+                            proposal_id,
+                            target.amount().to_string_native(),
+                            target.target()
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Error in RetroPgf transfer from proposal id {}, \
+                             amount {} to {}: {}",
+                            proposal_id,
+                            target.amount().to_string_native(),
+                            target.target(),
+                            e
+                        );
+                        Some(e.to_string())
+                    }
+                };
+                let epoch = storage.storage.block.epoch;
+                pgf::update_epoch_history(storage, epoch, |record| {
+                    record.payments.push(PgfPaymentRecord {
+                        kind: PgfPaymentKind::Retro,
                         proposal_id,
-                        target.amount().to_string_native(),
-                        target.target(),
-                        e
-                    ),
-                }
+                        target: target.target(),
+                        amount: target.amount(),
+                        error,
+                    })
+                })?;
             }
         }
     }
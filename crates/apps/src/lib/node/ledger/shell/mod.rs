@@ -32,6 +32,7 @@ use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::transaction::Transaction;
 use namada::core::hints;
+use namada::core::ledger::replay_protection;
 use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
 use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
 use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
@@ -98,6 +99,9 @@ fn key_to_tendermint(
             secp256k1::PublicKey::try_from_pk(pk)
                 .map(|pk| public_key::Sum::Secp256k1(pk.serialize_to_vec()))
         }
+        common::PublicKey::Secp256r1(_) => {
+            Err(ParsePublicKeyError::MismatchedScheme)
+        }
     }
 }
 
@@ -129,6 +133,8 @@ pub enum Error {
     Storage(#[from] namada::state::StorageError),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error("Invalid transaction expiration: {0}")]
+    InvalidExpiration(String),
 }
 
 impl From<Error> for TxResult {
@@ -163,7 +169,8 @@ pub fn rollback(config: config::Ledger) -> Result<()> {
 
     // Rollback Namada state
     let db_path = config.shell.db_dir(&config.chain_id);
-    let mut db = storage::PersistentDB::open(db_path, None);
+    let mut db =
+        storage::PersistentDB::open_backend(db_path, config.shell.db_backend);
     tracing::info!("Rollback Namada state");
 
     db.rollback(tendermint_block_height)
@@ -402,6 +409,8 @@ where
         vp_wasm_compilation_cache: u64,
         tx_wasm_compilation_cache: u64,
     ) -> Self {
+        let vp_wasm_cache_dir = config.vp_wasm_cache_dir();
+        let tx_wasm_cache_dir = config.tx_wasm_cache_dir();
         let chain_id = config.chain_id;
         let db_path = config.shell.db_dir(&chain_id);
         let base_dir = config.shell.base_dir;
@@ -432,16 +441,26 @@ where
             db_cache,
             config.shell.storage_read_past_height_limit,
         );
+        storage.diffs_retained_heights =
+            config.shell.state_pruning.retained_heights();
+        if let (Some(retained), Some(limit)) = (
+            storage.diffs_retained_heights,
+            storage_read_past_height_limit,
+        ) {
+            if retained < limit {
+                tracing::warn!(
+                    "Only the state of the last {retained} block heights is \
+                     retained, past heights can't be queried up to the \
+                     `shell.storage_read_past_height_limit` of {limit}"
+                );
+            }
+        }
         storage
             .load_last_state()
             .map_err(|e| {
                 tracing::error!("Cannot load the last state from the DB {}", e);
             })
             .expect("PersistentStorage cannot be initialized");
-        let vp_wasm_cache_dir =
-            base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
-        let tx_wasm_cache_dir =
-            base_dir.join(chain_id.as_str()).join("tx_wasm_cache");
         // load in keys and address from wallet if mode is set to `Validator`
         let mode = match mode {
             TendermintMode::Validator => {
@@ -506,10 +525,31 @@ where
             TendermintMode::Seed => ShellMode::Seed,
         };
 
-        let wl_storage = WlStorage {
+        let mut wl_storage = WlStorage {
             storage,
             write_log: WriteLog::default(),
         };
+        // Give an expiration to the replay protection entries written before
+        // their expiration was tracked, so that they get pruned too. The
+        // expiration of these txs was unbounded, so it's conservatively set
+        // well after the last committed block, which is the same for all the
+        // nodes upgrading at the same height.
+        if let Some(last_block_time) =
+            wl_storage.storage.last_block.as_ref().map(|b| b.time)
+        {
+            let migrated = wl_storage
+                .storage
+                .migrate_replay_protection(
+                    last_block_time
+                        + replay_protection::LEGACY_EXPIRATION_BOUND,
+                )
+                .expect("Failed replay protection migration");
+            if migrated > 0 {
+                tracing::info!(
+                    "Migrated {migrated} legacy replay protection entries"
+                );
+            }
+        }
         let mut shell = Self {
             chain_id,
             wl_storage,
@@ -1194,6 +1234,22 @@ where
                     return response;
                 }
 
+                // Tx expiration window
+                let last_block_timestamp = self
+                    .wl_storage
+                    .storage
+                    .get_last_block_timestamp()
+                    .expect("Failed to retrieve last block timestamp");
+                if let Err(e) = tx_expiration_window_check(
+                    &tx,
+                    last_block_timestamp,
+                    &self.wl_storage,
+                ) {
+                    response.code = ResultCode::InvalidExpiration.into();
+                    response.log = format!("{INVALID_MSG}: {e}");
+                    return response;
+                }
+
                 // Replay protection check
                 let inner_tx_hash = tx.raw_header_hash();
                 if self
@@ -1369,10 +1425,40 @@ where
 
     // Write wrapper hash to WAL
     temp_wl_storage
-        .write_tx_hash(wrapper_hash)
+        .write_tx_hash(wrapper_hash, wrapper.header.expiration)
         .map_err(|e| Error::ReplayAttempt(e.to_string()))
 }
 
+/// Checks that the wrapper has an expiration within the tx expiration window
+/// of the given block time, so that its replay protection entries can be
+/// pruned once it has expired. The wrapper timestamp is set by the client and
+/// is not trusted.
+pub fn tx_expiration_window_check<S>(
+    wrapper: &Tx,
+    block_time: DateTimeUtc,
+    storage: &S,
+) -> Result<()>
+where
+    S: StorageRead,
+{
+    let expiration = wrapper.header.expiration.ok_or_else(|| {
+        Error::InvalidExpiration(
+            "Wrapper transaction must have an expiration".to_string(),
+        )
+    })?;
+    let tx_expiration_window =
+        parameters::read_tx_expiration_window_parameter(storage)?;
+    let max_expiration = block_time + tx_expiration_window;
+    if expiration > max_expiration {
+        return Err(Error::InvalidExpiration(format!(
+            "Transaction expires at {expiration}, beyond the maximum allowed \
+             expiration {max_expiration}"
+        )));
+    }
+
+    Ok(())
+}
+
 // Perform the fee check in mempool
 fn mempool_fee_check<D, H, CA>(
     wrapper: &WrapperTx,
@@ -1520,6 +1606,7 @@ where
             vp_wasm_cache,
             tx_wasm_cache,
         ),
+        false,
     )
     .map_err(|e| {
         Error::TxApply(protocol::Error::FeeUnshieldingError(
@@ -1655,6 +1742,13 @@ mod test_utils {
                         .unwrap();
                 common::Signature::Secp256k1((&bytes).try_into().unwrap())
             }
+            common::Signature::Secp256r1(ref sig) => {
+                let mut sig_bytes = sig.serialize_to_vec();
+                sig_bytes[secp256r1::SIGNATURE_SIZE - 1] ^= 1;
+                common::Signature::Secp256r1(
+                    secp256r1::Signature::try_from_slice(&sig_bytes).unwrap(),
+                )
+            }
         }
     }
 
@@ -2100,6 +2194,8 @@ mod test_utils {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
 
@@ -2123,6 +2219,7 @@ mod test_utils {
                 min_duration: DurationSecs(3600),
             },
             max_expected_time_per_block: DurationSecs(3600),
+            tx_expiration_window: DurationSecs(86400),
             max_proposal_bytes: Default::default(),
             max_block_gas: 100,
             vp_whitelist: vec![],
@@ -2276,6 +2373,7 @@ mod shell_tests {
     use namada::types::ethereum_events::EthereumEvent;
     use namada::types::key::RefTo;
     use namada::types::storage::{BlockHeight, Epoch};
+    use namada::types::time::DurationSecs;
     use namada::vote_ext::{
         bridge_pool_roots, ethereum_events, ethereum_tx_data_variants,
     };
@@ -2601,6 +2699,8 @@ mod shell_tests {
                 None,
             ))));
         unsigned_wrapper.header.chain_id = shell.chain_id.clone();
+        unsigned_wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         unsigned_wrapper
             .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         unsigned_wrapper
@@ -2640,6 +2740,8 @@ mod shell_tests {
                 None,
             ))));
         invalid_wrapper.header.chain_id = shell.chain_id.clone();
+        invalid_wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         invalid_wrapper
             .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         invalid_wrapper
@@ -2712,6 +2814,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2727,7 +2831,7 @@ mod shell_tests {
         shell
             .wl_storage
             .storage
-            .write_replay_protection_entry(&mut batch, &wrapper_hash_key)
+            .write_replay_protection_entry(&mut batch, &wrapper_hash_key, b"")
             .expect("Test failed");
 
         // Try wrapper tx replay attack
@@ -2765,7 +2869,7 @@ mod shell_tests {
         shell
             .wl_storage
             .storage
-            .write_replay_protection_entry(&mut batch, &inner_hash_key)
+            .write_replay_protection_entry(&mut batch, &inner_hash_key, b"")
             .expect("Test failed");
 
         // Try inner tx replay attack
@@ -2846,6 +2950,50 @@ mod shell_tests {
         assert_eq!(result.code, ResultCode::ExpiredTx.into());
     }
 
+    /// Check that the expiration of a wrapper is checked against the block
+    /// time regardless of the timestamp set by the client
+    #[test]
+    fn test_tx_expiration_window() {
+        let (shell, _recv, _, _) = test_utils::setup();
+        let window =
+            parameters::read_tx_expiration_window_parameter(&shell.wl_storage)
+                .unwrap();
+        let block_time = DateTimeUtc::now();
+        let max_expiration = block_time + window;
+
+        // A wrapper without expiration is rejected
+        let tx = Tx::new(shell.chain_id.clone(), None);
+        assert!(matches!(
+            tx_expiration_window_check(&tx, block_time, &shell.wl_storage),
+            Err(Error::InvalidExpiration(_))
+        ));
+
+        // A wrapper expiring within the window is accepted, whatever its
+        // timestamp
+        let mut tx = Tx::new(shell.chain_id.clone(), Some(max_expiration));
+        for timestamp in
+            [DateTimeUtc::default(), block_time, max_expiration + window]
+        {
+            tx.header.timestamp = timestamp;
+            assert!(
+                tx_expiration_window_check(&tx, block_time, &shell.wl_storage)
+                    .is_ok()
+            );
+        }
+
+        // A wrapper expiring beyond the window is rejected, even with a
+        // timestamp in the past making it look short-lived
+        let mut tx = Tx::new(
+            shell.chain_id.clone(),
+            Some(max_expiration + DurationSecs(1)),
+        );
+        tx.header.timestamp = DateTimeUtc::default();
+        assert!(matches!(
+            tx_expiration_window_check(&tx, block_time, &shell.wl_storage),
+            Err(Error::InvalidExpiration(_))
+        ));
+    }
+
     /// Check that a tx requiring more gas than the block limit gets rejected
     #[test]
     fn test_exceeding_max_block_gas_tx() {
@@ -2867,6 +3015,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2900,6 +3050,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2939,6 +3091,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2974,6 +3128,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -3010,6 +3166,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -3046,6 +3204,8 @@ mod shell_tests {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -3093,6 +3253,8 @@ mod shell_tests {
                     None,
                 ))));
             wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper.header.expiration =
+                Some(DateTimeUtc::now() + DurationSecs(3600));
             wrapper
                 .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
             wrapper.set_data(Data::new(vec![0; size as usize]));
@@ -351,6 +351,18 @@ where
         let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
         tx_gas_meter.add_wrapper_gas(tx_bytes).map_err(|_| ())?;
 
+        // If time cannot be retrieved from block default to last block
+        // datetime, which only makes the check stricter
+        let block_time = match block_time {
+            Some(block_time) => block_time,
+            None => temp_wl_storage
+                .storage
+                .get_last_block_timestamp()
+                .map_err(|_| ())?,
+        };
+        super::tx_expiration_window_check(&tx, block_time, temp_wl_storage)
+            .map_err(|_| ())?;
+
         super::replay_protection_checks(&tx, temp_wl_storage)
             .map_err(|_| ())?;
 
@@ -449,6 +461,7 @@ mod test_prepare_proposal {
     use namada::types::ethereum_events::EthereumEvent;
     use namada::types::key::RefTo;
     use namada::types::storage::{BlockHeight, InnerEthEventsQueue};
+    use namada::types::time::DurationSecs;
     use namada::vote_ext::{ethereum_events, ethereum_tx_data_variants};
 
     use super::*;
@@ -485,6 +498,7 @@ mod test_prepare_proposal {
         let (shell, _recv, _, _) = test_utils::setup();
         let mut tx = Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
         tx.header.chain_id = shell.chain_id.clone();
+        tx.header.expiration = Some(DateTimeUtc::now() + DurationSecs(3600));
         let req = RequestPrepareProposal {
             txs: vec![tx.to_bytes().into()],
             ..Default::default()
@@ -514,6 +528,8 @@ mod test_prepare_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction_data".as_bytes().to_owned()));
         let wrapper = wrapper.to_bytes();
@@ -816,6 +832,8 @@ mod test_prepare_proposal {
                     None,
                 ))));
             tx.header.chain_id = shell.chain_id.clone();
+            tx.header.expiration =
+                Some(DateTimeUtc::now() + DurationSecs(3600));
             tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
             tx.set_data(Data::new(
                 format!("transaction data: {}", i).as_bytes().to_owned(),
@@ -885,6 +903,8 @@ mod test_prepare_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -930,6 +950,8 @@ mod test_prepare_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -967,6 +989,8 @@ mod test_prepare_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1013,6 +1037,8 @@ mod test_prepare_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         let tx_code = Code::new("wasm_code".as_bytes().to_owned(), None);
         wrapper.set_code(tx_code);
         let tx_data = Data::new("transaction data".as_bytes().to_owned());
@@ -1114,6 +1140,8 @@ mod test_prepare_proposal {
         );
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1154,6 +1182,8 @@ mod test_prepare_proposal {
 
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1210,6 +1240,8 @@ mod test_prepare_proposal {
 
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1258,6 +1290,8 @@ mod test_prepare_proposal {
 
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1308,6 +1342,8 @@ mod test_prepare_proposal {
         );
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1348,6 +1384,8 @@ mod test_prepare_proposal {
         );
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1389,6 +1427,8 @@ mod test_prepare_proposal {
         );
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
@@ -1430,6 +1470,8 @@ mod test_prepare_proposal {
         );
         let mut wrapper_tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
         wrapper_tx.header.chain_id = shell.chain_id.clone();
+        wrapper_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper_tx
             .set_data(Data::new("transaction data".as_bytes().to_owned()));
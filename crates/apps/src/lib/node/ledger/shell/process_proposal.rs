@@ -578,6 +578,18 @@ where
                     }
                 }
 
+                // Tx expiration window
+                if let Err(e) = super::tx_expiration_window_check(
+                    &tx,
+                    block_time,
+                    temp_wl_storage,
+                ) {
+                    return TxResult {
+                        code: ResultCode::InvalidExpiration.into(),
+                        info: e.to_string(),
+                    };
+                }
+
                 // Replay protection checks
                 if let Err(e) =
                     super::replay_protection_checks(&tx, temp_wl_storage)
@@ -678,7 +690,7 @@ mod test_process_proposal {
     use namada::types::ethereum_events::EthereumEvent;
     use namada::types::key::*;
     use namada::types::storage::Epoch;
-    use namada::types::time::DateTimeUtc;
+    use namada::types::time::{DateTimeUtc, DurationSecs};
     use namada::vote_ext::{
         bridge_pool_roots, ethereum_events, EthereumTxData,
     };
@@ -924,6 +936,8 @@ mod test_process_proposal {
                 None,
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
+        outer_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         outer_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         outer_tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
 
@@ -976,6 +990,8 @@ mod test_process_proposal {
                 None,
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
+        outer_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         outer_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         outer_tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
         outer_tx.add_section(Section::Signature(Signature::new(
@@ -1050,6 +1066,8 @@ mod test_process_proposal {
                 None,
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
+        outer_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         outer_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         outer_tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
         outer_tx.add_section(Section::Signature(Signature::new(
@@ -1118,6 +1136,8 @@ mod test_process_proposal {
                 None,
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
+        outer_tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         outer_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         outer_tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
         outer_tx.add_section(Section::Signature(Signature::new(
@@ -1176,6 +1196,8 @@ mod test_process_proposal {
                     None,
                 ))));
             outer_tx.header.chain_id = shell.chain_id.clone();
+            outer_tx.header.expiration =
+                Some(DateTimeUtc::now() + DurationSecs(3600));
             outer_tx
                 .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
             outer_tx.set_data(Data::new(
@@ -1237,6 +1259,7 @@ mod test_process_proposal {
             None,
         ))));
         tx.header.chain_id = shell.chain_id.clone();
+        tx.header.expiration = Some(DateTimeUtc::now() + DurationSecs(3600));
         tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
         let gas_limit = Gas::from(tx.header().wrapper().unwrap().gas_limit)
@@ -1314,6 +1337,7 @@ mod test_process_proposal {
         let (shell, _recv, _, _) = test_utils::setup_at_height(3u64);
         let mut tx = Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
         tx.header.chain_id = shell.chain_id.clone();
+        tx.header.expiration = Some(DateTimeUtc::now() + DurationSecs(3600));
         tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
 
@@ -1398,6 +1422,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1413,7 +1439,7 @@ mod test_process_proposal {
         shell
             .wl_storage
             .storage
-            .write_replay_protection_entry(&mut batch, &hash_key)
+            .write_replay_protection_entry(&mut batch, &hash_key, b"")
             .expect("Test failed");
 
         // Run validation
@@ -1470,6 +1496,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1524,6 +1552,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1538,7 +1568,7 @@ mod test_process_proposal {
         shell
             .wl_storage
             .storage
-            .write_replay_protection_entry(&mut batch, &hash_key)
+            .write_replay_protection_entry(&mut batch, &hash_key, b"")
             .expect("Test failed");
 
         // Run validation
@@ -1585,6 +1615,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         let mut new_wrapper = wrapper.clone();
@@ -1798,6 +1830,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1840,6 +1874,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1888,6 +1924,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1931,6 +1969,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -1976,6 +2016,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2021,6 +2063,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2067,6 +2111,8 @@ mod test_process_proposal {
                 None,
             ))));
         wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(3600));
         wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
         wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
         wrapper.add_section(Section::Signature(Signature::new(
@@ -2136,6 +2182,8 @@ mod test_process_proposal {
                     None,
                 ))));
             wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper.header.expiration =
+                Some(DateTimeUtc::now() + DurationSecs(3600));
             wrapper
                 .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
             wrapper.set_data(Data::new(vec![0; size as usize]));
@@ -27,7 +27,10 @@ where
         };
 
         // Invoke the root RPC handler - returns borsh-encoded data on success
-        let result = if query.path == "/shell/dry_run_tx" {
+        let result = if matches!(
+            query.path.as_str(),
+            "/shell/dry_run_tx" | "/shell/dry_run_tx_trace"
+        ) {
             dry_run_tx(ctx, &query)
         } else {
             namada::ledger::queries::handle_path(ctx, &query)
@@ -2,6 +2,7 @@ use clap::Command as App;
 use eyre::Report;
 use namada::types::io::Io;
 use namada_sdk::error::Error as SdkError;
+use namada_sdk::Namada;
 use tendermint_config::net::Address as TendermintAddress;
 
 use super::node::MockNode;
@@ -10,20 +11,30 @@ use crate::cli::args::Global;
 use crate::cli::{args, cmds, Cmd, Context, NamadaClient, NamadaRelayer};
 use crate::node::ledger::shell::testing::utils::{Bin, TestingIo};
 
+/// The global arguments of the commands run against the given node
+fn global_args(node: &MockNode) -> Global {
+    let locked = node.shell.lock().unwrap();
+    Global {
+        is_pre_genesis: false,
+        chain_id: Some(locked.chain_id.clone()),
+        base_dir: locked.base_dir.clone(),
+        wasm_dir: Some(locked.wasm_dir.clone()),
+    }
+}
+
+/// Make an SDK context using the wallet and shielded context of the client
+/// of the given node
+pub fn sdk_context(node: &MockNode) -> Result<impl Namada + '_, Report> {
+    let ctx = Context::new::<TestingIo>(global_args(node))?;
+    Ok(ctx.to_sdk(node, TestingIo))
+}
+
 pub fn run(
     node: &MockNode,
     who: Bin,
     mut args: Vec<&str>,
 ) -> Result<(), Report> {
-    let global = {
-        let locked = node.shell.lock().unwrap();
-        Global {
-            is_pre_genesis: false,
-            chain_id: Some(locked.chain_id.clone()),
-            base_dir: locked.base_dir.clone(),
-            wasm_dir: Some(locked.wasm_dir.clone()),
-        }
-    };
+    let global = global_args(node);
     let ctx = Context::new::<TestingIo>(global.clone())?;
 
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -696,7 +696,10 @@ impl<'a> Client for &'a MockNode {
             tx_wasm_cache: borrowed.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: None,
         };
-        if request.path == "/shell/dry_run_tx" {
+        if matches!(
+            request.path.as_str(),
+            "/shell/dry_run_tx" | "/shell/dry_run_tx_trace"
+        ) {
             dry_run_tx(ctx, &request)
         } else {
             rpc.handle(ctx, &request)
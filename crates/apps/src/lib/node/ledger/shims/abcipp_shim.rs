@@ -26,6 +26,7 @@ use crate::facade::tendermint::v0_37::abci::{
 use crate::facade::tendermint_proto::v0_37::abci::ResponseDeliverTx;
 use crate::facade::tower_abci::BoxError;
 use crate::node::ledger::shell::{EthereumOracleChannels, Shell};
+use crate::node::ledger::storage::PersistentDBCache;
 
 /// The shim wraps the shell, which implements ABCI++.
 /// The shim makes a crude translation between the ABCI interface currently used
@@ -50,7 +51,7 @@ impl AbcippShim {
         wasm_dir: PathBuf,
         broadcast_sender: UnboundedSender<Vec<u8>>,
         eth_oracle: Option<EthereumOracleChannels>,
-        db_cache: &rocksdb::Cache,
+        db_cache: &PersistentDBCache,
         vp_wasm_compilation_cache: u64,
         tx_wasm_compilation_cache: u64,
     ) -> (Self, AbciService, broadcast::Sender<()>) {
@@ -0,0 +1,1805 @@
+//! The persistent storage in LMDB.
+//!
+//! The storage tree is the same as the one of the RocksDB backend, with a named
+//! LMDB database in place of each column family:
+//! - `state`: the latest ledger state
+//! - `subspace`: accounts sub-spaces
+//! - `diffs`: diffs in account subspaces' key-vals
+//! - `block`: block state
+//! - `replay_protection`: hashes of processed tx
+//!
+//! The pruned diffs are deleted in chunks by a background thread, so that the
+//! commits of the blocks don't have to wait for a single long write
+//! transaction.
+//!
+//! Unlike RocksDB, LMDB limits the size of the keys to [`MAX_KEY_SIZE`] bytes.
+//! The key-vals with longer keys are stored in a second database of their
+//! table, grouped into sorted maps under the first [`MAX_KEY_SIZE`] bytes of
+//! their keys. As these heads have the same size, the order of the groups is
+//! the order of their keys, and the prefix iterators merge both databases in
+//! the order of the keys, like RocksDB.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Bound;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+use heed::types::{Bytes, Str};
+use heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn};
+use itertools::{Either, Itertools};
+use namada::eth_bridge::storage::proof::BridgePoolRootProof;
+use namada::ledger::eth_bridge::storage::bridge_pool;
+use namada::ledger::replay_protection;
+use namada::state::merkle_tree::{base_tree_key_prefix, subtree_key_prefix};
+use namada::state::{
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
+    DbResult as Result, MerkleTreeStoresRead, StoreType, DB,
+};
+use namada::types;
+use namada::types::ethereum_events;
+use namada::types::storage::{
+    BlockHeight, Epoch, Header, Key, KeySeg, KEY_SEGMENT_SEPARATOR,
+};
+
+/// Env. var to set the maximum size of the LMDB memory map in bytes
+const ENV_VAR_LMDB_MAP_SIZE: &str = "NAMADA_LMDB_MAP_SIZE";
+
+/// The default maximum size of the memory map, which bounds the size of the
+/// DB. The map is sparse, so this doesn't reserve any disk space.
+const DEFAULT_MAP_SIZE: usize = 1 << 40;
+
+/// The maximum size of a key in bytes, fixed when LMDB is built
+const MAX_KEY_SIZE: usize = 511;
+
+/// The key-vals whose keys are longer than [`MAX_KEY_SIZE`] and share the same
+/// first [`MAX_KEY_SIZE`] bytes
+type LongKeys = BTreeMap<String, Vec<u8>>;
+
+/// The number of key-vals read from the DB at once by the prefix iterators
+const ITER_PAGE_SIZE: usize = 1024;
+
+/// The number of diffs deleted in a single write transaction by the pruner
+const PRUNE_CHUNK_SIZE: usize = 1024;
+
+/// The named databases of the environment, replacing RocksDB's column families
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Table {
+    Subspace,
+    Diffs,
+    State,
+    Block,
+    ReplayProtection,
+}
+
+impl Table {
+    const ALL: [Table; 5] = [
+        Table::Subspace,
+        Table::Diffs,
+        Table::State,
+        Table::Block,
+        Table::ReplayProtection,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Table::Subspace => "subspace",
+            Table::Diffs => "diffs",
+            Table::State => "state",
+            Table::Block => "block",
+            Table::ReplayProtection => "replay_protection",
+        }
+    }
+}
+
+/// LMDB handle
+pub struct LmdbDB {
+    env: Env,
+    tables: [Database<Str, Bytes>; 5],
+    /// The key-vals of each table with keys longer than [`MAX_KEY_SIZE`],
+    /// grouped by the head of their keys
+    long_keys: [Database<Bytes, Bytes>; 5],
+    /// Started on the first pruning of the diffs
+    pruner: Option<DiffsPruner>,
+}
+
+/// DB Handle for batch writes. The operations are applied in order in a
+/// single write transaction.
+#[derive(Default)]
+pub struct LmdbWriteBatch(Vec<(Table, String, Option<Vec<u8>>)>);
+
+impl LmdbWriteBatch {
+    fn put(
+        &mut self,
+        table: Table,
+        key: impl Into<String>,
+        value: impl AsRef<[u8]>,
+    ) {
+        self.0
+            .push((table, key.into(), Some(value.as_ref().to_vec())));
+    }
+
+    fn delete(&mut self, table: Table, key: impl Into<String>) {
+        self.0.push((table, key.into(), None));
+    }
+}
+
+/// Open LMDB for the DB
+pub fn open(path: impl AsRef<Path>) -> Result<LmdbDB> {
+    let path = path.as_ref();
+    std::fs::create_dir_all(path).map_err(|e| Error::DBError(e.to_string()))?;
+    let map_size = match std::env::var(ENV_VAR_LMDB_MAP_SIZE) {
+        Ok(size) => size.parse().map_err(|e| {
+            Error::DBError(format!(
+                "Invalid {ENV_VAR_LMDB_MAP_SIZE} value \"{size}\": {e}"
+            ))
+        })?,
+        Err(_) => DEFAULT_MAP_SIZE,
+    };
+
+    // Safety: the environment is opened only once by the ledger process, which
+    // doesn't modify the files of the DB other than through LMDB
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(map_size)
+            .max_dbs(2 * Table::ALL.len() as u32)
+            .open(path)
+    }
+    .map_err(db_error)?;
+
+    let mut wtxn = env.write_txn().map_err(db_error)?;
+    let mut create = |table: Table| {
+        env.create_database(&mut wtxn, Some(table.name()))
+            .map_err(db_error)
+    };
+    // In the order of the `Table` variants
+    let tables = [
+        create(Table::Subspace)?,
+        create(Table::Diffs)?,
+        create(Table::State)?,
+        create(Table::Block)?,
+        create(Table::ReplayProtection)?,
+    ];
+    let mut create_long_keys = |table: Table| {
+        let name = format!("{}_long_keys", table.name());
+        env.create_database(&mut wtxn, Some(&name))
+            .map_err(db_error)
+    };
+    let long_keys = [
+        create_long_keys(Table::Subspace)?,
+        create_long_keys(Table::Diffs)?,
+        create_long_keys(Table::State)?,
+        create_long_keys(Table::Block)?,
+        create_long_keys(Table::ReplayProtection)?,
+    ];
+    wtxn.commit().map_err(db_error)?;
+
+    let mut db = LmdbDB {
+        env,
+        tables,
+        long_keys,
+        pruner: None,
+    };
+    // Resume the deletion of the diffs that might have been interrupted
+    if let Some(pruning_height) = db.read_diffs_pruning_height()? {
+        db.pruner_handle()?.prune(pruning_height);
+    }
+    Ok(db)
+}
+
+impl fmt::Debug for LmdbDB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LmdbDB")
+            .field("path", &self.env.path())
+            .finish()
+    }
+}
+
+impl LmdbDB {
+    fn table(&self, table: Table) -> Database<Str, Bytes> {
+        self.tables[table as usize]
+    }
+
+    fn long_keys(&self, table: Table) -> Database<Bytes, Bytes> {
+        self.long_keys[table as usize]
+    }
+
+    /// Get the pruner of the diffs, starting it if it's not running yet
+    fn pruner_handle(&mut self) -> Result<&DiffsPruner> {
+        if self.pruner.is_none() {
+            let pruner = DiffsPruner::spawn(
+                self.env.clone(),
+                self.table(Table::Diffs),
+                self.long_keys(Table::Diffs),
+            )?;
+            self.pruner = Some(pruner);
+        }
+        Ok(self.pruner.as_ref().expect("The pruner must be running"))
+    }
+
+    fn get(&self, table: Table, key: &str) -> Result<Option<Vec<u8>>> {
+        let rtxn = self.env.read_txn().map_err(db_error)?;
+        if key.len() > MAX_KEY_SIZE {
+            let mut long_keys =
+                read_long_keys(self.long_keys(table), &rtxn, key)?;
+            return Ok(long_keys.remove(key));
+        }
+        let value = self
+            .table(table)
+            .get(&rtxn, key)
+            .map_err(db_error)?
+            .map(<[u8]>::to_vec);
+        Ok(value)
+    }
+
+    /// Read and decode a value that must be present in a stored block state,
+    /// logging an error if it's missing
+    fn read_block_state_val<T: BorshDeserialize>(
+        &self,
+        table: Table,
+        key: &str,
+    ) -> Result<Option<T>> {
+        match self.get(table, key)? {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => {
+                tracing::error!("Couldn't load {key} from the DB");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read up to [`ITER_PAGE_SIZE`] key-vals of the given table whose keys
+    /// start with the given prefix and come after the given key, if any, in
+    /// the order of their keys.
+    fn read_page(
+        &self,
+        table: Table,
+        prefix: &str,
+        after: Option<&str>,
+    ) -> Result<VecDeque<(String, Vec<u8>)>> {
+        let rtxn = self.env.read_txn().map_err(db_error)?;
+
+        let mut page = Vec::new();
+        // The keys that fit in LMDB can't start with a longer prefix
+        if prefix.len() <= MAX_KEY_SIZE {
+            let start = match after {
+                Some(key) if key.len() <= MAX_KEY_SIZE => Bound::Excluded(key),
+                Some(key) => Bound::Included(truncate_key(key)),
+                None => Bound::Included(prefix),
+            };
+            let range = (start, Bound::Unbounded);
+            for entry in
+                self.table(table).range(&rtxn, &range).map_err(db_error)?
+            {
+                let (key, val) = entry.map_err(db_error)?;
+                if !key.starts_with(prefix) || page.len() == ITER_PAGE_SIZE {
+                    break;
+                }
+                if after.map_or(true, |after| key > after) {
+                    page.push((key.to_owned(), val.to_vec()));
+                }
+            }
+        }
+
+        let mut long_page = Vec::new();
+        let head = long_key_head(prefix);
+        let start = long_key_head(after.unwrap_or(prefix));
+        let range = (Bound::Included(start), Bound::Unbounded);
+        'groups: for entry in self
+            .long_keys(table)
+            .range(&rtxn, &range)
+            .map_err(db_error)?
+        {
+            let (group_head, group) = entry.map_err(db_error)?;
+            if !group_head.starts_with(head) {
+                break;
+            }
+            let group: LongKeys =
+                types::decode(group).map_err(Error::CodingError)?;
+            for (key, val) in group {
+                if long_page.len() == ITER_PAGE_SIZE {
+                    break 'groups;
+                }
+                if key.starts_with(prefix)
+                    && after.map_or(true, |after| key.as_str() > after)
+                {
+                    long_page.push((key, val));
+                }
+            }
+        }
+
+        Ok(page
+            .into_iter()
+            .merge_by(long_page, |(key, _), (other_key, _)| key < other_key)
+            .take(ITER_PAGE_SIZE)
+            .collect())
+    }
+
+    /// Persist the diff of an account subspace key-val under the height where
+    /// it was changed in a batch write.
+    fn batch_write_subspace_diff(
+        batch: &mut LmdbWriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        old_value: Option<&[u8]>,
+        new_value: Option<&[u8]>,
+    ) -> Result<()> {
+        let key_prefix = Key::from(height.to_db_key());
+
+        if let Some(old_value) = old_value {
+            let old_val_key = key_prefix
+                .push(&"old".to_owned())
+                .map_err(Error::KeyError)?
+                .join(key)
+                .to_string();
+            batch.put(Table::Diffs, old_val_key, old_value);
+        }
+
+        if let Some(new_value) = new_value {
+            let new_val_key = key_prefix
+                .push(&"new".to_owned())
+                .map_err(Error::KeyError)?
+                .join(key)
+                .to_string();
+            batch.put(Table::Diffs, new_val_key, new_value);
+        }
+        Ok(())
+    }
+
+    /// Dump last known block
+    pub fn dump_block(
+        &self,
+        out_file_path: std::path::PathBuf,
+        historic: bool,
+        height: Option<BlockHeight>,
+    ) {
+        // Find the last block height
+        let last_height: BlockHeight = types::decode(
+            self.get(Table::State, "height")
+                .expect("Unable to read DB")
+                .expect("No block height found"),
+        )
+        .expect("Unable to decode block height");
+
+        let height = height.unwrap_or(last_height);
+
+        let full_path = out_file_path
+            .with_file_name(format!(
+                "{}_{height}",
+                out_file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "dump_db".to_string())
+            ))
+            .with_extension("toml");
+
+        let mut file = File::options()
+            .append(true)
+            .create_new(true)
+            .open(&full_path)
+            .expect("Cannot open the output file");
+
+        println!("Will write to {} ...", full_path.to_string_lossy());
+
+        if historic {
+            // Dump the keys prepended with the selected block height (includes
+            // subspace diff keys)
+            let prefix = format!("{}/", height.raw());
+            self.dump_it(Table::Diffs, &prefix, &mut file);
+            self.dump_it(Table::Block, &prefix, &mut file);
+        }
+
+        // subspace
+        if height != last_height {
+            // Restoring subspace at specified height
+            let mut buf = BufWriter::new(&mut file);
+            for (key, _value, _gas) in self.iter_prefix(None) {
+                let value = self
+                    .read_subspace_val_with_height(
+                        &Key::from(key.to_db_key()),
+                        height,
+                        last_height,
+                    )
+                    .expect("Unable to find subspace key");
+                if let Some(value) = value {
+                    let val = HEXLOWER.encode(&value);
+                    let bytes = format!("\"{key}\" = \"{val}\"\n");
+                    buf.write_all(bytes.as_bytes())
+                        .expect("Unable to write to buffer");
+                }
+            }
+            buf.flush().expect("Unable to write to output file");
+        } else {
+            // Just dump the current subspace
+            self.dump_it(Table::Subspace, "", &mut file);
+        }
+
+        // replay protection
+        // Dump of replay protection keys is possible only at the last height or
+        // the previous one
+        if height == last_height {
+            self.dump_it(Table::ReplayProtection, "", &mut file);
+        } else if height == last_height - 1 {
+            self.dump_it(Table::ReplayProtection, "all", &mut file);
+        }
+
+        println!("Done writing to {}", full_path.to_string_lossy());
+    }
+
+    /// Dump data
+    fn dump_it(&self, table: Table, prefix: &str, file: &mut File) {
+        let mut buf = BufWriter::new(file);
+        // Empty stripped prefix to keep the full keys
+        for (key, raw_val, _gas) in
+            LmdbPrefixIterator::new(self, table, prefix.to_owned(), "")
+        {
+            let val = HEXLOWER.encode(&raw_val);
+            let bytes = format!("\"{key}\" = \"{val}\"\n");
+            buf.write_all(bytes.as_bytes())
+                .expect("Unable to write to buffer");
+        }
+        buf.flush().expect("Unable to write to output file");
+    }
+
+    /// Rollback to previous block. Given the inner working of tendermint
+    /// rollback and of the key structure of Namada, calling rollback more than
+    /// once without restarting the chain results in a single rollback.
+    pub fn rollback(
+        &mut self,
+        tendermint_block_height: BlockHeight,
+    ) -> Result<()> {
+        let last_block = self.read_last_block()?.ok_or(Error::DBError(
+            "Missing last block in storage".to_string(),
+        ))?;
+        tracing::info!(
+            "Namada last block height: {}, Tendermint last block height: {}",
+            last_block.height,
+            tendermint_block_height
+        );
+
+        // If the block height to which tendermint rolled back matches the
+        // Namada height, there's no need to rollback
+        if tendermint_block_height == last_block.height {
+            tracing::info!(
+                "Namada height already matches the rollback Tendermint \
+                 height, no need to rollback."
+            );
+            return Ok(());
+        }
+
+        let mut batch = LmdbWriteBatch::default();
+        let previous_height =
+            BlockHeight::from(u64::from(last_block.height) - 1);
+
+        // Revert the non-height-prepended metadata storage keys which get
+        // updated with every block. Because of the way we save these
+        // three keys in storage we can only perform one rollback before
+        // restarting the chain
+        tracing::info!("Reverting non-height-prepended metadata keys");
+        batch.put(Table::State, "height", types::encode(&previous_height));
+        for metadata_key in [
+            "next_epoch_min_start_height",
+            "next_epoch_min_start_time",
+            "tx_queue",
+        ] {
+            let previous_key = format!("pred/{}", metadata_key);
+            let previous_value = self
+                .get(Table::State, &previous_key)?
+                .ok_or(Error::UnknownKey { key: previous_key })?;
+            batch.put(Table::State, metadata_key, previous_value);
+        }
+
+        // Revert conversion state if the epoch had been changed
+        if last_block.pred_epochs.get_epoch(previous_height)
+            != Some(last_block.epoch)
+        {
+            let previous_key = "pred/conversion_state".to_string();
+            let previous_value = self
+                .get(Table::State, &previous_key)?
+                .ok_or(Error::UnknownKey { key: previous_key })?;
+            batch.put(Table::State, "conversion_state", previous_value);
+        }
+
+        // Delete block results for the last block
+        tracing::info!("Removing last block results");
+        batch.delete(Table::Block, format!("results/{}", last_block.height));
+
+        // Delete the tx hashes included in the last block
+        tracing::info!("Removing replay protection hashes");
+        for (hash, _, _) in self.iter_replay_protection() {
+            batch.delete(
+                Table::ReplayProtection,
+                replay_protection::last_prefix()
+                    .push(&hash)
+                    .map_err(Error::KeyError)?
+                    .to_string(),
+            );
+        }
+
+        tracing::info!("Restoring previous height subspace diffs");
+        for (key, _value, _gas) in self.iter_prefix(None) {
+            // Restore previous height diff if present, otherwise delete the
+            // subspace key
+            match self.read_subspace_val_with_height(
+                &Key::from(key.to_db_key()),
+                previous_height,
+                last_block.height,
+            )? {
+                Some(previous_value) => {
+                    batch.put(Table::Subspace, key, previous_value)
+                }
+                None => batch.delete(Table::Subspace, key),
+            }
+        }
+
+        // Look for diffs in this block to find what has been deleted
+        let diff_new_key_prefix = Key {
+            segments: vec![
+                last_block.height.to_db_key(),
+                "new".to_string().to_db_key(),
+            ],
+        };
+        for (key, val, _) in self.iter_old_diffs(last_block.height, None) {
+            let key = Key::parse(key).map_err(Error::KeyError)?;
+            let diff_new_key = diff_new_key_prefix.join(&key);
+            if self.get(Table::Diffs, &diff_new_key.to_string())?.is_none() {
+                // If there is no new value, it has been deleted in this
+                // block and we have to restore it
+                batch.put(Table::Subspace, key.to_string(), val)
+            }
+        }
+
+        tracing::info!("Deleting keys prepended with the last height");
+        let prefix = format!("{}/", last_block.height);
+        // Delete any height-prepended key in subspace diffs and in the block
+        for table in [Table::Diffs, Table::Block] {
+            for (key, _value, _gas) in
+                LmdbPrefixIterator::new(self, table, prefix.clone(), "")
+            {
+                batch.delete(table, key);
+            }
+        }
+
+        // Write the batch and persist changes to disk
+        tracing::info!("Flushing restored state to disk");
+        self.exec_batch(batch)
+    }
+}
+
+impl DB for LmdbDB {
+    type Cache = ();
+    type WriteBatch = LmdbWriteBatch;
+
+    fn open(
+        db_path: impl AsRef<std::path::Path>,
+        _cache: Option<&Self::Cache>,
+    ) -> Self {
+        open(db_path).expect("cannot open the DB")
+    }
+
+    fn flush(&self, _wait: bool) -> Result<()> {
+        self.env.force_sync().map_err(db_error)
+    }
+
+    fn read_last_block(&self) -> Result<Option<BlockStateRead>> {
+        // Block height
+        let height: BlockHeight = match self.get(Table::State, "height")? {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(None),
+        };
+
+        // Block results
+        let results_path = format!("results/{}", height.raw());
+        let results = match self.get(Table::Block, &results_path)? {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(None),
+        };
+
+        let Some(next_epoch_min_start_height) = self.read_block_state_val(
+            Table::State,
+            "next_epoch_min_start_height",
+        )?
+        else {
+            return Ok(None);
+        };
+        let Some(next_epoch_min_start_time) = self
+            .read_block_state_val(Table::State, "next_epoch_min_start_time")?
+        else {
+            return Ok(None);
+        };
+        let Some(update_epoch_blocks_delay) = self
+            .read_block_state_val(Table::State, "update_epoch_blocks_delay")?
+        else {
+            return Ok(None);
+        };
+        let Some(conversion_state) =
+            self.read_block_state_val(Table::State, "conversion_state")?
+        else {
+            return Ok(None);
+        };
+        let Some(tx_queue) =
+            self.read_block_state_val(Table::State, "tx_queue")?
+        else {
+            return Ok(None);
+        };
+        let Some(ethereum_height) =
+            self.read_block_state_val(Table::State, "ethereum_height")?
+        else {
+            return Ok(None);
+        };
+        let Some(eth_events_queue) =
+            self.read_block_state_val(Table::State, "eth_events_queue")?
+        else {
+            return Ok(None);
+        };
+
+        // Load data at the height
+        let prefix = format!("{}/", height.raw());
+        let mut merkle_tree_stores = MerkleTreeStoresRead::default();
+        let mut hash = None;
+        let mut time = None;
+        let mut epoch: Option<Epoch> = None;
+        let mut pred_epochs = None;
+        let mut address_gen = None;
+        for (path, bytes, _gas) in
+            LmdbPrefixIterator::new(self, Table::Block, prefix, "")
+        {
+            let segments: Vec<&str> =
+                path.split(KEY_SEGMENT_SEPARATOR).collect();
+            match segments.get(1) {
+                Some(prefix) => match *prefix {
+                    // Restore the base tree of Merkle tree
+                    "tree" => match segments.get(2) {
+                        Some(s) => {
+                            let st = StoreType::from_str(s)?;
+                            match segments.get(3) {
+                                Some(&"root") => merkle_tree_stores.set_root(
+                                    &st,
+                                    types::decode(bytes)
+                                        .map_err(Error::CodingError)?,
+                                ),
+                                Some(&"store") => merkle_tree_stores
+                                    .set_store(st.decode_store(bytes)?),
+                                _ => unknown_key_error(&path)?,
+                            }
+                        }
+                        None => unknown_key_error(&path)?,
+                    },
+                    "header" => {
+                        // the block header doesn't have to be restored
+                    }
+                    "hash" => {
+                        hash = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "time" => {
+                        time = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "epoch" => {
+                        epoch = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "pred_epochs" => {
+                        pred_epochs = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "address_gen" => {
+                        address_gen = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        );
+                    }
+                    _ => unknown_key_error(&path)?,
+                },
+                None => unknown_key_error(&path)?,
+            }
+        }
+        // Restore subtrees of Merkle tree
+        if let Some(epoch) = epoch {
+            for st in StoreType::iter_subtrees() {
+                let key_prefix = subtree_key_prefix(st, epoch);
+                let root_key =
+                    key_prefix.clone().with_segment("root".to_owned());
+                if let Some(bytes) =
+                    self.get(Table::Block, &root_key.to_string())?
+                {
+                    merkle_tree_stores.set_root(
+                        st,
+                        types::decode(bytes).map_err(Error::CodingError)?,
+                    );
+                }
+                let store_key = key_prefix.with_segment("store".to_owned());
+                if let Some(bytes) =
+                    self.get(Table::Block, &store_key.to_string())?
+                {
+                    merkle_tree_stores.set_store(st.decode_store(bytes)?);
+                }
+            }
+        }
+        match (hash, time, epoch, pred_epochs, address_gen) {
+            (
+                Some(hash),
+                Some(time),
+                Some(epoch),
+                Some(pred_epochs),
+                Some(address_gen),
+            ) => Ok(Some(BlockStateRead {
+                merkle_tree_stores,
+                hash,
+                height,
+                time,
+                epoch,
+                pred_epochs,
+                results,
+                conversion_state,
+                next_epoch_min_start_height,
+                next_epoch_min_start_time,
+                update_epoch_blocks_delay,
+                address_gen,
+                tx_queue,
+                ethereum_height,
+                eth_events_queue,
+            })),
+            _ => Err(Error::Temporary {
+                error: "Essential data couldn't be read from the DB"
+                    .to_string(),
+            }),
+        }
+    }
+
+    fn add_block_to_batch(
+        &self,
+        state: BlockStateWrite,
+        batch: &mut Self::WriteBatch,
+        is_full_commit: bool,
+    ) -> Result<()> {
+        let BlockStateWrite {
+            merkle_tree_stores,
+            header,
+            hash,
+            height,
+            time,
+            epoch,
+            pred_epochs,
+            next_epoch_min_start_height,
+            next_epoch_min_start_time,
+            update_epoch_blocks_delay,
+            address_gen,
+            results,
+            conversion_state,
+            tx_queue,
+            ethereum_height,
+            eth_events_queue,
+        }: BlockStateWrite = state;
+
+        // Write the predecessor values for rollback
+        let mut write_with_pred = |key: &str, value: Vec<u8>| -> Result<()> {
+            if let Some(current_value) = self.get(Table::State, key)? {
+                batch.put(Table::State, format!("pred/{key}"), current_value);
+            }
+            batch.put(Table::State, key, value);
+            Ok(())
+        };
+        write_with_pred(
+            "next_epoch_min_start_height",
+            types::encode(&next_epoch_min_start_height),
+        )?;
+        write_with_pred(
+            "next_epoch_min_start_time",
+            types::encode(&next_epoch_min_start_time),
+        )?;
+        write_with_pred(
+            "update_epoch_blocks_delay",
+            types::encode(&update_epoch_blocks_delay),
+        )?;
+        // Save the conversion state when the epoch is updated
+        if is_full_commit {
+            write_with_pred(
+                "conversion_state",
+                types::encode(conversion_state),
+            )?;
+        }
+        write_with_pred("tx_queue", types::encode(&tx_queue))?;
+        batch.put(
+            Table::State,
+            "ethereum_height",
+            types::encode(&ethereum_height),
+        );
+        batch.put(
+            Table::State,
+            "eth_events_queue",
+            types::encode(&eth_events_queue),
+        );
+
+        let prefix_key = Key::from(height.to_db_key());
+        // Merkle tree
+        for st in StoreType::iter() {
+            if *st == StoreType::Base || is_full_commit {
+                let key_prefix = if *st == StoreType::Base {
+                    base_tree_key_prefix(height)
+                } else {
+                    subtree_key_prefix(st, epoch)
+                };
+                let root_key =
+                    key_prefix.clone().with_segment("root".to_owned());
+                batch.put(
+                    Table::Block,
+                    root_key.to_string(),
+                    types::encode(merkle_tree_stores.root(st)),
+                );
+                let store_key = key_prefix.with_segment("store".to_owned());
+                batch.put(
+                    Table::Block,
+                    store_key.to_string(),
+                    merkle_tree_stores.store(st).encode(),
+                );
+            }
+        }
+        // Block header
+        if let Some(h) = header {
+            let key = prefix_key
+                .push(&"header".to_owned())
+                .map_err(Error::KeyError)?;
+            batch.put(Table::Block, key.to_string(), h.serialize_to_vec());
+        }
+        // Block hash, time, epoch, predecessor epochs and address gen
+        for (name, value) in [
+            ("hash", types::encode(&hash)),
+            ("time", types::encode(&time)),
+            ("epoch", types::encode(&epoch)),
+            ("pred_epochs", types::encode(&pred_epochs)),
+            ("address_gen", types::encode(&address_gen)),
+        ] {
+            let key =
+                prefix_key.push(&name.to_owned()).map_err(Error::KeyError)?;
+            batch.put(Table::Block, key.to_string(), value);
+        }
+        // Block results
+        batch.put(
+            Table::Block,
+            format!("results/{}", height.raw()),
+            types::encode(&results),
+        );
+
+        // Block height
+        batch.put(Table::State, "height", types::encode(&height));
+
+        Ok(())
+    }
+
+    fn read_block_header(&self, height: BlockHeight) -> Result<Option<Header>> {
+        let prefix_key = Key::from(height.to_db_key());
+        let key = prefix_key
+            .push(&"header".to_owned())
+            .map_err(Error::KeyError)?;
+        match self.get(Table::Block, &key.to_string())? {
+            Some(v) => Ok(Some(
+                Header::try_from_slice(&v[..])
+                    .map_err(Error::BorshCodingError)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn read_merkle_tree_stores(
+        &self,
+        epoch: Epoch,
+        base_height: BlockHeight,
+        store_type: Option<StoreType>,
+    ) -> Result<Option<MerkleTreeStoresRead>> {
+        let mut merkle_tree_stores = MerkleTreeStoresRead::default();
+        let store_types = store_type
+            .as_ref()
+            .map(|st| Either::Left(std::iter::once(st)))
+            .unwrap_or_else(|| Either::Right(StoreType::iter()));
+        for st in store_types {
+            let key_prefix = if *st == StoreType::Base {
+                base_tree_key_prefix(base_height)
+            } else {
+                subtree_key_prefix(st, epoch)
+            };
+            let root_key = key_prefix.clone().with_segment("root".to_owned());
+            match self.get(Table::Block, &root_key.to_string())? {
+                Some(b) => {
+                    let root = types::decode(b).map_err(Error::CodingError)?;
+                    merkle_tree_stores.set_root(st, root);
+                }
+                None => return Ok(None),
+            }
+
+            let store_key = key_prefix.with_segment("store".to_owned());
+            match self.get(Table::Block, &store_key.to_string())? {
+                Some(b) => {
+                    merkle_tree_stores.set_store(st.decode_store(b)?);
+                }
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(merkle_tree_stores))
+    }
+
+    fn has_replay_protection_entry(
+        &self,
+        hash: &namada::types::hash::Hash,
+    ) -> Result<bool> {
+        for key in [
+            replay_protection::last_key(hash),
+            replay_protection::all_key(hash),
+        ] {
+            if self
+                .get(Table::ReplayProtection, &key.to_string())?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        self.get(Table::Subspace, &key.to_string())
+    }
+
+    fn read_subspace_val_with_height(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> Result<Option<Vec<u8>>> {
+        let diff_key = |height: BlockHeight, kind: &str| -> Result<String> {
+            Ok(Key::from(height.to_db_key())
+                .push(&kind.to_owned())
+                .map_err(Error::KeyError)?
+                .join(key)
+                .to_string())
+        };
+
+        // If it has a "new" val, it was written at this height
+        if let Some(new_val) =
+            self.get(Table::Diffs, &diff_key(height, "new")?)?
+        {
+            return Ok(Some(new_val));
+        }
+        // If it has an "old" val, it was deleted at this height
+        if self.get(Table::Diffs, &diff_key(height, "old")?)?.is_some() {
+            return Ok(None);
+        }
+
+        // If the value didn't change at the given height, we try to look for it
+        // at successor heights, up to the `last_height`
+        let mut raw_height = height.0 + 1;
+        loop {
+            let height = BlockHeight(raw_height);
+            // If it has an "old" val, it's the one we're looking for
+            if let Some(old_val) =
+                self.get(Table::Diffs, &diff_key(height, "old")?)?
+            {
+                return Ok(Some(old_val));
+            }
+            // Check if the value was created at this height instead, which
+            // would mean that it wasn't present before
+            if self.get(Table::Diffs, &diff_key(height, "new")?)?.is_some() {
+                return Ok(None);
+            }
+            if raw_height >= last_height.0 {
+                // Read from latest height
+                return self.read_subspace_val(key);
+            }
+            raw_height += 1
+        }
+    }
+
+    fn write_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+    ) -> Result<i64> {
+        let mut batch = LmdbWriteBatch::default();
+        let size_diff =
+            self.batch_write_subspace_val(&mut batch, height, key, value)?;
+        self.exec_batch(batch)?;
+        Ok(size_diff)
+    }
+
+    fn delete_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+    ) -> Result<i64> {
+        let mut batch = LmdbWriteBatch::default();
+        let prev_len =
+            self.batch_delete_subspace_val(&mut batch, height, key)?;
+        self.exec_batch(batch)?;
+        Ok(prev_len)
+    }
+
+    fn batch() -> Self::WriteBatch {
+        LmdbWriteBatch::default()
+    }
+
+    fn exec_batch(&mut self, batch: Self::WriteBatch) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(db_error)?;
+        for (table, key, value) in batch.0 {
+            if key.len() > MAX_KEY_SIZE {
+                write_long_key(self.long_keys(table), &mut wtxn, key, value)?;
+                continue;
+            }
+            let table = self.table(table);
+            match value {
+                Some(value) => table.put(&mut wtxn, &key, &value),
+                None => table.delete(&mut wtxn, &key).map(|_| ()),
+            }
+            .map_err(db_error)?;
+        }
+        wtxn.commit().map_err(db_error)
+    }
+
+    fn batch_write_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+    ) -> Result<i64> {
+        let value = value.as_ref();
+        let size_diff = match self.read_subspace_val(key)? {
+            Some(old_value) => {
+                let size_diff = value.len() as i64 - old_value.len() as i64;
+                // Persist the previous value
+                Self::batch_write_subspace_diff(
+                    batch,
+                    height,
+                    key,
+                    Some(&old_value),
+                    Some(value),
+                )?;
+                size_diff
+            }
+            None => {
+                Self::batch_write_subspace_diff(
+                    batch,
+                    height,
+                    key,
+                    None,
+                    Some(value),
+                )?;
+                value.len() as i64
+            }
+        };
+
+        // Write the new key-val
+        batch.put(Table::Subspace, key.to_string(), value);
+
+        Ok(size_diff)
+    }
+
+    fn batch_delete_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+    ) -> Result<i64> {
+        // Check the length of previous value, if any
+        let prev_len = match self.read_subspace_val(key)? {
+            Some(prev_value) => {
+                let prev_len = prev_value.len() as i64;
+                // Persist the previous value
+                Self::batch_write_subspace_diff(
+                    batch,
+                    height,
+                    key,
+                    Some(&prev_value),
+                    None,
+                )?;
+                prev_len
+            }
+            None => 0,
+        };
+
+        // Delete the key-val
+        batch.delete(Table::Subspace, key.to_string());
+
+        Ok(prev_len)
+    }
+
+    fn prune_merkle_tree_store(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        store_type: &StoreType,
+        epoch: Epoch,
+    ) -> Result<()> {
+        let key_prefix = subtree_key_prefix(store_type, epoch);
+        let root_key = key_prefix.clone().with_segment("root".to_owned());
+        batch.delete(Table::Block, root_key.to_string());
+        let store_key = key_prefix.with_segment("store".to_owned());
+        batch.delete(Table::Block, store_key.to_string());
+        Ok(())
+    }
+
+    fn read_diffs_pruning_height(&self) -> Result<Option<BlockHeight>> {
+        match self.get(Table::State, "diffs_pruning_height")? {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn prune_diffs(&mut self, pruning_height: BlockHeight) -> Result<()> {
+        let mut batch = LmdbWriteBatch::default();
+        batch.put(
+            Table::State,
+            "diffs_pruning_height",
+            types::encode(&pruning_height),
+        );
+        self.exec_batch(batch)?;
+        self.pruner_handle()?.prune(pruning_height);
+        Ok(())
+    }
+
+    fn read_bridge_pool_signed_nonce(
+        &self,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> Result<Option<ethereum_events::Uint>> {
+        let nonce_key = bridge_pool::get_signed_root_key();
+        let bytes = if height == BlockHeight(0) || height >= last_height {
+            self.read_subspace_val(&nonce_key)?
+        } else {
+            self.read_subspace_val_with_height(&nonce_key, height, last_height)?
+        };
+        match bytes {
+            Some(bytes) => {
+                let bp_root_proof = BridgePoolRootProof::try_from_slice(&bytes)
+                    .map_err(Error::BorshCodingError)?;
+                Ok(Some(bp_root_proof.data.1))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn write_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+    ) -> Result<()> {
+        batch.put(Table::ReplayProtection, key.to_string(), value);
+        Ok(())
+    }
+
+    fn delete_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> Result<()> {
+        batch.delete(Table::ReplayProtection, key.to_string());
+        Ok(())
+    }
+}
+
+impl<'iter> DBIter<'iter> for LmdbDB {
+    type PrefixIter = LmdbPrefixIterator<'iter>;
+
+    fn iter_prefix(&'iter self, prefix: Option<&Key>) -> Self::PrefixIter {
+        iter_prefix(self, Table::Subspace, None, prefix)
+    }
+
+    fn iter_results(&'iter self) -> Self::PrefixIter {
+        LmdbPrefixIterator::new(
+            self,
+            Table::Block,
+            "results/".to_owned(),
+            "results/",
+        )
+    }
+
+    fn iter_old_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> Self::PrefixIter {
+        iter_diffs_prefix(self, height, prefix, true)
+    }
+
+    fn iter_new_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> Self::PrefixIter {
+        iter_diffs_prefix(self, height, prefix, false)
+    }
+
+    fn iter_replay_protection(&'iter self) -> Self::PrefixIter {
+        self.iter_replay_protection_prefix(&replay_protection::last_prefix())
+    }
+
+    fn iter_replay_protection_prefix(
+        &'iter self,
+        prefix: &Key,
+    ) -> Self::PrefixIter {
+        iter_prefix(self, Table::ReplayProtection, Some(prefix), None)
+    }
+}
+
+fn iter_diffs_prefix<'a>(
+    db: &'a LmdbDB,
+    height: BlockHeight,
+    prefix: Option<&Key>,
+    is_old: bool,
+) -> LmdbPrefixIterator<'a> {
+    let kind = if is_old { "old" } else { "new" };
+    let stripped_prefix = Some(
+        Key::from(height.to_db_key())
+            .push(&kind.to_string())
+            .unwrap(),
+    );
+    // get keys without the `stripped_prefix`
+    iter_prefix(db, Table::Diffs, stripped_prefix.as_ref(), prefix)
+}
+
+/// Create an iterator over key-vals in the given table matching the given
+/// prefix(es). If any, the `stripped_prefix` is matched first and will be
+/// removed from the matched keys. If any, the second `prefix` is matched
+/// against the stripped keys and remains in the matched keys.
+fn iter_prefix<'a>(
+    db: &'a LmdbDB,
+    table: Table,
+    stripped_prefix: Option<&Key>,
+    prefix: Option<&Key>,
+) -> LmdbPrefixIterator<'a> {
+    let stripped_prefix = match stripped_prefix {
+        Some(p) if !p.is_empty() => format!("{p}/"),
+        _ => "".to_owned(),
+    };
+    let prefix = match prefix {
+        Some(p) if !p.is_empty() => {
+            format!("{stripped_prefix}{p}/")
+        }
+        _ => stripped_prefix.clone(),
+    };
+    LmdbPrefixIterator::new(db, table, prefix, &stripped_prefix)
+}
+
+/// An iterator over the key-vals of a table whose keys start with a prefix.
+///
+/// The key-vals are read in pages, each one in a short-lived read transaction,
+/// so that the iterator doesn't keep an old snapshot of the DB alive. As a
+/// consequence, the iterator is not snapshot-consistent: the key-vals written
+/// or deleted after the last read key while iterating, including from the
+/// same thread, are reflected by the following pages.
+pub struct LmdbPrefixIterator<'a> {
+    db: &'a LmdbDB,
+    table: Table,
+    prefix: String,
+    stripped_prefix: String,
+    page: VecDeque<(String, Vec<u8>)>,
+    last_key: Option<String>,
+    done: bool,
+}
+
+impl<'a> LmdbPrefixIterator<'a> {
+    fn new(
+        db: &'a LmdbDB,
+        table: Table,
+        prefix: String,
+        stripped_prefix: &str,
+    ) -> Self {
+        Self {
+            db,
+            table,
+            prefix,
+            stripped_prefix: stripped_prefix.to_owned(),
+            page: VecDeque::new(),
+            last_key: None,
+            done: false,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for LmdbPrefixIterator<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LmdbPrefixIterator")
+            .field("table", &self.table)
+            .field("prefix", &self.prefix)
+            .field("stripped_prefix", &self.stripped_prefix)
+            .finish()
+    }
+}
+
+impl<'a> Iterator for LmdbPrefixIterator<'a> {
+    type Item = (String, Vec<u8>, u64);
+
+    /// Returns the next pair and the gas cost
+    fn next(&mut self) -> Option<(String, Vec<u8>, u64)> {
+        loop {
+            if self.page.is_empty() {
+                if self.done {
+                    return None;
+                }
+                self.page = self
+                    .db
+                    .read_page(
+                        self.table,
+                        &self.prefix,
+                        self.last_key.as_deref(),
+                    )
+                    .expect("Prefix iterator shouldn't fail");
+                self.done = self.page.len() < ITER_PAGE_SIZE;
+                match self.page.back() {
+                    Some((key, _)) => self.last_key = Some(key.clone()),
+                    None => return None,
+                }
+            }
+            let (key, val) = self.page.pop_front()?;
+            if let Some(k) = key.strip_prefix(&self.stripped_prefix) {
+                let gas = k.len() + val.len();
+                return Some((k.to_owned(), val, gas as _));
+            } else {
+                tracing::warn!(
+                    "Unmatched prefix \"{}\" in iterator's key \"{key}\"",
+                    self.stripped_prefix
+                );
+            }
+        }
+    }
+}
+
+impl DBWriteBatch for LmdbWriteBatch {}
+
+/// A background thread deleting the diffs below the last requested pruning
+/// height. The thread is stopped when the handle is dropped.
+struct DiffsPruner {
+    pruning_height: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl DiffsPruner {
+    fn spawn(
+        env: Env,
+        diffs: Database<Str, Bytes>,
+        long_diffs: Database<Bytes, Bytes>,
+    ) -> Result<Self> {
+        let pruning_height = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = thread::Builder::new()
+            .name("lmdb-diffs-pruner".to_owned())
+            .spawn({
+                let pruning_height = pruning_height.clone();
+                let stop = stop.clone();
+                move || {
+                    let mut pruned_height = 0;
+                    while !stop.load(Ordering::Acquire) {
+                        let height = pruning_height.load(Ordering::Acquire);
+                        if height <= pruned_height {
+                            thread::park();
+                            continue;
+                        }
+                        match delete_diffs_chunk(
+                            &env,
+                            diffs,
+                            long_diffs,
+                            BlockHeight(height),
+                        ) {
+                            Ok(false) => {}
+                            Ok(true) => pruned_height = height,
+                            Err(e) => {
+                                // The remaining diffs are deleted with the
+                                // next pruning
+                                tracing::error!(
+                                    "Failed to prune the diffs below height \
+                                     {height}: {e}"
+                                );
+                                pruned_height = height;
+                            }
+                        }
+                    }
+                }
+            })
+            .map_err(|e| Error::DBError(e.to_string()))?;
+        Ok(Self {
+            pruning_height,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// Request the deletion of the diffs below the given height
+    fn prune(&self, pruning_height: BlockHeight) {
+        self.pruning_height
+            .fetch_max(pruning_height.0, Ordering::AcqRel);
+        if let Some(handle) = &self.handle {
+            handle.thread().unpark();
+        }
+    }
+}
+
+impl Drop for DiffsPruner {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            if handle.join().is_err() {
+                tracing::error!("The LMDB diffs pruner thread panicked");
+            }
+        }
+    }
+}
+
+/// Delete up to [`PRUNE_CHUNK_SIZE`] diffs and groups of diffs with long keys
+/// below the given height in a single write transaction. Returns `true` when
+/// there are no more diffs to delete.
+fn delete_diffs_chunk(
+    env: &Env,
+    diffs: Database<Str, Bytes>,
+    long_diffs: Database<Bytes, Bytes>,
+    pruning_height: BlockHeight,
+) -> Result<bool> {
+    // The diffs' keys are prefixed with the fixed-length, order-preserving
+    // encoding of their height
+    let upper_bound = pruning_height.raw();
+    let range = (Bound::Unbounded, Bound::Excluded(upper_bound.as_str()));
+    let mut wtxn = env.write_txn().map_err(db_error)?;
+    let keys = diffs
+        .range(&wtxn, &range)
+        .map_err(db_error)?
+        .take(PRUNE_CHUNK_SIZE)
+        .map(|entry| entry.map(|(key, _val)| key.to_owned()))
+        .collect::<std::result::Result<Vec<String>, _>>()
+        .map_err(db_error)?;
+    for key in &keys {
+        diffs.delete(&mut wtxn, key).map_err(db_error)?;
+    }
+    // The heads of the long keys include their whole height prefix
+    let range = (Bound::Unbounded, Bound::Excluded(upper_bound.as_bytes()));
+    let long_keys = long_diffs
+        .range(&wtxn, &range)
+        .map_err(db_error)?
+        .take(PRUNE_CHUNK_SIZE)
+        .map(|entry| entry.map(|(head, _group)| head.to_vec()))
+        .collect::<std::result::Result<Vec<Vec<u8>>, _>>()
+        .map_err(db_error)?;
+    for head in &long_keys {
+        long_diffs.delete(&mut wtxn, head).map_err(db_error)?;
+    }
+    wtxn.commit().map_err(db_error)?;
+    Ok(keys.len() < PRUNE_CHUNK_SIZE && long_keys.len() < PRUNE_CHUNK_SIZE)
+}
+
+/// The first [`MAX_KEY_SIZE`] bytes of a key, under which it's grouped with
+/// the other keys sharing them if it's longer than that
+fn long_key_head(key: &str) -> &[u8] {
+    let key = key.as_bytes();
+    &key[..key.len().min(MAX_KEY_SIZE)]
+}
+
+/// The longest prefix of a key that fits in LMDB
+fn truncate_key(key: &str) -> &str {
+    let mut end = key.len().min(MAX_KEY_SIZE);
+    while !key.is_char_boundary(end) {
+        end -= 1;
+    }
+    &key[..end]
+}
+
+/// Read the group of the long keys sharing the head of the given key
+fn read_long_keys(
+    long_keys: Database<Bytes, Bytes>,
+    txn: &RoTxn<'_>,
+    key: &str,
+) -> Result<LongKeys> {
+    match long_keys.get(txn, long_key_head(key)).map_err(db_error)? {
+        Some(group) => types::decode(group).map_err(Error::CodingError),
+        None => Ok(LongKeys::default()),
+    }
+}
+
+/// Write or delete, if the value is `None`, a key-val with a long key in the
+/// group of its head
+fn write_long_key(
+    long_keys: Database<Bytes, Bytes>,
+    wtxn: &mut RwTxn<'_>,
+    key: String,
+    value: Option<Vec<u8>>,
+) -> Result<()> {
+    let mut group = read_long_keys(long_keys, wtxn, &key)?;
+    let head = long_key_head(&key).to_vec();
+    match value {
+        Some(value) => {
+            group.insert(key, value);
+        }
+        None => {
+            group.remove(&key);
+        }
+    }
+    if group.is_empty() {
+        long_keys.delete(wtxn, &head).map(|_| ())
+    } else {
+        long_keys.put(wtxn, &head, &group.serialize_to_vec())
+    }
+    .map_err(db_error)
+}
+
+fn db_error(error: heed::Error) -> Error {
+    Error::DBError(error.to_string())
+}
+
+fn unknown_key_error(key: &str) -> Result<()> {
+    Err(Error::UnknownKey {
+        key: key.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use namada::ledger::storage::tx_queue::TxQueue;
+    use namada::state::{MerkleTree, Sha256Hasher};
+    use namada::types::address::{
+        gen_established_address, EstablishedAddressGen,
+    };
+    use namada::types::storage::{
+        BlockHash, BlockResults, Epochs, EthEventsQueue,
+    };
+    use namada::types::time::DateTimeUtc;
+    use namada::types::token::ConversionState;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    use super::*;
+
+    /// Test that the prefix iterator reads every key-val across its pages.
+    #[test]
+    fn test_prefix_iter_pages() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path()).unwrap();
+
+        let prefix = Key::parse("prefix").unwrap();
+        let keys: Vec<Key> = (0..ITER_PAGE_SIZE * 2 + 1)
+            .map(|i| prefix.push(&format!("{i:06}")).unwrap())
+            .collect();
+        let mut batch = LmdbDB::batch();
+        let height = BlockHeight(1);
+        for key in &keys {
+            db.batch_write_subspace_val(&mut batch, height, key, [0_u8])
+                .unwrap();
+        }
+        // A key sorted right after the prefixed keys
+        db.batch_write_subspace_val(
+            &mut batch,
+            height,
+            &Key::parse("prefiy").unwrap(),
+            [0_u8],
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(Some(&prefix))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(keys, itered_keys);
+    }
+
+    /// Test that the keys longer than LMDB's limit are read, iterated in order
+    /// with the other keys and deleted.
+    #[test]
+    fn test_long_keys() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path()).unwrap();
+
+        let prefix = Key::parse("prefix").unwrap();
+        let long_seg = "a".repeat(MAX_KEY_SIZE);
+        // Short and long keys in distinct groups, spanning several pages
+        let mut keys: Vec<Key> = (0..ITER_PAGE_SIZE + 1)
+            .flat_map(|i| {
+                [
+                    prefix.push(&format!("{i:06}")).unwrap(),
+                    prefix.push(&format!("{i:06}{long_seg}")).unwrap(),
+                ]
+            })
+            .collect();
+        // Long keys in the same group
+        let group_prefix = prefix.push(&long_seg).unwrap();
+        let group_keys: Vec<Key> = (0..3)
+            .map(|i| group_prefix.push(&i.to_string()).unwrap())
+            .collect();
+        keys.extend(group_keys.iter().cloned());
+        let mut batch = LmdbDB::batch();
+        let height = BlockHeight(1);
+        for key in &keys {
+            db.batch_write_subspace_val(
+                &mut batch,
+                height,
+                key,
+                key.len().to_le_bytes(),
+            )
+            .unwrap();
+        }
+        db.exec_batch(batch).unwrap();
+        keys.sort_by_key(|key| key.to_string());
+
+        for key in &keys {
+            let val = db.read_subspace_val(key).unwrap().unwrap();
+            assert_eq!(val, key.len().to_le_bytes());
+        }
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(Some(&prefix))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(&keys, &itered_keys);
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(Some(&group_prefix))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(&group_keys, &itered_keys);
+
+        // Delete a key of the group
+        db.delete_subspace_val(height, &group_keys[1]).unwrap();
+        assert!(db.read_subspace_val(&group_keys[1]).unwrap().is_none());
+        assert!(db.read_subspace_val(&group_keys[2]).unwrap().is_some());
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(Some(&group_prefix))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal([&group_keys[0], &group_keys[2]], &itered_keys);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path()).unwrap();
+
+        // A key that's gonna be added on a second block
+        let add_key = Key::parse("add").unwrap();
+        // A key that's gonna be deleted on a second block
+        let delete_key = Key::parse("delete").unwrap();
+        // A key that's gonna be overwritten on a second block
+        let overwrite_key = Key::parse("overwrite").unwrap();
+
+        // Write first block
+        let mut batch = LmdbDB::batch();
+        let height_0 = BlockHeight(100);
+        let mut pred_epochs = Epochs::default();
+        pred_epochs.new_epoch(height_0);
+        let mut conversion_state_0 = ConversionState::default();
+        conversion_state_0
+            .tokens
+            .insert("dummy1".to_string(), gen_established_address("test"));
+        let to_delete_val = vec![1_u8, 1, 0, 0];
+        let to_overwrite_val = vec![1_u8, 1, 1, 0];
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_0,
+            &delete_key,
+            &to_delete_val,
+        )
+        .unwrap();
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_0,
+            &overwrite_key,
+            &to_overwrite_val,
+        )
+        .unwrap();
+
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            height_0,
+            Epoch(1),
+            pred_epochs.clone(),
+            &conversion_state_0,
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+
+        // Write second block
+        let mut batch = LmdbDB::batch();
+        let height_1 = BlockHeight(101);
+        pred_epochs.new_epoch(height_1);
+        let mut conversion_state_1 = ConversionState::default();
+        conversion_state_1
+            .tokens
+            .insert("dummy2".to_string(), gen_established_address("test"));
+        let add_val = vec![1_u8, 0, 0, 0];
+        let overwrite_val = vec![1_u8, 1, 1, 1];
+        db.batch_write_subspace_val(&mut batch, height_1, &add_key, &add_val)
+            .unwrap();
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_1,
+            &overwrite_key,
+            &overwrite_val,
+        )
+        .unwrap();
+        db.batch_delete_subspace_val(&mut batch, height_1, &delete_key)
+            .unwrap();
+
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            height_1,
+            Epoch(2),
+            pred_epochs,
+            &conversion_state_1,
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+
+        // Rollback to the first block height
+        db.rollback(height_0).unwrap();
+
+        // Check that the values are back to the state at the first block
+        let added = db.read_subspace_val(&add_key).unwrap();
+        assert_eq!(added, None);
+        let overwritten = db.read_subspace_val(&overwrite_key).unwrap();
+        assert_eq!(overwritten, Some(to_overwrite_val));
+        let deleted = db.read_subspace_val(&delete_key).unwrap();
+        assert_eq!(deleted, Some(to_delete_val));
+        // Check the conversion state and that the last block is gone
+        let conversion_state =
+            db.get(Table::State, "conversion_state").unwrap().unwrap();
+        assert_eq!(conversion_state, types::encode(&conversion_state_0));
+        let last_block = db.read_last_block().unwrap().unwrap();
+        assert_eq!(last_block.height, height_0);
+        assert_eq!(db.iter_new_diffs(height_1, None).count(), 0);
+    }
+
+    /// A test helper to write a block
+    fn add_block_to_batch(
+        db: &LmdbDB,
+        batch: &mut LmdbWriteBatch,
+        height: BlockHeight,
+        epoch: Epoch,
+        pred_epochs: Epochs,
+        conversion_state: &ConversionState,
+    ) -> Result<()> {
+        let merkle_tree = MerkleTree::<Sha256Hasher>::default();
+        let merkle_tree_stores = merkle_tree.stores();
+        let hash = BlockHash::default();
+        let time = DateTimeUtc::now();
+        let next_epoch_min_start_height = BlockHeight::default();
+        let next_epoch_min_start_time = DateTimeUtc::now();
+        let update_epoch_blocks_delay = None;
+        let address_gen = EstablishedAddressGen::new("whatever");
+        let tx_queue = TxQueue::default();
+        let results = BlockResults::default();
+        let eth_events_queue = EthEventsQueue::default();
+        let block = BlockStateWrite {
+            merkle_tree_stores,
+            header: None,
+            hash: &hash,
+            height,
+            time,
+            epoch,
+            results: &results,
+            conversion_state,
+            pred_epochs: &pred_epochs,
+            next_epoch_min_start_height,
+            next_epoch_min_start_time,
+            update_epoch_blocks_delay,
+            address_gen: &address_gen,
+            tx_queue: &tx_queue,
+            ethereum_height: None,
+            eth_events_queue: &eth_events_queue,
+        };
+
+        db.add_block_to_batch(block, batch, true)
+    }
+}
@@ -1,23 +1,391 @@
 //! The storage module handles both the current state in-memory and the stored
 //! state in DB.
 
+mod lmdb;
 mod rocksdb;
 
 use std::fmt;
+use std::path::{Path, PathBuf};
 
 use arse_merkle_tree::blake2b::Blake2bHasher;
 use arse_merkle_tree::traits::Hasher;
 use arse_merkle_tree::H256;
 use blake2b_rs::{Blake2b, Blake2bBuilder};
-use namada::state::{State, StorageHasher};
+use itertools::Either;
+use namada::state::{
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbResult,
+    MerkleTreeStoresRead, State, StorageHasher, StoreType, DB,
+};
+use namada::types::ethereum_events;
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Epoch, Header, Key};
+
+use crate::config::DbBackend;
 
 #[derive(Default)]
 pub struct PersistentStorageHasher(Blake2bHasher);
 
-pub type PersistentDB = rocksdb::RocksDB;
+/// The persistent DB of the ledger, in one of the [`DbBackend`]s
+#[derive(Debug)]
+pub enum PersistentDB {
+    RocksDB(rocksdb::RocksDB),
+    Lmdb(lmdb::LmdbDB),
+}
+
+/// The cache of the persistent DB. Its variant selects the backend in which
+/// the DB is opened and opening the DB without a cache uses RocksDB.
+pub enum PersistentDBCache {
+    /// RocksDB's block cache
+    RocksDB(::rocksdb::Cache),
+    /// LMDB relies on the page cache of the OS
+    Lmdb,
+}
+
+/// DB handle for batch writes, holding a batch of each backend of which only
+/// the one of the opened DB is used
+#[derive(Default)]
+pub struct PersistentDBWriteBatch {
+    rocksdb: rocksdb::RocksDBWriteBatch,
+    lmdb: lmdb::LmdbWriteBatch,
+}
 
 pub type PersistentStorage = State<PersistentDB, PersistentStorageHasher>;
 
+impl PersistentDBCache {
+    /// Create the cache of the given backend
+    pub fn new(backend: DbBackend, block_cache_bytes: u64) -> Self {
+        match backend {
+            DbBackend::RocksDB => Self::RocksDB(
+                ::rocksdb::Cache::new_lru_cache(block_cache_bytes as usize),
+            ),
+            DbBackend::Lmdb => Self::Lmdb,
+        }
+    }
+}
+
+impl PersistentDB {
+    /// Open the DB in the given backend, without a cache
+    pub fn open_backend(db_path: impl AsRef<Path>, backend: DbBackend) -> Self {
+        let db_path = db_path.as_ref();
+        assert_backend_files(db_path, backend);
+        match backend {
+            DbBackend::RocksDB => Self::RocksDB(DB::open(db_path, None)),
+            DbBackend::Lmdb => Self::Lmdb(DB::open(db_path, None)),
+        }
+    }
+
+    /// Dump last known block
+    pub fn dump_block(
+        &self,
+        out_file_path: PathBuf,
+        historic: bool,
+        height: Option<BlockHeight>,
+    ) {
+        match self {
+            Self::RocksDB(db) => db.dump_block(out_file_path, historic, height),
+            Self::Lmdb(db) => db.dump_block(out_file_path, historic, height),
+        }
+    }
+
+    /// Rollback to previous block. Given the inner working of tendermint
+    /// rollback and of the key structure of Namada, calling rollback more than
+    /// once without restarting the chain results in a single rollback.
+    pub fn rollback(
+        &mut self,
+        tendermint_block_height: BlockHeight,
+    ) -> DbResult<()> {
+        match self {
+            Self::RocksDB(db) => db.rollback(tendermint_block_height),
+            Self::Lmdb(db) => db.rollback(tendermint_block_height),
+        }
+    }
+}
+
+/// Panic if the DB directory contains the files of a backend other than the
+/// given one, as the backends can't read each other's files
+fn assert_backend_files(db_path: &Path, backend: DbBackend) {
+    let (other_backend, other_file) = match backend {
+        DbBackend::RocksDB => (DbBackend::Lmdb, "data.mdb"),
+        DbBackend::Lmdb => (DbBackend::RocksDB, "CURRENT"),
+    };
+    if db_path.join(other_file).exists() {
+        panic!(
+            "The DB at {} was created with the {other_backend:?} backend, but \
+             the configured `db_backend` is {backend:?}",
+            db_path.to_string_lossy()
+        );
+    }
+}
+
+/// Call the same method on the DB of either backend. The batch, if given, is
+/// substituted with the one of the backend.
+macro_rules! with_db {
+    ($self:expr, $db:ident => $body:expr) => {
+        match $self {
+            PersistentDB::RocksDB($db) => $body,
+            PersistentDB::Lmdb($db) => $body,
+        }
+    };
+    ($self:expr, $batch:expr, $db:ident, $db_batch:ident => $body:expr) => {
+        match $self {
+            PersistentDB::RocksDB($db) => {
+                let $db_batch = &mut $batch.rocksdb;
+                $body
+            }
+            PersistentDB::Lmdb($db) => {
+                let $db_batch = &mut $batch.lmdb;
+                $body
+            }
+        }
+    };
+}
+
+impl DB for PersistentDB {
+    type Cache = PersistentDBCache;
+    type WriteBatch = PersistentDBWriteBatch;
+
+    fn open(db_path: impl AsRef<Path>, cache: Option<&Self::Cache>) -> Self {
+        let db_path = db_path.as_ref();
+        match cache {
+            Some(PersistentDBCache::RocksDB(cache)) => {
+                assert_backend_files(db_path, DbBackend::RocksDB);
+                Self::RocksDB(DB::open(db_path, Some(cache)))
+            }
+            Some(PersistentDBCache::Lmdb) => {
+                Self::open_backend(db_path, DbBackend::Lmdb)
+            }
+            None => Self::open_backend(db_path, DbBackend::RocksDB),
+        }
+    }
+
+    fn flush(&self, wait: bool) -> DbResult<()> {
+        with_db!(self, db => db.flush(wait))
+    }
+
+    fn read_last_block(&self) -> DbResult<Option<BlockStateRead>> {
+        with_db!(self, db => db.read_last_block())
+    }
+
+    fn add_block_to_batch(
+        &self,
+        state: BlockStateWrite,
+        batch: &mut Self::WriteBatch,
+        is_full_commit: bool,
+    ) -> DbResult<()> {
+        with_db!(self, batch, db, batch => {
+            db.add_block_to_batch(state, batch, is_full_commit)
+        })
+    }
+
+    fn read_block_header(
+        &self,
+        height: BlockHeight,
+    ) -> DbResult<Option<Header>> {
+        with_db!(self, db => db.read_block_header(height))
+    }
+
+    fn read_merkle_tree_stores(
+        &self,
+        epoch: Epoch,
+        base_height: BlockHeight,
+        store_type: Option<StoreType>,
+    ) -> DbResult<Option<MerkleTreeStoresRead>> {
+        with_db!(self, db => {
+            db.read_merkle_tree_stores(epoch, base_height, store_type)
+        })
+    }
+
+    fn has_replay_protection_entry(&self, hash: &Hash) -> DbResult<bool> {
+        with_db!(self, db => db.has_replay_protection_entry(hash))
+    }
+
+    fn read_subspace_val(&self, key: &Key) -> DbResult<Option<Vec<u8>>> {
+        with_db!(self, db => db.read_subspace_val(key))
+    }
+
+    fn read_subspace_val_with_height(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> DbResult<Option<Vec<u8>>> {
+        with_db!(self, db => {
+            db.read_subspace_val_with_height(key, height, last_height)
+        })
+    }
+
+    fn write_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+    ) -> DbResult<i64> {
+        with_db!(self, db => db.write_subspace_val(height, key, value))
+    }
+
+    fn delete_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+    ) -> DbResult<i64> {
+        with_db!(self, db => db.delete_subspace_val(height, key))
+    }
+
+    fn batch() -> Self::WriteBatch {
+        PersistentDBWriteBatch::default()
+    }
+
+    fn exec_batch(&mut self, batch: Self::WriteBatch) -> DbResult<()> {
+        match self {
+            Self::RocksDB(db) => DB::exec_batch(db, batch.rocksdb),
+            Self::Lmdb(db) => DB::exec_batch(db, batch.lmdb),
+        }
+    }
+
+    fn batch_write_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+    ) -> DbResult<i64> {
+        with_db!(self, batch, db, batch => {
+            db.batch_write_subspace_val(batch, height, key, value)
+        })
+    }
+
+    fn batch_delete_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+    ) -> DbResult<i64> {
+        with_db!(self, batch, db, batch => {
+            db.batch_delete_subspace_val(batch, height, key)
+        })
+    }
+
+    fn prune_merkle_tree_store(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        store_type: &StoreType,
+        epoch: Epoch,
+    ) -> DbResult<()> {
+        with_db!(self, batch, db, batch => {
+            db.prune_merkle_tree_store(batch, store_type, epoch)
+        })
+    }
+
+    fn read_diffs_pruning_height(&self) -> DbResult<Option<BlockHeight>> {
+        with_db!(self, db => db.read_diffs_pruning_height())
+    }
+
+    fn prune_diffs(&mut self, pruning_height: BlockHeight) -> DbResult<()> {
+        with_db!(self, db => db.prune_diffs(pruning_height))
+    }
+
+    fn read_bridge_pool_signed_nonce(
+        &self,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> DbResult<Option<ethereum_events::Uint>> {
+        with_db!(self, db => {
+            db.read_bridge_pool_signed_nonce(height, last_height)
+        })
+    }
+
+    fn write_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+    ) -> DbResult<()> {
+        with_db!(self, batch, db, batch => {
+            db.write_replay_protection_entry(batch, key, value)
+        })
+    }
+
+    fn delete_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> DbResult<()> {
+        with_db!(self, batch, db, batch => {
+            db.delete_replay_protection_entry(batch, key)
+        })
+    }
+}
+
+impl<'iter> DBIter<'iter> for PersistentDB {
+    type PrefixIter = Either<
+        rocksdb::PersistentPrefixIterator<'iter>,
+        lmdb::LmdbPrefixIterator<'iter>,
+    >;
+
+    fn iter_prefix(&'iter self, prefix: Option<&Key>) -> Self::PrefixIter {
+        match self {
+            Self::RocksDB(db) => Either::Left(db.iter_prefix(prefix)),
+            Self::Lmdb(db) => Either::Right(db.iter_prefix(prefix)),
+        }
+    }
+
+    fn iter_results(&'iter self) -> Self::PrefixIter {
+        match self {
+            Self::RocksDB(db) => Either::Left(db.iter_results()),
+            Self::Lmdb(db) => Either::Right(db.iter_results()),
+        }
+    }
+
+    fn iter_old_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> Self::PrefixIter {
+        match self {
+            Self::RocksDB(db) => {
+                Either::Left(db.iter_old_diffs(height, prefix))
+            }
+            Self::Lmdb(db) => Either::Right(db.iter_old_diffs(height, prefix)),
+        }
+    }
+
+    fn iter_new_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> Self::PrefixIter {
+        match self {
+            Self::RocksDB(db) => {
+                Either::Left(db.iter_new_diffs(height, prefix))
+            }
+            Self::Lmdb(db) => Either::Right(db.iter_new_diffs(height, prefix)),
+        }
+    }
+
+    fn iter_replay_protection(&'iter self) -> Self::PrefixIter {
+        match self {
+            Self::RocksDB(db) => Either::Left(db.iter_replay_protection()),
+            Self::Lmdb(db) => Either::Right(db.iter_replay_protection()),
+        }
+    }
+
+    fn iter_replay_protection_prefix(
+        &'iter self,
+        prefix: &Key,
+    ) -> Self::PrefixIter {
+        match self {
+            Self::RocksDB(db) => {
+                Either::Left(db.iter_replay_protection_prefix(prefix))
+            }
+            Self::Lmdb(db) => {
+                Either::Right(db.iter_replay_protection_prefix(prefix))
+            }
+        }
+    }
+}
+
+impl DBWriteBatch for PersistentDBWriteBatch {}
+
 impl Hasher for PersistentStorageHasher {
     fn write_bytes(&mut self, h: &[u8]) {
         self.0.write_bytes(h)
@@ -51,6 +419,8 @@ fn new_blake2b() -> Blake2b {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::thread;
+    use std::time::{Duration, Instant};
 
     use itertools::Itertools;
     use namada::eth_bridge::storage::proof::BridgePoolRootProof;
@@ -76,17 +446,60 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn test_crud_value() {
-        let db_path =
-            TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
+    /// Generate a test running the given test function against each DB backend
+    macro_rules! backend_tests {
+        ($($test:ident),* $(,)?) => {
+            mod rocksdb_backend {
+                $(
+                    #[test]
+                    fn $test() {
+                        super::$test(super::DbBackend::RocksDB)
+                    }
+                )*
+            }
+
+            mod lmdb_backend {
+                $(
+                    #[test]
+                    fn $test() {
+                        super::$test(super::DbBackend::Lmdb)
+                    }
+                )*
+            }
+        };
+    }
+
+    backend_tests!(
+        test_crud_value,
+        test_commit_block,
+        test_iter,
+        test_validity_predicate,
+        test_prune_merkle_tree_stores,
+        test_prune_diffs,
+        test_persistent_storage_prefix_iter,
+        test_long_key,
+    );
+
+    /// Open the storage in a DB of the given backend
+    fn open_storage(
+        db_path: &Path,
+        backend: DbBackend,
+        storage_read_past_height_limit: Option<u64>,
+    ) -> PersistentStorage {
+        let lmdb_cache = PersistentDBCache::Lmdb;
+        PersistentStorage::open(
+            db_path,
             ChainId::default(),
             address::nam(),
-            None,
-            None,
-        );
+            (backend == DbBackend::Lmdb).then_some(&lmdb_cache),
+            storage_read_past_height_limit,
+        )
+    }
+
+    fn test_crud_value(backend: DbBackend) {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut storage = open_storage(db_path.path(), backend, None);
         let key = Key::parse("key").expect("cannot parse the key string");
         let value: u64 = 1;
         let value_bytes = types::encode(&value);
@@ -128,17 +541,10 @@ mod tests {
         assert_eq!(result, None);
     }
 
-    #[test]
-    fn test_commit_block() {
+    fn test_commit_block(backend: DbBackend) {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
-        );
+        let mut storage = open_storage(db_path.path(), backend, None);
         storage
             .begin_block(BlockHash::default(), BlockHeight(100))
             .expect("begin_block failed");
@@ -154,6 +560,7 @@ mod tests {
                 min_duration: DurationSecs(3600),
             },
             max_expected_time_per_block: DurationSecs(3600),
+            tx_expiration_window: DurationSecs(86400),
             max_proposal_bytes: Default::default(),
             max_block_gas: 100,
             vp_whitelist: vec![],
@@ -233,13 +640,7 @@ mod tests {
         drop(wl_storage);
 
         // load the last state
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
-        );
+        let mut storage = open_storage(db_path.path(), backend, None);
         storage
             .load_last_state()
             .expect("loading the last state failed");
@@ -253,17 +654,10 @@ mod tests {
         assert_eq!(val.expect("no value"), value_bytes);
     }
 
-    #[test]
-    fn test_iter() {
+    fn test_iter(backend: DbBackend) {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
-        );
+        let mut storage = open_storage(db_path.path(), backend, None);
         storage
             .begin_block(BlockHash::default(), BlockHeight(100))
             .expect("begin_block failed");
@@ -299,17 +693,66 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_validity_predicate() {
+    /// Test that a key longer than LMDB's limit is committed, read at the past
+    /// heights and iterated in order.
+    fn test_long_key(backend: DbBackend) {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
+        let mut storage = open_storage(db_path.path(), backend, None);
+        let prefix = Key::parse("prefix").expect("cannot parse the key string");
+        let short_key = prefix.push(&"a".to_owned()).unwrap();
+        let long_key = prefix.push(&"b".repeat(1024)).unwrap();
+        let other_key = prefix.push(&"c".to_owned()).unwrap();
+
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(1))
+            .expect("begin_block failed");
+        for key in [&other_key, &long_key, &short_key] {
+            storage
+                .write(key, types::encode(&key.to_string()))
+                .expect("write failed");
+        }
+        storage
+            .commit_block(PersistentStorage::batch())
+            .expect("commit failed");
+
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(2))
+            .expect("begin_block failed");
+        storage.delete(&long_key).expect("delete failed");
+        storage
+            .commit_block(PersistentStorage::batch())
+            .expect("commit failed");
+
+        let (value, _gas) = storage.read(&long_key).expect("read failed");
+        assert!(value.is_none());
+        let (value, _gas) = storage
+            .read_with_height(&long_key, BlockHeight(1))
+            .expect("read failed");
+        assert_eq!(value, Some(types::encode(&long_key.to_string())));
+
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(3))
+            .expect("begin_block failed");
+        storage
+            .write(&long_key, types::encode(&long_key.to_string()))
+            .expect("write failed");
+        storage
+            .commit_block(PersistentStorage::batch())
+            .expect("commit failed");
+
+        let (iter, _gas) = storage.iter_prefix(&prefix);
+        let keys: Vec<String> = iter.map(|(key, _val, _gas)| key).collect();
+        assert_eq!(
+            keys,
+            [short_key, long_key, other_key].map(|key| key.to_string())
         );
+    }
+
+    fn test_validity_predicate(backend: DbBackend) {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut storage = open_storage(db_path.path(), backend, None);
         storage
             .begin_block(BlockHash::default(), BlockHeight(100))
             .expect("begin_block failed");
@@ -344,12 +787,16 @@ mod tests {
         })]
         #[test]
         fn test_read_with_height(blocks_write_value in vec(any::<bool>(), 20)) {
-            test_read_with_height_aux(blocks_write_value).unwrap()
+            for backend in [DbBackend::RocksDB, DbBackend::Lmdb] {
+                test_read_with_height_aux(blocks_write_value.clone(), backend).unwrap()
+            }
         }
 
         #[test]
         fn test_get_merkle_tree(blocks_write_type in vec(0..5_u64, 50)) {
-            test_get_merkle_tree_aux(blocks_write_type).unwrap()
+            for backend in [DbBackend::RocksDB, DbBackend::Lmdb] {
+                test_get_merkle_tree_aux(blocks_write_type.clone(), backend).unwrap()
+            }
         }
     }
 
@@ -367,16 +814,11 @@ mod tests {
     ///    value, if any.
     fn test_read_with_height_aux(
         blocks_write_value: Vec<bool>,
+        backend: DbBackend,
     ) -> namada::state::Result<()> {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
-        );
+        let mut storage = open_storage(db_path.path(), backend, None);
 
         // 1. For each `blocks_write_value`, write the current block height if
         // true or delete otherwise.
@@ -460,16 +902,11 @@ mod tests {
     /// Test the restore of the merkle tree
     fn test_get_merkle_tree_aux(
         blocks_write_type: Vec<u64>,
+        backend: DbBackend,
     ) -> namada::state::Result<()> {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
-        );
+        let mut storage = open_storage(db_path.path(), backend, None);
 
         let num_keys = 5;
         let blocks_write_type = blocks_write_type.into_iter().enumerate().map(
@@ -577,17 +1014,10 @@ mod tests {
     }
 
     /// Test the restore of the merkle tree
-    #[test]
-    fn test_prune_merkle_tree_stores() {
+    fn test_prune_merkle_tree_stores(backend: DbBackend) {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let mut storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            Some(5),
-        );
+        let mut storage = open_storage(db_path.path(), backend, Some(5));
         let new_epoch_start = BlockHeight(1);
         let signed_root_key = bridge_pool::get_signed_root_key();
         // the first nonce isn't written for a test skipping pruning
@@ -681,18 +1111,80 @@ mod tests {
         assert!(result.is_err(), "The bridge pool tree should be pruned");
     }
 
-    /// Test the prefix iterator with RocksDB.
-    #[test]
-    fn test_persistent_storage_prefix_iter() {
+    /// Test that the diffs older than the retained heights are pruned and that
+    /// the state can't be read at the pruned heights anymore.
+    fn test_prune_diffs(backend: DbBackend) {
         let db_path =
             TempDir::new().expect("Unable to create a temporary DB directory");
-        let storage = PersistentStorage::open(
-            db_path.path(),
-            ChainId::default(),
-            address::nam(),
-            None,
-            None,
-        );
+        let mut storage = open_storage(db_path.path(), backend, None);
+        storage.diffs_retained_heights = Some(5);
+        let key = Key::parse("key").expect("cannot parse the key string");
+
+        for height in 1..=20_u64 {
+            storage
+                .begin_block(BlockHash::default(), height.into())
+                .expect("begin_block failed");
+            storage
+                .write(&key, types::encode(&height))
+                .expect("write failed");
+            // A new epoch every 4 heights
+            if height % 4 == 1 {
+                if height > 1 {
+                    storage.block.epoch = storage.block.epoch.next();
+                }
+                storage.block.pred_epochs.new_epoch(height.into());
+            }
+            let batch = PersistentStorage::batch();
+            storage.commit_block(batch).expect("commit failed");
+        }
+
+        // The oldest retained height 16 is in the epoch starting at height 13
+        let pruning_height = BlockHeight(13);
+        assert_eq!(storage.diffs_pruning_height, Some(pruning_height));
+        let (value, _gas) = storage
+            .read_with_height(&key, pruning_height)
+            .expect("read failed");
+        assert_eq!(value, Some(types::encode(&13_u64)));
+        let result = storage.get_merkle_tree(16.into(), None);
+        assert!(result.is_ok(), "The tree at height 16 should be restored");
+        let result = storage.read_with_height(&key, BlockHeight(12));
+        assert!(matches!(
+            result,
+            Err(state::Error::PrunedHeight {
+                height: BlockHeight(12),
+                oldest_height,
+            }) if oldest_height == pruning_height
+        ));
+        let result = storage.get_merkle_tree(12.into(), None);
+        assert!(matches!(result, Err(state::Error::PrunedHeight { .. })));
+
+        // The diffs may be deleted in the background
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while (1..pruning_height.0).any(|height| {
+            storage.db.iter_new_diffs(height.into(), None).count() > 0
+        }) {
+            assert!(
+                Instant::now() < deadline,
+                "The diffs should have been pruned"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(storage.db.iter_new_diffs(pruning_height, None).count() > 0);
+
+        // The pruning height is persisted
+        drop(storage);
+        let mut storage = open_storage(db_path.path(), backend, None);
+        storage
+            .load_last_state()
+            .expect("loading the last state failed");
+        assert_eq!(storage.diffs_pruning_height, Some(pruning_height));
+    }
+
+    /// Test the prefix iterator of the persistent DB.
+    fn test_persistent_storage_prefix_iter(backend: DbBackend) {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let storage = open_storage(db_path.path(), backend, None);
         let mut storage = WlStorage {
             storage,
             write_log: Default::default(),
@@ -18,6 +18,8 @@
 //!     - `next_epoch_min_start_height`
 //!     - `next_epoch_min_start_time`
 //!   - `conversion_state`: MASP conversion state
+//!   - `diffs_pruning_height`: the height below which the diffs have been
+//!     pruned
 //! - `subspace`: accounts sub-spaces
 //!   - `{address}/{dyn}`: any byte data associated with accounts
 //! - `diffs`: diffs in account subspaces' key-vals
@@ -505,11 +507,22 @@ impl RocksDB {
         tracing::info!("Removing last block results");
         batch.delete_cf(block_cf, format!("results/{}", last_block.height));
 
-        // Delete the tx hashes included in the last block
+        // Delete the tx hashes included in the last block. The entries
+        // finalized or pruned by the last block are not restored: the former
+        // are still protected under the `all` subkey and the latter expired
+        // before the previous block, so they can't be included in any block
+        // that replaces the last one
         let reprot_cf = self.get_column_family(REPLAY_PROTECTION_CF)?;
         tracing::info!("Removing replay protection hashes");
-        batch
-            .delete_cf(reprot_cf, replay_protection::last_prefix().to_string());
+        for (hash, _, _) in self.iter_replay_protection() {
+            batch.delete_cf(
+                reprot_cf,
+                replay_protection::last_prefix()
+                    .push(&hash)
+                    .map_err(Error::KeyError)?
+                    .to_string(),
+            );
+        }
 
         // Execute next step in parallel
         let batch = Mutex::new(batch);
@@ -1448,6 +1461,35 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn read_diffs_pruning_height(&self) -> Result<Option<BlockHeight>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        match self
+            .0
+            .get_cf(state_cf, "diffs_pruning_height")
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn prune_diffs(&mut self, pruning_height: BlockHeight) -> Result<()> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        let diffs_cf = self.get_column_family(DIFFS_CF)?;
+        let mut batch = WriteBatch::default();
+        batch.put_cf(
+            state_cf,
+            "diffs_pruning_height",
+            types::encode(&pruning_height),
+        );
+        // A single range tombstone, the space is reclaimed by the background
+        // compaction
+        batch.delete_range_cf(diffs_cf, "", pruning_height.raw().as_str());
+        self.exec_batch(batch)
+    }
+
     fn read_bridge_pool_signed_nonce(
         &self,
         height: BlockHeight,
@@ -1473,13 +1515,12 @@ impl DB for RocksDB {
         &mut self,
         batch: &mut Self::WriteBatch,
         key: &Key,
+        value: impl AsRef<[u8]>,
     ) -> Result<()> {
         let replay_protection_cf =
             self.get_column_family(REPLAY_PROTECTION_CF)?;
 
-        batch
-            .0
-            .put_cf(replay_protection_cf, key.to_string(), vec![]);
+        batch.0.put_cf(replay_protection_cf, key.to_string(), value);
 
         Ok(())
     }
@@ -1541,12 +1582,18 @@ impl<'iter> DBIter<'iter> for RocksDB {
     }
 
     fn iter_replay_protection(&'iter self) -> Self::PrefixIter {
+        self.iter_replay_protection_prefix(&replay_protection::last_prefix())
+    }
+
+    fn iter_replay_protection_prefix(
+        &'iter self,
+        prefix: &Key,
+    ) -> Self::PrefixIter {
         let replay_protection_cf = self
             .get_column_family(REPLAY_PROTECTION_CF)
             .expect("{REPLAY_PROTECTION_CF} column family should exist");
 
-        let stripped_prefix = Some(replay_protection::last_prefix());
-        iter_prefix(self, replay_protection_cf, stripped_prefix.as_ref(), None)
+        iter_prefix(self, replay_protection_cf, Some(prefix), None)
     }
 }
 
@@ -1733,6 +1780,7 @@ mod test {
     use namada::types::address::{
         gen_established_address, EstablishedAddressGen,
     };
+    use namada::types::hash::Hash;
     use namada::types::storage::{BlockHash, Epoch, Epochs};
     use tempfile::tempdir;
     use test_log::test;
@@ -2031,6 +2079,94 @@ mod test {
         assert_eq!(conversion_state, types::encode(&conversion_state_0));
     }
 
+    /// Test that a rollback only removes the tx hashes of the last block,
+    /// keeping the ones finalized by it together with their expiration index
+    #[test]
+    fn test_rollback_replay_protection() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let tx1 = Hash::sha256("tx1".as_bytes());
+        let tx2 = Hash::sha256("tx2".as_bytes());
+        let expiration = DateTimeUtc::now();
+        let expiration_val = types::encode(&expiration);
+
+        // Write first block, including tx1
+        let mut batch = RocksDB::batch();
+        let height_0 = BlockHeight(100);
+        let mut pred_epochs = Epochs::default();
+        pred_epochs.new_epoch(height_0);
+        let conversion_state = ConversionState::default();
+        db.write_replay_protection_entry(
+            &mut batch,
+            &replay_protection::last_key(&tx1),
+            &expiration_val,
+        )
+        .unwrap();
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            height_0,
+            Epoch(1),
+            pred_epochs.clone(),
+            &conversion_state,
+        )
+        .unwrap();
+        db.exec_batch(batch.0).unwrap();
+
+        // Write second block, finalizing tx1 and including tx2
+        let mut batch = RocksDB::batch();
+        let height_1 = BlockHeight(101);
+        pred_epochs.new_epoch(height_1);
+        db.delete_replay_protection_entry(
+            &mut batch,
+            &replay_protection::last_key(&tx1),
+        )
+        .unwrap();
+        db.write_replay_protection_entry(
+            &mut batch,
+            &replay_protection::all_key(&tx1),
+            &expiration_val,
+        )
+        .unwrap();
+        db.write_replay_protection_entry(
+            &mut batch,
+            &replay_protection::expiry_key(&expiration, &tx1),
+            b"",
+        )
+        .unwrap();
+        db.write_replay_protection_entry(
+            &mut batch,
+            &replay_protection::last_key(&tx2),
+            &expiration_val,
+        )
+        .unwrap();
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            height_1,
+            Epoch(2),
+            pred_epochs,
+            &conversion_state,
+        )
+        .unwrap();
+        db.exec_batch(batch.0).unwrap();
+
+        // Rollback to the first block height
+        db.rollback(height_0).unwrap();
+
+        // Check that only the hash of the rolled back block was removed
+        assert!(db.has_replay_protection_entry(&tx1).unwrap());
+        assert!(!db.has_replay_protection_entry(&tx2).unwrap());
+        let expiry: Vec<_> = db
+            .iter_replay_protection_prefix(&replay_protection::expiry_prefix())
+            .map(|(key, _, _)| {
+                replay_protection::parse_expiry_key(&key).unwrap().1
+            })
+            .collect();
+        assert_eq!(expiry, vec![tx1]);
+    }
+
     /// A test helper to write a block
     fn add_block_to_batch(
         db: &RocksDB,
@@ -281,6 +281,9 @@ fn validator_key_to_json(
                 sk_sec.serialize_to_vec(),
             )
         }
+        common::SecretKey::Secp256r1(_) => {
+            return Err(ParseSecretKeyError::MismatchedScheme);
+        }
     };
 
     Ok(json!({
@@ -365,6 +368,11 @@ pub fn id_from_pk(pk: &common::PublicKey) -> TendermintNodeId {
             let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
             bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
         }
+        common::PublicKey::Secp256r1(_) => {
+            let _pk: secp256r1::PublicKey = pk.try_to_pk().unwrap();
+            let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
+            bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
+        }
     }
     TendermintNodeId::new(bytes)
 }
@@ -12,7 +12,8 @@ pub use namada_sdk::wallet::alias::Alias;
 use namada_sdk::wallet::fs::FsWalletStorage;
 use namada_sdk::wallet::store::Store;
 use namada_sdk::wallet::{
-    ConfirmationResponse, FindKeyError, GenRestoreKeyError, Wallet, WalletIo,
+    ConfirmationResponse, FindKeyError, GenRestoreKeyError, LoadStoreError,
+    Wallet, WalletIo,
 };
 pub use namada_sdk::wallet::{ValidatorData, ValidatorKeys};
 use rand_core::OsRng;
@@ -36,6 +37,96 @@ impl FsWalletStorage for CliWalletUtils {
     fn store_dir(&self) -> &PathBuf {
         &self.store_dir
     }
+
+    fn read_store_passphrase(&self) -> Option<Zeroizing<String>> {
+        Some(read_store_passphrase(false))
+    }
+}
+
+/// Read the passphrase of a wallet store that is encrypted as a whole from
+/// the file/env/stdin, with confirmation if `confirm` is set and the
+/// passphrase is read from stdin.
+pub fn read_store_passphrase(confirm: bool) -> Zeroizing<String> {
+    let pwd = match store_passphrase_from_env() {
+        Some(pwd) => pwd,
+        None if confirm => read_new_store_passphrase_tty(),
+        None => {
+            let prompt = "Enter your wallet store passphrase: ";
+            rpassword::read_password_from_tty(Some(prompt))
+                .map(Zeroizing::new)
+                .expect("Failed reading password from tty.")
+        }
+    };
+    if confirm {
+        check_new_store_passphrase(&pwd);
+    }
+    pwd
+}
+
+/// Read a new passphrase for a wallet store. When the passphrase of an
+/// encrypted store is being changed (`rotate` is set), the file/env hold the
+/// current passphrase, so the new one is always read from stdin.
+pub fn read_new_store_passphrase(rotate: bool) -> Zeroizing<String> {
+    let pwd = new_store_passphrase_from_vars(rotate, env_var)
+        .unwrap_or_else(read_new_store_passphrase_tty);
+    check_new_store_passphrase(&pwd);
+    pwd
+}
+
+/// Get the new passphrase of a wallet store from the file/env, unless the
+/// passphrase of an encrypted store is being changed.
+fn new_store_passphrase_from_vars(
+    rotate: bool,
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<Zeroizing<String>> {
+    if rotate {
+        None
+    } else {
+        store_passphrase_from_vars(var)
+    }
+}
+
+/// Get the passphrase of a wallet store from the file/env, if set.
+fn store_passphrase_from_env() -> Option<Zeroizing<String>> {
+    store_passphrase_from_vars(env_var)
+}
+
+/// Get the passphrase of a wallet store from the file/variables looked up
+/// with `var`, if set.
+fn store_passphrase_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<Zeroizing<String>> {
+    match var("NAMADA_WALLET_STORE_PASSWORD_FILE") {
+        Some(path) => Some(Zeroizing::new(
+            fs::read_to_string(path)
+                .expect("Something went wrong reading the file"),
+        )),
+        None => var("NAMADA_WALLET_STORE_PASSWORD").map(Zeroizing::new),
+    }
+}
+
+/// Look up an environment variable, if set.
+fn env_var(key: &str) -> Option<String> {
+    env::var(key).ok()
+}
+
+/// Read and confirm a new wallet store passphrase from stdin.
+fn read_new_store_passphrase_tty() -> Zeroizing<String> {
+    let prompt = "Enter a new wallet store passphrase: ";
+    read_and_confirm_passphrase_tty(prompt).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        eprintln!("Action cancelled, no changes persisted.");
+        cli::safe_exit(1)
+    })
+}
+
+/// Exit if the new wallet store passphrase is empty.
+fn check_new_store_passphrase(pwd: &Zeroizing<String>) {
+    if pwd.as_str().is_empty() {
+        eprintln!("Passphrase cannot be empty");
+        eprintln!("Action cancelled, no changes persisted.");
+        cli::safe_exit(1)
+    }
 }
 
 impl WalletIo for CliWalletUtils {
@@ -244,22 +335,23 @@ pub fn save(wallet: &Wallet<CliWalletUtils>) -> std::io::Result<()> {
 /// Load a wallet from the store file.
 pub fn load(store_dir: &Path) -> Option<Wallet<CliWalletUtils>> {
     let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
-    if wallet.load().is_err() {
-        return None;
+    match wallet.load() {
+        Ok(()) => Some(wallet),
+        Err(err @ (LoadStoreError::Locked | LoadStoreError::Decrypt(_))) => {
+            eprintln!("Unable to load the wallet: {}", err);
+            cli::safe_exit(1)
+        }
+        Err(_) => None,
     }
-    Some(wallet)
 }
 
 /// Load a wallet from the store file or create a new wallet without any
 /// keys or addresses.
 pub fn load_or_new(store_dir: &Path) -> Wallet<CliWalletUtils> {
-    let store = self::store::load_or_new(store_dir).unwrap_or_else(|err| {
+    self::store::load_or_new(store_dir).unwrap_or_else(|err| {
         eprintln!("Unable to load the wallet: {}", err);
         cli::safe_exit(1)
-    });
-    let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
-    *wallet.store_mut() = store;
-    wallet
+    })
 }
 
 /// Check if a wallet exists in the given store dir.
@@ -283,11 +375,17 @@ pub fn read_and_confirm_encryption_password(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
     use namada::bip39::MnemonicType;
     use namada_sdk::wallet::WalletIo;
     use rand_core;
 
-    use super::CliWalletUtils;
+    use super::{
+        new_store_passphrase_from_vars, store_passphrase_from_vars,
+        CliWalletUtils,
+    };
 
     #[test]
     fn test_generate_mnemonic() {
@@ -302,4 +400,54 @@ mod tests {
                 .unwrap();
         assert_ne!(mnemonic1.into_phrase(), mnemonic2.into_phrase());
     }
+
+    /// Test that the wallet store passphrase is read from the env, but not
+    /// when the passphrase of an encrypted store is being changed, as the
+    /// env then holds the current passphrase.
+    #[test]
+    fn test_store_passphrase_from_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("passphrase");
+        fs::write(&file, "from file").unwrap();
+
+        // The variables are looked up in a map rather than in the process
+        // env, which is shared with the tests running concurrently
+        fn lookup<'a>(
+            vars: &'a HashMap<&str, String>,
+        ) -> impl Fn(&str) -> Option<String> + 'a {
+            move |key| vars.get(key).cloned()
+        }
+        let mut vars = HashMap::new();
+        assert!(store_passphrase_from_vars(lookup(&vars)).is_none());
+
+        vars.insert("NAMADA_WALLET_STORE_PASSWORD", "from env".to_string());
+        assert_eq!(
+            store_passphrase_from_vars(lookup(&vars)).unwrap().as_str(),
+            "from env"
+        );
+        assert_eq!(
+            new_store_passphrase_from_vars(false, lookup(&vars))
+                .unwrap()
+                .as_str(),
+            "from env"
+        );
+        assert!(new_store_passphrase_from_vars(true, lookup(&vars)).is_none());
+
+        // the file takes precedence over the env var
+        vars.insert(
+            "NAMADA_WALLET_STORE_PASSWORD_FILE",
+            file.to_string_lossy().into_owned(),
+        );
+        assert_eq!(
+            store_passphrase_from_vars(lookup(&vars)).unwrap().as_str(),
+            "from file"
+        );
+        assert_eq!(
+            new_store_passphrase_from_vars(false, lookup(&vars))
+                .unwrap()
+                .as_str(),
+            "from file"
+        );
+        assert!(new_store_passphrase_from_vars(true, lookup(&vars)).is_none());
+    }
 }
@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use namada::types::key::*;
 use namada_sdk::wallet::{
-    gen_secret_key, LoadStoreError, Store, ValidatorKeys,
+    gen_secret_key, LoadStoreError, ValidatorKeys, Wallet,
 };
 use rand::rngs::OsRng;
 
@@ -17,19 +17,30 @@ pub fn wallet_file(store_dir: impl AsRef<Path>) -> PathBuf {
 }
 
 /// Load the store file or create a new one without any keys or addresses.
-pub fn load_or_new(store_dir: &Path) -> Result<Store, LoadStoreError> {
-    load(store_dir).or_else(|_| {
-        let wallet = CliWalletUtils::new(store_dir.to_path_buf());
-        wallet.save()?;
-        Ok(wallet.into())
-    })
+pub fn load_or_new(
+    store_dir: &Path,
+) -> Result<Wallet<CliWalletUtils>, LoadStoreError> {
+    match load(store_dir) {
+        Ok(wallet) => Ok(wallet),
+        // Never replace an encrypted store that could not be unlocked
+        Err(err @ (LoadStoreError::Locked | LoadStoreError::Decrypt(_))) => {
+            Err(err)
+        }
+        Err(_) => {
+            let wallet = CliWalletUtils::new(store_dir.to_path_buf());
+            wallet.save()?;
+            Ok(wallet)
+        }
+    }
 }
 
 /// Attempt to load the store file.
-pub fn load(store_dir: &Path) -> Result<Store, LoadStoreError> {
+pub fn load(
+    store_dir: &Path,
+) -> Result<Wallet<CliWalletUtils>, LoadStoreError> {
     let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
     wallet.load()?;
-    Ok(wallet.into())
+    Ok(wallet)
 }
 
 /// Generate keypair for signing protocol txs and for the DKG
@@ -60,6 +71,7 @@ pub fn gen_validator_keys(
 #[cfg(test)]
 mod test_wallet {
     use namada::types::address::Address;
+    use namada_sdk::wallet::Store;
 
     use super::*;
 
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use criterion::{criterion_group, criterion_main, Criterion};
 use namada::core::types::account::AccountPublicKeysMap;
 use namada::core::types::address;
+use namada::core::types::key::{common, secp256r1, testing};
 use namada::ledger::storage::DB;
 use namada::token::{Amount, Transfer};
 use namada::tx::Signature;
@@ -52,7 +53,32 @@ fn tx_section_signature_validation(c: &mut Criterion) {
                     &mut HashSet::new(),
                     &pkim,
                     &None,
-                    &mut || Ok(()),
+                    &mut |_| Ok(()),
+                )
+                .unwrap()
+        })
+    });
+
+    // Secp256r1 (P-256) signatures are more expensive to verify and are
+    // charged separately
+    let p256_keypair = common::SecretKey::Secp256r1(testing::gen_keypair::<
+        secp256r1::SigScheme,
+    >());
+    let p256_pkim = AccountPublicKeysMap::from_iter([p256_keypair.to_public()]);
+    let p256_multisig = Signature::new(
+        vec![section_hash],
+        p256_pkim.index_secret_keys(vec![p256_keypair]),
+        None,
+    );
+
+    c.bench_function("tx_section_signature_validation_secp256r1", |b| {
+        b.iter(|| {
+            p256_multisig
+                .verify_signature(
+                    &mut HashSet::new(),
+                    &p256_pkim,
+                    &None,
+                    &mut |_| Ok(()),
                 )
                 .unwrap()
         })
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use masp_primitives::merkle_tree::{CommitmentTree, IncrementalWitness};
+use masp_primitives::sapling::Node;
+use namada::sdk::masp::WitnessStore;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The number of note commitments scanned by each iteration
+const COMMITMENTS: usize = 1_000;
+/// One in this many notes is owned by the tracked viewing keys
+const OWNED_RATIO: usize = 50;
+
+fn commitments() -> Vec<Node> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..COMMITMENTS).map(|_| Node::new(rng.gen())).collect()
+}
+
+// Benchmarks the maintenance of the note witnesses while scanning new note
+// commitments, then the computation of the merkle paths of the owned notes
+fn masp_witness_maintenance(c: &mut Criterion) {
+    let nodes = commitments();
+    let mut group = c.benchmark_group("masp_witness_maintenance");
+
+    // A witness for every note, updated with every new commitment
+    group.bench_function("witness_every_note", |b| {
+        b.iter(|| {
+            let mut tree = CommitmentTree::<Node>::empty();
+            let mut witnesses: HashMap<usize, IncrementalWitness<Node>> =
+                HashMap::new();
+            for node in &nodes {
+                for witness in witnesses.values_mut() {
+                    witness.append(*node).unwrap();
+                }
+                tree.append(*node).unwrap();
+                witnesses.insert(
+                    tree.size() - 1,
+                    IncrementalWitness::from_tree(&tree),
+                );
+            }
+            for pos in (0..COMMITMENTS).step_by(OWNED_RATIO) {
+                black_box(witnesses[&pos].path().unwrap());
+            }
+        })
+    });
+
+    // Witnesses only for the owned notes, updated in batches
+    group.bench_function("witness_store", |b| {
+        b.iter(|| {
+            let mut tree = CommitmentTree::<Node>::empty();
+            let mut witnesses = WitnessStore::default();
+            for node in &nodes {
+                witnesses.append(*node).unwrap();
+                tree.append(*node).unwrap();
+                if (tree.size() - 1) % OWNED_RATIO == 0 {
+                    witnesses.insert(
+                        tree.size() - 1,
+                        IncrementalWitness::from_tree(&tree),
+                    );
+                }
+            }
+            for pos in (0..COMMITMENTS).step_by(OWNED_RATIO) {
+                black_box(witnesses.path(pos).unwrap().unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(masp_witness, masp_witness_maintenance);
+criterion_main!(masp_witness);
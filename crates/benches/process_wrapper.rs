@@ -6,7 +6,7 @@ use namada::tx::data::{Fee, WrapperTx};
 use namada::tx::Signature;
 use namada::types::key::RefTo;
 use namada::types::storage::BlockHeight;
-use namada::types::time::DateTimeUtc;
+use namada::types::time::{DateTimeUtc, DurationSecs};
 use namada_apps::bench_utils::{BenchShell, TX_TRANSFER_WASM};
 use namada_apps::node::ledger::shell::process_proposal::ValidationMeta;
 use namada_apps::wallet::defaults;
@@ -47,6 +47,7 @@ fn process_tx(c: &mut Criterion) {
             None,
         ),
     )));
+    tx.header.expiration = Some(DateTimeUtc::now() + DurationSecs(3600));
     tx.add_section(namada::tx::Section::Signature(Signature::new(
         tx.sechashes(),
         [(0, defaults::albert_keypair())].into_iter().collect(),
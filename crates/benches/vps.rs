@@ -160,6 +160,7 @@ fn vp_user(c: &mut Criterion) {
                         &keys_changed,
                         &verifiers,
                         shell.vp_wasm_cache.clone(),
+                        &mut None,
                     )
                     .unwrap(),
                     "VP \"{bench_name}\" bench call failed"
@@ -306,6 +307,7 @@ fn vp_implicit(c: &mut Criterion) {
                         &keys_changed,
                         &verifiers,
                         shell.vp_wasm_cache.clone(),
+                        &mut None,
                     )
                     .unwrap()
                 )
@@ -458,6 +460,7 @@ fn vp_validator(c: &mut Criterion) {
                         &keys_changed,
                         &verifiers,
                         shell.vp_wasm_cache.clone(),
+                        &mut None,
                     )
                     .unwrap()
                 );
@@ -1,10 +1,26 @@
 //! Replay protection storage
+//!
+//! The hashes of the txs applied in the last block are kept under the `last`
+//! subkey and are moved under the `all` subkey at the beginning of the next
+//! block. Every entry under `all` is also indexed by its expiration under the
+//! `expiry` subkey, so that it can be pruned once the tx has expired and can't
+//! be included in a block anymore. The entries written before the expiration
+//! was tracked are migrated to expire [`LEGACY_EXPIRATION_BOUND`] after the
+//! last block committed before the migration. Any other entry without a known
+//! expiration is never pruned.
 
 use crate::types::hash::Hash;
 use crate::types::storage::Key;
+use crate::types::time::{DateTimeUtc, DurationSecs};
 
 const ERROR_MSG: &str = "Cannot obtain a valid db key";
 
+/// The time after the last committed block when the replay protection entries
+/// written before the expiration was tracked expire. The expiration of these
+/// txs was unbounded, so this is set well beyond the ones set by the clients.
+pub const LEGACY_EXPIRATION_BOUND: DurationSecs =
+    DurationSecs(365 * 24 * 60 * 60);
+
 /// Get the transaction hash prefix under the `all` subkey
 pub fn all_prefix() -> Key {
     Key::parse("all").expect(ERROR_MSG)
@@ -24,3 +40,33 @@ pub fn last_prefix() -> Key {
 pub fn last_key(hash: &Hash) -> Key {
     last_prefix().push(&hash.to_string()).expect(ERROR_MSG)
 }
+
+/// Get the transaction hash prefix under the `expiry` subkey
+pub fn expiry_prefix() -> Key {
+    Key::parse("expiry").expect(ERROR_MSG)
+}
+
+/// Get the key indexing the transaction hash by its expiration under the
+/// `expiry` subkey. The expiration is rounded up to the second and
+/// zero-padded so that the keys are ordered by expiration.
+pub fn expiry_key(expiration: &DateTimeUtc, hash: &Hash) -> Key {
+    let mut timestamp =
+        u64::try_from(expiration.0.timestamp()).unwrap_or_default();
+    if expiration.0.timestamp_subsec_nanos() > 0 {
+        timestamp += 1;
+    }
+    expiry_prefix()
+        .push(&format!("{timestamp:020}"))
+        .and_then(|key| key.push(&hash.to_string()))
+        .expect(ERROR_MSG)
+}
+
+/// Parse the expiration and the transaction hash from a key under the
+/// `expiry` subkey, stripped of the prefix
+pub fn parse_expiry_key(key: &str) -> Option<(DateTimeUtc, Hash)> {
+    let (timestamp, hash) = key.split_once('/')?;
+    let expiration =
+        DateTimeUtc::from_unix_timestamp(timestamp.parse::<i64>().ok()?)?;
+    let hash = hash.parse::<Hash>().ok()?;
+    Some((expiration, hash))
+}
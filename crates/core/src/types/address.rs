@@ -17,7 +17,7 @@ use sha2::{Digest, Sha256};
 use crate::ibc::primitives::Signer;
 use crate::impl_display_and_from_str_via_format;
 use crate::types::ethereum_events::EthAddress;
-use crate::types::ibc::IbcTokenHash;
+use crate::types::ibc::{IbcAccountHash, IbcTokenHash};
 use crate::types::key::PublicKeyHash;
 use crate::types::token::Denomination;
 use crate::types::{key, string_encoding};
@@ -62,6 +62,8 @@ pub const POS_SLASH_POOL: Address =
 pub const GOV: Address = Address::Internal(InternalAddress::Governance);
 /// Internal MASP address
 pub const MASP: Address = Address::Internal(InternalAddress::Masp);
+/// Internal IBC packet forward address
+pub const IBC_FORWARD: Address = Address::Internal(InternalAddress::IbcForward);
 /// Internal Multitoken address
 pub const MULTITOKEN: Address = Address::Internal(InternalAddress::Multitoken);
 
@@ -126,6 +128,12 @@ impl From<raw::Address<'_, raw::Validated>> for Address {
                 InternalAddress::IbcToken(IbcTokenHash(*raw_addr.data())),
             ),
             raw::Discriminant::Masp => Address::Internal(InternalAddress::Masp),
+            raw::Discriminant::IbcAccount => Address::Internal(
+                InternalAddress::IbcAccount(IbcAccountHash(*raw_addr.data())),
+            ),
+            raw::Discriminant::IbcForward => {
+                Address::Internal(InternalAddress::IbcForward)
+            }
         }
     }
 }
@@ -220,6 +228,19 @@ impl<'addr> From<&'addr Address> for raw::Address<'addr, raw::Validated> {
                     .validate()
                     .expect("This raw address is valid")
             }
+            Address::Internal(InternalAddress::IbcAccount(IbcAccountHash(
+                hash,
+            ))) => {
+                raw::Address::from_discriminant(raw::Discriminant::IbcAccount)
+                    .with_data_array_ref(hash)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
+            Address::Internal(InternalAddress::IbcForward) => {
+                raw::Address::from_discriminant(raw::Discriminant::IbcForward)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
         }
     }
 }
@@ -545,6 +566,10 @@ pub enum InternalAddress {
     Pgf,
     /// Masp
     Masp,
+    /// IBC interchain account hosted on this chain
+    IbcAccount(IbcAccountHash),
+    /// Holder of the tokens forwarded through this chain by IBC
+    IbcForward,
 }
 
 impl Display for InternalAddress {
@@ -566,6 +591,8 @@ impl Display for InternalAddress {
                 Self::Multitoken => "Multitoken".to_string(),
                 Self::Pgf => "PublicGoodFundings".to_string(),
                 Self::Masp => "MASP".to_string(),
+                Self::IbcAccount(hash) => format!("IbcAccount: {}", hash),
+                Self::IbcForward => "IbcForward".to_string(),
             }
         )
     }
@@ -855,6 +882,8 @@ pub mod testing {
             InternalAddress::Nut(_) => {}
             InternalAddress::Pgf => {}
             InternalAddress::Masp => {}
+            InternalAddress::IbcAccount(_) => {}
+            InternalAddress::IbcForward => {}
             InternalAddress::Multitoken => {} /* Add new addresses in the
                                                * `prop_oneof` below. */
         };
@@ -872,6 +901,8 @@ pub mod testing {
             Just(InternalAddress::Multitoken),
             Just(InternalAddress::Pgf),
             Just(InternalAddress::Masp),
+            arb_ibc_account(),
+            Just(InternalAddress::IbcForward),
         ]
     }
 
@@ -896,6 +927,23 @@ pub mod testing {
         })
     }
 
+    fn arb_ibc_account() -> impl Strategy<Value = InternalAddress> {
+        ("[a-zA-Z0-9_]{2,128}", any::<u64>()).prop_map(|(port, counter)| {
+            let mut hasher = sha2::Sha256::new();
+            let s =
+                format!("{}/{}", format_args!("connection-{}", counter), port);
+            hasher.update(&s);
+            let hash = hasher.finalize();
+            let hash = IbcAccountHash({
+                let input: &[u8; SHA_HASH_LEN] = hash.as_ref();
+                let mut output = [0; HASH_LEN];
+                output.copy_from_slice(&input[..HASH_LEN]);
+                output
+            });
+            InternalAddress::IbcAccount(hash)
+        })
+    }
+
     fn arb_erc20() -> InternalAddress {
         use crate::types::ethereum_events::testing::arbitrary_eth_address;
         // TODO: generate random erc20 addr data
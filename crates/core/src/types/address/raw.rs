@@ -65,6 +65,10 @@ pub enum Discriminant {
     IbcToken = 13,
     /// MASP raw address.
     Masp = 14,
+    /// IBC interchain account raw address.
+    IbcAccount = 15,
+    /// IBC packet forward raw address.
+    IbcForward = 16,
 }
 
 /// Raw address representation.
@@ -114,7 +118,8 @@ impl<S> Address<'_, S> {
                 | Discriminant::Established
                 | Discriminant::Erc20
                 | Discriminant::Nut
-                | Discriminant::IbcToken,
+                | Discriminant::IbcToken
+                | Discriminant::IbcAccount,
         )
     }
 }
@@ -33,6 +33,12 @@ pub struct Segments {
     pub signed_root: &'static str,
     /// Bridge pool nonce storage key
     pub bridge_pool_nonce: &'static str,
+    /// Storage key prefix of the gas fee bumps of pending transfers
+    pub gas_fee_bumps: &'static str,
+    /// Storage key prefix of the cancelled transfers awaiting a refund
+    pub cancelled: &'static str,
+    /// Minimum block height of the Bridge pool roots which can be signed
+    pub min_signed_root_height: &'static str,
 }
 
 /// Check if a key is for a pending transfer
@@ -63,6 +69,41 @@ pub fn get_key_from_hash(hash: &KeccakHash) -> Key {
     }
 }
 
+/// Get the storage key prefix of the gas fee bumps of pending transfers
+pub fn get_gas_fee_bump_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.gas_fee_bumps.into()),
+        ],
+    }
+}
+
+/// Get the storage key for the gas fees added to a pending transfer
+/// after it was added to the pool
+pub fn get_gas_fee_bump_key(hash: &KeccakHash) -> Key {
+    get_gas_fee_bump_prefix()
+        .push(hash)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the storage key prefix of the cancelled transfers
+pub fn get_cancelled_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.cancelled.into()),
+        ],
+    }
+}
+
+/// Get the storage key for a cancelled transfer awaiting a refund
+pub fn get_cancelled_key(hash: &KeccakHash) -> Key {
+    get_cancelled_prefix()
+        .push(hash)
+        .expect("Cannot obtain a storage key")
+}
+
 /// A version used in our Ethereuem smart contracts
 const VERSION: u8 = 1;
 
@@ -357,6 +398,49 @@ pub struct GasFee {
     pub token: Address,
 }
 
+/// An update of a transfer which is pending in the Ethereum bridge pool.
+///
+/// The transfer itself can't be modified once it's in the pool, since
+/// its hash may already be covered by a signed Bridge pool root.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub enum PendingTransferUpdate {
+    /// Cancel the transfer. The escrowed assets and gas fees are refunded
+    /// once the transfer can no longer be relayed, i.e. after the next
+    /// batch of transfers is relayed to Ethereum.
+    Cancel {
+        /// The hash of the pending transfer
+        transfer_hash: KeccakHash,
+    },
+    /// Add gas fees to the transfer, to have it relayed sooner. The fees are
+    /// paid by the original payer in the original gas token.
+    BumpGasFee {
+        /// The hash of the pending transfer
+        transfer_hash: KeccakHash,
+        /// The amount of gas fees to add
+        amount: Amount,
+    },
+}
+
+impl PendingTransferUpdate {
+    /// Get the hash of the updated transfer
+    pub fn transfer_hash(&self) -> &KeccakHash {
+        match self {
+            Self::Cancel { transfer_hash }
+            | Self::BumpGasFee { transfer_hash, .. } => transfer_hash,
+        }
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and strategies for the Ethereum bridge pool
 pub mod testing {
@@ -30,6 +30,8 @@ pub enum BpTransferStatus {
     Relayed,
     /// The transfer has expired.
     Expired,
+    /// The transfer has been cancelled by its sender.
+    Cancelled,
 }
 
 /// Ethereum bridge events on Namada's event log.
@@ -72,6 +74,14 @@ impl EthBridgeEvent {
             status: BpTransferStatus::Relayed,
         }
     }
+
+    /// Return a new Bridge pool cancelled transfer event.
+    pub const fn new_bridge_pool_cancelled(tx_hash: KeccakHash) -> Self {
+        Self::BridgePool {
+            tx_hash,
+            status: BpTransferStatus::Cancelled,
+        }
+    }
 }
 
 /// This type must be able to represent any valid Ethereum block height. It must
@@ -61,6 +61,42 @@ impl FromStr for IbcTokenHash {
     }
 }
 
+/// IBC interchain account hash derived from the connection ID and the
+/// controller port ID.
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+#[repr(transparent)]
+pub struct IbcAccountHash(pub [u8; HASH_LEN]);
+
+impl std::fmt::Display for IbcAccountHash {
+    #[inline(always)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.0))
+    }
+}
+
+impl FromStr for IbcAccountHash {
+    type Err = DecodePartial;
+
+    fn from_str(h: &str) -> std::result::Result<Self, Self::Err> {
+        let mut output = [0u8; HASH_LEN];
+        HEXLOWER_PERMISSIVE.decode_mut(h.as_ref(), &mut output)?;
+        Ok(IbcAccountHash(output))
+    }
+}
+
 /// Wrapped IbcEvent
 #[derive(
     Debug,
@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use super::{
-    ed25519, secp256k1, ParsePublicKeyError, ParseSecretKeyError,
+    ed25519, secp256k1, secp256r1, ParsePublicKeyError, ParseSecretKeyError,
     ParseSignatureError, RefTo, SchemeType, SigScheme as SigSchemeTrait,
     VerifySigError,
 };
@@ -40,10 +40,13 @@ pub enum PublicKey {
     Ed25519(ed25519::PublicKey),
     /// Encapsulate Secp256k1 public keys
     Secp256k1(secp256k1::PublicKey),
+    /// Encapsulate Secp256r1 (P-256) public keys
+    Secp256r1(secp256r1::PublicKey),
 }
 
 const ED25519_PK_PREFIX: &str = "ED25519_PK_PREFIX";
 const SECP256K1_PK_PREFIX: &str = "SECP256K1_PK_PREFIX";
+const SECP256R1_PK_PREFIX: &str = "SECP256R1_PK_PREFIX";
 
 impl Serialize for PublicKey {
     fn serialize<S>(
@@ -57,6 +60,7 @@ impl Serialize for PublicKey {
         let prefix = match self {
             PublicKey::Ed25519(_) => ED25519_PK_PREFIX,
             PublicKey::Secp256k1(_) => SECP256K1_PK_PREFIX,
+            PublicKey::Secp256r1(_) => SECP256R1_PK_PREFIX,
         };
         let keypair_string = format!("{}{}", prefix, self);
         Serialize::serialize(&keypair_string, serializer)
@@ -79,6 +83,10 @@ impl<'de> Deserialize<'de> for PublicKey {
             keypair_string.strip_prefix(SECP256K1_PK_PREFIX)
         {
             PublicKey::from_str(raw).map_err(D::Error::custom)
+        } else if let Some(raw) =
+            keypair_string.strip_prefix(SECP256R1_PK_PREFIX)
+        {
+            PublicKey::from_str(raw).map_err(D::Error::custom)
         } else {
             Err(D::Error::custom(
                 "Could not deserialize SecretKey do to invalid prefix",
@@ -110,6 +118,13 @@ impl super::PublicKey for PublicKey {
                 )
                 .map_err(ParsePublicKeyError::InvalidEncoding)?,
             ))
+        } else if PK::TYPE == secp256r1::PublicKey::TYPE {
+            Ok(Self::Secp256r1(
+                secp256r1::PublicKey::try_from_slice(
+                    pk.serialize_to_vec().as_slice(),
+                )
+                .map_err(ParsePublicKeyError::InvalidEncoding)?,
+            ))
         } else {
             Err(ParsePublicKeyError::MismatchedScheme)
         }
@@ -148,6 +163,10 @@ impl From<PublicKey> for crate::tendermint::PublicKey {
             PublicKey::Secp256k1(secp256k1::PublicKey(pk)) => {
                 TmPK::from_raw_secp256k1(&pk.to_sec1_bytes()).unwrap()
             }
+            // Validators are prevented from using these as consensus keys
+            PublicKey::Secp256r1(_) => {
+                unreachable!("Tendermint does not support secp256r1 keys")
+            }
         }
     }
 }
@@ -157,6 +176,8 @@ impl From<PublicKey> for crate::tendermint::PublicKey {
 pub enum EthAddressConvError {
     #[error("Eth key cannot be ed25519, only secp256k1")]
     CannotBeEd25519,
+    #[error("Eth key cannot be secp256r1, only secp256k1")]
+    CannotBeSecp256r1,
 }
 
 impl TryFrom<&PublicKey> for EthAddress {
@@ -166,6 +187,9 @@ impl TryFrom<&PublicKey> for EthAddress {
         match value {
             PublicKey::Ed25519(_) => Err(EthAddressConvError::CannotBeEd25519),
             PublicKey::Secp256k1(pk) => Ok(EthAddress::from(pk)),
+            PublicKey::Secp256r1(_) => {
+                Err(EthAddressConvError::CannotBeSecp256r1)
+            }
         }
     }
 }
@@ -178,6 +202,8 @@ pub enum SecretKey {
     Ed25519(ed25519::SecretKey),
     /// Encapsulate Secp256k1 secret keys
     Secp256k1(secp256k1::SecretKey),
+    /// Encapsulate Secp256r1 (P-256) secret keys
+    Secp256r1(secp256r1::SecretKey),
 }
 
 impl Serialize for SecretKey {
@@ -192,6 +218,7 @@ impl Serialize for SecretKey {
         let prefix = match self {
             SecretKey::Ed25519(_) => "ED25519_SK_PREFIX",
             SecretKey::Secp256k1(_) => "SECP256K1_SK_PREFIX",
+            SecretKey::Secp256r1(_) => "SECP256R1_SK_PREFIX",
         };
         let keypair_string = format!("{}{}", prefix, self);
         Serialize::serialize(&keypair_string, serializer)
@@ -214,6 +241,10 @@ impl<'de> Deserialize<'de> for SecretKey {
             keypair_string.strip_prefix("SECP256K1_SK_PREFIX")
         {
             SecretKey::from_str(raw).map_err(D::Error::custom)
+        } else if let Some(raw) =
+            keypair_string.strip_prefix("SECP256R1_SK_PREFIX")
+        {
+            SecretKey::from_str(raw).map_err(D::Error::custom)
         } else {
             Err(D::Error::custom(
                 "Could not deserialize SecretKey do to invalid prefix",
@@ -254,6 +285,13 @@ impl super::SecretKey for SecretKey {
                 )
                 .map_err(ParseSecretKeyError::InvalidEncoding)?,
             ))
+        } else if SK::TYPE == secp256r1::SecretKey::TYPE {
+            Ok(Self::Secp256r1(
+                secp256r1::SecretKey::try_from_slice(
+                    sk.serialize_to_vec().as_ref(),
+                )
+                .map_err(ParseSecretKeyError::InvalidEncoding)?,
+            ))
         } else {
             Err(ParseSecretKeyError::MismatchedScheme)
         }
@@ -265,6 +303,7 @@ impl RefTo<PublicKey> for SecretKey {
         match self {
             SecretKey::Ed25519(sk) => PublicKey::Ed25519(sk.ref_to()),
             SecretKey::Secp256k1(sk) => PublicKey::Secp256k1(sk.ref_to()),
+            SecretKey::Secp256r1(sk) => PublicKey::Secp256r1(sk.ref_to()),
         }
     }
 }
@@ -307,6 +346,8 @@ pub enum Signature {
     Ed25519(ed25519::Signature),
     /// Encapsulate Secp256k1 signatures
     Secp256k1(secp256k1::Signature),
+    /// Encapsulate Secp256r1 (P-256) signatures
+    Secp256r1(secp256r1::Signature),
 }
 
 impl string_encoding::Format for Signature {
@@ -340,6 +381,12 @@ impl From<secp256k1::Signature> for Signature {
     }
 }
 
+impl From<secp256r1::Signature> for Signature {
+    fn from(sig: secp256r1::Signature) -> Self {
+        Signature::Secp256r1(sig)
+    }
+}
+
 impl super::Signature for Signature {
     const TYPE: SchemeType = SigScheme::TYPE;
 
@@ -363,6 +410,13 @@ impl super::Signature for Signature {
                 )
                 .map_err(ParseSignatureError::InvalidEncoding)?,
             ))
+        } else if SIG::TYPE == secp256r1::Signature::TYPE {
+            Ok(Self::Secp256r1(
+                secp256r1::Signature::try_from_slice(
+                    sig.serialize_to_vec().as_slice(),
+                )
+                .map_err(ParseSignatureError::InvalidEncoding)?,
+            ))
         } else {
             Err(ParseSignatureError::MismatchedScheme)
         }
@@ -426,6 +480,9 @@ impl super::SigScheme for SigScheme {
             SecretKey::Secp256k1(kp) => Signature::Secp256k1(
                 secp256k1::SigScheme::sign_with_hasher::<H>(kp, data),
             ),
+            SecretKey::Secp256r1(kp) => Signature::Secp256r1(
+                secp256r1::SigScheme::sign_with_hasher::<H>(kp, data),
+            ),
         }
     }
 
@@ -448,6 +505,11 @@ impl super::SigScheme for SigScheme {
                     pk, data, sig,
                 )
             }
+            (PublicKey::Secp256r1(pk), Signature::Secp256r1(sig)) => {
+                secp256r1::SigScheme::verify_signature_with_hasher::<H>(
+                    pk, data, sig,
+                )
+            }
             _ => Err(VerifySigError::MismatchedScheme),
         }
     }
@@ -470,4 +532,35 @@ mod tests {
         println!("Public key: {}", public_key);
         println!("Secret key: {}", secret_key);
     }
+
+    /// Test that secp256r1 keys and signatures wrapped in `common` types
+    /// round-trip through their string and serde encodings.
+    #[test]
+    fn secp256r1_encodings_roundtrip() {
+        let secret_key =
+            SecretKey::Secp256r1(crate::types::key::testing::gen_keypair::<
+                secp256r1::SigScheme,
+            >());
+        let public_key = secret_key.to_public();
+        assert!(matches!(public_key, PublicKey::Secp256r1(_)));
+
+        let decoded_pk = PublicKey::from_str(&public_key.to_string()).unwrap();
+        assert_eq!(decoded_pk, public_key);
+        let pk_json = serde_json::to_string(&public_key).unwrap();
+        assert!(pk_json.contains(SECP256R1_PK_PREFIX));
+        let decoded_pk: PublicKey = serde_json::from_str(&pk_json).unwrap();
+        assert_eq!(decoded_pk, public_key);
+
+        let sk_json = serde_json::to_string(&secret_key).unwrap();
+        let decoded_sk: SecretKey = serde_json::from_str(&sk_json).unwrap();
+        assert_eq!(decoded_sk.to_public(), public_key);
+
+        let signature = SigScheme::sign(&secret_key, b"hello");
+        let decoded_sig = Signature::from_str(&signature.to_string()).unwrap();
+        assert_eq!(decoded_sig, signature);
+        assert!(
+            SigScheme::verify_signature(&public_key, b"hello", &decoded_sig)
+                .is_ok()
+        );
+    }
 }
@@ -3,6 +3,7 @@
 pub mod common;
 pub mod ed25519;
 pub mod secp256k1;
+pub mod secp256r1;
 
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
@@ -96,6 +97,8 @@ pub enum SchemeType {
     Ed25519,
     /// Type identifier for Secp256k1 scheme
     Secp256k1,
+    /// Type identifier for Secp256r1 (P-256) scheme
+    Secp256r1,
     /// Type identifier for Common
     Common,
 }
@@ -107,6 +110,7 @@ impl FromStr for SchemeType {
         match input.to_lowercase().as_str() {
             "ed25519" => Ok(Self::Ed25519),
             "secp256k1" => Ok(Self::Secp256k1),
+            "secp256r1" | "p256" => Ok(Self::Secp256r1),
             "common" => Ok(Self::Common),
             _ => Err(()),
         }
@@ -381,6 +385,7 @@ pub fn tm_consensus_key_raw_hash(pk: &common::PublicKey) -> String {
     let pkh = match pk {
         common::PublicKey::Ed25519(pk) => PublicKeyHash::from(pk),
         common::PublicKey::Secp256k1(pk) => PublicKeyHash::from(pk),
+        common::PublicKey::Secp256r1(pk) => PublicKeyHash::from(pk),
     };
     pkh.to_string()
 }
@@ -507,7 +512,10 @@ pub mod testing {
         let secp256k1 = arb_pk::<secp256k1::SigScheme>()
             .prop_map(common::PublicKey::Secp256k1)
             .sboxed();
-        ed25519.prop_union(secp256k1)
+        let secp256r1 = arb_pk::<secp256r1::SigScheme>()
+            .prop_map(common::PublicKey::Secp256r1)
+            .sboxed();
+        ed25519.prop_union(secp256k1).or(secp256r1)
     }
 
     /// A keypair for tests
@@ -585,6 +593,13 @@ pub mod testing {
             .prop_map(|keypair| keypair.try_to_sk().unwrap())
     }
 
+    /// Generate an arbitrary `secp256r1` [`common::SecretKey`].
+    pub fn arb_common_secp256r1_keypair()
+    -> impl Strategy<Value = common::SecretKey> {
+        arb_keypair::<secp256r1::SigScheme>()
+            .prop_map(|keypair| keypair.try_to_sk().unwrap())
+    }
+
     /// Derive an ed25519 [`common::SecretKey`] from a simple seed (`u64`).
     pub fn common_sk_from_simple_seed(seed: u64) -> common::SecretKey {
         let mut rng = StdRng::seed_from_u64(seed);
@@ -647,6 +662,8 @@ macro_rules! sigscheme_test {
 sigscheme_test! {ed25519_test, ed25519::SigScheme}
 #[cfg(test)]
 sigscheme_test! {secp256k1_test, secp256k1::SigScheme}
+#[cfg(test)]
+sigscheme_test! {secp256r1_test, secp256r1::SigScheme}
 
 #[cfg(test)]
 mod more_tests {
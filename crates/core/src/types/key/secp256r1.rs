@@ -0,0 +1,584 @@
+//! secp256r1 (P-256) keys and related functionality. These are the keys held
+//! by platform secure enclaves and WebAuthn authenticators.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::io::{ErrorKind, Read, Write};
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+#[cfg(any(test, feature = "rand"))]
+use rand::{CryptoRng, RngCore};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::{
+    ParsePublicKeyError, ParseSecretKeyError, ParseSignatureError, RefTo,
+    SchemeType, SigScheme as SigSchemeTrait, SignableBytes, VerifySigError,
+};
+use crate::types::key::StorageHasher;
+
+/// Size of a signature bytes, i.e. the `r` and `s` scalars
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// Size of a compressed public key bytes
+const COMPRESSED_PUBLIC_KEY_SIZE: usize = 33;
+/// Size of a secret key bytes
+pub(crate) const SECRET_KEY_SIZE: usize = 32;
+
+/// secp256r1 public key
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PublicKey(pub p256::PublicKey);
+
+impl PublicKey {
+    /// The SEC1 encoding of the compressed point of this key
+    fn to_compressed_bytes(&self) -> Vec<u8> {
+        self.0.to_encoded_point(true).as_bytes().to_vec()
+    }
+}
+
+impl super::PublicKey for PublicKey {
+    const TYPE: SchemeType = SigScheme::TYPE;
+
+    fn try_from_pk<PK: super::PublicKey>(
+        pk: &PK,
+    ) -> Result<Self, ParsePublicKeyError> {
+        if PK::TYPE == super::common::PublicKey::TYPE {
+            super::common::PublicKey::try_from_pk(pk).and_then(|x| match x {
+                super::common::PublicKey::Secp256r1(epk) => Ok(epk),
+                _ => Err(ParsePublicKeyError::MismatchedScheme),
+            })
+        } else if PK::TYPE == Self::TYPE {
+            Self::try_from_slice(pk.serialize_to_vec().as_slice())
+                .map_err(ParsePublicKeyError::InvalidEncoding)
+        } else {
+            Err(ParsePublicKeyError::MismatchedScheme)
+        }
+    }
+}
+
+impl BorshDeserialize for PublicKey {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        // deserialize the bytes first
+        let mut key_buf = [0u8; COMPRESSED_PUBLIC_KEY_SIZE];
+        reader.read_exact(&mut key_buf[..])?;
+        let pk = p256::PublicKey::from_sec1_bytes(&key_buf).map_err(|e| {
+            std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Error decoding secp256r1 public key: {}", e),
+            )
+        })?;
+        Ok(PublicKey(pk))
+    }
+}
+
+impl BorshSerialize for PublicKey {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.to_compressed_bytes())?;
+        Ok(())
+    }
+}
+
+impl BorshSchema for PublicKey {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        // Encoded as `[u8; COMPRESSED_PUBLIC_KEY_SIZE]`
+        let elements = "u8".into();
+        let length = COMPRESSED_PUBLIC_KEY_SIZE as u64;
+        let definition = borsh::schema::Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=length,
+            elements,
+        };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "secp256r1::PublicKey".into()
+    }
+}
+
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_compressed_bytes().hash(state);
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_compressed_bytes()
+            .cmp(&other.to_compressed_bytes())
+    }
+}
+
+impl Display for PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.to_compressed_bytes()))
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = ParsePublicKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = HEXLOWER
+            .decode(s.as_bytes())
+            .map_err(ParsePublicKeyError::InvalidHex)?;
+        BorshDeserialize::try_from_slice(&vec)
+            .map_err(ParsePublicKeyError::InvalidEncoding)
+    }
+}
+
+impl From<p256::PublicKey> for PublicKey {
+    fn from(pk: p256::PublicKey) -> Self {
+        Self(pk)
+    }
+}
+
+/// Secp256r1 secret key
+#[derive(Debug, Clone)]
+pub struct SecretKey(pub Box<p256::SecretKey>);
+
+impl super::SecretKey for SecretKey {
+    type PublicKey = PublicKey;
+
+    const TYPE: SchemeType = SigScheme::TYPE;
+
+    fn try_from_sk<PK: super::SecretKey>(
+        pk: &PK,
+    ) -> Result<Self, ParseSecretKeyError> {
+        if PK::TYPE == super::common::SecretKey::TYPE {
+            super::common::SecretKey::try_from_sk(pk).and_then(|x| match x {
+                super::common::SecretKey::Secp256r1(epk) => Ok(epk),
+                _ => Err(ParseSecretKeyError::MismatchedScheme),
+            })
+        } else if PK::TYPE == Self::TYPE {
+            Self::try_from_slice(pk.serialize_to_vec().as_slice())
+                .map_err(ParseSecretKeyError::InvalidEncoding)
+        } else {
+            Err(ParseSecretKeyError::MismatchedScheme)
+        }
+    }
+}
+
+impl Serialize for SecretKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let arr: [u8; SECRET_KEY_SIZE] = self.0.to_bytes().into();
+        serde::Serialize::serialize(&arr, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let arr_res: [u8; SECRET_KEY_SIZE] =
+            serde::Deserialize::deserialize(deserializer)?;
+        let key = p256::SecretKey::from_slice(&arr_res)
+            .map_err(D::Error::custom)?;
+        Ok(SecretKey(Box::new(key)))
+    }
+}
+
+impl BorshDeserialize for SecretKey {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        // deserialize the bytes first
+        let bytes: [u8; SECRET_KEY_SIZE] =
+            BorshDeserialize::deserialize_reader(reader)?;
+        let sk = p256::SecretKey::from_slice(&bytes).map_err(|e| {
+            std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Error decoding secp256r1 secret key: {}", e),
+            )
+        })?;
+        Ok(SecretKey(Box::new(sk)))
+    }
+}
+
+impl BorshSerialize for SecretKey {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let bytes: [u8; SECRET_KEY_SIZE] = self.0.to_bytes().into();
+        BorshSerialize::serialize(&bytes, writer)
+    }
+}
+
+impl BorshSchema for SecretKey {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        // Encoded as `[u8; SECRET_KEY_SIZE]`
+        let elements = "u8".into();
+        let length = SECRET_KEY_SIZE as u64;
+        let definition = borsh::schema::Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=length,
+            elements,
+        };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "secp256r1::SecretKey".into()
+    }
+}
+
+impl Display for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.0.to_bytes()))
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = ParseSecretKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = HEXLOWER
+            .decode(s.as_bytes())
+            .map_err(ParseSecretKeyError::InvalidHex)?;
+        BorshDeserialize::try_from_slice(&vec)
+            .map_err(ParseSecretKeyError::InvalidEncoding)
+    }
+}
+
+impl RefTo<PublicKey> for SecretKey {
+    fn ref_to(&self) -> PublicKey {
+        PublicKey(self.0.public_key())
+    }
+}
+
+/// Secp256r1 signature. Only signatures with a normalized (low) `s` are
+/// valid, such that signatures are not malleable.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature(pub p256::ecdsa::Signature);
+
+impl super::Signature for Signature {
+    const TYPE: SchemeType = SigScheme::TYPE;
+
+    fn try_from_sig<PK: super::Signature>(
+        pk: &PK,
+    ) -> Result<Self, ParseSignatureError> {
+        if PK::TYPE == super::common::Signature::TYPE {
+            super::common::Signature::try_from_sig(pk).and_then(|x| match x {
+                super::common::Signature::Secp256r1(epk) => Ok(epk),
+                _ => Err(ParseSignatureError::MismatchedScheme),
+            })
+        } else if PK::TYPE == Self::TYPE {
+            Self::try_from_slice(pk.serialize_to_vec().as_slice())
+                .map_err(ParseSignatureError::InvalidEncoding)
+        } else {
+            Err(ParseSignatureError::MismatchedScheme)
+        }
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let arr = self.0.to_bytes();
+        let mut seq = serializer.serialize_tuple(arr.len())?;
+        for elem in &arr[..] {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ByteArrayVisitor;
+
+        impl<'de> Visitor<'de> for ByteArrayVisitor {
+            type Value = [u8; SIGNATURE_SIZE];
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(&format!(
+                    "an array of length {}",
+                    SIGNATURE_SIZE,
+                ))
+            }
+
+            fn visit_seq<A>(
+                self,
+                mut seq: A,
+            ) -> Result<[u8; SIGNATURE_SIZE], A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut arr = [0u8; SIGNATURE_SIZE];
+                #[allow(clippy::needless_range_loop)]
+                for i in 0..SIGNATURE_SIZE {
+                    arr[i] = seq
+                        .next_element()?
+                        .ok_or_else(|| Error::invalid_length(i, &self))?;
+                }
+                Ok(arr)
+            }
+        }
+
+        let arr_res =
+            deserializer.deserialize_tuple(SIGNATURE_SIZE, ByteArrayVisitor)?;
+        let sig = p256::ecdsa::Signature::from_slice(&arr_res)
+            .map_err(D::Error::custom)?;
+        Ok(Signature(sig))
+    }
+}
+
+impl BorshDeserialize for Signature {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        // deserialize the bytes first
+        let sig_bytes: [u8; SIGNATURE_SIZE] =
+            BorshDeserialize::deserialize_reader(reader)?;
+        let sig =
+            p256::ecdsa::Signature::from_slice(&sig_bytes).map_err(|e| {
+                std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Error decoding secp256r1 signature: {}", e),
+                )
+            })?;
+        Ok(Signature(sig))
+    }
+}
+
+impl BorshSerialize for Signature {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let sig_bytes: [u8; SIGNATURE_SIZE] = self.0.to_bytes().into();
+        BorshSerialize::serialize(&sig_bytes, writer)
+    }
+}
+
+impl BorshSchema for Signature {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        // Encoded as `[u8; SIGNATURE_SIZE]`
+        let elements = "u8".into();
+        let length = SIGNATURE_SIZE as u64;
+        let definition = borsh::schema::Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=length,
+            elements,
+        };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "secp256r1::Signature".into()
+    }
+}
+
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl Hash for Signature {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bytes().hash(state);
+    }
+}
+
+impl PartialOrd for Signature {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Signature {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.to_bytes().cmp(&other.0.to_bytes())
+    }
+}
+
+/// An implementation of the Secp256r1 signature scheme
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Default,
+)]
+pub struct SigScheme;
+
+impl super::SigScheme for SigScheme {
+    type PublicKey = PublicKey;
+    type SecretKey = SecretKey;
+    type Signature = Signature;
+
+    const TYPE: SchemeType = SchemeType::Secp256r1;
+
+    #[cfg(any(test, feature = "rand"))]
+    fn generate<R>(csprng: &mut R) -> SecretKey
+    where
+        R: CryptoRng + RngCore,
+    {
+        SecretKey(Box::new(p256::SecretKey::random(csprng)))
+    }
+
+    fn from_bytes(sk: [u8; 32]) -> SecretKey {
+        SecretKey(Box::new(
+            p256::SecretKey::from_slice(&sk)
+                .expect("Secret key parsing should not fail."),
+        ))
+    }
+
+    fn sign_with_hasher<H>(
+        keypair: &SecretKey,
+        data: impl SignableBytes,
+    ) -> Self::Signature
+    where
+        H: 'static + StorageHasher,
+    {
+        use p256::ecdsa::signature::hazmat::PrehashSigner;
+
+        let sig_key = p256::ecdsa::SigningKey::from(keypair.0.as_ref());
+        let msg = data.signable_hash::<H>();
+        let sig: p256::ecdsa::Signature =
+            sig_key.sign_prehash(&msg).expect("Must be able to sign");
+        Signature(sig.normalize_s().unwrap_or(sig))
+    }
+
+    fn verify_signature_with_hasher<H>(
+        pk: &Self::PublicKey,
+        data: &impl SignableBytes,
+        sig: &Self::Signature,
+    ) -> Result<(), VerifySigError>
+    where
+        H: 'static + StorageHasher,
+    {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        // Reject the high `s` twin of a valid signature
+        if sig.0.normalize_s().is_some() {
+            return Err(VerifySigError::SigVerifyError(
+                "Error verifying secp256r1 signature: the signature is not \
+                 normalized"
+                    .to_string(),
+            ));
+        }
+        let vrf_key = p256::ecdsa::VerifyingKey::from(&pk.0);
+        let msg = data.signable_hash::<H>();
+        vrf_key.verify_prehash(&msg, &sig.0).map_err(|e| {
+            VerifySigError::SigVerifyError(format!(
+                "Error verifying secp256r1 signature: {}",
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A P-256 secret key for the tests
+    const SECRET_KEY_HEX: &str =
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+
+    fn secret_key() -> SecretKey {
+        let sk_bytes = HEXLOWER.decode(SECRET_KEY_HEX.as_bytes()).unwrap();
+        SecretKey::try_from_slice(&sk_bytes[..]).unwrap()
+    }
+
+    /// Test that a signature can be verified with the matching public key
+    /// only.
+    #[test]
+    fn test_sign_and_verify() {
+        let sk = secret_key();
+        let pk: PublicKey = sk.ref_to();
+        let signature = SigScheme::sign(&sk, "test".as_bytes());
+        assert!(
+            SigScheme::verify_signature(&pk, &"test".as_bytes(), &signature)
+                .is_ok()
+        );
+        assert!(
+            SigScheme::verify_signature(&pk, &"tset".as_bytes(), &signature)
+                .is_err()
+        );
+    }
+
+    /// Test that the high `s` twin of a valid signature is rejected.
+    #[test]
+    fn test_reject_high_s() {
+        let sk = secret_key();
+        let pk: PublicKey = sk.ref_to();
+        let signature = SigScheme::sign(&sk, "test".as_bytes());
+        let (r, s) = signature.0.split_scalars();
+        let high_s = p256::ecdsa::Signature::from_scalars(r, -*s).unwrap();
+        assert!(
+            SigScheme::verify_signature(
+                &pk,
+                &"test".as_bytes(),
+                &Signature(high_s)
+            )
+            .is_err()
+        );
+    }
+
+    /// Test that public keys are Borsh encoded as compressed points and
+    /// round-trip.
+    #[test]
+    fn test_roundtrip_pk_borsh() {
+        let pk: PublicKey = secret_key().ref_to();
+        let pk_bytes = pk.serialize_to_vec();
+        assert_eq!(pk_bytes.len(), COMPRESSED_PUBLIC_KEY_SIZE);
+        let decoded = PublicKey::try_from_slice(&pk_bytes).unwrap();
+        assert_eq!(decoded, pk);
+    }
+
+    /// Test serializing and then de-serializing a signature
+    /// with Serde is idempotent.
+    #[test]
+    fn test_roundtrip_serde() {
+        let signature = SigScheme::sign(&secret_key(), "test".as_bytes());
+        let sig_json = serde_json::to_string(&signature).expect("Test failed");
+        let sig: Signature =
+            serde_json::from_str(&sig_json).expect("Test failed");
+        assert_eq!(sig, signature)
+    }
+
+    /// Test serializing and then de-serializing a signature
+    /// with Borsh is idempotent.
+    #[test]
+    fn test_roundtrip_borsh() {
+        let signature = SigScheme::sign(&secret_key(), "test".as_bytes());
+        let sig_bytes = signature.serialize_to_vec();
+        let sig = Signature::try_from_slice(sig_bytes.as_slice())
+            .expect("Test failed");
+        assert_eq!(sig, signature);
+    }
+}
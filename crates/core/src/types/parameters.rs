@@ -30,6 +30,9 @@ pub struct Parameters {
     pub epoch_duration: EpochDuration,
     /// Maximum expected time per block (read only)
     pub max_expected_time_per_block: DurationSecs,
+    /// Max time between a block and the expiration of the wrapper txs it
+    /// includes, after which their replay protection entries are pruned
+    pub tx_expiration_window: DurationSecs,
     /// Max payload size, in bytes, for a tx batch proposal.
     pub max_proposal_bytes: ProposalBytes,
     /// Max gas for block
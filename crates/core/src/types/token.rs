@@ -1048,6 +1048,86 @@ pub struct Transfer {
     pub shielded: Option<Hash>,
 }
 
+/// A leg of a [`MultiTransfer`]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct TransferLeg {
+    /// Source address will spend the tokens
+    pub source: Address,
+    /// Target address will receive the tokens
+    pub target: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount of tokens
+    pub amount: DenominatedAmount,
+}
+
+/// The event emitted when a leg of a [`MultiTransfer`] is applied
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct TransferLegEvent {
+    /// The index of the leg in its multi-transfer
+    pub index: u64,
+    /// The applied leg
+    pub leg: TransferLeg,
+}
+
+/// A batch of transparent token transfers from one or more sources to
+/// many targets, which are applied atomically
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct MultiTransfer {
+    /// The transfers to apply, in order
+    pub legs: Vec<TransferLeg>,
+}
+
+impl MultiTransfer {
+    /// Get the total amount of each token debited from each source
+    pub fn debits(&self) -> BTreeMap<(&Address, &Address), Amount> {
+        let mut debits: BTreeMap<_, Amount> = BTreeMap::new();
+        for leg in &self.legs {
+            if leg.source == leg.target {
+                continue;
+            }
+            let debit = debits.entry((&leg.source, &leg.token)).or_default();
+            *debit += leg.amount.amount();
+        }
+        debits
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum TransferError {
@@ -1107,6 +1187,32 @@ pub mod testing {
         }
     }
 
+    prop_compose! {
+        /// Generate a leg of a multi-transfer
+        pub fn arb_transfer_leg()(
+            source in arb_non_internal_address(),
+            target in arb_non_internal_address(),
+            token in arb_established_address().prop_map(Address::Established),
+            amount in arb_denominated_amount(),
+        ) -> TransferLeg {
+            TransferLeg {
+                source,
+                target,
+                token,
+                amount,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate a multi-transfer
+        pub fn arb_multi_transfer()(
+            legs in proptest::collection::vec(arb_transfer_leg(), 1..10),
+        ) -> MultiTransfer {
+            MultiTransfer { legs }
+        }
+    }
+
     /// Generate an arbitrary token amount
     pub fn arb_amount() -> impl Strategy<Value = Amount> {
         any::<u64>().prop_map(|val| Amount::from_uint(val, 0).unwrap())
@@ -1129,6 +1235,7 @@ pub mod testing {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::address;
 
     #[test]
     fn test_token_display() {
@@ -1386,4 +1493,31 @@ mod tests {
             Ordering::Less
         );
     }
+
+    #[test]
+    fn test_multi_transfer_debits() {
+        let source = address::testing::established_address_1();
+        let other = address::testing::established_address_2();
+        let token = address::nam();
+        let amount = |val| DenominatedAmount {
+            amount: Amount::from_uint(val, 0).expect("Test failed"),
+            denom: 0.into(),
+        };
+        let leg = |target: &Address, val| TransferLeg {
+            source: source.clone(),
+            target: target.clone(),
+            token: token.clone(),
+            amount: amount(val),
+        };
+        let multi = MultiTransfer {
+            legs: vec![leg(&other, 1), leg(&other, 2), leg(&source, 4)],
+        };
+        assert_eq!(
+            multi.debits(),
+            BTreeMap::from([(
+                (&source, &token),
+                Amount::from_uint(3, 0).expect("Test failed")
+            )])
+        );
+    }
 }
@@ -17,7 +17,9 @@ use crate::protocol::transactions::utils::GetVoters;
 use crate::protocol::transactions::votes::update::NewVotes;
 use crate::protocol::transactions::votes::{calculate_new, Votes};
 use crate::protocol::transactions::{utils, votes, ChangedKeys};
-use crate::storage::bridge_pool::get_signed_root_key;
+use crate::storage::bridge_pool::{
+    get_min_signed_root_height_key, get_signed_root_key,
+};
 use crate::storage::eth_bridge_queries::EthBridgeQueries;
 use crate::storage::proof::BridgePoolRootProof;
 use crate::storage::vote_tallies::{self, BridgePoolRoot};
@@ -122,7 +124,22 @@ where
                 // if no signed root was present in storage, write the new one
                 true
             });
-        if should_write_root {
+        // the older roots may contain refunded transfers
+        let min_root_height = wl_storage
+            .read::<BlockHeight>(&get_min_signed_root_height_key())
+            .expect(
+                "Reading the minimum height of the signed Bridge pool roots \
+                 from storage should not fail",
+            )
+            .unwrap_or(BlockHeight(0));
+        if should_write_root && root_height < min_root_height {
+            tracing::debug!(
+                ?root_height,
+                ?min_root_height,
+                "Discarding Bridge pool root proof which may contain refunded \
+                 transfers"
+            );
+        } else if should_write_root {
             tracing::debug!(
                 ?root_height,
                 "New Bridge pool root proof acquired"
@@ -767,6 +784,49 @@ mod test_apply_bp_roots_to_storage {
         assert_eq!(proof.data, expected.data);
     }
 
+    #[test]
+    /// Test that we do not store a complete proof of a root which
+    /// is older than the minimum signed root height, as it might
+    /// contain transfers that have already been refunded.
+    fn test_quorum_below_min_root_height() {
+        let TestPackage {
+            validators,
+            keys,
+            mut wl_storage,
+        } = setup();
+        wl_storage
+            .write(&get_min_signed_root_height_key(), BlockHeight(101))
+            .expect("Test failed");
+        let root = wl_storage.ethbridge_queries().get_bridge_pool_root();
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        let to_sign = keccak_hash([root.0, nonce.to_bytes()].concat());
+
+        let mut vexts = MultiSignedVext(Default::default());
+        for validator in &validators[..2] {
+            let hot_key = &keys[validator].eth_bridge;
+            vexts.insert(
+                bridge_pool_roots::Vext {
+                    validator_addr: validator.clone(),
+                    block_height: 100.into(),
+                    sig: Signed::<_, SignableEthMessage>::new(
+                        hot_key,
+                        to_sign.clone(),
+                    )
+                    .sig,
+                }
+                .sign(&keys[validator].protocol),
+            );
+        }
+
+        _ = apply_derived_tx(&mut wl_storage, vexts).expect("Test failed");
+        assert!(
+            wl_storage
+                .read_bytes(&get_signed_root_key())
+                .expect("Test failed")
+                .is_none()
+        );
+    }
+
     /// Test that when we acquire a complete BP roots proof,
     /// the block height stored in storage is that of the
     /// tree root that was decided.
@@ -18,15 +18,20 @@ use namada_core::types::ethereum_events::{
     TransfersToNamada,
 };
 use namada_core::types::ethereum_structs::EthBridgeEvent;
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::storage::{BlockHeight, Key, KeySeg};
+use namada_core::types::token::Amount;
 use namada_parameters::read_epoch_duration_parameter;
+use namada_state::write_log::StorageModification;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
 use namada_storage::{StorageRead, StorageWrite};
 use namada_trans_token::storage_key::{balance_key, minted_balance_key};
 
 use crate::protocol::transactions::update;
 use crate::storage::bridge_pool::{
-    get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
+    get_cancelled_prefix, get_gas_fee_bump_key, get_min_signed_root_height_key,
+    get_nonce_key, get_pending_key, is_pending_transfer_key,
+    BRIDGE_POOL_ADDRESS,
 };
 use crate::storage::eth_bridge_queries::{EthAssetMint, EthBridgeQueries};
 use crate::storage::parameters::read_native_erc20_address;
@@ -338,6 +343,13 @@ where
             wl_storage.ethbridge_queries().lookup_transfer_to_eth(event)
         {
             (pending, key)
+        } else if let Some((pending, key)) = wl_storage
+            .ethbridge_queries()
+            .lookup_cancelled_transfer_to_eth(event)
+        {
+            // NB: the transfer was cancelled after having been signed
+            // over in a Bridge pool root, so it could still be relayed
+            (pending, key)
         } else {
             hints::cold();
             unreachable!("The transfer should exist in the bridge pool");
@@ -355,13 +367,19 @@ where
             balance_key(&pending_transfer.gas_fee.token, &BRIDGE_POOL_ADDRESS);
         let relayer_rewards_key =
             balance_key(&pending_transfer.gas_fee.token, relayer);
+        let gas_fee = pending_transfer.gas_fee.amount
+            + take_gas_fee_bump(
+                wl_storage,
+                &pending_transfer,
+                &mut changed_keys,
+            )?;
         // give the relayer the gas fee for this transfer.
         update::amount(wl_storage, &relayer_rewards_key, |balance| {
-            balance.receive(&pending_transfer.gas_fee.amount);
+            balance.receive(&gas_fee);
         })?;
         // the gas fee is removed from escrow.
         update::amount(wl_storage, &pool_balance_key, |balance| {
-            balance.spend(&pending_transfer.gas_fee.amount);
+            balance.spend(&gas_fee);
         })?;
         wl_storage.delete(&key)?;
         _ = pending_keys.remove(&key);
@@ -373,6 +391,25 @@ where
         ));
     }
 
+    // Refund the cancelled transfers which were part of a signed root, since
+    // they can no longer be relayed once the nonce has been incremented
+    let cancelled_keys: Vec<Key> = wl_storage
+        .iter_prefix(&get_cancelled_prefix())
+        .context("Failed to iterate over storage")?
+        .map(|(k, _, _)| {
+            Key::from_str(k.as_str()).expect("Key should be parsable")
+        })
+        .collect();
+    for key in cancelled_keys {
+        let (mut keys, mut new_tx_events) = refund_transfer(
+            wl_storage,
+            key,
+            EthBridgeEvent::new_bridge_pool_cancelled,
+        )?;
+        changed_keys.append(&mut keys);
+        tx_events.append(&mut new_tx_events);
+    }
+
     if pending_keys.is_empty() {
         return Ok((changed_keys, tx_events));
     }
@@ -391,8 +428,11 @@ where
             )
             .expect("BlockHeight should be decoded");
             if inserted_height <= timeout_height {
-                let (mut keys, mut new_tx_events) =
-                    refund_transfer(wl_storage, key)?;
+                let (mut keys, mut new_tx_events) = refund_transfer(
+                    wl_storage,
+                    key,
+                    EthBridgeEvent::new_bridge_pool_expired,
+                )?;
                 changed_keys.append(&mut keys);
                 tx_events.append(&mut new_tx_events);
             }
@@ -402,6 +442,91 @@ where
     Ok((changed_keys, tx_events))
 }
 
+/// Refund the transfers cancelled in the current block which were never part
+/// of a signed Bridge pool root with the current nonce. The other cancelled
+/// transfers could still be relayed, so they are refunded once the nonce has
+/// been incremented.
+///
+/// The roots of the previous blocks may contain the refunded transfers, thus
+/// only the roots from the current height onwards can be signed afterwards.
+pub fn refund_unsigned_cancelled_transfers<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> Result<(BTreeSet<Key>, BTreeSet<EthBridgeEvent>)>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let mut changed_keys = BTreeSet::default();
+    let mut tx_events = BTreeSet::default();
+
+    let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+    let signed_height = wl_storage
+        .ethbridge_queries()
+        .get_signed_bridge_pool_root()
+        .and_then(|(proof, height)| {
+            let (_, signed_nonce) = proof.data;
+            (signed_nonce == nonce).then_some(height)
+        });
+    // the transfers cancelled in the current block
+    let cancelled_keys: Vec<Key> = wl_storage
+        .iter_prefix(&get_cancelled_prefix())
+        .context("Failed to iterate over storage")?
+        .map(|(k, _, _)| {
+            Key::from_str(k.as_str()).expect("Key should be parsable")
+        })
+        .filter(|key| {
+            matches!(
+                wl_storage.write_log.read(key).0,
+                Some(StorageModification::Write { .. })
+            )
+        })
+        .collect();
+
+    let mut refunded_from_root = false;
+    for key in cancelled_keys {
+        let transfer: PendingTransfer = wl_storage
+            .read(&key)?
+            .expect("The cancelled transfer should be in storage");
+        // the height at which the transfer was added to the Bridge pool
+        // tree, if it was committed before being cancelled
+        let pending_key = get_pending_key(&transfer);
+        let tree = &wl_storage.storage.block.tree;
+        let inserted_height = if tree.has_key(&pending_key)? {
+            Some(BlockHeight::try_from_slice(&tree.get(&pending_key)?)?)
+        } else {
+            None
+        };
+        if matches!(
+            (inserted_height, signed_height),
+            (Some(inserted), Some(signed)) if inserted <= signed
+        ) {
+            tracing::debug!(
+                ?transfer,
+                "Deferring the refund of a cancelled transfer which was part \
+                 of a signed Bridge pool root"
+            );
+            continue;
+        }
+        refunded_from_root |= inserted_height.is_some();
+        let (mut keys, mut new_tx_events) = refund_transfer(
+            wl_storage,
+            key,
+            EthBridgeEvent::new_bridge_pool_cancelled,
+        )?;
+        changed_keys.append(&mut keys);
+        tx_events.append(&mut new_tx_events);
+    }
+
+    if refunded_from_root {
+        let min_height_key = get_min_signed_root_height_key();
+        let height = wl_storage.storage.block.height;
+        wl_storage.write(&min_height_key, height)?;
+        _ = changed_keys.insert(min_height_key);
+    }
+
+    Ok((changed_keys, tx_events))
+}
+
 fn increment_bp_nonce<D, H>(
     nonce_key: &Key,
     wl_storage: &mut WlStorage<D, H>,
@@ -419,9 +544,12 @@ where
     Ok(())
 }
 
+/// Refund the transfer stored under the given key and remove it from
+/// storage, emitting the given event.
 fn refund_transfer<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     key: Key,
+    event: fn(KeccakHash) -> EthBridgeEvent,
 ) -> Result<(BTreeSet<Key>, BTreeSet<EthBridgeEvent>)>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -441,10 +569,8 @@ where
     wl_storage.delete(&key)?;
     _ = changed_keys.insert(key);
 
-    // Emit expiration event
-    _ = tx_events.insert(EthBridgeEvent::new_bridge_pool_expired(
-        transfer.keccak256(),
-    ));
+    // Emit the refund event
+    _ = tx_events.insert(event(transfer.keccak256()));
 
     Ok((changed_keys, tx_events))
 }
@@ -459,15 +585,17 @@ where
 {
     let mut changed_keys = BTreeSet::default();
 
+    let gas_fee = transfer.gas_fee.amount
+        + take_gas_fee_bump(wl_storage, transfer, &mut changed_keys)?;
     let payer_balance_key =
         balance_key(&transfer.gas_fee.token, &transfer.gas_fee.payer);
     let pool_balance_key =
         balance_key(&transfer.gas_fee.token, &BRIDGE_POOL_ADDRESS);
     update::amount(wl_storage, &payer_balance_key, |balance| {
-        balance.receive(&transfer.gas_fee.amount);
+        balance.receive(&gas_fee);
     })?;
     update::amount(wl_storage, &pool_balance_key, |balance| {
-        balance.spend(&transfer.gas_fee.amount);
+        balance.spend(&gas_fee);
     })?;
 
     tracing::debug!(?transfer, "Refunded Bridge pool transfer fees");
@@ -476,6 +604,26 @@ where
     Ok(changed_keys)
 }
 
+/// Remove the gas fees added to a transfer after it entered the Bridge
+/// pool, returning their amount.
+fn take_gas_fee_bump<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    transfer: &PendingTransfer,
+    changed_keys: &mut BTreeSet<Key>,
+) -> Result<Amount>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let key = get_gas_fee_bump_key(&transfer.keccak256());
+    let Some(bump) = wl_storage.read::<Amount>(&key)? else {
+        return Ok(Amount::zero());
+    };
+    wl_storage.delete(&key)?;
+    _ = changed_keys.insert(key);
+    Ok(bump)
+}
+
 fn refund_transferred_assets<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     transfer: &PendingTransfer,
@@ -596,7 +744,10 @@ mod tests {
     use namada_storage::mockdb::MockDBWriteBatch;
 
     use super::*;
-    use crate::storage::bridge_pool::get_pending_key;
+    use crate::storage::bridge_pool::{
+        get_cancelled_key, get_pending_key, get_signed_root_key,
+    };
+    use crate::storage::proof::BridgePoolRootProof;
     use crate::storage::wrapped_erc20s;
     use crate::test_utils::{self, stored_keys_count};
 
@@ -1243,6 +1394,183 @@ mod tests {
         }
     }
 
+    /// Test that cancelled transfers, along with their gas fee bumps,
+    /// are refunded once the next batch of transfers is relayed.
+    #[test]
+    fn test_act_on_refunds_cancelled_transfers() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        let pending_transfers = init_bridge_pool(&mut wl_storage);
+        init_balance(&mut wl_storage, &pending_transfers);
+        wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+        wl_storage.storage.block.height += 1;
+
+        // cancel all the pending transfers, and bump
+        // the gas fees of the first one
+        for transfer in &pending_transfers {
+            let hash = transfer.keccak256();
+            wl_storage
+                .delete(&get_pending_key(transfer))
+                .expect("Test failed");
+            wl_storage
+                .write(&get_cancelled_key(&hash), transfer)
+                .expect("Test failed");
+        }
+        let bumped = &pending_transfers[0];
+        let bump = Amount::from(2);
+        wl_storage
+            .write(&get_gas_fee_bump_key(&bumped.keccak256()), bump)
+            .expect("Test failed");
+        let pool_key = balance_key(&bumped.gas_fee.token, &BRIDGE_POOL_ADDRESS);
+        update::amount(&mut wl_storage, &pool_key, |balance| {
+            balance.receive(&bump);
+        })
+        .expect("Test failed");
+
+        let event = EthereumEvent::TransfersToEthereum {
+            nonce: arbitrary_nonce(),
+            transfers: vec![],
+            relayer: gen_implicit_address(),
+        };
+        let (_, tx_events) = act_on(&mut wl_storage, event).unwrap();
+
+        // all the cancelled transfers were refunded
+        assert_eq!(
+            wl_storage
+                .iter_prefix(&get_cancelled_prefix())
+                .expect("Test failed")
+                .count(),
+            0
+        );
+        assert!(
+            !wl_storage
+                .has_key(&get_gas_fee_bump_key(&bumped.keccak256()))
+                .expect("Test failed")
+        );
+        for transfer in &pending_transfers {
+            assert!(tx_events.contains(
+                &EthBridgeEvent::new_bridge_pool_cancelled(
+                    transfer.keccak256()
+                )
+            ));
+            let token = transfer.token_address();
+            let sender_key = balance_key(&token, &transfer.transfer.sender);
+            let sender_balance: Amount = wl_storage
+                .read(&sender_key)
+                .expect("Test failed")
+                .expect("Test failed");
+            assert_eq!(sender_balance, transfer.transfer.amount);
+        }
+
+        // the gas fees and their bump were refunded to the payer
+        let expected = pending_transfers
+            .iter()
+            .fold(bump, |acc, t| acc + t.gas_fee.amount);
+        let payer = address::testing::established_address_2();
+        let payer_balance: Amount = wl_storage
+            .read(&balance_key(&nam(), &payer))
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(payer_balance, expected);
+        let pool_balance: Amount = wl_storage
+            .read(&balance_key(&nam(), &BRIDGE_POOL_ADDRESS))
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(pool_balance, Amount::from(0));
+    }
+
+    /// Test that cancelled transfers are refunded right away, unless
+    /// they are part of the latest signed Bridge pool root.
+    #[test]
+    fn test_refund_unsigned_cancelled_transfers() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        let inserted_height = wl_storage.storage.block.height;
+        let pending_transfers = init_bridge_pool(&mut wl_storage);
+        init_balance(&mut wl_storage, &pending_transfers);
+        wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+        wl_storage.storage.block.height += 1;
+
+        let write_signed_root = |wl_storage: &mut TestWlStorage, nonce| {
+            let proof = BridgePoolRootProof::new((KeccakHash([1; 32]), nonce));
+            wl_storage
+                .write(&get_signed_root_key(), (proof, inserted_height))
+                .expect("Test failed");
+        };
+        let cancelled_count = |wl_storage: &TestWlStorage| {
+            wl_storage
+                .iter_prefix(&get_cancelled_prefix())
+                .expect("Test failed")
+                .count()
+        };
+
+        // the latest signed root contains the pending transfers
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        write_signed_root(&mut wl_storage, nonce);
+        for transfer in &pending_transfers {
+            let hash = transfer.keccak256();
+            wl_storage
+                .delete(&get_pending_key(transfer))
+                .expect("Test failed");
+            wl_storage
+                .write(&get_cancelled_key(&hash), transfer)
+                .expect("Test failed");
+        }
+
+        // the refunds are deferred
+        let (_, tx_events) =
+            refund_unsigned_cancelled_transfers(&mut wl_storage)
+                .expect("Test failed");
+        assert!(tx_events.is_empty());
+        assert_eq!(cancelled_count(&wl_storage), pending_transfers.len());
+        assert!(
+            !wl_storage
+                .has_key(&get_min_signed_root_height_key())
+                .expect("Test failed")
+        );
+
+        // the latest signed root is for another nonce, so the
+        // transfers were never signed
+        let other_nonce = nonce.checked_increment().expect("Test failed");
+        write_signed_root(&mut wl_storage, other_nonce);
+
+        // the transfers are refunded, and older roots can no
+        // longer be signed
+        let (_, tx_events) =
+            refund_unsigned_cancelled_transfers(&mut wl_storage)
+                .expect("Test failed");
+        assert_eq!(cancelled_count(&wl_storage), 0);
+        for transfer in &pending_transfers {
+            assert!(tx_events.contains(
+                &EthBridgeEvent::new_bridge_pool_cancelled(
+                    transfer.keccak256()
+                )
+            ));
+            let token = transfer.token_address();
+            let sender_key = balance_key(&token, &transfer.transfer.sender);
+            let sender_balance: Amount = wl_storage
+                .read(&sender_key)
+                .expect("Test failed")
+                .expect("Test failed");
+            assert_eq!(sender_balance, transfer.transfer.amount);
+        }
+        let min_height: BlockHeight = wl_storage
+            .read(&get_min_signed_root_height_key())
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(min_height, wl_storage.storage.block.height);
+    }
+
     #[test]
     fn test_redeem_native_token() -> Result<()> {
         let mut wl_storage = TestWlStorage::default();
@@ -3,6 +3,8 @@
 mod eth_msgs;
 mod events;
 
+pub use events::refund_unsigned_cancelled_transfers;
+
 use std::collections::{BTreeSet, HashMap, HashSet};
 
 use borsh::BorshDeserialize;
@@ -3,8 +3,9 @@
 
 use namada_core::types::eth_bridge_pool::Segments;
 pub use namada_core::types::eth_bridge_pool::{
-    get_key_from_hash, get_pending_key, is_pending_transfer_key,
-    BRIDGE_POOL_ADDRESS,
+    get_cancelled_key, get_cancelled_prefix, get_gas_fee_bump_key,
+    get_gas_fee_bump_prefix, get_key_from_hash, get_pending_key,
+    is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
 };
 use namada_core::types::storage::{DbKeySeg, Key};
 pub use namada_state::merkle_tree::eth_bridge_pool::BridgePoolTree;
@@ -20,6 +21,20 @@ pub fn get_signed_root_key() -> Key {
     }
 }
 
+/// Get the storage key for the minimum block height of the Bridge pool
+/// roots which can be signed. The roots below this height contain
+/// transfers which were refunded after being cancelled.
+pub fn get_min_signed_root_height_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(
+                Segments::VALUES.min_signed_root_height.into(),
+            ),
+        ],
+    }
+}
+
 /// Get the storage key for the batch nonce of
 /// the bridge pool. Used for replay protection.
 pub fn get_nonce_key() -> Key {
@@ -35,3 +50,11 @@ pub fn get_nonce_key() -> Key {
 pub fn is_bridge_pool_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &BRIDGE_POOL_ADDRESS)
 }
+
+/// Check if a key is for the gas fee bump or the cancellation of a pending
+/// transfer, which are only written when a transfer is updated
+pub fn is_transfer_update_key(key: &Key) -> bool {
+    [get_gas_fee_bump_prefix(), get_cancelled_prefix()]
+        .iter()
+        .any(|prefix| key.split_prefix(prefix).is_some())
+}
@@ -535,6 +535,21 @@ where
             .zip(Some(pending_key))
     }
 
+    /// Given a [`TransferToEthereum`] event, look-up the corresponding
+    /// [`PendingTransfer`] which was cancelled, but whose refund is
+    /// still pending.
+    pub fn lookup_cancelled_transfer_to_eth(
+        self,
+        transfer: &TransferToEthereum,
+    ) -> Option<(PendingTransfer, StorageKey)> {
+        let cancelled_key =
+            bridge_pool::get_cancelled_key(&transfer.keccak256());
+        self.wl_storage
+            .read(&cancelled_key)
+            .expect("Reading from storage should not fail")
+            .zip(Some(cancelled_key))
+    }
+
     /// Valdidate an [`EthereumEvent`]'s nonce against the current
     /// state of the ledger.
     ///
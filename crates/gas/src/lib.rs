@@ -1,10 +1,13 @@
 //! Gas accounting module to track the gas usage in a block for transactions and
 //! validity predicates triggered by transactions.
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::ops::Div;
 
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::key::common;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -45,6 +48,9 @@ pub const STORAGE_WRITE_GAS_PER_BYTE: u64 =
     MEMORY_ACCESS_GAS_PER_BYTE + 848 + STORAGE_OCCUPATION_GAS_PER_BYTE;
 /// The cost of verifying a single signature of a transaction
 pub const VERIFY_TX_SIG_GAS: u64 = 9_793;
+/// The cost of verifying a single secp256r1 (P-256) signature of a
+/// transaction
+pub const VERIFY_TX_SIG_P256_GAS: u64 = 24_483;
 /// The cost for requesting one more page in wasm (64KiB)
 pub const WASM_MEMORY_PAGE_GAS: u32 =
     MEMORY_ACCESS_GAS_PER_BYTE as u32 * 64 * 1_024;
@@ -58,6 +64,17 @@ pub const MASP_VERIFY_SHIELDED_TX_GAS: u64 = 62_381_957;
 /// Gas module result for functions that may fail
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Get the cost of verifying a single transaction signature made with the
+/// given public key
+pub fn verify_tx_sig_gas(pk: &common::PublicKey) -> u64 {
+    match pk {
+        common::PublicKey::Ed25519(_) | common::PublicKey::Secp256k1(_) => {
+            VERIFY_TX_SIG_GAS
+        }
+        common::PublicKey::Secp256r1(_) => VERIFY_TX_SIG_P256_GAS,
+    }
+}
+
 /// Decimal scale of Gas units
 const SCALE: u64 = 10_000;
 
@@ -138,16 +155,134 @@ impl Display for Gas {
     }
 }
 
+/// The categories of costs reported by a [`GasProfile`]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum GasCategory {
+    /// Validation, block space and network transmission of the wrapper
+    Wrapper,
+    /// Validation and compilation of wasm code
+    Compilation,
+    /// Pages of memory requested by wasm code
+    MemoryPages,
+    /// Reads from the write log and the storage
+    StorageRead,
+    /// Writes and deletions in the write log
+    StorageWrite,
+    /// Verification of signatures
+    SignatureVerification,
+    /// Execution and validation of IBC actions
+    Ibc,
+    /// Verification of shielded transfers
+    Masp,
+    /// Memory accesses and any other cost
+    Other,
+}
+
+impl Display for GasCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let category = match self {
+            GasCategory::Wrapper => "wrapper",
+            GasCategory::Compilation => "compilation",
+            GasCategory::MemoryPages => "memory pages",
+            GasCategory::StorageRead => "storage read",
+            GasCategory::StorageWrite => "storage write",
+            GasCategory::SignatureVerification => "signature verification",
+            GasCategory::Ibc => "ibc",
+            GasCategory::Masp => "masp",
+            GasCategory::Other => "other",
+        };
+        write!(f, "{category}")
+    }
+}
+
+/// A breakdown of the gas charged to a transaction, by category and by
+/// validity predicate.
+///
+/// The charges of the VPs are reported before the discount applied to
+/// parallel runs, so the sum of the categories can exceed the gas actually
+/// used by the transaction.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct GasProfile {
+    /// The gas charged for each category of cost
+    pub categories: BTreeMap<GasCategory, Gas>,
+    /// The gas charged by each validity predicate
+    pub vps: BTreeMap<Address, Gas>,
+}
+
+impl GasProfile {
+    /// Record a charge of the given category
+    fn record(&mut self, category: GasCategory, gas: Gas) -> Result<()> {
+        let total = self.categories.entry(category).or_default();
+        *total = total.checked_add(gas).ok_or(Error::GasOverflow)?;
+        Ok(())
+    }
+
+    /// Merge another profile into this one
+    pub fn merge(&mut self, other: GasProfile) -> Result<()> {
+        for (category, gas) in other.categories {
+            self.record(category, gas)?;
+        }
+        for (vp, gas) in other.vps {
+            let total = self.vps.entry(vp).or_default();
+            *total = total.checked_add(gas).ok_or(Error::GasOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for GasProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (category, gas) in &self.categories {
+            writeln!(f, "{category}: {gas}")?;
+        }
+        for (vp, gas) in &self.vps {
+            writeln!(f, "VP of {vp}: {gas}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Trait to share gas operations for transactions and validity predicates
 pub trait GasMetering {
+    /// Add gas cost of the given category. It will return error when the
+    /// consumed gas exceeds the provided transaction gas limit, but the state
+    /// will still be updated
+    fn consume_as(&mut self, category: GasCategory, gas: u64) -> Result<()>;
+
     /// Add gas cost. It will return error when the
     /// consumed gas exceeds the provided transaction gas limit, but the state
     /// will still be updated
-    fn consume(&mut self, gas: u64) -> Result<()>;
+    fn consume(&mut self, gas: u64) -> Result<()> {
+        self.consume_as(GasCategory::Other, gas)
+    }
 
     /// Add the compiling cost proportionate to the code length
     fn add_compiling_gas(&mut self, bytes_len: u64) -> Result<()> {
-        self.consume(
+        self.consume_as(
+            GasCategory::Compilation,
             bytes_len
                 .checked_mul(COMPILE_GAS_PER_BYTE)
                 .ok_or(Error::GasOverflow)?,
@@ -156,7 +291,8 @@ pub trait GasMetering {
 
     /// Add the gas for loading the wasm code from storage
     fn add_wasm_load_from_storage_gas(&mut self, bytes_len: u64) -> Result<()> {
-        self.consume(
+        self.consume_as(
+            GasCategory::StorageRead,
             bytes_len
                 .checked_mul(STORAGE_ACCESS_GAS_PER_BYTE)
                 .ok_or(Error::GasOverflow)?,
@@ -165,7 +301,8 @@ pub trait GasMetering {
 
     /// Add the gas for validating untrusted wasm code
     fn add_wasm_validation_gas(&mut self, bytes_len: u64) -> Result<()> {
-        self.consume(
+        self.consume_as(
+            GasCategory::Compilation,
             bytes_len
                 .checked_mul(WASM_CODE_VALIDATION_GAS_PER_BYTE)
                 .ok_or(Error::GasOverflow)?,
@@ -185,6 +322,8 @@ pub struct TxGasMeter {
     /// The gas limit for a transaction
     pub tx_gas_limit: Gas,
     transaction_gas: Gas,
+    /// The breakdown of the charges, only recorded when profiling
+    profile: Option<GasProfile>,
 }
 
 /// Gas metering in a validity predicate
@@ -196,6 +335,8 @@ pub struct VpGasMeter {
     initial_gas: Gas,
     /// The current gas usage in the VP
     current_gas: Gas,
+    /// The breakdown of the charges, only recorded when profiling
+    profile: Option<BTreeMap<GasCategory, Gas>>,
 }
 
 /// Gas meter for VPs parallel runs
@@ -212,20 +353,18 @@ pub struct VpGasMeter {
 pub struct VpsGas {
     max: Gas,
     rest: Vec<Gas>,
+    /// The profile of the VPs, only recorded when profiling
+    #[borsh(skip)]
+    #[serde(skip)]
+    profile: Option<GasProfile>,
 }
 
 impl GasMetering for TxGasMeter {
-    fn consume(&mut self, gas: u64) -> Result<()> {
-        self.transaction_gas = self
-            .transaction_gas
-            .checked_add(gas.into())
-            .ok_or(Error::GasOverflow)?;
-
-        if self.transaction_gas > self.tx_gas_limit {
-            return Err(Error::TransactionGasExceededError);
+    fn consume_as(&mut self, category: GasCategory, gas: u64) -> Result<()> {
+        if let Some(profile) = self.profile.as_mut() {
+            profile.record(category, gas.into())?;
         }
-
-        Ok(())
+        self.charge(gas)
     }
 
     fn get_tx_consumed_gas(&self) -> Gas {
@@ -244,6 +383,7 @@ impl TxGasMeter {
         Self {
             tx_gas_limit: tx_gas_limit.into(),
             transaction_gas: Gas::default(),
+            profile: None,
         }
     }
 
@@ -253,7 +393,33 @@ impl TxGasMeter {
         Self {
             tx_gas_limit,
             transaction_gas: Gas::default(),
+            profile: None,
+        }
+    }
+
+    /// Start recording a [`GasProfile`] of the charges. The VP gas meters
+    /// created from this meter record their charges too.
+    pub fn enable_profiling(&mut self) {
+        self.profile.get_or_insert_with(GasProfile::default);
+    }
+
+    /// Take the recorded [`GasProfile`], if profiling was enabled
+    pub fn take_profile(&mut self) -> Option<GasProfile> {
+        self.profile.take()
+    }
+
+    /// Add gas cost without recording it in the profile
+    fn charge(&mut self, gas: u64) -> Result<()> {
+        self.transaction_gas = self
+            .transaction_gas
+            .checked_add(gas.into())
+            .ok_or(Error::GasOverflow)?;
+
+        if self.transaction_gas > self.tx_gas_limit {
+            return Err(Error::TransactionGasExceededError);
         }
+
+        Ok(())
     }
 
     /// Add the gas required by a wrapper transaction which is comprised of:
@@ -262,10 +428,11 @@ impl TxGasMeter {
     ///  - cost of downloading (as part of the block) the transaction bytes over
     ///    the network
     pub fn add_wrapper_gas(&mut self, tx_bytes: &[u8]) -> Result<()> {
-        self.consume(WRAPPER_TX_VALIDATION_GAS)?;
+        self.consume_as(GasCategory::Wrapper, WRAPPER_TX_VALIDATION_GAS)?;
 
         let bytes_len = tx_bytes.len() as u64;
-        self.consume(
+        self.consume_as(
+            GasCategory::Wrapper,
             bytes_len
                 .checked_mul(
                     STORAGE_OCCUPATION_GAS_PER_BYTE
@@ -276,8 +443,14 @@ impl TxGasMeter {
     }
 
     /// Add the gas cost used in validity predicates to the current transaction.
+    /// The charges of the VPs are already categorized in their profile.
     pub fn add_vps_gas(&mut self, vps_gas: &VpsGas) -> Result<()> {
-        self.consume(vps_gas.get_current_gas()?.into())
+        if let (Some(profile), Some(vps_profile)) =
+            (self.profile.as_mut(), vps_gas.profile.as_ref())
+        {
+            profile.merge(vps_profile.clone())?;
+        }
+        self.charge(vps_gas.get_current_gas()?.into())
     }
 
     /// Get the amount of gas still available to the transaction
@@ -289,7 +462,11 @@ impl TxGasMeter {
 }
 
 impl GasMetering for VpGasMeter {
-    fn consume(&mut self, gas: u64) -> Result<()> {
+    fn consume_as(&mut self, category: GasCategory, gas: u64) -> Result<()> {
+        if let Some(profile) = self.profile.as_mut() {
+            let total = profile.entry(category).or_default();
+            *total = total.checked_add(gas.into()).ok_or(Error::GasOverflow)?;
+        }
         self.current_gas = self
             .current_gas
             .checked_add(gas.into())
@@ -323,11 +500,40 @@ impl VpGasMeter {
             tx_gas_limit: tx_gas_meter.tx_gas_limit,
             initial_gas: tx_gas_meter.transaction_gas,
             current_gas: Gas::default(),
+            profile: tx_gas_meter.profile.as_ref().map(|_| BTreeMap::new()),
         }
     }
+
+    /// Get the gas consumed by the VP so far
+    pub fn get_vp_consumed_gas(&self) -> Gas {
+        self.current_gas
+    }
 }
 
 impl VpsGas {
+    /// Record the charges of the VP of the given address in the profile of
+    /// the VPs, if its gas meter was profiling. Must be called before
+    /// [`VpsGas::set`].
+    pub fn record_profile(
+        &mut self,
+        address: &Address,
+        vp_gas_meter: &VpGasMeter,
+    ) -> Result<()> {
+        if let Some(categories) = &vp_gas_meter.profile {
+            let mut vp_profile = GasProfile {
+                categories: categories.clone(),
+                vps: BTreeMap::new(),
+            };
+            vp_profile
+                .vps
+                .insert(address.clone(), vp_gas_meter.current_gas);
+            self.profile
+                .get_or_insert_with(GasProfile::default)
+                .merge(vp_profile)?;
+        }
+        Ok(())
+    }
+
     /// Set the gas cost from a VP run. It consumes the [`VpGasMeter`]
     /// instance which shouldn't be accessed passed this point.
     pub fn set(&mut self, vp_gas_meter: VpGasMeter) -> Result<()> {
@@ -355,6 +561,11 @@ impl VpsGas {
             self.rest.push(other.max);
         }
         self.rest.append(&mut other.rest);
+        if let Some(other_profile) = other.profile {
+            self.profile
+                .get_or_insert_with(GasProfile::default)
+                .merge(other_profile)?;
+        }
 
         self.check_limit(tx_gas_meter)
     }
@@ -397,6 +608,7 @@ mod tests {
         let tx_gas_meter = TxGasMeter {
             tx_gas_limit: BLOCK_GAS_LIMIT.into(),
             transaction_gas: Gas::default(),
+            profile: None,
         };
             let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
             meter.consume(gas).expect("cannot add the gas");
@@ -409,6 +621,7 @@ mod tests {
         let tx_gas_meter = TxGasMeter {
             tx_gas_limit: BLOCK_GAS_LIMIT.into(),
             transaction_gas: (TX_GAS_LIMIT - 1).into(),
+            profile: None,
         };
         let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
         assert_matches!(
@@ -422,6 +635,7 @@ mod tests {
         let tx_gas_meter = TxGasMeter {
             tx_gas_limit: TX_GAS_LIMIT.into(),
             transaction_gas: (TX_GAS_LIMIT - 1).into(),
+            profile: None,
         };
         let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
         assert_matches!(
@@ -442,6 +656,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gas_profile() {
+        let mut meter = TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into());
+        meter.enable_profiling();
+        meter
+            .consume_as(GasCategory::StorageRead, 10)
+            .expect("cannot add the gas");
+        meter.consume(5).expect("cannot add the gas");
+
+        let mut vp_meter = VpGasMeter::new_from_tx_meter(&meter);
+        vp_meter
+            .consume_as(GasCategory::StorageRead, 20)
+            .expect("cannot add the gas");
+        vp_meter
+            .consume_as(GasCategory::Masp, 30)
+            .expect("cannot add the gas");
+        let mut vps_gas = VpsGas::default();
+        vps_gas
+            .record_profile(&namada_core::types::address::MASP, &vp_meter)
+            .expect("cannot record the profile");
+        vps_gas.set(vp_meter).expect("cannot set the vp gas");
+        meter.add_vps_gas(&vps_gas).expect("cannot add the vps gas");
+
+        let profile = meter.take_profile().expect("missing profile");
+        assert_eq!(
+            profile.categories,
+            BTreeMap::from([
+                (GasCategory::StorageRead, Gas::from(30)),
+                (GasCategory::Masp, Gas::from(30)),
+                (GasCategory::Other, Gas::from(5)),
+            ])
+        );
+        assert_eq!(
+            profile.vps,
+            BTreeMap::from([(namada_core::types::address::MASP, 50.into())])
+        );
+        assert_eq!(meter.get_tx_consumed_gas(), 65.into());
+    }
+
     #[test]
     fn test_tx_gas_limit() {
         let mut meter = TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into());
@@ -13,7 +13,7 @@ use super::validation::{
     ProposalValidation,
 };
 use crate::parameters::GovernanceParameters;
-use crate::storage::proposal::PGFTarget;
+use crate::storage::proposal::{PGFContinuousFunding, PGFTarget};
 
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -278,7 +278,7 @@ impl PgfAction {
 )]
 pub struct PgfFunding {
     /// Pgf continuous funding
-    pub continuous: Vec<PGFTarget>,
+    pub continuous: Vec<PGFContinuousFunding>,
     /// pgf retro fundings
     pub retro: Vec<PGFTarget>,
 }
@@ -289,7 +289,7 @@ pub struct PgfFunding {
 )]
 pub struct PgfContinuous {
     /// Pgf target
-    pub target: PGFTarget,
+    pub target: PGFContinuousFunding,
     /// Pgf action
     pub action: PgfAction,
 }
@@ -69,6 +69,12 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The schedule of a pgf continuous funding is not valid
+    #[error(
+        "Invalid pgf continuous funding to {0}: the end epoch must not be \
+         before the start epoch and the budget must not be zero."
+    )]
+    InvalidPgfFundingSchedule(String),
 }
 
 pub fn is_valid_author_balance(
@@ -244,9 +250,21 @@ pub fn is_valid_pgf_stewards_data(
 pub fn is_valid_pgf_funding_data(
     data: &PgfFunding,
 ) -> Result<(), ProposalValidation> {
-    if !data.continuous.is_empty() || !data.retro.is_empty() {
-        Ok(())
-    } else {
-        Err(ProposalValidation::InvalidPgfFundingExtraData)
+    if data.continuous.is_empty() && data.retro.is_empty() {
+        return Err(ProposalValidation::InvalidPgfFundingExtraData);
+    }
+    for funding in &data.continuous {
+        let valid_epochs = match (funding.start_epoch, funding.end_epoch) {
+            (Some(start), Some(end)) => start <= end,
+            _ => true,
+        };
+        let valid_budget =
+            funding.budget.map_or(true, |budget| !budget.is_zero());
+        if !valid_epochs || !valid_budget {
+            return Err(ProposalValidation::InvalidPgfFundingSchedule(
+                funding.target.target(),
+            ));
+        }
     }
+    Ok(())
 }
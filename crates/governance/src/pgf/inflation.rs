@@ -5,19 +5,29 @@ use namada_core::types::dec::Dec;
 use namada_core::types::token;
 use namada_parameters::storage as params_storage;
 use namada_state::{
-    DBIter, StorageHasher, StorageRead, StorageResult, WlStorage, DB,
+    DBIter, StorageHasher, StorageRead, StorageResult, StorageWrite, WlStorage,
+    DB,
 };
 use namada_trans_token::credit_tokens;
 use namada_trans_token::storage_key::minted_balance_key;
 
-use crate::pgf::storage::{get_parameters, get_payments, get_stewards};
-use crate::storage::proposal::{PGFIbcTarget, PGFTarget};
+use crate::pgf::storage::batch::PgfBatchStatus;
+use crate::pgf::storage::history::{
+    PgfEpochRecord, PgfPaymentKind, PgfPaymentRecord, StewardRewardRecord,
+};
+use crate::pgf::storage::keys::{batches_handle, fundings_handle};
+use crate::pgf::storage::{
+    get_batches, get_parameters, get_payments, get_stewards, prune_history,
+    update_epoch_history,
+};
+use crate::storage::proposal::{PGFIbcTarget, PGFTarget, StoragePgfFunding};
 
-/// Apply the PGF inflation.
+/// Apply the PGF inflation. Returns the continuous fundings that have reached
+/// their end epoch or exhausted their budget, which are removed from storage.
 pub fn apply_inflation<D, H, F>(
     storage: &mut WlStorage<D, H>,
     transfer_over_ibc: F,
-) -> StorageResult<()>
+) -> StorageResult<Vec<StoragePgfFunding>>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
     H: StorageHasher + Sync + 'static,
@@ -30,6 +40,7 @@ where
 {
     let pgf_parameters = get_parameters(storage)?;
     let staking_token = storage.get_native_token()?;
+    let current_epoch = storage.get_block_epoch()?;
 
     let epochs_per_year: u64 = storage
         .read(&params_storage::get_epochs_per_year_key())?
@@ -55,42 +66,139 @@ where
         pgf_inflation_amount.to_string_native()
     );
 
+    let mut record = PgfEpochRecord {
+        minted: pgf_inflation_amount,
+        ..Default::default()
+    };
+
     let mut pgf_fundings = get_payments(storage)?;
     // we want to pay first the oldest fundings
     pgf_fundings.sort_by(|a, b| a.id.cmp(&b.id));
 
-    for funding in pgf_fundings {
+    let mut completed_fundings = vec![];
+    for mut funding in pgf_fundings {
+        if !funding.has_started(current_epoch) {
+            continue;
+        }
+        if funding.is_completed(current_epoch) {
+            complete_funding(storage, funding, &mut completed_fundings)?;
+            continue;
+        }
+
+        let amount = funding.epoch_payment();
         let result = match &funding.detail {
             PGFTarget::Internal(target) => namada_trans_token::transfer(
                 storage,
                 &staking_token,
                 &super::ADDRESS,
                 &target.target,
-                target.amount,
+                amount,
             ),
             PGFTarget::Ibc(target) => transfer_over_ibc(
                 storage,
                 &staking_token,
                 &super::ADDRESS,
-                target,
+                &PGFIbcTarget {
+                    amount,
+                    ..target.clone()
+                },
             ),
         };
-        match result {
+        let is_paid = result.is_ok();
+        let error = match result {
             Ok(()) => {
                 tracing::info!(
                     "Paying {} tokens for {} project.",
-                    funding.detail.amount().to_string_native(),
+                    amount.to_string_native(),
                     &funding.detail.target(),
                 );
+                funding.paid += amount;
+                None
             }
-            Err(_) => {
+            Err(err) => {
                 tracing::warn!(
                     "Failed to pay {} tokens for {} project.",
-                    funding.detail.amount().to_string_native(),
+                    amount.to_string_native(),
                     &funding.detail.target(),
                 );
+                Some(err.to_string())
             }
+        };
+        record.payments.push(PgfPaymentRecord {
+            kind: PgfPaymentKind::Continuous,
+            proposal_id: funding.id,
+            target: funding.detail.target(),
+            amount,
+            error,
+        });
+
+        // The funding is done if this was its last epoch or if it has just
+        // exhausted its budget
+        if funding.is_completed(current_epoch.next()) {
+            complete_funding(storage, funding, &mut completed_fundings)?;
+        } else if is_paid {
+            fundings_handle().insert(
+                storage,
+                funding.detail.target(),
+                funding,
+            )?;
+        }
+    }
+
+    // Unchallenged steward batches
+    let mut executable_batches = get_batches(storage)?
+        .into_iter()
+        .filter(|batch| batch.is_executable(current_epoch))
+        .collect::<Vec<_>>();
+    executable_batches.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for mut batch in executable_batches {
+        for payment in &batch.payments {
+            let result = match payment {
+                PGFTarget::Internal(target) => namada_trans_token::transfer(
+                    storage,
+                    &staking_token,
+                    &super::ADDRESS,
+                    &target.target,
+                    target.amount,
+                ),
+                PGFTarget::Ibc(target) => transfer_over_ibc(
+                    storage,
+                    &staking_token,
+                    &super::ADDRESS,
+                    target,
+                ),
+            };
+            let error = match result {
+                Ok(()) => {
+                    tracing::info!(
+                        "Execute steward batch {}: sent {} to {}.",
+                        batch.id,
+                        payment.amount().to_string_native(),
+                        payment.target(),
+                    );
+                    None
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "Execute steward batch {}: failed to send {} to {}.",
+                        batch.id,
+                        payment.amount().to_string_native(),
+                        payment.target(),
+                    );
+                    Some(err.to_string())
+                }
+            };
+            record.payments.push(PgfPaymentRecord {
+                kind: PgfPaymentKind::StewardBatch,
+                proposal_id: batch.id,
+                target: payment.target(),
+                amount: payment.amount(),
+                error,
+            });
         }
+        batch.status = PgfBatchStatus::Executed;
+        batches_handle().insert(storage, batch.id, batch)?;
     }
 
     // Pgf steward inflation
@@ -106,23 +214,258 @@ where
                 .unwrap_or_default();
             let reward_amount = token::Amount::from(pgf_steward_reward);
 
-            if credit_tokens(storage, &staking_token, &address, reward_amount)
-                .is_ok()
-            {
-                tracing::info!(
-                    "Minting {} tokens for steward {}.",
-                    reward_amount.to_string_native(),
-                    address,
-                );
-            } else {
-                tracing::warn!(
-                    "Failed minting {} tokens for steward {}.",
-                    reward_amount.to_string_native(),
-                    address,
-                );
-            }
+            let error = match credit_tokens(
+                storage,
+                &staking_token,
+                &address,
+                reward_amount,
+            ) {
+                Ok(()) => {
+                    tracing::info!(
+                        "Minting {} tokens for steward {}.",
+                        reward_amount.to_string_native(),
+                        address,
+                    );
+                    None
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed minting {} tokens for steward {}.",
+                        reward_amount.to_string_native(),
+                        address,
+                    );
+                    Some(err.to_string())
+                }
+            };
+            record.steward_rewards.push(StewardRewardRecord {
+                steward: steward.address.clone(),
+                recipient: address,
+                amount: reward_amount,
+                error,
+            });
         }
     }
 
+    update_epoch_history(storage, current_epoch, |epoch_record| {
+        epoch_record.minted += record.minted;
+        epoch_record.payments.append(&mut record.payments);
+        epoch_record
+            .steward_rewards
+            .append(&mut record.steward_rewards);
+    })?;
+    prune_history(storage, current_epoch)?;
+
+    Ok(completed_fundings)
+}
+
+/// Remove a continuous funding that has reached the end of its schedule
+fn complete_funding<S>(
+    storage: &mut S,
+    funding: StoragePgfFunding,
+    completed_fundings: &mut Vec<StoragePgfFunding>,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    tracing::info!(
+        "Continuous funding for {} project from proposal id {} completed \
+         after paying {} tokens.",
+        funding.detail.target(),
+        funding.id,
+        funding.paid.to_string_native(),
+    );
+    fundings_handle().remove(storage, &funding.detail.target())?;
+    completed_fundings.push(funding);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::storage::Epoch;
+    use namada_state::testing::TestWlStorage;
+    use namada_trans_token::read_balance;
+
+    use super::*;
+    use crate::pgf::parameters::PgfParameters;
+    use crate::pgf::storage::batch::SubmitPgfBatchData;
+    use crate::pgf::storage::{get_batch, get_epoch_history, submit_batch};
+    use crate::storage::proposal::{PGFContinuousFunding, PGFInternalTarget};
+
+    fn init_storage() -> TestWlStorage {
+        let mut storage = TestWlStorage::default();
+        let native_token = storage.storage.native_token.clone();
+        PgfParameters::default().init_storage(&mut storage).unwrap();
+        storage
+            .write(&params_storage::get_epochs_per_year_key(), 1_u64)
+            .unwrap();
+        credit_tokens(
+            &mut storage,
+            &native_token,
+            &crate::pgf::ADDRESS,
+            token::Amount::native_whole(1_000_000),
+        )
+        .unwrap();
+        storage
+    }
+
+    fn no_ibc(
+        _: &mut TestWlStorage,
+        _: &Address,
+        _: &Address,
+        _: &PGFIbcTarget,
+    ) -> StorageResult<()> {
+        Ok(())
+    }
+
+    fn insert_funding(
+        storage: &mut TestWlStorage,
+        id: u64,
+        target: Address,
+        start_epoch: Option<u64>,
+        end_epoch: Option<u64>,
+        budget: Option<u64>,
+    ) {
+        let target = PGFTarget::Internal(PGFInternalTarget {
+            target,
+            amount: token::Amount::native_whole(10),
+        });
+        let funding = StoragePgfFunding::new(
+            PGFContinuousFunding {
+                target,
+                start_epoch: start_epoch.map(Epoch),
+                end_epoch: end_epoch.map(Epoch),
+                budget: budget.map(token::Amount::native_whole),
+            },
+            id,
+        );
+        fundings_handle()
+            .insert(storage, funding.detail.target(), funding)
+            .unwrap();
+    }
+
+    /// Test that the continuous fundings are paid according to their
+    /// schedule and budget and removed once completed
+    #[test]
+    fn test_apply_inflation_continuous_fundings() {
+        let mut storage = init_storage();
+        let native_token = storage.storage.native_token.clone();
+
+        let budgeted = established_address_1();
+        let scheduled = established_address_2();
+        // Pays 10, 10 and then the 5 left of its budget
+        insert_funding(&mut storage, 0, budgeted.clone(), None, None, Some(25));
+        // Pays 10 only in epoch 1
+        insert_funding(
+            &mut storage,
+            1,
+            scheduled.clone(),
+            Some(1),
+            Some(1),
+            None,
+        );
+
+        let balance = |storage: &TestWlStorage, owner: &Address| {
+            read_balance(storage, &native_token, owner).unwrap()
+        };
+
+        let expected = [
+            // (budgeted balance, scheduled balance, completed ids)
+            (10, 0, vec![]),
+            (20, 10, vec![1]),
+            (25, 10, vec![0]),
+            (25, 10, vec![]),
+        ];
+        for (epoch, (budgeted_balance, scheduled_balance, completed_ids)) in
+            expected.into_iter().enumerate()
+        {
+            let epoch = Epoch(epoch as u64);
+            storage.storage.block.epoch = epoch;
+            let completed = apply_inflation(&mut storage, no_ibc).unwrap();
+
+            let completed = completed
+                .into_iter()
+                .map(|funding| funding.id)
+                .collect::<Vec<_>>();
+            assert_eq!(completed, completed_ids);
+            assert_eq!(
+                balance(&storage, &budgeted),
+                token::Amount::native_whole(budgeted_balance)
+            );
+            assert_eq!(
+                balance(&storage, &scheduled),
+                token::Amount::native_whole(scheduled_balance)
+            );
+
+            let record = get_epoch_history(&storage, epoch).unwrap().unwrap();
+            assert!(!record.minted.is_zero());
+            assert!(record.payments.iter().all(|payment| {
+                payment.kind == PgfPaymentKind::Continuous
+                    && payment.error.is_none()
+            }));
+        }
+        assert!(get_payments(&storage).unwrap().is_empty());
+    }
+
+    /// Test that the steward batches are paid once their challenge window has
+    /// ended, unless they were escalated
+    #[test]
+    fn test_apply_inflation_steward_batches() {
+        let mut storage = init_storage();
+        let native_token = storage.storage.native_token.clone();
+        let target = established_address_1();
+        let payment = PGFTarget::Internal(PGFInternalTarget {
+            target: target.clone(),
+            amount: token::Amount::native_whole(1),
+        });
+        let data = SubmitPgfBatchData {
+            steward: established_address_2(),
+            payments: vec![payment; 3],
+        };
+        let executed = submit_batch(&mut storage, data.clone()).unwrap();
+        let escalated = submit_batch(&mut storage, data).unwrap();
+        let mut batch = get_batch(&storage, escalated).unwrap().unwrap();
+        batch.status = PgfBatchStatus::Escalated {
+            challenger: established_address_2(),
+            proposal_id: 0,
+        };
+        batches_handle().insert(&mut storage, escalated, batch).unwrap();
+
+        // The batches can still be challenged in epoch 1
+        storage.storage.block.epoch = Epoch(1);
+        apply_inflation(&mut storage, no_ibc).unwrap();
+        let batch = get_batch(&storage, executed).unwrap().unwrap();
+        assert_eq!(batch.status, PgfBatchStatus::Pending);
+        assert!(read_balance(&storage, &native_token, &target)
+            .unwrap()
+            .is_zero());
+
+        storage.storage.block.epoch = Epoch(2);
+        apply_inflation(&mut storage, no_ibc).unwrap();
+        let batch = get_batch(&storage, executed).unwrap().unwrap();
+        assert_eq!(batch.status, PgfBatchStatus::Executed);
+        let batch = get_batch(&storage, escalated).unwrap().unwrap();
+        assert!(matches!(batch.status, PgfBatchStatus::Escalated { .. }));
+        assert_eq!(
+            read_balance(&storage, &native_token, &target).unwrap(),
+            token::Amount::native_whole(3)
+        );
+        let record = get_epoch_history(&storage, Epoch(2)).unwrap().unwrap();
+        assert_eq!(record.payments.len(), 3);
+        assert!(record.payments.iter().all(|payment| {
+            payment.kind == PgfPaymentKind::StewardBatch
+                && payment.proposal_id == executed
+                && payment.error.is_none()
+        }));
+
+        // An executed batch is not paid again
+        storage.storage.block.epoch = Epoch(3);
+        apply_inflation(&mut storage, no_ibc).unwrap();
+        assert_eq!(
+            read_balance(&storage, &native_token, &target).unwrap(),
+            token::Amount::native_whole(3)
+        );
+    }
+}
@@ -13,3 +13,6 @@ pub mod storage;
 
 /// The Pgf internal address
 pub const ADDRESS: Address = Address::Internal(InternalAddress::Pgf);
+
+/// The maximum number of item in a pgf proposal or steward payment batch
+pub const MAX_PGF_ACTIONS: usize = 20;
@@ -30,6 +30,9 @@ pub struct PgfParameters {
     pub pgf_inflation_rate: Dec,
     /// The pgf stewards inflation rate
     pub stewards_inflation_rate: Dec,
+    /// The number of epochs during which a steward payment batch can be
+    /// challenged
+    pub batch_challenge_epochs: u64,
 }
 
 impl Default for PgfParameters {
@@ -38,6 +41,7 @@ impl Default for PgfParameters {
             stewards: BTreeSet::default(),
             pgf_inflation_rate: Dec::new(10, 2).unwrap(),
             stewards_inflation_rate: Dec::new(1, 2).unwrap(),
+            batch_challenge_epochs: 2,
         }
     }
 }
@@ -52,6 +56,7 @@ impl PgfParameters {
             stewards,
             pgf_inflation_rate,
             stewards_inflation_rate,
+            batch_challenge_epochs,
         } = self;
 
         for steward in stewards {
@@ -67,6 +72,10 @@ impl PgfParameters {
 
         let steward_inflation_rate_key =
             pgf_storage::get_steward_inflation_rate_key();
-        storage.write(&steward_inflation_rate_key, stewards_inflation_rate)
+        storage.write(&steward_inflation_rate_key, stewards_inflation_rate)?;
+
+        let batch_challenge_epochs_key =
+            pgf_storage::get_batch_challenge_epochs_key();
+        storage.write(&batch_challenge_epochs_key, batch_challenge_epochs)
     }
 }
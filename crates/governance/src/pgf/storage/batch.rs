@@ -0,0 +1,145 @@
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::hash::Hash;
+use namada_core::types::storage::Epoch;
+use namada_core::types::token;
+use serde::{Deserialize, Serialize};
+
+use crate::storage::proposal::{PGFAction, PGFTarget, ProposalType};
+
+/// The status of a steward payment batch
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub enum PgfBatchStatus {
+    /// The batch can still be challenged
+    Pending,
+    /// The challenge window ended and the payments have been made
+    Executed,
+    /// The batch was challenged and turned into a governance proposal
+    Escalated {
+        /// The validator that challenged the batch
+        challenger: Address,
+        /// The id of the governance proposal created for the batch
+        proposal_id: u64,
+    },
+}
+
+impl std::fmt::Display for PgfBatchStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgfBatchStatus::Pending => write!(f, "pending"),
+            PgfBatchStatus::Executed => write!(f, "executed"),
+            PgfBatchStatus::Escalated {
+                challenger,
+                proposal_id,
+            } => write!(
+                f,
+                "escalated to proposal {} by {}",
+                proposal_id, challenger
+            ),
+        }
+    }
+}
+
+/// A batch of retro payments proposed by a steward. Unless a validator
+/// challenges it before the end of its challenge window, the batch is paid
+/// from the PGF account without a governance vote.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct StewardPaymentBatch {
+    /// The batch id
+    pub id: u64,
+    /// The steward that submitted the batch
+    pub steward: Address,
+    /// The payments of the batch
+    pub payments: Vec<PGFTarget>,
+    /// The epoch in which the batch was submitted
+    pub submission_epoch: Epoch,
+    /// The first epoch in which the batch can't be challenged anymore
+    pub challenge_end_epoch: Epoch,
+    /// The batch status
+    pub status: PgfBatchStatus,
+}
+
+impl StewardPaymentBatch {
+    /// Check if the batch can be challenged in the given epoch
+    pub fn is_challengeable(&self, epoch: Epoch) -> bool {
+        self.status == PgfBatchStatus::Pending
+            && epoch < self.challenge_end_epoch
+    }
+
+    /// Check if the batch can be executed in the given epoch
+    pub fn is_executable(&self, epoch: Epoch) -> bool {
+        self.status == PgfBatchStatus::Pending
+            && epoch >= self.challenge_end_epoch
+    }
+
+    /// The sum of the amounts of the batch payments
+    pub fn total_amount(&self) -> token::Amount {
+        self.payments
+            .iter()
+            .fold(token::Amount::zero(), |acc, payment| acc + payment.amount())
+    }
+
+    /// The type of the governance proposal a challenged batch is escalated
+    /// to
+    pub fn to_proposal_type(&self) -> ProposalType {
+        ProposalType::PGFPayment(
+            self.payments
+                .iter()
+                .cloned()
+                .map(PGFAction::Retro)
+                .collect(),
+        )
+    }
+}
+
+/// A tx data type to submit a steward payment batch
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct SubmitPgfBatchData {
+    /// The steward submitting the batch
+    pub steward: Address,
+    /// The payments of the batch
+    pub payments: Vec<PGFTarget>,
+}
+
+/// A tx data type to challenge a steward payment batch
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct ChallengePgfBatchData {
+    /// The id of the challenged batch
+    pub batch_id: u64,
+    /// The validator challenging the batch
+    pub challenger: Address,
+    /// The content of the governance proposal created for the batch
+    pub content: Hash,
+}
@@ -0,0 +1,84 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::token;
+
+/// The number of past epochs for which the PGF history is retained. The
+/// record of an older epoch is pruned at the start of each new epoch.
+pub const HISTORY_RETENTION_EPOCHS: u64 = 1_000;
+
+/// The maximum number of epochs of PGF history returned by a single query
+pub const MAX_HISTORY_QUERY_EPOCHS: u64 = 100;
+
+/// The kind of a PGF payment
+#[derive(Clone, Copy, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+pub enum PgfPaymentKind {
+    /// A payment of a continuous funding
+    Continuous,
+    /// A retro payment
+    Retro,
+    /// A retro payment of an unchallenged steward batch
+    StewardBatch,
+}
+
+impl std::fmt::Display for PgfPaymentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgfPaymentKind::Continuous => write!(f, "continuous"),
+            PgfPaymentKind::Retro => write!(f, "retro"),
+            PgfPaymentKind::StewardBatch => write!(f, "steward batch"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+/// A payment from the PGF account to a funding target
+pub struct PgfPaymentRecord {
+    /// The kind of the payment
+    pub kind: PgfPaymentKind,
+    /// The id of the proposal, or of the steward batch, that funded the
+    /// target
+    pub proposal_id: u64,
+    /// The funding target
+    pub target: String,
+    /// The paid amount
+    pub amount: token::Amount,
+    /// The reason of the failure, if the payment failed
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+/// A reward minted for a pgf steward
+pub struct StewardRewardRecord {
+    /// The steward address
+    pub steward: Address,
+    /// The address receiving the reward, as per the steward reward
+    /// distribution
+    pub recipient: Address,
+    /// The minted amount
+    pub amount: token::Amount,
+    /// The reason of the failure, if the reward couldn't be minted
+    pub error: Option<String>,
+}
+
+#[derive(
+    Clone, Debug, Default, BorshSerialize, BorshDeserialize, PartialEq,
+)]
+/// The PGF accounting of a single epoch
+pub struct PgfEpochRecord {
+    /// The PGF inflation minted into the PGF account
+    pub minted: token::Amount,
+    /// The payments made from the PGF account
+    pub payments: Vec<PgfPaymentRecord>,
+    /// The rewards minted for the stewards
+    pub steward_rewards: Vec<StewardRewardRecord>,
+}
+
+impl PgfEpochRecord {
+    /// The total amount successfully paid from the PGF account
+    pub fn total_paid(&self) -> token::Amount {
+        self.payments
+            .iter()
+            .filter(|payment| payment.error.is_none())
+            .fold(token::Amount::zero(), |acc, payment| acc + payment.amount)
+    }
+}
@@ -1,8 +1,10 @@
 use namada_core::types::address::Address;
-use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
+use namada_core::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
 use namada_macros::StorageKeys;
 use namada_state::collections::{lazy_map, LazyCollection, LazyMap};
 
+use crate::pgf::storage::batch::StewardPaymentBatch;
+use crate::pgf::storage::history::PgfEpochRecord;
 use crate::pgf::storage::steward::StewardDetail;
 use crate::pgf::ADDRESS;
 use crate::storage::proposal::StoragePgfFunding;
@@ -12,8 +14,12 @@ use crate::storage::proposal::StoragePgfFunding;
 struct Keys {
     stewards: &'static str,
     fundings: &'static str,
+    history: &'static str,
+    batches: &'static str,
+    batch_counter: &'static str,
     pgf_inflation_rate: &'static str,
     steward_inflation_rate: &'static str,
+    batch_challenge_epochs: &'static str,
 }
 
 /// Obtain a storage key for stewards key
@@ -72,6 +78,67 @@ pub fn is_fundings_key(key: &Key) -> bool {
                 && data.as_str() == lazy_map::DATA_SUBKEY)
 }
 
+/// Obtain a storage key for the pgf history.
+pub fn history_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.history.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the pgf history substorage
+pub fn history_handle() -> LazyMap<Epoch, PgfEpochRecord> {
+    LazyMap::open(history_key_prefix())
+}
+
+/// Obtain a storage key for the steward payment batches.
+pub fn batches_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.batches.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the steward payment batches substorage
+pub fn batches_handle() -> LazyMap<u64, StewardPaymentBatch> {
+    LazyMap::open(batches_key_prefix())
+}
+
+/// Check if the given storage key is a steward payment batch key. If it is,
+/// returns the batch id.
+pub fn is_batches_key(key: &Key) -> Option<u64> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(pgf),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::StringSeg(id),
+        ] if pgf.eq(&ADDRESS)
+            && prefix.as_str() == Keys::VALUES.batches
+            && data.as_str() == lazy_map::DATA_SUBKEY =>
+        {
+            id.parse::<u64>().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Check if key is the steward payment batch counter key
+pub fn is_batch_counter_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix)] if addr == &ADDRESS && prefix == Keys::VALUES.batch_counter)
+}
+
+/// Get key for the steward payment batch counter
+pub fn get_batch_counter_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.batch_counter.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Check if key is inside governance address space
 pub fn is_pgf_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
@@ -87,6 +154,11 @@ pub fn is_steward_inflation_rate_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix)] if addr == &ADDRESS && prefix == Keys::VALUES.steward_inflation_rate)
 }
 
+/// Check if key is a batch challenge epochs key
+pub fn is_batch_challenge_epochs_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix)] if addr == &ADDRESS && prefix == Keys::VALUES.batch_challenge_epochs)
+}
+
 /// Get key for inflation rate key
 pub fn get_pgf_inflation_rate_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -100,3 +172,10 @@ pub fn get_steward_inflation_rate_key() -> Key {
         .push(&Keys::VALUES.steward_inflation_rate.to_owned())
         .expect("Cannot obtain a storage key")
 }
+
+/// Get key for the batch challenge epochs key
+pub fn get_batch_challenge_epochs_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.batch_challenge_epochs.to_owned())
+        .expect("Cannot obtain a storage key")
+}
@@ -1,20 +1,35 @@
 //! Pgf
 
+/// Pgf steward payment batch structures
+pub mod batch;
+/// Pgf payment history structures
+pub mod history;
 /// Pgf storage keys
 pub mod keys;
 /// Pgf steward structures
 pub mod steward;
 
+use std::cmp;
 use std::collections::HashMap;
 
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
-use namada_state::{StorageRead, StorageResult, StorageWrite};
+use namada_core::types::storage::Epoch;
+use namada_state::{StorageError, StorageRead, StorageResult, StorageWrite};
 
 use crate::pgf::parameters::PgfParameters;
+use crate::pgf::storage::batch::{
+    ChallengePgfBatchData, PgfBatchStatus, StewardPaymentBatch,
+    SubmitPgfBatchData,
+};
+use crate::pgf::storage::history::{
+    PgfEpochRecord, HISTORY_RETENTION_EPOCHS, MAX_HISTORY_QUERY_EPOCHS,
+};
 use crate::pgf::storage::keys as pgf_keys;
 use crate::pgf::storage::steward::StewardDetail;
-use crate::storage::proposal::StoragePgfFunding;
+use crate::pgf::MAX_PGF_ACTIONS;
+use crate::storage::keys as governance_keys;
+use crate::storage::proposal::{InitProposalData, StoragePgfFunding};
 
 /// Query the current pgf steward set
 pub fn get_stewards<S>(storage: &S) -> StorageResult<Vec<StewardDetail>>
@@ -80,6 +95,190 @@ where
     Ok(fundings)
 }
 
+/// Query the pgf history of the epochs in the inclusive range from `from` to
+/// `to`. At most [`MAX_HISTORY_QUERY_EPOCHS`] epochs are read, starting from
+/// `from`, and the epochs without a record are skipped.
+pub fn get_history<S>(
+    storage: &S,
+    from: Epoch,
+    to: Epoch,
+) -> StorageResult<Vec<(Epoch, PgfEpochRecord)>>
+where
+    S: StorageRead,
+{
+    let handle = pgf_keys::history_handle();
+    let len = to.0.checked_sub(from.0).map_or(0, |diff| {
+        cmp::min(diff, MAX_HISTORY_QUERY_EPOCHS - 1) + 1
+    });
+    from.iter_range(len)
+        .filter_map(|epoch| {
+            handle
+                .get(storage, &epoch)
+                .transpose()
+                .map(|record| record.map(|record| (epoch, record)))
+        })
+        .collect()
+}
+
+/// Query the pgf history of an epoch
+pub fn get_epoch_history<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> StorageResult<Option<PgfEpochRecord>>
+where
+    S: StorageRead,
+{
+    pgf_keys::history_handle().get(storage, &epoch)
+}
+
+/// Update the pgf history of an epoch
+pub fn update_epoch_history<S, F>(
+    storage: &mut S,
+    epoch: Epoch,
+    update: F,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+    F: FnOnce(&mut PgfEpochRecord),
+{
+    let handle = pgf_keys::history_handle();
+    let mut record = handle.get(storage, &epoch)?.unwrap_or_default();
+    update(&mut record);
+    handle.insert(storage, epoch, record)?;
+
+    Ok(())
+}
+
+/// Remove the pgf history record of the epoch that has just fallen out of the
+/// retention window
+pub fn prune_history<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if let Some(epoch) = current_epoch.checked_sub(HISTORY_RETENTION_EPOCHS)
+    {
+        pgf_keys::history_handle().remove(storage, &epoch)?;
+    }
+    Ok(())
+}
+
+/// Query the steward payment batches
+pub fn get_batches<S>(storage: &S) -> StorageResult<Vec<StewardPaymentBatch>>
+where
+    S: StorageRead,
+{
+    pgf_keys::batches_handle()
+        .iter(storage)?
+        .map(|data| data.map(|(_, batch)| batch))
+        .collect::<StorageResult<Vec<StewardPaymentBatch>>>()
+}
+
+/// Query a steward payment batch by id
+pub fn get_batch<S>(
+    storage: &S,
+    id: u64,
+) -> StorageResult<Option<StewardPaymentBatch>>
+where
+    S: StorageRead,
+{
+    pgf_keys::batches_handle().get(storage, &id)
+}
+
+/// Store a new steward payment batch, challengeable until the end of the
+/// challenge window. Returns the batch id.
+pub fn submit_batch<S>(
+    storage: &mut S,
+    data: SubmitPgfBatchData,
+) -> StorageResult<u64>
+where
+    S: StorageRead + StorageWrite,
+{
+    if data.payments.is_empty() {
+        return Err(StorageError::new_const("Empty steward payment batch"));
+    }
+    if data.payments.len() > MAX_PGF_ACTIONS {
+        return Err(StorageError::new_const(
+            "Too many payments in steward payment batch",
+        ));
+    }
+
+    let pgf_parameters = get_parameters(storage)?;
+    let current_epoch = storage.get_block_epoch()?;
+
+    let counter_key = pgf_keys::get_batch_counter_key();
+    let id: u64 = storage.read(&counter_key)?.unwrap_or_default();
+
+    let batch = StewardPaymentBatch {
+        id,
+        steward: data.steward,
+        payments: data.payments,
+        submission_epoch: current_epoch,
+        challenge_end_epoch: current_epoch
+            + pgf_parameters.batch_challenge_epochs,
+        status: PgfBatchStatus::Pending,
+    };
+    pgf_keys::batches_handle().insert(storage, id, batch)?;
+    storage.write(&counter_key, id + 1)?;
+
+    Ok(id)
+}
+
+/// Escalate a pending steward payment batch to a pgf funding governance
+/// proposal authored by the challenger. The proposal voting starts in the
+/// next epoch and lasts the minimum voting period. Returns the proposal id.
+pub fn challenge_batch<S>(
+    storage: &mut S,
+    data: ChallengePgfBatchData,
+    content: Vec<u8>,
+) -> StorageResult<u64>
+where
+    S: StorageRead + StorageWrite,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    let mut batch = get_batch(storage, data.batch_id)?.ok_or_else(|| {
+        StorageError::new_const("Steward payment batch not found")
+    })?;
+    if !batch.is_challengeable(current_epoch) {
+        return Err(StorageError::new_const(
+            "Steward payment batch can't be challenged anymore",
+        ));
+    }
+
+    let governance_parameters = crate::storage::get_parameters(storage)?;
+    let proposal_id: u64 =
+        storage.read(&governance_keys::get_counter_key())?.expect(
+            "Storage should have been initialized with an initial governance \
+             proposal id",
+        );
+    let voting_start_epoch = current_epoch.next();
+    let voting_end_epoch =
+        voting_start_epoch + governance_parameters.min_proposal_voting_period;
+    let grace_epoch =
+        voting_end_epoch + governance_parameters.min_proposal_grace_epochs;
+
+    let proposal = InitProposalData {
+        id: proposal_id,
+        content: data.content,
+        author: data.challenger.clone(),
+        r#type: batch.to_proposal_type(),
+        voting_start_epoch,
+        voting_end_epoch,
+        grace_epoch,
+    };
+    crate::storage::init_proposal(storage, proposal, content, None)?;
+
+    batch.status = PgfBatchStatus::Escalated {
+        challenger: data.challenger,
+        proposal_id,
+    };
+    pgf_keys::batches_handle().insert(storage, batch.id, batch)?;
+
+    Ok(proposal_id)
+}
+
 /// Query the pgf parameters
 pub fn get_parameters<S>(storage: &S) -> StorageResult<PgfParameters>
 where
@@ -88,6 +287,7 @@ where
     let pgf_inflation_rate_key = pgf_keys::get_pgf_inflation_rate_key();
     let stewards_inflation_rate_key =
         pgf_keys::get_steward_inflation_rate_key();
+    let batch_challenge_epochs_key = pgf_keys::get_batch_challenge_epochs_key();
 
     let pgf_inflation_rate: Dec = storage
         .read(&pgf_inflation_rate_key)?
@@ -95,10 +295,16 @@ where
     let stewards_inflation_rate: Dec = storage
         .read(&stewards_inflation_rate_key)?
         .expect("Parameter should be defined.");
+    // The parameter isn't written on the chains started before the steward
+    // payment batches were introduced
+    let batch_challenge_epochs: u64 = storage
+        .read(&batch_challenge_epochs_key)?
+        .unwrap_or(PgfParameters::default().batch_challenge_epochs);
 
     Ok(PgfParameters {
         pgf_inflation_rate,
         stewards_inflation_rate,
+        batch_challenge_epochs,
         ..Default::default()
     })
 }
@@ -123,3 +329,198 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::hash::Hash;
+    use namada_core::types::token;
+    use namada_state::testing::TestWlStorage;
+    use namada_trans_token::credit_tokens;
+
+    use super::*;
+    use crate::parameters::GovernanceParameters;
+    use crate::storage::proposal::{
+        PGFInternalTarget, PGFTarget, ProposalType,
+    };
+
+    fn init_storage() -> TestWlStorage {
+        let mut storage = TestWlStorage::default();
+        PgfParameters::default().init_storage(&mut storage).unwrap();
+        GovernanceParameters::default()
+            .init_storage(&mut storage)
+            .unwrap();
+        storage
+    }
+
+    fn batch_data(len: usize) -> SubmitPgfBatchData {
+        let payment = PGFTarget::Internal(PGFInternalTarget {
+            target: established_address_2(),
+            amount: token::Amount::native_whole(1),
+        });
+        SubmitPgfBatchData {
+            steward: established_address_1(),
+            payments: vec![payment; len],
+        }
+    }
+
+    fn record(minted: u64) -> PgfEpochRecord {
+        PgfEpochRecord {
+            minted: token::Amount::native_whole(minted),
+            ..Default::default()
+        }
+    }
+
+    /// Test that the pgf history can only be queried in bounded ranges
+    #[test]
+    fn test_get_history_range() {
+        let mut storage = TestWlStorage::default();
+        let max = MAX_HISTORY_QUERY_EPOCHS;
+        for epoch in (0..2 * max).filter(|epoch| epoch % 2 == 0) {
+            update_epoch_history(&mut storage, Epoch(epoch), |rec| {
+                *rec = record(epoch)
+            })
+            .unwrap();
+        }
+
+        let history = get_history(&storage, Epoch(2), Epoch(6)).unwrap();
+        assert_eq!(
+            history,
+            vec![
+                (Epoch(2), record(2)),
+                (Epoch(4), record(4)),
+                (Epoch(6), record(6)),
+            ]
+        );
+
+        // An empty range
+        assert!(get_history(&storage, Epoch(6), Epoch(2)).unwrap().is_empty());
+
+        // The range is capped at the max number of epochs
+        let history = get_history(&storage, Epoch(0), Epoch(u64::MAX)).unwrap();
+        assert_eq!(history.len() as u64, max / 2);
+        assert_eq!(history.last().unwrap().0, Epoch(max - 2));
+    }
+
+    /// Test that the pgf history is pruned past the retention window
+    #[test]
+    fn test_prune_history() {
+        let mut storage = TestWlStorage::default();
+        let retention = HISTORY_RETENTION_EPOCHS;
+        for epoch in 0..retention + 2 {
+            update_epoch_history(&mut storage, Epoch(epoch), |rec| {
+                *rec = record(epoch)
+            })
+            .unwrap();
+            prune_history(&mut storage, Epoch(epoch)).unwrap();
+        }
+
+        assert!(get_epoch_history(&storage, Epoch(0)).unwrap().is_none());
+        assert!(get_epoch_history(&storage, Epoch(1)).unwrap().is_none());
+        assert_eq!(
+            get_epoch_history(&storage, Epoch(2)).unwrap(),
+            Some(record(2))
+        );
+        let retained = pgf_keys::history_handle().len(&storage).unwrap();
+        assert_eq!(retained, retention);
+    }
+
+    /// Test that the parameters of a chain without the batch challenge epochs
+    /// parameter fall back to the default
+    #[test]
+    fn test_get_parameters_without_batch_challenge_epochs() {
+        let mut storage = init_storage();
+        storage
+            .delete(&pgf_keys::get_batch_challenge_epochs_key())
+            .unwrap();
+
+        let params = get_parameters(&storage).unwrap();
+        assert_eq!(
+            params.batch_challenge_epochs,
+            PgfParameters::default().batch_challenge_epochs
+        );
+    }
+
+    /// Test that a steward batch must contain between one and
+    /// [`MAX_PGF_ACTIONS`] payments
+    #[test]
+    fn test_submit_batch() {
+        let mut storage = init_storage();
+        storage.storage.block.epoch = Epoch(3);
+
+        assert!(submit_batch(&mut storage, batch_data(0)).is_err());
+        assert!(submit_batch(&mut storage, batch_data(MAX_PGF_ACTIONS + 1))
+            .is_err());
+        assert!(get_batches(&storage).unwrap().is_empty());
+
+        let id =
+            submit_batch(&mut storage, batch_data(MAX_PGF_ACTIONS)).unwrap();
+        assert_eq!(id, 0);
+        let batch = get_batch(&storage, id).unwrap().unwrap();
+        assert_eq!(batch.steward, established_address_1());
+        assert_eq!(batch.payments.len(), MAX_PGF_ACTIONS);
+        assert_eq!(batch.submission_epoch, Epoch(3));
+        assert_eq!(batch.challenge_end_epoch, Epoch(5));
+        assert_eq!(batch.status, PgfBatchStatus::Pending);
+
+        assert_eq!(submit_batch(&mut storage, batch_data(1)).unwrap(), 1);
+    }
+
+    /// Test that a pending steward batch can be escalated to a governance
+    /// proposal only within its challenge window
+    #[test]
+    fn test_challenge_batch() {
+        let mut storage = init_storage();
+        let challenger = established_address_2();
+        let native_token = storage.storage.native_token.clone();
+        credit_tokens(
+            &mut storage,
+            &native_token,
+            &challenger,
+            token::Amount::native_whole(1_000),
+        )
+        .unwrap();
+        let challenge = |batch_id| ChallengePgfBatchData {
+            batch_id,
+            challenger: challenger.clone(),
+            content: Hash::default(),
+        };
+
+        let id = submit_batch(&mut storage, batch_data(2)).unwrap();
+        assert!(
+            challenge_batch(&mut storage, challenge(id + 1), vec![]).is_err()
+        );
+
+        let proposal_id =
+            challenge_batch(&mut storage, challenge(id), b"content".to_vec())
+                .unwrap();
+        let batch = get_batch(&storage, id).unwrap().unwrap();
+        assert_eq!(
+            batch.status,
+            PgfBatchStatus::Escalated {
+                challenger: challenger.clone(),
+                proposal_id,
+            }
+        );
+        let author: Option<Address> = storage
+            .read(&governance_keys::get_author_key(proposal_id))
+            .unwrap();
+        assert_eq!(author, Some(challenger.clone()));
+        let proposal_type: Option<ProposalType> = storage
+            .read(&governance_keys::get_proposal_type_key(proposal_id))
+            .unwrap();
+        assert_eq!(proposal_type, Some(batch.to_proposal_type()));
+
+        // An escalated batch can't be challenged again
+        assert!(challenge_batch(&mut storage, challenge(id), vec![]).is_err());
+
+        // Nor a batch past its challenge window
+        let id = submit_batch(&mut storage, batch_data(2)).unwrap();
+        storage.storage.block.epoch = Epoch(2);
+        assert!(challenge_batch(&mut storage, challenge(id), vec![]).is_err());
+        let batch = get_batch(&storage, id).unwrap().unwrap();
+        assert_eq!(batch.status, PgfBatchStatus::Pending);
+    }
+}
@@ -126,11 +126,11 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             .continuous
             .iter()
             .cloned()
-            .map(|target| {
-                if target.amount().is_zero() {
-                    PGFAction::Continuous(AddRemove::Remove(target))
+            .map(|funding| {
+                if funding.target.amount().is_zero() {
+                    PGFAction::Continuous(AddRemove::Remove(funding))
                 } else {
-                    PGFAction::Continuous(AddRemove::Add(target))
+                    PGFAction::Continuous(AddRemove::Add(funding))
                 }
             })
             .collect::<Vec<PGFAction>>();
@@ -157,29 +157,149 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
     }
 }
 
-/// Storage struture for pgf fundings
+/// Storage struture for pgf fundings. The Borsh decoding also accepts the
+/// legacy encoding made only of the detail and the id, which is decoded as a
+/// funding with no schedule nor budget.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, BorshSerialize, Serialize, Deserialize,
+)]
+pub struct StoragePgfFunding {
+    /// The data about the pgf funding
+    pub detail: PGFTarget,
+    /// The id of the proposal that added this funding
+    pub id: u64,
+    /// The first epoch in which the funding is paid, if any
+    pub start_epoch: Option<Epoch>,
+    /// The last epoch in which the funding is paid, if any
+    pub end_epoch: Option<Epoch>,
+    /// The maximum total amount paid by the funding, if any
+    pub budget: Option<Amount>,
+    /// The total amount paid so far
+    pub paid: Amount,
+}
+
+impl StoragePgfFunding {
+    /// Init a new pgf funding struct
+    pub fn new(funding: PGFContinuousFunding, id: u64) -> Self {
+        let PGFContinuousFunding {
+            target,
+            start_epoch,
+            end_epoch,
+            budget,
+        } = funding;
+        Self {
+            detail: target,
+            id,
+            start_epoch,
+            end_epoch,
+            budget,
+            paid: Amount::zero(),
+        }
+    }
+
+    /// Returns the part of the budget that is left to pay, if the funding
+    /// has a budget
+    pub fn remaining_budget(&self) -> Option<Amount> {
+        self.budget
+            .map(|budget| budget.checked_sub(self.paid).unwrap_or_default())
+    }
+
+    /// Check if the funding is due to be paid in the given epoch
+    pub fn has_started(&self, epoch: Epoch) -> bool {
+        self.start_epoch.map_or(true, |start| start <= epoch)
+    }
+
+    /// Check if the funding has reached its end epoch or exhausted its
+    /// budget, as of the given epoch
+    pub fn is_completed(&self, epoch: Epoch) -> bool {
+        self.end_epoch.map_or(false, |end| end < epoch)
+            || self
+                .remaining_budget()
+                .map_or(false, |remaining| remaining.is_zero())
+    }
+
+    /// Returns the amount to pay in a single epoch, capped by the remaining
+    /// budget
+    pub fn epoch_payment(&self) -> Amount {
+        let amount = self.detail.amount();
+        match self.remaining_budget() {
+            Some(remaining) if remaining < amount => remaining,
+            _ => amount,
+        }
+    }
+}
+
+impl BorshDeserialize for StoragePgfFunding {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::Read;
+        let detail: PGFTarget = BorshDeserialize::deserialize_reader(reader)?;
+        let id: u64 = BorshDeserialize::deserialize_reader(reader)?;
+        // Fundings stored before the schedule and budget were introduced end
+        // right after the id
+        let mut tag = [0_u8; 1];
+        if reader.read(&mut tag)? == 0 {
+            return Ok(Self::new(PGFContinuousFunding::from(detail), id));
+        }
+        let mut reader = (&tag[..]).chain(reader);
+        let start_epoch: Option<Epoch> =
+            BorshDeserialize::deserialize_reader(&mut reader)?;
+        let end_epoch: Option<Epoch> =
+            BorshDeserialize::deserialize_reader(&mut reader)?;
+        let budget: Option<Amount> =
+            BorshDeserialize::deserialize_reader(&mut reader)?;
+        let paid: Amount = BorshDeserialize::deserialize_reader(&mut reader)?;
+        Ok(Self {
+            detail,
+            id,
+            start_epoch,
+            end_epoch,
+            budget,
+            paid,
+        })
+    }
+}
+
+/// A continuous PGF funding with an optional schedule and budget
 #[derive(
     Debug,
     Clone,
     PartialEq,
     Eq,
     PartialOrd,
+    Ord,
     BorshSerialize,
     BorshDeserialize,
     Serialize,
     Deserialize,
 )]
-pub struct StoragePgfFunding {
-    /// The data about the pgf funding
-    pub detail: PGFTarget,
-    /// The id of the proposal that added this funding
-    pub id: u64,
+pub struct PGFContinuousFunding {
+    /// The target of the funding, paid every epoch
+    #[serde(flatten)]
+    pub target: PGFTarget,
+    /// The first epoch in which the funding is paid. When not set, the
+    /// funding starts right away.
+    #[serde(default)]
+    pub start_epoch: Option<Epoch>,
+    /// The last epoch in which the funding is paid. When not set, the
+    /// funding runs until it's removed or its budget is exhausted.
+    #[serde(default)]
+    pub end_epoch: Option<Epoch>,
+    /// The maximum total amount to pay. When not set, the funding is not
+    /// capped.
+    #[serde(default)]
+    pub budget: Option<Amount>,
 }
 
-impl StoragePgfFunding {
-    /// Init a new pgf funding struct
-    pub fn new(detail: PGFTarget, id: u64) -> Self {
-        Self { detail, id }
+impl From<PGFTarget> for PGFContinuousFunding {
+    fn from(target: PGFTarget) -> Self {
+        Self {
+            target,
+            start_epoch: None,
+            end_epoch: None,
+            budget: None,
+        }
     }
 }
 
@@ -372,7 +492,7 @@ impl borsh::BorshSchema for PGFIbcTarget {
 )]
 pub enum PGFAction {
     /// A continuous payment
-    Continuous(AddRemove<PGFTarget>),
+    Continuous(AddRemove<PGFContinuousFunding>),
     /// A retro payment
     Retro(PGFTarget),
 }
@@ -597,9 +717,26 @@ pub mod testing {
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary continuous PGF funding
+        pub fn arb_pgf_continuous_funding()(
+            target in arb_pgf_target(),
+            start_epoch in option::of(arb_epoch()),
+            end_epoch in option::of(arb_epoch()),
+            budget in option::of(arb_amount()),
+        ) -> PGFContinuousFunding {
+            PGFContinuousFunding {
+                target,
+                start_epoch,
+                end_epoch,
+                budget,
+            }
+        }
+    }
+
     /// Generate an arbitrary PGF action
     pub fn arb_pgf_action() -> impl Strategy<Value = PGFAction> {
-        arb_add_remove(arb_pgf_target())
+        arb_add_remove(arb_pgf_continuous_funding())
             .prop_map(PGFAction::Continuous)
             .boxed()
             .prop_union(arb_pgf_target().prop_map(PGFAction::Retro).boxed())
@@ -663,3 +800,85 @@ pub mod testing {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::established_address_1;
+
+    use super::*;
+
+    fn funding(
+        start_epoch: Option<u64>,
+        end_epoch: Option<u64>,
+        budget: Option<u64>,
+    ) -> StoragePgfFunding {
+        let target = PGFTarget::Internal(PGFInternalTarget {
+            target: established_address_1(),
+            amount: Amount::native_whole(10),
+        });
+        StoragePgfFunding::new(
+            PGFContinuousFunding {
+                target,
+                start_epoch: start_epoch.map(Epoch),
+                end_epoch: end_epoch.map(Epoch),
+                budget: budget.map(Amount::native_whole),
+            },
+            0,
+        )
+    }
+
+    /// Test that fundings stored in the legacy format are decoded without a
+    /// schedule nor budget
+    #[test]
+    fn test_storage_pgf_funding_legacy_decode() {
+        let expected = funding(None, None, None);
+        let legacy =
+            borsh::to_vec(&(expected.detail.clone(), expected.id)).unwrap();
+        let decoded = StoragePgfFunding::try_from_slice(&legacy).unwrap();
+        assert_eq!(decoded, expected);
+
+        let mut funding = funding(Some(1), Some(5), Some(25));
+        funding.paid = Amount::native_whole(10);
+        let bytes = borsh::to_vec(&funding).unwrap();
+        let decoded = StoragePgfFunding::try_from_slice(&bytes).unwrap();
+        assert_eq!(decoded, funding);
+
+        // Trailing bytes that don't make a full schedule are rejected
+        let mut truncated = legacy;
+        truncated.push(1);
+        assert!(StoragePgfFunding::try_from_slice(&truncated).is_err());
+    }
+
+    /// Test the schedule of a continuous funding
+    #[test]
+    fn test_storage_pgf_funding_schedule() {
+        let unscheduled = funding(None, None, None);
+        assert!(unscheduled.has_started(Epoch(0)));
+        assert!(!unscheduled.is_completed(Epoch(u64::MAX)));
+
+        let scheduled = funding(Some(2), Some(4), None);
+        assert!(!scheduled.has_started(Epoch(1)));
+        assert!(scheduled.has_started(Epoch(2)));
+        assert!(scheduled.has_started(Epoch(5)));
+        assert!(!scheduled.is_completed(Epoch(4)));
+        assert!(scheduled.is_completed(Epoch(5)));
+    }
+
+    /// Test that the payments of a continuous funding are capped by its
+    /// budget
+    #[test]
+    fn test_storage_pgf_funding_budget() {
+        let mut funding = funding(None, None, Some(25));
+        assert_eq!(funding.remaining_budget(), Some(Amount::native_whole(25)));
+        assert_eq!(funding.epoch_payment(), Amount::native_whole(10));
+        assert!(!funding.is_completed(Epoch(0)));
+
+        funding.paid = Amount::native_whole(20);
+        assert_eq!(funding.epoch_payment(), Amount::native_whole(5));
+        assert!(!funding.is_completed(Epoch(0)));
+
+        funding.paid = Amount::native_whole(25);
+        assert_eq!(funding.remaining_budget(), Some(Amount::zero()));
+        assert!(funding.is_completed(Epoch(0)));
+    }
+}
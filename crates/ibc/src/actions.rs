@@ -62,8 +62,12 @@ where
         self.wl_storage.split_borrow()
     }
 
-    fn write_tx_hash(&mut self, hash: Hash) -> write_log::Result<()> {
-        self.wl_storage.write_tx_hash(hash)
+    fn write_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: Option<DateTimeUtc>,
+    ) -> write_log::Result<()> {
+        self.wl_storage.write_tx_hash(hash, expiration)
     }
 }
 namada_state::impl_storage_traits!(IbcProtocolContext<'_, D, H>);
@@ -136,6 +140,11 @@ where
         token::burn(self.wl_storage, token, target, amount.amount())
     }
 
+    /// No gas is charged in a protocol transaction
+    fn charge_gas(&mut self, _used_gas: u64) -> Result<(), StorageError> {
+        Ok(())
+    }
+
     fn log_string(&self, message: String) {
         tracing::trace!(message);
     }
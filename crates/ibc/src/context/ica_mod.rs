@@ -0,0 +1,873 @@
+//! IBC modules for Interchain Accounts
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use namada_core::ibc::apps::transfer::types::ack_success_b64;
+use namada_core::ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
+use namada_core::ibc::core::channel::types::channel::{Counterparty, Order};
+use namada_core::ibc::core::channel::types::error::{
+    ChannelError, PacketError,
+};
+use namada_core::ibc::core::channel::types::packet::Packet;
+use namada_core::ibc::core::channel::types::Version;
+use namada_core::ibc::core::host::types::identifiers::{
+    ChannelId, ConnectionId, PortId,
+};
+use namada_core::ibc::core::router::module::Module;
+use namada_core::ibc::core::router::types::module::{ModuleExtras, ModuleId};
+use namada_core::ibc::primitives::Signer;
+use namada_gas::Gas;
+
+use super::common::IbcCommonContext;
+use super::transfer_mod::ModuleWrapper;
+use crate::ica::{
+    self, HostTx, InterchainAccountPacketData, Metadata, PendingHostTxs,
+    CONTROLLER_MODULE_ID_STR, ENCODING_BORSH, HOST_MODULE_ID_STR,
+    TX_TYPE_NAMADA, VERSION,
+};
+use crate::storage;
+
+/// IBC module for the interchain accounts hosted on this chain
+#[derive(Debug)]
+pub struct IcaHostModule<C>
+where
+    C: IbcCommonContext,
+{
+    /// Context
+    pub ctx: Rc<RefCell<C>>,
+}
+
+impl<C> IcaHostModule<C>
+where
+    C: IbcCommonContext,
+{
+    /// Make a new module
+    pub fn new(ctx: Rc<RefCell<C>>) -> Self {
+        Self { ctx }
+    }
+
+    /// Get the module ID
+    pub fn module_id(&self) -> ModuleId {
+        ModuleId::new(HOST_MODULE_ID_STR.to_string())
+    }
+
+    /// Validate the channel opening and return the version with the
+    /// interchain account address
+    fn validate_open_try(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ConnectionId, Version), ChannelError> {
+        if order != Order::Ordered {
+            return Err(into_channel_error(
+                "The channel for an interchain account should be ordered",
+            ));
+        }
+        if *port_id != ica::host_port_id() {
+            return Err(into_channel_error(format!(
+                "The port should be the host port: {port_id}"
+            )));
+        }
+        let controller_port_id = counterparty.port_id();
+        if !ica::is_controller_port(controller_port_id) {
+            return Err(into_channel_error(format!(
+                "The counterparty port should be a controller port: \
+                 {controller_port_id}"
+            )));
+        }
+        let connection_id = first_connection(connection_hops)?;
+        let mut metadata = Metadata::from_version(counterparty_version)
+            .map_err(into_channel_error)?;
+        if metadata.version != VERSION
+            || metadata.encoding != ENCODING_BORSH
+            || metadata.tx_type != TX_TYPE_NAMADA
+        {
+            return Err(into_channel_error(format!(
+                "The version metadata is not supported: {metadata:?}"
+            )));
+        }
+        if metadata.host_connection_id != connection_id.as_str() {
+            return Err(into_channel_error(format!(
+                "The host connection mismatched: Metadata {metadata:?}, \
+                 Connection {connection_id}"
+            )));
+        }
+        check_no_active_channel(
+            &*self.ctx.borrow(),
+            port_id,
+            controller_port_id,
+            &connection_id,
+        )?;
+
+        let account = storage::ica_account(&connection_id, controller_port_id);
+        metadata.address = account.to_string();
+        Ok((connection_id, metadata.to_version()))
+    }
+
+    /// Queue the transactions received with the packet to be executed by the
+    /// interchain account and charge their gas limits
+    fn queue_host_txs(&mut self, packet: &Packet) -> Result<(), String> {
+        let packet_data: InterchainAccountPacketData =
+            serde_json::from_slice(&packet.data)
+                .map_err(|e| format!("Decoding the packet data failed: {e}"))?;
+        let txs = packet_data.host_txs()?;
+        if txs.is_empty() {
+            return Err("No transaction to be executed".to_string());
+        }
+        let allowlist = ica::read_allowlist(&*self.ctx.borrow())
+            .map_err(|e| format!("Reading the allowlist failed: {e}"))?;
+        if let Some(tx) =
+            txs.iter().find(|tx| !allowlist.contains(&tx.code_hash))
+        {
+            return Err(format!(
+                "The transaction is not allowed: Code hash {}",
+                tx.code_hash
+            ));
+        }
+        self.check_host_limits(&txs)?;
+
+        let connection_id = self
+            .ctx
+            .borrow()
+            .channel_end(&packet.port_id_on_b, &packet.chan_id_on_b)
+            .map_err(|e| e.to_string())
+            .and_then(|channel| {
+                first_connection(channel.connection_hops())
+                    .map_err(|e| e.to_string())
+            })?;
+        let active_channel = read_active_channel(
+            &*self.ctx.borrow(),
+            &packet.port_id_on_a,
+            &connection_id,
+        )
+        .map_err(|e| e.to_string())?;
+        if active_channel.as_ref() != Some(&packet.chan_id_on_b) {
+            return Err(format!(
+                "The channel is not active for the interchain account: \
+                 Channel {}",
+                packet.chan_id_on_b
+            ));
+        }
+
+        let pending = PendingHostTxs {
+            channel_id: packet.chan_id_on_b.to_string(),
+            sequence: packet.seq_on_a.into(),
+            account: storage::ica_account(&connection_id, &packet.port_id_on_a),
+            txs,
+        };
+        // The declared gas is paid by the relayer when receiving the packet
+        let gas_limit = pending
+            .gas_limit()
+            .expect("The total gas limit should have been checked");
+        self.ctx
+            .borrow_mut()
+            .charge_gas(Gas::from_whole_units(gas_limit).into())
+            .map_err(|e| format!("Charging the gas failed: {e}"))?;
+        let key =
+            storage::ica_pending_tx_key(&packet.chan_id_on_b, packet.seq_on_a);
+        self.ctx
+            .borrow_mut()
+            .write(&key, pending)
+            .map_err(|e| format!("Writing the transactions failed: {e}"))
+    }
+
+    /// Check the number and the total gas limit of the transactions to be
+    /// executed in this block
+    fn check_host_limits(&self, txs: &[HostTx]) -> Result<(), String> {
+        let ctx = self.ctx.borrow();
+        let pending = ica::pending_host_txs(&*ctx).map_err(|e| {
+            format!("Reading the pending transactions failed: {e}")
+        })?;
+        let num_txs = pending
+            .iter()
+            .fold(txs.len(), |num, (_, pending)| num + pending.txs.len());
+        if num_txs > ica::MAX_HOST_TXS_PER_BLOCK {
+            return Err(format!(
+                "The number of the transactions exceeds the limit per block: \
+                 Limit {}",
+                ica::MAX_HOST_TXS_PER_BLOCK
+            ));
+        }
+        let max_gas = ica::max_host_gas_per_block(&*ctx)
+            .map_err(|e| format!("Reading the max block gas failed: {e}"))?;
+        let total_gas = pending
+            .iter()
+            .map(|(_, pending)| pending.gas_limit())
+            .chain(std::iter::once(ica::total_gas_limit(txs)))
+            .try_fold(0u64, |total, gas| total.checked_add(gas?));
+        match total_gas {
+            Some(total_gas) if total_gas <= max_gas => Ok(()),
+            _ => Err(format!(
+                "The total gas limit of the transactions exceeds the limit \
+                 per block: Limit {max_gas}"
+            )),
+        }
+    }
+}
+
+impl<C> ModuleWrapper for IcaHostModule<C>
+where
+    C: IbcCommonContext + Debug,
+{
+    fn as_module(&self) -> &dyn Module {
+        self
+    }
+
+    fn as_module_mut(&mut self) -> &mut dyn Module {
+        self
+    }
+}
+
+impl<C> Module for IcaHostModule<C>
+where
+    C: IbcCommonContext + Debug,
+{
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Err(into_channel_error(
+            "The channel can't be initialized by the host module",
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Err(into_channel_error(
+            "The channel can't be initialized by the host module",
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        let (_, version) = self.validate_open_try(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            counterparty_version,
+        )?;
+        Ok(version)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let (connection_id, version) = self.validate_open_try(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            counterparty_version,
+        )?;
+        let account =
+            storage::ica_account(&connection_id, counterparty.port_id());
+        let key =
+            storage::ica_account_key(counterparty.port_id(), &connection_id);
+        self.ctx
+            .borrow_mut()
+            .write(&key, account.to_string())
+            .map_err(into_channel_error)?;
+        Ok((ModuleExtras::empty(), version))
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        Err(into_channel_error(
+            "The channel opening can't be acknowledged by the host module",
+        ))
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Err(into_channel_error(
+            "The channel opening can't be acknowledged by the host module",
+        ))
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        let channel = self
+            .ctx
+            .borrow()
+            .channel_end(port_id, channel_id)
+            .map_err(into_channel_error)?;
+        let connection_id = first_connection(channel.connection_hops())?;
+        write_active_channel(
+            &mut *self.ctx.borrow_mut(),
+            channel.counterparty().port_id(),
+            &connection_id,
+            channel_id,
+        )?;
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Err(into_channel_error(
+            "The channel for an interchain account can't be closed",
+        ))
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Err(into_channel_error(
+            "The channel for an interchain account can't be closed",
+        ))
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        // The successful acknowledgement is overwritten with an error if the
+        // execution of the transactions fails at the end of the block
+        let ack = match self.queue_host_txs(packet) {
+            Ok(()) => AcknowledgementStatus::success(ack_success_b64()),
+            Err(e) => AcknowledgementStatus::error(
+                StatusValue::new(e)
+                    .expect("The error message shouldn't be empty"),
+            ),
+        };
+        (ModuleExtras::empty(), ack.into())
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Err(into_packet_error("The host module doesn't send any packet"))
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (
+            ModuleExtras::empty(),
+            Err(into_packet_error("The host module doesn't send any packet")),
+        )
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Err(into_packet_error("The host module doesn't send any packet"))
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (
+            ModuleExtras::empty(),
+            Err(into_packet_error("The host module doesn't send any packet")),
+        )
+    }
+}
+
+/// IBC module for the interchain accounts controlled by accounts on this chain
+#[derive(Debug)]
+pub struct IcaControllerModule<C>
+where
+    C: IbcCommonContext,
+{
+    /// Context
+    pub ctx: Rc<RefCell<C>>,
+}
+
+impl<C> IcaControllerModule<C>
+where
+    C: IbcCommonContext,
+{
+    /// Make a new module
+    pub fn new(ctx: Rc<RefCell<C>>) -> Self {
+        Self { ctx }
+    }
+
+    /// Get the module ID
+    pub fn module_id(&self) -> ModuleId {
+        ModuleId::new(CONTROLLER_MODULE_ID_STR.to_string())
+    }
+
+    /// Validate the channel opening on the controller port
+    fn validate_open_init(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(), ChannelError> {
+        if order != Order::Ordered {
+            return Err(into_channel_error(
+                "The channel for an interchain account should be ordered",
+            ));
+        }
+        if ica::controller_owner(port_id).is_none() {
+            return Err(into_channel_error(format!(
+                "The port should be a controller port of an owner: {port_id}"
+            )));
+        }
+        if *counterparty.port_id() != ica::host_port_id() {
+            return Err(into_channel_error(format!(
+                "The counterparty port should be the host port: {}",
+                counterparty.port_id()
+            )));
+        }
+        let connection_id = first_connection(connection_hops)?;
+        let metadata =
+            Metadata::from_version(version).map_err(into_channel_error)?;
+        if metadata.version != VERSION {
+            return Err(into_channel_error(format!(
+                "The version is not supported: {}",
+                metadata.version
+            )));
+        }
+        if metadata.controller_connection_id != connection_id.as_str() {
+            return Err(into_channel_error(format!(
+                "The controller connection mismatched: Metadata {metadata:?}, \
+                 Connection {connection_id}"
+            )));
+        }
+        check_no_active_channel(
+            &*self.ctx.borrow(),
+            port_id,
+            port_id,
+            &connection_id,
+        )
+    }
+
+    /// Validate the channel opening acknowledged by the host and return the
+    /// connection and the interchain account address on the host chain
+    fn validate_open_ack(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(ConnectionId, String), ChannelError> {
+        let channel = self
+            .ctx
+            .borrow()
+            .channel_end(port_id, channel_id)
+            .map_err(into_channel_error)?;
+        let connection_id = first_connection(channel.connection_hops())?;
+        let metadata = Metadata::from_version(counterparty_version)
+            .map_err(into_channel_error)?;
+        if metadata.version != VERSION {
+            return Err(into_channel_error(format!(
+                "The version is not supported: {}",
+                metadata.version
+            )));
+        }
+        if metadata.controller_connection_id != connection_id.as_str() {
+            return Err(into_channel_error(format!(
+                "The controller connection mismatched: Metadata {metadata:?}, \
+                 Connection {connection_id}"
+            )));
+        }
+        if metadata.address.is_empty() {
+            return Err(into_channel_error(
+                "The interchain account address wasn't set by the host",
+            ));
+        }
+        Ok((connection_id, metadata.address))
+    }
+}
+
+impl<C> ModuleWrapper for IcaControllerModule<C>
+where
+    C: IbcCommonContext + Debug,
+{
+    fn as_module(&self) -> &dyn Module {
+        self
+    }
+
+    fn as_module_mut(&mut self) -> &mut dyn Module {
+        self
+    }
+}
+
+impl<C> Module for IcaControllerModule<C>
+where
+    C: IbcCommonContext + Debug,
+{
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.validate_open_init(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            version,
+        )?;
+        Ok(version.clone())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        self.validate_open_init(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            version,
+        )?;
+        Ok((ModuleExtras::empty(), version.clone()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Err(into_channel_error(
+            "The channel opening can't be tried by the controller module",
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Err(into_channel_error(
+            "The channel opening can't be tried by the controller module",
+        ))
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        self.validate_open_ack(port_id, channel_id, counterparty_version)
+            .map(|_| ())
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        let (connection_id, address) =
+            self.validate_open_ack(port_id, channel_id, counterparty_version)?;
+        let key = storage::ica_account_key(port_id, &connection_id);
+        self.ctx
+            .borrow_mut()
+            .write(&key, address)
+            .map_err(into_channel_error)?;
+        write_active_channel(
+            &mut *self.ctx.borrow_mut(),
+            port_id,
+            &connection_id,
+            channel_id,
+        )?;
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Err(into_channel_error(
+            "The channel opening can't be confirmed by the controller module",
+        ))
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Err(into_channel_error(
+            "The channel opening can't be confirmed by the controller module",
+        ))
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Err(into_channel_error(
+            "The channel for an interchain account can't be closed",
+        ))
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Err(into_channel_error(
+            "The channel for an interchain account can't be closed",
+        ))
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        let ack = AcknowledgementStatus::error(
+            StatusValue::new("The controller module doesn't receive a packet")
+                .expect("The error message shouldn't be empty"),
+        );
+        (ModuleExtras::empty(), ack.into())
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        // The result of the execution on the host chain is given in the
+        // acknowledgement for the owner
+        (ModuleExtras::empty(), Ok(()))
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        // The ordered channel is closed on timeout. The owner can register the
+        // interchain account again to reopen a channel.
+        (ModuleExtras::empty(), Ok(()))
+    }
+}
+
+/// Get the connection of the channel for an interchain account
+fn first_connection(
+    connection_hops: &[ConnectionId],
+) -> Result<ConnectionId, ChannelError> {
+    match connection_hops {
+        [connection_id] => Ok(connection_id.clone()),
+        _ => Err(into_channel_error(format!(
+            "The channel should have only one connection: {connection_hops:?}"
+        ))),
+    }
+}
+
+/// Read the active channel for the controller port on the connection
+fn read_active_channel<C>(
+    ctx: &C,
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Result<Option<ChannelId>, ChannelError>
+where
+    C: IbcCommonContext,
+{
+    let key = storage::ica_active_channel_key(port_id, connection_id);
+    ctx.read::<String>(&key)
+        .map_err(into_channel_error)?
+        .map(|channel_id| {
+            ChannelId::from_str(&channel_id).map_err(into_channel_error)
+        })
+        .transpose()
+}
+
+/// Write the active channel for the controller port on the connection
+fn write_active_channel<C>(
+    ctx: &mut C,
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+    channel_id: &ChannelId,
+) -> Result<(), ChannelError>
+where
+    C: IbcCommonContext,
+{
+    let key = storage::ica_active_channel_key(port_id, connection_id);
+    ctx.write(&key, channel_id.to_string())
+        .map_err(into_channel_error)
+}
+
+/// Check that the active channel for the controller port on the connection
+/// doesn't exist or has been closed. The channel on this chain is on the local
+/// port, i.e. the controller port or the host port.
+fn check_no_active_channel<C>(
+    ctx: &C,
+    local_port_id: &PortId,
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Result<(), ChannelError>
+where
+    C: IbcCommonContext,
+{
+    let Some(channel_id) = read_active_channel(ctx, port_id, connection_id)?
+    else {
+        return Ok(());
+    };
+    let channel = ctx
+        .channel_end(local_port_id, &channel_id)
+        .map_err(into_channel_error)?;
+    if channel.is_open() {
+        return Err(into_channel_error(format!(
+            "The active channel already exists: Port {port_id}, Connection \
+             {connection_id}, Channel {channel_id}"
+        )));
+    }
+    Ok(())
+}
+
+fn into_channel_error(error: impl ToString) -> ChannelError {
+    ChannelError::AppModule {
+        description: error.to_string(),
+    }
+}
+
+fn into_packet_error(error: impl ToString) -> PacketError {
+    PacketError::AppModule {
+        description: error.to_string(),
+    }
+}
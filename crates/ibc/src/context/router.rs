@@ -9,12 +9,15 @@ use namada_core::ibc::core::router::router::Router;
 use namada_core::ibc::core::router::types::module::ModuleId;
 
 use super::super::ModuleWrapper;
+use crate::ica;
 
 /// IBC router
 #[derive(Debug, Default)]
 pub struct IbcRouter<'a> {
     modules: HashMap<ModuleId, Rc<dyn ModuleWrapper + 'a>>,
     ports: HashMap<PortId, ModuleId>,
+    /// The module for all the controller ports of the interchain accounts
+    ica_controller: Option<ModuleId>,
 }
 
 impl<'a> IbcRouter<'a> {
@@ -23,6 +26,7 @@ impl<'a> IbcRouter<'a> {
         Self {
             modules: HashMap::new(),
             ports: HashMap::new(),
+            ica_controller: None,
         }
     }
 
@@ -35,6 +39,26 @@ impl<'a> IbcRouter<'a> {
         self.modules.insert(module_id.clone(), Rc::new(module));
         self.ports.insert(PortId::transfer(), module_id);
     }
+
+    /// Add Interchain Accounts host route
+    pub fn add_ica_host_module(
+        &mut self,
+        module_id: ModuleId,
+        module: impl ModuleWrapper + 'a,
+    ) {
+        self.modules.insert(module_id.clone(), Rc::new(module));
+        self.ports.insert(ica::host_port_id(), module_id);
+    }
+
+    /// Add Interchain Accounts controller route for all the controller ports
+    pub fn add_ica_controller_module(
+        &mut self,
+        module_id: ModuleId,
+        module: impl ModuleWrapper + 'a,
+    ) {
+        self.modules.insert(module_id.clone(), Rc::new(module));
+        self.ica_controller = Some(module_id);
+    }
 }
 
 impl<'a> Router for IbcRouter<'a> {
@@ -53,6 +77,12 @@ impl<'a> Router for IbcRouter<'a> {
     }
 
     fn lookup_module(&self, port_id: &PortId) -> Option<ModuleId> {
-        self.ports.get(port_id).cloned()
+        match self.ports.get(port_id) {
+            Some(module_id) => Some(module_id.clone()),
+            None if ica::is_controller_port(port_id) => {
+                self.ica_controller.clone()
+            }
+            None => None,
+        }
     }
 }
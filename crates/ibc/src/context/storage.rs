@@ -49,6 +49,9 @@ pub trait IbcStorageContext: StorageRead + StorageWrite {
         amount: DenominatedAmount,
     ) -> Result<(), Error>;
 
+    /// Charge gas in sub units
+    fn charge_gas(&mut self, used_gas: u64) -> Result<(), Error>;
+
     /// Logging
     fn log_string(&self, message: String);
 }
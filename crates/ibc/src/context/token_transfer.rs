@@ -25,7 +25,7 @@ pub struct TokenTransferContext<C>
 where
     C: IbcCommonContext,
 {
-    inner: Rc<RefCell<C>>,
+    pub(crate) inner: Rc<RefCell<C>>,
 }
 
 impl<C> TokenTransferContext<C>
@@ -39,7 +39,7 @@ where
 
     /// Get the token address and the amount from PrefixedCoin. If the base
     /// denom is not an address, it returns `IbcToken`
-    fn get_token_amount(
+    pub(crate) fn get_token_amount(
         &self,
         coin: &PrefixedCoin,
     ) -> Result<(Address, token::DenominatedAmount), TokenTransferError> {
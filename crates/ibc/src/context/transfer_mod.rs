@@ -3,7 +3,14 @@
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::str::FromStr;
 
+use namada_core::ibc::apps::transfer::context::{
+    TokenTransferExecutionContext, TokenTransferValidationContext,
+};
+use namada_core::ibc::apps::transfer::handler::{
+    send_transfer_execute, send_transfer_validate,
+};
 use namada_core::ibc::apps::transfer::module::{
     on_acknowledgement_packet_execute, on_acknowledgement_packet_validate,
     on_chan_close_confirm_execute, on_chan_close_confirm_validate,
@@ -16,13 +23,20 @@ use namada_core::ibc::apps::transfer::module::{
     on_timeout_packet_validate,
 };
 use namada_core::ibc::apps::transfer::types::error::TokenTransferError;
-use namada_core::ibc::apps::transfer::types::MODULE_ID_STR;
-use namada_core::ibc::core::channel::types::acknowledgement::Acknowledgement;
+use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use namada_core::ibc::apps::transfer::types::packet::PacketData;
+use namada_core::ibc::apps::transfer::types::{
+    is_receiver_chain_source, PrefixedCoin, MODULE_ID_STR,
+};
+use namada_core::ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
 use namada_core::ibc::core::channel::types::channel::{Counterparty, Order};
 use namada_core::ibc::core::channel::types::error::{
     ChannelError, PacketError,
 };
 use namada_core::ibc::core::channel::types::packet::Packet;
+use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
 use namada_core::ibc::core::channel::types::Version;
 use namada_core::ibc::core::host::types::identifiers::{
     ChannelId, ConnectionId, PortId,
@@ -33,6 +47,12 @@ use namada_core::ibc::primitives::Signer;
 
 use super::common::IbcCommonContext;
 use super::token_transfer::TokenTransferContext;
+use super::IbcContext;
+use crate::forward::{
+    self, ForwardMetadata, InFlightPacket, IBC_FORWARD, MAX_REFUND_ATTEMPTS,
+};
+use crate::rate_limit::{self, FlowDirection};
+use crate::received_coin;
 
 /// IBC module wrapper for getting the reference of the module
 pub trait ModuleWrapper: Module {
@@ -68,6 +88,309 @@ where
     pub fn module_id(&self) -> ModuleId {
         ModuleId::new(MODULE_ID_STR.to_string())
     }
+
+    /// Receive the token to the forward address and send it to the next chain
+    /// according to the forwarding metadata
+    fn forward_packet(
+        &mut self,
+        packet: &Packet,
+        data: PacketData,
+        metadata: ForwardMetadata,
+    ) -> (ModuleExtras, Acknowledgement) {
+        // Check the forwarding before receiving the token not to hold the
+        // token which can't be forwarded
+        let coin = received_coin(packet, data.token.clone());
+        let msg = match self.make_forward_msg(coin.clone(), &metadata) {
+            Ok(msg) => msg,
+            Err(e) => return (ModuleExtras::empty(), error_ack(e)),
+        };
+        let fallback = match metadata.fallback_address() {
+            Ok(fallback) => fallback,
+            Err(e) => return (ModuleExtras::empty(), error_ack(e)),
+        };
+        let mut ibc_ctx = IbcContext::new(self.ctx.inner.clone());
+        if let Err(e) = send_transfer_validate(&ibc_ctx, &self.ctx, msg.clone())
+        {
+            return (ModuleExtras::empty(), error_ack(e));
+        }
+        if let Err(e) = self.check_rate_limit(
+            &msg.chan_id_on_a,
+            &msg.packet_data.token,
+            FlowDirection::Outflow,
+        ) {
+            return (ModuleExtras::empty(), error_ack(e));
+        }
+
+        let mut received = packet.clone();
+        received.data = serde_json::to_vec(&PacketData {
+            receiver: IBC_FORWARD.to_string().into(),
+            ..data.clone()
+        })
+        .expect("Encoding the packet data shouldn't fail");
+        let (extras, ack) = on_recv_packet_execute(&mut self.ctx, &received);
+        if !is_ack_successful(&ack) {
+            return (extras, ack);
+        }
+
+        let in_flight = InFlightPacket {
+            refund_port_id: packet.port_id_on_b.to_string(),
+            refund_channel_id: packet.chan_id_on_b.to_string(),
+            original_sender: data.sender.to_string(),
+            original_sequence: packet.seq_on_a.value(),
+            timeout: metadata.timeout().as_nanos() as u64,
+            fallback,
+            refund_attempts: 0,
+        };
+        if let Err(e) = self.send_forward_msg(&mut ibc_ctx, msg, in_flight) {
+            // Return the received token not to be refunded twice on the
+            // previous chain with the error acknowledgement
+            if let Err(e) = self.revert_receiving(packet, &data, &coin) {
+                return (extras, error_ack(e));
+            }
+            return (extras, error_ack(e));
+        }
+        (extras, ack)
+    }
+
+    /// Make the message to send the received token to the next chain
+    fn make_forward_msg(
+        &self,
+        coin: PrefixedCoin,
+        metadata: &ForwardMetadata,
+    ) -> Result<MsgTransfer, String> {
+        let port_id_on_a = metadata.port_id()?;
+        let chan_id_on_a = metadata.channel_id()?;
+        let timeout_timestamp_on_b = (self
+            .ctx
+            .inner
+            .borrow()
+            .host_timestamp()
+            .map_err(|e| e.to_string())?
+            + metadata.timeout())
+        .map_err(|e| e.to_string())?;
+        Ok(MsgTransfer {
+            port_id_on_a,
+            chan_id_on_a,
+            packet_data: PacketData {
+                token: coin,
+                sender: IBC_FORWARD.to_string().into(),
+                receiver: metadata.receiver.clone().into(),
+                memo: metadata.next_memo().into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b,
+        })
+    }
+
+    /// Check the rate limit of the token flowing through the channel. The
+    /// flow itself is recorded once the token has been sent or received.
+    fn check_rate_limit(
+        &self,
+        channel_id: &ChannelId,
+        coin: &PrefixedCoin,
+        direction: FlowDirection,
+    ) -> Result<(), String> {
+        let (token, amount) =
+            self.ctx.get_token_amount(coin).map_err(|e| e.to_string())?;
+        let exceeded = rate_limit::check_flow(
+            &*self.ctx.inner.borrow(),
+            channel_id,
+            &token,
+            direction,
+            amount.amount(),
+        )
+        .map_err(|e| e.to_string())?;
+        match exceeded {
+            Some((usage, quota)) => Err(format!(
+                "The {direction} of the token {token} on the channel \
+                 {channel_id} exceeds the quota: Usage {usage}, Quota {quota}",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Send the token to the next chain and record the in-flight packet to
+    /// refund the token when the forwarding fails
+    fn send_forward_msg(
+        &mut self,
+        ibc_ctx: &mut IbcContext<C>,
+        msg: MsgTransfer,
+        in_flight: InFlightPacket,
+    ) -> Result<(), String> {
+        let port_id = msg.port_id_on_a.clone();
+        let channel_id = msg.chan_id_on_a.clone();
+        let sequence = self
+            .ctx
+            .inner
+            .borrow()
+            .get_next_sequence_send(&port_id, &channel_id)
+            .map_err(|e| e.to_string())?;
+        let (token, amount) = self
+            .ctx
+            .get_token_amount(&msg.packet_data.token)
+            .map_err(|e| e.to_string())?;
+        send_transfer_execute(ibc_ctx, &mut self.ctx, msg)
+            .map_err(|e| e.to_string())?;
+
+        let mut inner = self.ctx.inner.borrow_mut();
+        rate_limit::record_flow(
+            &mut *inner,
+            &channel_id,
+            &token,
+            FlowDirection::Outflow,
+            amount.amount(),
+        )
+        .map_err(|e| e.to_string())?;
+        forward::write_in_flight(
+            &mut *inner,
+            &port_id,
+            &channel_id,
+            sequence,
+            in_flight,
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    /// Return the token received to the forward address
+    fn revert_receiving(
+        &mut self,
+        packet: &Packet,
+        data: &PacketData,
+        coin: &PrefixedCoin,
+    ) -> Result<(), String> {
+        if is_receiver_chain_source(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+            &data.token.denom,
+        ) {
+            // The token was unescrowed
+            let escrow = self
+                .ctx
+                .get_escrow_account(&packet.port_id_on_b, &packet.chan_id_on_b)
+                .map_err(|e| e.to_string())?;
+            self.ctx
+                .send_coins_execute(&IBC_FORWARD, &escrow, coin)
+                .map_err(|e| e.to_string())
+        } else {
+            // The token was minted
+            self.ctx
+                .burn_coins_execute(&IBC_FORWARD, coin)
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    /// Complete the forwarding when the forwarded or the refund packet has
+    /// been acknowledged or has timed out. The token refunded to the forward
+    /// address is sent back to the original sender if the forwarding failed.
+    /// When the refund fails, the token is sent to the fallback address or the
+    /// refund is retried.
+    fn complete_forward(
+        &mut self,
+        packet: &Packet,
+        is_refunded: bool,
+    ) -> Result<(), PacketError> {
+        let in_flight = forward::read_in_flight(
+            &*self.ctx.inner.borrow(),
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            packet.seq_on_a,
+        )
+        .map_err(into_packet_error)?;
+        let Some(in_flight) = in_flight else {
+            // The packet wasn't forwarded
+            return Ok(());
+        };
+        forward::delete_in_flight(
+            &mut *self.ctx.inner.borrow_mut(),
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            packet.seq_on_a,
+        )
+        .map_err(into_packet_error)?;
+        if !is_refunded {
+            return Ok(());
+        }
+
+        let data = serde_json::from_slice::<PacketData>(&packet.data)
+            .map_err(into_packet_error)?;
+        if in_flight.is_refund() {
+            // The refund failed
+            if let Some(fallback) = &in_flight.fallback {
+                return self
+                    .ctx
+                    .send_coins_execute(&IBC_FORWARD, fallback, &data.token)
+                    .map_err(into_packet_error);
+            }
+            if in_flight.refund_attempts >= MAX_REFUND_ATTEMPTS {
+                self.ctx.inner.borrow().log_string(format!(
+                    "The refund of the forwarded packet {} on {}/{} failed {} \
+                     times. The token {} is held by the forward address.",
+                    in_flight.original_sequence,
+                    in_flight.refund_port_id,
+                    in_flight.refund_channel_id,
+                    in_flight.refund_attempts,
+                    data.token,
+                ));
+                return Ok(());
+            }
+        }
+        self.send_refund(in_flight, data)
+    }
+
+    /// Send the token back to the original sender and record the refund
+    /// packet as an in-flight packet
+    fn send_refund(
+        &mut self,
+        in_flight: InFlightPacket,
+        data: PacketData,
+    ) -> Result<(), PacketError> {
+        let port_id = PortId::from_str(&in_flight.refund_port_id)
+            .map_err(into_packet_error)?;
+        let channel_id = ChannelId::from_str(&in_flight.refund_channel_id)
+            .map_err(into_packet_error)?;
+        let timeout_timestamp_on_b = (self
+            .ctx
+            .inner
+            .borrow()
+            .host_timestamp()
+            .map_err(into_packet_error)?
+            + in_flight.timeout())
+        .map_err(into_packet_error)?;
+        let sequence = self
+            .ctx
+            .inner
+            .borrow()
+            .get_next_sequence_send(&port_id, &channel_id)
+            .map_err(into_packet_error)?;
+        let msg = MsgTransfer {
+            port_id_on_a: port_id.clone(),
+            chan_id_on_a: channel_id.clone(),
+            packet_data: PacketData {
+                token: data.token,
+                sender: IBC_FORWARD.to_string().into(),
+                receiver: in_flight.original_sender.clone().into(),
+                memo: String::new().into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b,
+        };
+        let mut ibc_ctx = IbcContext::new(self.ctx.inner.clone());
+        send_transfer_execute(&mut ibc_ctx, &mut self.ctx, msg)
+            .map_err(into_packet_error)?;
+
+        let refund_attempts = in_flight.refund_attempts + 1;
+        forward::write_in_flight(
+            &mut *self.ctx.inner.borrow_mut(),
+            &port_id,
+            &channel_id,
+            sequence,
+            InFlightPacket {
+                refund_attempts,
+                ..in_flight
+            },
+        )
+        .map_err(into_packet_error)
+    }
 }
 
 impl<C> ModuleWrapper for TransferModule<C>
@@ -266,7 +589,30 @@ where
         packet: &Packet,
         _relayer: &Signer,
     ) -> (ModuleExtras, Acknowledgement) {
-        on_recv_packet_execute(&mut self.ctx, packet)
+        let data = serde_json::from_slice::<PacketData>(&packet.data).ok();
+        // Reject the token over the inflow quota with the error
+        // acknowledgement, so that it's refunded on the sender chain
+        if let Some(data) = &data {
+            let coin = received_coin(packet, data.token.clone());
+            if let Err(e) = self.check_rate_limit(
+                &packet.chan_id_on_b,
+                &coin,
+                FlowDirection::Inflow,
+            ) {
+                return (ModuleExtras::empty(), error_ack(e));
+            }
+        }
+        let forward = data.and_then(|data| {
+            ForwardMetadata::from_memo(data.memo.as_ref())
+                .map(|metadata| (data, metadata))
+        });
+        match forward {
+            Some((data, Ok(metadata))) => {
+                self.forward_packet(packet, data, metadata)
+            }
+            Some((_, Err(e))) => (ModuleExtras::empty(), error_ack(e)),
+            None => on_recv_packet_execute(&mut self.ctx, packet),
+        }
     }
 
     fn on_acknowledgement_packet_validate(
@@ -296,7 +642,11 @@ where
             acknowledgement,
             relayer,
         );
-        (extras, result.map_err(into_packet_error))
+        let result = result.map_err(into_packet_error).and_then(|_| {
+            let is_refunded = !is_ack_successful(acknowledgement);
+            self.complete_forward(packet, is_refunded)
+        });
+        (extras, result)
     }
 
     fn on_timeout_packet_validate(
@@ -315,7 +665,10 @@ where
     ) -> (ModuleExtras, Result<(), PacketError>) {
         let (extras, result) =
             on_timeout_packet_execute(&mut self.ctx, packet, relayer);
-        (extras, result.map_err(into_packet_error))
+        let result = result
+            .map_err(into_packet_error)
+            .and_then(|_| self.complete_forward(packet, true));
+        (extras, result)
     }
 }
 
@@ -325,12 +678,25 @@ fn into_channel_error(error: TokenTransferError) -> ChannelError {
     }
 }
 
-fn into_packet_error(error: TokenTransferError) -> PacketError {
+fn into_packet_error(error: impl ToString) -> PacketError {
     PacketError::AppModule {
         description: error.to_string(),
     }
 }
 
+fn error_ack(error: impl ToString) -> Acknowledgement {
+    AcknowledgementStatus::error(
+        StatusValue::new(error.to_string())
+            .expect("The error message shouldn't be empty"),
+    )
+    .into()
+}
+
+fn is_ack_successful(ack: &Acknowledgement) -> bool {
+    serde_json::from_slice::<AcknowledgementStatus>(ack.as_ref())
+        .map_or(false, |status| status.is_successful())
+}
+
 /// Helpers for testing
 #[cfg(any(test, feature = "testing"))]
 pub mod testing {
@@ -0,0 +1,319 @@
+//! Packet forwarding for multi-hop IBC transfers
+//!
+//! A token can be transferred from a chain to another chain through this chain
+//! with a single transfer on the source chain. The forwarding metadata is set
+//! in the memo of the transfer, e.g.
+//!
+//! ```json
+//! {
+//!   "forward": {
+//!     "receiver": "cosmos1...",
+//!     "port": "transfer",
+//!     "channel": "channel-1",
+//!     "timeout": 600000000000,
+//!     "fallback": "tnam1...",
+//!     "next": { "forward": { ... } }
+//!   }
+//! }
+//! ```
+//!
+//! The received token is held by [`IBC_FORWARD`] and sent along the next hop
+//! with the `next` metadata as the memo. When the forwarded packet times out or
+//! fails on the next chain, the token is refunded to the original sender on
+//! the previous chain with a new transfer. The original packet has been
+//! already acknowledged successfully because IBC acknowledgements can't be
+//! written asynchronously here.
+//!
+//! The refund packet is also tracked as an in-flight packet with the timeout
+//! of the metadata. When the refund fails, the token is sent to the
+//! `fallback` address on this chain if it is given. Otherwise, the refund is
+//! retried up to [`MAX_REFUND_ATTEMPTS`] times and then the token is held by
+//! [`IBC_FORWARD`].
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::ibc::core::host::types::identifiers::{
+    ChannelId, PortId, Sequence,
+};
+use namada_core::types::address::Address;
+pub use namada_core::types::address::IBC_FORWARD;
+use namada_core::types::storage::Key;
+use namada_storage::{Result as StorageResult, StorageRead, StorageWrite};
+use namada_trans_token::storage_key::is_any_token_balance_key;
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+/// The timeout of the forwarded packet if it isn't given in the metadata
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The maximum number of the refund transfers for a forwarded packet
+pub const MAX_REFUND_ATTEMPTS: u8 = 3;
+
+/// The metadata to forward the received token to the next chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardMetadata {
+    /// The receiver on the next chain
+    pub receiver: String,
+    /// The port on this chain to send the token
+    #[serde(default = "default_port")]
+    pub port: String,
+    /// The channel on this chain to send the token
+    pub channel: String,
+    /// The timeout of the forwarded packet in nanoseconds relative to the
+    /// block time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// The address on this chain to receive the token when the refund to the
+    /// original sender fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    /// The memo of the forwarded packet, e.g. the metadata for the next hop
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ForwardMemo {
+    forward: ForwardMetadata,
+}
+
+fn default_port() -> String {
+    PortId::transfer().to_string()
+}
+
+impl ForwardMetadata {
+    /// Parse the forwarding metadata from the memo. Returns `None` if the memo
+    /// isn't for forwarding.
+    pub fn from_memo(memo: &str) -> Option<Result<Self, String>> {
+        let value = serde_json::from_str::<serde_json::Value>(memo).ok()?;
+        value.as_object()?.get("forward")?;
+        Some(
+            serde_json::from_value::<ForwardMemo>(value)
+                .map(|memo| memo.forward)
+                .map_err(|e| {
+                    format!("Decoding the forwarding metadata failed: {e}")
+                }),
+        )
+    }
+
+    /// Make the memo to forward the token with this metadata
+    pub fn to_memo(&self) -> String {
+        serde_json::to_string(&ForwardMemo {
+            forward: self.clone(),
+        })
+        .expect("Encoding the forwarding metadata shouldn't fail")
+    }
+
+    /// Get the port ID to send the token
+    pub fn port_id(&self) -> Result<PortId, String> {
+        PortId::from_str(&self.port)
+            .map_err(|e| format!("Invalid forwarding port: {e}"))
+    }
+
+    /// Get the channel ID to send the token
+    pub fn channel_id(&self) -> Result<ChannelId, String> {
+        ChannelId::from_str(&self.channel)
+            .map_err(|e| format!("Invalid forwarding channel: {e}"))
+    }
+
+    /// Get the timeout of the forwarded packet
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+            .map(Duration::from_nanos)
+            .unwrap_or(DEFAULT_FORWARD_TIMEOUT)
+    }
+
+    /// Get the fallback address on this chain
+    pub fn fallback_address(&self) -> Result<Option<Address>, String> {
+        self.fallback
+            .as_ref()
+            .map(|fallback| {
+                Address::decode(fallback)
+                    .map_err(|e| format!("Invalid fallback address: {e}"))
+            })
+            .transpose()
+    }
+
+    /// Get the memo of the forwarded packet
+    pub fn next_memo(&self) -> String {
+        match &self.next {
+            Some(serde_json::Value::String(memo)) => memo.clone(),
+            Some(next) => next.to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+/// The packet forwarded through this chain which hasn't been acknowledged yet
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct InFlightPacket {
+    /// The port on which the original packet was received
+    pub refund_port_id: String,
+    /// The channel on which the original packet was received
+    pub refund_channel_id: String,
+    /// The sender of the original packet on the previous chain
+    pub original_sender: String,
+    /// The sequence of the original packet
+    pub original_sequence: u64,
+    /// The timeout of the forwarded and the refund packets in nanoseconds
+    /// relative to the block time
+    pub timeout: u64,
+    /// The address on this chain to receive the token when the refund fails
+    pub fallback: Option<Address>,
+    /// The number of the refund transfers. The in-flight packet is the
+    /// forwarded packet if it is zero, otherwise it is the refund packet.
+    pub refund_attempts: u8,
+}
+
+impl InFlightPacket {
+    /// Get the timeout of the packet
+    pub fn timeout(&self) -> Duration {
+        Duration::from_nanos(self.timeout)
+    }
+
+    /// Check if the in-flight packet is a refund packet
+    pub fn is_refund(&self) -> bool {
+        self.refund_attempts > 0
+    }
+}
+
+/// Check if the key is a balance key of [`IBC_FORWARD`]. Returns the token
+/// address if it is.
+pub fn is_forward_balance_key(key: &Key) -> Option<&Address> {
+    match is_any_token_balance_key(key) {
+        Some([token, owner]) if *owner == IBC_FORWARD => Some(token),
+        _ => None,
+    }
+}
+
+/// Read the in-flight packet for the forwarded packet
+pub fn read_in_flight<S>(
+    storage: &S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> StorageResult<Option<InFlightPacket>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::forward_in_flight_key(
+        port_id, channel_id, sequence,
+    ))
+}
+
+/// Write the in-flight packet for the forwarded packet
+pub fn write_in_flight<S>(
+    storage: &mut S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+    in_flight: InFlightPacket,
+) -> StorageResult<()>
+where
+    S: StorageWrite,
+{
+    storage.write(
+        &storage::forward_in_flight_key(port_id, channel_id, sequence),
+        in_flight,
+    )
+}
+
+/// Delete the in-flight packet when the forwarded packet has been
+/// acknowledged or has timed out
+pub fn delete_in_flight<S>(
+    storage: &mut S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> StorageResult<()>
+where
+    S: StorageWrite,
+{
+    storage.delete(&storage::forward_in_flight_key(
+        port_id, channel_id, sequence,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_memo() {
+        // Not for forwarding
+        assert!(ForwardMetadata::from_memo("").is_none());
+        assert!(ForwardMetadata::from_memo("memo").is_none());
+        assert!(ForwardMetadata::from_memo(r#"{"wasm": {}}"#).is_none());
+
+        // Invalid metadata
+        assert!(matches!(
+            ForwardMetadata::from_memo(r#"{"forward": {"receiver": "a"}}"#),
+            Some(Err(_))
+        ));
+
+        let memo = r#"{
+            "forward": {
+                "receiver": "receiver",
+                "channel": "channel-1",
+                "next": {
+                    "forward": {"receiver": "final", "channel": "channel-2"}
+                }
+            }
+        }"#;
+        let metadata = ForwardMetadata::from_memo(memo).unwrap().unwrap();
+        assert_eq!(metadata.port_id().unwrap(), PortId::transfer());
+        assert_eq!(
+            metadata.channel_id().unwrap(),
+            ChannelId::from_str("channel-1").unwrap()
+        );
+        assert_eq!(metadata.timeout(), DEFAULT_FORWARD_TIMEOUT);
+        assert_eq!(metadata.fallback_address().unwrap(), None);
+
+        let next = ForwardMetadata::from_memo(&metadata.next_memo())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.receiver, "final");
+        assert_eq!(next.next_memo(), "");
+
+        // Round trip
+        let decoded = ForwardMetadata::from_memo(&metadata.to_memo())
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded, metadata);
+
+        // With the timeout and the fallback address
+        let memo = r#"{
+            "forward": {
+                "receiver": "receiver",
+                "channel": "channel-1",
+                "timeout": 1000,
+                "fallback": "tnam1q8j5s6xp55p05yznwnftkv3kr9gjtsw3nq7x6tw5"
+            }
+        }"#;
+        let metadata = ForwardMetadata::from_memo(memo).unwrap().unwrap();
+        assert_eq!(metadata.timeout(), Duration::from_nanos(1000));
+        assert_eq!(
+            metadata.fallback_address().unwrap(),
+            Some(
+                Address::decode(
+                    "tnam1q8j5s6xp55p05yznwnftkv3kr9gjtsw3nq7x6tw5"
+                )
+                .unwrap()
+            )
+        );
+        let memo = r#"{
+            "forward": {
+                "receiver": "receiver",
+                "channel": "channel-1",
+                "fallback": "invalid"
+            }
+        }"#;
+        let metadata = ForwardMetadata::from_memo(memo).unwrap().unwrap();
+        assert!(metadata.fallback_address().is_err());
+    }
+}
@@ -0,0 +1,467 @@
+//! IBC Interchain Accounts (ICS-27)
+//!
+//! An account on this chain can control an account on another chain through
+//! an ordered channel between the controller port `icacontroller-<owner>` and
+//! the host port `icahost`. When this chain is the host chain, the
+//! transactions in a received packet are executed by the protocol at the end
+//! of the block under the interchain account derived from the connection and
+//! the controller port. Only the transactions whose code hash is in the
+//! allowlist set by governance can be executed. The gas limits declared by the
+//! transactions are charged when the packet is received, and the number and
+//! the total gas of the transactions executed in a block are capped. When the
+//! execution fails, the acknowledgement of the packet is overwritten with an
+//! error so that the controller chain is notified.
+
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use data_encoding::BASE64;
+use namada_core::borsh::BorshSerializeExt;
+use namada_core::ibc::core::channel::types::acknowledgement::{
+    AcknowledgementStatus, StatusValue,
+};
+use namada_core::ibc::core::channel::types::Version;
+use namada_core::ibc::core::host::types::error::IdentifierError;
+use namada_core::ibc::core::host::types::identifiers::{
+    ChannelId, ConnectionId, PortId, Sequence,
+};
+use namada_core::types::address::Address;
+use namada_core::types::hash::Hash;
+use namada_core::types::storage::Key;
+use namada_storage::{Result as StorageResult, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+/// The port ID of the host module
+pub const HOST_PORT_ID_STR: &str = "icahost";
+/// The module ID of the host module
+pub const HOST_MODULE_ID_STR: &str = "icahost";
+/// The prefix of the port IDs of the controller module
+pub const CONTROLLER_PORT_ID_PREFIX: &str = "icacontroller-";
+/// The module ID of the controller module
+pub const CONTROLLER_MODULE_ID_STR: &str = "icacontroller";
+/// The version of ICS-27
+pub const VERSION: &str = "ics27-1";
+/// The encoding of the transactions executed on Namada as the host chain
+pub const ENCODING_BORSH: &str = "borsh";
+/// The type of the transactions executed on Namada as the host chain
+pub const TX_TYPE_NAMADA: &str = "namada_tx";
+/// The packet type to execute transactions on the host chain
+pub const TYPE_EXECUTE_TX: &str = "TYPE_EXECUTE_TX";
+/// The maximum number of the transactions executed by the interchain accounts
+/// hosted on this chain in a block
+pub const MAX_HOST_TXS_PER_BLOCK: usize = 16;
+/// The total gas of the transactions executed by the interchain accounts in a
+/// block is limited to the max block gas divided by this
+pub const HOST_GAS_BLOCK_DIVISOR: u64 = 4;
+
+/// Get the port ID of the host module
+pub fn host_port_id() -> PortId {
+    PortId::from_str(HOST_PORT_ID_STR)
+        .expect("The host port ID should be valid")
+}
+
+/// Get the controller port ID of the owner
+pub fn controller_port_id(owner: &Address) -> Result<PortId, IdentifierError> {
+    PortId::from_str(&format!("{CONTROLLER_PORT_ID_PREFIX}{owner}"))
+}
+
+/// Get the owner of the controller port. Returns `None` if the port isn't a
+/// controller port.
+pub fn controller_owner(port_id: &PortId) -> Option<Address> {
+    port_id
+        .as_str()
+        .strip_prefix(CONTROLLER_PORT_ID_PREFIX)
+        .and_then(|owner| Address::decode(owner).ok())
+}
+
+/// Check if the port is a controller port
+pub fn is_controller_port(port_id: &PortId) -> bool {
+    port_id.as_str().starts_with(CONTROLLER_PORT_ID_PREFIX)
+}
+
+/// The channel version metadata negotiated between the controller and the
+/// host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// ICS-27 version
+    pub version: String,
+    /// The connection ID on the controller chain
+    pub controller_connection_id: String,
+    /// The connection ID on the host chain
+    pub host_connection_id: String,
+    /// The interchain account address on the host chain. It is empty until
+    /// the host chain sets it.
+    #[serde(default)]
+    pub address: String,
+    /// The encoding of the transactions
+    pub encoding: String,
+    /// The type of the transactions
+    pub tx_type: String,
+}
+
+impl Metadata {
+    /// Make the metadata to register an interchain account on Namada as the
+    /// host chain
+    pub fn new(
+        controller_connection_id: &ConnectionId,
+        host_connection_id: &ConnectionId,
+    ) -> Self {
+        Self {
+            version: VERSION.to_string(),
+            controller_connection_id: controller_connection_id.to_string(),
+            host_connection_id: host_connection_id.to_string(),
+            address: String::new(),
+            encoding: ENCODING_BORSH.to_string(),
+            tx_type: TX_TYPE_NAMADA.to_string(),
+        }
+    }
+
+    /// Decode the metadata from the channel version
+    pub fn from_version(version: &Version) -> Result<Self, String> {
+        serde_json::from_str(version.as_str())
+            .map_err(|e| format!("Decoding the version metadata failed: {e}"))
+    }
+
+    /// Encode the metadata into the channel version
+    pub fn to_version(&self) -> Version {
+        Version::new(
+            serde_json::to_string(self)
+                .expect("Encoding the metadata shouldn't fail"),
+        )
+    }
+}
+
+/// A transaction executed by an interchain account on Namada
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct HostTx {
+    /// The hash of the transaction code in the allowlist
+    pub code_hash: Hash,
+    /// The transaction data
+    pub data: Vec<u8>,
+    /// The gas limit of the transaction in whole gas units. It is charged
+    /// when the packet is received.
+    pub gas_limit: u64,
+}
+
+/// The packet data of ICS-27
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct InterchainAccountPacketData {
+    /// The packet type
+    #[serde(rename = "type")]
+    pub packet_type: String,
+    /// Base64-encoded transactions encoded in the host chain encoding
+    pub data: String,
+    /// Memo
+    #[serde(default)]
+    pub memo: String,
+}
+
+impl InterchainAccountPacketData {
+    /// Make the packet data to execute the encoded transactions on the host
+    /// chain
+    pub fn new(data: &[u8], memo: String) -> Self {
+        Self {
+            packet_type: TYPE_EXECUTE_TX.to_string(),
+            data: BASE64.encode(data),
+            memo,
+        }
+    }
+
+    /// Make the packet data to execute the transactions on Namada as the
+    /// host chain
+    pub fn with_host_txs(txs: &[HostTx], memo: String) -> Self {
+        Self::new(&txs.to_vec().serialize_to_vec(), memo)
+    }
+
+    /// Decode the transactions to be executed on Namada as the host chain
+    pub fn host_txs(&self) -> Result<Vec<HostTx>, String> {
+        if self.packet_type != TYPE_EXECUTE_TX {
+            return Err(format!(
+                "The packet type is not supported: {}",
+                self.packet_type
+            ));
+        }
+        let data = BASE64
+            .decode(self.data.as_bytes())
+            .map_err(|e| format!("Decoding the packet data failed: {e}"))?;
+        Vec::<HostTx>::try_from_slice(&data)
+            .map_err(|e| format!("Decoding the transactions failed: {e}"))
+    }
+}
+
+/// The transactions received with a packet which will be executed by an
+/// interchain account on this chain
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct PendingHostTxs {
+    /// The channel on this chain
+    pub channel_id: String,
+    /// The packet sequence
+    pub sequence: u64,
+    /// The interchain account executing the transactions
+    pub account: Address,
+    /// The transactions
+    pub txs: Vec<HostTx>,
+}
+
+impl PendingHostTxs {
+    /// Get the total gas limit of the transactions
+    pub fn gas_limit(&self) -> Option<u64> {
+        total_gas_limit(&self.txs)
+    }
+}
+
+/// Message to register an interchain account on the host chain. It opens an
+/// ordered channel from the controller port of the owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgRegisterInterchainAccount {
+    /// The owner of the interchain account on this chain
+    pub owner: Address,
+    /// The connection to the host chain
+    pub connection_id: ConnectionId,
+    /// The channel version. The default metadata for Namada as the host chain
+    /// is used if it is empty.
+    pub version: String,
+}
+
+impl BorshSerialize for MsgRegisterInterchainAccount {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        let members = (
+            self.owner.clone(),
+            self.connection_id.to_string(),
+            self.version.clone(),
+        );
+        BorshSerialize::serialize(&members, writer)
+    }
+}
+
+impl BorshDeserialize for MsgRegisterInterchainAccount {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let (owner, connection_id, version): (Address, String, String) =
+            BorshDeserialize::deserialize_reader(reader)?;
+        let connection_id = ConnectionId::from_str(&connection_id)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        Ok(Self {
+            owner,
+            connection_id,
+            version,
+        })
+    }
+}
+
+/// Message to send transactions to be executed by the interchain account on
+/// the host chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgSendInterchainTx {
+    /// The owner of the interchain account on this chain
+    pub owner: Address,
+    /// The connection to the host chain
+    pub connection_id: ConnectionId,
+    /// The packet data
+    pub packet_data: InterchainAccountPacketData,
+    /// The timeout in nanoseconds relative to the block time
+    pub relative_timeout: u64,
+}
+
+impl BorshSerialize for MsgSendInterchainTx {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        let members = (
+            self.owner.clone(),
+            self.connection_id.to_string(),
+            self.packet_data.clone(),
+            self.relative_timeout,
+        );
+        BorshSerialize::serialize(&members, writer)
+    }
+}
+
+impl BorshDeserialize for MsgSendInterchainTx {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let (owner, connection_id, packet_data, relative_timeout): (
+            Address,
+            String,
+            InterchainAccountPacketData,
+            u64,
+        ) = BorshDeserialize::deserialize_reader(reader)?;
+        let connection_id = ConnectionId::from_str(&connection_id)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        Ok(Self {
+            owner,
+            connection_id,
+            packet_data,
+            relative_timeout,
+        })
+    }
+}
+
+/// Read the code hashes of the transactions which can be executed by the
+/// interchain accounts hosted on this chain
+pub fn read_allowlist<S>(storage: &S) -> StorageResult<Vec<Hash>>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&storage::ica_allowlist_key())?
+        .unwrap_or_default())
+}
+
+/// Write the allowlist of the interchain account transactions. This should be
+/// used only by governance proposals.
+pub fn write_allowlist<S>(
+    storage: &mut S,
+    allowlist: Vec<Hash>,
+) -> StorageResult<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&storage::ica_allowlist_key(), allowlist)
+}
+
+/// Read the interchain account address for the controller port on the
+/// connection. The address is that on the host chain.
+pub fn read_account<S>(
+    storage: &S,
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> StorageResult<Option<String>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::ica_account_key(port_id, connection_id))
+}
+
+/// Get the total gas limit of the transactions. Returns `None` on overflow.
+pub fn total_gas_limit(txs: &[HostTx]) -> Option<u64> {
+    txs.iter()
+        .try_fold(0u64, |total, tx| total.checked_add(tx.gas_limit))
+}
+
+/// Get the total gas limit of the transactions executed by the interchain
+/// accounts in a block
+pub fn max_host_gas_per_block<S>(storage: &S) -> StorageResult<u64>
+where
+    S: StorageRead,
+{
+    let max_block_gas = namada_parameters::get_max_block_gas(storage)?;
+    Ok(max_block_gas / HOST_GAS_BLOCK_DIVISOR)
+}
+
+/// Overwrite the acknowledgement of the packet with the error when the
+/// transactions received with the packet failed, and return the encoded new
+/// acknowledgement. The packet has been received in the same block, so that
+/// the relayer can only see the overwritten acknowledgement.
+pub fn write_error_ack<S>(
+    storage: &mut S,
+    pending: &PendingHostTxs,
+    error: impl ToString,
+) -> StorageResult<Vec<u8>>
+where
+    S: StorageWrite,
+{
+    let channel_id = ChannelId::from_str(&pending.channel_id)
+        .map_err(|e| namada_storage::Error::new(e.to_string()))?;
+    let status = StatusValue::new(error.to_string())
+        .map_err(|e| namada_storage::Error::new(e.to_string()))?;
+    let ack: Vec<u8> = AcknowledgementStatus::error(status).into();
+    let key = storage::ack_key(
+        &host_port_id(),
+        &channel_id,
+        Sequence::from(pending.sequence),
+    );
+    storage.write_bytes(&key, Hash::sha256(&ack).to_vec())?;
+    Ok(ack)
+}
+
+/// Get all the transactions which haven't been executed yet by the interchain
+/// accounts with their storage keys. They are sorted in the order of receiving
+/// on each channel.
+pub fn pending_host_txs<S>(
+    storage: &S,
+) -> StorageResult<Vec<(Key, PendingHostTxs)>>
+where
+    S: StorageRead,
+{
+    let prefix = storage::ica_pending_tx_prefix();
+    let mut pending: Vec<(Key, PendingHostTxs)> = Vec::new();
+    for res in namada_storage::iter_prefix(storage, &prefix)? {
+        pending.push(res?);
+    }
+    pending.sort_by(|(_, a), (_, b)| {
+        (&a.channel_id, a.sequence).cmp(&(&b.channel_id, b.sequence))
+    });
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_data_host_txs() {
+        let txs = vec![
+            HostTx {
+                code_hash: Hash::sha256(b"tx_transfer"),
+                data: vec![1, 2, 3],
+                gas_limit: 50_000,
+            },
+            HostTx {
+                code_hash: Hash::sha256(b"tx_bond"),
+                data: vec![],
+                gas_limit: 100_000,
+            },
+        ];
+        let packet_data =
+            InterchainAccountPacketData::with_host_txs(&txs, "memo".into());
+        let json = serde_json::to_string(&packet_data).unwrap();
+        assert!(json.contains(r#""type":"TYPE_EXECUTE_TX""#));
+
+        let decoded: InterchainAccountPacketData =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.host_txs().unwrap(), txs);
+
+        // Unknown packet type
+        let packet_data = InterchainAccountPacketData {
+            packet_type: "TYPE_UNSPECIFIED".to_string(),
+            ..decoded
+        };
+        assert!(packet_data.host_txs().is_err());
+    }
+
+    #[test]
+    fn test_controller_port() {
+        let owner =
+            Address::decode("tnam1q8j5s6xp55p05yznwnftkv3kr9gjtsw3nq7x6tw5")
+                .unwrap();
+        let port_id = controller_port_id(&owner).unwrap();
+        assert!(is_controller_port(&port_id));
+        assert_eq!(controller_owner(&port_id), Some(owner));
+        assert!(!is_controller_port(&host_port_id()));
+        assert_eq!(controller_owner(&host_port_id()), None);
+    }
+}
@@ -2,31 +2,47 @@
 
 mod actions;
 pub mod context;
+pub mod forward;
+pub mod ica;
+pub mod rate_limit;
 pub mod storage;
 
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::time::Duration;
 
 pub use actions::transfer_over_ibc;
 use borsh::BorshDeserialize;
 pub use context::common::IbcCommonContext;
+pub use context::ica_mod::{IcaControllerModule, IcaHostModule};
 use context::router::IbcRouter;
 pub use context::storage::{IbcStorageContext, ProofSpec};
 pub use context::token_transfer::TokenTransferContext;
 pub use context::transfer_mod::{ModuleWrapper, TransferModule};
 use context::IbcContext;
 pub use context::ValidationParams;
+use ica::{MsgRegisterInterchainAccount, MsgSendInterchainTx};
 use namada_core::ibc::apps::transfer::handler::{
     send_transfer_execute, send_transfer_validate,
 };
 use namada_core::ibc::apps::transfer::types::error::TokenTransferError;
 use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use namada_core::ibc::apps::transfer::types::packet::PacketData;
 use namada_core::ibc::apps::transfer::types::{
-    is_receiver_chain_source, PrefixedDenom, TracePrefix,
+    is_receiver_chain_source, PrefixedCoin, PrefixedDenom, TracePrefix,
 };
-use namada_core::ibc::core::channel::types::msgs::PacketMsg;
+use namada_core::ibc::core::channel::handler::{
+    send_packet_execute, send_packet_validate,
+};
+use namada_core::ibc::core::channel::types::channel::Order;
+use namada_core::ibc::core::channel::types::msgs::{
+    ChannelMsg, MsgChannelOpenInit, PacketMsg,
+};
+use namada_core::ibc::core::channel::types::packet::Packet;
+use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
+use namada_core::ibc::core::channel::types::Version;
 use namada_core::ibc::core::entrypoint::{execute, validate};
 use namada_core::ibc::core::handler::types::error::ContextError;
 use namada_core::ibc::core::handler::types::msgs::MsgEnvelope;
@@ -43,6 +59,7 @@ use namada_core::types::ibc::{
 };
 use namada_core::types::masp::PaymentAddress;
 use prost::Message;
+use rate_limit::FlowDirection;
 use thiserror::Error;
 
 #[allow(missing_docs)]
@@ -62,6 +79,10 @@ pub enum Error {
     ChainId(IdentifierError),
     #[error("Handling MASP transaction error: {0}")]
     MaspTx(String),
+    #[error("Rate limit error: {0}")]
+    RateLimit(String),
+    #[error("Interchain account error: {0}")]
+    Ica(String),
 }
 
 /// IBC actions to handle IBC operations
@@ -95,6 +116,24 @@ where
         self.router.add_transfer_module(module_id, module)
     }
 
+    /// Add Interchain Accounts host route
+    pub fn add_ica_host_module(
+        &mut self,
+        module_id: ModuleId,
+        module: impl ModuleWrapper + 'a,
+    ) {
+        self.router.add_ica_host_module(module_id, module)
+    }
+
+    /// Add Interchain Accounts controller route
+    pub fn add_ica_controller_module(
+        &mut self,
+        module_id: ModuleId,
+        module: impl ModuleWrapper + 'a,
+    ) {
+        self.router.add_ica_controller_module(module_id, module)
+    }
+
     /// Set the validation parameters
     pub fn set_validation_params(&mut self, params: ValidationParams) {
         self.ctx.validation_params = params;
@@ -112,7 +151,8 @@ where
                     &mut token_transfer_ctx,
                     msg.clone(),
                 )
-                .map_err(Error::TokenTransfer)
+                .map_err(Error::TokenTransfer)?;
+                self.record_outflow(msg)
             }
             IbcMessage::ShieldedTransfer(msg) => {
                 let mut token_transfer_ctx =
@@ -123,17 +163,205 @@ where
                     msg.message.clone(),
                 )
                 .map_err(Error::TokenTransfer)?;
+                self.record_outflow(&msg.message)?;
                 self.handle_masp_tx(message)
             }
             IbcMessage::Envelope(envelope) => {
+                check_controller_channel(envelope)?;
                 execute(&mut self.ctx, &mut self.router, envelope.clone())
                     .map_err(|e| Error::Context(Box::new(e)))?;
                 // the current ibc-rs execution doesn't store the denom for the
                 // token hash when transfer with MsgRecvPacket
                 self.store_denom(envelope)?;
+                self.record_inflow(envelope)?;
                 // For receiving the token to a shielded address
                 self.handle_masp_tx(message)
             }
+            IbcMessage::RegisterInterchainAccount(msg) => {
+                let envelope = self.ica_channel_open_init(msg)?;
+                execute(&mut self.ctx, &mut self.router, envelope)
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
+            IbcMessage::SendInterchainTx(msg) => {
+                let packet = self.ica_packet(msg)?;
+                send_packet_execute(&mut self.ctx, packet)
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
+        }
+    }
+
+    /// Make the message to open a channel for the interchain account of the
+    /// owner
+    fn ica_channel_open_init(
+        &self,
+        msg: &MsgRegisterInterchainAccount,
+    ) -> Result<MsgEnvelope, Error> {
+        let port_id_on_a = ica::controller_port_id(&msg.owner)
+            .map_err(|e| Error::Ica(e.to_string()))?;
+        let version_proposal = if msg.version.is_empty() {
+            let connection = self
+                .ctx
+                .inner
+                .borrow()
+                .connection_end(&msg.connection_id)
+                .map_err(|e| Error::Context(Box::new(e)))?;
+            let host_connection_id =
+                connection.counterparty().connection_id().ok_or_else(|| {
+                    Error::Ica(format!(
+                        "The counterparty connection doesn't exist: {}",
+                        msg.connection_id
+                    ))
+                })?;
+            ica::Metadata::new(&msg.connection_id, host_connection_id)
+                .to_version()
+        } else {
+            Version::new(msg.version.clone())
+        };
+        let msg = MsgChannelOpenInit {
+            port_id_on_a,
+            connection_hops_on_a: vec![msg.connection_id.clone()],
+            port_id_on_b: ica::host_port_id(),
+            ordering: Order::Ordered,
+            signer: msg.owner.to_string().into(),
+            version_proposal,
+        };
+        Ok(MsgEnvelope::Channel(ChannelMsg::OpenInit(msg)))
+    }
+
+    /// Make the packet to send the transactions to the interchain account of
+    /// the owner
+    fn ica_packet(&self, msg: &MsgSendInterchainTx) -> Result<Packet, Error> {
+        if msg.relative_timeout == 0 {
+            return Err(Error::Ica("The timeout should be set".to_string()));
+        }
+        let port_id_on_a = ica::controller_port_id(&msg.owner)
+            .map_err(|e| Error::Ica(e.to_string()))?;
+        let ctx = self.ctx.inner.borrow();
+        let key =
+            storage::ica_active_channel_key(&port_id_on_a, &msg.connection_id);
+        let chan_id_on_a = ctx
+            .read::<String>(&key)
+            .map_err(|e| {
+                Error::Ica(format!("Reading the active channel failed: {e}"))
+            })?
+            .and_then(|channel_id| ChannelId::from_str(&channel_id).ok())
+            .ok_or_else(|| {
+                Error::Ica(format!(
+                    "No active channel for the interchain account: Port \
+                     {port_id_on_a}, Connection {}",
+                    msg.connection_id
+                ))
+            })?;
+        let channel = ctx
+            .channel_end(&port_id_on_a, &chan_id_on_a)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let port_id_on_b = channel.counterparty().port_id().clone();
+        let chan_id_on_b = channel
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or_else(|| {
+                Error::Ica(format!(
+                    "The counterparty channel doesn't exist: {chan_id_on_a}"
+                ))
+            })?;
+        let seq_on_a = ctx
+            .get_next_sequence_send(&port_id_on_a, &chan_id_on_a)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let timeout_timestamp_on_b = (ctx
+            .host_timestamp()
+            .map_err(|e| Error::Context(Box::new(e)))?
+            + Duration::from_nanos(msg.relative_timeout))
+        .map_err(|e| Error::Ica(e.to_string()))?;
+        let data = serde_json::to_vec(&msg.packet_data)
+            .expect("Encoding the packet data shouldn't fail");
+
+        Ok(Packet {
+            seq_on_a,
+            port_id_on_a,
+            chan_id_on_a,
+            port_id_on_b,
+            chan_id_on_b,
+            data,
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b,
+        })
+    }
+
+    /// Record the outflow of the sent token and check the rate limit
+    fn record_outflow(&mut self, msg: &MsgTransfer) -> Result<(), Error> {
+        self.record_flow(
+            &msg.chan_id_on_a,
+            &msg.packet_data.token,
+            FlowDirection::Outflow,
+        )
+    }
+
+    /// Record the inflow of the token received with MsgRecvPacket and check
+    /// the rate limit. The packet over the quota has been already rejected
+    /// with the error acknowledgement by the transfer module.
+    fn record_inflow(&mut self, envelope: &MsgEnvelope) -> Result<(), Error> {
+        let packet = match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg)) => &msg.packet,
+            _ => return Ok(()),
+        };
+        if !self.is_receiving_success()? {
+            // No token has been received
+            return Ok(());
+        }
+        let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+            Ok(data) => data,
+            // The packet isn't for the token transfer
+            Err(_) => return Ok(()),
+        };
+        let coin = received_coin(packet, data.token);
+        self.record_flow(&packet.chan_id_on_b, &coin, FlowDirection::Inflow)
+    }
+
+    /// Check if the token has been received successfully from the IBC event
+    fn is_receiving_success(&self) -> Result<bool, Error> {
+        let receive_event = self
+            .ctx
+            .inner
+            .borrow()
+            .get_ibc_events(EVENT_TYPE_PACKET)
+            .map_err(|_| {
+                Error::RateLimit("Reading the IBC event failed".to_string())
+            })?;
+        // The receiving event should be only one in the single IBC transaction
+        Ok(receive_event
+            .first()
+            .and_then(|event| event.attributes.get("success"))
+            .map_or(false, |success| success == "true"))
+    }
+
+    /// Record the token flow on the channel and check if the flow is within
+    /// the quota of the rate limit
+    fn record_flow(
+        &mut self,
+        channel_id: &ChannelId,
+        coin: &PrefixedCoin,
+        direction: FlowDirection,
+    ) -> Result<(), Error> {
+        let (token, amount) = TokenTransferContext::new(self.ctx.inner.clone())
+            .get_token_amount(coin)
+            .map_err(Error::TokenTransfer)?;
+        let exceeded = rate_limit::record_flow(
+            &mut *self.ctx.inner.borrow_mut(),
+            channel_id,
+            &token,
+            direction,
+            amount.amount(),
+        )
+        .map_err(|e| {
+            Error::RateLimit(format!("Recording the token flow failed: {}", e))
+        })?;
+        match exceeded {
+            Some((usage, quota)) => Err(Error::RateLimit(format!(
+                "The {direction} of the token {token} on the channel \
+                 {channel_id} exceeds the quota: Usage {usage}, Quota {quota}",
+            ))),
+            None => Ok(()),
         }
     }
 
@@ -249,9 +477,20 @@ where
                 .map_err(Error::TokenTransfer)
             }
             IbcMessage::Envelope(envelope) => {
+                check_controller_channel(&envelope)?;
                 validate(&self.ctx, &self.router, envelope)
                     .map_err(|e| Error::Context(Box::new(e)))
             }
+            IbcMessage::RegisterInterchainAccount(msg) => {
+                let envelope = self.ica_channel_open_init(&msg)?;
+                validate(&self.ctx, &self.router, envelope)
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
+            IbcMessage::SendInterchainTx(msg) => {
+                let packet = self.ica_packet(&msg)?;
+                send_packet_validate(&self.ctx, &packet)
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
         }
     }
 
@@ -304,6 +543,39 @@ pub enum IbcMessage {
     Transfer(MsgTransfer),
     /// Ibc shielded transfer
     ShieldedTransfer(MsgShieldedTransfer),
+    /// Interchain account registration
+    RegisterInterchainAccount(MsgRegisterInterchainAccount),
+    /// Transactions sent to an interchain account
+    SendInterchainTx(MsgSendInterchainTx),
+}
+
+impl IbcMessage {
+    /// Get the owner of the interchain account who should authorize the
+    /// message
+    pub fn ica_owner(&self) -> Option<&Address> {
+        match self {
+            Self::RegisterInterchainAccount(msg) => Some(&msg.owner),
+            Self::SendInterchainTx(msg) => Some(&msg.owner),
+            _ => None,
+        }
+    }
+}
+
+/// Check that the channel on a controller port is opened only with
+/// `MsgRegisterInterchainAccount` which is authorized by the owner
+fn check_controller_channel(envelope: &MsgEnvelope) -> Result<(), Error> {
+    match envelope {
+        MsgEnvelope::Channel(ChannelMsg::OpenInit(msg))
+            if ica::is_controller_port(&msg.port_id_on_a) =>
+        {
+            Err(Error::Ica(format!(
+                "The channel on the controller port should be opened by the \
+                 interchain account registration: Port {}",
+                msg.port_id_on_a
+            )))
+        }
+        _ => Ok(()),
+    }
 }
 
 /// Tries to decode transaction data to an `IbcMessage`
@@ -323,9 +595,40 @@ pub fn decode_message(tx_data: &[u8]) -> Result<IbcMessage, Error> {
         return Ok(IbcMessage::ShieldedTransfer(msg));
     }
 
+    // Messages for the interchain account controlled by an account on this
+    // chain
+    if let Ok(msg) = MsgRegisterInterchainAccount::try_from_slice(tx_data) {
+        return Ok(IbcMessage::RegisterInterchainAccount(msg));
+    }
+    if let Ok(msg) = MsgSendInterchainTx::try_from_slice(tx_data) {
+        return Ok(IbcMessage::SendInterchainTx(msg));
+    }
+
     Err(Error::DecodingData)
 }
 
+/// Get the coin received with the packet whose denom is that on this chain
+pub fn received_coin(packet: &Packet, mut coin: PrefixedCoin) -> PrefixedCoin {
+    if is_receiver_chain_source(
+        packet.port_id_on_a.clone(),
+        packet.chan_id_on_a.clone(),
+        &coin.denom,
+    ) {
+        let prefix = TracePrefix::new(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+        );
+        coin.denom.remove_trace_prefix(&prefix);
+    } else {
+        let prefix = TracePrefix::new(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+        );
+        coin.denom.add_trace_prefix(prefix);
+    }
+    coin
+}
+
 /// Get the IbcToken from the source/destination ports and channels
 pub fn received_ibc_token(
     ibc_denom: &PrefixedDenom,
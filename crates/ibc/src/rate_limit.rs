@@ -0,0 +1,309 @@
+//! IBC rate limits of the token flow per channel and per token
+//!
+//! Governance can set a [`RateLimit`] for a token on a channel. The amounts
+//! sent (outflow) and received (inflow) through the channel are recorded per
+//! epoch and a transfer is rejected when the total amount in the rolling
+//! window of epochs would exceed the quota.
+
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::ibc::core::host::types::identifiers::ChannelId;
+use namada_core::types::address::Address;
+use namada_core::types::storage::Epoch;
+use namada_core::types::token::Amount;
+use namada_storage::{Result as StorageResult, StorageRead, StorageWrite};
+
+use crate::storage;
+
+/// The direction of a token flow through a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowDirection {
+    /// Tokens received from the counterparty chain
+    Inflow,
+    /// Tokens sent to the counterparty chain
+    Outflow,
+}
+
+impl std::fmt::Display for FlowDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inflow => write!(f, "inflow"),
+            Self::Outflow => write!(f, "outflow"),
+        }
+    }
+}
+
+/// The quotas of a token flow through a channel
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct RateLimit {
+    /// The maximum amount which can be received in the window. No limit if
+    /// it is `None`.
+    pub inflow: Option<Amount>,
+    /// The maximum amount which can be sent in the window. No limit if it is
+    /// `None`.
+    pub outflow: Option<Amount>,
+    /// The number of epochs of the rolling window, including the current
+    /// epoch. It should be at least 1.
+    pub window: u64,
+}
+
+impl RateLimit {
+    /// Get the quota for the given direction
+    pub fn quota(&self, direction: FlowDirection) -> Option<Amount> {
+        match direction {
+            FlowDirection::Inflow => self.inflow,
+            FlowDirection::Outflow => self.outflow,
+        }
+    }
+}
+
+/// The token amounts which went through a channel in each epoch of the
+/// rolling window
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct FlowWindow {
+    /// The amount per epoch
+    pub amounts: BTreeMap<Epoch, Amount>,
+}
+
+impl FlowWindow {
+    /// Get the total amount in the window ending at the given epoch
+    pub fn usage(&self, current_epoch: Epoch, window: u64) -> Amount {
+        let start = window_start(current_epoch, window);
+        self.amounts.range(start..=current_epoch).fold(
+            Amount::zero(),
+            |acc, (_, amount)| {
+                acc.checked_add(*amount).unwrap_or_else(Amount::max)
+            },
+        )
+    }
+
+    /// Add the amount to the current epoch after removing the amounts of
+    /// epochs out of the window. Returns `None` on overflow.
+    pub fn record(
+        &mut self,
+        current_epoch: Epoch,
+        window: u64,
+        amount: Amount,
+    ) -> Option<()> {
+        let start = window_start(current_epoch, window);
+        self.amounts.retain(|epoch, _| *epoch >= start);
+        let current = self.amounts.entry(current_epoch).or_default();
+        *current = current.checked_add(amount)?;
+        Some(())
+    }
+}
+
+/// The first epoch of the window ending at the given epoch
+fn window_start(current_epoch: Epoch, window: u64) -> Epoch {
+    current_epoch
+        .checked_sub(window.saturating_sub(1))
+        .unwrap_or_default()
+}
+
+/// The rate limit of a token on a channel and the current usage
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct RateLimitUsage {
+    /// The channel on this chain
+    pub channel_id: String,
+    /// The token
+    pub token: Address,
+    /// The rate limit set by governance
+    pub limit: RateLimit,
+    /// The amount received in the current window
+    pub inflow: Amount,
+    /// The amount sent in the current window
+    pub outflow: Amount,
+    /// The epoch at which the usage was computed
+    pub epoch: Epoch,
+}
+
+/// Read the rate limit of the token on the channel
+pub fn read_rate_limit<S>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> StorageResult<Option<RateLimit>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::rate_limit_key(channel_id, token))
+}
+
+/// Write the rate limit of the token on the channel. This should be used only
+/// by governance proposals.
+pub fn write_rate_limit<S>(
+    storage: &mut S,
+    channel_id: &ChannelId,
+    token: &Address,
+    limit: RateLimit,
+) -> StorageResult<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&storage::rate_limit_key(channel_id, token), limit)
+}
+
+/// Read the recorded token flow in the given direction on the channel
+pub fn read_flow<S>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+    direction: FlowDirection,
+) -> StorageResult<FlowWindow>
+where
+    S: StorageRead,
+{
+    let key = storage::rate_limit_flow_key(channel_id, token, direction);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Record the token flow through the channel if a rate limit has been set for
+/// the token on the channel. Returns the usage and the quota when the flow
+/// exceeds the quota.
+pub fn record_flow<S>(
+    storage: &mut S,
+    channel_id: &ChannelId,
+    token: &Address,
+    direction: FlowDirection,
+    amount: Amount,
+) -> StorageResult<Option<(Amount, Amount)>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let limit = match read_rate_limit(storage, channel_id, token)? {
+        Some(limit) => limit,
+        None => return Ok(None),
+    };
+    let epoch = storage.get_block_epoch()?;
+    let mut flow = read_flow(storage, channel_id, token, direction)?;
+    flow.record(epoch, limit.window, amount).ok_or_else(|| {
+        namada_storage::Error::SimpleMessage("The token flow overflowed")
+    })?;
+    let usage = flow.usage(epoch, limit.window);
+    let key = storage::rate_limit_flow_key(channel_id, token, direction);
+    storage.write(&key, flow)?;
+
+    Ok(limit
+        .quota(direction)
+        .filter(|quota| usage > *quota)
+        .map(|quota| (usage, quota)))
+}
+
+/// Check if the token flow through the channel would exceed the quota of the
+/// rate limit without recording it. Returns the usage and the quota when the
+/// flow would exceed the quota.
+pub fn check_flow<S>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+    direction: FlowDirection,
+    amount: Amount,
+) -> StorageResult<Option<(Amount, Amount)>>
+where
+    S: StorageRead,
+{
+    let limit = match read_rate_limit(storage, channel_id, token)? {
+        Some(limit) => limit,
+        None => return Ok(None),
+    };
+    let epoch = storage.get_block_epoch()?;
+    let mut flow = read_flow(storage, channel_id, token, direction)?;
+    flow.record(epoch, limit.window, amount).ok_or_else(|| {
+        namada_storage::Error::SimpleMessage("The token flow overflowed")
+    })?;
+    let usage = flow.usage(epoch, limit.window);
+
+    Ok(limit
+        .quota(direction)
+        .filter(|quota| usage > *quota)
+        .map(|quota| (usage, quota)))
+}
+
+/// Get the rate limit and the current usage of the token on the channel
+pub fn rate_limit_usage<S>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> StorageResult<Option<RateLimitUsage>>
+where
+    S: StorageRead,
+{
+    let limit = match read_rate_limit(storage, channel_id, token)? {
+        Some(limit) => limit,
+        None => return Ok(None),
+    };
+    let epoch = storage.get_block_epoch()?;
+    let inflow = read_flow(storage, channel_id, token, FlowDirection::Inflow)?
+        .usage(epoch, limit.window);
+    let outflow =
+        read_flow(storage, channel_id, token, FlowDirection::Outflow)?
+            .usage(epoch, limit.window);
+    Ok(Some(RateLimitUsage {
+        channel_id: channel_id.to_string(),
+        token: token.clone(),
+        limit,
+        inflow,
+        outflow,
+        epoch,
+    }))
+}
+
+/// Get all the rate limits and their current usage
+pub fn rate_limit_usages<S>(storage: &S) -> StorageResult<Vec<RateLimitUsage>>
+where
+    S: StorageRead,
+{
+    let mut usages = Vec::new();
+    let prefix = storage::rate_limit_prefix();
+    for res in namada_storage::iter_prefix_bytes(storage, &prefix)? {
+        let (key, _) = res?;
+        if let Some((channel_id, token)) = storage::is_rate_limit_key(&key) {
+            if let Some(usage) = rate_limit_usage(storage, &channel_id, &token)?
+            {
+                usages.push(usage);
+            }
+        }
+    }
+    Ok(usages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow_window() {
+        let mut flow = FlowWindow::default();
+        let amount = Amount::from_u64(10);
+        flow.record(Epoch(1), 3, amount).unwrap();
+        flow.record(Epoch(2), 3, amount).unwrap();
+        flow.record(Epoch(2), 3, amount).unwrap();
+        assert_eq!(flow.usage(Epoch(2), 3), Amount::from_u64(30));
+        assert_eq!(flow.usage(Epoch(3), 3), Amount::from_u64(30));
+        // The amount in epoch 1 is out of the window
+        assert_eq!(flow.usage(Epoch(4), 3), Amount::from_u64(20));
+        assert_eq!(flow.usage(Epoch(5), 3), Amount::zero());
+
+        // The old amounts are removed when recording a new one
+        flow.record(Epoch(4), 3, amount).unwrap();
+        assert_eq!(flow.amounts.len(), 2);
+        assert_eq!(flow.usage(Epoch(4), 3), Amount::from_u64(30));
+
+        // The window includes only the current epoch
+        assert_eq!(flow.usage(Epoch(4), 1), Amount::from_u64(10));
+    }
+}
@@ -14,15 +14,28 @@ use namada_core::ibc::core::host::types::path::{
 use namada_core::types::address::{
     Address, InternalAddress, HASH_LEN, SHA_HASH_LEN,
 };
-use namada_core::types::ibc::IbcTokenHash;
+use namada_core::types::ibc::{IbcAccountHash, IbcTokenHash};
 use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
+use crate::rate_limit::FlowDirection;
+
 const CLIENTS_COUNTER: &str = "clients/counter";
 const CONNECTIONS_COUNTER: &str = "connections/counter";
 const CHANNELS_COUNTER: &str = "channelEnds/counter";
 const DENOM: &str = "ibc_denom";
+const RATE_LIMIT: &str = "rate_limit";
+const RATE_LIMIT_PARAMS: &str = "limit";
+const INFLOW: &str = "inflow";
+const OUTFLOW: &str = "outflow";
+const ICA: &str = "ica";
+const ICA_ALLOWLIST: &str = "allowlist";
+const ICA_ACCOUNT: &str = "account";
+const ICA_CHANNEL: &str = "channel";
+const ICA_PENDING: &str = "pending";
+const ICA_EXECUTION: &str = "execution";
+const FORWARD: &str = "forward";
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -396,6 +409,179 @@ pub fn ibc_denom_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// Returns a key prefix of the IBC rate limits `#IBC/rate_limit`
+pub fn rate_limit_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&RATE_LIMIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key prefix of the rate limit for the token on the channel
+/// `#IBC/rate_limit/<channel_id>/<token>`
+fn rate_limit_token_prefix(channel_id: &ChannelId, token: &Address) -> Key {
+    rate_limit_prefix()
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&token.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the rate limit configured by governance for the token on
+/// the channel `#IBC/rate_limit/<channel_id>/<token>/limit`
+pub fn rate_limit_key(channel_id: &ChannelId, token: &Address) -> Key {
+    rate_limit_token_prefix(channel_id, token)
+        .push(&RATE_LIMIT_PARAMS.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the token flow in the given direction on the channel
+/// `#IBC/rate_limit/<channel_id>/<token>/<inflow|outflow>`
+pub fn rate_limit_flow_key(
+    channel_id: &ChannelId,
+    token: &Address,
+    direction: FlowDirection,
+) -> Key {
+    let flow = match direction {
+        FlowDirection::Inflow => INFLOW,
+        FlowDirection::Outflow => OUTFLOW,
+    };
+    rate_limit_token_prefix(channel_id, token)
+        .push(&flow.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns the channel ID and the token if the given key is the key of a rate
+/// limit configured by governance
+pub fn is_rate_limit_key(key: &Key) -> Option<(ChannelId, Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(channel_id),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(params),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && prefix == RATE_LIMIT
+            && params == RATE_LIMIT_PARAMS =>
+        {
+            let channel_id = ChannelId::from_str(&channel_id.raw()).ok()?;
+            Some((channel_id, token.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns a key prefix of the interchain accounts `#IBC/ica`
+fn ica_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&ICA.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the code hashes of the transactions which can be executed
+/// by the interchain accounts hosted on this chain `#IBC/ica/allowlist`
+pub fn ica_allowlist_key() -> Key {
+    ica_prefix()
+        .push(&ICA_ALLOWLIST.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns true if the given key is the key of the allowlist of the
+/// interchain account transactions
+pub fn is_ica_allowlist_key(key: &Key) -> bool {
+    *key == ica_allowlist_key()
+}
+
+/// Returns a key of the interchain account address for the controller port
+/// on the connection `#IBC/ica/account/<port_id>/<connection_id>`
+pub fn ica_account_key(port_id: &PortId, connection_id: &ConnectionId) -> Key {
+    ica_prefix()
+        .push(&ICA_ACCOUNT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&port_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&connection_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the active channel for the controller port on the
+/// connection `#IBC/ica/channel/<port_id>/<connection_id>`
+pub fn ica_active_channel_key(
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Key {
+    ica_prefix()
+        .push(&ICA_CHANNEL.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&port_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&connection_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key prefix of the received transactions which will be executed
+/// by the interchain accounts `#IBC/ica/pending`
+pub fn ica_pending_tx_prefix() -> Key {
+    ica_prefix()
+        .push(&ICA_PENDING.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the transactions received with the packet
+/// `#IBC/ica/pending/<channel_id>/<sequence>`
+pub fn ica_pending_tx_key(channel_id: &ChannelId, sequence: Sequence) -> Key {
+    ica_pending_tx_prefix()
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&sequence.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key which is written by the protocol only while a transaction is
+/// executed by the interchain account `#IBC/ica/execution/<account>`
+pub fn ica_execution_key(account: &Address) -> Key {
+    ica_prefix()
+        .push(&ICA_EXECUTION.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&account.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Obtain the interchain account hosted on this chain for the controller port
+/// on the connection
+pub fn ica_account(connection_id: &ConnectionId, port_id: &PortId) -> Address {
+    let hash = {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{connection_id}/{port_id}"));
+        hasher.finalize()
+    };
+
+    let input: &[u8; SHA_HASH_LEN] = hash.as_ref();
+    let mut output = [0; HASH_LEN];
+
+    output.copy_from_slice(&input[..HASH_LEN]);
+    Address::Internal(InternalAddress::IbcAccount(IbcAccountHash(output)))
+}
+
+/// Returns a key of the packet forwarded through this chain which hasn't been
+/// acknowledged yet `#IBC/forward/<port_id>/<channel_id>/<sequence>`. The port,
+/// the channel and the sequence are those of the forwarded packet.
+pub fn forward_in_flight_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&FORWARD.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&port_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&sequence.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Hash the denom
 #[inline]
 pub fn calc_hash(denom: impl AsRef<str>) -> String {
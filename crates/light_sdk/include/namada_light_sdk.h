@@ -0,0 +1,202 @@
+#ifndef NAMADA_LIGHT_SDK_H
+#define NAMADA_LIGHT_SDK_H
+
+/* Warning: this file is generated by cbindgen. Do not edit it manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The outcome of a call through the FFI
+typedef enum NamadaStatus {
+  // The call succeeded
+  NAMADA_STATUS_OK = 0,
+  // A required pointer argument was null
+  NAMADA_STATUS_NULL_POINTER = 1,
+  // An argument could not be decoded
+  NAMADA_STATUS_INVALID_ARGUMENT = 2,
+  // A query or broadcast to the node failed
+  NAMADA_STATUS_REQUEST_FAILED = 3,
+  // The call panicked
+  NAMADA_STATUS_PANICKED = 4,
+} NamadaStatus;
+
+// An opaque handle on a connection to a Namada node
+typedef struct NamadaClient NamadaClient;
+
+// An opaque handle on a transaction built through the FFI
+typedef struct NamadaTx NamadaTx;
+
+// A byte buffer allocated by the library
+typedef struct NamadaBuffer {
+  // Pointer to the first byte, null for an empty buffer
+  uint8_t *data;
+  // The number of bytes in the buffer
+  size_t len;
+} NamadaBuffer;
+
+// Release a buffer that was handed out by the library.
+//
+// # Safety
+//
+// The buffer must have been returned by this library and not released
+// before.
+void namada_buffer_free(struct NamadaBuffer buffer);
+
+// Get a UTF-8 description of the last error that occurred on the calling
+// thread. The returned buffer is empty if no error occurred.
+struct NamadaBuffer namada_last_error(void);
+
+// Connect to the Namada node at the given NUL-terminated Tendermint RPC
+// address.
+//
+// # Safety
+//
+// `tendermint_addr` must be a valid NUL-terminated string and `out` must be
+// valid for writes.
+enum NamadaStatus namada_client_new(const char *tendermint_addr, struct NamadaClient **out);
+
+// Run the JSON encoded query, e.g.
+// `{"query": "get_token_balance", "token": "tnam1...", "owner": "tnam1..."}`,
+// and return the Borsh encoding of its result.
+//
+// # Safety
+//
+// `client` must be a live handle, `query` must point to `query_len`
+// readable bytes and `out` must be valid for writes.
+enum NamadaStatus namada_client_query(const struct NamadaClient *client,
+                                      const uint8_t *query,
+                                      size_t query_len,
+                                      struct NamadaBuffer *out);
+
+// Dry run the given protobuf encoded transaction and return the Borsh
+// encoding of its `TxResult`.
+//
+// # Safety
+//
+// `client` must be a live handle, `tx` must point to `tx_len` readable
+// bytes and `out` must be valid for writes.
+enum NamadaStatus namada_client_dry_run_tx(const struct NamadaClient *client,
+                                           const uint8_t *tx,
+                                           size_t tx_len,
+                                           struct NamadaBuffer *out);
+
+// Broadcast the given protobuf encoded wrapper transaction and wait for it
+// to be applied, for up to `timeout_secs` seconds or a default of one
+// minute if zero. The result is the JSON encoding of the transaction's
+// response.
+//
+// # Safety
+//
+// `client` must be a live handle, `tx` must point to `tx_len` readable
+// bytes and `out` must be valid for writes.
+enum NamadaStatus namada_client_broadcast_tx(const struct NamadaClient *client,
+                                             const uint8_t *tx,
+                                             size_t tx_len,
+                                             uint64_t timeout_secs,
+                                             struct NamadaBuffer *out);
+
+// Release a client handle.
+//
+// # Safety
+//
+// `client` must be null or a handle that was not released before.
+void namada_client_free(struct NamadaClient *client);
+
+// Build a transparent transfer from its JSON arguments: `source`, `target`,
+// `token`, `amount` (a denominated amount string), an optional `key` and
+// the global arguments `chain_id`, `code_hash` and an optional
+// `expiration`.
+//
+// # Safety
+//
+// `args` must point to `args_len` readable bytes and `out` must be valid
+// for writes.
+enum NamadaStatus namada_tx_transfer_new(const uint8_t *args,
+                                         size_t args_len,
+                                         struct NamadaTx **out);
+
+// Build a bond from its JSON arguments: `validator`, `amount`, an optional
+// `source` and the global arguments.
+//
+// # Safety
+//
+// `args` must point to `args_len` readable bytes and `out` must be valid
+// for writes.
+enum NamadaStatus namada_tx_bond_new(const uint8_t *args, size_t args_len, struct NamadaTx **out);
+
+// Build a governance vote from its JSON arguments: the proposal `id`, the
+// `vote` (`"Yay"`, `"Nay"` or `"Abstain"`), the `voter`, its
+// `delegations` and the global arguments.
+//
+// # Safety
+//
+// `args` must point to `args_len` readable bytes and `out` must be valid
+// for writes.
+enum NamadaStatus namada_tx_vote_proposal_new(const uint8_t *args,
+                                              size_t args_len,
+                                              struct NamadaTx **out);
+
+// Build an IBC transfer from a protobuf encoded `Any` wrapping a
+// `MsgTransfer` and the JSON encoded global arguments.
+//
+// # Safety
+//
+// `msg` and `args` must point to `msg_len` and `args_len` readable bytes
+// respectively and `out` must be valid for writes.
+enum NamadaStatus namada_tx_ibc_transfer_new(const uint8_t *msg,
+                                             size_t msg_len,
+                                             const uint8_t *args,
+                                             size_t args_len,
+                                             struct NamadaTx **out);
+
+// Build a public key revelation from its JSON arguments: the
+// `public_key` and the global arguments.
+//
+// # Safety
+//
+// `args` must point to `args_len` readable bytes and `out` must be valid
+// for writes.
+enum NamadaStatus namada_tx_reveal_pk_new(const uint8_t *args,
+                                          size_t args_len,
+                                          struct NamadaTx **out);
+
+// Get the Borsh encoded list of hashes that must be signed for the
+// transaction.
+//
+// # Safety
+//
+// `tx` must be a live handle and `out` must be valid for writes.
+enum NamadaStatus namada_tx_sign_bytes(const struct NamadaTx *tx, struct NamadaBuffer *out);
+
+// Attach a signature to the transaction, given the Borsh encoded
+// `common::PublicKey` of the signer and the Borsh encoded
+// `common::Signature`.
+//
+// # Safety
+//
+// `tx` must be a live handle and the key and signature pointers must point
+// to the given number of readable bytes.
+enum NamadaStatus namada_tx_attach_signature(struct NamadaTx *tx,
+                                             const uint8_t *public_key,
+                                             size_t public_key_len,
+                                             const uint8_t *signature,
+                                             size_t signature_len);
+
+// Get the protobuf encoding of the transaction, ready to be wrapped or
+// broadcast.
+//
+// # Safety
+//
+// `tx` must be a live handle and `out` must be valid for writes.
+enum NamadaStatus namada_tx_to_bytes(const struct NamadaTx *tx, struct NamadaBuffer *out);
+
+// Release a transaction handle.
+//
+// # Safety
+//
+// `tx` must be null or a handle that was not released before.
+void namada_tx_free(struct NamadaTx *tx);
+
+#endif /* NAMADA_LIGHT_SDK_H */
@@ -0,0 +1,299 @@
+//! C-compatible bindings over the light SDK, built into the `cdylib` when the
+//! `ffi` feature is enabled. The matching C header lives in
+//! `include/namada_light_sdk.h` and is generated with `cbindgen`.
+//!
+//! # Conventions
+//!
+//! - Transactions and node connections are exposed as opaque handles that must
+//!   be released with their matching `*_free` function.
+//! - Structured arguments are passed in as UTF-8 JSON buffers, while data
+//!   returned by the library is Borsh encoded unless stated otherwise.
+//! - Every fallible function returns a [`NamadaStatus`]. On failure, a
+//!   description of the error can be retrieved with [`namada_last_error`].
+//! - Panics never unwind across the FFI boundary. They are caught and reported
+//!   with [`NamadaStatus::Panicked`].
+//! - Buffers handed out by the library are owned by the caller and must be
+//!   released with [`namada_buffer_free`].
+
+pub mod reading;
+pub mod transaction;
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::panic::AssertUnwindSafe;
+
+thread_local! {
+    /// The error produced by the last failed call on this thread
+    static LAST_ERROR: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// The outcome of a call through the FFI
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NamadaStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// An argument could not be decoded
+    InvalidArgument = 2,
+    /// A query or broadcast to the node failed
+    RequestFailed = 3,
+    /// The call panicked
+    Panicked = 4,
+}
+
+/// A byte buffer allocated by the library
+#[repr(C)]
+pub struct NamadaBuffer {
+    /// Pointer to the first byte, null for an empty buffer
+    pub data: *mut u8,
+    /// The number of bytes in the buffer
+    pub len: usize,
+}
+
+impl NamadaBuffer {
+    /// A buffer without any content
+    pub const fn empty() -> Self {
+        Self {
+            data: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+impl From<Vec<u8>> for NamadaBuffer {
+    fn from(bytes: Vec<u8>) -> Self {
+        if bytes.is_empty() {
+            return Self::empty();
+        }
+        let bytes = bytes.into_boxed_slice();
+        let len = bytes.len();
+        let data = Box::into_raw(bytes) as *mut u8;
+        Self { data, len }
+    }
+}
+
+/// Release a buffer that was handed out by the library.
+///
+/// # Safety
+///
+/// The buffer must have been returned by this library and not released
+/// before.
+#[no_mangle]
+pub unsafe extern "C" fn namada_buffer_free(buffer: NamadaBuffer) {
+    _ = catch_panic(|| {
+        if !buffer.data.is_null() {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                buffer.data,
+                buffer.len,
+            )));
+        }
+        NamadaStatus::Ok
+    });
+}
+
+/// Get a UTF-8 description of the last error that occurred on the calling
+/// thread. The returned buffer is empty if no error occurred.
+#[no_mangle]
+pub extern "C" fn namada_last_error() -> NamadaBuffer {
+    std::panic::catch_unwind(|| {
+        LAST_ERROR
+            .with(|last| last.borrow().clone())
+            .map(|err| NamadaBuffer::from(err.into_bytes()))
+            .unwrap_or_else(NamadaBuffer::empty)
+    })
+    .unwrap_or_else(|_| NamadaBuffer::empty())
+}
+
+/// Record the given error for [`namada_last_error`] and return its status
+pub(crate) fn fail(status: NamadaStatus, err: impl Display) -> NamadaStatus {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(err.to_string()));
+    status
+}
+
+/// Run the body of an FFI function, catching any panic so that it doesn't
+/// unwind into the caller and reporting it with [`NamadaStatus::Panicked`]
+pub(crate) fn catch_panic(body: impl FnOnce() -> NamadaStatus) -> NamadaStatus {
+    std::panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|panic| {
+        let msg = panic
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string());
+        fail(NamadaStatus::Panicked, format!("Panicked: {msg}"))
+    })
+}
+
+/// Borrow the bytes of a buffer passed in by the caller
+///
+/// # Safety
+///
+/// `data` must either be null with a `len` of zero or point to `len`
+/// readable bytes.
+pub(crate) unsafe fn input<'a>(
+    data: *const u8,
+    len: usize,
+) -> Result<&'a [u8], NamadaStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(fail(NamadaStatus::NullPointer, "Input buffer is null"))
+    } else {
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+}
+
+/// Decode a JSON argument passed in by the caller
+///
+/// # Safety
+///
+/// Same requirements as [`input`].
+pub(crate) unsafe fn json_input<T: serde::de::DeserializeOwned>(
+    data: *const u8,
+    len: usize,
+) -> Result<T, NamadaStatus> {
+    serde_json::from_slice(input(data, len)?)
+        .map_err(|err| fail(NamadaStatus::InvalidArgument, err))
+}
+
+/// Write an output value through a pointer passed in by the caller
+///
+/// # Safety
+///
+/// `out` must either be null or valid for writes.
+pub(crate) unsafe fn output<T>(out: *mut T, value: T) -> NamadaStatus {
+    if out.is_null() {
+        return fail(NamadaStatus::NullPointer, "Output pointer is null");
+    }
+    out.write(value);
+    NamadaStatus::Ok
+}
+
+/// Unwrap the result of a fallible argument conversion or return its status
+/// from the enclosing function
+macro_rules! try_ffi {
+    ($e:expr) => {
+        match $e {
+            Ok(value) => value,
+            Err(status) => return status,
+        }
+    };
+}
+pub(crate) use try_ffi;
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+    use std::net::TcpListener;
+
+    use super::reading::{
+        namada_client_new, namada_client_query, NamadaClient,
+    };
+    use super::transaction::{namada_tx_bond_new, namada_tx_free, NamadaTx};
+    use super::*;
+
+    /// Get the address of a node that can't be reached, from a port that was
+    /// free until its listener was dropped
+    fn unreachable_node() -> CString {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        CString::new(format!("tcp://{addr}")).unwrap()
+    }
+
+    /// Get the last error as a string
+    fn last_error() -> String {
+        let buffer = namada_last_error();
+        let err =
+            unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) };
+        let err = String::from_utf8(err.to_vec()).unwrap();
+        unsafe { namada_buffer_free(buffer) };
+        err
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| NamadaStatus::Ok), NamadaStatus::Ok);
+        assert_eq!(catch_panic(|| panic!("boom")), NamadaStatus::Panicked);
+        assert_eq!(last_error(), "Panicked: boom");
+        let status = catch_panic(|| panic!("{} {}", "formatted", "boom"));
+        assert_eq!(status, NamadaStatus::Panicked);
+        assert_eq!(last_error(), "Panicked: formatted boom");
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let mut tx: *mut NamadaTx = std::ptr::null_mut();
+        let status =
+            unsafe { namada_tx_bond_new(std::ptr::null(), 1, &mut tx) };
+        assert_eq!(status, NamadaStatus::NullPointer);
+        assert!(tx.is_null());
+
+        let mut client: *mut NamadaClient = std::ptr::null_mut();
+        let status =
+            unsafe { namada_client_new(std::ptr::null(), &mut client) };
+        assert_eq!(status, NamadaStatus::NullPointer);
+        assert!(client.is_null());
+
+        let query = br#"{"query": "query_epoch"}"#;
+        let mut out = NamadaBuffer::empty();
+        let status = unsafe {
+            namada_client_query(
+                std::ptr::null(),
+                query.as_ptr(),
+                query.len(),
+                &mut out,
+            )
+        };
+        assert_eq!(status, NamadaStatus::NullPointer);
+        assert!(out.data.is_null());
+
+        // releasing null handles is a no-op
+        unsafe { namada_tx_free(std::ptr::null_mut()) };
+        unsafe { namada_buffer_free(NamadaBuffer::empty()) };
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let args = b"not json";
+        let mut tx: *mut NamadaTx = std::ptr::null_mut();
+        let status =
+            unsafe { namada_tx_bond_new(args.as_ptr(), args.len(), &mut tx) };
+        assert_eq!(status, NamadaStatus::InvalidArgument);
+        assert!(tx.is_null());
+        assert!(!last_error().is_empty());
+    }
+
+    #[test]
+    fn test_failed_query() {
+        let addr = unreachable_node();
+        let mut client: *mut NamadaClient = std::ptr::null_mut();
+        let status = unsafe { namada_client_new(addr.as_ptr(), &mut client) };
+        assert_eq!(status, NamadaStatus::Ok);
+
+        for query in [
+            br#"{"query": "query_epoch"}"#.as_slice(),
+            br#"{"query": "query_governance_parameters"}"#.as_slice(),
+        ] {
+            let mut out = NamadaBuffer::empty();
+            let status = unsafe {
+                namada_client_query(
+                    client,
+                    query.as_ptr(),
+                    query.len(),
+                    &mut out,
+                )
+            };
+            assert_eq!(status, NamadaStatus::RequestFailed);
+        }
+        let query = br#"{"query": "unknown"}"#;
+        let mut out = NamadaBuffer::empty();
+        let status = unsafe {
+            namada_client_query(client, query.as_ptr(), query.len(), &mut out)
+        };
+        assert_eq!(status, NamadaStatus::InvalidArgument);
+
+        unsafe { reading::namada_client_free(client) };
+    }
+}
@@ -0,0 +1,382 @@
+//! FFI bindings to query a Namada node and broadcast transactions through a
+//! persistent [`LightClient`]
+
+use std::ffi::{c_char, CStr};
+
+use borsh_ext::BorshSerializeExt;
+use namada_sdk::error::Error;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::storage::{BlockHeight, Epoch};
+use serde::Deserialize;
+
+use super::{
+    catch_panic, fail, input, json_input, output, try_ffi, NamadaBuffer,
+    NamadaStatus,
+};
+use crate::writing::blocking::{LightClient, DEFAULT_TX_WAIT_TIME};
+
+/// An opaque handle on a connection to a Namada node
+pub struct NamadaClient(LightClient);
+
+/// The queries that can be made through [`namada_client_query`], tagged by
+/// their `query` field in JSON. The name of each query matches the
+/// corresponding method of [`LightClient`].
+#[derive(Deserialize)]
+#[serde(tag = "query", rename_all = "snake_case")]
+enum Query {
+    QueryNativeToken,
+    QueryBlock,
+    QueryResults,
+    GetTokenBalance {
+        token: Address,
+        owner: Address,
+    },
+    KnownAddress {
+        address: Address,
+    },
+    GetAccountInfo {
+        owner: Address,
+    },
+    IsPublicKeyRevealed {
+        owner: Address,
+    },
+    GetPublicKeyAt {
+        owner: Address,
+        index: u8,
+    },
+    QueryProposalById {
+        proposal_id: u64,
+    },
+    QueryGovernanceParameters,
+    QueryProposalVotes {
+        proposal_id: u64,
+    },
+    IsSteward {
+        address: Address,
+    },
+    QueryEpoch,
+    QueryEpochAtHeight {
+        height: BlockHeight,
+    },
+    IsValidator {
+        address: Address,
+    },
+    IsDelegator {
+        address: Address,
+    },
+    IsDelegatorAt {
+        address: Address,
+        epoch: Epoch,
+    },
+    GetConsensusKeys,
+    GetPosParams,
+    GetAllValidators {
+        epoch: Epoch,
+    },
+    GetTotalStakedTokens {
+        epoch: Epoch,
+    },
+    GetValidatorStake {
+        epoch: Epoch,
+        validator: Address,
+    },
+    GetValidatorState {
+        validator: Address,
+        epoch: Option<Epoch>,
+    },
+    GetDelegatorsDelegation {
+        address: Address,
+    },
+    GetDelegatorsDelegationAt {
+        address: Address,
+        epoch: Epoch,
+    },
+    QueryCommissionRate {
+        validator: Address,
+        epoch: Option<Epoch>,
+    },
+    QueryMetadata {
+        validator: Address,
+        epoch: Option<Epoch>,
+    },
+    QueryIncomingRedelegations {
+        src_validator: Address,
+        delegator: Address,
+    },
+    QueryBond {
+        source: Address,
+        validator: Address,
+        epoch: Option<Epoch>,
+    },
+    QueryWithdrawableTokens {
+        bond_source: Address,
+        validator: Address,
+        epoch: Option<Epoch>,
+    },
+    QueryUnbondWithSlashing {
+        source: Address,
+        validator: Address,
+    },
+    GetBondAmountAt {
+        delegator: Address,
+        validator: Address,
+        epoch: Epoch,
+    },
+    BondsAndUnbonds {
+        source: Option<Address>,
+        validator: Option<Address>,
+    },
+    QueryTxEvents {
+        tx_hash: String,
+    },
+}
+
+impl Query {
+    /// Run the query and return the Borsh encoding of its result
+    fn run(self, client: &LightClient) -> Result<Vec<u8>, Error> {
+        Ok(match self {
+            Query::QueryNativeToken => {
+                client.query_native_token()?.serialize_to_vec()
+            }
+            Query::QueryBlock => client.query_block()?.serialize_to_vec(),
+            Query::QueryResults => client.query_results()?.serialize_to_vec(),
+            Query::GetTokenBalance { token, owner } => {
+                client.get_token_balance(&token, &owner)?.serialize_to_vec()
+            }
+            Query::KnownAddress { address } => {
+                client.known_address(&address)?.serialize_to_vec()
+            }
+            Query::GetAccountInfo { owner } => {
+                client.get_account_info(&owner)?.serialize_to_vec()
+            }
+            Query::IsPublicKeyRevealed { owner } => {
+                client.is_public_key_revealed(&owner)?.serialize_to_vec()
+            }
+            Query::GetPublicKeyAt { owner, index } => {
+                client.get_public_key_at(&owner, index)?.serialize_to_vec()
+            }
+            Query::QueryProposalById { proposal_id } => {
+                client.query_proposal_by_id(proposal_id)?.serialize_to_vec()
+            }
+            Query::QueryGovernanceParameters => {
+                client.query_governance_parameters()?.serialize_to_vec()
+            }
+            Query::QueryProposalVotes { proposal_id } => {
+                client.query_proposal_votes(proposal_id)?.serialize_to_vec()
+            }
+            Query::IsSteward { address } => {
+                client.is_steward(&address)?.serialize_to_vec()
+            }
+            Query::QueryEpoch => client.query_epoch()?.serialize_to_vec(),
+            Query::QueryEpochAtHeight { height } => {
+                client.query_epoch_at_height(height)?.serialize_to_vec()
+            }
+            Query::IsValidator { address } => {
+                client.is_validator(&address)?.serialize_to_vec()
+            }
+            Query::IsDelegator { address } => {
+                client.is_delegator(&address)?.serialize_to_vec()
+            }
+            Query::IsDelegatorAt { address, epoch } => {
+                client.is_delegator_at(&address, epoch)?.serialize_to_vec()
+            }
+            Query::GetConsensusKeys => {
+                client.get_consensus_keys()?.serialize_to_vec()
+            }
+            Query::GetPosParams => client.get_pos_params()?.serialize_to_vec(),
+            Query::GetAllValidators { epoch } => {
+                client.get_all_validators(epoch)?.serialize_to_vec()
+            }
+            Query::GetTotalStakedTokens { epoch } => {
+                client.get_total_staked_tokens(epoch)?.serialize_to_vec()
+            }
+            Query::GetValidatorStake { epoch, validator } => client
+                .get_validator_stake(epoch, &validator)?
+                .serialize_to_vec(),
+            Query::GetValidatorState { validator, epoch } => client
+                .get_validator_state(&validator, epoch)?
+                .serialize_to_vec(),
+            Query::GetDelegatorsDelegation { address } => client
+                .get_delegators_delegation(&address)?
+                .serialize_to_vec(),
+            Query::GetDelegatorsDelegationAt { address, epoch } => client
+                .get_delegators_delegation_at(&address, epoch)?
+                .serialize_to_vec(),
+            Query::QueryCommissionRate { validator, epoch } => client
+                .query_commission_rate(&validator, epoch)?
+                .serialize_to_vec(),
+            Query::QueryMetadata { validator, epoch } => {
+                client.query_metadata(&validator, epoch)?.serialize_to_vec()
+            }
+            Query::QueryIncomingRedelegations {
+                src_validator,
+                delegator,
+            } => client
+                .query_incoming_redelegations(&src_validator, &delegator)?
+                .serialize_to_vec(),
+            Query::QueryBond {
+                source,
+                validator,
+                epoch,
+            } => client
+                .query_bond(&source, &validator, epoch)?
+                .serialize_to_vec(),
+            Query::QueryWithdrawableTokens {
+                bond_source,
+                validator,
+                epoch,
+            } => client
+                .query_withdrawable_tokens(&bond_source, &validator, epoch)?
+                .serialize_to_vec(),
+            Query::QueryUnbondWithSlashing { source, validator } => client
+                .query_unbond_with_slashing(&source, &validator)?
+                .serialize_to_vec(),
+            Query::GetBondAmountAt {
+                delegator,
+                validator,
+                epoch,
+            } => client
+                .get_bond_amount_at(&delegator, &validator, epoch)?
+                .serialize_to_vec(),
+            Query::BondsAndUnbonds { source, validator } => client
+                .bonds_and_unbonds(&source, &validator)?
+                .serialize_to_vec(),
+            Query::QueryTxEvents { tx_hash } => {
+                client.query_tx_events(&tx_hash)?.serialize_to_vec()
+            }
+        })
+    }
+}
+
+/// Borrow the client behind a handle
+unsafe fn client_ref<'a>(
+    client: *const NamadaClient,
+) -> Result<&'a LightClient, NamadaStatus> {
+    client
+        .as_ref()
+        .map(|NamadaClient(client)| client)
+        .ok_or_else(|| fail(NamadaStatus::NullPointer, "Client is null"))
+}
+
+/// Connect to the Namada node at the given NUL-terminated Tendermint RPC
+/// address.
+///
+/// # Safety
+///
+/// `tendermint_addr` must be a valid NUL-terminated string and `out` must be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_client_new(
+    tendermint_addr: *const c_char,
+    out: *mut *mut NamadaClient,
+) -> NamadaStatus {
+    catch_panic(|| {
+        if tendermint_addr.is_null() {
+            return fail(NamadaStatus::NullPointer, "Address is null");
+        }
+        let addr = try_ffi!(CStr::from_ptr(tendermint_addr)
+            .to_str()
+            .map_err(|err| fail(NamadaStatus::InvalidArgument, err)));
+        let client = try_ffi!(LightClient::new(addr)
+            .map_err(|err| fail(NamadaStatus::InvalidArgument, err)));
+        output(out, Box::into_raw(Box::new(NamadaClient(client))))
+    })
+}
+
+/// Run the JSON encoded query, e.g.
+/// `{"query": "get_token_balance", "token": "tnam1...", "owner": "tnam1..."}`,
+/// and return the Borsh encoding of its result.
+///
+/// # Safety
+///
+/// `client` must be a live handle, `query` must point to `query_len`
+/// readable bytes and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_client_query(
+    client: *const NamadaClient,
+    query: *const u8,
+    query_len: usize,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let client = try_ffi!(client_ref(client));
+        let query: Query = try_ffi!(json_input(query, query_len));
+        let result = try_ffi!(query
+            .run(client)
+            .map_err(|err| fail(NamadaStatus::RequestFailed, err)));
+        output(out, result.into())
+    })
+}
+
+/// Dry run the given protobuf encoded transaction and return the Borsh
+/// encoding of its `TxResult`.
+///
+/// # Safety
+///
+/// `client` must be a live handle, `tx` must point to `tx_len` readable
+/// bytes and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_client_dry_run_tx(
+    client: *const NamadaClient,
+    tx: *const u8,
+    tx_len: usize,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let client = try_ffi!(client_ref(client));
+        let tx = try_ffi!(input(tx, tx_len)).to_vec();
+        let result = try_ffi!(client
+            .dry_run_tx(tx)
+            .map_err(|err| fail(NamadaStatus::RequestFailed, err)));
+        output(out, result.serialize_to_vec().into())
+    })
+}
+
+/// Broadcast the given protobuf encoded wrapper transaction and wait for it
+/// to be applied, for up to `timeout_secs` seconds or a default of one
+/// minute if zero. The result is the JSON encoding of the transaction's
+/// response.
+///
+/// # Safety
+///
+/// `client` must be a live handle, `tx` must point to `tx_len` readable
+/// bytes and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_client_broadcast_tx(
+    client: *const NamadaClient,
+    tx: *const u8,
+    tx_len: usize,
+    timeout_secs: u64,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let client = try_ffi!(client_ref(client));
+        let tx = try_ffi!(input(tx, tx_len)).to_vec();
+        let timeout = if timeout_secs == 0 {
+            DEFAULT_TX_WAIT_TIME
+        } else {
+            std::time::Duration::from_secs(timeout_secs)
+        };
+        let response = try_ffi!(client
+            .broadcast_tx_and_wait(tx, timeout)
+            .map_err(|err| fail(NamadaStatus::RequestFailed, err)));
+        let response = try_ffi!(serde_json::to_vec(&response)
+            .map_err(|err| fail(NamadaStatus::RequestFailed, err)));
+        output(out, response.into())
+    })
+}
+
+/// Release a client handle.
+///
+/// # Safety
+///
+/// `client` must be null or a handle that was not released before.
+#[no_mangle]
+pub unsafe extern "C" fn namada_client_free(client: *mut NamadaClient) {
+    _ = catch_panic(|| {
+        if !client.is_null() {
+            drop(Box::from_raw(client));
+        }
+        NamadaStatus::Ok
+    });
+}
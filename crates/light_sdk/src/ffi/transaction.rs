@@ -0,0 +1,372 @@
+//! FFI bindings to build, sign and encode transactions
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
+use namada_sdk::governance::ProposalVote;
+use namada_sdk::ibc::primitives::proto::Any;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::chain::ChainId;
+use namada_sdk::types::hash::Hash;
+use namada_sdk::types::key::common;
+use namada_sdk::types::time::DateTimeUtc;
+use namada_sdk::types::token::{self, DenominatedAmount};
+use serde::Deserialize;
+
+use super::{
+    catch_panic, fail, input, json_input, output, try_ffi, NamadaBuffer,
+    NamadaStatus,
+};
+use crate::transaction::account::RevealPk;
+use crate::transaction::governance::VoteProposal;
+use crate::transaction::ibc::{IbcTransfer, MsgTransfer};
+use crate::transaction::pos::Bond;
+use crate::transaction::transfer::Transfer;
+use crate::transaction::GlobalArgs;
+
+/// An opaque handle on a transaction built through the FFI
+pub struct NamadaTx(Option<LightTx>);
+
+/// The transactions that can be built through the FFI
+enum LightTx {
+    Transfer(Transfer),
+    Bond(Bond),
+    VoteProposal(VoteProposal),
+    IbcTransfer(IbcTransfer),
+    RevealPk(RevealPk),
+}
+
+/// Apply the same expression to the transaction in any variant of
+/// [`LightTx`]
+macro_rules! with_tx {
+    ($light_tx:expr, $tx:ident => $body:expr) => {
+        match $light_tx {
+            LightTx::Transfer($tx) => $body,
+            LightTx::Bond($tx) => $body,
+            LightTx::VoteProposal($tx) => $body,
+            LightTx::IbcTransfer($tx) => $body,
+            LightTx::RevealPk($tx) => $body,
+        }
+    };
+}
+
+impl LightTx {
+    fn get_sign_bytes(&self) -> Vec<Hash> {
+        with_tx!(self, tx => tx.get_sign_bytes())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        with_tx!(self, tx => tx.to_bytes())
+    }
+
+    fn attach_signatures(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        match self {
+            LightTx::Transfer(tx) => {
+                LightTx::Transfer(tx.attach_signatures(signer, signature))
+            }
+            LightTx::Bond(tx) => {
+                LightTx::Bond(tx.attach_signatures(signer, signature))
+            }
+            LightTx::VoteProposal(tx) => {
+                LightTx::VoteProposal(tx.attach_signatures(signer, signature))
+            }
+            LightTx::IbcTransfer(tx) => {
+                LightTx::IbcTransfer(tx.attach_signatures(signer, signature))
+            }
+            LightTx::RevealPk(tx) => {
+                LightTx::RevealPk(tx.attach_signatures(signer, signature))
+            }
+        }
+    }
+}
+
+/// JSON encoding of the [`GlobalArgs`] shared by all the transactions
+#[derive(Deserialize)]
+struct GlobalArgsJson {
+    expiration: Option<DateTimeUtc>,
+    /// Hex encoded hash of the transaction's wasm code
+    code_hash: String,
+    chain_id: ChainId,
+}
+
+impl TryFrom<GlobalArgsJson> for GlobalArgs {
+    type Error = NamadaStatus;
+
+    fn try_from(args: GlobalArgsJson) -> Result<Self, Self::Error> {
+        let code_hash = args
+            .code_hash
+            .parse::<Hash>()
+            .map_err(|err| fail(NamadaStatus::InvalidArgument, err))?;
+        Ok(GlobalArgs {
+            expiration: args.expiration,
+            code_hash,
+            chain_id: args.chain_id,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct TransferJson {
+    source: Address,
+    target: Address,
+    token: Address,
+    amount: DenominatedAmount,
+    key: Option<String>,
+    #[serde(flatten)]
+    global: GlobalArgsJson,
+}
+
+#[derive(Deserialize)]
+struct BondJson {
+    validator: Address,
+    amount: token::Amount,
+    source: Option<Address>,
+    #[serde(flatten)]
+    global: GlobalArgsJson,
+}
+
+#[derive(Deserialize)]
+struct VoteProposalJson {
+    id: u64,
+    vote: ProposalVote,
+    voter: Address,
+    delegations: Vec<Address>,
+    #[serde(flatten)]
+    global: GlobalArgsJson,
+}
+
+#[derive(Deserialize)]
+struct RevealPkJson {
+    public_key: common::PublicKey,
+    #[serde(flatten)]
+    global: GlobalArgsJson,
+}
+
+/// Hand out a new transaction handle through the given output pointer
+unsafe fn new_tx(out: *mut *mut NamadaTx, tx: LightTx) -> NamadaStatus {
+    output(out, Box::into_raw(Box::new(NamadaTx(Some(tx)))))
+}
+
+/// Borrow the transaction behind a handle
+unsafe fn tx_ref<'a>(tx: *const NamadaTx) -> Result<&'a LightTx, NamadaStatus> {
+    tx.as_ref()
+        .and_then(|tx| tx.0.as_ref())
+        .ok_or_else(|| fail(NamadaStatus::NullPointer, "Transaction is null"))
+}
+
+/// Build a transparent transfer from its JSON arguments: `source`, `target`,
+/// `token`, `amount` (a denominated amount string), an optional `key` and
+/// the global arguments `chain_id`, `code_hash` and an optional
+/// `expiration`.
+///
+/// # Safety
+///
+/// `args` must point to `args_len` readable bytes and `out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_transfer_new(
+    args: *const u8,
+    args_len: usize,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let args: TransferJson = try_ffi!(json_input(args, args_len));
+        let global = try_ffi!(GlobalArgs::try_from(args.global));
+        let tx = Transfer::new(
+            args.source,
+            args.target,
+            args.token,
+            args.amount,
+            args.key,
+            None,
+            global,
+        );
+        new_tx(out, LightTx::Transfer(tx))
+    })
+}
+
+/// Build a bond from its JSON arguments: `validator`, `amount`, an optional
+/// `source` and the global arguments.
+///
+/// # Safety
+///
+/// `args` must point to `args_len` readable bytes and `out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_bond_new(
+    args: *const u8,
+    args_len: usize,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let args: BondJson = try_ffi!(json_input(args, args_len));
+        let global = try_ffi!(GlobalArgs::try_from(args.global));
+        let tx = Bond::new(args.validator, args.amount, args.source, global);
+        new_tx(out, LightTx::Bond(tx))
+    })
+}
+
+/// Build a governance vote from its JSON arguments: the proposal `id`, the
+/// `vote` (`"Yay"`, `"Nay"` or `"Abstain"`), the `voter`, its
+/// `delegations` and the global arguments.
+///
+/// # Safety
+///
+/// `args` must point to `args_len` readable bytes and `out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_vote_proposal_new(
+    args: *const u8,
+    args_len: usize,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let args: VoteProposalJson = try_ffi!(json_input(args, args_len));
+        let global = try_ffi!(GlobalArgs::try_from(args.global));
+        let tx = VoteProposal::new(
+            args.id,
+            args.vote,
+            args.voter,
+            args.delegations,
+            global,
+        );
+        new_tx(out, LightTx::VoteProposal(tx))
+    })
+}
+
+/// Build an IBC transfer from a protobuf encoded `Any` wrapping a
+/// `MsgTransfer` and the JSON encoded global arguments.
+///
+/// # Safety
+///
+/// `msg` and `args` must point to `msg_len` and `args_len` readable bytes
+/// respectively and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_ibc_transfer_new(
+    msg: *const u8,
+    msg_len: usize,
+    args: *const u8,
+    args_len: usize,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let msg = try_ffi!(input(msg, msg_len));
+        let any: Any = try_ffi!(prost::Message::decode(msg)
+            .map_err(|err| fail(NamadaStatus::InvalidArgument, err)));
+        let msg = try_ffi!(MsgTransfer::try_from(any)
+            .map_err(|err| fail(NamadaStatus::InvalidArgument, err)));
+        let global: GlobalArgsJson = try_ffi!(json_input(args, args_len));
+        let global = try_ffi!(GlobalArgs::try_from(global));
+        new_tx(out, LightTx::IbcTransfer(IbcTransfer::new(msg, global)))
+    })
+}
+
+/// Build a public key revelation from its JSON arguments: the
+/// `public_key` and the global arguments.
+///
+/// # Safety
+///
+/// `args` must point to `args_len` readable bytes and `out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_reveal_pk_new(
+    args: *const u8,
+    args_len: usize,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let args: RevealPkJson = try_ffi!(json_input(args, args_len));
+        let global = try_ffi!(GlobalArgs::try_from(args.global));
+        new_tx(
+            out,
+            LightTx::RevealPk(RevealPk::new(args.public_key, global)),
+        )
+    })
+}
+
+/// Get the Borsh encoded list of hashes that must be signed for the
+/// transaction.
+///
+/// # Safety
+///
+/// `tx` must be a live handle and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_sign_bytes(
+    tx: *const NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let tx = try_ffi!(tx_ref(tx));
+        output(out, tx.get_sign_bytes().serialize_to_vec().into())
+    })
+}
+
+/// Attach a signature to the transaction, given the Borsh encoded
+/// `common::PublicKey` of the signer and the Borsh encoded
+/// `common::Signature`.
+///
+/// # Safety
+///
+/// `tx` must be a live handle and the key and signature pointers must point
+/// to the given number of readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_attach_signature(
+    tx: *mut NamadaTx,
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let public_key = try_ffi!(input(public_key, public_key_len));
+        let public_key =
+            try_ffi!(common::PublicKey::try_from_slice(public_key)
+                .map_err(|err| fail(NamadaStatus::InvalidArgument, err)));
+        let signature = try_ffi!(input(signature, signature_len));
+        let signature = try_ffi!(common::Signature::try_from_slice(signature)
+            .map_err(|err| fail(NamadaStatus::InvalidArgument, err)));
+        let Some(NamadaTx(slot)) = tx.as_mut() else {
+            return fail(NamadaStatus::NullPointer, "Transaction is null");
+        };
+        let Some(light_tx) = slot.take() else {
+            return fail(NamadaStatus::NullPointer, "Transaction is null");
+        };
+        *slot = Some(light_tx.attach_signatures(public_key, signature));
+        NamadaStatus::Ok
+    })
+}
+
+/// Get the protobuf encoding of the transaction, ready to be wrapped or
+/// broadcast.
+///
+/// # Safety
+///
+/// `tx` must be a live handle and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_to_bytes(
+    tx: *const NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    catch_panic(|| {
+        let tx = try_ffi!(tx_ref(tx));
+        output(out, tx.to_bytes().into())
+    })
+}
+
+/// Release a transaction handle.
+///
+/// # Safety
+///
+/// `tx` must be null or a handle that was not released before.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_free(tx: *mut NamadaTx) {
+    _ = catch_panic(|| {
+        if !tx.is_null() {
+            drop(Box::from_raw(tx));
+        }
+        NamadaStatus::Ok
+    });
+}
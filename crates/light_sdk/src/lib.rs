@@ -13,8 +13,14 @@
 //! - transaction: contains functions to construct all the transactions
 //!   currently supported by the protocol
 //! - reading: exposes queries to retrieve data from a Namada node
-//! - writing: TO BE DONE
+//! - writing: exposes a persistent client handle, in both blocking and async
+//!   flavours, to query a Namada node and broadcast transactions
+//!
+//! With the `ffi` feature, the `ffi` module additionally exposes C-compatible
+//! bindings over the transaction and reading modules.
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod reading;
 pub mod transaction;
 pub mod writing;
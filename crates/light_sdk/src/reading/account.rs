@@ -10,13 +10,7 @@ pub fn get_token_balance(
     token: &Address,
     owner: &Address,
 ) -> Result<token::Amount, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_token_balance(&client, token, owner))
+    blocking::LightClient::new(tendermint_addr)?.get_token_balance(token, owner)
 }
 
 /// Check if the address exists on chain. Established address exists if it
@@ -26,13 +20,7 @@ pub fn known_address(
     tendermint_addr: &str,
     address: &Address,
 ) -> Result<bool, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::known_address(&client, address))
+    blocking::LightClient::new(tendermint_addr)?.known_address(address)
 }
 
 /// Query the accunt substorage space of an address
@@ -40,13 +28,7 @@ pub fn get_account_info(
     tendermint_addr: &str,
     owner: &Address,
 ) -> Result<Option<Account>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_account_info(&client, owner))
+    blocking::LightClient::new(tendermint_addr)?.get_account_info(owner)
 }
 
 /// Query if the public_key is revealed
@@ -54,13 +36,7 @@ pub fn is_public_key_revealed(
     tendermint_addr: &str,
     owner: &Address,
 ) -> Result<bool, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::is_public_key_revealed(&client, owner))
+    blocking::LightClient::new(tendermint_addr)?.is_public_key_revealed(owner)
 }
 
 /// Query an account substorage at a specific index
@@ -69,11 +45,5 @@ pub fn get_public_key_at(
     owner: &Address,
     index: u8,
 ) -> Result<Option<common::PublicKey>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_public_key_at(&client, owner, index))
+    blocking::LightClient::new(tendermint_addr)?.get_public_key_at(owner, index)
 }
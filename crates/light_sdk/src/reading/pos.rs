@@ -13,13 +13,7 @@ use super::*;
 
 /// Query the epoch of the last committed block
 pub fn query_epoch(tendermint_addr: &str) -> Result<Epoch, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_epoch(&client))
+    blocking::LightClient::new(tendermint_addr)?.query_epoch()
 }
 
 /// Query the epoch of the given block height, if it exists.
@@ -29,13 +23,7 @@ pub fn query_epoch_at_height(
     tendermint_addr: &str,
     height: BlockHeight,
 ) -> Result<Option<Epoch>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_epoch_at_height(&client, height))
+    blocking::LightClient::new(tendermint_addr)?.query_epoch_at_height(height)
 }
 
 /// Check if the given address is a known validator.
@@ -43,13 +31,7 @@ pub fn is_validator(
     tendermint_addr: &str,
     address: &Address,
 ) -> Result<bool, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::is_validator(&client, address))
+    blocking::LightClient::new(tendermint_addr)?.is_validator(address)
 }
 
 /// Check if a given address is a known delegator
@@ -57,13 +39,7 @@ pub fn is_delegator(
     tendermint_addr: &str,
     address: &Address,
 ) -> Result<bool, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::is_delegator(&client, address))
+    blocking::LightClient::new(tendermint_addr)?.is_delegator(address)
 }
 
 /// Check if a given address is a known delegator at the given epoch
@@ -72,37 +48,19 @@ pub fn is_delegator_at(
     address: &Address,
     epoch: Epoch,
 ) -> Result<bool, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::is_delegator_at(&client, address, epoch))
+    blocking::LightClient::new(tendermint_addr)?.is_delegator_at(address, epoch)
 }
 
 /// Get the set of consensus keys registered in the network
 pub fn get_consensus_keys(
     tendermint_addr: &str,
 ) -> Result<BTreeSet<common::PublicKey>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_consensus_keys(&client))
+    blocking::LightClient::new(tendermint_addr)?.get_consensus_keys()
 }
 
 /// Get the PoS parameters
 pub fn get_pos_params(tendermint_addr: &str) -> Result<PosParams, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_pos_params(&client))
+    blocking::LightClient::new(tendermint_addr)?.get_pos_params()
 }
 
 /// Get all validators in the given epoch
@@ -110,13 +68,7 @@ pub fn get_all_validators(
     tendermint_addr: &str,
     epoch: Epoch,
 ) -> Result<HashSet<Address>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_all_validators(&client, epoch))
+    blocking::LightClient::new(tendermint_addr)?.get_all_validators(epoch)
 }
 
 /// Get the total staked tokens in the given epoch
@@ -124,13 +76,7 @@ pub fn get_total_staked_tokens(
     tendermint_addr: &str,
     epoch: Epoch,
 ) -> Result<token::Amount, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_total_staked_tokens(&client, epoch))
+    blocking::LightClient::new(tendermint_addr)?.get_total_staked_tokens(epoch)
 }
 
 /// Get the given validator's stake at the given epoch
@@ -139,13 +85,8 @@ pub fn get_validator_stake(
     epoch: Epoch,
     validator: &Address,
 ) -> Result<token::Amount, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_validator_stake(&client, epoch, validator))
+    blocking::LightClient::new(tendermint_addr)?
+        .get_validator_stake(epoch, validator)
 }
 
 /// Query and return a validator's state
@@ -154,13 +95,8 @@ pub fn get_validator_state(
     validator: &Address,
     epoch: Option<Epoch>,
 ) -> Result<Option<ValidatorState>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_validator_state(&client, validator, epoch))
+    blocking::LightClient::new(tendermint_addr)?
+        .get_validator_state(validator, epoch)
 }
 
 /// Get the delegator's delegation
@@ -168,13 +104,8 @@ pub fn get_delegators_delegation(
     tendermint_addr: &str,
     address: &Address,
 ) -> Result<HashSet<Address>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_delegators_delegation(&client, address))
+    blocking::LightClient::new(tendermint_addr)?
+        .get_delegators_delegation(address)
 }
 
 /// Get the delegator's delegation at some epoh
@@ -183,13 +114,8 @@ pub fn get_delegators_delegation_at(
     address: &Address,
     epoch: Epoch,
 ) -> Result<HashMap<Address, token::Amount>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_delegators_delegation_at(&client, address, epoch))
+    blocking::LightClient::new(tendermint_addr)?
+        .get_delegators_delegation_at(address, epoch)
 }
 
 /// Query and return validator's commission rate and max commission rate
@@ -199,13 +125,8 @@ pub fn query_commission_rate(
     validator: &Address,
     epoch: Option<Epoch>,
 ) -> Result<Option<CommissionPair>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_commission_rate(&client, validator, epoch))
+    blocking::LightClient::new(tendermint_addr)?
+        .query_commission_rate(validator, epoch)
 }
 
 /// Query and return validator's metadata, including the commission rate and
@@ -215,13 +136,8 @@ pub fn query_metadata(
     validator: &Address,
     epoch: Option<Epoch>,
 ) -> Result<(Option<ValidatorMetaData>, Option<CommissionPair>), Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_metadata(&client, validator, epoch))
+    blocking::LightClient::new(tendermint_addr)?
+        .query_metadata(validator, epoch)
 }
 
 /// Query and return the incoming redelegation epoch for a given pair of
@@ -231,17 +147,8 @@ pub fn query_incoming_redelegations(
     src_validator: &Address,
     delegator: &Address,
 ) -> Result<Option<Epoch>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_incoming_redelegations(
-        &client,
-        src_validator,
-        delegator,
-    ))
+    blocking::LightClient::new(tendermint_addr)?
+        .query_incoming_redelegations(src_validator, delegator)
 }
 
 /// Query a validator's bonds for a given epoch
@@ -251,13 +158,8 @@ pub fn query_bond(
     validator: &Address,
     epoch: Option<Epoch>,
 ) -> Result<token::Amount, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_bond(&client, source, validator, epoch))
+    blocking::LightClient::new(tendermint_addr)?
+        .query_bond(source, validator, epoch)
 }
 
 /// Query a validator's unbonds for a given epoch
@@ -266,10 +168,8 @@ pub fn query_and_print_unbonds(
     source: &Address,
     validator: &Address,
 ) -> Result<HashMap<(Epoch, Epoch), token::Amount>, Error> {
-    let rt = Runtime::new().unwrap();
-    rt.block_on(async {
-        query_unbond_with_slashing(tendermint_addr, source, validator)
-    })
+    blocking::LightClient::new(tendermint_addr)?
+        .query_unbond_with_slashing(source, validator)
 }
 
 /// Query withdrawable tokens in a validator account for a given epoch
@@ -279,18 +179,11 @@ pub fn query_withdrawable_tokens(
     validator: &Address,
     epoch: Option<Epoch>,
 ) -> Result<token::Amount, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_withdrawable_tokens(
-        &client,
+    blocking::LightClient::new(tendermint_addr)?.query_withdrawable_tokens(
         bond_source,
         validator,
         epoch,
-    ))
+    )
 }
 
 /// Query all unbonds for a validator, applying slashes
@@ -299,13 +192,8 @@ pub fn query_unbond_with_slashing(
     source: &Address,
     validator: &Address,
 ) -> Result<HashMap<(Epoch, Epoch), token::Amount>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_unbond_with_slashing(&client, source, validator))
+    blocking::LightClient::new(tendermint_addr)?
+        .query_unbond_with_slashing(source, validator)
 }
 
 /// Get the bond amount at the given epoch
@@ -315,15 +203,8 @@ pub fn get_bond_amount_at(
     validator: &Address,
     epoch: Epoch,
 ) -> Result<token::Amount, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::get_bond_amount_at(
-        &client, delegator, validator, epoch,
-    ))
+    blocking::LightClient::new(tendermint_addr)?
+        .get_bond_amount_at(delegator, validator, epoch)
 }
 
 /// Get bonds and unbonds with all details (slashes and rewards, if any)
@@ -333,13 +214,8 @@ pub fn bonds_and_unbonds(
     source: &Option<Address>,
     validator: &Option<Address>,
 ) -> Result<BondsAndUnbondsDetails, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::bonds_and_unbonds(&client, source, validator))
+    blocking::LightClient::new(tendermint_addr)?
+        .bonds_and_unbonds(source, validator)
 }
 
 /// Get bonds and unbonds with all details (slashes and rewards, if any)
@@ -351,16 +227,9 @@ pub fn enriched_bonds_and_unbonds(
     source: &Option<Address>,
     validator: &Option<Address>,
 ) -> Result<EnrichedBondsAndUnbondsDetails, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::enriched_bonds_and_unbonds(
-        &client,
+    blocking::LightClient::new(tendermint_addr)?.enriched_bonds_and_unbonds(
         current_epoch,
         source,
         validator,
-    ))
+    )
 }
@@ -1,11 +1,8 @@
-use std::str::FromStr;
-
 pub use namada_sdk::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use namada_sdk::ibc::primitives::Msg;
 use namada_sdk::tx::Tx;
 use namada_sdk::types::hash::Hash;
 use namada_sdk::types::key::common;
-use namada_sdk::types::time::DateTimeUtc;
 
 use super::GlobalArgs;
 use crate::transaction;
@@ -25,9 +22,7 @@ impl IbcTransfer {
             chain_id,
         }: GlobalArgs,
     ) -> Self {
-        let mut tx = Tx::new(chain_id, expiration);
-        tx.header.timestamp =
-            DateTimeUtc::from_str("2000-01-01T00:00:00Z").unwrap();
+        let mut tx = transaction::new_tx(chain_id, expiration);
         tx.add_code_from_hash(code_hash, Some(TX_IBC_WASM.to_string()));
 
         let mut data = vec![];
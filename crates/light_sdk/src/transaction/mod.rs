@@ -1,11 +1,10 @@
-use std::str::FromStr;
-
 use borsh::BorshSerialize;
+use namada_sdk::signing::DEFAULT_TX_EXPIRATION_SECS;
 use namada_sdk::tx::{Section, Signature, Signer, Tx};
 use namada_sdk::types::chain::ChainId;
 use namada_sdk::types::hash::Hash;
 use namada_sdk::types::key::common;
-use namada_sdk::types::time::DateTimeUtc;
+use namada_sdk::types::time::{DateTimeUtc, DurationSecs};
 
 pub mod account;
 pub mod bridge;
@@ -23,6 +22,19 @@ pub struct GlobalArgs {
     pub chain_id: ChainId,
 }
 
+/// Create a new transaction timestamped now. The expiration defaults to
+/// [`DEFAULT_TX_EXPIRATION_SECS`] from now, since the ledger rejects the
+/// transactions without one
+pub(in crate::transaction) fn new_tx(
+    chain_id: ChainId,
+    expiration: Option<DateTimeUtc>,
+) -> Tx {
+    let expiration = expiration.unwrap_or_else(|| {
+        DateTimeUtc::now() + DurationSecs(DEFAULT_TX_EXPIRATION_SECS)
+    });
+    Tx::new(chain_id, Some(expiration))
+}
+
 pub(in crate::transaction) fn build_tx(
     GlobalArgs {
         expiration,
@@ -32,10 +44,8 @@ pub(in crate::transaction) fn build_tx(
     data: impl BorshSerialize,
     code_tag: String,
 ) -> Tx {
-    let mut inner_tx = Tx::new(chain_id, expiration);
+    let mut inner_tx = new_tx(chain_id, expiration);
 
-    inner_tx.header.timestamp =
-        DateTimeUtc::from_str("2000-01-01T00:00:00Z").unwrap();
     inner_tx.add_code_from_hash(code_hash, Some(code_tag));
     inner_tx.add_data(data);
 
@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use namada_sdk::governance::pgf::storage::batch::{
+    ChallengePgfBatchData, SubmitPgfBatchData,
+};
+use namada_sdk::governance::storage::proposal::PGFTarget;
 use namada_sdk::tx::Tx;
 use namada_sdk::types::address::Address;
 use namada_sdk::types::dec::Dec;
@@ -11,6 +15,8 @@ use crate::transaction;
 
 const TX_RESIGN_STEWARD: &str = "tx_resign_steward.wasm";
 const TX_UPDATE_STEWARD_COMMISSION: &str = "tx_update_steward_commission.wasm";
+const TX_SUBMIT_PGF_BATCH: &str = "tx_submit_pgf_batch.wasm";
+const TX_CHALLENGE_PGF_BATCH: &str = "tx_challenge_pgf_batch.wasm";
 
 /// A transaction to resign from stewarding pgf
 pub struct ResignSteward(Tx);
@@ -92,3 +98,91 @@ impl UpdateStewardCommission {
         self.0.to_bytes()
     }
 }
+
+/// Transaction for a pgf steward to submit a batch of payments
+pub struct SubmitPgfBatch(Tx);
+
+impl SubmitPgfBatch {
+    /// Build a raw SubmitPgfBatch transaction from the given parameters
+    pub fn new(
+        steward: Address,
+        payments: Vec<PGFTarget>,
+        args: GlobalArgs,
+    ) -> Self {
+        let submit_batch = SubmitPgfBatchData { steward, payments };
+
+        Self(transaction::build_tx(
+            args,
+            submit_batch,
+            TX_SUBMIT_PGF_BATCH.to_string(),
+        ))
+    }
+
+    /// Get the bytes to sign for the given transaction
+    pub fn get_sign_bytes(&self) -> Vec<Hash> {
+        transaction::get_sign_bytes(&self.0)
+    }
+
+    /// Attach the provided signatures to the tx
+    pub fn attach_signatures(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        Self(transaction::attach_raw_signatures(
+            self.0, signer, signature,
+        ))
+    }
+
+    /// Generates the protobuf encoding of this transaction
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+}
+
+/// Transaction for a validator to escalate a steward batch of payments to a
+/// governance proposal
+pub struct ChallengePgfBatch(Tx);
+
+impl ChallengePgfBatch {
+    /// Build a raw ChallengePgfBatch transaction from the given parameters
+    pub fn new(
+        batch_id: u64,
+        challenger: Address,
+        content: Hash,
+        args: GlobalArgs,
+    ) -> Self {
+        let challenge_batch = ChallengePgfBatchData {
+            batch_id,
+            challenger,
+            content,
+        };
+
+        Self(transaction::build_tx(
+            args,
+            challenge_batch,
+            TX_CHALLENGE_PGF_BATCH.to_string(),
+        ))
+    }
+
+    /// Get the bytes to sign for the given transaction
+    pub fn get_sign_bytes(&self) -> Vec<Hash> {
+        transaction::get_sign_bytes(&self.0)
+    }
+
+    /// Attach the provided signatures to the tx
+    pub fn attach_signatures(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        Self(transaction::attach_raw_signatures(
+            self.0, signer, signature,
+        ))
+    }
+
+    /// Generates the protobuf encoding of this transaction
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+}
@@ -0,0 +1,394 @@
+//! A blocking handle on a Namada node, which drives its own async runtime.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use namada_sdk::account::Account;
+use namada_sdk::control_flow::time;
+use namada_sdk::error::Error;
+use namada_sdk::events::Event;
+use namada_sdk::governance::parameters::GovernanceParameters;
+use namada_sdk::governance::storage::proposal::StorageProposal;
+use namada_sdk::governance::utils::Vote;
+use namada_sdk::proof_of_stake::types::{
+    BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
+};
+use namada_sdk::proof_of_stake::PosParams;
+use namada_sdk::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
+use namada_sdk::rpc::TxResponse;
+use namada_sdk::state::LastBlock;
+use namada_sdk::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::key::common;
+use namada_sdk::types::storage::{BlockHeight, BlockResults, Epoch};
+use namada_sdk::types::token;
+use tokio::runtime::Runtime;
+
+use super::unblocking;
+pub use super::unblocking::DEFAULT_TX_WAIT_TIME;
+
+/// A handle on a Namada node which reuses the same connection and runtime for
+/// all the queries and broadcasts
+pub struct LightClient {
+    inner: unblocking::LightClient,
+    rt: Runtime,
+}
+
+impl LightClient {
+    /// Connect to the Namada node at the given Tendermint RPC address
+    pub fn new(tendermint_addr: &str) -> Result<Self, Error> {
+        let inner = unblocking::LightClient::new(tendermint_addr)?;
+        let rt = Runtime::new().map_err(|e| Error::Other(e.to_string()))?;
+        Ok(Self { inner, rt })
+    }
+
+    /// Get the asynchronous handle backing this client
+    pub fn unblocking(&self) -> &unblocking::LightClient {
+        &self.inner
+    }
+
+    /// Query the address of the native token
+    pub fn query_native_token(&self) -> Result<Address, Error> {
+        self.rt.block_on(self.inner.query_native_token())
+    }
+
+    /// Query the last committed block, if any.
+    pub fn query_block(&self) -> Result<Option<LastBlock>, Error> {
+        self.rt.block_on(self.inner.query_block())
+    }
+
+    /// Query the results of the last committed block
+    pub fn query_results(&self) -> Result<Vec<BlockResults>, Error> {
+        self.rt.block_on(self.inner.query_results())
+    }
+
+    /// Query token amount of owner.
+    pub fn get_token_balance(
+        &self,
+        token: &Address,
+        owner: &Address,
+    ) -> Result<token::Amount, Error> {
+        self.rt.block_on(self.inner.get_token_balance(token, owner))
+    }
+
+    /// Check if the address exists on chain. Established address exists if it
+    /// has a stored validity predicate. Implicit and internal addresses
+    /// always return true.
+    pub fn known_address(&self, address: &Address) -> Result<bool, Error> {
+        self.rt.block_on(self.inner.known_address(address))
+    }
+
+    /// Query the account substorage space of an address
+    pub fn get_account_info(
+        &self,
+        owner: &Address,
+    ) -> Result<Option<Account>, Error> {
+        self.rt.block_on(self.inner.get_account_info(owner))
+    }
+
+    /// Query if the public_key is revealed
+    pub fn is_public_key_revealed(
+        &self,
+        owner: &Address,
+    ) -> Result<bool, Error> {
+        self.rt.block_on(self.inner.is_public_key_revealed(owner))
+    }
+
+    /// Query an account substorage at a specific index
+    pub fn get_public_key_at(
+        &self,
+        owner: &Address,
+        index: u8,
+    ) -> Result<Option<common::PublicKey>, Error> {
+        self.rt.block_on(self.inner.get_public_key_at(owner, index))
+    }
+
+    /// Query proposal by Id
+    pub fn query_proposal_by_id(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<StorageProposal>, Error> {
+        self.rt
+            .block_on(self.inner.query_proposal_by_id(proposal_id))
+    }
+
+    /// Get the governance parameters
+    pub fn query_governance_parameters(
+        &self,
+    ) -> Result<GovernanceParameters, Error> {
+        self.rt.block_on(self.inner.query_governance_parameters())
+    }
+
+    /// Get the votes of a proposal
+    pub fn query_proposal_votes(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Vec<Vote>, Error> {
+        self.rt
+            .block_on(self.inner.query_proposal_votes(proposal_id))
+    }
+
+    /// Check if the given address is a pgf steward.
+    pub fn is_steward(&self, address: &Address) -> Result<bool, Error> {
+        self.rt.block_on(self.inner.is_steward(address))
+    }
+
+    /// Query the epoch of the last committed block
+    pub fn query_epoch(&self) -> Result<Epoch, Error> {
+        self.rt.block_on(self.inner.query_epoch())
+    }
+
+    /// Query the epoch of the given block height, if it exists.
+    /// Will return none if the input block height is greater than
+    /// the latest committed block height.
+    pub fn query_epoch_at_height(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<Epoch>, Error> {
+        self.rt.block_on(self.inner.query_epoch_at_height(height))
+    }
+
+    /// Check if the given address is a known validator.
+    pub fn is_validator(&self, address: &Address) -> Result<bool, Error> {
+        self.rt.block_on(self.inner.is_validator(address))
+    }
+
+    /// Check if a given address is a known delegator
+    pub fn is_delegator(&self, address: &Address) -> Result<bool, Error> {
+        self.rt.block_on(self.inner.is_delegator(address))
+    }
+
+    /// Check if a given address is a known delegator at the given epoch
+    pub fn is_delegator_at(
+        &self,
+        address: &Address,
+        epoch: Epoch,
+    ) -> Result<bool, Error> {
+        self.rt.block_on(self.inner.is_delegator_at(address, epoch))
+    }
+
+    /// Get the set of consensus keys registered in the network
+    pub fn get_consensus_keys(
+        &self,
+    ) -> Result<BTreeSet<common::PublicKey>, Error> {
+        self.rt.block_on(self.inner.get_consensus_keys())
+    }
+
+    /// Get the PoS parameters
+    pub fn get_pos_params(&self) -> Result<PosParams, Error> {
+        self.rt.block_on(self.inner.get_pos_params())
+    }
+
+    /// Get all validators in the given epoch
+    pub fn get_all_validators(
+        &self,
+        epoch: Epoch,
+    ) -> Result<HashSet<Address>, Error> {
+        self.rt.block_on(self.inner.get_all_validators(epoch))
+    }
+
+    /// Get the total staked tokens in the given epoch
+    pub fn get_total_staked_tokens(
+        &self,
+        epoch: Epoch,
+    ) -> Result<token::Amount, Error> {
+        self.rt.block_on(self.inner.get_total_staked_tokens(epoch))
+    }
+
+    /// Get the given validator's stake at the given epoch
+    pub fn get_validator_stake(
+        &self,
+        epoch: Epoch,
+        validator: &Address,
+    ) -> Result<token::Amount, Error> {
+        self.rt
+            .block_on(self.inner.get_validator_stake(epoch, validator))
+    }
+
+    /// Query and return a validator's state
+    pub fn get_validator_state(
+        &self,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ValidatorState>, Error> {
+        self.rt
+            .block_on(self.inner.get_validator_state(validator, epoch))
+    }
+
+    /// Get the delegator's delegation
+    pub fn get_delegators_delegation(
+        &self,
+        address: &Address,
+    ) -> Result<HashSet<Address>, Error> {
+        self.rt
+            .block_on(self.inner.get_delegators_delegation(address))
+    }
+
+    /// Get the delegator's delegation at some epoch
+    pub fn get_delegators_delegation_at(
+        &self,
+        address: &Address,
+        epoch: Epoch,
+    ) -> Result<HashMap<Address, token::Amount>, Error> {
+        self.rt
+            .block_on(self.inner.get_delegators_delegation_at(address, epoch))
+    }
+
+    /// Query and return validator's commission rate and max commission rate
+    /// change per epoch
+    pub fn query_commission_rate(
+        &self,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<CommissionPair>, Error> {
+        self.rt
+            .block_on(self.inner.query_commission_rate(validator, epoch))
+    }
+
+    /// Query and return validator's metadata, including the commission rate and
+    /// max commission rate change
+    pub fn query_metadata(
+        &self,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<(Option<ValidatorMetaData>, Option<CommissionPair>), Error>
+    {
+        self.rt
+            .block_on(self.inner.query_metadata(validator, epoch))
+    }
+
+    /// Query and return the incoming redelegation epoch for a given pair of
+    /// source validator and delegator, if there is any.
+    pub fn query_incoming_redelegations(
+        &self,
+        src_validator: &Address,
+        delegator: &Address,
+    ) -> Result<Option<Epoch>, Error> {
+        self.rt.block_on(
+            self.inner
+                .query_incoming_redelegations(src_validator, delegator),
+        )
+    }
+
+    /// Query a validator's bonds for a given epoch
+    pub fn query_bond(
+        &self,
+        source: &Address,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<token::Amount, Error> {
+        self.rt
+            .block_on(self.inner.query_bond(source, validator, epoch))
+    }
+
+    /// Query withdrawable tokens in a validator account for a given epoch
+    pub fn query_withdrawable_tokens(
+        &self,
+        bond_source: &Address,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<token::Amount, Error> {
+        self.rt.block_on(self.inner.query_withdrawable_tokens(
+            bond_source,
+            validator,
+            epoch,
+        ))
+    }
+
+    /// Query all unbonds for a validator, applying slashes
+    pub fn query_unbond_with_slashing(
+        &self,
+        source: &Address,
+        validator: &Address,
+    ) -> Result<HashMap<(Epoch, Epoch), token::Amount>, Error> {
+        self.rt
+            .block_on(self.inner.query_unbond_with_slashing(source, validator))
+    }
+
+    /// Get the bond amount at the given epoch
+    pub fn get_bond_amount_at(
+        &self,
+        delegator: &Address,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> Result<token::Amount, Error> {
+        self.rt.block_on(
+            self.inner.get_bond_amount_at(delegator, validator, epoch),
+        )
+    }
+
+    /// Get bonds and unbonds with all details (slashes and rewards, if any)
+    /// grouped by their bond IDs.
+    pub fn bonds_and_unbonds(
+        &self,
+        source: &Option<Address>,
+        validator: &Option<Address>,
+    ) -> Result<BondsAndUnbondsDetails, Error> {
+        self.rt
+            .block_on(self.inner.bonds_and_unbonds(source, validator))
+    }
+
+    /// Get bonds and unbonds with all details (slashes and rewards, if any)
+    /// grouped by their bond IDs, enriched with extra information calculated
+    /// from the data.
+    pub fn enriched_bonds_and_unbonds(
+        &self,
+        current_epoch: Epoch,
+        source: &Option<Address>,
+        validator: &Option<Address>,
+    ) -> Result<EnrichedBondsAndUnbondsDetails, Error> {
+        self.rt.block_on(self.inner.enriched_bonds_and_unbonds(
+            current_epoch,
+            source,
+            validator,
+        ))
+    }
+
+    /// Call the corresponding `tx_event_query` RPC method, to fetch
+    /// the current status of a transaction.
+    pub fn query_tx_events(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<Event>, Error> {
+        self.rt.block_on(self.inner.query_tx_events(tx_hash))
+    }
+
+    /// Dry run a transaction
+    pub fn dry_run_tx(
+        &self,
+        tx_bytes: Vec<u8>,
+    ) -> Result<namada_sdk::tx::data::TxResult, Error> {
+        self.rt.block_on(self.inner.dry_run_tx(tx_bytes))
+    }
+
+    /// Lookup the full response accompanying the specified transaction event
+    pub fn query_tx_response(
+        &self,
+        tx_hash: &str,
+    ) -> Result<TxResponse, Error> {
+        self.rt.block_on(self.inner.query_tx_response(tx_hash))
+    }
+
+    /// Query the status of a given transaction.
+    pub fn query_tx_status(&self, tx_hash: &str) -> Result<Event, Error> {
+        self.rt.block_on(self.inner.query_tx_status(tx_hash))
+    }
+
+    /// Broadcast the given encoded transaction, as produced by the
+    /// `transaction` module, without waiting for it to be included in a
+    /// block. Returns the response of the mempool check.
+    pub fn broadcast_tx(&self, tx_bytes: Vec<u8>) -> Result<Response, Error> {
+        self.rt.block_on(self.inner.broadcast_tx(tx_bytes))
+    }
+
+    /// Broadcast the given encoded wrapper transaction and wait up to
+    /// `timeout` for it to be applied. The wrapper's response is returned if
+    /// it was rejected, otherwise the response of the inner transaction.
+    pub fn broadcast_tx_and_wait(
+        &self,
+        tx_bytes: Vec<u8>,
+        timeout: time::Duration,
+    ) -> Result<TxResponse, Error> {
+        self.rt
+            .block_on(self.inner.broadcast_tx_and_wait(tx_bytes, timeout))
+    }
+}
@@ -1,3 +1,8 @@
-pub mod blocking {}
+//! Long-lived handles on a Namada node. Unlike the functions of the `reading`
+//! module, a [`blocking::LightClient`] or [`unblocking::LightClient`] keeps a
+//! single connection (and, for the blocking flavour, a single runtime) open
+//! across calls, and can also broadcast the transactions built with the
+//! `transaction` module.
 
-pub mod unblocking {}
+pub mod blocking;
+pub mod unblocking;
@@ -0,0 +1,522 @@
+//! An asynchronous handle on a Namada node, to be driven by the caller's own
+//! async runtime.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use namada_sdk::account::Account;
+use namada_sdk::control_flow::time;
+use namada_sdk::error::{Error, QueryError, TxSubmitError};
+use namada_sdk::events::Event;
+use namada_sdk::governance::parameters::GovernanceParameters;
+use namada_sdk::governance::storage::proposal::StorageProposal;
+use namada_sdk::governance::utils::Vote;
+use namada_sdk::proof_of_stake::types::{
+    BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
+};
+use namada_sdk::proof_of_stake::PosParams;
+use namada_sdk::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
+use namada_sdk::queries::{Client, RPC};
+use namada_sdk::rpc::{self, TxEventQuery, TxResponse};
+use namada_sdk::state::LastBlock;
+use namada_sdk::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use namada_sdk::tx::data::ResultCode;
+use namada_sdk::tx::Tx;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::key::common;
+use namada_sdk::types::storage::{BlockHeight, BlockResults, Epoch};
+use namada_sdk::types::token;
+use tendermint_config::net::Address as TendermintAddress;
+use tendermint_rpc::{Error as RpcError, HttpClient};
+
+/// The default amount of time to wait for a broadcasted transaction to be
+/// applied
+pub const DEFAULT_TX_WAIT_TIME: time::Duration = time::Duration::from_secs(60);
+
+/// A handle on a Namada node which reuses the same connection for all the
+/// queries and broadcasts
+#[derive(Clone)]
+pub struct LightClient {
+    client: HttpClient,
+}
+
+impl LightClient {
+    /// Connect to the Namada node at the given Tendermint RPC address
+    pub fn new(tendermint_addr: &str) -> Result<Self, Error> {
+        let addr = tendermint_addr
+            .parse::<TendermintAddress>()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let client =
+            HttpClient::new(addr).map_err(|e| Error::Other(e.to_string()))?;
+        Ok(Self { client })
+    }
+
+    /// Get the underlying RPC client
+    pub fn client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    /// Query the address of the native token
+    pub async fn query_native_token(&self) -> Result<Address, Error> {
+        rpc::query_native_token(&self.client).await
+    }
+
+    /// Query the last committed block, if any.
+    pub async fn query_block(&self) -> Result<Option<LastBlock>, Error> {
+        rpc::query_block(&self.client).await
+    }
+
+    /// Query the results of the last committed block
+    pub async fn query_results(&self) -> Result<Vec<BlockResults>, Error> {
+        rpc::query_results(&self.client).await
+    }
+
+    /// Query token amount of owner.
+    pub async fn get_token_balance(
+        &self,
+        token: &Address,
+        owner: &Address,
+    ) -> Result<token::Amount, Error> {
+        rpc::get_token_balance(&self.client, token, owner).await
+    }
+
+    /// Check if the address exists on chain. Established address exists if it
+    /// has a stored validity predicate. Implicit and internal addresses
+    /// always return true.
+    pub async fn known_address(
+        &self,
+        address: &Address,
+    ) -> Result<bool, Error> {
+        rpc::known_address(&self.client, address).await
+    }
+
+    /// Query the account substorage space of an address
+    pub async fn get_account_info(
+        &self,
+        owner: &Address,
+    ) -> Result<Option<Account>, Error> {
+        rpc::get_account_info(&self.client, owner).await
+    }
+
+    /// Query if the public_key is revealed
+    pub async fn is_public_key_revealed(
+        &self,
+        owner: &Address,
+    ) -> Result<bool, Error> {
+        rpc::is_public_key_revealed(&self.client, owner).await
+    }
+
+    /// Query an account substorage at a specific index
+    pub async fn get_public_key_at(
+        &self,
+        owner: &Address,
+        index: u8,
+    ) -> Result<Option<common::PublicKey>, Error> {
+        rpc::get_public_key_at(&self.client, owner, index).await
+    }
+
+    /// Query proposal by Id
+    pub async fn query_proposal_by_id(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<StorageProposal>, Error> {
+        rpc::query_proposal_by_id(&self.client, proposal_id).await
+    }
+
+    /// Get the governance parameters
+    pub async fn query_governance_parameters(
+        &self,
+    ) -> Result<GovernanceParameters, Error> {
+        RPC.vp()
+            .gov()
+            .parameters(&self.client)
+            .await
+            .map_err(|err| Error::from(QueryError::NoResponse(err.to_string())))
+    }
+
+    /// Get the votes of a proposal
+    pub async fn query_proposal_votes(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Vec<Vote>, Error> {
+        rpc::query_proposal_votes(&self.client, proposal_id).await
+    }
+
+    /// Check if the given address is a pgf steward.
+    pub async fn is_steward(&self, address: &Address) -> Result<bool, Error> {
+        RPC.vp()
+            .pgf()
+            .is_steward(&self.client, address)
+            .await
+            .map_err(|err| Error::from(QueryError::NoResponse(err.to_string())))
+    }
+
+    /// Query the epoch of the last committed block
+    pub async fn query_epoch(&self) -> Result<Epoch, Error> {
+        rpc::query_epoch(&self.client).await
+    }
+
+    /// Query the epoch of the given block height, if it exists.
+    /// Will return none if the input block height is greater than
+    /// the latest committed block height.
+    pub async fn query_epoch_at_height(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<Epoch>, Error> {
+        rpc::query_epoch_at_height(&self.client, height).await
+    }
+
+    /// Check if the given address is a known validator.
+    pub async fn is_validator(&self, address: &Address) -> Result<bool, Error> {
+        rpc::is_validator(&self.client, address).await
+    }
+
+    /// Check if a given address is a known delegator
+    pub async fn is_delegator(&self, address: &Address) -> Result<bool, Error> {
+        rpc::is_delegator(&self.client, address).await
+    }
+
+    /// Check if a given address is a known delegator at the given epoch
+    pub async fn is_delegator_at(
+        &self,
+        address: &Address,
+        epoch: Epoch,
+    ) -> Result<bool, Error> {
+        rpc::is_delegator_at(&self.client, address, epoch).await
+    }
+
+    /// Get the set of consensus keys registered in the network
+    pub async fn get_consensus_keys(
+        &self,
+    ) -> Result<BTreeSet<common::PublicKey>, Error> {
+        rpc::get_consensus_keys(&self.client).await
+    }
+
+    /// Get the PoS parameters
+    pub async fn get_pos_params(&self) -> Result<PosParams, Error> {
+        rpc::get_pos_params(&self.client).await
+    }
+
+    /// Get all validators in the given epoch
+    pub async fn get_all_validators(
+        &self,
+        epoch: Epoch,
+    ) -> Result<HashSet<Address>, Error> {
+        rpc::get_all_validators(&self.client, epoch).await
+    }
+
+    /// Get the total staked tokens in the given epoch
+    pub async fn get_total_staked_tokens(
+        &self,
+        epoch: Epoch,
+    ) -> Result<token::Amount, Error> {
+        rpc::get_total_staked_tokens(&self.client, epoch).await
+    }
+
+    /// Get the given validator's stake at the given epoch
+    pub async fn get_validator_stake(
+        &self,
+        epoch: Epoch,
+        validator: &Address,
+    ) -> Result<token::Amount, Error> {
+        rpc::get_validator_stake(&self.client, epoch, validator).await
+    }
+
+    /// Query and return a validator's state
+    pub async fn get_validator_state(
+        &self,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ValidatorState>, Error> {
+        rpc::get_validator_state(&self.client, validator, epoch).await
+    }
+
+    /// Get the delegator's delegation
+    pub async fn get_delegators_delegation(
+        &self,
+        address: &Address,
+    ) -> Result<HashSet<Address>, Error> {
+        rpc::get_delegators_delegation(&self.client, address).await
+    }
+
+    /// Get the delegator's delegation at some epoch
+    pub async fn get_delegators_delegation_at(
+        &self,
+        address: &Address,
+        epoch: Epoch,
+    ) -> Result<HashMap<Address, token::Amount>, Error> {
+        rpc::get_delegators_delegation_at(&self.client, address, epoch).await
+    }
+
+    /// Query and return validator's commission rate and max commission rate
+    /// change per epoch
+    pub async fn query_commission_rate(
+        &self,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<CommissionPair>, Error> {
+        rpc::query_commission_rate(&self.client, validator, epoch).await
+    }
+
+    /// Query and return validator's metadata, including the commission rate and
+    /// max commission rate change
+    pub async fn query_metadata(
+        &self,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<(Option<ValidatorMetaData>, Option<CommissionPair>), Error>
+    {
+        rpc::query_metadata(&self.client, validator, epoch).await
+    }
+
+    /// Query and return the incoming redelegation epoch for a given pair of
+    /// source validator and delegator, if there is any.
+    pub async fn query_incoming_redelegations(
+        &self,
+        src_validator: &Address,
+        delegator: &Address,
+    ) -> Result<Option<Epoch>, Error> {
+        rpc::query_incoming_redelegations(
+            &self.client,
+            src_validator,
+            delegator,
+        )
+        .await
+    }
+
+    /// Query a validator's bonds for a given epoch
+    pub async fn query_bond(
+        &self,
+        source: &Address,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<token::Amount, Error> {
+        rpc::query_bond(&self.client, source, validator, epoch).await
+    }
+
+    /// Query withdrawable tokens in a validator account for a given epoch
+    pub async fn query_withdrawable_tokens(
+        &self,
+        bond_source: &Address,
+        validator: &Address,
+        epoch: Option<Epoch>,
+    ) -> Result<token::Amount, Error> {
+        rpc::query_withdrawable_tokens(
+            &self.client,
+            bond_source,
+            validator,
+            epoch,
+        )
+        .await
+    }
+
+    /// Query all unbonds for a validator, applying slashes
+    pub async fn query_unbond_with_slashing(
+        &self,
+        source: &Address,
+        validator: &Address,
+    ) -> Result<HashMap<(Epoch, Epoch), token::Amount>, Error> {
+        rpc::query_unbond_with_slashing(&self.client, source, validator).await
+    }
+
+    /// Get the bond amount at the given epoch
+    pub async fn get_bond_amount_at(
+        &self,
+        delegator: &Address,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> Result<token::Amount, Error> {
+        rpc::get_bond_amount_at(&self.client, delegator, validator, epoch).await
+    }
+
+    /// Get bonds and unbonds with all details (slashes and rewards, if any)
+    /// grouped by their bond IDs.
+    pub async fn bonds_and_unbonds(
+        &self,
+        source: &Option<Address>,
+        validator: &Option<Address>,
+    ) -> Result<BondsAndUnbondsDetails, Error> {
+        rpc::bonds_and_unbonds(&self.client, source, validator).await
+    }
+
+    /// Get bonds and unbonds with all details (slashes and rewards, if any)
+    /// grouped by their bond IDs, enriched with extra information calculated
+    /// from the data.
+    pub async fn enriched_bonds_and_unbonds(
+        &self,
+        current_epoch: Epoch,
+        source: &Option<Address>,
+        validator: &Option<Address>,
+    ) -> Result<EnrichedBondsAndUnbondsDetails, Error> {
+        rpc::enriched_bonds_and_unbonds(
+            &self.client,
+            current_epoch,
+            source,
+            validator,
+        )
+        .await
+    }
+
+    /// Call the corresponding `tx_event_query` RPC method, to fetch
+    /// the current status of a transaction.
+    pub async fn query_tx_events(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<Event>, Error> {
+        let tx_event_query = TxEventQuery::Applied(tx_hash);
+        rpc::query_tx_events(&self.client, tx_event_query)
+            .await
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+
+    /// Dry run a transaction
+    pub async fn dry_run_tx(
+        &self,
+        tx_bytes: Vec<u8>,
+    ) -> Result<namada_sdk::tx::data::TxResult, Error> {
+        let (data, height, prove) = (Some(tx_bytes), None, false);
+        let result = RPC
+            .shell()
+            .dry_run_tx(&self.client, data, height, prove)
+            .await
+            .map_err(|err| {
+                Error::from(QueryError::NoResponse(err.to_string()))
+            })?
+            .data;
+        Ok(result)
+    }
+
+    /// Lookup the full response accompanying the specified transaction event
+    pub async fn query_tx_response(
+        &self,
+        tx_hash: &str,
+    ) -> Result<TxResponse, Error> {
+        let tx_query = TxEventQuery::Applied(tx_hash);
+        rpc::query_tx_response(&self.client, tx_query)
+            .await
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+
+    /// Query the status of a given transaction.
+    pub async fn query_tx_status(&self, tx_hash: &str) -> Result<Event, Error> {
+        self.query_tx_events(tx_hash)
+            .await?
+            .ok_or(Error::Tx(TxSubmitError::AppliedTimeout))
+    }
+
+    /// Broadcast the given encoded transaction, as produced by the
+    /// `transaction` module, without waiting for it to be included in a
+    /// block. Returns the response of the mempool check.
+    pub async fn broadcast_tx(
+        &self,
+        tx_bytes: Vec<u8>,
+    ) -> Result<Response, Error> {
+        let response = self
+            .client
+            .broadcast_tx_sync(tx_bytes)
+            .await
+            .map_err(|err| Error::from(TxSubmitError::TxBroadcast(err)))?;
+        if response.code == 0.into() {
+            Ok(response)
+        } else {
+            Err(Error::from(TxSubmitError::TxBroadcast(RpcError::server(
+                format!(
+                    "The transaction was rejected from the mempool with code \
+                     {}: {}",
+                    response.code.value(),
+                    response.log
+                ),
+            ))))
+        }
+    }
+
+    /// Broadcast the given encoded wrapper transaction and wait up to
+    /// `timeout` for it to be applied. The wrapper's response is returned if
+    /// it was rejected, otherwise the response of the inner transaction.
+    pub async fn broadcast_tx_and_wait(
+        &self,
+        tx_bytes: Vec<u8>,
+        timeout: time::Duration,
+    ) -> Result<TxResponse, Error> {
+        let tx = Tx::try_from(tx_bytes.as_ref())
+            .map_err(|e| Error::Other(e.to_string()))?;
+        // We use this to determine when the wrapper tx makes it on-chain
+        let wrapper_hash = tx.header_hash().to_string();
+        // We use this to determine when the decrypted inner tx makes it
+        // on-chain
+        let decrypted_hash = tx.raw_header_hash().to_string();
+
+        self.broadcast_tx(tx_bytes).await?;
+
+        let deadline = time::Instant::now() + timeout;
+        let wrapper_query = TxEventQuery::Accepted(wrapper_hash.as_str());
+        let wrapper_resp = TxResponse::from_event(
+            self.wait_tx_event(wrapper_query, deadline).await?,
+        );
+        if wrapper_resp.code != ResultCode::Ok {
+            return Ok(wrapper_resp);
+        }
+        let decrypted_query = TxEventQuery::Applied(decrypted_hash.as_str());
+        Ok(TxResponse::from_event(
+            self.wait_tx_event(decrypted_query, deadline).await?,
+        ))
+    }
+
+    /// Poll the node for the given transaction event until it is found or the
+    /// deadline is exceeded
+    async fn wait_tx_event(
+        &self,
+        query: TxEventQuery<'_>,
+        deadline: time::Instant,
+    ) -> Result<Event, Error> {
+        time::Sleep {
+            strategy: time::LinearBackoff {
+                delta: time::Duration::from_secs(1),
+            },
+        }
+        .timeout(deadline, || async {
+            match rpc::query_tx_events(&self.client, query).await {
+                Ok(Some(event)) => std::ops::ControlFlow::Break(event),
+                _ => std::ops::ControlFlow::Continue(()),
+            }
+        })
+        .await
+        .map_err(|_| match query {
+            TxEventQuery::Accepted(_) => {
+                Error::Tx(TxSubmitError::AcceptTimeout)
+            }
+            TxEventQuery::Applied(_) => {
+                Error::Tx(TxSubmitError::AppliedTimeout)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use namada_sdk::types::address;
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    /// Get the address of a node that can't be reached, from a port that was
+    /// free until its listener was dropped
+    fn unreachable_node() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        format!("tcp://{addr}")
+    }
+
+    /// Test that the queries fail gracefully when the node can't be reached
+    #[test]
+    fn test_queries_without_node() {
+        let rt = Runtime::new().unwrap();
+        let client = LightClient::new(&unreachable_node()).unwrap();
+        rt.block_on(async {
+            assert!(client.query_governance_parameters().await.is_err());
+            assert!(client.is_steward(&address::nam()).await.is_err());
+        });
+    }
+}
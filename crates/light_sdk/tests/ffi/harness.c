@@ -0,0 +1,165 @@
+/*
+ * Exercises the C bindings of the Namada light SDK.
+ *
+ * Build the library with `cargo build -p namada_light_sdk --features ffi`
+ * and run `make -C crates/light_sdk/tests/ffi`. The queries are only run
+ * when `NAMADA_LEDGER_ADDRESS` points to a running node, e.g.
+ * `http://127.0.0.1:26657`.
+ */
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "namada_light_sdk.h"
+
+#define GLOBAL_ARGS                                                        \
+  "\"chain_id\": \"namada-test.000000000000000\", "                        \
+  "\"code_hash\": "                                                        \
+  "\"0000000000000000000000000000000000000000000000000000000000000000\", " \
+  "\"expiration\": null"
+
+static const char *ALBERT = "tnam1q99c37u38grkdcc2qze0hz4zjjd8zr3yucd3mzgz";
+
+static void print_last_error(const char *context) {
+  NamadaBuffer err = namada_last_error();
+  fprintf(stderr, "%s: %.*s\n", context, (int)err.len, (const char *)err.data);
+  namada_buffer_free(err);
+}
+
+static NamadaStatus build(NamadaStatus (*ctor)(const uint8_t *, size_t,
+                                               NamadaTx **),
+                          const char *args, NamadaTx **tx) {
+  return ctor((const uint8_t *)args, strlen(args), tx);
+}
+
+/* Build, sign and encode a transaction with the given constructor */
+static void check_tx(const char *name,
+                     NamadaStatus (*ctor)(const uint8_t *, size_t, NamadaTx **),
+                     const char *args) {
+  NamadaTx *tx = NULL;
+  if (build(ctor, args, &tx) != NAMADA_STATUS_OK) {
+    print_last_error(name);
+    assert(0);
+  }
+
+  NamadaBuffer sign_bytes = {0};
+  assert(namada_tx_sign_bytes(tx, &sign_bytes) == NAMADA_STATUS_OK);
+  /* A Borsh encoded vector with a single 32 bytes hash */
+  assert(sign_bytes.len == 4 + 32);
+  assert(sign_bytes.data[0] == 1);
+  namada_buffer_free(sign_bytes);
+
+  NamadaBuffer unsigned_bytes = {0};
+  assert(namada_tx_to_bytes(tx, &unsigned_bytes) == NAMADA_STATUS_OK);
+  assert(unsigned_bytes.len > 0);
+
+  /* Borsh encoded ed25519 `common::PublicKey` from RFC 8032 test 1 */
+  uint8_t public_key[33] = {
+      0x00, 0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b,
+      0xfe, 0xd3, 0xc9, 0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda,
+      0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+  };
+  /* Borsh encoded ed25519 `common::Signature`, which is not checked here */
+  uint8_t signature[65] = {0};
+  assert(namada_tx_attach_signature(tx, public_key, sizeof(public_key),
+                                    signature, sizeof(signature)) ==
+         NAMADA_STATUS_OK);
+
+  NamadaBuffer signed_bytes = {0};
+  assert(namada_tx_to_bytes(tx, &signed_bytes) == NAMADA_STATUS_OK);
+  assert(signed_bytes.len > unsigned_bytes.len);
+  namada_buffer_free(unsigned_bytes);
+  namada_buffer_free(signed_bytes);
+
+  namada_tx_free(tx);
+  printf("%s: ok\n", name);
+}
+
+static void check_errors(void) {
+  NamadaTx *tx = NULL;
+  const char *invalid = "{\"source\": 42}";
+  assert(build(namada_tx_transfer_new, invalid, &tx) ==
+         NAMADA_STATUS_INVALID_ARGUMENT);
+  assert(tx == NULL);
+  NamadaBuffer err = namada_last_error();
+  assert(err.len > 0);
+  namada_buffer_free(err);
+
+  NamadaBuffer out = {0};
+  assert(namada_tx_to_bytes(NULL, &out) == NAMADA_STATUS_NULL_POINTER);
+  assert(namada_tx_transfer_new(NULL, 1, &tx) == NAMADA_STATUS_NULL_POINTER);
+  /* Releasing null handles is a no-op */
+  namada_tx_free(NULL);
+  namada_client_free(NULL);
+  printf("errors: ok\n");
+}
+
+static void check_queries(const char *ledger_address) {
+  NamadaClient *client = NULL;
+  if (namada_client_new(ledger_address, &client) != NAMADA_STATUS_OK) {
+    print_last_error("client");
+    assert(0);
+  }
+
+  const char *query = "{\"query\": \"query_epoch\"}";
+  NamadaBuffer epoch = {0};
+  if (namada_client_query(client, (const uint8_t *)query, strlen(query),
+                          &epoch) != NAMADA_STATUS_OK) {
+    print_last_error("query_epoch");
+    assert(0);
+  }
+  /* A Borsh encoded `u64` */
+  assert(epoch.len == 8);
+  namada_buffer_free(epoch);
+
+  const char *unknown = "{\"query\": \"no_such_query\"}";
+  NamadaBuffer out = {0};
+  assert(namada_client_query(client, (const uint8_t *)unknown,
+                             strlen(unknown),
+                             &out) == NAMADA_STATUS_INVALID_ARGUMENT);
+
+  namada_client_free(client);
+  printf("queries: ok\n");
+}
+
+int main(void) {
+  char args[1024];
+
+  snprintf(args, sizeof(args),
+           "{\"source\": \"%s\", \"target\": "
+           "\"tnam1q8g8780290hs6p6qtuqaknlc62akwgyn4cj48tkq\", "
+           "\"token\": \"tnam1q87teqzjytwa9xd9qk8u558xxnrwuzdjzs7zvhzr\", "
+           "\"amount\": \"10.5\", \"key\": null, " GLOBAL_ARGS "}",
+           ALBERT);
+  check_tx("transfer", namada_tx_transfer_new, args);
+
+  snprintf(args, sizeof(args),
+           "{\"validator\": \"%s\", \"amount\": \"100\", \"source\": null, "
+           GLOBAL_ARGS "}",
+           ALBERT);
+  check_tx("bond", namada_tx_bond_new, args);
+
+  snprintf(args, sizeof(args),
+           "{\"id\": 0, \"vote\": \"Yay\", \"voter\": \"%s\", "
+           "\"delegations\": [], " GLOBAL_ARGS "}",
+           ALBERT);
+  check_tx("vote_proposal", namada_tx_vote_proposal_new, args);
+
+  snprintf(args, sizeof(args),
+           "{\"public_key\": "
+           "\"tpknam1qrnw8mxyqlj60mykgevnldcj5mg2fya7fs5a8xqdkd2gwtxhef0zy8a2wha\", "
+           GLOBAL_ARGS "}");
+  check_tx("reveal_pk", namada_tx_reveal_pk_new, args);
+
+  check_errors();
+
+  const char *ledger_address = getenv("NAMADA_LEDGER_ADDRESS");
+  if (ledger_address != NULL) {
+    check_queries(ledger_address);
+  } else {
+    printf("queries: skipped, NAMADA_LEDGER_ADDRESS is not set\n");
+  }
+  return 0;
+}
@@ -13,7 +13,10 @@ use arse_merkle_tree::{
 };
 use eth_bridge_pool::{BridgePoolProof, BridgePoolTree};
 use ics23::commitment_proof::Proof as Ics23Proof;
-use ics23::{CommitmentProof, ExistenceProof, NonExistenceProof};
+use ics23::{
+    CommitmentProof, ExistenceProof, HostFunctionsManager, NonExistenceProof,
+    ProofSpec,
+};
 use ics23_specs::ibc_leaf_spec;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
 use namada_core::bytes::ByteBuf;
@@ -103,6 +106,8 @@ pub enum Error {
     Ics23MultiLeaf,
     #[error("A Tendermint proof can only be constructed from an ICS23 proof.")]
     TendermintProof,
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
 }
 
 /// Result for functions that may fail
@@ -776,6 +781,166 @@ pub struct Proof {
     pub base_proof: CommitmentProof,
 }
 
+impl Proof {
+    /// Verify that the proof commits the given value of its key to the
+    /// given root of the whole merkle tree.
+    pub fn verify_existence<H: StorageHasher>(
+        &self,
+        root: &MerkleRoot,
+        value: &[u8],
+    ) -> Result<()> {
+        let (store_type, sub_key) = StoreType::sub_key(&self.key)?;
+        let [sub_spec, base_spec] = Self::specs::<H>(&store_type)?;
+        let sub_root = match &self.sub_proof.proof {
+            Some(Ics23Proof::Exist(ep)) => existence_root(ep)?,
+            _ => {
+                return Err(Error::InvalidProof(
+                    "The sub-tree proof is not an existence proof".into(),
+                ));
+            }
+        };
+        if !ics23::verify_membership::<HostFunctionsManager>(
+            &self.sub_proof,
+            &sub_spec,
+            &sub_root,
+            sub_key.to_string().as_bytes(),
+            value,
+        ) {
+            return Err(Error::InvalidProof(format!(
+                "The value of {} is not committed to the {store_type} sub-tree",
+                self.key
+            )));
+        }
+        self.verify_sub_root(root, &store_type, &base_spec, sub_root)
+    }
+
+    /// Verify that the proof commits the absence of its key to the given
+    /// root of the whole merkle tree.
+    pub fn verify_non_existence<H: StorageHasher>(
+        &self,
+        root: &MerkleRoot,
+    ) -> Result<()> {
+        let (store_type, sub_key) = StoreType::sub_key(&self.key)?;
+        let [sub_spec, base_spec] = Self::specs::<H>(&store_type)?;
+        let sub_root = match &self.sub_proof.proof {
+            Some(Ics23Proof::Nonexist(nep)) => {
+                match nep.left.as_ref().or(nep.right.as_ref()) {
+                    Some(ep) => existence_root(ep)?,
+                    None => {
+                        return Err(Error::InvalidProof(
+                            "The non-existence proof has no neighbours".into(),
+                        ));
+                    }
+                }
+            }
+            _ => {
+                return Err(Error::InvalidProof(
+                    "The sub-tree proof is not a non-existence proof".into(),
+                ));
+            }
+        };
+        if !ics23::verify_non_membership::<HostFunctionsManager>(
+            &self.sub_proof,
+            &sub_spec,
+            &sub_root,
+            sub_key.to_string().as_bytes(),
+        ) {
+            return Err(Error::InvalidProof(format!(
+                "The absence of {} is not committed to the {store_type} \
+                 sub-tree",
+                self.key
+            )));
+        }
+        self.verify_sub_root(root, &store_type, &base_spec, sub_root)
+    }
+
+    /// Verify that the base proof commits the given sub-tree root to the
+    /// root of the whole tree
+    fn verify_sub_root(
+        &self,
+        root: &MerkleRoot,
+        store_type: &StoreType,
+        base_spec: &ProofSpec,
+        sub_root: Vec<u8>,
+    ) -> Result<()> {
+        if ics23::verify_membership::<HostFunctionsManager>(
+            &self.base_proof,
+            base_spec,
+            &root.0.to_vec(),
+            store_type.to_string().as_bytes(),
+            &sub_root,
+        ) {
+            Ok(())
+        } else {
+            Err(Error::InvalidProof(format!(
+                "The root of the {store_type} sub-tree is not committed to \
+                 the merkle root {root}"
+            )))
+        }
+    }
+
+    /// Get the proof specs of the sub-tree and of the base tree for the
+    /// given store type
+    fn specs<H: StorageHasher>(
+        store_type: &StoreType,
+    ) -> Result<[ProofSpec; 2]> {
+        let specs = match store_type {
+            StoreType::Ibc => ics23_specs::ibc_proof_specs::<H>(),
+            StoreType::Account | StoreType::PoS => {
+                ics23_specs::proof_specs::<H>()
+            }
+            StoreType::Base | StoreType::BridgePool => {
+                return Err(Error::InvalidProof(format!(
+                    "No ICS23 proofs for the {store_type} store"
+                )));
+            }
+        };
+        specs.try_into().map_err(|_| {
+            Error::InvalidProof("Expected a sub-tree and a base spec".into())
+        })
+    }
+}
+
+/// Compute the root committed to by an existence proof
+fn existence_root(ep: &ExistenceProof) -> Result<Vec<u8>> {
+    ics23::calculate_existence_root::<HostFunctionsManager>(ep)
+        .map_err(|err| Error::InvalidProof(err.to_string()))
+}
+
+impl TryFrom<&namada_core::tendermint::merkle::proof::ProofOps> for Proof {
+    type Error = Error;
+
+    fn try_from(
+        proof_ops: &namada_core::tendermint::merkle::proof::ProofOps,
+    ) -> Result<Self> {
+        use prost::Message;
+
+        let [sub_proof_op, base_proof_op] = proof_ops.ops.as_slice() else {
+            return Err(Error::InvalidProof(format!(
+                "Expected a sub-tree and a base proof, got {} proof ops",
+                proof_ops.ops.len()
+            )));
+        };
+        if sub_proof_op.key != base_proof_op.key {
+            return Err(Error::InvalidProof(
+                "The proof ops are for different keys".into(),
+            ));
+        }
+        let key = String::from_utf8(sub_proof_op.key.clone())
+            .map_err(|err| Error::InvalidProof(err.to_string()))?;
+        let key = Key::parse(key).map_err(Error::InvalidKey)?;
+        let decode = |op: &namada_core::tendermint::merkle::proof::ProofOp| {
+            CommitmentProof::decode(op.data.as_slice())
+                .map_err(|err| Error::InvalidProof(err.to_string()))
+        };
+        Ok(Self {
+            key,
+            sub_proof: decode(sub_proof_op)?,
+            base_proof: decode(base_proof_op)?,
+        })
+    }
+}
+
 impl From<Proof> for namada_core::tendermint::merkle::proof::ProofOps {
     fn from(
         Proof {
@@ -1003,7 +1168,6 @@ impl<'a> SubTreeWrite for &'a mut BridgePoolTree {
 
 #[cfg(test)]
 mod test {
-    use ics23::HostFunctionsManager;
     use namada_core::types::hash::Sha256Hasher;
     use namada_core::types::storage::KeySeg;
 
@@ -1313,4 +1477,65 @@ mod test {
             );
         assert!(basetree_verification_res);
     }
+
+    #[test]
+    fn test_verify_proof_ops() {
+        use namada_core::tendermint::merkle::proof::ProofOps;
+
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Ibc).to_db_key().into();
+        let ibc_key = key_prefix.push(&"test".to_string()).unwrap();
+        let ibc_non_key = key_prefix.push(&"test2".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::PoS).to_db_key().into();
+        let pos_key = key_prefix.push(&"test".to_string()).unwrap();
+
+        let ibc_val = [1u8; 8].to_vec();
+        tree.update(&ibc_key, ibc_val.clone()).unwrap();
+        let pos_val = [2u8; 8].to_vec();
+        tree.update(&pos_key, pos_val.clone()).unwrap();
+        let root = tree.root();
+
+        for (key, val) in [(&ibc_key, &ibc_val), (&pos_key, &pos_val)] {
+            let proof = match tree
+                .get_sub_tree_existence_proof(
+                    std::array::from_ref(key),
+                    vec![val.as_slice()],
+                )
+                .unwrap()
+            {
+                MembershipProof::ICS23(proof) => proof,
+                _ => panic!("Test failed"),
+            };
+            let proof_ops: ProofOps =
+                tree.get_sub_tree_proof(key, proof).unwrap().into();
+            let proof = Proof::try_from(&proof_ops).unwrap();
+            assert_eq!(&proof.key, key);
+            proof.verify_existence::<Sha256Hasher>(&root, val).unwrap();
+            // A different value or root must be rejected
+            assert!(proof
+                .verify_existence::<Sha256Hasher>(&root, &[3u8; 8])
+                .is_err());
+            assert!(proof
+                .verify_existence::<Sha256Hasher>(&MerkleRoot([0; 32]), val)
+                .is_err());
+            assert!(proof.verify_non_existence::<Sha256Hasher>(&root).is_err());
+        }
+
+        let proof_ops: ProofOps =
+            tree.get_non_existence_proof(&ibc_non_key).unwrap().into();
+        let proof = Proof::try_from(&proof_ops).unwrap();
+        proof.verify_non_existence::<Sha256Hasher>(&root).unwrap();
+        assert!(proof
+            .verify_non_existence::<Sha256Hasher>(&MerkleRoot([0; 32]))
+            .is_err());
+
+        // Proof ops of a single sub-tree are not a complete proof
+        let truncated = ProofOps {
+            ops: proof_ops.ops[..1].to_vec(),
+        };
+        assert!(Proof::try_from(&truncated).is_err());
+    }
 }
@@ -28,8 +28,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// The governance internal address
 pub const ADDRESS: Address = Address::Internal(InternalAddress::Governance);
 
-/// The maximum number of item in a pgf proposal
-pub const MAX_PGF_ACTIONS: usize = 20;
+pub use namada_governance::pgf::MAX_PGF_ACTIONS;
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
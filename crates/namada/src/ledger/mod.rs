@@ -30,7 +30,8 @@ mod dry_run_tx {
     use crate::vm::wasm::{TxCache, VpCache};
     use crate::vm::WasmCacheAccess;
 
-    /// Dry run a transaction
+    /// Dry run a transaction. The execution is traced if the request was made
+    /// on the `dry_run_tx_trace` path.
     pub fn dry_run_tx<D, H, CA>(
         mut ctx: RequestCtx<'_, D, H, VpCache<CA>, TxCache<CA>>,
         request: &RequestQuery,
@@ -41,7 +42,7 @@ mod dry_run_tx {
         CA: 'static + WasmCacheAccess + Sync,
     {
         use borsh_ext::BorshSerializeExt;
-        use namada_gas::{Gas, GasMetering, TxGasMeter};
+        use namada_gas::{Gas, GasMetering, GasProfile, TxGasMeter};
         use namada_state::TempWlStorage;
         use namada_tx::data::{DecryptedTx, TxType};
         use namada_tx::Tx;
@@ -51,15 +52,18 @@ mod dry_run_tx {
 
         let mut tx = Tx::try_from(&request.data[..]).into_storage_result()?;
         tx.validate_tx().into_storage_result()?;
+        let trace = request.path == "/shell/dry_run_tx_trace";
 
         let mut temp_wl_storage = TempWlStorage::new(&ctx.wl_storage.storage);
         let mut cumulated_gas = Gas::default();
+        let mut gas_profile = GasProfile::default();
 
         // Wrapper dry run to allow estimating the gas cost of a transaction
         let mut tx_gas_meter = match tx.header().tx_type {
             TxType::Wrapper(wrapper) => {
                 let mut tx_gas_meter =
                     TxGasMeter::new(wrapper.gas_limit.to_owned());
+                tx_gas_meter.enable_profiling();
                 protocol::apply_wrapper_tx(
                     tx.clone(),
                     &wrapper,
@@ -77,6 +81,7 @@ mod dry_run_tx {
 
                 temp_wl_storage.write_log.commit_tx();
                 cumulated_gas = tx_gas_meter.get_tx_consumed_gas();
+                gas_profile = tx_gas_meter.take_profile().unwrap_or_default();
 
                 tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
                 TxGasMeter::new_from_sub_limit(tx_gas_meter.get_available_gas())
@@ -101,6 +106,7 @@ mod dry_run_tx {
                 ))
             }
         };
+        tx_gas_meter.enable_profiling();
 
         let mut data = protocol::apply_wasm_tx(
             tx,
@@ -111,6 +117,7 @@ mod dry_run_tx {
                 &mut ctx.vp_wasm_cache,
                 &mut ctx.tx_wasm_cache,
             ),
+            trace,
         )
         .into_storage_result()?;
         cumulated_gas = cumulated_gas
@@ -118,8 +125,12 @@ mod dry_run_tx {
             .ok_or(namada_state::StorageError::SimpleMessage(
                 "Overflow in gas",
             ))?;
+        if let Some(profile) = tx_gas_meter.take_profile() {
+            gas_profile.merge(profile).into_storage_result()?;
+        }
         // Account gas for both inner and wrapper (if available)
         data.gas_used = cumulated_gas;
+        data.gas_profile = Some(gas_profile);
         // NOTE: the keys changed by the wrapper transaction (if any) are not
         // returned from this function
         let data = data.serialize_to_vec();
@@ -250,7 +261,10 @@ mod test {
             };
             // TODO: this is a hack to propagate errors to the caller, we should
             // really permit error types other than [`std::io::Error`]
-            if request.path == "/shell/dry_run_tx" {
+            if matches!(
+                request.path.as_str(),
+                "/shell/dry_run_tx" | "/shell/dry_run_tx_trace"
+            ) {
                 super::dry_run_tx(ctx, &request)
             } else {
                 self.rpc.handle(ctx, &request)
@@ -299,10 +313,26 @@ mod test {
         let tx_bytes = outer_tx.to_bytes();
         let result = RPC
             .shell()
-            .dry_run_tx(&client, Some(tx_bytes), None, false)
+            .dry_run_tx(&client, Some(tx_bytes.clone()), None, false)
+            .await
+            .unwrap();
+        assert!(result.data.is_accepted());
+        assert!(result.data.trace.is_none());
+        let gas_profile = result.data.gas_profile.expect("missing gas profile");
+        assert!(
+            gas_profile
+                .categories
+                .contains_key(&namada_gas::GasCategory::Compilation)
+        );
+
+        // Request traced dry run tx
+        let result = RPC
+            .shell()
+            .dry_run_tx_trace(&client, Some(tx_bytes), None, false)
             .await
             .unwrap();
         assert!(result.data.is_accepted());
+        assert!(result.data.trace.is_some());
 
         // Request storage value for a balance key ...
         let token_addr = address::testing::established_address_1();
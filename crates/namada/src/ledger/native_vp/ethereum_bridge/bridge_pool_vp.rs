@@ -20,20 +20,26 @@ use eyre::eyre;
 use namada_core::hints;
 use namada_core::types::eth_bridge_pool::erc20_token_address;
 use namada_ethereum_bridge::storage::bridge_pool::{
-    get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+    get_cancelled_key, get_gas_fee_bump_key, get_key_from_hash,
+    get_pending_key, is_bridge_pool_key, is_transfer_update_key,
+    BRIDGE_POOL_ADDRESS,
 };
 use namada_ethereum_bridge::storage::parameters::read_native_erc20_address;
 use namada_ethereum_bridge::storage::whitelist;
 use namada_ethereum_bridge::ADDRESS as BRIDGE_ADDRESS;
+use namada_gas::{verify_tx_sig_gas, GasCategory, GasMetering};
 use namada_state::{DBIter, StorageHasher, DB};
 use namada_tx::Tx;
 
 use crate::ledger::native_vp::{Ctx, NativeVp, StorageReader};
-use crate::token::storage_key::balance_key;
+use crate::token::storage_key::{balance_key, is_any_token_balance_key};
 use crate::token::Amount;
 use crate::types::address::{Address, InternalAddress};
-use crate::types::eth_bridge_pool::{PendingTransfer, TransferToEthereumKind};
+use crate::types::eth_bridge_pool::{
+    PendingTransfer, PendingTransferUpdate, TransferToEthereumKind,
+};
 use crate::types::ethereum_events::EthAddress;
+use crate::types::keccak::KeccakHash;
 use crate::types::storage::Key;
 use crate::vm::WasmCacheAccess;
 
@@ -401,6 +407,243 @@ where
             },
         })
     }
+
+    /// Validate an update of a transfer which is pending in the pool.
+    fn validate_update(
+        &self,
+        tx: &Tx,
+        update: &PendingTransferUpdate,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let pending_key = get_key_from_hash(update.transfer_hash());
+        let Some(transfer) =
+            (&self.ctx).read_pre_value::<PendingTransfer>(&pending_key)?
+        else {
+            tracing::debug!(
+                ?update,
+                "Rejecting transaction as the updated transfer is not in the \
+                 Ethereum bridge pool."
+            );
+            return Ok(false);
+        };
+        match update {
+            PendingTransferUpdate::Cancel { transfer_hash } => {
+                self.validate_cancel(tx, transfer_hash, &transfer, keys_changed)
+            }
+            PendingTransferUpdate::BumpGasFee {
+                transfer_hash,
+                amount,
+            } => self.validate_gas_fee_bump(
+                transfer_hash,
+                &transfer,
+                amount,
+                keys_changed,
+            ),
+        }
+    }
+
+    /// Validate the cancellation of a pending transfer. The transfer
+    /// must be moved from the pool to the cancelled transfers, and
+    /// the cancellation must be signed by the sender of the transfer.
+    fn validate_cancel(
+        &self,
+        tx: &Tx,
+        transfer_hash: &KeccakHash,
+        transfer: &PendingTransfer,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let pending_key = get_key_from_hash(transfer_hash);
+        let cancelled_key = get_cancelled_key(transfer_hash);
+        if !self.check_bridge_pool_keys_changed(
+            keys_changed,
+            &[&pending_key, &cancelled_key],
+        ) {
+            return Ok(false);
+        }
+        if self.escrow_balance_changed(keys_changed, |_| true) {
+            tracing::debug!(
+                ?transfer,
+                "Escrowed assets can't be moved when cancelling a transfer."
+            );
+            return Ok(false);
+        }
+        if (&self.ctx)
+            .read_post_value::<PendingTransfer>(&pending_key)?
+            .is_some()
+        {
+            tracing::debug!(
+                ?transfer,
+                "The cancelled transfer wasn't removed from the pool of \
+                 pending transfers."
+            );
+            return Ok(false);
+        }
+        let cancelled: Option<PendingTransfer> =
+            (&self.ctx).read_post_value(&cancelled_key)?;
+        if cancelled.as_ref() != Some(transfer) {
+            tracing::debug!(
+                ?transfer,
+                ?cancelled,
+                "An incorrect transfer was marked as cancelled."
+            );
+            return Ok(false);
+        }
+        self.check_signed_by_sender(tx, transfer)
+    }
+
+    /// Validate an increase of the gas fees of a pending transfer.
+    /// The additional fees must be escrowed from the original payer,
+    /// in the original gas token.
+    fn validate_gas_fee_bump(
+        &self,
+        transfer_hash: &KeccakHash,
+        transfer: &PendingTransfer,
+        amount: &Amount,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        if amount.is_zero() {
+            tracing::debug!(?transfer, "The gas fee bump must not be zero.");
+            return Ok(false);
+        }
+        let pending_key = get_key_from_hash(transfer_hash);
+        let bump_key = get_gas_fee_bump_key(transfer_hash);
+        if !self.check_bridge_pool_keys_changed(keys_changed, &[&bump_key]) {
+            return Ok(false);
+        }
+        if self.escrow_balance_changed(keys_changed, |token| {
+            token != &transfer.gas_fee.token
+        }) {
+            tracing::debug!(
+                ?transfer,
+                "Only the gas token can be escrowed when bumping the gas fees \
+                 of a transfer."
+            );
+            return Ok(false);
+        }
+        let pending: Option<PendingTransfer> =
+            (&self.ctx).read_post_value(&pending_key)?;
+        if pending.as_ref() != Some(transfer) {
+            tracing::debug!(
+                ?transfer,
+                "The transfer must remain unchanged in the Ethereum bridge \
+                 pool."
+            );
+            return Ok(false);
+        }
+        let pre_bump: Amount =
+            (&self.ctx).read_pre_value(&bump_key)?.unwrap_or_default();
+        let post_bump: Amount =
+            (&self.ctx).read_post_value(&bump_key)?.unwrap_or_default();
+        if pre_bump.checked_add(*amount) != Some(post_bump) {
+            tracing::debug!(
+                ?transfer,
+                pre_bump = %pre_bump.to_string_native(),
+                post_bump = %post_bump.to_string_native(),
+                bump = %amount.to_string_native(),
+                "The gas fee bump of the transfer was incorrectly updated."
+            );
+            return Ok(false);
+        }
+        let wnam_address = read_native_erc20_address(&self.ctx.pre())?;
+        let gas_check = EscrowDelta {
+            token: Cow::Borrowed(&transfer.gas_fee.token),
+            payer_account: &transfer.gas_fee.payer,
+            escrow_account: &BRIDGE_POOL_ADDRESS,
+            expected_debit: *amount,
+            expected_credit: *amount,
+            transferred_amount: amount,
+            _kind: PhantomData,
+        };
+        if !gas_check.validate(keys_changed) {
+            tracing::debug!(
+                ?transfer,
+                "Missing storage modifications in the Bridge pool"
+            );
+            return Ok(false);
+        }
+        self.check_gas_escrow(&wnam_address, transfer, gas_check)
+    }
+
+    /// Check that the only keys of the Bridge pool that were
+    /// modified are the `expected` ones.
+    fn check_bridge_pool_keys_changed(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+        expected: &[&Key],
+    ) -> bool {
+        for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
+            if !expected.contains(&key) {
+                tracing::debug!(
+                    "Rejecting transaction as it is attempting to change an \
+                     incorrect key in the Ethereum bridge pool: {}.\n \
+                     Expected keys: {:?}",
+                    key,
+                    expected
+                );
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Check if the balance of the Bridge pool escrow was modified,
+    /// for any token that matches the given predicate.
+    fn escrow_balance_changed<F>(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+        mut is_token: F,
+    ) -> bool
+    where
+        F: FnMut(&Address) -> bool,
+    {
+        keys_changed.iter().any(|key| {
+            matches!(
+                is_any_token_balance_key(key),
+                Some([token, owner])
+                    if owner == &BRIDGE_POOL_ADDRESS && is_token(token)
+            )
+        })
+    }
+
+    /// Check that the tx was signed by the sender of a pending transfer.
+    fn check_signed_by_sender(
+        &self,
+        tx: &Tx,
+        transfer: &PendingTransfer,
+    ) -> Result<bool, Error> {
+        let sender = &transfer.transfer.sender;
+        let max_signatures =
+            namada_parameters::max_signatures_per_transaction(&self.ctx.pre())
+                .map_err(|e| Error(e.into()))?;
+        let public_keys_index_map =
+            namada_account::public_keys_index_map(&self.ctx.pre(), sender)
+                .map_err(|e| Error(e.into()))?;
+        let threshold = namada_account::threshold(&self.ctx.pre(), sender)
+            .map_err(|e| Error(e.into()))?
+            .unwrap_or(1);
+        let verified = tx.verify_signatures(
+            &[tx.raw_header_hash()],
+            public_keys_index_map,
+            &Some(sender.clone()),
+            threshold,
+            max_signatures,
+            |pk| {
+                self.ctx.gas_meter.borrow_mut().consume_as(
+                    GasCategory::SignatureVerification,
+                    verify_tx_sig_gas(pk),
+                )
+            },
+        );
+        if let Err(error) = verified {
+            tracing::debug!(
+                ?transfer,
+                %error,
+                "The update of the transfer isn't authorized by its sender."
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
 }
 
 /// Helper struct for handling the different escrow
@@ -540,6 +783,14 @@ where
         let Some(tx_data) = tx.data() else {
             return Err(eyre!("No transaction data found").into());
         };
+        // NB: a transfer update always changes its gas fee bump or
+        // cancellation key, which adding a transfer never does
+        if keys_changed.iter().any(is_transfer_update_key) {
+            let update: PendingTransferUpdate =
+                BorshDeserialize::try_from_slice(&tx_data[..])
+                    .map_err(|e| Error(e.into()))?;
+            return self.validate_update(tx, &update, keys_changed);
+        }
         let transfer: PendingTransfer =
             BorshDeserialize::try_from_slice(&tx_data[..])
                 .map_err(|e| Error(e.into()))?;
@@ -1941,4 +2192,199 @@ mod test_bridge_pool_vp {
 
         assert!(!delta.validate(&some_changed_keys));
     }
+
+    /// Helper function that checks an update of the transfer
+    /// initially in the pool against the Bridge pool VP.
+    fn assert_transfer_update<F>(
+        update: PendingTransferUpdate,
+        apply_update: F,
+        expect: Expect,
+    ) where
+        F: FnOnce(&PendingTransfer, &mut WriteLog) -> BTreeSet<Key>,
+    {
+        let mut wl_storage = setup_storage();
+        let tx = Tx::from_type(TxType::Raw);
+        let keys_changed =
+            apply_update(&initial_pool(), &mut wl_storage.write_log);
+        let verifiers = BTreeSet::default();
+        let vp = BridgePoolVp {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        tx.add_data(update);
+
+        let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+        match expect {
+            Expect::True => assert!(res.expect("Test failed")),
+            Expect::False => assert!(!res.expect("Test failed")),
+            Expect::Error => assert!(res.is_err()),
+        }
+    }
+
+    /// Escrow a gas fee bump of the transfer initially in the pool.
+    fn escrow_gas_fee_bump(
+        write_log: &mut WriteLog,
+        amount: Amount,
+    ) -> BTreeSet<Key> {
+        let payer_key = balance_key(&nam(), &bertha_address());
+        let escrow_key = balance_key(&nam(), &BRIDGE_POOL_ADDRESS);
+        write_log
+            .write(
+                &payer_key,
+                (Amount::from(BERTHA_WEALTH) - amount).serialize_to_vec(),
+            )
+            .expect("Test failed");
+        write_log
+            .write(
+                &escrow_key,
+                (Amount::from(ESCROWED_AMOUNT) + amount).serialize_to_vec(),
+            )
+            .expect("Test failed");
+        [payer_key, escrow_key].into()
+    }
+
+    /// Test that escrowing a gas fee bump of a pending
+    /// transfer is accepted by the VP.
+    #[test]
+    fn test_bump_gas_fee_happy_flow() {
+        let transfer_hash = initial_pool().keccak256();
+        assert_transfer_update(
+            PendingTransferUpdate::BumpGasFee {
+                transfer_hash: transfer_hash.clone(),
+                amount: GAS_FEE.into(),
+            },
+            |_, write_log| {
+                let bump_key = get_gas_fee_bump_key(&transfer_hash);
+                write_log
+                    .write(&bump_key, Amount::from(GAS_FEE).serialize_to_vec())
+                    .expect("Test failed");
+                let mut keys_changed =
+                    escrow_gas_fee_bump(write_log, GAS_FEE.into());
+                keys_changed.insert(bump_key);
+                keys_changed
+            },
+            Expect::True,
+        );
+    }
+
+    /// Test that a gas fee bump which is not escrowed
+    /// under the Bridge pool is rejected.
+    #[test]
+    fn test_bump_gas_fee_not_escrowed() {
+        let transfer_hash = initial_pool().keccak256();
+        assert_transfer_update(
+            PendingTransferUpdate::BumpGasFee {
+                transfer_hash: transfer_hash.clone(),
+                amount: GAS_FEE.into(),
+            },
+            |_, write_log| {
+                let bump_key = get_gas_fee_bump_key(&transfer_hash);
+                write_log
+                    .write(&bump_key, Amount::from(GAS_FEE).serialize_to_vec())
+                    .expect("Test failed");
+                [bump_key].into()
+            },
+            Expect::False,
+        );
+    }
+
+    /// Test that a gas fee bump which doesn't match the
+    /// escrowed amount is rejected.
+    #[test]
+    fn test_bump_gas_fee_wrong_amount() {
+        let transfer_hash = initial_pool().keccak256();
+        assert_transfer_update(
+            PendingTransferUpdate::BumpGasFee {
+                transfer_hash: transfer_hash.clone(),
+                amount: GAS_FEE.into(),
+            },
+            |_, write_log| {
+                let bump_key = get_gas_fee_bump_key(&transfer_hash);
+                write_log
+                    .write(
+                        &bump_key,
+                        Amount::from(2 * GAS_FEE).serialize_to_vec(),
+                    )
+                    .expect("Test failed");
+                let mut keys_changed =
+                    escrow_gas_fee_bump(write_log, GAS_FEE.into());
+                keys_changed.insert(bump_key);
+                keys_changed
+            },
+            Expect::False,
+        );
+    }
+
+    /// Test that updating a transfer which is not in the
+    /// pool is rejected.
+    #[test]
+    fn test_update_unknown_transfer() {
+        let transfer_hash = KeccakHash([1; 32]);
+        assert_transfer_update(
+            PendingTransferUpdate::BumpGasFee {
+                transfer_hash: transfer_hash.clone(),
+                amount: GAS_FEE.into(),
+            },
+            |_, write_log| {
+                let bump_key = get_gas_fee_bump_key(&transfer_hash);
+                write_log
+                    .write(&bump_key, Amount::from(GAS_FEE).serialize_to_vec())
+                    .expect("Test failed");
+                let mut keys_changed =
+                    escrow_gas_fee_bump(write_log, GAS_FEE.into());
+                keys_changed.insert(bump_key);
+                keys_changed
+            },
+            Expect::False,
+        );
+    }
+
+    /// Test that cancelling a transfer without the
+    /// signature of its sender is rejected.
+    #[test]
+    fn test_cancel_not_signed_by_sender() {
+        let transfer_hash = initial_pool().keccak256();
+        assert_transfer_update(
+            PendingTransferUpdate::Cancel {
+                transfer_hash: transfer_hash.clone(),
+            },
+            |transfer, write_log| {
+                let pending_key = get_key_from_hash(&transfer_hash);
+                let cancelled_key = get_cancelled_key(&transfer_hash);
+                write_log.delete(&pending_key).expect("Test failed");
+                write_log
+                    .write(&cancelled_key, transfer.serialize_to_vec())
+                    .expect("Test failed");
+                [pending_key, cancelled_key].into()
+            },
+            Expect::False,
+        );
+    }
+
+    /// Test that cancelling a transfer without removing
+    /// it from the pool is rejected.
+    #[test]
+    fn test_cancel_transfer_left_in_pool() {
+        let transfer_hash = initial_pool().keccak256();
+        assert_transfer_update(
+            PendingTransferUpdate::Cancel {
+                transfer_hash: transfer_hash.clone(),
+            },
+            |transfer, write_log| {
+                let cancelled_key = get_cancelled_key(&transfer_hash);
+                write_log
+                    .write(&cancelled_key, transfer.serialize_to_vec())
+                    .expect("Test failed");
+                [cancelled_key].into()
+            },
+            Expect::False,
+        );
+    }
 }
@@ -7,7 +7,7 @@ use namada_core::types::storage::Epochs;
 use namada_ibc::{IbcCommonContext, IbcStorageContext};
 use namada_state::{StorageRead, StorageWrite};
 
-use crate::ledger::ibc::storage::is_ibc_key;
+use super::is_pseudo_executed_key;
 use crate::ledger::native_vp::CtxPreStorageRead;
 use crate::state::write_log::StorageModification;
 use crate::state::{self as ledger_storage, StorageHasher};
@@ -55,7 +55,10 @@ where
 
     /// Get the set of changed keys
     pub(crate) fn get_changed_keys(&self) -> HashSet<&Key> {
-        self.store.keys().filter(|k| is_ibc_key(k)).collect()
+        self.store
+            .keys()
+            .filter(|k| is_pseudo_executed_key(k))
+            .collect()
     }
 
     /// Get the changed value
@@ -206,11 +209,10 @@ where
         let amount = crate::token::denom_to_amount(amount, token, self)?;
         let src_key = token::storage_key::balance_key(token, src);
         let dest_key = token::storage_key::balance_key(token, dest);
-        let src_bal: Option<Amount> = self.ctx.read(&src_key)?;
+        let src_bal: Option<Amount> = self.read(&src_key)?;
         let mut src_bal = src_bal.expect("The source has no balance");
         src_bal.spend(&amount);
-        let mut dest_bal: Amount =
-            self.ctx.read(&dest_key)?.unwrap_or_default();
+        let mut dest_bal: Amount = self.read(&dest_key)?.unwrap_or_default();
         dest_bal.receive(&amount);
 
         self.write(&src_key, src_bal.serialize_to_vec())?;
@@ -235,12 +237,12 @@ where
         let amount = crate::token::denom_to_amount(amount, token, self)?;
         let target_key = token::storage_key::balance_key(token, target);
         let mut target_bal: Amount =
-            self.ctx.read(&target_key)?.unwrap_or_default();
+            self.read(&target_key)?.unwrap_or_default();
         target_bal.receive(&amount);
 
         let minted_key = token::storage_key::minted_balance_key(token);
         let mut minted_bal: Amount =
-            self.ctx.read(&minted_key)?.unwrap_or_default();
+            self.read(&minted_key)?.unwrap_or_default();
         minted_bal.receive(&amount);
 
         self.write(&target_key, target_bal.serialize_to_vec())?;
@@ -262,18 +264,23 @@ where
         let amount = crate::token::denom_to_amount(amount, token, self)?;
         let target_key = token::storage_key::balance_key(token, target);
         let mut target_bal: Amount =
-            self.ctx.read(&target_key)?.unwrap_or_default();
+            self.read(&target_key)?.unwrap_or_default();
         target_bal.spend(&amount);
 
         let minted_key = token::storage_key::minted_balance_key(token);
         let mut minted_bal: Amount =
-            self.ctx.read(&minted_key)?.unwrap_or_default();
+            self.read(&minted_key)?.unwrap_or_default();
         minted_bal.spend(&amount);
 
         self.write(&target_key, target_bal.serialize_to_vec())?;
         self.write(&minted_key, minted_bal.serialize_to_vec())
     }
 
+    /// The gas has been charged in the transaction
+    fn charge_gas(&mut self, _used_gas: u64) -> Result<()> {
+        Ok(())
+    }
+
     fn log_string(&self, message: String) {
         tracing::debug!("{message} in the pseudo execution for IBC VP");
     }
@@ -450,6 +457,10 @@ where
         unimplemented!("Validation doesn't burn")
     }
 
+    fn charge_gas(&mut self, _used_gas: u64) -> Result<()> {
+        unimplemented!("Validation doesn't charge gas")
+    }
+
     /// Logging
     fn log_string(&self, message: String) {
         tracing::debug!("{message} for validation in IBC VP");
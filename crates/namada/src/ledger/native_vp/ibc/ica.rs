@@ -0,0 +1,89 @@
+//! Native VP for interchain accounts hosted on this chain
+
+use std::collections::BTreeSet;
+
+use namada_core::types::address::Address;
+use namada_core::types::storage::Key;
+use namada_state::StorageHasher;
+use namada_tx::Tx;
+use namada_vp_env::VpEnv;
+
+use super::{Error, VpResult};
+use crate::ledger::ibc::storage::ica_execution_key;
+use crate::ledger::native_vp::{Ctx, NativeVp};
+use crate::token::storage_key::is_any_token_balance_key;
+use crate::token::Amount;
+use crate::vm::WasmCacheAccess;
+
+/// Interchain account VP
+pub struct IcaVp<'a, DB, H, CA>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, DB, H, CA>,
+}
+
+impl<'a, DB, H, CA> NativeVp for IcaVp<'a, DB, H, CA>
+where
+    DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    fn validate_tx(
+        &self,
+        _tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        _verifiers: &BTreeSet<Address>,
+    ) -> VpResult<bool> {
+        // The protocol is executing a transaction received from the
+        // controller chain
+        if self
+            .ctx
+            .has_key_pre(&ica_execution_key(self.ctx.address))
+            .map_err(Error::NativeVpError)?
+        {
+            return Ok(true);
+        }
+
+        // Otherwise, the account can only receive tokens
+        for key in keys_changed {
+            if !key.find_addresses().contains(self.ctx.address) {
+                continue;
+            }
+            match is_any_token_balance_key(key) {
+                Some([_, owner]) if owner == self.ctx.address => {
+                    let pre: Amount = self
+                        .ctx
+                        .read_pre(key)
+                        .map_err(Error::NativeVpError)?
+                        .unwrap_or_default();
+                    let post: Amount = self
+                        .ctx
+                        .read_post(key)
+                        .map_err(Error::NativeVpError)?
+                        .unwrap_or_default();
+                    if post < pre {
+                        return Err(Error::Ica(format!(
+                            "The balance of the interchain account {} can be \
+                             decreased only by the controller: Key {key}",
+                            self.ctx.address
+                        )));
+                    }
+                }
+                _ => {
+                    return Err(Error::Ica(format!(
+                        "The interchain account {} can be updated only by the \
+                         controller: Key {key}",
+                        self.ctx.address
+                    )));
+                }
+            }
+        }
+        Ok(true)
+    }
+}
@@ -1,6 +1,7 @@
 //! IBC integration as a native validity predicate
 
 pub mod context;
+pub mod ica;
 
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashSet};
@@ -10,9 +11,15 @@ use std::time::Duration;
 use context::{PseudoExecutionContext, VpValidationContext};
 use namada_core::types::address::Address;
 use namada_core::types::storage::Key;
-use namada_gas::{IBC_ACTION_EXECUTE_GAS, IBC_ACTION_VALIDATE_GAS};
+use namada_gas::{
+    verify_tx_sig_gas, GasCategory, GasMetering, IBC_ACTION_EXECUTE_GAS,
+    IBC_ACTION_VALIDATE_GAS,
+};
+use namada_ibc::forward::is_forward_balance_key;
+use namada_ibc::rate_limit::RateLimit;
 use namada_ibc::{
-    Error as ActionError, IbcActions, TransferModule, ValidationParams,
+    Error as ActionError, IbcActions, IcaControllerModule, IcaHostModule,
+    TransferModule, ValidationParams,
 };
 use namada_proof_of_stake::storage::read_pos_params;
 use namada_state::write_log::StorageModification;
@@ -22,7 +29,10 @@ use namada_vp_env::VpEnv;
 use thiserror::Error;
 
 use crate::ibc::core::host::types::identifiers::ChainId as IbcChainId;
-use crate::ledger::ibc::storage::{calc_hash, is_ibc_denom_key, is_ibc_key};
+use crate::ledger::ibc::storage::{
+    calc_hash, is_ibc_denom_key, is_ibc_key, is_ica_allowlist_key,
+    is_rate_limit_key,
+};
 use crate::ledger::native_vp::{self, Ctx, NativeVp};
 use crate::ledger::parameters::read_epoch_duration_parameter;
 use crate::vm::WasmCacheAccess;
@@ -42,6 +52,10 @@ pub enum Error {
     StateChange(String),
     #[error("IBC event error: {0}")]
     IbcEvent(String),
+    #[error("IBC rate limit error: {0}")]
+    RateLimit(String),
+    #[error("Interchain account error: {0}")]
+    Ica(String),
 }
 
 /// IBC functions result
@@ -75,12 +89,24 @@ where
         let signed = tx_data;
         let tx_data = signed.data().ok_or(Error::NoTxData)?;
 
+        // The rate limits and the allowlist of the interchain account
+        // transactions can be updated only by governance
+        if keys_changed.iter().any(|key| {
+            is_rate_limit_key(key).is_some() || is_ica_allowlist_key(key)
+        }) {
+            self.validate_governance_update(&tx_data, keys_changed)?;
+            return Ok(true);
+        }
+
         // Pseudo execution and compare them
         self.validate_state(&tx_data, keys_changed)?;
 
         // Validate the state according to the given IBC message
         self.validate_with_msg(&tx_data)?;
 
+        // Validate the authorization of the interchain account owner
+        self.validate_ica_owner(signed, &tx_data)?;
+
         // Validate the denom store if a denom key has been changed
         self.validate_denom(keys_changed)?;
 
@@ -105,14 +131,20 @@ where
         let mut actions = IbcActions::new(ctx.clone());
         let module = TransferModule::new(ctx.clone());
         actions.add_transfer_module(module.module_id(), module);
+        let module = IcaHostModule::new(ctx.clone());
+        actions.add_ica_host_module(module.module_id(), module);
+        let module = IcaControllerModule::new(ctx.clone());
+        actions.add_ica_controller_module(module.module_id(), module);
         // Charge gas for the expensive execution
         self.ctx
-            .charge_gas(IBC_ACTION_EXECUTE_GAS)
+            .charge_gas_as(GasCategory::Ibc, IBC_ACTION_EXECUTE_GAS)
             .map_err(Error::NativeVpError)?;
         actions.execute(tx_data)?;
 
-        let changed_ibc_keys: HashSet<&Key> =
-            keys_changed.iter().filter(|k| is_ibc_key(k)).collect();
+        let changed_ibc_keys: HashSet<&Key> = keys_changed
+            .iter()
+            .filter(|k| is_pseudo_executed_key(k))
+            .collect();
         if changed_ibc_keys.len() != ctx.borrow().get_changed_keys().len() {
             return Err(Error::StateChange(format!(
                 "The changed keys mismatched: Actual {:?}, Expected {:?}",
@@ -149,11 +181,15 @@ where
         let mut actions = IbcActions::new(ctx.clone());
         actions.set_validation_params(self.validation_params()?);
 
-        let module = TransferModule::new(ctx);
+        let module = TransferModule::new(ctx.clone());
         actions.add_transfer_module(module.module_id(), module);
+        let module = IcaHostModule::new(ctx.clone());
+        actions.add_ica_host_module(module.module_id(), module);
+        let module = IcaControllerModule::new(ctx);
+        actions.add_ica_controller_module(module.module_id(), module);
         // Charge gas for the expensive validation
         self.ctx
-            .charge_gas(IBC_ACTION_VALIDATE_GAS)
+            .charge_gas_as(GasCategory::Ibc, IBC_ACTION_VALIDATE_GAS)
             .map_err(Error::NativeVpError)?;
         actions.validate(tx_data).map_err(Error::IbcAction)
     }
@@ -179,6 +215,92 @@ where
         })
     }
 
+    fn validate_ica_owner(&self, tx: &Tx, tx_data: &[u8]) -> VpResult<()> {
+        let message = namada_ibc::decode_message(tx_data)?;
+        let Some(owner) = message.ica_owner() else {
+            return Ok(());
+        };
+        let max_signatures =
+            namada_parameters::max_signatures_per_transaction(&self.ctx.pre())
+                .map_err(Error::NativeVpError)?;
+        let public_keys_index_map =
+            namada_account::public_keys_index_map(&self.ctx.pre(), owner)
+                .map_err(Error::NativeVpError)?;
+        let threshold = namada_account::threshold(&self.ctx.pre(), owner)
+            .map_err(Error::NativeVpError)?
+            .unwrap_or(1);
+        tx.verify_signatures(
+            &[tx.raw_header_hash()],
+            public_keys_index_map,
+            &Some(owner.clone()),
+            threshold,
+            max_signatures,
+            |pk| {
+                self.ctx.gas_meter.borrow_mut().consume_as(
+                    GasCategory::SignatureVerification,
+                    verify_tx_sig_gas(pk),
+                )
+            },
+        )
+        .map_err(|e| {
+            Error::Ica(format!(
+                "The message isn't authorized by the owner {owner}: {e}"
+            ))
+        })?;
+        Ok(())
+    }
+
+    fn validate_governance_update(
+        &self,
+        tx_data: &[u8],
+        keys_changed: &BTreeSet<Key>,
+    ) -> VpResult<()> {
+        let is_accepted = namada_governance::storage::is_proposal_accepted(
+            &self.ctx.pre(),
+            tx_data,
+        )
+        .map_err(Error::NativeVpError)?;
+        for key in keys_changed.iter().filter(|k| is_ibc_key(k)) {
+            if is_ica_allowlist_key(key) {
+                if !is_accepted {
+                    return Err(Error::Ica(
+                        "The allowlist of the interchain account transactions \
+                         can be updated only by governance"
+                            .to_string(),
+                    ));
+                }
+                continue;
+            }
+            if is_rate_limit_key(key).is_none() {
+                return Err(Error::StateChange(format!(
+                    "Only the rate limits and the allowlist of the interchain \
+                     account transactions can be updated with the proposal: \
+                     Key {key}"
+                )));
+            }
+            if !is_accepted {
+                return Err(Error::RateLimit(
+                    "The rate limit can be updated only by governance"
+                        .to_string(),
+                ));
+            }
+            // Deleting the rate limit removes the limit
+            if let Some(limit) = self
+                .ctx
+                .read_post::<RateLimit>(key)
+                .map_err(Error::NativeVpError)?
+            {
+                if limit.window == 0 {
+                    return Err(Error::RateLimit(format!(
+                        "The window of the rate limit should be at least 1 \
+                         epoch: Key {key}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn validate_denom(&self, keys_changed: &BTreeSet<Key>) -> VpResult<()> {
         for key in keys_changed {
             if let Some((_, hash)) = is_ibc_denom_key(key) {
@@ -211,6 +333,13 @@ where
     }
 }
 
+/// Check if the key is changed only by the IBC actions, so that its change has
+/// to match the pseudo execution. The balances of the forward address are
+/// changed only by the forwarding and the refund.
+pub(crate) fn is_pseudo_executed_key(key: &Key) -> bool {
+    is_ibc_key(key) || is_forward_balance_key(key).is_some()
+}
+
 fn match_value(
     key: &Key,
     actual: Option<Vec<u8>>,
@@ -13,7 +13,7 @@ use namada_core::types::address::Address;
 use namada_core::types::address::InternalAddress::Masp;
 use namada_core::types::masp::encode_asset_type;
 use namada_core::types::storage::{IndexedTx, Key};
-use namada_gas::MASP_VERIFY_SHIELDED_TX_GAS;
+use namada_gas::{GasCategory, MASP_VERIFY_SHIELDED_TX_GAS};
 use namada_sdk::masp::verify_shielded_tx;
 use namada_state::{OptionExt, ResultExt};
 use namada_tx::Tx;
@@ -22,9 +22,10 @@ use ripemd::Digest as RipemdDigest;
 use sha2::Digest as Sha2Digest;
 use thiserror::Error;
 use token::storage_key::{
-    is_masp_allowed_key, is_masp_key, is_masp_nullifier_key,
-    masp_commitment_anchor_key, masp_commitment_tree_key,
-    masp_convert_anchor_key, masp_nullifier_key, masp_pin_tx_key,
+    is_masp_allowed_key, is_masp_key, is_masp_nullifier_count_key,
+    is_masp_nullifier_key, masp_commitment_anchor_key,
+    masp_commitment_tree_key, masp_convert_anchor_key,
+    masp_nullifier_count_key, masp_nullifier_key, masp_pin_tx_key,
 };
 use token::MaspDenom;
 
@@ -255,6 +256,43 @@ where
 
         Ok(true)
     }
+
+    // Check that the nullifier count of the current epoch has been increased
+    // by exactly the number of spends of the transaction
+    fn valid_nullifier_count_update(
+        &self,
+        masp_keys_changed: &[&Key],
+        spends: u64,
+    ) -> Result<bool> {
+        let epoch = self.ctx.get_block_epoch()?;
+        // Only the count of the current epoch can be updated
+        if masp_keys_changed.iter().any(|key| {
+            is_masp_nullifier_count_key(key)
+                .is_some_and(|key_epoch| key_epoch != epoch)
+        }) {
+            tracing::debug!(
+                "The nullifier count of a past or future epoch has been \
+                 modified"
+            );
+            return Ok(false);
+        }
+
+        let count_key = masp_nullifier_count_key(epoch);
+        let pre_count: u64 = self.ctx.read_pre(&count_key)?.unwrap_or_default();
+        let post_count: u64 =
+            self.ctx.read_post(&count_key)?.unwrap_or_default();
+        if pre_count.checked_add(spends) != Some(post_count) {
+            tracing::debug!(
+                "The nullifier count was not correctly updated, expected {} \
+                 new nullifiers but got {}",
+                spends,
+                post_count.saturating_sub(pre_count)
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
 }
 
 // Make a map to help recognize asset types lacking an epoch
@@ -310,6 +348,11 @@ where
         if !self.valid_state(
             masp_keys_changed.as_slice(),
             transfer.key.as_deref(),
+        )? || !self.valid_nullifier_count_update(
+            masp_keys_changed.as_slice(),
+            shielded_tx
+                .sapling_bundle()
+                .map_or(0, |bundle| bundle.shielded_spends.len() as u64),
         )? {
             return Ok(false);
         }
@@ -560,7 +603,7 @@ where
 
         // Verify the proofs and charge the gas for the expensive execution
         self.ctx
-            .charge_gas(MASP_VERIFY_SHIELDED_TX_GAS)
+            .charge_gas_as(GasCategory::Masp, MASP_VERIFY_SHIELDED_TX_GAS)
             .map_err(Error::NativeVpError)?;
         Ok(verify_shielded_tx(&shielded_tx))
     }
@@ -571,3 +614,107 @@ impl From<native_vp::Error> for Error {
         Self::NativeVpError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use borsh_ext::BorshSerializeExt;
+    use namada_gas::TxGasMeter;
+    use namada_state::testing::TestWlStorage;
+    use namada_tx::data::TxType;
+    use namada_tx::{Code, Data};
+
+    use super::*;
+    use crate::ledger::gas::VpGasMeter;
+    use crate::types::address::MASP;
+    use crate::types::storage::{Epoch, TxIndex};
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    fn dummy_tx(wl_storage: &TestWlStorage) -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = wl_storage.storage.chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![]));
+        tx
+    }
+
+    /// Run the nullifier count check of the MASP VP for a tx with the given
+    /// number of spends
+    fn valid_nullifier_count_update(
+        wl_storage: &TestWlStorage,
+        keys_changed: &BTreeSet<Key>,
+        spends: u64,
+    ) -> bool {
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let verifiers = BTreeSet::new();
+        let ctx = Ctx::new(
+            &MASP,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let masp_keys_changed: Vec<&Key> =
+            keys_changed.iter().filter(|key| is_masp_key(key)).collect();
+
+        MaspVp { ctx }
+            .valid_nullifier_count_update(&masp_keys_changed, spends)
+            .expect("validation failed")
+    }
+
+    #[test]
+    fn test_nullifier_count_update() {
+        let mut wl_storage = TestWlStorage::default();
+        let epoch = Epoch(2);
+        wl_storage.storage.block.epoch = epoch;
+        let count_key = masp_nullifier_count_key(epoch);
+        wl_storage
+            .storage
+            .write(&count_key, 3_u64.serialize_to_vec())
+            .expect("write failed");
+
+        // A tx without spends doesn't need to update the count
+        let keys_changed = BTreeSet::new();
+        assert!(valid_nullifier_count_update(&wl_storage, &keys_changed, 0));
+
+        // The count must be increased by the number of spends
+        let keys_changed = BTreeSet::from([count_key.clone()]);
+        wl_storage
+            .write_log
+            .write(&count_key, 5_u64.serialize_to_vec())
+            .expect("write failed");
+        assert!(valid_nullifier_count_update(&wl_storage, &keys_changed, 2));
+        assert!(!valid_nullifier_count_update(&wl_storage, &keys_changed, 1));
+        assert!(!valid_nullifier_count_update(&wl_storage, &keys_changed, 3));
+
+        // The count can't be left untouched by a tx with spends
+        wl_storage
+            .write_log
+            .write(&count_key, 3_u64.serialize_to_vec())
+            .expect("write failed");
+        assert!(!valid_nullifier_count_update(&wl_storage, &keys_changed, 2));
+
+        // The count of another epoch can't be updated
+        let past_count_key = masp_nullifier_count_key(Epoch(1));
+        wl_storage
+            .write_log
+            .write(&count_key, 5_u64.serialize_to_vec())
+            .expect("write failed");
+        wl_storage
+            .write_log
+            .write(&past_count_key, 1_u64.serialize_to_vec())
+            .expect("write failed");
+        let keys_changed = BTreeSet::from([count_key, past_count_key]);
+        assert!(!valid_nullifier_count_update(&wl_storage, &keys_changed, 2));
+    }
+}
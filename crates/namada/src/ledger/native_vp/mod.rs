@@ -15,7 +15,7 @@ use eyre::WrapErr;
 use namada_core::types::storage;
 use namada_core::types::storage::Epochs;
 use namada_core::types::validity_predicate::VpSentinel;
-use namada_gas::GasMetering;
+use namada_gas::{GasCategory, GasMetering};
 use namada_tx::Tx;
 pub use namada_vp_env::VpEnv;
 
@@ -169,6 +169,20 @@ where
     ) -> CtxPostStorageRead<'view, 'a, DB, H, CA> {
         CtxPostStorageRead { ctx: self }
     }
+
+    /// Charge a gas cost of the given category
+    pub fn charge_gas_as(
+        &self,
+        category: GasCategory,
+        used_gas: u64,
+    ) -> Result<(), state::StorageError> {
+        self.gas_meter
+            .borrow_mut()
+            .consume_as(category, used_gas)
+            .map_err(|_| {
+                Error::SimpleMessage("Gas limit exceeded in native vp")
+            })
+    }
 }
 
 impl<'view, 'a: 'view, DB, H, CA> StorageRead
@@ -536,6 +550,7 @@ where
             let mut iterators: PrefixIterators<'_, DB> =
                 PrefixIterators::default();
             let mut result_buffer: Option<Vec<u8>> = None;
+            let mut trace = None;
             let mut vp_wasm_cache = self.vp_wasm_cache.clone();
 
             let ctx = VpCtx::new(
@@ -551,6 +566,7 @@ where
                 &mut result_buffer,
                 self.keys_changed,
                 &eval_runner,
+                &mut trace,
                 &mut vp_wasm_cache,
             );
             match eval_runner.eval_native_result(ctx, vp_code_hash, input_data)
@@ -578,9 +594,7 @@ where
     }
 
     fn charge_gas(&self, used_gas: u64) -> Result<(), state::StorageError> {
-        self.gas_meter.borrow_mut().consume(used_gas).map_err(|_| {
-            Error::SimpleMessage("Gas limit exceeded in native vp")
-        })
+        self.charge_gas_as(GasCategory::Other, used_gas)
     }
 
     fn get_tx_code_hash(&self) -> Result<Option<Hash>, state::StorageError> {
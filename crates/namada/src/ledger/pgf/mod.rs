@@ -5,8 +5,15 @@ pub mod utils;
 
 use std::collections::BTreeSet;
 
+use namada_governance::pgf::storage::batch::{
+    PgfBatchStatus, StewardPaymentBatch,
+};
 use namada_governance::pgf::storage::keys as pgf_storage;
+use namada_governance::storage::keys as gov_storage;
+use namada_governance::storage::proposal::ProposalType;
 use namada_governance::{is_proposal_accepted, pgf};
+use namada_proof_of_stake::is_validator;
+use namada_state::StorageRead;
 use namada_tx::Tx;
 use thiserror::Error;
 
@@ -93,8 +100,11 @@ where
                     Ok(is_valid)
                 }
                 KeyType::FUNDINGS => Ok(false),
+                KeyType::BATCHES(id) => self.is_valid_batch(id, verifiers),
+                KeyType::BATCH_COUNTER => self.is_valid_batch_counter(),
                 KeyType::PGF_INFLATION_RATE
-                | KeyType::STEWARD_INFLATION_RATE => {
+                | KeyType::STEWARD_INFLATION_RATE
+                | KeyType::BATCH_CHALLENGE_EPOCHS => {
                     self.is_valid_parameter_change(tx_data)
                 }
                 KeyType::UNKNOWN_PGF => Ok(false),
@@ -120,6 +130,94 @@ where
             None => Ok(true),
         }
     }
+
+    /// Validate a steward payment batch. A batch of at most
+    /// [`pgf::MAX_PGF_ACTIONS`] payments can only be submitted by a steward
+    /// and escalated to a governance proposal by a validator within the
+    /// challenge window.
+    pub fn is_valid_batch(
+        &self,
+        id: u64,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let pre_batch = pgf::storage::get_batch(&self.ctx.pre(), id)?;
+        let post_batch = pgf::storage::get_batch(&self.ctx.post(), id)?;
+        let current_epoch = self.ctx.get_block_epoch()?;
+
+        match (pre_batch, post_batch) {
+            (None, Some(batch)) => {
+                let counter: u64 = self
+                    .ctx
+                    .pre()
+                    .read(&pgf_storage::get_batch_counter_key())?
+                    .unwrap_or_default();
+                let pgf_parameters =
+                    pgf::storage::get_parameters(&self.ctx.pre())?;
+                let is_steward =
+                    pgf::storage::is_steward(&self.ctx.pre(), &batch.steward)?;
+
+                Ok(batch.id == id
+                    && id == counter
+                    && is_steward
+                    && verifiers.contains(&batch.steward)
+                    && !batch.payments.is_empty()
+                    && batch.payments.len() <= pgf::MAX_PGF_ACTIONS
+                    && batch.submission_epoch == current_epoch
+                    && batch.challenge_end_epoch
+                        == current_epoch
+                            + pgf_parameters.batch_challenge_epochs
+                    && batch.status == PgfBatchStatus::Pending)
+            }
+            (Some(pre_batch), Some(post_batch)) => {
+                let (challenger, proposal_id) = match &post_batch.status {
+                    PgfBatchStatus::Escalated {
+                        challenger,
+                        proposal_id,
+                    } => (challenger, *proposal_id),
+                    _ => return Ok(false),
+                };
+                let is_unchanged = post_batch
+                    == StewardPaymentBatch {
+                        status: post_batch.status.clone(),
+                        ..pre_batch.clone()
+                    };
+                if !is_unchanged || !pre_batch.is_challengeable(current_epoch) {
+                    return Ok(false);
+                }
+
+                // The batch must be escalated to a new proposal authored by
+                // the challenger and funding the batch payments
+                let proposal_counter: Option<u64> =
+                    self.ctx.pre().read(&gov_storage::get_counter_key())?;
+                let author: Option<Address> = self
+                    .ctx
+                    .post()
+                    .read(&gov_storage::get_author_key(proposal_id))?;
+                let proposal_type: Option<ProposalType> = self
+                    .ctx
+                    .post()
+                    .read(&gov_storage::get_proposal_type_key(proposal_id))?;
+                let is_validator = is_validator(&self.ctx.pre(), challenger)?;
+
+                Ok(proposal_counter == Some(proposal_id)
+                    && author.as_ref() == Some(challenger)
+                    && proposal_type == Some(pre_batch.to_proposal_type())
+                    && is_validator
+                    && verifiers.contains(challenger))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Validate the steward payment batch counter
+    pub fn is_valid_batch_counter(&self) -> Result<bool> {
+        let counter_key = pgf_storage::get_batch_counter_key();
+        let pre_counter: u64 =
+            self.ctx.pre().read(&counter_key)?.unwrap_or_default();
+        let post_counter: Option<u64> = self.ctx.post().read(&counter_key)?;
+
+        Ok(post_counter == Some(pre_counter + 1))
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -130,10 +228,16 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     FUNDINGS,
     #[allow(non_camel_case_types)]
+    BATCHES(u64),
+    #[allow(non_camel_case_types)]
+    BATCH_COUNTER,
+    #[allow(non_camel_case_types)]
     PGF_INFLATION_RATE,
     #[allow(non_camel_case_types)]
     STEWARD_INFLATION_RATE,
     #[allow(non_camel_case_types)]
+    BATCH_CHALLENGE_EPOCHS,
+    #[allow(non_camel_case_types)]
     UNKNOWN_PGF,
     #[allow(non_camel_case_types)]
     UNKNOWN,
@@ -145,10 +249,16 @@ impl From<&Key> for KeyType {
             Self::STEWARDS
         } else if pgf_storage::is_fundings_key(key) {
             KeyType::FUNDINGS
+        } else if let Some(id) = pgf_storage::is_batches_key(key) {
+            KeyType::BATCHES(id)
+        } else if pgf_storage::is_batch_counter_key(key) {
+            KeyType::BATCH_COUNTER
         } else if pgf_storage::is_pgf_inflation_rate_key(key) {
             Self::PGF_INFLATION_RATE
         } else if pgf_storage::is_steward_inflation_rate_key(key) {
             Self::STEWARD_INFLATION_RATE
+        } else if pgf_storage::is_batch_challenge_epochs_key(key) {
+            Self::BATCH_CHALLENGE_EPOCHS
         } else if pgf_storage::is_pgf_key(key) {
             KeyType::UNKNOWN_PGF
         } else {
@@ -156,3 +266,124 @@ impl From<&Key> for KeyType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use namada_gas::TxGasMeter;
+    use namada_governance::pgf::parameters::PgfParameters;
+    use namada_governance::pgf::storage::batch::SubmitPgfBatchData;
+    use namada_governance::pgf::MAX_PGF_ACTIONS;
+    use namada_governance::storage::proposal::{PGFInternalTarget, PGFTarget};
+    use namada_state::testing::TestWlStorage;
+    use namada_state::StorageWrite;
+    use namada_tx::data::TxType;
+
+    use super::*;
+    use crate::core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use crate::ledger::gas::VpGasMeter;
+    use crate::token;
+    use crate::types::storage::TxIndex;
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    fn init_storage(steward: &Address) -> TestWlStorage {
+        let mut wl_storage = TestWlStorage::default();
+        PgfParameters {
+            stewards: [steward.clone()].into(),
+            ..Default::default()
+        }
+        .init_storage(&mut wl_storage)
+        .unwrap();
+        wl_storage.commit_tx();
+        wl_storage.commit_block().unwrap();
+        wl_storage
+    }
+
+    fn batch_data(steward: &Address, len: usize) -> SubmitPgfBatchData {
+        let payment = PGFTarget::Internal(PGFInternalTarget {
+            target: established_address_2(),
+            amount: token::Amount::native_whole(1),
+        });
+        SubmitPgfBatchData {
+            steward: steward.clone(),
+            payments: vec![payment; len],
+        }
+    }
+
+    fn validate(wl_storage: &TestWlStorage, verifiers: &[&Address]) -> bool {
+        let keys_changed = wl_storage.write_log.get_keys();
+        let verifiers = verifiers
+            .iter()
+            .map(|&address| address.clone())
+            .collect::<BTreeSet<_>>();
+        let tx = Tx::from_type(TxType::Raw);
+        let tx_index = TxIndex::default();
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = PgfVp { ctx };
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("validation failed")
+    }
+
+    /// Test that a steward can submit a batch of up to `MAX_PGF_ACTIONS`
+    /// payments
+    #[test]
+    fn test_valid_batch() {
+        let steward = established_address_1();
+        let mut wl_storage = init_storage(&steward);
+
+        pgf::storage::submit_batch(
+            &mut wl_storage,
+            batch_data(&steward, MAX_PGF_ACTIONS),
+        )
+        .unwrap();
+
+        assert!(validate(&wl_storage, &[&steward]));
+        // The steward must sign the batch
+        assert!(!validate(&wl_storage, &[]));
+    }
+
+    /// Test that a batch that is empty or has more than `MAX_PGF_ACTIONS`
+    /// payments is rejected, even if written without the storage checks
+    #[test]
+    fn test_invalid_batch_size() {
+        let steward = established_address_1();
+        for len in [0, MAX_PGF_ACTIONS + 1] {
+            let mut wl_storage = init_storage(&steward);
+            let data = batch_data(&steward, len);
+            let batch = StewardPaymentBatch {
+                id: 0,
+                steward: data.steward,
+                payments: data.payments,
+                submission_epoch: 0.into(),
+                challenge_end_epoch: 2.into(),
+                status: PgfBatchStatus::Pending,
+            };
+            pgf_storage::batches_handle()
+                .insert(&mut wl_storage, 0, batch)
+                .unwrap();
+            wl_storage
+                .write(&pgf_storage::get_batch_counter_key(), 1_u64)
+                .unwrap();
+
+            assert!(!validate(&wl_storage, &[&steward]));
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use namada_core::types::address::Address;
+use namada_governance::storage::proposal::StoragePgfFunding;
 
-use crate::ledger::events::EventType;
+use crate::ledger::events::{Event, EventLevel, EventType};
 use crate::token;
 
 /// Proposal event definition
@@ -13,6 +14,16 @@ pub struct ProposalEvent {
     pub attributes: HashMap<String, String>,
 }
 
+impl From<ProposalEvent> for Event {
+    fn from(proposal_event: ProposalEvent) -> Self {
+        Self {
+            event_type: EventType::PgfPayment,
+            level: EventLevel::Block,
+            attributes: proposal_event.attributes,
+        }
+    }
+}
+
 impl ProposalEvent {
     /// Create a proposal event
     pub fn new(
@@ -63,4 +74,19 @@ impl ProposalEvent {
             success,
         )
     }
+
+    /// Create a new proposal event for a continuous funding that reached its
+    /// end epoch or exhausted its budget
+    pub fn pgf_funding_completed(funding: &StoragePgfFunding) -> Self {
+        let attributes = HashMap::from([
+            ("target".to_string(), funding.detail.target()),
+            ("proposal_id".to_string(), funding.id.to_string()),
+            ("total_paid".to_string(), funding.paid.to_string_native()),
+            ("completed".to_string(), true.to_string()),
+        ]);
+        Self {
+            event_type: EventType::PgfPayment.to_string(),
+            attributes,
+        }
+    }
 }
@@ -1,5 +1,6 @@
 //! The ledger's protocol
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
 
 use borsh_ext::BorshSerializeExt;
 use eyre::{eyre, WrapErr};
@@ -11,6 +12,9 @@ use namada_sdk::tx::TX_TRANSFER_WASM;
 use namada_state::wl_storage::WriteLogAndStorage;
 use namada_state::StorageRead;
 use namada_tx::data::protocol::ProtocolTxType;
+use namada_tx::data::trace::{
+    ExecutionTrace, TraceEntry, VpDecision, VpTrace,
+};
 use namada_tx::data::{
     DecryptedTx, GasLimit, TxResult, TxType, VpsResult, WrapperTx,
 };
@@ -19,11 +23,12 @@ use namada_vote_ext::EthereumTxData;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use thiserror::Error;
 
-use crate::ledger::gas::{GasMetering, VpGasMeter};
+use crate::ledger::gas::{GasCategory, GasMetering, VpGasMeter};
 use crate::ledger::governance::GovernanceVp;
 use crate::ledger::native_vp::ethereum_bridge::bridge_pool_vp::BridgePoolVp;
 use crate::ledger::native_vp::ethereum_bridge::nut::NonUsableTokens;
 use crate::ledger::native_vp::ethereum_bridge::vp::EthBridge;
+use crate::ledger::native_vp::ibc::ica::IcaVp;
 use crate::ledger::native_vp::ibc::Ibc;
 use crate::ledger::native_vp::masp::MaspVp;
 use crate::ledger::native_vp::multitoken::MultitokenVp;
@@ -174,6 +179,7 @@ where
                 vp_wasm_cache,
                 tx_wasm_cache,
             },
+            false,
         ),
         TxType::Protocol(protocol_tx) => {
             apply_protocol_tx(protocol_tx.tx, tx.data(), wl_storage)
@@ -201,6 +207,9 @@ where
                 initialized_accounts: vec![],
                 ibc_events: BTreeSet::default(),
                 eth_bridge_events: BTreeSet::default(),
+                transfer_leg_events: vec![],
+                trace: None,
+                gas_profile: None,
             })
         }
         TxType::Decrypted(DecryptedTx::Undecryptable) => {
@@ -250,7 +259,7 @@ where
     // Write wrapper tx hash to storage
     shell_params
         .wl_storage
-        .write_tx_hash(tx.header_hash())
+        .write_tx_hash(tx.header_hash(), tx.header.expiration)
         .expect("Error while writing tx hash to storage");
 
     // Charge fee before performing any fallible operations
@@ -352,6 +361,7 @@ where
                         vp_wasm_cache,
                         tx_wasm_cache,
                     },
+                    false,
                 ) {
                     Ok(result) => {
                         // NOTE: do not commit yet cause this could be
@@ -559,11 +569,14 @@ where
 }
 
 /// Apply a transaction going via the wasm environment. Gas will be metered and
-/// validity predicates will be triggered in the normal way.
+/// validity predicates will be triggered in the normal way. If `trace` is set,
+/// the execution trace of the transaction and its VPs is included in the
+/// result.
 pub fn apply_wasm_tx<'a, D, H, CA, WLS>(
     tx: Tx,
     tx_index: &TxIndex,
     shell_params: ShellParams<'a, CA, WLS>,
+    trace: bool,
 ) -> Result<TxResult>
 where
     CA: 'static + WasmCacheAccess + Sync,
@@ -596,6 +609,7 @@ where
         return Err(Error::ReplayAttempt(tx_hash));
     }
 
+    let mut tx_trace = trace.then(Vec::new);
     let verifiers = execute_tx(
         &tx,
         tx_index,
@@ -604,8 +618,10 @@ where
         write_log,
         vp_wasm_cache,
         tx_wasm_cache,
+        &mut tx_trace,
     )?;
 
+    let vp_traces = trace.then(|| Mutex::new(BTreeMap::new()));
     let vps_result = check_vps(CheckVps {
         tx: &tx,
         tx_index,
@@ -614,12 +630,14 @@ where
         write_log,
         verifiers_from_tx: &verifiers,
         vp_wasm_cache,
+        vp_traces: vp_traces.as_ref(),
     })?;
 
     let gas_used = tx_gas_meter.get_tx_consumed_gas();
     let initialized_accounts = write_log.get_initialized_accounts();
     let changed_keys = write_log.get_keys();
     let ibc_events = write_log.take_ibc_events();
+    let transfer_leg_events = write_log.take_transfer_leg_events();
 
     Ok(TxResult {
         gas_used,
@@ -628,6 +646,12 @@ where
         initialized_accounts,
         ibc_events,
         eth_bridge_events: BTreeSet::default(),
+        transfer_leg_events,
+        trace: tx_trace.zip(vp_traces).map(|(tx, vps)| ExecutionTrace {
+            tx,
+            vps: vps.into_inner().unwrap(),
+        }),
+        gas_profile: None,
     })
 }
 
@@ -716,6 +740,7 @@ fn execute_tx<D, H, CA>(
     write_log: &mut WriteLog,
     vp_wasm_cache: &mut VpCache<CA>,
     tx_wasm_cache: &mut TxCache<CA>,
+    trace: &mut Option<Vec<TraceEntry>>,
 ) -> Result<BTreeSet<Address>>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -730,6 +755,7 @@ where
         tx,
         vp_wasm_cache,
         tx_wasm_cache,
+        trace,
     )
     .map_err(|err| match err {
         wasm::run::Error::GasError(msg) => Error::GasError(msg),
@@ -752,6 +778,8 @@ where
     write_log: &'a WriteLog,
     verifiers_from_tx: &'a BTreeSet<Address>,
     vp_wasm_cache: &'a mut VpCache<CA>,
+    /// Where to record the traces of the VPs, if they're traced
+    vp_traces: Option<&'a Mutex<BTreeMap<Address, VpTrace>>>,
 }
 
 /// Check the acceptance of a transaction by validity predicates
@@ -764,6 +792,7 @@ fn check_vps<D, H, CA>(
         write_log,
         verifiers_from_tx,
         vp_wasm_cache,
+        vp_traces,
     }: CheckVps<'_, D, H, CA>,
 ) -> Result<VpsResult>
 where
@@ -783,6 +812,7 @@ where
         write_log,
         tx_gas_meter,
         vp_wasm_cache,
+        vp_traces,
     )?;
     tracing::debug!("Total VPs gas cost {:?}", vps_result.gas_used);
 
@@ -804,6 +834,7 @@ fn execute_vps<D, H, CA>(
     write_log: &WriteLog,
     tx_gas_meter: &TxGasMeter,
     vp_wasm_cache: &mut VpCache<CA>,
+    vp_traces: Option<&Mutex<BTreeMap<Address, VpTrace>>>,
 ) -> Result<VpsResult>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -814,13 +845,14 @@ where
         .par_iter()
         .try_fold(VpsResult::default, |mut result, addr| {
             let mut gas_meter = VpGasMeter::new_from_tx_meter(tx_gas_meter);
+            let mut vp_calls = vp_traces.map(|_| Vec::new());
             let accept = match &addr {
                 Address::Implicit(_) | Address::Established(_) => {
                     let (vp_hash, gas) = storage
                         .validity_predicate(addr)
                         .map_err(Error::StorageError)?;
                     gas_meter
-                        .consume(gas)
+                        .consume_as(GasCategory::StorageRead, gas)
                         .map_err(|err| Error::GasError(err.to_string()))?;
                     let Some(vp_code_hash) = vp_hash else {
                         return Err(Error::MissingAddress(addr.clone()));
@@ -842,6 +874,7 @@ where
                         &keys_changed,
                         &verifiers,
                         vp_wasm_cache.clone(),
+                        &mut vp_calls,
                     )
                     .map_err(|err| match err {
                         wasm::run::Error::GasError(msg) => Error::GasError(msg),
@@ -901,6 +934,17 @@ where
                                 gas_meter = ibc.ctx.gas_meter.into_inner();
                                 (result, ibc.ctx.sentinel.into_inner())
                             }
+                            InternalAddress::IbcAccount(_) => {
+                                let ica = IcaVp { ctx };
+                                let result = ica
+                                    .validate_tx(tx, &keys_changed, &verifiers)
+                                    .map_err(Error::IbcNativeVpError);
+                                // Take the gas meter and the sentinel
+                                // back
+                                // out of the context
+                                gas_meter = ica.ctx.gas_meter.into_inner();
+                                (result, ica.ctx.sentinel.into_inner())
+                            }
                             InternalAddress::Parameters => {
                                 let parameters = ParametersVp { ctx };
                                 let result = parameters
@@ -1015,6 +1059,21 @@ where
                                     ctx.sentinel.into_inner(),
                                 )
                             }
+                            InternalAddress::IbcForward => {
+                                // The forwarded tokens are moved only by IBC
+                                // and the IBC VP checks that the changes match
+                                // the forwarding and the refund
+                                // Take the gas meter and the sentinel
+                                // back
+                                // out of the context
+                                gas_meter = ctx.gas_meter.into_inner();
+                                (
+                                    Ok(verifiers.contains(&Address::Internal(
+                                        InternalAddress::Ibc,
+                                    ))),
+                                    ctx.sentinel.into_inner(),
+                                )
+                            }
                             InternalAddress::Masp => {
                                 let masp = MaspVp { ctx };
                                 let result = masp
@@ -1039,6 +1098,24 @@ where
                 }
             };
 
+            if let Some(vp_traces) = vp_traces {
+                let decision = match &accept {
+                    Ok(true) => VpDecision::Accepted,
+                    Ok(false) => VpDecision::Rejected,
+                    Err(Error::InvalidTxSignature) => {
+                        VpDecision::InvalidSignature
+                    }
+                    Err(err) => VpDecision::Error(err.to_string()),
+                };
+                vp_traces.lock().unwrap().insert(
+                    addr.clone(),
+                    VpTrace {
+                        calls: vp_calls.unwrap_or_default(),
+                        decision,
+                    },
+                );
+            }
+
             match accept {
                 Ok(accepted) => {
                     if accepted {
@@ -1070,6 +1147,10 @@ where
                 },
             }
 
+            result
+                .gas_used
+                .record_profile(addr, &gas_meter)
+                .map_err(|err| Error::GasError(err.to_string()))?;
             result
                 .gas_used
                 .set(gas_meter)
@@ -16,7 +16,7 @@ use namada_tx::{Section, Tx};
 use thiserror::Error;
 
 use crate::ledger::gas;
-use crate::ledger::gas::{GasMetering, VpGasMeter};
+use crate::ledger::gas::{GasCategory, GasMetering, VpGasMeter};
 use crate::types::ibc::IbcEvent;
 
 /// These runtime errors will abort VP execution immediately
@@ -52,7 +52,17 @@ pub fn add_gas(
     used_gas: u64,
     sentinel: &mut VpSentinel,
 ) -> EnvResult<()> {
-    gas_meter.consume(used_gas).map_err(|err| {
+    add_gas_as(gas_meter, GasCategory::Other, used_gas, sentinel)
+}
+
+/// Add a gas cost of the given category incured in a validity predicate
+pub fn add_gas_as(
+    gas_meter: &mut VpGasMeter,
+    category: GasCategory,
+    used_gas: u64,
+    sentinel: &mut VpSentinel,
+) -> EnvResult<()> {
+    gas_meter.consume_as(category, used_gas).map_err(|err| {
         sentinel.set_out_of_gas();
         tracing::info!("Stopping VP execution because of gas error: {}", err);
         RuntimeError::OutOfGas(err)
@@ -73,7 +83,7 @@ where
     H: StorageHasher,
 {
     let (log_val, gas) = write_log.read_pre(key);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     match log_val {
         Some(write_log::StorageModification::Write { ref value }) => {
             Ok(Some(value.clone()))
@@ -95,7 +105,7 @@ where
             // When not found in write log, try to read from the storage
             let (value, gas) =
                 storage.read(key).map_err(RuntimeError::StorageError)?;
-            add_gas(gas_meter, gas, sentinel)?;
+            add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
             Ok(value)
         }
     }
@@ -116,7 +126,7 @@ where
 {
     // Try to read from the write log first
     let (log_val, gas) = write_log.read(key);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     match log_val {
         Some(write_log::StorageModification::Write { ref value }) => {
             Ok(Some(value.clone()))
@@ -138,7 +148,7 @@ where
             // When not found in write log, try to read from the storage
             let (value, gas) =
                 storage.read(key).map_err(RuntimeError::StorageError)?;
-            add_gas(gas_meter, gas, sentinel)?;
+            add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
             Ok(value)
         }
     }
@@ -154,7 +164,7 @@ pub fn read_temp(
 ) -> EnvResult<Option<Vec<u8>>> {
     // Try to read from the write log first
     let (log_val, gas) = write_log.read(key);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     match log_val {
         Some(write_log::StorageModification::Temp { ref value }) => {
             Ok(Some(value.clone()))
@@ -179,7 +189,7 @@ where
 {
     // Try to read from the write log first
     let (log_val, gas) = write_log.read_pre(key);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     match log_val {
         Some(&write_log::StorageModification::Write { .. }) => Ok(true),
         Some(&write_log::StorageModification::Delete) => {
@@ -192,7 +202,7 @@ where
             // When not found in write log, try to check the storage
             let (present, gas) =
                 storage.has_key(key).map_err(RuntimeError::StorageError)?;
-            add_gas(gas_meter, gas, sentinel)?;
+            add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
             Ok(present)
         }
     }
@@ -213,7 +223,7 @@ where
 {
     // Try to read from the write log first
     let (log_val, gas) = write_log.read(key);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     match log_val {
         Some(&write_log::StorageModification::Write { .. }) => Ok(true),
         Some(&write_log::StorageModification::Delete) => {
@@ -226,7 +236,7 @@ where
             // When not found in write log, try to check the storage
             let (present, gas) =
                 storage.has_key(key).map_err(RuntimeError::StorageError)?;
-            add_gas(gas_meter, gas, sentinel)?;
+            add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
             Ok(present)
         }
     }
@@ -413,7 +423,7 @@ where
     H: StorageHasher,
 {
     let (iter, gas) = namada_state::iter_prefix_pre(write_log, storage, prefix);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     Ok(iter)
 }
 
@@ -432,7 +442,7 @@ where
 {
     let (iter, gas) =
         namada_state::iter_prefix_post(write_log, storage, prefix);
-    add_gas(gas_meter, gas, sentinel)?;
+    add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
     Ok(iter)
 }
 
@@ -446,7 +456,7 @@ where
     DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
 {
     if let Some((key, val, gas)) = iter.next() {
-        add_gas(gas_meter, gas, sentinel)?;
+        add_gas_as(gas_meter, GasCategory::StorageRead, gas, sentinel)?;
         return Ok(Some((key, val)));
     }
     Ok(None)
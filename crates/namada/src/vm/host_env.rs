@@ -12,11 +12,12 @@ use namada_core::types::internal::KeyVal;
 use namada_core::types::storage::{Epochs, TX_INDEX_LENGTH};
 use namada_core::types::validity_predicate::VpSentinel;
 use namada_gas::{
-    self as gas, GasMetering, TxGasMeter, VpGasMeter,
+    self as gas, GasCategory, GasMetering, TxGasMeter, VpGasMeter,
     MEMORY_ACCESS_GAS_PER_BYTE,
 };
 use namada_state::write_log::{self, WriteLog};
 use namada_state::{self, ResultExt, State, StorageHasher};
+use namada_tx::data::trace::{HostCall, TraceEntry};
 use namada_tx::data::TxSentinel;
 use namada_tx::Tx;
 use thiserror::Error;
@@ -36,6 +37,7 @@ use crate::types::hash::Hash;
 use crate::types::ibc::IbcEvent;
 use crate::types::internal::HostEnvResult;
 use crate::types::storage::{BlockHeight, Epoch, Key, TxIndex};
+use crate::types::token::TransferLegEvent;
 use crate::vm::memory::VmMemory;
 use crate::vm::prefix_iter::{PrefixIteratorId, PrefixIterators};
 use crate::vm::{HostRef, MutHostRef};
@@ -117,6 +119,8 @@ where
     pub verifiers: MutHostRef<'a, &'a BTreeSet<Address>>,
     /// Cache for 2-step reads from host environment.
     pub result_buffer: MutHostRef<'a, &'a Option<Vec<u8>>>,
+    /// The execution trace, recorded only if present
+    pub trace: MutHostRef<'a, &'a Option<Vec<TraceEntry>>>,
     /// VP WASM compilation cache (this is available in tx context, because
     /// we're pre-compiling VPs from [`tx_init_account`])
     #[cfg(feature = "wasm-runtime")]
@@ -155,6 +159,7 @@ where
         tx_index: &TxIndex,
         verifiers: &mut BTreeSet<Address>,
         result_buffer: &mut Option<Vec<u8>>,
+        trace: &mut Option<Vec<TraceEntry>>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
         #[cfg(feature = "wasm-runtime")] tx_wasm_cache: &mut TxCache<CA>,
     ) -> Self {
@@ -167,6 +172,7 @@ where
         let tx_index = unsafe { HostRef::new(tx_index) };
         let verifiers = unsafe { MutHostRef::new(verifiers) };
         let result_buffer = unsafe { MutHostRef::new(result_buffer) };
+        let trace = unsafe { MutHostRef::new(trace) };
         #[cfg(feature = "wasm-runtime")]
         let vp_wasm_cache = unsafe { MutHostRef::new(vp_wasm_cache) };
         #[cfg(feature = "wasm-runtime")]
@@ -181,6 +187,7 @@ where
             tx_index,
             verifiers,
             result_buffer,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -225,6 +232,7 @@ where
             tx_index: self.tx_index.clone(),
             verifiers: self.verifiers.clone(),
             result_buffer: self.result_buffer.clone(),
+            trace: self.trace.clone(),
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache: self.vp_wasm_cache.clone(),
             #[cfg(feature = "wasm-runtime")]
@@ -284,6 +292,8 @@ where
     /// The verifiers whose validity predicates should be triggered. Used for
     /// calls to `eval`.
     pub verifiers: HostRef<'a, &'a BTreeSet<Address>>,
+    /// The execution trace, recorded only if present
+    pub trace: MutHostRef<'a, &'a Option<Vec<TraceEntry>>>,
     /// VP WASM compilation cache
     #[cfg(feature = "wasm-runtime")]
     pub vp_wasm_cache: MutHostRef<'a, &'a VpCache<CA>>,
@@ -346,6 +356,7 @@ where
         result_buffer: &mut Option<Vec<u8>>,
         keys_changed: &BTreeSet<Key>,
         eval_runner: &EVAL,
+        trace: &mut Option<Vec<TraceEntry>>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
     ) -> Self {
         let ctx = VpCtx::new(
@@ -361,6 +372,7 @@ where
             result_buffer,
             keys_changed,
             eval_runner,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
         );
@@ -413,6 +425,7 @@ where
         result_buffer: &mut Option<Vec<u8>>,
         keys_changed: &BTreeSet<Key>,
         eval_runner: &EVAL,
+        trace: &mut Option<Vec<TraceEntry>>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
     ) -> Self {
         let address = unsafe { HostRef::new(address) };
@@ -427,6 +440,7 @@ where
         let result_buffer = unsafe { MutHostRef::new(result_buffer) };
         let keys_changed = unsafe { HostRef::new(keys_changed) };
         let eval_runner = unsafe { HostRef::new(eval_runner) };
+        let trace = unsafe { MutHostRef::new(trace) };
         #[cfg(feature = "wasm-runtime")]
         let vp_wasm_cache = unsafe { MutHostRef::new(vp_wasm_cache) };
         Self {
@@ -442,6 +456,7 @@ where
             result_buffer,
             keys_changed,
             verifiers,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(not(feature = "wasm-runtime"))]
@@ -471,6 +486,7 @@ where
             result_buffer: self.result_buffer.clone(),
             keys_changed: self.keys_changed.clone(),
             verifiers: self.verifiers.clone(),
+            trace: self.trace.clone(),
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache: self.vp_wasm_cache.clone(),
             #[cfg(not(feature = "wasm-runtime"))]
@@ -484,6 +500,36 @@ pub fn tx_charge_gas<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
     used_gas: u64,
 ) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    tx_charge_gas_as(env, GasCategory::Other, used_gas)
+}
+
+/// Called from the gas metering injected in tx wasm, which only charges for
+/// the growth of the memory
+pub fn tx_charge_memory_gas<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    used_gas: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    tx_charge_gas_as(env, GasCategory::MemoryPages, used_gas)
+}
+
+/// Add a gas cost of the given category incured in a transaction
+fn tx_charge_gas_as<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    category: GasCategory,
+    used_gas: u64,
+) -> TxResult<()>
 where
     MEM: VmMemory,
     DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
@@ -492,7 +538,7 @@ where
 {
     let gas_meter = unsafe { env.ctx.gas_meter.get() };
     // if we run out of gas, we need to stop the execution
-    gas_meter.consume(used_gas).map_err(|err| {
+    gas_meter.consume_as(category, used_gas).map_err(|err| {
         let sentinel = unsafe { env.ctx.sentinel.get() };
         sentinel.set_out_of_gas();
         tracing::info!(
@@ -521,6 +567,84 @@ where
     vp_host_fns::add_gas(gas_meter, used_gas, sentinel)
 }
 
+/// Called from the gas metering injected in VP wasm, which only charges for
+/// the growth of the memory
+pub fn vp_charge_memory_gas<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    used_gas: u64,
+) -> vp_host_fns::EnvResult<()>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    vp_host_fns::add_gas_as(
+        gas_meter,
+        GasCategory::MemoryPages,
+        used_gas,
+        sentinel,
+    )
+}
+
+/// Check if the transaction's execution is being traced
+fn tx_is_traced<MEM, DB, H, CA>(env: &TxVmEnv<MEM, DB, H, CA>) -> bool
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let trace = unsafe { env.ctx.trace.get() };
+    trace.is_some()
+}
+
+/// Record a host call in the transaction's execution trace, if it's being
+/// traced. The call is only built when needed.
+fn tx_trace<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    call: impl FnOnce() -> HostCall,
+) where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let trace = unsafe { env.ctx.trace.get() };
+    if let Some(trace) = trace {
+        let gas_meter = unsafe { env.ctx.gas_meter.get() };
+        trace.push(TraceEntry {
+            call: call(),
+            gas_used: gas_meter.get_tx_consumed_gas(),
+        });
+    }
+}
+
+/// Record a host call in the validity predicate's execution trace, if it's
+/// being traced. The call is only built when needed.
+fn vp_trace<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    call: impl FnOnce() -> HostCall,
+) where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let trace = unsafe { env.ctx.trace.get() };
+    if let Some(trace) = trace {
+        let gas_meter = unsafe { env.ctx.gas_meter.get() };
+        trace.push(TraceEntry {
+            call: call(),
+            gas_used: gas_meter.get_vp_consumed_gas(),
+        });
+    }
+}
+
 /// Storage `has_key` function exposed to the wasm VM Tx environment. It will
 /// try to check the write log first and if no entry found then the storage.
 pub fn tx_has_key<MEM, DB, H, CA>(
@@ -547,31 +671,27 @@ where
     // try to read from the write log first
     let write_log = unsafe { env.ctx.write_log.get() };
     let (log_val, gas) = write_log.read(&key);
-    tx_charge_gas(env, gas)?;
-    Ok(match log_val {
-        Some(&write_log::StorageModification::Write { .. }) => {
-            HostEnvResult::Success.to_i64()
-        }
+    tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
+    let present = match log_val {
+        Some(&write_log::StorageModification::Write { .. }) => true,
         Some(&write_log::StorageModification::Delete) => {
             // the given key has been deleted
-            HostEnvResult::Fail.to_i64()
-        }
-        Some(&write_log::StorageModification::InitAccount { .. }) => {
-            HostEnvResult::Success.to_i64()
-        }
-        Some(&write_log::StorageModification::Temp { .. }) => {
-            HostEnvResult::Success.to_i64()
+            false
         }
+        Some(&write_log::StorageModification::InitAccount { .. }) => true,
+        Some(&write_log::StorageModification::Temp { .. }) => true,
         None => {
             // when not found in write log, try to check the storage
             let storage = unsafe { env.ctx.storage.get() };
             let (present, gas) = storage
                 .has_key(&key)
                 .map_err(TxRuntimeError::StorageError)?;
-            tx_charge_gas(env, gas)?;
-            HostEnvResult::from(present).to_i64()
+            tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
+            present
         }
-    })
+    };
+    tx_trace(env, || HostCall::HasKey { key, present });
+    Ok(HostEnvResult::from(present).to_i64())
 }
 
 /// Storage read function exposed to the wasm VM Tx environment. It will try to
@@ -603,8 +723,8 @@ where
     // try to read from the write log first
     let write_log = unsafe { env.ctx.write_log.get() };
     let (log_val, gas) = write_log.read(&key);
-    tx_charge_gas(env, gas)?;
-    Ok(match log_val {
+    tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
+    let len = match log_val {
         Some(write_log::StorageModification::Write { ref value }) => {
             let len: i64 = value
                 .len()
@@ -644,7 +764,7 @@ where
             let storage = unsafe { env.ctx.storage.get() };
             let (value, gas) =
                 storage.read(&key).map_err(TxRuntimeError::StorageError)?;
-            tx_charge_gas(env, gas)?;
+            tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
             match value {
                 Some(value) => {
                     let len: i64 = value
@@ -658,7 +778,17 @@ where
                 None => HostEnvResult::Fail.to_i64(),
             }
         }
-    })
+    };
+    tx_trace(env, || {
+        let result_buffer = unsafe { env.ctx.result_buffer.get() };
+        let value = if len == HostEnvResult::Fail.to_i64() {
+            None
+        } else {
+            result_buffer.clone()
+        };
+        HostCall::Read { key, value }
+    });
+    Ok(len)
 }
 
 /// This function is a helper to handle the first step of reading var-len
@@ -717,7 +847,10 @@ where
     let storage = unsafe { env.ctx.storage.get() };
     let (iter, gas) =
         namada_state::iter_prefix_post(write_log, storage, &prefix);
-    tx_charge_gas(env, gas)?;
+    tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
+    tx_trace(env, || HostCall::IterPrefix {
+        prefix: prefix.clone(),
+    });
 
     let iterators = unsafe { env.ctx.iterators.get() };
     Ok(iterators.insert(iter).id())
@@ -749,7 +882,7 @@ where
             &Key::parse(key.clone())
                 .map_err(TxRuntimeError::StorageDataError)?,
         );
-        tx_charge_gas(env, iter_gas + log_gas)?;
+        tx_charge_gas_as(env, GasCategory::StorageRead, iter_gas + log_gas)?;
         match log_val {
             Some(write_log::StorageModification::Write { ref value }) => {
                 let key_val = borsh::to_vec(&KeyVal {
@@ -838,11 +971,16 @@ where
 
     check_address_existence(env, &key)?;
 
+    let traced_value = tx_is_traced(env).then(|| value.clone());
     let write_log = unsafe { env.ctx.write_log.get() };
     let (gas, _size_diff) = write_log
         .write(&key, value)
         .map_err(TxRuntimeError::StorageModificationError)?;
-    tx_charge_gas(env, gas)
+    tx_charge_gas_as(env, GasCategory::StorageWrite, gas)?;
+    if let Some(value) = traced_value {
+        tx_trace(env, || HostCall::Write { key, value });
+    }
+    Ok(())
 }
 
 /// Temporary storage write function exposed to the wasm VM Tx environment. The
@@ -878,11 +1016,16 @@ where
 
     check_address_existence(env, &key)?;
 
+    let traced_value = tx_is_traced(env).then(|| value.clone());
     let write_log = unsafe { env.ctx.write_log.get() };
     let (gas, _size_diff) = write_log
         .write_temp(&key, value)
         .map_err(TxRuntimeError::StorageModificationError)?;
-    tx_charge_gas(env, gas)
+    tx_charge_gas_as(env, GasCategory::StorageWrite, gas)?;
+    if let Some(value) = traced_value {
+        tx_trace(env, || HostCall::WriteTemp { key, value });
+    }
+    Ok(())
 }
 
 fn check_address_existence<MEM, DB, H, CA>(
@@ -915,14 +1058,14 @@ where
         }
         let vp_key = Key::validity_predicate(&addr);
         let (vp, gas) = write_log.read(&vp_key);
-        tx_charge_gas(env, gas)?;
+        tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
         // just check the existence because the write log should not have the
         // delete log of the VP
         if vp.is_none() {
             let (is_present, gas) = storage
                 .has_key(&vp_key)
                 .map_err(TxRuntimeError::StorageError)?;
-            tx_charge_gas(env, gas)?;
+            tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
             if !is_present {
                 tracing::info!(
                     "Trying to write into storage with a key containing an \
@@ -968,7 +1111,9 @@ where
     let (gas, _size_diff) = write_log
         .delete(&key)
         .map_err(TxRuntimeError::StorageModificationError)?;
-    tx_charge_gas(env, gas)
+    tx_charge_gas_as(env, GasCategory::StorageWrite, gas)?;
+    tx_trace(env, || HostCall::Delete { key });
+    Ok(())
 }
 
 /// Emitting an IBC event function exposed to the wasm VM Tx environment.
@@ -996,6 +1141,32 @@ where
     tx_charge_gas(env, gas)
 }
 
+/// Emitting the event of an applied leg of a multi-transfer function exposed
+/// to the wasm VM Tx environment. The given event will be set to the write
+/// log.
+pub fn tx_emit_transfer_leg_event<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    event_ptr: u64,
+    event_len: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (event, gas) = env
+        .memory
+        .read_bytes(event_ptr, event_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let event: TransferLegEvent = BorshDeserialize::try_from_slice(&event)
+        .map_err(TxRuntimeError::EncodingError)?;
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let gas = write_log.emit_transfer_leg_event(event);
+    tx_charge_gas(env, gas)
+}
+
 /// Getting an IBC event function exposed to the wasm VM Tx environment.
 pub fn tx_get_ibc_events<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -1068,6 +1239,10 @@ where
         key,
         value,
     );
+    vp_trace(env, || HostCall::ReadPre {
+        key,
+        value: value.clone(),
+    });
     Ok(match value {
         Some(value) => {
             let len: i64 = value
@@ -1117,6 +1292,10 @@ where
     let write_log = unsafe { env.ctx.write_log.get() };
     let value =
         vp_host_fns::read_post(gas_meter, storage, write_log, &key, sentinel)?;
+    vp_trace(env, || HostCall::ReadPost {
+        key,
+        value: value.clone(),
+    });
     Ok(match value {
         Some(value) => {
             let len: i64 = value
@@ -1163,6 +1342,10 @@ where
         Key::parse(key).map_err(vp_host_fns::RuntimeError::StorageDataError)?;
     let write_log = unsafe { env.ctx.write_log.get() };
     let value = vp_host_fns::read_temp(gas_meter, write_log, &key, sentinel)?;
+    vp_trace(env, || HostCall::ReadTemp {
+        key,
+        value: value.clone(),
+    });
     Ok(match value {
         Some(value) => {
             let len: i64 = value
@@ -1238,6 +1421,7 @@ where
     let present = vp_host_fns::has_key_pre(
         gas_meter, storage, write_log, &key, sentinel,
     )?;
+    vp_trace(env, || HostCall::HasKeyPre { key, present });
     Ok(HostEnvResult::from(present).to_i64())
 }
 
@@ -1273,6 +1457,7 @@ where
     let present = vp_host_fns::has_key_post(
         gas_meter, storage, write_log, &key, sentinel,
     )?;
+    vp_trace(env, || HostCall::HasKeyPost { key, present });
     Ok(HostEnvResult::from(present).to_i64())
 }
 
@@ -1310,6 +1495,9 @@ where
     let iter = vp_host_fns::iter_prefix_pre(
         gas_meter, write_log, storage, &prefix, sentinel,
     )?;
+    vp_trace(env, || HostCall::IterPrefixPre {
+        prefix: prefix.clone(),
+    });
 
     let iterators = unsafe { env.ctx.iterators.get() };
     Ok(iterators.insert(iter).id())
@@ -1349,6 +1537,9 @@ where
     let iter = vp_host_fns::iter_prefix_post(
         gas_meter, write_log, storage, &prefix, sentinel,
     )?;
+    vp_trace(env, || HostCall::IterPrefixPost {
+        prefix: prefix.clone(),
+    });
 
     let iterators = unsafe { env.ctx.iterators.get() };
     Ok(iterators.insert(iter).id())
@@ -1420,6 +1611,9 @@ where
     // This is not a storage write, use the same multiplier used for a storage
     // read
     tx_charge_gas(env, addr_len * MEMORY_ACCESS_GAS_PER_BYTE)?;
+    tx_trace(env, || HostCall::InsertVerifier {
+        address: addr.clone(),
+    });
     verifiers.insert(addr);
 
     Ok(())
@@ -1467,11 +1661,16 @@ where
 
     tx_validate_vp_code_hash(env, &code_hash, &code_tag)?;
 
+    let traced_value = tx_is_traced(env).then(|| code_hash.clone());
     let write_log = unsafe { env.ctx.write_log.get() };
     let (gas, _size_diff) = write_log
         .write(&key, code_hash)
         .map_err(TxRuntimeError::StorageModificationError)?;
-    tx_charge_gas(env, gas)
+    tx_charge_gas_as(env, GasCategory::StorageWrite, gas)?;
+    if let Some(value) = traced_value {
+        tx_trace(env, || HostCall::Write { key, value });
+    }
+    Ok(())
 }
 
 /// Initialize a new account established address.
@@ -1513,12 +1712,14 @@ where
         .map_err(|e| TxRuntimeError::InvalidVpCodeHash(e.to_string()))?;
     let (addr, gas) = write_log.init_account(&storage.address_gen, code_hash);
     let addr_bytes = addr.serialize_to_vec();
-    tx_charge_gas(env, gas)?;
+    tx_charge_gas_as(env, GasCategory::StorageWrite, gas)?;
     let gas = env
         .memory
         .write_bytes(result_ptr, addr_bytes)
         .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
-    tx_charge_gas(env, gas)
+    tx_charge_gas(env, gas)?;
+    tx_trace(env, || HostCall::InitAccount { address: addr });
+    Ok(())
 }
 
 /// Getting the chain ID function exposed to the wasm VM Tx environment.
@@ -1997,7 +2198,12 @@ where
         &Some(signer),
         threshold,
         max_signatures,
-        || gas_meter.consume(gas::VERIFY_TX_SIG_GAS),
+        |pk| {
+            gas_meter.consume_as(
+                GasCategory::SignatureVerification,
+                gas::verify_tx_sig_gas(pk),
+            )
+        },
     ) {
         Ok(_) => Ok(HostEnvResult::Success.to_i64()),
         Err(err) => match err {
@@ -2033,6 +2239,7 @@ where
         .read_string(str_ptr, str_len as _)
         .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
     tracing::info!("WASM Transaction log: {}", str);
+    tx_trace(env, || HostCall::Log { message: str });
     Ok(())
 }
 
@@ -2051,7 +2258,9 @@ where
     use std::cell::RefCell;
     use std::rc::Rc;
 
-    use namada_ibc::{IbcActions, TransferModule};
+    use namada_ibc::{
+        IbcActions, IcaControllerModule, IcaHostModule, TransferModule,
+    };
 
     let tx_data = unsafe { env.ctx.tx.get().data() }.ok_or_else(|| {
         let sentinel = unsafe { env.ctx.sentinel.get() };
@@ -2060,8 +2269,12 @@ where
     })?;
     let ctx = Rc::new(RefCell::new(env.ctx.clone()));
     let mut actions = IbcActions::new(ctx.clone());
-    let module = TransferModule::new(ctx);
+    let module = TransferModule::new(ctx.clone());
     actions.add_transfer_module(module.module_id(), module);
+    let module = IcaHostModule::new(ctx.clone());
+    actions.add_ica_host_module(module.module_id(), module);
+    let module = IcaControllerModule::new(ctx);
+    actions.add_ica_controller_module(module.module_id(), module);
     actions.execute(&tx_data)?;
 
     Ok(())
@@ -2089,7 +2302,7 @@ where
         let (result, gas) = storage
             .read(&hash_key)
             .map_err(TxRuntimeError::StorageError)?;
-        tx_charge_gas(env, gas)?;
+        tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
         if let Some(tag_hash) = result {
             let tag_hash = Hash::try_from(&tag_hash[..]).map_err(|e| {
                 TxRuntimeError::InvalidVpCodeHash(e.to_string())
@@ -2112,13 +2325,13 @@ where
     let code_key = Key::wasm_code(&code_hash);
     let write_log = unsafe { env.ctx.write_log.get() };
     let (result, gas) = write_log.read(&code_key);
-    tx_charge_gas(env, gas)?;
+    tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
     if result.is_none() {
         let storage = unsafe { env.ctx.storage.get() };
         let (is_present, gas) = storage
             .has_key(&code_key)
             .map_err(TxRuntimeError::StorageError)?;
-        tx_charge_gas(env, gas)?;
+        tx_charge_gas_as(env, GasCategory::StorageRead, gas)?;
         if !is_present {
             return Err(TxRuntimeError::InvalidVpCodeHash(
                 "The corresponding VP code doesn't exist".to_string(),
@@ -2198,7 +2411,12 @@ where
         &None,
         threshold,
         max_signatures,
-        || gas_meter.consume(gas::VERIFY_TX_SIG_GAS),
+        |pk| {
+            gas_meter.consume_as(
+                GasCategory::SignatureVerification,
+                gas::verify_tx_sig_gas(pk),
+            )
+        },
     ) {
         Ok(_) => Ok(HostEnvResult::Success.to_i64()),
         Err(err) => match err {
@@ -2290,9 +2508,12 @@ where
     })?);
 
     let eval_runner = unsafe { env.ctx.eval_runner.get() };
-    Ok(eval_runner
-        .eval(env.ctx.clone(), vp_code_hash, input_data)
-        .to_i64())
+    let result = eval_runner.eval(env.ctx.clone(), vp_code_hash, input_data);
+    vp_trace(env, || HostCall::Eval {
+        vp_code_hash,
+        accepted: result == HostEnvResult::Success,
+    });
+    Ok(result.to_i64())
 }
 
 /// Get the native token's address
@@ -2340,6 +2561,7 @@ where
         .read_string(str_ptr, str_len as _)
         .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
     tracing::info!("WASM Validity predicate log: {}", str);
+    vp_trace(env, || HostCall::Log { message: str });
     Ok(())
 }
 
@@ -2626,6 +2848,13 @@ where
         crate::token::utils::update_note_commitment_tree(self, shielded)
     }
 
+    fn charge_gas(
+        &mut self,
+        used_gas: u64,
+    ) -> Result<(), namada_state::StorageError> {
+        ibc_tx_charge_gas(self, used_gas)
+    }
+
     fn mint_token(
         &mut self,
         target: &Address,
@@ -2697,7 +2926,9 @@ where
 {
     let gas_meter = unsafe { ctx.gas_meter.get() };
     // if we run out of gas, we need to stop the execution
-    let result = gas_meter.consume(used_gas).into_storage_result();
+    let result = gas_meter
+        .consume_as(GasCategory::Ibc, used_gas)
+        .into_storage_result();
     if let Err(err) = &result {
         let sentinel = unsafe { ctx.sentinel.get() };
         sentinel.set_out_of_gas();
@@ -2740,6 +2971,7 @@ pub mod testing {
         tx: &Tx,
         tx_index: &TxIndex,
         result_buffer: &mut Option<Vec<u8>>,
+        trace: &mut Option<Vec<TraceEntry>>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
         #[cfg(feature = "wasm-runtime")] tx_wasm_cache: &mut TxCache<CA>,
     ) -> TxVmEnv<'static, NativeMemory, DB, H, CA>
@@ -2759,6 +2991,7 @@ pub mod testing {
             tx_index,
             verifiers,
             result_buffer,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -2781,6 +3014,7 @@ pub mod testing {
         result_buffer: &mut Option<Vec<u8>>,
         keys_changed: &BTreeSet<Key>,
         eval_runner: &EVAL,
+        trace: &mut Option<Vec<TraceEntry>>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
     ) -> VpVmEnv<'static, NativeMemory, DB, H, EVAL, CA>
     where
@@ -2803,6 +3037,7 @@ pub mod testing {
             result_buffer,
             keys_changed,
             eval_runner,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
         )
@@ -0,0 +1,355 @@
+//! Bundles of ahead-of-time compiled WASM modules.
+//!
+//! A bundle holds the compiled modules of the whitelisted txs and VPs,
+//! serialized for the host target. It can be loaded into the file system
+//! caches of a fresh node, so that it doesn't have to compile the WASMs
+//! while processing its first blocks. The modules are only usable with the
+//! same wasmer version and CPU features they were compiled with, so a
+//! bundle built for a different configuration is rejected.
+//!
+//! The compiled modules are native code which is loaded without validation,
+//! so a bundle grants its builder arbitrary code execution on the node. It
+//! must only be loaded from a trusted source, e.g. when built by the node's
+//! operator. Only the modules of whitelisted WASMs are accepted, to not
+//! fill the caches with modules that would never be used.
+
+use std::fs;
+use std::path::Path;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use thiserror::Error;
+use wasmer::{Module, Target};
+
+use super::common::{compile, file_write_module, hash_of_code, store};
+use crate::types::hash::Hash;
+use crate::vm::wasm;
+
+/// The version of the bundle format
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Failed to read or write the bundle: {0}")]
+    Io(std::io::Error),
+    #[error("Failed to decode the bundle: {0}")]
+    Decoding(std::io::Error),
+    #[error("Unsupported bundle version {found}, expected {expected}")]
+    Version { expected: u32, found: u32 },
+    #[error("The bundle was compiled for {found}, but the host is {expected}")]
+    Configuration { expected: String, found: String },
+    #[error("Checksum mismatch of the compiled module of {0}")]
+    Checksum(Hash),
+    #[error("The compiled module of {0} is not of a whitelisted WASM")]
+    NotWhitelisted(Hash),
+    #[error("Failed to compile the WASM {0}: {1}")]
+    Compile(Hash, wasm::run::Error),
+    #[error("Failed to serialize the compiled module of {0}: {1}")]
+    Serialize(Hash, String),
+    #[error("Failed to deserialize the compiled module of {0}: {1}")]
+    Deserialize(Hash, String),
+}
+
+/// The configuration that the modules of a bundle were compiled for
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct BundleHeader {
+    /// The version of the bundle format
+    pub version: u32,
+    /// The version of wasmer used to compile the modules
+    pub wasmer_version: String,
+    /// The target triple
+    pub target: String,
+    /// The enabled CPU features of the target
+    pub cpu_features: Vec<String>,
+}
+
+impl BundleHeader {
+    /// The configuration of the host
+    pub fn host() -> Self {
+        let target = Target::default();
+        Self {
+            version: BUNDLE_VERSION,
+            wasmer_version: wasmer::VERSION.to_string(),
+            target: target.triple().to_string(),
+            cpu_features: target
+                .cpu_features()
+                .iter()
+                .map(|feature| format!("{feature:?}"))
+                .collect(),
+        }
+    }
+
+    /// Describe the compilation configuration
+    fn configuration(&self) -> String {
+        format!(
+            "wasmer {} on {} with CPU features [{}]",
+            self.wasmer_version,
+            self.target,
+            self.cpu_features.join(", ")
+        )
+    }
+}
+
+/// The kind of a compiled WASM
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub enum ArtifactKind {
+    /// A transaction
+    Tx,
+    /// A validity predicate
+    Vp,
+}
+
+/// The whitelisted code hashes of the txs and VPs, as hex strings. An empty
+/// whitelist allows any WASM.
+#[derive(Clone, Debug, Default)]
+pub struct Whitelists {
+    /// The whitelisted txs
+    pub tx: Vec<String>,
+    /// The whitelisted VPs
+    pub vp: Vec<String>,
+}
+
+impl Whitelists {
+    /// Check if the WASM of the given kind and code hash is whitelisted
+    pub fn allows(&self, kind: ArtifactKind, code_hash: &Hash) -> bool {
+        let whitelist = match kind {
+            ArtifactKind::Tx => &self.tx,
+            ArtifactKind::Vp => &self.vp,
+        };
+        let code_hash = code_hash.to_string();
+        whitelist.is_empty()
+            || whitelist
+                .iter()
+                .any(|hash| hash.eq_ignore_ascii_case(&code_hash))
+    }
+}
+
+/// A compiled WASM module
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct Artifact {
+    /// The kind of the WASM
+    pub kind: ArtifactKind,
+    /// The hash of the WASM code
+    pub code_hash: Hash,
+    /// The hash of the serialized module
+    pub checksum: Hash,
+    /// The serialized module
+    pub module: Vec<u8>,
+}
+
+/// A bundle of compiled WASM modules
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct Bundle {
+    /// The configuration the modules were compiled for
+    pub header: BundleHeader,
+    /// The compiled modules
+    pub artifacts: Vec<Artifact>,
+}
+
+impl Default for Bundle {
+    fn default() -> Self {
+        Self {
+            header: BundleHeader::host(),
+            artifacts: vec![],
+        }
+    }
+}
+
+impl Bundle {
+    /// Compile the given WASM code for the host and add it to the bundle
+    pub fn compile(
+        &mut self,
+        kind: ArtifactKind,
+        code: impl AsRef<[u8]>,
+    ) -> Result<(), Error> {
+        let code_hash = hash_of_code(&code);
+        let (module, _store) = wasm::run::prepare_wasm_code(code)
+            .and_then(compile)
+            .map_err(|err| Error::Compile(code_hash, err))?;
+        let module = module
+            .serialize()
+            .map_err(|err| Error::Serialize(code_hash, err.to_string()))?;
+        self.artifacts.push(Artifact {
+            kind,
+            code_hash,
+            checksum: Hash::sha256(&module),
+            module,
+        });
+        Ok(())
+    }
+
+    /// Write the bundle to a file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.serialize_to_vec()).map_err(Error::Io)
+    }
+
+    /// Read a bundle from a file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let bytes = fs::read(path).map_err(Error::Io)?;
+        Self::try_from_slice(&bytes).map_err(Error::Decoding)
+    }
+
+    /// Check that the bundle was compiled for the host, that the checksums
+    /// of its modules are valid and that they are all of whitelisted WASMs.
+    /// The checksums only guard against corruption, not against tampering.
+    pub fn verify(&self, whitelists: &Whitelists) -> Result<(), Error> {
+        if self.header.version != BUNDLE_VERSION {
+            return Err(Error::Version {
+                expected: BUNDLE_VERSION,
+                found: self.header.version,
+            });
+        }
+        let host = BundleHeader::host();
+        if self.header != host {
+            return Err(Error::Configuration {
+                expected: host.configuration(),
+                found: self.header.configuration(),
+            });
+        }
+        for artifact in &self.artifacts {
+            if Hash::sha256(&artifact.module) != artifact.checksum {
+                return Err(Error::Checksum(artifact.code_hash));
+            }
+            if !whitelists.allows(artifact.kind, &artifact.code_hash) {
+                return Err(Error::NotWhitelisted(artifact.code_hash));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the bundle and write its modules into the file system caches
+    /// of txs and VPs. Returns the number of loaded modules.
+    ///
+    /// # Safety
+    ///
+    /// The bundle must come from a trusted source. Its modules are native
+    /// code that is deserialized without validation and that is run in
+    /// place of the WASMs with the same code hashes.
+    pub unsafe fn load(
+        &self,
+        whitelists: &Whitelists,
+        tx_cache_dir: impl AsRef<Path>,
+        vp_cache_dir: impl AsRef<Path>,
+    ) -> Result<usize, Error> {
+        self.verify(whitelists)?;
+        let store = store();
+        for artifact in &self.artifacts {
+            // Deserializing checks that the module is usable by this wasmer
+            let module = Module::deserialize(&store, &artifact.module[..])
+                .map_err(|err| {
+                    Error::Deserialize(artifact.code_hash, err.to_string())
+                })?;
+            let dir = match artifact.kind {
+                ArtifactKind::Tx => tx_cache_dir.as_ref(),
+                ArtifactKind::Vp => vp_cache_dir.as_ref(),
+            };
+            fs::create_dir_all(dir).map_err(Error::Io)?;
+            file_write_module(dir, &module, &artifact.code_hash);
+        }
+        Ok(self.artifacts.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada_test_utils::TestWasms;
+    use tempfile::tempdir;
+
+    use super::super::common::module_file_exists;
+    use super::*;
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let code = TestWasms::TxNoOp.read_bytes();
+        let code_hash = hash_of_code(&code);
+        let mut bundle = Bundle::default();
+        bundle.compile(ArtifactKind::Tx, &code).unwrap();
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bundle");
+        bundle.write(&path).unwrap();
+        let bundle = Bundle::read(&path).unwrap();
+
+        let tx_dir = dir.path().join("tx");
+        let vp_dir = dir.path().join("vp");
+        let whitelists = Whitelists {
+            tx: vec![code_hash.to_string().to_lowercase()],
+            vp: vec![],
+        };
+        let loaded = unsafe { bundle.load(&whitelists, &tx_dir, &vp_dir) };
+        assert_eq!(loaded.unwrap(), 1);
+        assert!(module_file_exists(&tx_dir, &code_hash));
+        assert!(!module_file_exists(&vp_dir, &code_hash));
+    }
+
+    #[test]
+    fn test_bundle_whitelists() {
+        let code = TestWasms::TxNoOp.read_bytes();
+        let code_hash = hash_of_code(&code);
+        let other_hash = Hash::sha256(b"other");
+        let mut bundle = Bundle::default();
+        bundle.compile(ArtifactKind::Tx, code).unwrap();
+
+        // empty whitelists allow any WASM
+        assert_matches!(bundle.verify(&Whitelists::default()), Ok(()));
+        let whitelists = Whitelists {
+            tx: vec![code_hash.to_string().to_lowercase()],
+            vp: vec![other_hash.to_string()],
+        };
+        assert_matches!(bundle.verify(&whitelists), Ok(()));
+
+        let whitelists = Whitelists {
+            tx: vec![other_hash.to_string()],
+            vp: vec![],
+        };
+        assert_matches!(
+            bundle.verify(&whitelists),
+            Err(Error::NotWhitelisted(hash)) if hash == code_hash
+        );
+        let dir = tempdir().unwrap();
+        let tx_dir = dir.path().join("tx");
+        let vp_dir = dir.path().join("vp");
+        let loaded = unsafe { bundle.load(&whitelists, &tx_dir, &vp_dir) };
+        assert_matches!(loaded, Err(Error::NotWhitelisted(_)));
+        assert!(!module_file_exists(&tx_dir, &code_hash));
+
+        // the whitelist of the VPs doesn't apply to txs
+        let whitelists = Whitelists {
+            tx: vec![other_hash.to_string()],
+            vp: vec![code_hash.to_string()],
+        };
+        assert_matches!(
+            bundle.verify(&whitelists),
+            Err(Error::NotWhitelisted(_))
+        );
+    }
+
+    #[test]
+    fn test_bundle_rejected() {
+        let code = TestWasms::TxNoOp.read_bytes();
+        let mut bundle = Bundle::default();
+        bundle.compile(ArtifactKind::Tx, code).unwrap();
+
+        let whitelists = Whitelists::default();
+        let mut tampered = bundle.clone();
+        tampered.artifacts[0].module.push(0);
+        assert_matches!(tampered.verify(&whitelists), Err(Error::Checksum(_)));
+
+        let mut other_target = bundle.clone();
+        other_target.header.cpu_features.push("Unknown".to_string());
+        assert_matches!(
+            other_target.verify(&whitelists),
+            Err(Error::Configuration { .. })
+        );
+
+        let mut other_version = bundle;
+        other_version.header.version += 1;
+        assert_matches!(
+            other_version.verify(&whitelists),
+            Err(Error::Version { .. })
+        );
+    }
+}
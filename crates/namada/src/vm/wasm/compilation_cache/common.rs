@@ -466,11 +466,11 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
     }
 }
 
-fn hash_of_code(code: impl AsRef<[u8]>) -> Hash {
+pub(super) fn hash_of_code(code: impl AsRef<[u8]>) -> Hash {
     Hash::sha256(code.as_ref())
 }
 
-fn compile(
+pub(super) fn compile(
     code: impl AsRef<[u8]>,
 ) -> Result<(Module, Store), wasm::run::Error> {
     // There's an issue with dylib compiler on mac in linker and on linux
@@ -485,13 +485,17 @@ fn file_ext() -> &'static str {
     universal::FILE_EXT
 }
 
-fn store() -> Store {
+pub(super) fn store() -> Store {
     // This has to be using the store matching the compilation method in the
     // `fn compile`
     universal::store()
 }
 
-fn file_write_module(dir: impl AsRef<Path>, module: &Module, hash: &Hash) {
+pub(super) fn file_write_module(
+    dir: impl AsRef<Path>,
+    module: &Module,
+    hash: &Hash,
+) {
     use wasmer_cache::Cache;
     let mut fs_cache = fs_cache(dir, hash);
     fs_cache.store(CacheHash::new(hash.0), module).unwrap();
@@ -522,7 +526,7 @@ fn fs_cache(dir: impl AsRef<Path>, hash: &Hash) -> FileSystemCache {
     fs_cache
 }
 
-fn module_file_exists(dir: impl AsRef<Path>, hash: &Hash) -> bool {
+pub(super) fn module_file_exists(dir: impl AsRef<Path>, hash: &Hash) -> bool {
     let file =
         dir.as_ref()
             .join(hash.to_string().to_lowercase())
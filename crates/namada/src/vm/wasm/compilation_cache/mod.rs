@@ -1,5 +1,6 @@
 //! WASM compilation cache for transactions and VPs.
 
+pub mod bundle;
 pub mod common;
 pub mod tx;
 pub mod vp;
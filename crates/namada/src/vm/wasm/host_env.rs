@@ -60,7 +60,7 @@ where
         "env" => {
             "memory" => initial_memory,
             // Wasm middleware gas injection hook
-            "gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_charge_gas),
+            "gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_charge_memory_gas),
             // Whitelisted gas exposed function, we need two different functions just because of colliding names in the vm_host_env macro to generate implementations
             "namada_tx_charge_gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_charge_gas),
             "namada_tx_read" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_read),
@@ -75,6 +75,7 @@ where
             "namada_tx_update_validity_predicate" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_update_validity_predicate),
             "namada_tx_init_account" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_init_account),
             "namada_tx_emit_ibc_event" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_emit_ibc_event),
+            "namada_tx_emit_transfer_leg_event" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_emit_transfer_leg_event),
             "namada_tx_get_ibc_events" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_ibc_events),
             "namada_tx_get_chain_id" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_chain_id),
             "namada_tx_get_tx_index" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_tx_index),
@@ -111,7 +112,7 @@ where
         "env" => {
             "memory" => initial_memory,
             // Wasm middleware gas injection hook
-            "gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_charge_gas),
+            "gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_charge_memory_gas),
             // Whitelisted gas exposed function, we need two different functions just because of colliding names in the vm_host_env macro to generate implementations
             "namada_vp_charge_gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_charge_gas),
             "namada_vp_read_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_pre),
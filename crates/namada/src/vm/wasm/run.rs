@@ -8,6 +8,7 @@ use namada_core::types::validity_predicate::VpSentinel;
 use namada_gas::{GasMetering, TxGasMeter, WASM_MEMORY_PAGE_GAS};
 use namada_state::write_log::StorageModification;
 use namada_state::{State, StorageHasher};
+use namada_tx::data::trace::TraceEntry;
 use namada_tx::data::TxSentinel;
 use namada_tx::{Commitment, Section, Tx};
 use parity_wasm::elements;
@@ -89,7 +90,7 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Execute a transaction code. Returns the set verifiers addresses requested by
-/// the transaction.
+/// the transaction. The host calls are recorded in the given `trace`, if any.
 #[allow(clippy::too_many_arguments)]
 pub fn tx<DB, H, CA>(
     storage: &State<DB, H>,
@@ -99,6 +100,7 @@ pub fn tx<DB, H, CA>(
     tx: &Tx,
     vp_wasm_cache: &mut VpCache<CA>,
     tx_wasm_cache: &mut TxCache<CA>,
+    trace: &mut Option<Vec<TraceEntry>>,
 ) -> Result<BTreeSet<Address>>
 where
     DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
@@ -169,6 +171,7 @@ where
         tx_index,
         &mut verifiers,
         &mut result_buffer,
+        trace,
         vp_wasm_cache,
         tx_wasm_cache,
     );
@@ -217,7 +220,8 @@ where
 
 /// Execute a validity predicate code. Returns whether the validity
 /// predicate accepted storage modifications performed by the transaction
-/// that triggered the execution.
+/// that triggered the execution. The host calls are recorded in the given
+/// `trace`, if any.
 #[allow(clippy::too_many_arguments)]
 pub fn vp<DB, H, CA>(
     vp_code_hash: Hash,
@@ -230,6 +234,7 @@ pub fn vp<DB, H, CA>(
     keys_changed: &BTreeSet<Key>,
     verifiers: &BTreeSet<Address>,
     mut vp_wasm_cache: VpCache<CA>,
+    trace: &mut Option<Vec<TraceEntry>>,
 ) -> Result<bool>
 where
     DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
@@ -268,6 +273,7 @@ where
         &mut result_buffer,
         keys_changed,
         &eval_runner,
+        trace,
         &mut vp_wasm_cache,
     );
 
@@ -794,6 +800,7 @@ mod tests {
             &outer_tx,
             &mut vp_cache,
             &mut tx_cache,
+            &mut None,
         );
         assert!(result.is_ok(), "Expected success, got {:?}", result);
 
@@ -811,6 +818,7 @@ mod tests {
             &outer_tx,
             &mut vp_cache,
             &mut tx_cache,
+            &mut None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -883,6 +891,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache.clone(),
+            &mut None,
         )
         .unwrap();
         assert!(passed);
@@ -915,6 +924,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            &mut None,
         )
         .unwrap();
 
@@ -967,6 +977,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache.clone(),
+            &mut None,
         );
         assert!(result.is_ok(), "Expected success, got {:?}", result);
 
@@ -987,6 +998,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            &mut None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1033,6 +1045,7 @@ mod tests {
             &outer_tx,
             &mut vp_cache,
             &mut tx_cache,
+            &mut None,
         );
         // Depending on platform, we get a different error from the running out
         // of memory
@@ -1099,6 +1112,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            &mut None,
         );
         // Depending on platform, we get a different error from the running out
         // of memory
@@ -1167,6 +1181,7 @@ mod tests {
             &outer_tx,
             &mut vp_cache,
             &mut tx_cache,
+            &mut None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1225,6 +1240,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            &mut None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1302,6 +1318,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            &mut None,
         )
         .unwrap();
         assert!(!passed);
@@ -1338,6 +1355,7 @@ mod tests {
             &outer_tx,
             &mut vp_cache,
             &mut tx_cache,
+            &mut None,
         )
     }
 
@@ -1438,6 +1456,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            &mut None,
         )
     }
 
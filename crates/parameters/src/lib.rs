@@ -51,6 +51,7 @@ where
         max_tx_bytes,
         epoch_duration,
         max_expected_time_per_block,
+        tx_expiration_window,
         max_proposal_bytes,
         max_block_gas,
         vp_whitelist,
@@ -118,6 +119,10 @@ where
         max_expected_time_per_block,
     )?;
 
+    // write tx expiration window
+    let tx_expiration_window_key = storage::get_tx_expiration_window_key();
+    storage.write(&tx_expiration_window_key, tx_expiration_window)?;
+
     // write implicit vp parameter
     let implicit_vp_key = storage::get_implicit_vp_key();
     // Using `fn write_bytes` here, because implicit_vp code hash doesn't
@@ -173,6 +178,19 @@ where
     storage.write(&key, value)
 }
 
+/// Update the tx_expiration_window parameter in storage. Returns the
+/// parameters and gas cost.
+pub fn update_tx_expiration_window_parameter<S>(
+    storage: &mut S,
+    value: &DurationSecs,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage::get_tx_expiration_window_key();
+    storage.write(&key, value)
+}
+
 /// Update the vp whitelist parameter in storage. Returns the parameters and gas
 /// cost.
 pub fn update_vp_whitelist_parameter<S>(
@@ -306,6 +324,20 @@ where
         .into_storage_result()
 }
 
+/// Read the tx expiration window parameter from store
+pub fn read_tx_expiration_window_parameter<S>(
+    storage: &S,
+) -> namada_storage::Result<DurationSecs>
+where
+    S: StorageRead,
+{
+    let tx_expiration_window_key = storage::get_tx_expiration_window_key();
+    let tx_expiration_window = storage.read(&tx_expiration_window_key)?;
+    tx_expiration_window
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()
+}
+
 /// Read the cost per unit of gas for the provided token
 pub fn read_gas_cost<S>(
     storage: &S,
@@ -370,6 +402,9 @@ where
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
+    // read tx expiration window
+    let tx_expiration_window = read_tx_expiration_window_parameter(storage)?;
+
     let implicit_vp_key = storage::get_implicit_vp_key();
     let value = storage
         .read_bytes(&implicit_vp_key)?
@@ -442,6 +477,7 @@ where
         max_tx_bytes,
         epoch_duration,
         max_expected_time_per_block,
+        tx_expiration_window,
         max_proposal_bytes,
         max_block_gas,
         vp_whitelist,
@@ -36,6 +36,7 @@ struct Keys {
     epochs_per_year: &'static str,
     implicit_vp: &'static str,
     max_expected_time_per_block: &'static str,
+    tx_expiration_window: &'static str,
     tx_whitelist: &'static str,
     vp_whitelist: &'static str,
     max_proposal_bytes: &'static str,
@@ -75,6 +76,11 @@ pub fn is_max_expected_time_per_block_key(key: &Key) -> bool {
     is_max_expected_time_per_block_key_at_addr(key, &ADDRESS)
 }
 
+/// Returns if the key is the tx_expiration_window key.
+pub fn is_tx_expiration_window_key(key: &Key) -> bool {
+    is_tx_expiration_window_key_at_addr(key, &ADDRESS)
+}
+
 /// Returns if the key is the tx_whitelist key.
 pub fn is_tx_whitelist_key(key: &Key) -> bool {
     is_tx_whitelist_key_at_addr(key, &ADDRESS)
@@ -145,6 +151,11 @@ pub fn get_max_expected_time_per_block_key() -> Key {
     get_max_expected_time_per_block_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for tx_expiration_window parameter.
+pub fn get_tx_expiration_window_key() -> Key {
+    get_tx_expiration_window_key_at_addr(ADDRESS)
+}
+
 /// Storage key used for implicit VP parameter.
 pub fn get_implicit_vp_key() -> Key {
     get_implicit_vp_key_at_addr(ADDRESS)
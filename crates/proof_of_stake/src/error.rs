@@ -30,6 +30,8 @@ pub enum InflationError {
 pub enum BecomeValidatorError {
     #[error("The given address {0} is already a validator")]
     AlreadyValidator(Address),
+    #[error("The consensus key cannot be a secp256r1 key")]
+    UnsupportedConsensusKey,
 }
 
 #[allow(missing_docs)]
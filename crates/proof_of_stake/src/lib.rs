@@ -1235,6 +1235,11 @@ where
         ));
     }
 
+    // Tendermint cannot use secp256r1 keys for consensus
+    if let common::PublicKey::Secp256r1(_) = consensus_key {
+        return Err(BecomeValidatorError::UnsupportedConsensusKey.into());
+    }
+
     // This will fail if the key is already being used
     try_insert_consensus_key(storage, consensus_key)?;
 
@@ -1326,7 +1331,7 @@ where
     // Require that the new consensus key is an Ed25519 key
     match consensus_key {
         common::PublicKey::Ed25519(_) => {}
-        common::PublicKey::Secp256k1(_) => {
+        common::PublicKey::Secp256k1(_) | common::PublicKey::Secp256r1(_) => {
             return Err(ConsensusKeyChangeError::MustBeEd25519.into());
         }
     }
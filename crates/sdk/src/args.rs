@@ -8,6 +8,7 @@ use namada_core::types::address::Address;
 use namada_core::types::chain::ChainId;
 use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::hash::Hash;
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::{common, SchemeType};
 use namada_core::types::masp::PaymentAddress;
@@ -23,7 +24,10 @@ use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
 use crate::eth_bridge::bridge_pool;
-use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use crate::ibc::core::host::types::identifiers::{
+    ChannelId, ConnectionId, PortId,
+};
+use crate::masp::{ShieldedHistoryFormat, ShieldedMultiTransferBuilder};
 use crate::signing::SigningTxData;
 use crate::{rpc, tx, Namada};
 
@@ -290,6 +294,126 @@ impl TxTransfer {
     }
 }
 
+/// A single leg of a multi-transfer
+#[derive(Clone, Debug)]
+pub struct TxTransferLeg<C: NamadaTypes = SdkTypes> {
+    /// Leg source address
+    pub source: C::Address,
+    /// Leg target address
+    pub target: C::Address,
+    /// Transferred token address
+    pub token: C::Address,
+    /// Transferred token amount
+    pub amount: InputAmount,
+}
+
+/// Multi-transfer transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxMultiTransfer<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The legs to apply atomically, in order
+    pub legs: Vec<TxTransferLeg<C>>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxMultiTransfer<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxMultiTransfer {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxMultiTransfer<C> {
+    /// The legs to apply atomically, in order
+    pub fn legs(self, legs: Vec<TxTransferLeg<C>>) -> Self {
+        Self { legs, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxMultiTransfer {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &mut self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_multi_transfer(context, self).await
+    }
+}
+
+/// Shielded multi-transfer transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxShieldedMultiTransfer<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The spending keys, outputs and change address of the MASP transaction
+    pub shielded: ShieldedMultiTransferBuilder,
+    /// Native token address
+    pub native_token: C::NativeAddress,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxShieldedMultiTransfer<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxShieldedMultiTransfer {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxShieldedMultiTransfer<C> {
+    /// The spending keys, outputs and change address of the MASP transaction
+    pub fn shielded(self, shielded: ShieldedMultiTransferBuilder) -> Self {
+        Self { shielded, ..self }
+    }
+
+    /// Native token address
+    pub fn native_token(self, native_token: C::NativeAddress) -> Self {
+        Self {
+            native_token,
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxShieldedMultiTransfer {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &mut self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData, Option<Epoch>)>
+    {
+        tx::build_shielded_multi_transfer(context, self).await
+    }
+}
+
 /// IBC transfer transaction arguments
 #[derive(Clone, Debug)]
 pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
@@ -404,6 +528,161 @@ impl TxIbcTransfer {
     }
 }
 
+/// Transaction to register an interchain account on the host chain
+#[derive(Clone, Debug)]
+pub struct TxRegisterInterchainAccount<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The owner of the interchain account
+    pub owner: C::Address,
+    /// The connection to the host chain
+    pub connection_id: ConnectionId,
+    /// The channel version. The default version for Namada as the host chain
+    /// is used if it isn't given.
+    pub version: Option<String>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxRegisterInterchainAccount<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxRegisterInterchainAccount {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxRegisterInterchainAccount<C> {
+    /// The owner of the interchain account
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The connection to the host chain
+    pub fn connection_id(self, connection_id: ConnectionId) -> Self {
+        Self {
+            connection_id,
+            ..self
+        }
+    }
+
+    /// The channel version
+    pub fn version(self, version: String) -> Self {
+        Self {
+            version: Some(version),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxRegisterInterchainAccount {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_ibc_register_account(context, self).await
+    }
+}
+
+/// Transaction to send transactions to be executed by the interchain account
+/// on the host chain
+#[derive(Clone, Debug)]
+pub struct TxSendInterchainTx<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The owner of the interchain account
+    pub owner: C::Address,
+    /// The connection to the host chain
+    pub connection_id: ConnectionId,
+    /// The transactions encoded in the host chain encoding
+    pub data: C::Data,
+    /// Timeout timestamp offset
+    pub timeout_sec_offset: Option<u64>,
+    /// Memo
+    pub memo: Option<String>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxSendInterchainTx<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxSendInterchainTx {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxSendInterchainTx<C> {
+    /// The owner of the interchain account
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The connection to the host chain
+    pub fn connection_id(self, connection_id: ConnectionId) -> Self {
+        Self {
+            connection_id,
+            ..self
+        }
+    }
+
+    /// The transactions encoded in the host chain encoding
+    pub fn data(self, data: C::Data) -> Self {
+        Self { data, ..self }
+    }
+
+    /// Timeout timestamp offset
+    pub fn timeout_sec_offset(self, timeout_sec_offset: u64) -> Self {
+        Self {
+            timeout_sec_offset: Some(timeout_sec_offset),
+            ..self
+        }
+    }
+
+    /// Memo
+    pub fn memo(self, memo: String) -> Self {
+        Self {
+            memo: Some(memo),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxSendInterchainTx {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_ibc_send_interchain_tx(context, self).await
+    }
+}
+
 /// Transaction to initialize create a new proposal
 #[derive(Clone, Debug)]
 pub struct InitProposal<C: NamadaTypes = SdkTypes> {
@@ -1166,6 +1445,21 @@ pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
 pub struct QueryPgf<C: NamadaTypes = SdkTypes> {
     /// Common query args
     pub query: Query<C>,
+    /// Show the PGF payment history instead of the current state
+    pub history: bool,
+    /// Restrict the history to this epoch
+    pub epoch: Option<Epoch>,
+}
+
+/// Query IBC rate limits
+#[derive(Clone, Debug)]
+pub struct QueryIbcRateLimit<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The channel on this chain
+    pub channel_id: Option<ChannelId>,
+    /// Address of a token
+    pub token: Option<C::Address>,
 }
 
 /// Withdraw arguments
@@ -1295,6 +1589,9 @@ pub struct QueryBalance<C: NamadaTypes = SdkTypes> {
     pub token: Option<C::Address>,
     /// Whether not to convert balances
     pub no_conversions: bool,
+    /// Verify the response with the light client instead of trusting the
+    /// node
+    pub light: bool,
 }
 
 /// Query historical transfer(s)
@@ -1308,6 +1605,19 @@ pub struct QueryTransfers<C: NamadaTypes = SdkTypes> {
     pub token: Option<C::Address>,
 }
 
+/// Export the shielded history of a viewing key
+#[derive(Clone, Debug)]
+pub struct ExportShieldedHistory<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The viewing key whose history is exported
+    pub viewing_key: C::ViewingKey,
+    /// The format of the export
+    pub format: ShieldedHistoryFormat,
+    /// The file to write the export to, instead of the standard output
+    pub output: Option<PathBuf>,
+}
+
 /// Query PoS bond(s)
 #[derive(Clone, Debug)]
 pub struct QueryBonds<C: NamadaTypes = SdkTypes> {
@@ -1668,6 +1978,123 @@ impl ResignSteward {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Submit a steward payment batch args
+pub struct SubmitPgfBatch<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Steward address
+    pub steward: C::Address,
+    /// The payments of the batch
+    pub payments: C::Data,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for SubmitPgfBatch<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        SubmitPgfBatch {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> SubmitPgfBatch<C> {
+    /// Steward address
+    pub fn steward(self, steward: C::Address) -> Self {
+        Self { steward, ..self }
+    }
+
+    /// The payments of the batch
+    pub fn payments(self, payments: C::Data) -> Self {
+        Self { payments, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl SubmitPgfBatch {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_submit_pgf_batch(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Challenge a steward payment batch args
+pub struct ChallengePgfBatch<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address
+    pub challenger: C::Address,
+    /// The id of the challenged batch
+    pub batch_id: u64,
+    /// The content of the governance proposal created for the batch
+    pub content: C::Data,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for ChallengePgfBatch<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        ChallengePgfBatch {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> ChallengePgfBatch<C> {
+    /// Validator address
+    pub fn challenger(self, challenger: C::Address) -> Self {
+        Self { challenger, ..self }
+    }
+
+    /// The id of the challenged batch
+    pub fn batch_id(self, batch_id: u64) -> Self {
+        Self { batch_id, ..self }
+    }
+
+    /// The content of the governance proposal created for the batch
+    pub fn content(self, content: C::Data) -> Self {
+        Self { content, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl ChallengePgfBatch {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_challenge_pgf_batch(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Re-activate a jailed validator args
 pub struct TxUnjailValidator<C: NamadaTypes = SdkTypes> {
@@ -1890,6 +2317,20 @@ pub struct QueryRawBytes<C: NamadaTypes = SdkTypes> {
     pub storage_key: storage::Key,
     /// Common query args
     pub query: Query<C>,
+    /// Verify the response with the light client instead of trusting the
+    /// node
+    pub light: bool,
+}
+
+/// Initialize the light client from a trusted checkpoint
+#[derive(Clone, Debug)]
+pub struct LightClientInit<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The height of the trusted block
+    pub trust_height: storage::BlockHeight,
+    /// The hash of the trusted block
+    pub trust_hash: Hash,
 }
 
 /// Common transaction arguments
@@ -1899,6 +2340,8 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub dry_run: bool,
     /// Simulate applying both the wrapper and inner transactions
     pub dry_run_wrapper: bool,
+    /// Trace the execution of the transaction and its VPs when simulating it
+    pub trace: bool,
     /// Dump the transaction bytes to file
     pub dump_tx: bool,
     /// The output directory path to where serialize the data
@@ -1963,6 +2406,10 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// Trace the execution of the transaction and its VPs when simulating it
+    fn trace(self, trace: bool) -> Self {
+        self.tx(|x| Tx { trace, ..x })
+    }
     /// Dump the transaction bytes to file
     fn dump_tx(self, dump_tx: bool) -> Self {
         self.tx(|x| Tx { dump_tx, ..x })
@@ -2214,6 +2661,14 @@ pub struct KeyAddressRemove {
     pub do_it: bool,
 }
 
+/// Wallet store encryption arguments
+#[derive(Clone, Debug)]
+pub struct StoreEncrypt {
+    /// Remove the encryption of the whole store instead of setting a new
+    /// passphrase
+    pub decrypt: bool,
+}
+
 /// Generate payment address arguments
 #[derive(Clone, Debug)]
 pub struct PayAddressGen<C: NamadaTypes = SdkTypes> {
@@ -2348,6 +2803,104 @@ impl EthereumBridgePool {
     }
 }
 
+/// Cancel a transfer which is pending in the Ethereum bridge pool.
+#[derive(Clone, Debug)]
+pub struct CancelEthBridgePoolTransfer<C: NamadaTypes = SdkTypes> {
+    /// The args for building a tx to the bridge pool
+    pub tx: Tx<C>,
+    /// The keccak hash of the pending transfer
+    pub transfer: KeccakHash,
+    /// Path to the tx WASM code file
+    pub code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for CancelEthBridgePoolTransfer<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        CancelEthBridgePoolTransfer {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> CancelEthBridgePoolTransfer<C> {
+    /// The keccak hash of the pending transfer
+    pub fn transfer(self, transfer: KeccakHash) -> Self {
+        Self { transfer, ..self }
+    }
+
+    /// Path to the tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+}
+
+impl CancelEthBridgePoolTransfer {
+    /// Build a transaction from this builder
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        bridge_pool::build_cancel_bridge_pool_transfer_tx(context, self).await
+    }
+}
+
+/// Add gas fees to a transfer which is pending in the Ethereum bridge pool.
+#[derive(Clone, Debug)]
+pub struct BumpEthBridgePoolFee<C: NamadaTypes = SdkTypes> {
+    /// The args for building a tx to the bridge pool
+    pub tx: Tx<C>,
+    /// The keccak hash of the pending transfer
+    pub transfer: KeccakHash,
+    /// The amount of gas fees to add, paid by the original
+    /// fee payer in the original fee token
+    pub fee_amount: InputAmount,
+    /// Path to the tx WASM code file
+    pub code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for BumpEthBridgePoolFee<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        BumpEthBridgePoolFee {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> BumpEthBridgePoolFee<C> {
+    /// The keccak hash of the pending transfer
+    pub fn transfer(self, transfer: KeccakHash) -> Self {
+        Self { transfer, ..self }
+    }
+
+    /// The amount of gas fees to add
+    pub fn fee_amount(self, fee_amount: InputAmount) -> Self {
+        Self { fee_amount, ..self }
+    }
+
+    /// Path to the tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+}
+
+impl BumpEthBridgePoolFee {
+    /// Build a transaction from this builder
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        bridge_pool::build_bump_bridge_pool_fee_tx(context, self).await
+    }
+}
+
 /// Bridge pool proof arguments.
 #[derive(Debug, Clone)]
 pub struct BridgePoolProof<C: NamadaTypes = SdkTypes> {
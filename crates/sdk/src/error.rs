@@ -96,6 +96,13 @@ pub enum QueryError {
     /// synchronizing with the network.
     #[error("Node is still catching up with the network")]
     CatchingUp,
+    /// The storage proof returned by the node doesn't check out against
+    /// the trusted state commitment
+    #[error("Invalid storage proof: {0}")]
+    InvalidProof(String),
+    /// The light client failed to verify the chain's headers
+    #[error("Light client error: {0}")]
+    LightClient(String),
 }
 
 /// Errors that deal with Decoding, Encoding, or Conversions
@@ -174,6 +181,15 @@ pub enum TxSubmitError {
     /// The address is not a valid steward
     #[error("The address {0} is not a valid steward.")]
     InvalidSteward(Address),
+    /// The steward payment batch is not valid
+    #[error("Invalid steward payment batch: {0}.")]
+    InvalidPgfBatch(String),
+    /// The steward payment batch can't be found
+    #[error("Steward payment batch {0} can't be found")]
+    PgfBatchDoesNotExist(u64),
+    /// The steward payment batch can't be challenged
+    #[error("Steward payment batch {0} can't be challenged")]
+    InvalidPgfBatchChallenge(u64),
     /// Rate of epoch change too large for current epoch
     #[error(
         "New rate, {0}, is too large of a change with respect to the \
@@ -385,6 +401,9 @@ pub enum EthereumBridgeError {
     /// Transfer already in pool error.
     #[error("An identical transfer is already present in the Bridge pool")]
     TransferAlreadyInPool,
+    /// Transfer not in pool error.
+    #[error("The transfer {0} is not present in the Bridge pool")]
+    TransferNotInPool(String),
 }
 
 /// Checks if the given error is an invalid viewing key
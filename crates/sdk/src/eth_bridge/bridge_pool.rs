@@ -3,6 +3,7 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 
 use borsh_ext::BorshSerializeExt;
@@ -12,13 +13,17 @@ use futures::future::FutureExt;
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::eth_bridge_pool::{
-    erc20_token_address, GasFee, PendingTransfer, TransferToEthereum,
-    TransferToEthereumKind,
+    erc20_token_address, GasFee, PendingTransfer, PendingTransferUpdate,
+    TransferToEthereum, TransferToEthereumKind,
 };
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::hash::Hash;
 use namada_core::types::keccak::KeccakHash;
+use namada_core::types::key::common;
 use namada_core::types::voting_power::FractionalVotingPower;
-use namada_ethereum_bridge::storage::bridge_pool::get_pending_key;
+use namada_ethereum_bridge::storage::bridge_pool::{
+    get_key_from_hash, get_pending_key,
+};
 use namada_token::storage_key::balance_key;
 use namada_token::Amount;
 use namada_tx::Tx;
@@ -115,6 +120,124 @@ pub async fn build_bridge_pool_tx(
     Ok((tx, signing_data))
 }
 
+/// Craft a transaction that cancels a transfer pending in the Ethereum
+/// bridge pool. It must be signed by the sender of the transfer.
+pub async fn build_cancel_bridge_pool_transfer_tx(
+    context: &impl Namada,
+    args::CancelEthBridgePoolTransfer {
+        tx: tx_args,
+        transfer,
+        code_path,
+    }: args::CancelEthBridgePoolTransfer,
+) -> Result<(Tx, SigningTxData), Error> {
+    let pending = query_pending_transfer(context, &transfer).await?;
+    let sender = pending.transfer.sender;
+    let (tx_code_hash, signing_data) = futures::try_join!(
+        query_wasm_code_hash(context, code_path.to_string_lossy()),
+        aux_signing_data(context, &tx_args, Some(sender.clone()), Some(sender)),
+    )?;
+    let update = PendingTransferUpdate::Cancel {
+        transfer_hash: transfer,
+    };
+    let tx = build_transfer_update_tx(
+        context,
+        &tx_args,
+        &code_path,
+        tx_code_hash,
+        signing_data.fee_payer.clone(),
+        update,
+    )
+    .await?;
+    Ok((tx, signing_data))
+}
+
+/// Craft a transaction that adds gas fees to a transfer pending in the
+/// Ethereum bridge pool. The fees are paid by the original fee payer, in
+/// the original fee token.
+pub async fn build_bump_bridge_pool_fee_tx(
+    context: &impl Namada,
+    args::BumpEthBridgePoolFee {
+        tx: tx_args,
+        transfer,
+        fee_amount,
+        code_path,
+    }: args::BumpEthBridgePoolFee,
+) -> Result<(Tx, SigningTxData), Error> {
+    let pending = query_pending_transfer(context, &transfer).await?;
+    let GasFee { token, payer, .. } = pending.gas_fee;
+    let (fee_denominated, tx_code_hash, signing_data) = futures::try_join!(
+        validate_amount(context, fee_amount, &token, tx_args.force).map(
+            |result| {
+                result.map_err(|e| {
+                    Error::Other(format!(
+                        "Failed to validate Bridge pool fee amount: {e}",
+                    ))
+                })
+            }
+        ),
+        query_wasm_code_hash(context, code_path.to_string_lossy()),
+        aux_signing_data(context, &tx_args, Some(payer.clone()), Some(payer)),
+    )?;
+    let update = PendingTransferUpdate::BumpGasFee {
+        transfer_hash: transfer,
+        amount: fee_denominated.amount(),
+    };
+    let tx = build_transfer_update_tx(
+        context,
+        &tx_args,
+        &code_path,
+        tx_code_hash,
+        signing_data.fee_payer.clone(),
+        update,
+    )
+    .await?;
+    Ok((tx, signing_data))
+}
+
+/// Query a transfer which is pending in the Ethereum bridge pool.
+async fn query_pending_transfer(
+    context: &impl Namada,
+    transfer: &KeccakHash,
+) -> Result<PendingTransfer, Error> {
+    query_storage_value(context.client(), &get_key_from_hash(transfer))
+        .await
+        .map_err(|_| {
+            Error::EthereumBridge(EthereumBridgeError::TransferNotInPool(
+                transfer.to_string(),
+            ))
+        })
+}
+
+/// Craft a transaction that updates a transfer pending in the Ethereum
+/// bridge pool.
+async fn build_transfer_update_tx(
+    context: &impl Namada,
+    tx_args: &args::Tx,
+    code_path: &Path,
+    tx_code_hash: Hash,
+    fee_payer: common::PublicKey,
+    update: PendingTransferUpdate,
+) -> Result<Tx, Error> {
+    let chain_id = tx_args
+        .chain_id
+        .clone()
+        .ok_or_else(|| Error::Other("No chain id available".into()))?;
+
+    let mut tx = Tx::new(chain_id, tx_args.expiration);
+    if let Some(memo) = &tx_args.memo {
+        tx.add_memo(memo);
+    }
+    tx.add_code_from_hash(
+        tx_code_hash,
+        Some(code_path.to_string_lossy().into_owned()),
+    )
+    .add_data(update);
+
+    prepare_tx(context, tx_args, &mut tx, fee_payer, None).await?;
+
+    Ok(tx)
+}
+
 /// Perform client validation checks on a Bridge pool transfer.
 #[allow(clippy::too_many_arguments)]
 async fn validate_bridge_pool_tx(
@@ -931,12 +1054,40 @@ mod recommendations {
             * signature_checks(voting_powers, &bp_root.signatures)
             + valset_fee() * valset_size;
 
+        // account for the gas fees added to transfers after
+        // they were included in the Bridge pool
+        let gas_fee_bumps = RPC
+            .shell()
+            .eth_bridge()
+            .read_gas_fee_bumps(context.client())
+            .await
+            .map_err(|e| {
+                Error::EthereumBridge(EthereumBridgeError::ReadBridgePool(
+                    e.to_string(),
+                ))
+            })?;
+        let mut signed_pool =
+            query_signed_bridge_pool(context.client(), context.io()).await?;
+        for (hash, bump) in gas_fee_bumps {
+            if let Some(pending) = signed_pool.get_mut(&hash.to_string()) {
+                pending.gas_fee.amount =
+                    pending.gas_fee.amount.checked_add(bump).ok_or_else(
+                        || {
+                            Error::Other(format!(
+                                "Overflow adding the gas fee bump of the \
+                                 transfer {hash}"
+                            ))
+                        },
+                    )?;
+            }
+        }
+
         // we don't recommend transfers that have already been relayed
         let eligible = generate_eligible(
             context.io(),
             &args.conversion_table,
             &in_progress,
-            query_signed_bridge_pool(context.client(), context.io()).await?,
+            signed_pool,
         )?;
 
         let max_gas =
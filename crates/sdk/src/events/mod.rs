@@ -10,6 +10,7 @@ use std::str::FromStr;
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::ethereum_structs::{BpTransferStatus, EthBridgeEvent};
 use namada_core::types::ibc::IbcEvent;
+use namada_core::types::token::TransferLegEvent;
 use namada_tx::data::TxType;
 use serde_json::Value;
 
@@ -37,6 +38,9 @@ impl From<&EthBridgeEvent> for Event {
                         match status {
                             BpTransferStatus::Relayed => "bridge_pool_relayed",
                             BpTransferStatus::Expired => "bridge_pool_expired",
+                            BpTransferStatus::Cancelled => {
+                                "bridge_pool_cancelled"
+                            }
                         }
                         .into(),
                     );
@@ -86,6 +90,8 @@ pub enum EventType {
     PgfPayment,
     /// Ethereum Bridge event
     EthereumBridge,
+    /// An applied leg of a multi-transfer
+    TransferLeg,
 }
 
 impl Display for EventType {
@@ -97,6 +103,7 @@ impl Display for EventType {
             EventType::Proposal => write!(f, "proposal"),
             EventType::PgfPayment => write!(f, "pgf_payment"),
             EventType::EthereumBridge => write!(f, "ethereum_bridge"),
+            EventType::TransferLeg => write!(f, "transfer_leg"),
         }?;
         Ok(())
     }
@@ -118,6 +125,7 @@ impl FromStr for EventType {
                 Ok(EventType::Ibc("write_acknowledgement".to_string()))
             }
             "ethereum_bridge" => Ok(EventType::EthereumBridge),
+            "transfer_leg" => Ok(EventType::TransferLeg),
             _ => Err(EventError::InvalidEventType),
         }
     }
@@ -205,6 +213,23 @@ impl From<IbcEvent> for Event {
     }
 }
 
+impl From<&TransferLegEvent> for Event {
+    fn from(event: &TransferLegEvent) -> Self {
+        let TransferLegEvent { index, leg } = event;
+        let mut attributes = HashMap::new();
+        attributes.insert("index".to_string(), index.to_string());
+        attributes.insert("source".to_string(), leg.source.to_string());
+        attributes.insert("target".to_string(), leg.target.to_string());
+        attributes.insert("token".to_string(), leg.token.to_string());
+        attributes.insert("amount".to_string(), leg.amount.to_string());
+        Self {
+            event_type: EventType::TransferLeg,
+            level: EventLevel::Tx,
+            attributes,
+        }
+    }
+}
+
 /// Convert our custom event into the necessary tendermint proto type
 impl From<Event> for crate::tendermint_proto::v0_37::abci::Event {
     fn from(event: Event) -> Self {
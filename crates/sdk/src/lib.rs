@@ -24,6 +24,8 @@ pub mod error;
 pub mod events;
 pub(crate) mod internal_macros;
 pub mod io;
+#[cfg(feature = "light-client")]
+pub mod light_client;
 pub mod queries;
 pub mod wallet;
 
@@ -36,10 +38,13 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use args::{InputAmount, SdkTypes};
-use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use namada_core::ibc::core::host::types::identifiers::{
+    ChannelId, ConnectionId, PortId,
+};
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::*;
 use namada_core::types::masp::{TransferSource, TransferTarget};
 use namada_core::types::token;
@@ -48,7 +53,9 @@ use namada_tx::Tx;
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use crate::io::Io;
-use crate::masp::{ShieldedContext, ShieldedUtils};
+use crate::masp::{
+    ShieldedContext, ShieldedMultiTransferBuilder, ShieldedUtils,
+};
 use crate::rpc::{
     denominate_amount, format_denominated_amount, query_native_token,
 };
@@ -56,14 +63,15 @@ use crate::signing::SigningTxData;
 use crate::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use crate::tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
-    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_BRIDGE_POOL_UPDATE_WASM, TX_BRIDGE_POOL_WASM, TX_CHALLENGE_PGF_BATCH,
+    TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
+    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
+    TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
+    TX_INIT_PROPOSAL, TX_MULTI_TRANSFER_WASM, TX_REACTIVATE_VALIDATOR_WASM,
+    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_SUBMIT_PGF_BATCH,
+    TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL,
+    TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use crate::wallet::{Wallet, WalletIo, WalletStorage};
 
@@ -123,6 +131,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         args::Tx {
             dry_run: false,
             dry_run_wrapper: false,
+            trace: false,
             dump_tx: false,
             output_folder: None,
             force: false,
@@ -166,6 +175,32 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxMultiTransfer builder from the given legs
+    fn new_multi_transfer(
+        &self,
+        legs: Vec<args::TxTransferLeg>,
+    ) -> args::TxMultiTransfer {
+        args::TxMultiTransfer {
+            legs,
+            tx_code_path: PathBuf::from(TX_MULTI_TRANSFER_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxShieldedMultiTransfer builder from the given MASP transaction
+    /// builder
+    fn new_shielded_multi_transfer(
+        &self,
+        shielded: ShieldedMultiTransferBuilder,
+    ) -> args::TxShieldedMultiTransfer {
+        args::TxShieldedMultiTransfer {
+            shielded,
+            native_token: self.native_token(),
+            tx_code_path: PathBuf::from(TX_TRANSFER_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a InitAccount builder from the given minimum set of arguments
     fn new_init_account(
         &self,
@@ -267,6 +302,41 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxRegisterInterchainAccount builder from the given minimum set
+    /// of arguments
+    fn new_register_interchain_account(
+        &self,
+        owner: Address,
+        connection_id: ConnectionId,
+    ) -> args::TxRegisterInterchainAccount {
+        args::TxRegisterInterchainAccount {
+            owner,
+            connection_id,
+            version: None,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_IBC_WASM),
+        }
+    }
+
+    /// Make a TxSendInterchainTx builder from the given minimum set of
+    /// arguments
+    fn new_send_interchain_tx(
+        &self,
+        owner: Address,
+        connection_id: ConnectionId,
+        data: Vec<u8>,
+    ) -> args::TxSendInterchainTx {
+        args::TxSendInterchainTx {
+            owner,
+            connection_id,
+            data,
+            timeout_sec_offset: None,
+            memo: None,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_IBC_WASM),
+        }
+    }
+
     /// Make a InitProposal builder from the given minimum set of arguments
     fn new_init_proposal(&self, proposal_data: Vec<u8>) -> args::InitProposal {
         args::InitProposal {
@@ -499,6 +569,34 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a CancelEthBridgePoolTransfer builder from the given minimum set
+    /// of arguments
+    fn new_cancel_erc20_transfer(
+        &self,
+        transfer: KeccakHash,
+    ) -> args::CancelEthBridgePoolTransfer {
+        args::CancelEthBridgePoolTransfer {
+            transfer,
+            code_path: PathBuf::from(TX_BRIDGE_POOL_UPDATE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a BumpEthBridgePoolFee builder from the given minimum set of
+    /// arguments
+    fn new_bump_erc20_transfer_fee(
+        &self,
+        transfer: KeccakHash,
+        fee_amount: InputAmount,
+    ) -> args::BumpEthBridgePoolFee {
+        args::BumpEthBridgePoolFee {
+            transfer,
+            fee_amount,
+            code_path: PathBuf::from(TX_BRIDGE_POOL_UPDATE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a ResignSteward builder from the given minimum set of arguments
     fn new_resign_steward(&self, steward: Address) -> args::ResignSteward {
         args::ResignSteward {
@@ -523,6 +621,37 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a SubmitPgfBatch builder from the given minimum set of arguments
+    fn new_submit_pgf_batch(
+        &self,
+        steward: Address,
+        payments: Vec<u8>,
+    ) -> args::SubmitPgfBatch {
+        args::SubmitPgfBatch {
+            steward,
+            payments,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_SUBMIT_PGF_BATCH),
+        }
+    }
+
+    /// Make a ChallengePgfBatch builder from the given minimum set of
+    /// arguments
+    fn new_challenge_pgf_batch(
+        &self,
+        challenger: Address,
+        batch_id: u64,
+        content: Vec<u8>,
+    ) -> args::ChallengePgfBatch {
+        args::ChallengePgfBatch {
+            challenger,
+            batch_id,
+            content,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_CHALLENGE_PGF_BATCH),
+        }
+    }
+
     /// Make a TxCustom builder from the given minimum set of arguments
     fn new_custom(&self, owner: Address) -> args::TxCustom {
         args::TxCustom {
@@ -638,6 +767,7 @@ where
             prototype: args::Tx {
                 dry_run: false,
                 dry_run_wrapper: false,
+                trace: false,
                 dump_tx: false,
                 output_folder: None,
                 force: false,
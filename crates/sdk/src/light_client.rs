@@ -0,0 +1,326 @@
+//! An embedded Tendermint light client, providing trusted app hashes to
+//! verify query responses with (see [`crate::rpc::verified`]).
+//!
+//! Starting from a trusted checkpoint, the light client follows the chain's
+//! validator set by bisection over the headers served by a full node. Its
+//! latest trusted block is persisted to a file, typically next to the wallet
+//! store, so that the client can resume from it rather than from the
+//! checkpoint.
+//!
+//! Only the queries that have a verified counterpart in
+//! [`crate::rpc::verified`] can be made through the light client, i.e. the
+//! raw storage values and the transparent balances. In the CLI, these are
+//! the `--light` modes of `query-bytes` and `query-balance`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tendermint_light_client::builder::LightClientBuilder;
+use tendermint_light_client::instance::Instance;
+use tendermint_light_client::store::memory::MemoryStore;
+use tendermint_light_client::verifier::options::Options;
+use tendermint_light_client::verifier::types::{LightBlock, TrustThreshold};
+
+use crate::error::{Error, QueryError};
+use crate::rpc::verified::TrustedRoot;
+use crate::tendermint::block::Height;
+use crate::tendermint::{node, Hash};
+use crate::tendermint_rpc::{Client, HttpClient};
+
+/// The name of the file in which the light client's trusted state is
+/// persisted
+pub const LIGHT_CLIENT_STATE_FILE: &str = "light-client.json";
+
+/// The default period for which a validator set is trusted after the time
+/// of its block. It must be shorter than the unbonding period of the chain.
+pub const DEFAULT_TRUSTING_PERIOD: Duration =
+    Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The default tolerated difference between the clocks of the light client
+/// and of the chain
+pub const DEFAULT_CLOCK_DRIFT: Duration = Duration::from_secs(10);
+
+/// The default timeout of the requests made to the full node
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default verification options, trusting validator sets for
+/// [`DEFAULT_TRUSTING_PERIOD`] and requiring a third of their voting power to
+/// skip blocks
+pub fn default_options() -> Options {
+    Options {
+        trust_threshold: TrustThreshold::ONE_THIRD,
+        trusting_period: DEFAULT_TRUSTING_PERIOD,
+        clock_drift: DEFAULT_CLOCK_DRIFT,
+    }
+}
+
+/// A light client following a chain through a full node, with its trusted
+/// state persisted to a file
+#[derive(Clone)]
+pub struct LightClient {
+    instance: Arc<Mutex<Instance>>,
+    state_path: PathBuf,
+}
+
+impl LightClient {
+    /// Start following the chain from the block with the given height and
+    /// hash, which must be obtained from a trusted source. The block is
+    /// persisted at the given path as the initial trusted state.
+    pub async fn from_checkpoint(
+        rpc_client: HttpClient,
+        height: Height,
+        hash: Hash,
+        options: Options,
+        state_path: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        let peer_id = peer_id(&rpc_client).await?;
+        let instance = blocking(move || {
+            Ok(LightClientBuilder::prod(
+                peer_id,
+                rpc_client,
+                Box::new(MemoryStore::new()),
+                options,
+                Some(DEFAULT_RPC_TIMEOUT),
+            )
+            .trust_primary_at(height, hash)?
+            .build())
+        })
+        .await?;
+        let client = Self {
+            instance: Arc::new(Mutex::new(instance)),
+            state_path: state_path.as_ref().to_path_buf(),
+        };
+        client.save()?;
+        Ok(client)
+    }
+
+    /// Resume following the chain from the trusted state persisted at the
+    /// given path.
+    pub async fn load(
+        rpc_client: HttpClient,
+        options: Options,
+        state_path: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        let state_path = state_path.as_ref().to_path_buf();
+        let state = std::fs::read(&state_path).map_err(|err| {
+            light_client_error(format!(
+                "Failed to read the light client state from {}: {err}. Was \
+                 the light client initialized?",
+                state_path.to_string_lossy()
+            ))
+        })?;
+        let light_block: LightBlock =
+            serde_json::from_slice(&state).map_err(|err| {
+                light_client_error(format!(
+                    "Failed to decode the light client state: {err}"
+                ))
+            })?;
+        let peer_id = peer_id(&rpc_client).await?;
+        let instance = blocking(move || {
+            Ok(LightClientBuilder::prod(
+                peer_id,
+                rpc_client,
+                Box::new(MemoryStore::new()),
+                options,
+                Some(DEFAULT_RPC_TIMEOUT),
+            )
+            .trust_light_block(light_block)?
+            .build())
+        })
+        .await?;
+        Ok(Self {
+            instance: Arc::new(Mutex::new(instance)),
+            state_path,
+        })
+    }
+
+    /// Get the latest block trusted by the light client, without contacting
+    /// the full node
+    pub fn latest_trusted(&self) -> Option<LightBlock> {
+        self.instance
+            .lock()
+            .expect("The light client lock shouldn't be poisoned")
+            .latest_trusted()
+    }
+
+    /// Verify the latest block of the chain and persist it as the trusted
+    /// state.
+    pub async fn verify_to_highest(&self) -> Result<LightBlock, Error> {
+        let instance = self.instance.clone();
+        let light_block = blocking(move || {
+            let mut instance = instance
+                .lock()
+                .expect("The light client lock shouldn't be poisoned");
+            let Instance {
+                light_client,
+                state,
+            } = &mut *instance;
+            light_client.verify_to_highest(state)
+        })
+        .await?;
+        self.save()?;
+        Ok(light_block)
+    }
+
+    /// Verify the block at the given height and persist the latest trusted
+    /// block as the trusted state.
+    pub async fn verify_to_target(
+        &self,
+        height: Height,
+    ) -> Result<LightBlock, Error> {
+        let instance = self.instance.clone();
+        let light_block = blocking(move || {
+            let mut instance = instance
+                .lock()
+                .expect("The light client lock shouldn't be poisoned");
+            let Instance {
+                light_client,
+                state,
+            } = &mut *instance;
+            light_client.verify_to_target(height, state)
+        })
+        .await?;
+        self.save()?;
+        Ok(light_block)
+    }
+
+    /// Verify the latest block of the chain and get the state commitment of
+    /// its app hash, against which query responses can be verified.
+    pub async fn trusted_root(&self) -> Result<TrustedRoot, Error> {
+        let light_block = self.verify_to_highest().await?;
+        TrustedRoot::from_header(&light_block.signed_header.header)
+    }
+
+    /// Persist the latest trusted block
+    fn save(&self) -> Result<(), Error> {
+        let light_block = self.latest_trusted().ok_or_else(|| {
+            light_client_error("The light client has no trusted block")
+        })?;
+        let state = serde_json::to_vec_pretty(&light_block)
+            .map_err(|err| light_client_error(err.to_string()))?;
+        // Write to a temporary file first so that an interruption can't
+        // leave a partially written state behind
+        let tmp_path = self.state_path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, state)
+            .and_then(|()| std::fs::rename(&tmp_path, &self.state_path))
+            .map_err(|err| {
+                light_client_error(format!(
+                    "Failed to persist the light client state to {}: {err}",
+                    self.state_path.to_string_lossy()
+                ))
+            })
+    }
+}
+
+/// Get the ID of the full node, which the light client uses as its primary
+async fn peer_id(rpc_client: &HttpClient) -> Result<node::Id, Error> {
+    Ok(rpc_client
+        .status()
+        .await
+        .map_err(|err| light_client_error(err.to_string()))?
+        .node_info
+        .id)
+}
+
+/// Run a light client operation on a blocking thread, as the light client
+/// drives its own async runtime to talk to the full node
+async fn blocking<T, F>(f: F) -> Result<T, Error>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, tendermint_light_client::errors::Error>
+        + Send
+        + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|err| light_client_error(err.to_string()))?
+        .map_err(|err| light_client_error(err.to_string()))
+}
+
+/// Wrap a light client failure into an SDK error
+fn light_client_error(err: impl ToString) -> Error {
+    Error::from(QueryError::LightClient(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get a client to a node that can't be reached
+    fn unreachable_node() -> HttpClient {
+        // nothing should be listening on this port
+        HttpClient::new("http://127.0.0.1:1").unwrap()
+    }
+
+    /// Get the message of an error returned by the light client
+    fn light_client_msg(err: Error) -> String {
+        match err {
+            Error::Query(QueryError::LightClient(msg)) => msg,
+            err => panic!("Unexpected error {err}"),
+        }
+    }
+
+    #[test]
+    fn test_default_options() {
+        let options = default_options();
+        assert_eq!(options.trust_threshold, TrustThreshold::ONE_THIRD);
+        assert!(options.clock_drift < options.trusting_period);
+    }
+
+    /// Test that loading the light client fails without a persisted state
+    #[tokio::test]
+    async fn test_load_without_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join(LIGHT_CLIENT_STATE_FILE);
+        let err = LightClient::load(
+            unreachable_node(),
+            default_options(),
+            &state_path,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(light_client_msg(err).contains("Was the light client"));
+    }
+
+    /// Test that loading the light client fails with a corrupted state
+    #[tokio::test]
+    async fn test_load_invalid_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join(LIGHT_CLIENT_STATE_FILE);
+        std::fs::write(&state_path, "not a light block").unwrap();
+        let err = LightClient::load(
+            unreachable_node(),
+            default_options(),
+            &state_path,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(
+            light_client_msg(err)
+                .starts_with("Failed to decode the light client state")
+        );
+    }
+
+    /// Test that no state is persisted if the checkpoint can't be trusted
+    #[tokio::test]
+    async fn test_checkpoint_without_node() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join(LIGHT_CLIENT_STATE_FILE);
+        let result = LightClient::from_checkpoint(
+            unreachable_node(),
+            Height::from(1_u32),
+            Hash::None,
+            default_options(),
+            &state_path,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(Error::Query(QueryError::LightClient(_)))
+        ));
+        assert!(!state_path.exists());
+    }
+}
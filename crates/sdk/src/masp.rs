@@ -22,7 +22,7 @@ use masp_primitives::consensus::TestNetwork;
 use masp_primitives::convert::AllowedConversion;
 use masp_primitives::ff::PrimeField;
 use masp_primitives::group::GroupEncoding;
-use masp_primitives::memo::MemoBytes;
+use masp_primitives::memo::{Memo, MemoBytes};
 use masp_primitives::merkle_tree::{
     CommitmentTree, IncrementalWitness, MerklePath,
 };
@@ -65,9 +65,11 @@ use namada_tx::data::{TxResult, WrapperTx};
 use namada_tx::Tx;
 use rand_core::{CryptoRng, OsRng, RngCore};
 use ripemd::Digest as RipemdDigest;
+use serde::Serialize;
 use sha2::Digest;
 use thiserror::Error;
 
+use crate::args::InputAmount;
 #[cfg(feature = "testing")]
 use crate::error::EncodingError;
 use crate::error::{Error, PinnedBalanceError, QueryError};
@@ -78,6 +80,10 @@ use crate::tendermint_rpc::query::Query;
 use crate::tendermint_rpc::Order;
 use crate::{display_line, edisplay_line, rpc, MaybeSend, MaybeSync, Namada};
 
+pub mod witness;
+
+pub use witness::WitnessStore;
+
 /// Env var to point to a dir with MASP parameters. When not specified,
 /// the default OS specific path is used.
 pub const ENV_VAR_MASP_PARAMS_DIR: &str = "NAMADA_MASP_PARAMS_DIR";
@@ -430,7 +436,8 @@ pub trait ShieldedUtils:
         ctx: &mut ShieldedContext<U>,
     ) -> std::io::Result<()>;
 
-    /// Save the given ShieldedContext for future loads
+    /// Save the given ShieldedContext for future loads. Its note witnesses
+    /// are not part of its Borsh encoding and must be saved separately.
     async fn save<U: ShieldedUtils + MaybeSync>(
         &self,
         ctx: &ShieldedContext<U>,
@@ -501,6 +508,60 @@ pub type TransferDelta = HashMap<Address, MaspChange>;
 /// Represents the changes that were made to a list of shielded accounts
 pub type TransactionDelta = HashMap<ViewingKey, I128Sum>;
 
+/// The positions of the notes, decryptable by the tracked viewing keys, that
+/// were created and spent by a transaction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct TxNotes {
+    /// Positions of the notes created by the transaction
+    pub received: BTreeSet<usize>,
+    /// Positions of the notes spent by the transaction
+    pub spent: BTreeSet<usize>,
+}
+
+/// An entry of the shielded history of a viewing key
+#[derive(Serialize, Debug, Clone)]
+pub struct ShieldedHistoryEntry {
+    /// The height of the block containing the transaction
+    pub height: BlockHeight,
+    /// The index of the transaction in its block
+    pub index: TxIndex,
+    /// The epoch of the transaction
+    pub epoch: Epoch,
+    /// The value received by the viewing key, per token, net of the change
+    /// returned to it
+    pub incoming: BTreeMap<Address, token::DenominatedAmount>,
+    /// The nominal value spent by the viewing key, per token, net of the
+    /// change returned to it
+    pub outgoing: BTreeMap<Address, token::DenominatedAmount>,
+    /// The conversion rewards realized by the spent notes, per token
+    pub rewards: BTreeMap<Address, token::DenominatedAmount>,
+    /// The balance of the viewing key after the transaction, per token
+    pub balances: BTreeMap<Address, token::DenominatedAmount>,
+    /// The text memos attached to the received notes
+    pub memos: Vec<String>,
+}
+
+/// The formats in which a shielded history can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShieldedHistoryFormat {
+    /// One line per transaction and token, with a header line
+    Csv,
+    /// An array of history entries
+    Json,
+}
+
+impl FromStr for ShieldedHistoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown shielded history format: {s}")),
+        }
+    }
+}
+
 /// Represents the current state of the shielded pool from the perspective of
 /// the chosen viewing keys.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
@@ -522,8 +583,10 @@ pub struct ShieldedContext<U: ShieldedUtils> {
     pub memo_map: HashMap<usize, MemoBytes>,
     /// Maps note positions to the diversifier of their payment address
     pub div_map: HashMap<usize, Diversifier>,
-    /// Maps note positions to their witness (used to make merkle paths)
-    pub witness_map: HashMap<usize, IncrementalWitness<Node>>,
+    /// Maintains the witnesses of our unspent notes (used to make merkle
+    /// paths). Persisted separately from the rest of the context.
+    #[borsh(skip)]
+    pub witnesses: WitnessStore,
     /// Tracks what each transaction does to various account balances
     pub delta_map:
         BTreeMap<IndexedTx, (Epoch, TransferDelta, TransactionDelta)>,
@@ -533,6 +596,8 @@ pub struct ShieldedContext<U: ShieldedUtils> {
     pub asset_types: HashMap<AssetType, (Address, MaspDenom, Option<Epoch>)>,
     /// Maps note positions to their corresponding viewing keys
     pub vk_map: HashMap<usize, ViewingKey>,
+    /// Maps transactions to the notes they created and spent
+    pub tx_note_map: BTreeMap<IndexedTx, TxNotes>,
 }
 
 /// Default implementation to ease construction of TxContexts. Derive cannot be
@@ -548,11 +613,12 @@ impl<U: ShieldedUtils + Default> Default for ShieldedContext<U> {
             note_map: HashMap::default(),
             memo_map: HashMap::default(),
             div_map: HashMap::default(),
-            witness_map: HashMap::default(),
+            witnesses: WitnessStore::default(),
             spents: HashSet::default(),
             delta_map: BTreeMap::default(),
             asset_types: HashMap::default(),
             vk_map: HashMap::default(),
+            tx_note_map: BTreeMap::default(),
         }
     }
 }
@@ -581,7 +647,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         self.note_map.extend(new_ctx.note_map);
         self.memo_map.extend(new_ctx.memo_map);
         self.div_map.extend(new_ctx.div_map);
-        self.witness_map.extend(new_ctx.witness_map);
+        self.witnesses.merge(new_ctx.witnesses);
         self.spents.extend(new_ctx.spents);
         self.asset_types.extend(new_ctx.asset_types);
         self.vk_map.extend(new_ctx.vk_map);
@@ -596,6 +662,12 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             tfer_delta.extend(ntfer_delta);
             tx_delta.extend(ntx_delta);
         }
+        // Same goes for the notes of each transaction
+        for (indexed_tx, new_notes) in new_ctx.tx_note_map {
+            let notes = self.tx_note_map.entry(indexed_tx).or_default();
+            notes.received.extend(new_notes.received);
+            notes.spent.extend(new_notes.spent);
+        }
     }
 
     /// Fetch the current state of the multi-asset shielded pool into a
@@ -850,8 +922,9 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     /// Newly discovered notes are associated to the supplied viewing keys. Note
     /// nullifiers are mapped to their originating notes. Note positions are
     /// associated to notes, memos, and diversifiers. And the set of notes that
-    /// we have spent are updated. The witness map is maintained to make it
-    /// easier to construct note merkle paths in other code. See
+    /// we have spent are updated. The witnesses of our unspent notes are
+    /// maintained to make it easier to construct note merkle paths in other
+    /// code. See
     /// <https://zips.z.cash/protocol/protocol.pdf#scan>
     pub fn scan_tx(
         &mut self,
@@ -862,6 +935,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     ) -> Result<(), Error> {
         // For tracking the account changes caused by this Transaction
         let mut transaction_delta = TransactionDelta::new();
+        // For tracking the notes created and spent by this Transaction
+        let mut tx_notes = TxNotes::default();
         // Listen for notes sent to our viewing keys
         for so in shielded
             .sapling_bundle()
@@ -869,21 +944,12 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         {
             // Create merkle tree leaf node from note commitment
             let node = Node::new(so.cmu.to_repr());
-            // Update each merkle tree in the witness map with the latest
-            // addition
-            for (_, witness) in self.witness_map.iter_mut() {
-                witness.append(node).map_err(|()| {
-                    Error::Other("note commitment tree is full".to_string())
-                })?;
-            }
+            // Queue the latest addition for the witnesses of our notes
+            self.witnesses.append(node)?;
             let note_pos = self.tree.size();
             self.tree.append(node).map_err(|()| {
                 Error::Other("note commitment tree is full".to_string())
             })?;
-            // Finally, make it easier to construct merkle paths to this new
-            // note
-            let witness = IncrementalWitness::<Node>::from_tree(&self.tree);
-            self.witness_map.insert(note_pos, witness);
             // Let's try to see if any of our viewing keys can decrypt latest
             // note
             let mut pos_map = HashMap::new();
@@ -929,6 +995,13 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                     })?;
 
                     self.vk_map.insert(note_pos, *vk);
+                    tx_notes.received.insert(note_pos);
+                    // Finally, make it easier to construct merkle paths to
+                    // this new note
+                    self.witnesses.insert(
+                        note_pos,
+                        IncrementalWitness::<Node>::from_tree(&self.tree),
+                    );
                     break;
                 }
             }
@@ -943,6 +1016,9 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             // is rendered unusable
             if let Some(note_pos) = self.nf_map.get(&ss.nullifier) {
                 self.spents.insert(*note_pos);
+                tx_notes.spent.insert(*note_pos);
+                // Spent notes will never need a merkle path again
+                self.witnesses.remove(*note_pos);
                 // Note the account changes
                 let balance = transaction_delta
                     .entry(self.vk_map[note_pos])
@@ -974,6 +1050,9 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
 
         self.delta_map
             .insert(indexed_tx, (epoch, transfer_delta, transaction_delta));
+        if !(tx_notes.received.is_empty() && tx_notes.spent.is_empty()) {
+            self.tx_note_map.insert(indexed_tx, tx_notes);
+        }
         Ok(())
     }
 
@@ -985,6 +1064,134 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         &self.delta_map
     }
 
+    /// Compute the chronological history of the transactions that created or
+    /// spent notes of the given viewing key. The outgoing amounts are the
+    /// nominal values of the spent notes, whereas the rewards are the
+    /// additional values obtained by converting these notes to the epoch of
+    /// the spending transaction. The notes received by the viewing key in a
+    /// transaction that also spends its notes are change, which is netted
+    /// out of both amounts. The running balances track the nominal values of
+    /// the unspent notes.
+    pub async fn shielded_history<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        io: &impl Io,
+        vk: &ViewingKey,
+    ) -> Result<Vec<ShieldedHistoryEntry>, Error> {
+        let mut history = Vec::new();
+        let mut balances = ValueSum::<Address, token::Change>::zero();
+        let tx_note_map = self.tx_note_map.clone();
+        for (indexed_tx, tx_notes) in tx_note_map {
+            let epoch = match self.delta_map.get(&indexed_tx) {
+                Some((epoch, _, _)) => *epoch,
+                None => continue,
+            };
+            let is_own = |pos: &&usize| self.vk_map.get(*pos) == Some(vk);
+            let received: Vec<usize> =
+                tx_notes.received.iter().filter(is_own).copied().collect();
+            let spent: Vec<usize> =
+                tx_notes.spent.iter().filter(is_own).copied().collect();
+            if received.is_empty() && spent.is_empty() {
+                continue;
+            }
+            let memos = received
+                .iter()
+                .filter_map(|pos| self.memo_map.get(pos).and_then(decode_memo))
+                .collect();
+            let received = self.sum_notes(&received)?;
+            let spent = self.sum_notes(&spent)?;
+            // Realize the rewards accrued by the spent notes up to the epoch
+            // of the spending transaction
+            let exchanged = self
+                .compute_exchanged_amount(
+                    client,
+                    io,
+                    spent.clone(),
+                    epoch,
+                    Conversions::new(),
+                )
+                .await?
+                .0;
+            let mut incoming = self
+                .decode_combine_sum_to_epoch(client, received, epoch)
+                .await;
+            let mut outgoing =
+                self.decode_combine_sum_to_epoch(client, spent, epoch).await;
+            let mut rewards = self
+                .decode_combine_sum_to_epoch(client, exchanged, epoch)
+                .await;
+            rewards -= outgoing.clone();
+            balances += incoming.clone();
+            balances -= outgoing.clone();
+
+            let tokens: BTreeSet<Address> = incoming
+                .components()
+                .chain(outgoing.components())
+                .map(|(token, _)| token.clone())
+                .collect();
+            // Net the change returned to the viewing key out of the amounts
+            // it received and spent
+            let mut change = ValueSum::<Address, token::Change>::zero();
+            for (token, received) in incoming.components() {
+                let returned = std::cmp::min(*received, outgoing.get(token));
+                if returned.is_positive() {
+                    change += ValueSum::from_pair(token.clone(), returned)
+                        .expect("expected this to fit");
+                }
+            }
+            incoming -= change.clone();
+            outgoing -= change;
+            let mut entry = ShieldedHistoryEntry {
+                height: indexed_tx.height,
+                index: indexed_tx.index,
+                epoch,
+                incoming: BTreeMap::new(),
+                outgoing: BTreeMap::new(),
+                rewards: BTreeMap::new(),
+                balances: BTreeMap::new(),
+                memos,
+            };
+            for token in tokens {
+                for (column, sum) in [
+                    (&mut entry.incoming, &incoming),
+                    (&mut entry.outgoing, &outgoing),
+                    (&mut entry.rewards, &rewards),
+                    (&mut entry.balances, &balances),
+                ] {
+                    let amount = rpc::denominate_amount(
+                        client,
+                        io,
+                        &token,
+                        sum.get(&token).into(),
+                    )
+                    .await;
+                    column.insert(token.clone(), amount);
+                }
+            }
+            history.push(entry);
+        }
+        Ok(history)
+    }
+
+    /// Sum the values of the notes at the given positions
+    fn sum_notes(&self, positions: &[usize]) -> Result<I128Sum, Error> {
+        let mut sum = I128Sum::zero();
+        for pos in positions {
+            let note = self.note_map.get(pos).ok_or_else(|| {
+                Error::Other(format!("Unable to get note {pos}"))
+            })?;
+            sum +=
+                I128Sum::from_nonnegative(note.asset_type, note.value as i128)
+                    .map_err(|()| {
+                        Error::Other(
+                            "found note with invalid value or asset type"
+                                .to_string(),
+                        )
+                    })?;
+        }
+        Ok(sum)
+    }
+
     /// Compute the total unspent notes associated with the viewing key in the
     /// context. If the key is not in the context, then we do not know the
     /// balance and hence we return None.
@@ -1350,16 +1557,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                     normed_val_acc += normed_contr;
                     // Commit the conversions that were used to exchange
                     conversions = proposed_convs;
-                    let merkle_path = self
-                        .witness_map
-                        .get(note_idx)
-                        .ok_or_else(|| {
-                            Error::Other(format!(
-                                "Unable to get note {note_idx}"
-                            ))
-                        })?
-                        .path()
-                        .ok_or_else(|| {
+                    let merkle_path =
+                        self.witnesses.path(*note_idx)?.ok_or_else(|| {
                             Error::Other(format!(
                                 "Unable to get path: {}",
                                 line!()
@@ -1603,12 +1802,6 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         token: &Address,
         amount: token::DenominatedAmount,
     ) -> Result<Option<ShieldedTransfer>, TransferErr> {
-        // No shielded components are needed when neither source nor destination
-        // are shielded
-
-        use rand::rngs::StdRng;
-        use rand_core::SeedableRng;
-
         let spending_key = source.spending_key();
         let payment_address = target.payment_address();
         // No shielded components are needed when neither source nor
@@ -1637,69 +1830,15 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         // possession
         let memo = MemoBytes::empty();
 
-        // Try to get a seed from env var, if any.
-        let rng = if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
-            .map_err(|e| Error::Other(e.to_string()))
-            .and_then(|seed| {
-                let exp_str =
-                    format!("Env var {ENV_VAR_MASP_TEST_SEED} must be a u64.");
-                let parsed_seed: u64 = FromStr::from_str(&seed)
-                    .map_err(|_| Error::Other(exp_str))?;
-                Ok(parsed_seed)
-            }) {
-            tracing::warn!(
-                "UNSAFE: Using a seed from {ENV_VAR_MASP_TEST_SEED} env var \
-                 to build proofs."
-            );
-            StdRng::seed_from_u64(seed)
-        } else {
-            StdRng::from_rng(OsRng).unwrap()
-        };
-
         // Now we build up the transaction within this object
-        let expiration_height: u32 = match context.tx_builder().expiration {
-            Some(expiration) => {
-                // Try to match a DateTime expiration with a plausible
-                // corresponding block height
-                let last_block_height: u64 =
-                    crate::rpc::query_block(context.client())
-                        .await?
-                        .map_or_else(|| 1, |block| u64::from(block.height));
-                let current_time = DateTimeUtc::now();
-                let delta_time =
-                    expiration.0.signed_duration_since(current_time.0);
-
-                let max_expected_time_per_block_key =
-                    namada_parameters::storage::get_max_expected_time_per_block_key();
-                let max_block_time =
-                    crate::rpc::query_storage_value::<_, DurationSecs>(
-                        context.client(),
-                        &max_expected_time_per_block_key,
-                    )
-                    .await?;
-
-                let delta_blocks = u32::try_from(
-                    delta_time.num_seconds() / max_block_time.0 as i64,
-                )
-                .map_err(|e| Error::Other(e.to_string()))?;
-                u32::try_from(last_block_height)
-                    .map_err(|e| Error::Other(e.to_string()))?
-                    + delta_blocks
-            }
-            None => {
-                // NOTE: The masp library doesn't support optional expiration so
-                // we set the max to mimic a never-expiring tx. We also need to
-                // remove 20 which is going to be added back by the builder
-                u32::MAX - 20
-            }
-        };
+        let expiration_height = masp_expiration_height(context).await?;
         let mut builder = Builder::<TestNetwork, _>::new_with_rng(
             NETWORK,
             // NOTE: this is going to add 20 more blocks to the actual
             // expiration but there's no other exposed function that we could
             // use from the masp crate to specify the expiration better
             expiration_height.into(),
-            rng,
+            masp_rng(),
         );
 
         // Convert transaction amount into MASP types
@@ -2216,6 +2355,394 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     }
 }
 
+/// Make the randomness source for building MASP transactions. A seed may be
+/// supplied through the environment to get deterministic proofs in tests.
+fn masp_rng() -> rand::rngs::StdRng {
+    use rand::rngs::StdRng;
+    use rand_core::SeedableRng;
+
+    // Try to get a seed from env var, if any.
+    if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
+        .map_err(|e| Error::Other(e.to_string()))
+        .and_then(|seed| {
+            let exp_str =
+                format!("Env var {ENV_VAR_MASP_TEST_SEED} must be a u64.");
+            let parsed_seed: u64 =
+                FromStr::from_str(&seed).map_err(|_| Error::Other(exp_str))?;
+            Ok(parsed_seed)
+        })
+    {
+        tracing::warn!(
+            "UNSAFE: Using a seed from {ENV_VAR_MASP_TEST_SEED} env var to \
+             build proofs."
+        );
+        StdRng::seed_from_u64(seed)
+    } else {
+        StdRng::from_rng(OsRng).unwrap()
+    }
+}
+
+/// Estimate the block height at which a MASP transaction built with the
+/// given context should expire
+async fn masp_expiration_height(context: &impl Namada) -> Result<u32, Error> {
+    use namada_parameters::storage::get_max_expected_time_per_block_key;
+
+    let expiration_height = match context.tx_builder().expiration {
+        Some(expiration) => {
+            // Try to match a DateTime expiration with a plausible
+            // corresponding block height
+            let last_block_height: u64 =
+                crate::rpc::query_block(context.client())
+                    .await?
+                    .map_or_else(|| 1, |block| u64::from(block.height));
+            let current_time = DateTimeUtc::now();
+            let delta_time = expiration.0.signed_duration_since(current_time.0);
+
+            let max_expected_time_per_block_key =
+                get_max_expected_time_per_block_key();
+            let max_block_time =
+                crate::rpc::query_storage_value::<_, DurationSecs>(
+                    context.client(),
+                    &max_expected_time_per_block_key,
+                )
+                .await?;
+
+            let delta_blocks = u32::try_from(
+                delta_time.num_seconds() / max_block_time.0 as i64,
+            )
+            .map_err(|e| Error::Other(e.to_string()))?;
+            u32::try_from(last_block_height)
+                .map_err(|e| Error::Other(e.to_string()))?
+                + delta_blocks
+        }
+        None => {
+            // NOTE: The masp library doesn't support optional expiration so
+            // we set the max to mimic a never-expiring tx. We also need to
+            // remove 20 which is going to be added back by the builder
+            u32::MAX - 20
+        }
+    };
+    Ok(expiration_height)
+}
+
+/// Decode the text of a note memo, if any
+fn decode_memo(memo: &MemoBytes) -> Option<String> {
+    match Memo::try_from(memo.clone()) {
+        Ok(Memo::Text(text)) => Some(String::from(&*text)),
+        _ => None,
+    }
+}
+
+/// Escape a CSV field, quoting it if it contains separators or quotes
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render the given shielded history in the requested format
+pub fn render_shielded_history(
+    history: &[ShieldedHistoryEntry],
+    format: ShieldedHistoryFormat,
+) -> Result<String, Error> {
+    match format {
+        ShieldedHistoryFormat::Json => serde_json::to_string_pretty(history)
+            .map_err(|err| Error::Other(err.to_string())),
+        ShieldedHistoryFormat::Csv => {
+            let mut csv = String::from(
+                "height,index,epoch,token,incoming,outgoing,rewards,balance,\
+                 memos\n",
+            );
+            for entry in history {
+                let memos = csv_field(&entry.memos.join("; "));
+                for (token, balance) in &entry.balances {
+                    let column = |amounts: &BTreeMap<
+                        Address,
+                        token::DenominatedAmount,
+                    >| {
+                        amounts
+                            .get(token)
+                            .map(ToString::to_string)
+                            .unwrap_or_default()
+                    };
+                    csv.push_str(&format!(
+                        "{},{},{},{},{},{},{},{},{}\n",
+                        entry.height,
+                        entry.index.0,
+                        entry.epoch,
+                        token,
+                        column(&entry.incoming),
+                        column(&entry.outgoing),
+                        column(&entry.rewards),
+                        balance,
+                        memos,
+                    ));
+                }
+            }
+            Ok(csv)
+        }
+    }
+}
+
+/// A shielded payment made by a [`ShieldedMultiTransferBuilder`]
+#[derive(Clone, Debug)]
+pub struct ShieldedOutput {
+    /// The payment address receiving the note
+    pub target: PaymentAddress,
+    /// The token being paid
+    pub token: Address,
+    /// The amount being paid
+    pub amount: token::DenominatedAmount,
+}
+
+/// Builds a single MASP transaction that combines the notes of several
+/// spending keys and pays several shielded outputs, possibly in different
+/// tokens. Whatever the selected notes hold in excess of the outputs is
+/// returned as change, so that the value balance of the transaction is
+/// exactly zero and nothing is left in the transparent pool.
+#[derive(Clone, Debug, Default)]
+pub struct ShieldedMultiTransferBuilder {
+    /// The keys funding the transaction, in order of preference
+    pub spending_keys: Vec<ExtendedSpendingKey>,
+    /// The payments to make
+    pub outputs: Vec<ShieldedOutput>,
+    /// Where to send the change. Defaults to the default address of the
+    /// first spending key.
+    pub change_address: Option<PaymentAddress>,
+}
+
+impl ShieldedMultiTransferBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fund the transaction with the notes of the given spending key. Keys
+    /// are drawn from in the order that they are added.
+    pub fn spending_key(mut self, key: impl Into<ExtendedSpendingKey>) -> Self {
+        self.spending_keys.push(key.into());
+        self
+    }
+
+    /// Pay the given amount of a token to a payment address
+    pub fn output(
+        mut self,
+        target: PaymentAddress,
+        token: Address,
+        amount: token::DenominatedAmount,
+    ) -> Self {
+        self.outputs.push(ShieldedOutput {
+            target,
+            token,
+            amount,
+        });
+        self
+    }
+
+    /// Send the change of the transaction to the given payment address
+    pub fn change_address(self, change_address: PaymentAddress) -> Self {
+        Self {
+            change_address: Some(change_address),
+            ..self
+        }
+    }
+
+    /// Select the notes covering the outputs, add the outputs and the
+    /// change, and prove the resulting MASP transaction
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> Result<ShieldedTransfer, TransferErr> {
+        // The same notes must not be spent twice
+        let mut seen = HashSet::new();
+        let spending_keys: Vec<_> = self
+            .spending_keys
+            .into_iter()
+            .filter(|sk| seen.insert(to_viewing_key(sk).vk))
+            .collect();
+        let first_key = *spending_keys.first().ok_or_else(|| {
+            Error::Other(
+                "A shielded multi-transfer requires at least one spending key"
+                    .to_string(),
+            )
+        })?;
+        if self.outputs.is_empty() {
+            return Err(Error::Other(
+                "A shielded multi-transfer requires at least one output"
+                    .to_string(),
+            )
+            .into());
+        }
+        {
+            // Load the current shielded context given the spending keys we
+            // possess
+            let mut shielded = context.shielded_mut().await;
+            let _ = shielded.load().await;
+            shielded
+                .fetch(context.client(), &spending_keys, &[])
+                .await?;
+            // Save the update state so that future fetches can be
+            // short-circuited
+            let _ = shielded.save().await;
+        }
+        // Determine epoch in which to submit the shielded transaction
+        let epoch = rpc::query_epoch(context.client()).await?;
+        let memo = MemoBytes::empty();
+        let expiration_height = masp_expiration_height(context).await?;
+        let mut builder = Builder::<TestNetwork, _>::new_with_rng(
+            NETWORK,
+            expiration_height.into(),
+            masp_rng(),
+        );
+
+        // Convert the outputs into MASP types and sum up what they require
+        let mut outputs = Vec::with_capacity(self.outputs.len());
+        let mut required = I128Sum::zero();
+        for output in &self.outputs {
+            let amount = rpc::validate_amount(
+                context,
+                InputAmount::Unvalidated(output.amount),
+                &output.token,
+                false,
+            )
+            .await?;
+            let (_, masp_amount) = context
+                .shielded_mut()
+                .await
+                .convert_amount(
+                    context.client(),
+                    epoch,
+                    &output.token,
+                    amount.amount(),
+                )
+                .await?;
+            required += I128Sum::from_sum(masp_amount.clone());
+            outputs.push((output.target, masp_amount));
+        }
+
+        // Draw notes from each key in turn until the outputs are covered
+        for sk in &spending_keys {
+            let mut remaining = required.clone();
+            remaining -= builder.value_balance().map_err(|e| {
+                Error::Other(format!("unable to complete value balance: {}", e))
+            })?;
+            let mut shortfall = I128Sum::zero();
+            for (asset_type, amt) in remaining.components() {
+                if *amt > 0 {
+                    shortfall += I128Sum::from_pair(*asset_type, *amt)
+                        .expect("unable to construct value sum");
+                }
+            }
+            if shortfall.is_zero() {
+                break;
+            }
+            let (_, unspent_notes, used_convs) = context
+                .shielded_mut()
+                .await
+                .collect_unspent_notes(
+                    context,
+                    &to_viewing_key(sk).vk,
+                    shortfall,
+                    epoch,
+                )
+                .await?;
+            // Commit the notes found to our transaction
+            for (diversifier, note, merkle_path) in unspent_notes {
+                builder
+                    .add_sapling_spend(*sk, diversifier, note, merkle_path)
+                    .map_err(builder::Error::SaplingBuild)?;
+            }
+            // Commit the conversion notes used during summation
+            for (conv, wit, value) in used_convs.values() {
+                if value.is_positive() {
+                    builder
+                        .add_sapling_convert(
+                            conv.clone(),
+                            *value as u64,
+                            wit.clone(),
+                        )
+                        .map_err(builder::Error::SaplingBuild)?;
+                }
+            }
+        }
+
+        // Now add the requested payments
+        let ovk = first_key.expsk.ovk;
+        for (target, masp_amount) in outputs {
+            for (asset_type, value) in masp_amount.components() {
+                builder
+                    .add_sapling_output(
+                        Some(ovk),
+                        target.into(),
+                        *asset_type,
+                        *value,
+                        memo.clone(),
+                    )
+                    .map_err(builder::Error::SaplingBuild)?;
+            }
+        }
+
+        // Return the excess as change, which leaves the value balance at
+        // exactly zero as required by the fee rule
+        let change_address = self
+            .change_address
+            .map_or_else(|| first_key.default_address().1, Into::into);
+        // Represents the amount of inputs we are short by
+        let mut additional = I128Sum::zero();
+        for (asset_type, amt) in builder
+            .value_balance()
+            .map_err(|e| {
+                Error::Other(format!("unable to complete value balance: {}", e))
+            })?
+            .components()
+        {
+            match amt.cmp(&0) {
+                Ordering::Greater => {
+                    builder
+                        .add_sapling_output(
+                            Some(ovk),
+                            change_address,
+                            *asset_type,
+                            *amt as u64,
+                            memo.clone(),
+                        )
+                        .map_err(builder::Error::SaplingBuild)?;
+                }
+                Ordering::Less => {
+                    additional += I128Sum::from_nonnegative(*asset_type, -*amt)
+                        .map_err(|()| {
+                            Error::Other(format!(
+                                "from non negative conversion: {}",
+                                line!()
+                            ))
+                        })?;
+                }
+                Ordering::Equal => {}
+            }
+        }
+        // If we are short by a non-zero amount, then we have insufficient
+        // funds
+        if !additional.is_zero() {
+            return Err(TransferErr::from(builder::Error::InsufficientFunds(
+                additional,
+            )));
+        }
+
+        let builder_clone = builder.clone().map_builder(WalletMap);
+        let prover = context.shielded().await.utils.local_tx_prover();
+        let (masp_tx, metadata) =
+            builder.build(&prover, &FeeRule::non_standard(U64Sum::zero()))?;
+        Ok(ShieldedTransfer {
+            builder: builder_clone,
+            masp_tx,
+            metadata,
+            epoch,
+        })
+    }
+}
+
 /// Extract the payload from the given Tx object
 fn extract_payload(
     tx: Tx,
@@ -2463,6 +2990,190 @@ mod tests {
             &fake_params_paths[2].0,
         );
     }
+
+    /// Check that the shielded history tracks the running balance, the
+    /// rewards realized by the spent notes and the memos of the received
+    /// notes, with the change netted out
+    #[tokio::test]
+    async fn test_shielded_history() {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        use masp_primitives::convert::AllowedConversion;
+        use masp_primitives::ff::PrimeField;
+        use masp_primitives::memo::MemoBytes;
+        use masp_primitives::merkle_tree::FrozenCommitmentTree;
+        use masp_primitives::sapling::{Node, Rseed};
+        use masp_primitives::transaction::components::I128Sum;
+        use masp_primitives::zip32::{
+            ExtendedFullViewingKey, ExtendedSpendingKey,
+        };
+        use namada_core::types::address::nam;
+        use namada_core::types::masp::encode_asset_type;
+        use namada_core::types::storage::{
+            BlockHeight, Epoch, IndexedTx, TxIndex,
+        };
+        use namada_core::types::token::{Amount, DenominatedAmount};
+        use namada_token::MaspDenom;
+        use rand_core::OsRng;
+
+        use super::fs::FsShieldedUtils;
+        use super::{find_valid_diversifier, ShieldedContext, TxNotes};
+        use crate::io::StdIo;
+        use crate::queries::testing::TestClient;
+        use crate::queries::RPC;
+
+        let mut client = TestClient::new(RPC);
+        let nam = nam();
+        namada_token::write_denom(&mut client.wl_storage, &nam, 6.into())
+            .unwrap();
+
+        // The notes of epoch 0 convert to epoch 1 with a 10% reward
+        let asset_type = |epoch| {
+            encode_asset_type(Some(Epoch(epoch)), &nam, MaspDenom::Zero)
+                .unwrap()
+        };
+        let conv: AllowedConversion = (I128Sum::from_pair(asset_type(0), -10)
+            .unwrap()
+            + I128Sum::from_pair(asset_type(1), 11).unwrap())
+        .into();
+        let conversion_state = &mut client.wl_storage.storage.conversion_state;
+        conversion_state.tree =
+            FrozenCommitmentTree::new(&[Node::new(conv.cmu().to_repr())]);
+        conversion_state.assets.insert(
+            asset_type(0),
+            ((nam.clone(), MaspDenom::Zero), Epoch(0), conv, 0),
+        );
+        conversion_state.assets.insert(
+            asset_type(1),
+            (
+                (nam.clone(), MaspDenom::Zero),
+                Epoch(1),
+                I128Sum::zero().into(),
+                0,
+            ),
+        );
+
+        // A first tx pays 100 NAM to the viewing key in epoch 0, then a
+        // second one spends them in epoch 1 returning 40 NAM of change
+        let vk = ExtendedFullViewingKey::from(&ExtendedSpendingKey::master(
+            &[0; 32],
+        ))
+        .fvk
+        .vk;
+        let (div, _g_d) = find_valid_diversifier(&mut OsRng);
+        let pa = vk.to_payment_address(div).unwrap();
+        let mut ctx = ShieldedContext::<FsShieldedUtils>::default();
+        for (pos, epoch, value) in [(0, 0, 100_000_000), (1, 1, 40_000_000)] {
+            ctx.note_map.insert(
+                pos,
+                pa.create_note(
+                    asset_type(epoch),
+                    value,
+                    Rseed::AfterZip212([0; 32]),
+                ),
+            );
+            ctx.vk_map.insert(pos, vk);
+        }
+        ctx.pos_map.insert(vk, BTreeSet::from([0, 1]));
+        ctx.spents.insert(0);
+        ctx.memo_map
+            .insert(0, MemoBytes::from_bytes(b"salary").unwrap());
+        let payment = IndexedTx {
+            height: BlockHeight(1),
+            index: TxIndex(0),
+        };
+        let spending = IndexedTx {
+            height: BlockHeight(5),
+            index: TxIndex(1),
+        };
+        for (indexed_tx, epoch, received, spent) in [
+            (payment, 0, BTreeSet::from([0]), BTreeSet::new()),
+            (spending, 1, BTreeSet::from([1]), BTreeSet::from([0])),
+        ] {
+            ctx.delta_map.insert(
+                indexed_tx,
+                (Epoch(epoch), Default::default(), Default::default()),
+            );
+            ctx.tx_note_map
+                .insert(indexed_tx, TxNotes { received, spent });
+        }
+
+        let history = ctx.shielded_history(&client, &StdIo, &vk).await.unwrap();
+        let amount = |raw: u64| {
+            BTreeMap::from([(
+                nam.clone(),
+                DenominatedAmount::new(Amount::from(raw), 6.into()),
+            )])
+        };
+        assert_eq!(history.len(), 2);
+
+        let entry = &history[0];
+        assert_eq!((entry.height, entry.epoch), (BlockHeight(1), Epoch(0)));
+        assert_eq!(entry.incoming, amount(100_000_000));
+        assert_eq!(entry.outgoing, amount(0));
+        assert_eq!(entry.rewards, amount(0));
+        assert_eq!(entry.balances, amount(100_000_000));
+        assert_eq!(entry.memos, vec!["salary".to_string()]);
+
+        let entry = &history[1];
+        assert_eq!((entry.height, entry.epoch), (BlockHeight(5), Epoch(1)));
+        assert_eq!(entry.incoming, amount(0));
+        assert_eq!(entry.outgoing, amount(60_000_000));
+        assert_eq!(entry.rewards, amount(10_000_000));
+        assert_eq!(entry.balances, amount(40_000_000));
+        assert!(entry.memos.is_empty());
+    }
+
+    /// Check that the CSV export has one row per transaction and token, and
+    /// that memos are escaped
+    #[test]
+    fn test_render_shielded_history_csv() {
+        use std::collections::BTreeMap;
+
+        use namada_core::types::address::nam;
+        use namada_core::types::storage::{BlockHeight, Epoch, TxIndex};
+        use namada_core::types::token::{Amount, DenominatedAmount};
+
+        use super::{
+            render_shielded_history, ShieldedHistoryEntry,
+            ShieldedHistoryFormat,
+        };
+
+        let amount = |raw: u64| {
+            BTreeMap::from([(
+                nam(),
+                DenominatedAmount::new(Amount::from(raw), 6.into()),
+            )])
+        };
+        let entry = ShieldedHistoryEntry {
+            height: BlockHeight(10),
+            index: TxIndex(2),
+            epoch: Epoch(1),
+            incoming: amount(1_500_000),
+            outgoing: amount(0),
+            rewards: amount(0),
+            balances: amount(1_500_000),
+            memos: vec!["rent, \"march\"".to_string()],
+        };
+        let csv = render_shielded_history(&[entry], ShieldedHistoryFormat::Csv)
+            .unwrap();
+        let mut rows = csv.lines();
+        assert_eq!(
+            rows.next(),
+            Some(
+                "height,index,epoch,token,incoming,outgoing,rewards,balance,\
+                 memos"
+            )
+        );
+        assert_eq!(
+            rows.next().map(ToString::to_string),
+            Some(format!(
+                "10,2,1,{},1.5,0,0,1.5,\"rent, \"\"march\"\"\"",
+                nam()
+            ))
+        );
+        assert_eq!(rows.next(), None);
+    }
 }
 
 #[cfg(feature = "std")]
@@ -2476,6 +3187,9 @@ pub mod fs {
     /// Shielded context file name
     const FILE_NAME: &str = "shielded.dat";
     const TMP_FILE_NAME: &str = "shielded.tmp";
+    /// Note witnesses file name
+    const WITNESSES_FILE_NAME: &str = "shielded_witnesses.dat";
+    const WITNESSES_TMP_FILE_NAME: &str = "shielded_witnesses.tmp";
 
     #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
     /// An implementation of ShieldedUtils for standard filesystems
@@ -2548,10 +3262,26 @@ pub mod fs {
             let mut ctx_file = File::open(self.context_dir.join(FILE_NAME))?;
             let mut bytes = Vec::new();
             ctx_file.read_to_end(&mut bytes)?;
+            let loaded = ShieldedContext::<U>::deserialize(&mut &bytes[..])?;
+            // The note witnesses are kept in their own file
+            let mut witnesses_file =
+                File::open(self.context_dir.join(WITNESSES_FILE_NAME))?;
+            let mut bytes = Vec::new();
+            witnesses_file.read_to_end(&mut bytes)?;
+            let witnesses = WitnessStore::deserialize(&mut &bytes[..])?;
+            // Both files must describe the same commitment tree
+            if witnesses.tree_size() != loaded.tree.size() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "the saved note witnesses do not match the saved shielded \
+                     context",
+                ));
+            }
             // Fill the supplied context with the deserialized object
             *ctx = ShieldedContext {
                 utils: ctx.utils.clone(),
-                ..ShieldedContext::<U>::deserialize(&mut &bytes[..])?
+                witnesses,
+                ..loaded
             };
             Ok(())
         }
@@ -2560,36 +3290,43 @@ pub mod fs {
         async fn save<U: ShieldedUtils + MaybeSync>(
             &self,
             ctx: &ShieldedContext<U>,
+        ) -> std::io::Result<()> {
+            self.write_file(
+                WITNESSES_FILE_NAME,
+                WITNESSES_TMP_FILE_NAME,
+                &ctx.witnesses.serialize_to_vec(),
+            )?;
+            self.write_file(FILE_NAME, TMP_FILE_NAME, &ctx.serialize_to_vec())
+        }
+    }
+
+    impl FsShieldedUtils {
+        /// Atomically replace the given file of the context directory with
+        /// the given bytes
+        fn write_file(
+            &self,
+            file_name: &str,
+            tmp_file_name: &str,
+            bytes: &[u8],
         ) -> std::io::Result<()> {
             // TODO: use mktemp crate?
-            let tmp_path = self.context_dir.join(TMP_FILE_NAME);
+            let tmp_path = self.context_dir.join(tmp_file_name);
             {
-                // First serialize the shielded context into a temporary file.
-                // Inability to create this file implies a simultaneuous write
-                // is in progress. In this case, immediately
-                // fail. This is unproblematic because the data
-                // intended to be stored can always be re-fetched
-                // from the blockchain.
-                let mut ctx_file = OpenOptions::new()
+                // First write the data into a temporary file. Inability to
+                // create this file implies a simultaneuous write is in
+                // progress. In this case, immediately fail. This is
+                // unproblematic because the data intended to be stored can
+                // always be re-fetched from the blockchain.
+                let mut file = OpenOptions::new()
                     .write(true)
                     .create_new(true)
                     .open(tmp_path.clone())?;
-                let mut bytes = Vec::new();
-                ctx.serialize(&mut bytes)
-                    .expect("cannot serialize shielded context");
-                ctx_file.write_all(&bytes[..])?;
+                file.write_all(bytes)?;
             }
-            // Atomically update the old shielded context file with new data.
-            // Atomicity is required to prevent other client instances from
-            // reading corrupt data.
-            std::fs::rename(
-                tmp_path.clone(),
-                self.context_dir.join(FILE_NAME),
-            )?;
-            // Finally, remove our temporary file to allow future saving of
-            // shielded contexts.
-            std::fs::remove_file(tmp_path)?;
-            Ok(())
+            // Atomically update the old file with new data. Atomicity is
+            // required to prevent other client instances from reading corrupt
+            // data. Renaming also frees the temporary file for future saves.
+            std::fs::rename(tmp_path, self.context_dir.join(file_name))
         }
     }
 }
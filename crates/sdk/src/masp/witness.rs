@@ -0,0 +1,189 @@
+//! Maintenance of the incremental witnesses of the notes owned by the viewing
+//! keys of a shielded context.
+//!
+//! Only the notes decrypted by a tracked viewing key get a witness, and the
+//! witnesses of spent notes are dropped. New note commitments are buffered and
+//! appended to the witnesses in batches, or to a single witness when its merkle
+//! path is requested, instead of updating every witness for every commitment.
+
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use masp_primitives::merkle_tree::{IncrementalWitness, MerklePath};
+use masp_primitives::sapling::Node;
+
+use crate::error::Error;
+
+/// The number of note commitments that are buffered before being appended to
+/// all the witnesses
+pub const WITNESS_BATCH_SIZE: usize = 4096;
+
+/// The incremental witnesses of the unspent notes of the tracked viewing keys
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct WitnessStore {
+    /// The size of the commitment tree that this store is level with
+    tree_size: usize,
+    /// Maps note positions to their witness and to the size of the commitment
+    /// tree that the witness reflects
+    witnesses: BTreeMap<usize, (IncrementalWitness<Node>, usize)>,
+    /// The last note commitments of the tree, which have not yet been
+    /// appended to all the witnesses
+    pending: Vec<Node>,
+}
+
+impl WitnessStore {
+    /// The size of the commitment tree that this store is level with
+    pub fn tree_size(&self) -> usize {
+        self.tree_size
+    }
+
+    /// The number of tracked notes
+    pub fn len(&self) -> usize {
+        self.witnesses.len()
+    }
+
+    /// Check if no note is tracked
+    pub fn is_empty(&self) -> bool {
+        self.witnesses.is_empty()
+    }
+
+    /// Check if the note at the given position is tracked
+    pub fn contains(&self, note_pos: usize) -> bool {
+        self.witnesses.contains_key(&note_pos)
+    }
+
+    /// Record a note commitment that was appended to the commitment tree
+    pub fn append(&mut self, node: Node) -> Result<(), Error> {
+        self.tree_size += 1;
+        // Nothing needs to be remembered while no note is tracked
+        if self.witnesses.is_empty() {
+            return Ok(());
+        }
+        self.pending.push(node);
+        if self.pending.len() >= WITNESS_BATCH_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Track the note at the given position. The witness must have been made
+    /// from the commitment tree after its last commitment was recorded.
+    pub fn insert(
+        &mut self,
+        note_pos: usize,
+        witness: IncrementalWitness<Node>,
+    ) {
+        self.witnesses.insert(note_pos, (witness, self.tree_size));
+    }
+
+    /// Stop tracking the note at the given position
+    pub fn remove(&mut self, note_pos: usize) {
+        self.witnesses.remove(&note_pos);
+        if self.witnesses.is_empty() {
+            self.pending.clear();
+        }
+    }
+
+    /// Append the pending note commitments to all the witnesses
+    pub fn flush(&mut self) -> Result<(), Error> {
+        for (witness, synced) in self.witnesses.values_mut() {
+            sync_witness(witness, synced, &self.pending, self.tree_size)?;
+        }
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Get the merkle path of the note at the given position, bringing only
+    /// its witness level with the commitment tree
+    pub fn path(
+        &mut self,
+        note_pos: usize,
+    ) -> Result<Option<MerklePath<Node>>, Error> {
+        match self.witnesses.get_mut(&note_pos) {
+            Some((witness, synced)) => {
+                sync_witness(witness, synced, &self.pending, self.tree_size)?;
+                Ok(witness.path())
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Merge the witnesses of another store that is level with the same
+    /// commitment tree
+    pub fn merge(&mut self, other: WitnessStore) {
+        debug_assert_eq!(self.tree_size, other.tree_size);
+        // Both buffers end at the tip of the same tree, so the longer one
+        // contains the other
+        if other.pending.len() > self.pending.len() {
+            self.pending = other.pending;
+        }
+        self.witnesses.extend(other.witnesses);
+    }
+}
+
+/// Append to the given witness the pending note commitments that it does not
+/// reflect yet
+fn sync_witness(
+    witness: &mut IncrementalWitness<Node>,
+    synced: &mut usize,
+    pending: &[Node],
+    tree_size: usize,
+) -> Result<(), Error> {
+    let pending_start = tree_size - pending.len();
+    for node in &pending[*synced - pending_start..] {
+        witness.append(*node).map_err(|()| {
+            Error::Other("note commitment tree is full".to_string())
+        })?;
+    }
+    *synced = tree_size;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use masp_primitives::merkle_tree::CommitmentTree;
+
+    use super::*;
+
+    fn node(i: u8) -> Node {
+        Node::new([i; 32])
+    }
+
+    /// Check that the lazily maintained witnesses give the same merkle paths
+    /// as witnesses updated with every commitment
+    #[test]
+    fn test_witness_store_paths() {
+        let mut tree = CommitmentTree::<Node>::empty();
+        let mut eager: BTreeMap<usize, IncrementalWitness<Node>> =
+            BTreeMap::new();
+        let mut store = WitnessStore::default();
+        for i in 0..40u8 {
+            let node = node(i);
+            for witness in eager.values_mut() {
+                witness.append(node).unwrap();
+            }
+            tree.append(node).unwrap();
+            store.append(node).unwrap();
+            if i % 7 == 3 {
+                let witness = IncrementalWitness::from_tree(&tree);
+                eager.insert(tree.size() - 1, witness.clone());
+                store.insert(tree.size() - 1, witness);
+            }
+            if i == 30 {
+                eager.remove(&10);
+                store.remove(10);
+            }
+        }
+        assert_eq!(store.tree_size(), tree.size());
+        assert_eq!(store.len(), eager.len());
+        assert!(!store.contains(10));
+        for (pos, witness) in &eager {
+            assert_eq!(store.path(*pos).unwrap(), witness.path());
+        }
+        // Flushing must not change the paths
+        store.flush().unwrap();
+        for (pos, witness) in &eager {
+            assert_eq!(store.path(*pos).unwrap(), witness.path());
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub(super) mod eth_bridge;
 
@@ -9,12 +9,13 @@ use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_account::{Account, AccountPublicKeysMap};
 use namada_core::hints;
-use namada_core::types::address::Address;
+use namada_core::types::address::{Address, MASP};
+use namada_core::types::dec::Dec;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::{
     self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
 };
-use namada_core::types::token::MaspDenom;
+use namada_core::types::token::{self, MaspDenom};
 use namada_state::{DBIter, LastBlock, StorageHasher, DB};
 use namada_storage::{self, ResultExt, StorageRead};
 #[cfg(any(test, feature = "async-client"))]
@@ -69,6 +70,9 @@ router! {SHELL,
     // Dry run a transaction
     ( "dry_run_tx" ) -> TxResult = (with_options dry_run_tx),
 
+    // Dry run a transaction and trace its execution
+    ( "dry_run_tx_trace" ) -> TxResult = (with_options dry_run_tx_trace),
+
     // Raw storage access - prefix iterator
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
@@ -86,6 +90,18 @@ router! {SHELL,
     // Conversion state access - read conversion
     ( "masp_reward_tokens" ) -> BTreeMap<String, Address> = masp_reward_tokens,
 
+    // MASP statistics - number of note commitments in the commitment tree
+    ( "masp_note_commitments" ) -> u64 = masp_note_commitments,
+
+    // MASP statistics - number of nullifiers revealed in each epoch
+    ( "masp_nullifiers" ) -> BTreeMap<Epoch, u64> = masp_nullifiers,
+
+    // MASP statistics - shielded supply of the tokens in the conversion state
+    ( "masp_shielded_supply" ) -> BTreeMap<Address, token::Amount> = masp_shielded_supply,
+
+    // MASP statistics - reward rate of a token in each epoch
+    ( "masp_reward_rates" / [token: Address] ) -> BTreeMap<Epoch, Dec> = masp_reward_rates,
+
     // Block results access - read bit-vec
     ( "results" ) -> Vec<BlockResults> = read_results,
 
@@ -121,6 +137,17 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+fn dry_run_tx_trace<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
+}
+
 /// Query to read block results from storage
 pub fn read_results<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -220,6 +247,66 @@ where
     Ok(ctx.wl_storage.storage.conversion_state.tokens.clone())
 }
 
+/// Query the number of note commitments in the MASP commitment tree.
+fn masp_note_commitments<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<u64>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_token::read_note_commitment_count(ctx.wl_storage)
+}
+
+/// Query the number of MASP nullifiers revealed in each epoch.
+fn masp_nullifiers<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<Epoch, u64>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_token::read_nullifier_counts(ctx.wl_storage)
+}
+
+/// Query the balance held by the MASP of each token in the conversion state.
+fn masp_shielded_supply<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<Address, token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let tokens: BTreeSet<Address> = ctx
+        .wl_storage
+        .storage
+        .conversion_state
+        .assets
+        .values()
+        .map(|((addr, _), _, _, _)| addr.clone())
+        .collect();
+    tokens
+        .into_iter()
+        .map(|token| {
+            let supply =
+                namada_token::read_balance(ctx.wl_storage, &token, &MASP)?;
+            Ok((token, supply))
+        })
+        .collect()
+}
+
+/// Query the MASP reward rate of the given token in each epoch.
+fn masp_reward_rates<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+) -> namada_storage::Result<BTreeMap<Epoch, Dec>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_token::read_reward_rate_history(ctx.wl_storage, &token)
+}
+
 fn epoch<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
 ) -> namada_storage::Result<Epoch>
@@ -535,9 +622,23 @@ where
 
 #[cfg(test)]
 mod test {
-    use namada_core::types::address;
-    use namada_token::storage_key::balance_key;
+    use std::collections::BTreeMap;
+
+    use masp_primitives::merkle_tree::CommitmentTree;
+    use masp_primitives::sapling::Node;
+    use masp_primitives::transaction::components::I128Sum;
+    use namada_core::types::address::{self, MASP};
+    use namada_core::types::dec::Dec;
+    use namada_core::types::masp::encode_asset_type;
+    use namada_core::types::storage::Epoch;
+    use namada_core::types::token::{Amount, MaspDenom};
+    use namada_storage::StorageWrite;
+    use namada_token::storage_key::{
+        balance_key, masp_commitment_tree_key, masp_nullifier_count_key,
+        masp_reward_rate_history_key,
+    };
 
+    use crate::queries::testing::TestClient;
     use crate::queries::RPC;
 
     #[test]
@@ -554,10 +655,92 @@ mod test {
         let path = RPC.shell().dry_run_tx_path();
         assert_eq!("/shell/dry_run_tx", path);
 
+        let path = RPC.shell().dry_run_tx_trace_path();
+        assert_eq!("/shell/dry_run_tx_trace", path);
+
         let path = RPC.shell().storage_prefix_path(&key);
         assert_eq!(format!("/shell/prefix/{}", key), path);
 
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
     }
+
+    /// Test the MASP statistics queries
+    #[tokio::test]
+    async fn test_masp_stats_queries() {
+        let mut client = TestClient::new(RPC);
+        let nam = address::nam();
+        let btc = address::btc();
+
+        // Nothing has been shielded yet
+        let note_commitments =
+            RPC.shell().masp_note_commitments(&client).await.unwrap();
+        assert_eq!(note_commitments, 0);
+        let nullifiers = RPC.shell().masp_nullifiers(&client).await.unwrap();
+        assert!(nullifiers.is_empty());
+        let supply = RPC.shell().masp_shielded_supply(&client).await.unwrap();
+        assert!(supply.is_empty());
+        let reward_rates =
+            RPC.shell().masp_reward_rates(&client, &nam).await.unwrap();
+        assert!(reward_rates.is_empty());
+
+        // Write some MASP state
+        let mut tree = CommitmentTree::<Node>::empty();
+        for _ in 0..3 {
+            tree.append(Node::new([0; 32])).unwrap();
+        }
+        client
+            .wl_storage
+            .write(&masp_commitment_tree_key(), tree)
+            .unwrap();
+        for (epoch, count) in [(1, 2_u64), (3, 5)] {
+            client
+                .wl_storage
+                .write(&masp_nullifier_count_key(Epoch(epoch)), count)
+                .unwrap();
+        }
+        let rates = BTreeMap::from([
+            (Epoch(1), Dec::new(5, 2).unwrap()),
+            (Epoch(2), Dec::new(4, 2).unwrap()),
+        ]);
+        for (epoch, rate) in &rates {
+            client
+                .wl_storage
+                .write(&masp_reward_rate_history_key(&nam, *epoch), rate)
+                .unwrap();
+        }
+        for token in [&nam, &btc] {
+            let asset = encode_asset_type(None, token, MaspDenom::Zero)
+                .expect("unable to create asset type");
+            client.wl_storage.storage.conversion_state.assets.insert(
+                asset,
+                (
+                    (token.clone(), MaspDenom::Zero),
+                    Epoch(0),
+                    I128Sum::zero().into(),
+                    0,
+                ),
+            );
+        }
+        let shielded = Amount::native_whole(100);
+        client
+            .wl_storage
+            .write(&balance_key(&nam, &MASP), shielded)
+            .unwrap();
+
+        // Check the statistics
+        let note_commitments =
+            RPC.shell().masp_note_commitments(&client).await.unwrap();
+        assert_eq!(note_commitments, 3);
+        let nullifiers = RPC.shell().masp_nullifiers(&client).await.unwrap();
+        assert_eq!(nullifiers, BTreeMap::from([(Epoch(1), 2), (Epoch(3), 5)]));
+        let supply = RPC.shell().masp_shielded_supply(&client).await.unwrap();
+        assert_eq!(
+            supply,
+            BTreeMap::from([(nam.clone(), shielded), (btc, Amount::zero())])
+        );
+        let reward_rates =
+            RPC.shell().masp_reward_rates(&client, &nam).await.unwrap();
+        assert_eq!(reward_rates, rates);
+    }
 }
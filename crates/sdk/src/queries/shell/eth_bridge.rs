@@ -23,7 +23,9 @@ use namada_core::types::voting_power::FractionalVotingPower;
 use namada_ethereum_bridge::protocol::transactions::votes::{
     EpochedVotingPower, EpochedVotingPowerExt,
 };
-use namada_ethereum_bridge::storage::bridge_pool::get_key_from_hash;
+use namada_ethereum_bridge::storage::bridge_pool::{
+    get_cancelled_key, get_gas_fee_bump_prefix, get_key_from_hash,
+};
 use namada_ethereum_bridge::storage::eth_bridge_queries::EthBridgeQueries;
 use namada_ethereum_bridge::storage::parameters::UpgradeableContract;
 use namada_ethereum_bridge::storage::proof::{sort_sigs, EthereumProof};
@@ -72,6 +74,9 @@ pub struct TransferToEthereumStatus {
     /// Transfers in the query whose status it was determined
     /// to be `expired`.
     pub expired: HashSet<KeccakHash>,
+    /// Transfers in the query whose status it was determined
+    /// to be `cancelled`.
+    pub cancelled: HashSet<KeccakHash>,
     /// Hashes pertaining to bogus data that might have been queried,
     /// or transfers that were not in the event log, despite having
     /// been relayed to Ethereum or expiring from the Bridge pool.
@@ -163,8 +168,13 @@ router! {ETH_BRIDGE,
         -> HashMap<PendingTransfer, FractionalVotingPower>
         = transfer_to_ethereum_progress,
 
+    // Get the gas fees added to the transfers in the Ethereum
+    // bridge pool, after they were added to the pool.
+    ( "pool" / "gas_fee_bumps" )
+        -> HashMap<KeccakHash, Amount> = read_gas_fee_bumps,
+
     // Given a list of keccak hashes, check whether they have been
-    // relayed, expired or if they are still pending.
+    // relayed, expired, cancelled or if they are still pending.
     ( "pool" / "transfer_status" )
         -> TransferToEthereumStatus = (with_options pending_eth_transfer_status),
 
@@ -215,7 +225,7 @@ router! {ETH_BRIDGE,
 }
 
 /// Given a list of keccak hashes, check whether they have been
-/// relayed, expired or if they are still pending.
+/// relayed, expired, cancelled or if they are still pending.
 fn pending_eth_transfer_status<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     request: &RequestQuery,
@@ -270,6 +280,14 @@ where
         }
     }
 
+    // check which transfers were cancelled, and are awaiting a refund
+    for hash in transfer_hashes.clone() {
+        if ctx.wl_storage.has_key(&get_cancelled_key(&hash))? {
+            transfer_hashes.remove(&hash);
+            status.cancelled.insert(hash);
+        }
+    }
+
     if transfer_hashes.is_empty() {
         let data = status.serialize_to_vec();
         return Ok(EncodedResponseQuery {
@@ -288,11 +306,14 @@ where
         }
         let eth_event_kind =
             ev.attributes.get(&kind_key).map(|k| k.as_str())?;
-        let is_relayed = match eth_event_kind {
-            "bridge_pool_relayed" => true,
-            "bridge_pool_expired" => false,
-            _ => return None,
-        };
+        if !matches!(
+            eth_event_kind,
+            "bridge_pool_relayed"
+                | "bridge_pool_expired"
+                | "bridge_pool_cancelled"
+        ) {
+            return None;
+        }
         let tx_hash: KeccakHash = ev
             .attributes
             .get("tx_hash")
@@ -303,14 +324,14 @@ where
         if !transfer_hashes.remove(&tx_hash) {
             return None;
         }
-        Some((tx_hash, is_relayed, transfer_hashes.is_empty()))
+        Some((tx_hash, eth_event_kind, transfer_hashes.is_empty()))
     });
-    for (hash, is_relayed, early_exit) in completed_transfers {
-        if hints::likely(is_relayed) {
-            status.relayed.insert(hash.clone());
-        } else {
-            status.expired.insert(hash.clone());
-        }
+    for (hash, eth_event_kind, early_exit) in completed_transfers {
+        match eth_event_kind {
+            "bridge_pool_relayed" => status.relayed.insert(hash.clone()),
+            "bridge_pool_expired" => status.expired.insert(hash.clone()),
+            _ => status.cancelled.insert(hash.clone()),
+        };
         if early_exit {
             // early drop of the transfer hashes, in
             // case its storage capacity was big
@@ -331,6 +352,32 @@ where
     })
 }
 
+/// Read the gas fees added to the transfers in the Ethereum
+/// bridge pool, after they were added to the pool.
+fn read_gas_fee_bumps<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<HashMap<KeccakHash, Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_storage::iter_prefix(ctx.wl_storage, &get_gas_fee_bump_prefix())?
+        .map(|bump| {
+            let (key, amount) = bump?;
+            match key.segments.last() {
+                Some(DbKeySeg::StringSeg(hash)) => {
+                    let hash = KeccakHash::try_from(hash.as_str())
+                        .into_storage_result()?;
+                    Ok((hash, amount))
+                }
+                _ => Err(namada_storage::Error::SimpleMessage(
+                    "Found an invalid gas fee bump key in storage",
+                )),
+            }
+        })
+        .collect()
+}
+
 /// Read the total supply and respective cap of some wrapped
 /// ERC20 token in Namada.
 fn get_erc20_flow_control<D, H, V, T>(
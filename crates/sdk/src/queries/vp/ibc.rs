@@ -0,0 +1,39 @@
+//! IBC validity predicate queries
+
+use namada_core::types::address::Address;
+use namada_ibc::rate_limit::{self, RateLimitUsage};
+use namada_state::{DBIter, StorageHasher, DB};
+
+use crate::ibc::core::host::types::identifiers::ChannelId;
+use crate::queries::types::RequestCtx;
+
+// IBC validity predicate queries
+router! {IBC,
+    ( "rate_limit" / [channel_id: ChannelId] / [token: Address] )
+        -> Option<RateLimitUsage> = rate_limit,
+    ( "rate_limits" ) -> Vec<RateLimitUsage> = rate_limits,
+}
+
+/// Query the rate limit of the token on the channel and its current usage
+fn rate_limit<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    channel_id: ChannelId,
+    token: Address,
+) -> namada_storage::Result<Option<RateLimitUsage>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    rate_limit::rate_limit_usage(ctx.wl_storage, &channel_id, &token)
+}
+
+/// Query all the rate limits and their current usage
+fn rate_limits<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<RateLimitUsage>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    rate_limit::rate_limit_usages(ctx.wl_storage)
+}
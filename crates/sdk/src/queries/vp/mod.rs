@@ -3,11 +3,14 @@
 // Re-export to show in rustdoc!
 pub use governance::Gov;
 use governance::GOV;
+pub use ibc::Ibc;
+use ibc::IBC;
 pub use pos::Pos;
 use pos::POS;
 pub use token::Token;
 use token::TOKEN;
 mod governance;
+mod ibc;
 pub use pgf::Pgf;
 use pgf::PGF;
 mod pgf;
@@ -21,4 +24,5 @@ router! {VP,
     ( "token" ) = (sub TOKEN),
     ( "governance" ) = (sub GOV),
     ( "pgf" ) = (sub PGF),
+    ( "ibc" ) = (sub IBC),
 }
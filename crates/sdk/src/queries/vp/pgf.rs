@@ -1,5 +1,8 @@
 use namada_core::types::address::Address;
+use namada_core::types::storage::Epoch;
 use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::pgf::storage::batch::StewardPaymentBatch;
+use namada_governance::pgf::storage::history::PgfEpochRecord;
 use namada_governance::pgf::storage::steward::StewardDetail;
 use namada_governance::storage::proposal::StoragePgfFunding;
 use namada_state::{DBIter, StorageHasher, DB};
@@ -12,6 +15,10 @@ router! {PGF,
     ( "stewards" ) -> Vec<StewardDetail> = stewards,
     ( "fundings" ) -> Vec<StoragePgfFunding> = funding,
     ( "parameters" ) -> PgfParameters = parameters,
+    ( "history" / [ epoch: Epoch ] ) -> Option<PgfEpochRecord> = epoch_history,
+    ( "history" / [ from: Epoch ] / [ to: Epoch ] ) -> Vec<(Epoch, PgfEpochRecord)> = history,
+    ( "batches" / [ id: u64 ] ) -> Option<StewardPaymentBatch> = batch,
+    ( "batches" ) -> Vec<StewardPaymentBatch> = batches,
 }
 
 /// Query the current pgf steward set
@@ -48,6 +55,55 @@ where
     namada_governance::pgf::storage::get_payments(ctx.wl_storage)
 }
 
+/// Query the pgf history of a range of epochs, inclusive of the bounds. At
+/// most `MAX_HISTORY_QUERY_EPOCHS` epochs starting from `from` are returned.
+fn history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    from: Epoch,
+    to: Epoch,
+) -> namada_storage::Result<Vec<(Epoch, PgfEpochRecord)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_history(ctx.wl_storage, from, to)
+}
+
+/// Query the pgf history of an epoch
+fn epoch_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<PgfEpochRecord>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_epoch_history(ctx.wl_storage, epoch)
+}
+
+/// Query the steward payment batches
+fn batches<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<StewardPaymentBatch>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_batches(ctx.wl_storage)
+}
+
+/// Query a steward payment batch by id
+fn batch<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<StewardPaymentBatch>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_batch(ctx.wl_storage, id)
+}
+
 /// Query the PGF parameters
 fn parameters<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
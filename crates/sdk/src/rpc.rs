@@ -1,5 +1,7 @@
 //! SDK RPC queries
 
+pub mod verified;
+
 use std::cell::Cell;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::ControlFlow;
@@ -11,6 +13,7 @@ use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_account::Account;
 use namada_core::types::address::{Address, InternalAddress};
+use namada_core::types::dec::Dec;
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
 use namada_core::types::storage::{
@@ -22,11 +25,13 @@ use namada_core::types::token::{
 use namada_core::types::{storage, token};
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::pgf::storage::batch::StewardPaymentBatch;
 use namada_governance::pgf::storage::steward::StewardDetail;
 use namada_governance::storage::proposal::StorageProposal;
 use namada_governance::utils::{
     compute_proposal_result, ProposalResult, ProposalVotes, Vote,
 };
+use namada_ibc::rate_limit::RateLimitUsage;
 use namada_ibc::storage::{
     ibc_denom_key, ibc_denom_key_prefix, is_ibc_denom_key,
 };
@@ -42,6 +47,7 @@ use crate::args::InputAmount;
 use crate::control_flow::time;
 use crate::error::{EncodingError, Error, QueryError, TxSubmitError};
 use crate::events::Event;
+use crate::ibc::core::host::types::identifiers::ChannelId;
 use crate::internal_macros::echo_error;
 use crate::io::Io;
 use crate::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
@@ -247,6 +253,25 @@ pub async fn query_pgf_stewards<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the steward payment batches
+pub async fn query_pgf_batches<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<StewardPaymentBatch>, error::Error> {
+    convert_response::<C, Vec<StewardPaymentBatch>>(
+        RPC.vp().pgf().batches(client).await,
+    )
+}
+
+/// Get a steward payment batch by id
+pub async fn query_pgf_batch<C: crate::queries::Client + Sync>(
+    client: &C,
+    id: u64,
+) -> Result<Option<StewardPaymentBatch>, error::Error> {
+    convert_response::<C, Option<StewardPaymentBatch>>(
+        RPC.vp().pgf().batch(client, &id).await,
+    )
+}
+
 /// Query the consensus key by validator address
 pub async fn query_validator_consensus_keys<
     C: crate::queries::Client + Sync,
@@ -325,6 +350,35 @@ pub async fn query_masp_reward_tokens<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().masp_reward_tokens(client).await)
 }
 
+/// Query the number of note commitments in the MASP commitment tree.
+pub async fn query_masp_note_commitments<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<u64, Error> {
+    convert_response::<C, _>(RPC.shell().masp_note_commitments(client).await)
+}
+
+/// Query the number of MASP nullifiers revealed in each epoch.
+pub async fn query_masp_nullifiers<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<Epoch, u64>, Error> {
+    convert_response::<C, _>(RPC.shell().masp_nullifiers(client).await)
+}
+
+/// Query the amount of each token held in the shielded pool.
+pub async fn query_masp_shielded_supply<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<Address, token::Amount>, Error> {
+    convert_response::<C, _>(RPC.shell().masp_shielded_supply(client).await)
+}
+
+/// Query the MASP reward rate of the given token in each epoch.
+pub async fn query_masp_reward_rates<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<BTreeMap<Epoch, Dec>, Error> {
+    convert_response::<C, _>(RPC.shell().masp_reward_rates(client, token).await)
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -513,18 +567,24 @@ pub async fn query_tx_events<C: crate::queries::Client + Sync>(
     }
 }
 
-/// Dry run a transaction
+/// Dry run a transaction. If `trace` is set, the execution trace of the
+/// transaction and its VPs is requested and displayed too.
 pub async fn dry_run_tx<N: Namada>(
     context: &N,
     tx_bytes: Vec<u8>,
+    trace: bool,
 ) -> Result<namada_tx::data::TxResult, Error> {
     let (data, height, prove) = (Some(tx_bytes), None, false);
-    let result = convert_response::<N::Client, _>(
+    let response = if trace {
+        RPC.shell()
+            .dry_run_tx_trace(context.client(), data, height, prove)
+            .await
+    } else {
         RPC.shell()
             .dry_run_tx(context.client(), data, height, prove)
-            .await,
-    )?
-    .data;
+            .await
+    };
+    let result = convert_response::<N::Client, _>(response)?.data;
     let result_str = if result.is_accepted() {
         format!(
             "Transaction was successfully applied. Used {} gas.",
@@ -539,6 +599,12 @@ pub async fn dry_run_tx<N: Namada>(
         )
     };
     display_line!(context.io(), "Dry-run result: {result_str}");
+    if let Some(profile) = &result.gas_profile {
+        display_line!(context.io(), "Gas used by category:\n{profile}");
+    }
+    if let Some(trace) = &result.trace {
+        display_line!(context.io(), "Execution trace:\n{trace}");
+    }
     Ok(result)
 }
 
@@ -1368,3 +1434,23 @@ pub async fn query_ibc_denom<N: Namada>(
 
     token.as_ref().to_string()
 }
+
+/// Query the IBC rate limit of the token on the channel and its current usage
+pub async fn query_ibc_rate_limit<C: crate::queries::Client + Sync>(
+    client: &C,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> Result<Option<RateLimitUsage>, error::Error> {
+    convert_response::<C, Option<RateLimitUsage>>(
+        RPC.vp().ibc().rate_limit(client, channel_id, token).await,
+    )
+}
+
+/// Query all the IBC rate limits and their current usage
+pub async fn query_ibc_rate_limits<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<RateLimitUsage>, error::Error> {
+    convert_response::<C, Vec<RateLimitUsage>>(
+        RPC.vp().ibc().rate_limits(client).await,
+    )
+}
@@ -0,0 +1,180 @@
+//! Storage queries whose results are verified against a trusted state
+//! commitment, rather than trusting the queried full node.
+//!
+//! The node is asked for the merkle proofs of the values it returns, which
+//! are then checked against the sub-tree roots and the root of the whole
+//! merkle tree, as committed to by the app hash of a trusted block header.
+//! The header itself must come from a source the client already trusts,
+//! e.g. a light client.
+//!
+//! Only the IBC sub-tree supports proofs of non-existence, so a missing key
+//! from any other sub-tree results in an error rather than a verified
+//! absence. For the same reason, there are no verified prefix queries, as
+//! nothing could attest that the node didn't omit any of the values. The
+//! values stored in collections, such as the bonds, can only be verified
+//! one element at a time at their known keys.
+
+use borsh::BorshDeserialize;
+use namada_core::types::address::Address;
+use namada_core::types::hash::Sha256Hasher;
+use namada_core::types::storage::{self, BlockHeight, Epoch};
+use namada_core::types::token;
+use namada_proof_of_stake::storage::bond_handle;
+use namada_state::merkle_tree::{MerkleRoot, Proof};
+use namada_token::storage_key::balance_key;
+
+use super::convert_response;
+use crate::error::{EncodingError, Error, QueryError};
+use crate::queries::RPC;
+use crate::tendermint::block::Header;
+use crate::tendermint::merkle::proof::ProofOps;
+
+/// A state commitment trusted by the client, against which the query
+/// results are verified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedRoot {
+    /// The height of the committed state
+    pub height: BlockHeight,
+    /// The root of the merkle tree after the block at `height`
+    pub app_hash: [u8; 32],
+}
+
+impl TrustedRoot {
+    /// Get the trusted root from a trusted block header. The app hash of a
+    /// header commits to the state after the previous block, so the
+    /// queries will be made at the height before the header's.
+    pub fn from_header(header: &Header) -> Result<Self, Error> {
+        let height = header.height.value().checked_sub(1).ok_or_else(|| {
+            Error::from(QueryError::InvalidProof(
+                "The genesis header doesn't commit to any state".to_string(),
+            ))
+        })?;
+        let app_hash = header.app_hash.as_bytes().try_into().map_err(|_| {
+            Error::from(QueryError::InvalidProof(format!(
+                "Unexpected app hash length of {} bytes",
+                header.app_hash.as_bytes().len()
+            )))
+        })?;
+        Ok(Self {
+            height: BlockHeight(height),
+            app_hash,
+        })
+    }
+
+    /// Check the proof ops returned by the node for the given key and value
+    /// against this root. A `None` value requires a proof of non-existence.
+    pub fn verify(
+        &self,
+        key: &storage::Key,
+        value: Option<&[u8]>,
+        proof_ops: &ProofOps,
+    ) -> Result<(), Error> {
+        let proof = Proof::try_from(proof_ops).map_err(invalid_proof)?;
+        if &proof.key != key {
+            return Err(Error::from(QueryError::InvalidProof(format!(
+                "Expected a proof for {key}, got one for {}",
+                proof.key
+            ))));
+        }
+        let root = MerkleRoot(self.app_hash);
+        match value {
+            Some(value) => proof.verify_existence::<Sha256Hasher>(&root, value),
+            None => proof.verify_non_existence::<Sha256Hasher>(&root),
+        }
+        .map_err(invalid_proof)
+    }
+}
+
+/// Wrap a failed proof verification into an SDK error
+fn invalid_proof(err: impl std::fmt::Display) -> Error {
+    Error::from(QueryError::InvalidProof(err.to_string()))
+}
+
+/// Query a storage value at the height of the trusted root and verify it,
+/// without decoding. Returns `None` if the absence of the key is proven.
+/// Outside of the IBC sub-tree, a missing key results in the error of the
+/// node failing to prove its absence.
+pub async fn query_storage_value_bytes<C: crate::queries::Client + Sync>(
+    client: &C,
+    root: &TrustedRoot,
+    key: &storage::Key,
+) -> Result<Option<Vec<u8>>, Error> {
+    let response = convert_response::<C, _>(
+        RPC.shell()
+            .storage_value(client, None, Some(root.height), true, key)
+            .await,
+    )?;
+    let proof = response.proof.ok_or_else(|| {
+        Error::from(QueryError::InvalidProof(format!(
+            "The node returned no proof for {key}"
+        )))
+    })?;
+    let value = (!response.data.is_empty()).then_some(response.data);
+    root.verify(key, value.as_deref(), &proof)?;
+    Ok(value)
+}
+
+/// Query a storage value at the height of the trusted root, verify it and
+/// decode it with [`BorshDeserialize`].
+pub async fn query_storage_value<C, T>(
+    client: &C,
+    root: &TrustedRoot,
+    key: &storage::Key,
+) -> Result<Option<T>, Error>
+where
+    T: BorshDeserialize,
+    C: crate::queries::Client + Sync,
+{
+    query_storage_value_bytes(client, root, key)
+        .await?
+        .map(|value| {
+            T::try_from_slice(&value).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Query the verified token balance of an owner at the height of the
+/// trusted root.
+///
+/// A balance is only stored once the owner receives some of the token, and
+/// its absence can't be proven, so an owner that never held the token
+/// results in an error rather than a zero amount. A balance that was spent
+/// down to zero stays in storage and is verified as such.
+pub async fn get_token_balance<C: crate::queries::Client + Sync>(
+    client: &C,
+    root: &TrustedRoot,
+    token: &Address,
+    owner: &Address,
+) -> Result<token::Amount, Error> {
+    let key = balance_key(token, owner);
+    query_storage_value::<C, token::Amount>(client, root, &key)
+        .await?
+        .ok_or_else(|| Error::from(QueryError::NoSuchKey(key.to_string())))
+}
+
+/// Query the verified delta of a bond at the given epoch, at the height of
+/// the trusted root.
+///
+/// A bond is stored as the deltas of its amount at the epochs in which it was
+/// bonded or unbonded, and the bonded amount at an epoch is the sum of the
+/// deltas up to it. Each stored delta is verified on its own, but an epoch
+/// without any delta results in an error, as its absence can't be proven.
+/// Old deltas are also summed into the oldest kept epoch when the bond is
+/// updated, so the epochs to query have to be known by the client, e.g. from
+/// the bonds it submitted.
+pub async fn get_bond_delta<C: crate::queries::Client + Sync>(
+    client: &C,
+    root: &TrustedRoot,
+    source: &Address,
+    validator: &Address,
+    epoch: Epoch,
+) -> Result<token::Amount, Error> {
+    let key = bond_handle(source, validator)
+        .get_data_handler()
+        .get_data_key(&epoch);
+    query_storage_value::<C, token::Amount>(client, root, &key)
+        .await?
+        .ok_or_else(|| Error::from(QueryError::NoSuchKey(key.to_string())))
+}
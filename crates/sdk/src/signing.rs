@@ -20,6 +20,7 @@ use namada_core::types::masp::{
 };
 use namada_core::types::sign::SignatureIndex;
 use namada_core::types::storage::Epoch;
+use namada_core::types::time::{DateTimeUtc, DurationSecs};
 use namada_core::types::token;
 use namada_core::types::token::Transfer;
 // use namada_core::types::storage::Key;
@@ -48,17 +49,17 @@ use crate::ibc::primitives::proto::Any;
 use crate::io::*;
 use crate::rpc::validate_amount;
 use crate::tx::{
-    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-    TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-    TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_UPDATE_WASM,
+    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_MULTI_TRANSFER_WASM,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL,
+    TX_WITHDRAW_WASM, VP_USER_WASM,
 };
-use crate::types::eth_bridge_pool::PendingTransfer;
+use crate::types::eth_bridge_pool::{PendingTransfer, PendingTransferUpdate};
 pub use crate::wallet::store::AddressVpType;
 use crate::wallet::{Wallet, WalletIo};
 use crate::{args, display_line, rpc, MaybeSend, Namada};
@@ -405,6 +406,67 @@ pub async fn init_validator_signing_data(
     })
 }
 
+/// Signing data for a multi-transfer. A single source signs as the owner of
+/// the tx, whereas several sources are covered by one signature section over
+/// the union of their public keys.
+pub async fn multi_transfer_signing_data(
+    context: &impl Namada,
+    args: &args::Tx<SdkTypes>,
+    sources: Vec<Address>,
+) -> Result<SigningTxData, Error> {
+    match sources.as_slice() {
+        [] => other_err("A multi-transfer must have a source".to_string()),
+        [source] => {
+            aux_signing_data(
+                context,
+                args,
+                Some(source.clone()),
+                Some(source.clone()),
+            )
+            .await
+        }
+        sources => {
+            let mut public_keys = vec![];
+            for source in sources {
+                for pk in
+                    tx_signers(context, args, Some(source.clone())).await?
+                {
+                    if !public_keys.contains(&pk) {
+                        public_keys.push(pk);
+                    }
+                }
+            }
+
+            let account_public_keys_map =
+                Some(AccountPublicKeysMap::from_iter(public_keys.clone()));
+
+            let fee_payer = if args.disposable_signing_key {
+                context
+                    .wallet_mut()
+                    .await
+                    .gen_disposable_signing_key(&mut OsRng)
+                    .to_public()
+            } else {
+                match &args.wrapper_fee_payer {
+                    Some(keypair) => keypair.clone(),
+                    None => public_keys
+                        .get(0)
+                        .ok_or(TxSubmitError::InvalidFeePayer)?
+                        .clone(),
+                }
+            };
+
+            Ok(SigningTxData {
+                owner: None,
+                public_keys,
+                threshold: 0,
+                account_public_keys_map,
+                fee_payer,
+            })
+        }
+    }
+}
+
 /// Information about the post-tx balance of the tx's source. Used to correctly
 /// handle fee validation in the wrapper tx
 pub struct TxSourcePostBalance {
@@ -416,6 +478,10 @@ pub struct TxSourcePostBalance {
     pub token: Address,
 }
 
+/// Expiration, in seconds from now, given to wrappers built without an
+/// explicit one
+pub const DEFAULT_TX_EXPIRATION_SECS: u64 = 3600;
+
 /// Create a wrapper tx from a normal tx. Get the hash of the
 /// wrapper and its payload which is needed for monitoring its
 /// progress on chain.
@@ -643,6 +709,13 @@ pub async fn wrap_tx<N: Namada>(
         namada_core::types::hash::Hash(hasher.finalize().into())
     });
 
+    // Wrappers must carry an expiration within the chain's expiration
+    // window, so fall back to a conservative default if none was given
+    if tx.header.expiration.is_none() {
+        tx.header.expiration =
+            Some(DateTimeUtc::now() + DurationSecs(DEFAULT_TX_EXPIRATION_SECS));
+    }
+
     tx.add_wrapper(
         Fee {
             amount_per_gas_unit: fee_amount,
@@ -1222,6 +1295,29 @@ pub async fn to_ledger_vector(
                 HEXLOWER.encode(&extra_code_hash.0)
             )]);
         }
+    } else if code_sec.tag == Some(TX_MULTI_TRANSFER_WASM.to_string()) {
+        let multi_transfer = token::MultiTransfer::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Multi_Transfer_0".to_string();
+
+        tv.output.push("Type : Multi Transfer".to_string());
+        for leg in &multi_transfer.legs {
+            for output in [&mut tv.output, &mut tv.output_expert] {
+                output.extend(vec![
+                    format!("Sender : {}", leg.source),
+                    format!("Destination : {}", leg.target),
+                ]);
+                make_ledger_amount_addr(
+                    &tokens, output, leg.amount, &leg.token, "Sending ",
+                );
+            }
+        }
     } else if code_sec.tag == Some(TX_TRANSFER_WASM.to_string()) {
         let transfer = Transfer::try_from_slice(
             &tx.data()
@@ -1698,6 +1794,41 @@ pub async fn to_ledger_vector(
             format!("Gas Token : {}", transfer.gas_fee.token),
             format!("Gas Amount : {}", transfer.gas_fee.amount),
         ]);
+    } else if code_sec.tag == Some(TX_BRIDGE_POOL_UPDATE_WASM.to_string()) {
+        let update = PendingTransferUpdate::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Bridge_Pool_Update_0".to_string();
+
+        match update {
+            PendingTransferUpdate::Cancel { transfer_hash } => {
+                tv.output.extend(vec![
+                    format!("Type : Cancel Bridge Pool Transfer"),
+                    format!("Transfer Hash : {}", transfer_hash),
+                ]);
+                tv.output_expert
+                    .push(format!("Transfer Hash : {}", transfer_hash));
+            }
+            PendingTransferUpdate::BumpGasFee {
+                transfer_hash,
+                amount,
+            } => {
+                tv.output.extend(vec![
+                    format!("Type : Bump Bridge Pool Gas Fee"),
+                    format!("Transfer Hash : {}", transfer_hash),
+                    format!("Gas Amount : {}", amount),
+                ]);
+                tv.output_expert.extend(vec![
+                    format!("Transfer Hash : {}", transfer_hash),
+                    format!("Gas Amount : {}", amount),
+                ]);
+            }
+        }
     } else {
         tv.name = "Custom_0".to_string();
         tv.output.push("Type : Custom".to_string());
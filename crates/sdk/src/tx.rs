@@ -6,8 +6,9 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
+use itertools::Itertools;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::builder;
 use masp_primitives::transaction::builder::Builder;
@@ -40,10 +41,18 @@ use namada_governance::cli::onchain::{
     DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
+use namada_governance::pgf::storage::batch::{
+    ChallengePgfBatchData, SubmitPgfBatchData,
+};
+use namada_governance::pgf::MAX_PGF_ACTIONS;
 use namada_governance::storage::proposal::{
-    InitProposalData, ProposalType, VoteProposalData,
+    InitProposalData, PGFTarget, ProposalType, VoteProposalData,
 };
 use namada_governance::storage::vote::ProposalVote;
+use namada_ibc::ica::{
+    InterchainAccountPacketData, MsgRegisterInterchainAccount,
+    MsgSendInterchainTx,
+};
 use namada_ibc::storage::channel_key;
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
@@ -89,6 +98,8 @@ pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
 /// Transfer transaction WASM path
 pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
+/// Multi-transfer transaction WASM path
+pub const TX_MULTI_TRANSFER_WASM: &str = "tx_multi_transfer.wasm";
 /// IBC transaction WASM path
 pub const TX_IBC_WASM: &str = "tx_ibc.wasm";
 /// User validity predicate WASM path
@@ -103,6 +114,8 @@ pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
+/// Bridge pool transfer update WASM path
+pub const TX_BRIDGE_POOL_UPDATE_WASM: &str = "tx_bridge_pool_update.wasm";
 /// Change commission WASM path
 pub const TX_CHANGE_COMMISSION_WASM: &str =
     "tx_change_validator_commission.wasm";
@@ -115,6 +128,10 @@ pub const TX_RESIGN_STEWARD: &str = "tx_resign_steward.wasm";
 /// Update steward commission WASM path
 pub const TX_UPDATE_STEWARD_COMMISSION: &str =
     "tx_update_steward_commission.wasm";
+/// Submit steward payment batch WASM path
+pub const TX_SUBMIT_PGF_BATCH: &str = "tx_submit_pgf_batch.wasm";
+/// Challenge steward payment batch WASM path
+pub const TX_CHALLENGE_PGF_BATCH: &str = "tx_challenge_pgf_batch.wasm";
 /// Redelegate transaction WASM path
 pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
 
@@ -217,7 +234,8 @@ pub async fn process_tx(
     // println!("HTTP request body: {}", request_body);
 
     if args.dry_run || args.dry_run_wrapper {
-        expect_dry_broadcast(TxBroadcastData::DryRun(tx), context).await
+        expect_dry_broadcast(TxBroadcastData::DryRun(tx), context, args.trace)
+            .await
     } else {
         // We use this to determine when the wrapper tx makes it on-chain
         let wrapper_hash = tx.header_hash().to_string();
@@ -908,6 +926,165 @@ pub async fn build_resign_steward(
     .map(|tx| (tx, signing_data))
 }
 
+/// Craft transaction to submit a steward payment batch
+pub async fn build_submit_pgf_batch(
+    context: &impl Namada,
+    args::SubmitPgfBatch {
+        tx: tx_args,
+        steward,
+        payments,
+        tx_code_path,
+    }: &args::SubmitPgfBatch,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(steward.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(steward.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if !rpc::is_steward(context.client(), steward).await && !tx_args.force {
+        edisplay_line!(
+            context.io(),
+            "The given address {} is not a steward.",
+            &steward
+        );
+        return Err(Error::from(TxSubmitError::InvalidSteward(
+            steward.clone(),
+        )));
+    };
+
+    let payments: Vec<PGFTarget> = serde_json::from_slice(payments)
+        .map_err(|e| TxSubmitError::InvalidPgfBatch(e.to_string()))?;
+    if payments.is_empty() && !tx_args.force {
+        edisplay_line!(context.io(), "The payment batch is empty.");
+        return Err(Error::from(TxSubmitError::InvalidPgfBatch(
+            "The batch must contain at least one payment".to_string(),
+        )));
+    }
+    if payments.len() > MAX_PGF_ACTIONS && !tx_args.force {
+        edisplay_line!(
+            context.io(),
+            "The payment batch contains more than {} payments.",
+            MAX_PGF_ACTIONS
+        );
+        return Err(Error::from(TxSubmitError::InvalidPgfBatch(format!(
+            "The batch must contain at most {MAX_PGF_ACTIONS} payments"
+        ))));
+    }
+
+    let data = SubmitPgfBatchData {
+        steward: steward.clone(),
+        payments,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to escalate a steward payment batch to a governance
+/// proposal
+pub async fn build_challenge_pgf_batch(
+    context: &impl Namada,
+    args::ChallengePgfBatch {
+        tx: tx_args,
+        challenger,
+        batch_id,
+        content,
+        tx_code_path,
+    }: &args::ChallengePgfBatch,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(challenger.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(challenger.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if !rpc::is_validator(context.client(), challenger).await? && !tx_args.force
+    {
+        edisplay_line!(
+            context.io(),
+            "The address {} doesn't belong to any known validator account.",
+            challenger
+        );
+        return Err(Error::from(TxSubmitError::InvalidValidatorAddress(
+            challenger.clone(),
+        )));
+    }
+
+    let batch = rpc::query_pgf_batch(context.client(), *batch_id).await?;
+    let current_epoch = rpc::query_epoch(context.client()).await?;
+    match batch {
+        Some(batch) if batch.is_challengeable(current_epoch) => {}
+        Some(_) => {
+            edisplay_line!(
+                context.io(),
+                "The steward payment batch {} can't be challenged anymore.",
+                batch_id
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::InvalidPgfBatchChallenge(*batch_id),
+                ));
+            }
+        }
+        None => {
+            edisplay_line!(
+                context.io(),
+                "The steward payment batch {} doesn't exist.",
+                batch_id
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::PgfBatchDoesNotExist(
+                    *batch_id,
+                )));
+            }
+        }
+    }
+
+    let content: BTreeMap<String, String> = serde_json::from_slice(content)
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+    let content = borsh::to_vec(&content)
+        .map_err(|e| Error::from(EncodingError::Conversion(e.to_string())))?;
+
+    let data = ChallengePgfBatchData {
+        batch_id: *batch_id,
+        challenger: challenger.clone(),
+        content: Hash::default(),
+    };
+    let add_section = |tx: &mut Tx, data: &mut ChallengePgfBatchData| {
+        let (_, extra_section_hash) = tx.add_extra_section(content, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        add_section,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit transaction to unjail a jailed validator
 pub async fn build_unjail_validator(
     context: &impl Namada,
@@ -2164,6 +2341,104 @@ pub async fn build_ibc_transfer(
     Ok((tx, signing_data, shielded_tx_epoch))
 }
 
+/// Build a transaction to register an interchain account on the host chain
+pub async fn build_ibc_register_account(
+    context: &impl Namada,
+    args::TxRegisterInterchainAccount {
+        tx: tx_args,
+        owner,
+        connection_id,
+        version,
+        tx_code_path,
+    }: &args::TxRegisterInterchainAccount,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+    // Check that the owner address exists on chain
+    let owner =
+        source_exists_or_err(owner.clone(), tx_args.force, context).await?;
+
+    let message = MsgRegisterInterchainAccount {
+        owner,
+        connection_id: connection_id.clone(),
+        version: version.clone().unwrap_or_default(),
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        message,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a transaction to send transactions to be executed by the
+/// interchain account on the host chain
+pub async fn build_ibc_send_interchain_tx(
+    context: &impl Namada,
+    args::TxSendInterchainTx {
+        tx: tx_args,
+        owner,
+        connection_id,
+        data,
+        timeout_sec_offset,
+        memo,
+        tx_code_path,
+    }: &args::TxSendInterchainTx,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+    // Check that the owner address exists on chain
+    let owner =
+        source_exists_or_err(owner.clone(), tx_args.force, context).await?;
+
+    let packet_data = InterchainAccountPacketData::new(
+        data,
+        memo.clone().unwrap_or_default(),
+    );
+    // The timeout is relative to the block time when the packet is sent
+    let relative_timeout =
+        Duration::from_secs(timeout_sec_offset.unwrap_or(3600)).as_nanos();
+    let relative_timeout = u64::try_from(relative_timeout).map_err(|_| {
+        Error::Other("The timeout offset is too large".to_string())
+    })?;
+    let message = MsgSendInterchainTx {
+        owner,
+        connection_id: connection_id.clone(),
+        packet_data,
+        relative_timeout,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        message,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Abstraction for helping build transactions
 #[allow(clippy::too_many_arguments)]
 pub async fn build<F, D>(
@@ -2420,6 +2695,163 @@ pub async fn build_transfer<N: Namada>(
     Ok((tx, signing_data, shielded_tx_epoch))
 }
 
+/// Submit a batch of transparent transfers to be applied atomically
+pub async fn build_multi_transfer<N: Namada>(
+    context: &N,
+    args: &mut args::TxMultiTransfer,
+) -> Result<(Tx, SigningTxData)> {
+    if args.legs.is_empty() {
+        return Err(Error::Other(
+            "A multi-transfer must have at least one leg".to_string(),
+        ));
+    }
+
+    let mut legs = Vec::with_capacity(args.legs.len());
+    for leg in args.legs.iter_mut() {
+        // Shielded transfers must be submitted on their own
+        if leg.source == MASP || leg.target == MASP {
+            return Err(Error::Other(format!(
+                "A multi-transfer leg cannot involve the MASP, found a leg \
+                 from {} to {}",
+                leg.source, leg.target
+            )));
+        }
+        // Check that the source address exists on chain
+        source_exists_or_err(leg.source.clone(), args.tx.force, context)
+            .await?;
+        // Check that the target address exists on chain
+        target_exists_or_err(leg.target.clone(), args.tx.force, context)
+            .await?;
+        // validate the amount given
+        let validated_amount =
+            validate_amount(context, leg.amount, &leg.token, args.tx.force)
+                .await?;
+        leg.amount = InputAmount::Validated(validated_amount);
+        legs.push(token::TransferLeg {
+            source: leg.source.clone(),
+            target: leg.target.clone(),
+            token: leg.token.clone(),
+            amount: validated_amount,
+        });
+    }
+    let multi_transfer = token::MultiTransfer { legs };
+
+    let sources = multi_transfer
+        .legs
+        .iter()
+        .map(|leg| leg.source.clone())
+        .unique()
+        .collect();
+    let signing_data =
+        signing::multi_transfer_signing_data(context, &args.tx, sources)
+            .await?;
+    let fee_payer = Address::from(&signing_data.fee_payer);
+
+    // Check the balance of each source against its total debit
+    let mut tx_source_balance = None;
+    for ((source, token), debit) in multi_transfer.debits() {
+        let post_balance = check_balance_too_low_err(
+            token,
+            source,
+            debit,
+            balance_key(token, source),
+            args.tx.force,
+            context,
+        )
+        .await?;
+        if *source == fee_payer && *token == args.tx.fee_token {
+            tx_source_balance = Some(TxSourcePostBalance {
+                post_balance,
+                source: source.clone(),
+                token: token.clone(),
+            });
+        }
+    }
+
+    build(
+        context,
+        &args.tx,
+        args.tx_code_path.clone(),
+        multi_transfer,
+        do_nothing,
+        &signing_data.fee_payer,
+        tx_source_balance,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit a single MASP transaction spending the notes of several spending
+/// keys to pay several shielded outputs
+pub async fn build_shielded_multi_transfer<N: Namada>(
+    context: &N,
+    args: &mut args::TxShieldedMultiTransfer,
+) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
+    // Use the special sentinel key recognized by VPs as the default signer
+    let signing_data =
+        signing::aux_signing_data(context, &args.tx, Some(MASP), Some(MASP))
+            .await?;
+
+    let ShieldedTransfer {
+        builder,
+        masp_tx,
+        metadata,
+        epoch,
+    } = args
+        .shielded
+        .clone()
+        .build(context)
+        .await
+        .map_err(|err| TxSubmitError::MaspError(err.to_string()))?;
+    // Get the decoded asset types used in the transaction to give offline
+    // wallet users more information
+    let asset_types = used_asset_types(context, &builder)
+        .await
+        .unwrap_or_default();
+
+    // The amounts and tokens are all carried by the MASP Transaction, so the
+    // transparent part is redacted like for any transfer to self
+    let transfer = token::Transfer {
+        source: MASP,
+        target: MASP,
+        token: args.native_token.clone(),
+        amount: token::Amount::zero().into(),
+        key: None,
+        // Link the Transfer to the MASP Transaction by hash code
+        shielded: None,
+    };
+
+    let add_shielded = |tx: &mut Tx, transfer: &mut token::Transfer| {
+        // Add a MASP Transaction section to the Tx and get the tx hash
+        let masp_tx_hash = tx.add_masp_tx_section(masp_tx).1;
+        transfer.shielded = Some(masp_tx_hash);
+
+        tracing::debug!("Transfer data {:?}", transfer);
+
+        tx.add_masp_builder(MaspBuilder {
+            asset_types,
+            // Store how the Info objects map to Descriptors/Outputs
+            metadata,
+            // Store the data that was used to construct the Transaction
+            builder,
+            // Link the Builder to the Transaction by hash code
+            target: masp_tx_hash,
+        });
+        Ok(())
+    };
+    let tx = build_pow_flag(
+        context,
+        &args.tx,
+        args.tx_code_path.clone(),
+        transfer,
+        add_shielded,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await?;
+    Ok((tx, signing_data, Some(epoch)))
+}
+
 // Construct the shielded part of the transaction, if any
 async fn construct_shielded_parts<N: Namada>(
     context: &N,
@@ -2781,10 +3213,11 @@ async fn get_ibc_src_port_channel(
 async fn expect_dry_broadcast(
     to_broadcast: TxBroadcastData,
     context: &impl Namada,
+    trace: bool,
 ) -> Result<ProcessTxResponse> {
     match to_broadcast {
         TxBroadcastData::DryRun(tx) => {
-            let result = rpc::dry_run_tx(context, tx.to_bytes()).await?;
+            let result = rpc::dry_run_tx(context, tx.to_bytes(), trace).await?;
             Ok(ProcessTxResponse::DryRun(result))
         }
         TxBroadcastData::Live {
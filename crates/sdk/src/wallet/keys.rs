@@ -199,17 +199,8 @@ where
 impl<T: BorshSerialize + BorshDeserialize> EncryptedKeypair<T> {
     /// Encrypt a keypair and store it with its salt.
     pub fn new(keypair: &T, password: Zeroizing<String>) -> Self {
-        let salt = encryption_salt();
-        let encryption_key = encryption_key(&salt, &password);
-
         let data = keypair.serialize_to_vec();
-
-        let encrypted_keypair = aead::seal(&encryption_key, &data)
-            .expect("Encryption of data shouldn't fail");
-
-        let encrypted_data = [salt.as_ref(), &encrypted_keypair].concat();
-
-        Self(encrypted_data, PhantomData)
+        Self(encrypt_with_password(&data, &password), PhantomData)
     }
 
     /// Decrypt an encrypted keypair
@@ -217,20 +208,43 @@ impl<T: BorshSerialize + BorshDeserialize> EncryptedKeypair<T> {
         &self,
         password: Zeroizing<String>,
     ) -> Result<T, DecryptionError> {
-        let salt_len = encryption_salt().len();
-        let (raw_salt, cipher) = self.0.split_at(salt_len);
+        let decrypted_data = decrypt_with_password(&self.0, &password)?;
 
-        let salt = kdf::Salt::from_slice(raw_salt)
-            .map_err(|_| DecryptionError::BadSalt)?;
+        T::try_from_slice(&decrypted_data)
+            .map_err(|_| DecryptionError::DeserializingError)
+    }
+}
 
-        let encryption_key = encryption_key(&salt, &password);
+/// Encrypt the given data with a key derived from the password. The salt is
+/// prepended to the returned cipher text.
+pub(crate) fn encrypt_with_password(data: &[u8], password: &str) -> Vec<u8> {
+    let salt = encryption_salt();
+    let encryption_key = encryption_key(&salt, password);
 
-        let decrypted_data = aead::open(&encryption_key, cipher)
-            .map_err(|_| DecryptionError::DecryptionError)?;
+    let encrypted_data = aead::seal(&encryption_key, data)
+        .expect("Encryption of data shouldn't fail");
 
-        T::try_from_slice(&decrypted_data)
-            .map_err(|_| DecryptionError::DeserializingError)
+    [salt.as_ref(), &encrypted_data].concat()
+}
+
+/// Decrypt data that was encrypted with [`encrypt_with_password`].
+pub(crate) fn decrypt_with_password(
+    data: &[u8],
+    password: &str,
+) -> Result<Vec<u8>, DecryptionError> {
+    let salt_len = encryption_salt().len();
+    if data.len() < salt_len {
+        return Err(DecryptionError::BadSalt);
     }
+    let (raw_salt, cipher) = data.split_at(salt_len);
+
+    let salt = kdf::Salt::from_slice(raw_salt)
+        .map_err(|_| DecryptionError::BadSalt)?;
+
+    let encryption_key = encryption_key(&salt, password);
+
+    aead::open(&encryption_key, cipher)
+        .map_err(|_| DecryptionError::DecryptionError)
 }
 
 /// Keypair encryption salt
@@ -112,6 +112,12 @@ pub enum LoadStoreError {
     /// Wallet store writing error
     #[error("Failed to write the wallet store: {0}")]
     StoreNewWallet(String),
+    /// The wallet store is encrypted but no passphrase is available
+    #[error("The wallet store is encrypted and no passphrase was provided")]
+    Locked,
+    /// Wallet store decryption error
+    #[error("Failed to decrypt the wallet store: {0}")]
+    Decrypt(DecryptionError),
 }
 
 /// Captures the permanent storage parts of the wallet's functioning
@@ -139,6 +145,13 @@ pub mod fs {
     pub trait FsWalletStorage: Clone {
         /// The directory in which the wallet is supposed to be stored
         fn store_dir(&self) -> &PathBuf;
+
+        /// Read the passphrase that unlocks a wallet store which is encrypted
+        /// as a whole. Only called when loading such a store and no
+        /// passphrase has been set on the wallet beforehand.
+        fn read_store_passphrase(&self) -> Option<Zeroizing<String>> {
+            None
+        }
     }
 
     /// Wallet file name
@@ -146,7 +159,10 @@ pub mod fs {
 
     impl<F: FsWalletStorage> WalletStorage for F {
         fn save<U>(&self, wallet: &Wallet<U>) -> Result<(), LoadStoreError> {
-            let data = wallet.store.encode();
+            let data = match &wallet.store_passphrase {
+                Some(passphrase) => wallet.store.encode_encrypted(passphrase),
+                None => wallet.store.encode(),
+            };
             let wallet_path = self.store_dir().join(FILE_NAME);
             // Make sure the dir exists
             let wallet_dir = wallet_path.parent().unwrap();
@@ -195,8 +211,20 @@ pub mod fs {
                     err.to_string(),
                 )
             })?;
-            wallet.store =
-                Store::decode(store).map_err(LoadStoreError::Decode)?;
+            if Store::is_encrypted(&store) {
+                let passphrase = wallet
+                    .store_passphrase
+                    .clone()
+                    .or_else(|| self.read_store_passphrase())
+                    .ok_or(LoadStoreError::Locked)?;
+                wallet.store = Store::decode_encrypted(store, &passphrase)?;
+                // Keep the passphrase so that the store is encrypted again
+                // when saved
+                wallet.store_passphrase = Some(passphrase);
+            } else {
+                wallet.store =
+                    Store::decode(store).map_err(LoadStoreError::Decode)?;
+            }
             Ok(())
         }
     }
@@ -236,6 +264,9 @@ pub fn gen_secret_key(
         SchemeType::Secp256k1 => {
             secp256k1::SigScheme::generate(csprng).try_to_sk()
         }
+        SchemeType::Secp256r1 => {
+            secp256r1::SigScheme::generate(csprng).try_to_sk()
+        }
         SchemeType::Common => common::SigScheme::generate(csprng).try_to_sk(),
     }
     .unwrap()
@@ -267,6 +298,8 @@ pub struct Wallet<U> {
     /// Location where this shielded context is saved
     utils: U,
     store: Store,
+    /// If set, the whole store is encrypted with this passphrase when saved
+    store_passphrase: Option<Zeroizing<String>>,
     decrypted_key_cache: HashMap<Alias, common::SecretKey>,
     decrypted_spendkey_cache: HashMap<Alias, ExtendedSpendingKey>,
 }
@@ -283,6 +316,7 @@ impl<U> Wallet<U> {
         Self {
             utils,
             store,
+            store_passphrase: None,
             decrypted_key_cache: HashMap::default(),
             decrypted_spendkey_cache: HashMap::default(),
         }
@@ -327,6 +361,24 @@ impl<U> Wallet<U> {
         &mut self.store
     }
 
+    /// Indicates whether the backing store is encrypted as a whole when saved
+    pub fn is_store_encrypted(&self) -> bool {
+        self.store_passphrase.is_some()
+    }
+
+    /// Set the passphrase used to encrypt the whole backing store when it is
+    /// saved. This also unlocks an encrypted store before it is loaded and,
+    /// on an already encrypted store, rotates its passphrase.
+    pub fn set_store_passphrase(&mut self, passphrase: Zeroizing<String>) {
+        self.store_passphrase = Some(passphrase);
+    }
+
+    /// Stop encrypting the backing store as a whole. Secret keys that were
+    /// encrypted individually remain encrypted.
+    pub fn remove_store_passphrase(&mut self) {
+        self.store_passphrase = None;
+    }
+
     /// Extend this wallet from pre-genesis validator wallet.
     pub fn extend_from_pre_genesis_validator(
         &mut self,
@@ -549,7 +601,8 @@ impl<U: WalletIo> Wallet<U> {
             scheme,
             seed.as_bytes(),
             derivation_path.clone(),
-        );
+        )
+        .map_err(GenRestoreKeyError::DerivationPathError)?;
 
         self.insert_keypair(
             alias.unwrap_or_default(),
@@ -655,7 +708,8 @@ impl<U: WalletIo> Wallet<U> {
             scheme,
             seed.as_bytes(),
             derivation_path.clone(),
-        );
+        )
+        .map_err(GenRestoreKeyError::DerivationPathError)?;
         self.insert_keypair(
             alias.unwrap_or_default(),
             alias_force,
@@ -5,6 +5,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 use bimap::BiBTreeMap;
+use data_encoding::HEXLOWER;
 use itertools::Itertools;
 use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada_core::types::address::{Address, ImplicitAddress};
@@ -17,9 +18,10 @@ use slip10_ed25519;
 use zeroize::Zeroizing;
 
 use super::alias::{self, Alias};
-use super::derivation_path::DerivationPath;
+use super::derivation_path::{DerivationPath, DerivationPathError};
+use super::keys::{decrypt_with_password, encrypt_with_password};
 use super::pre_genesis;
-use crate::wallet::{StoredKeypair, WalletIo};
+use crate::wallet::{LoadStoreError, StoredKeypair, WalletIo};
 
 /// Actions that can be taken when there is an alias conflict
 pub enum ConfirmationResponse {
@@ -693,15 +695,62 @@ impl Store {
     pub fn encode(&self) -> Vec<u8> {
         toml::to_vec(self).expect("Serializing of store shouldn't fail")
     }
+
+    /// Check whether the given bytes contain a store that is encrypted as a
+    /// whole, rather than one with only its secret keys encrypted
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        toml::from_slice::<EncryptedStore>(data).is_ok()
+    }
+
+    /// Decrypt and decode a store that was encrypted as a whole with the
+    /// given passphrase
+    pub fn decode_encrypted(
+        data: Vec<u8>,
+        passphrase: &str,
+    ) -> Result<Self, LoadStoreError> {
+        let encrypted: EncryptedStore =
+            toml::from_slice(&data).map_err(LoadStoreError::Decode)?;
+        let cipher = HEXLOWER
+            .decode(encrypted.encrypted_store.as_bytes())
+            .map_err(|err| {
+                LoadStoreError::Decode(serde::de::Error::custom(err))
+            })?;
+        let data = Zeroizing::new(
+            decrypt_with_password(&cipher, passphrase)
+                .map_err(LoadStoreError::Decrypt)?,
+        );
+        toml::from_slice(&data).map_err(LoadStoreError::Decode)
+    }
+
+    /// Encode a store and encrypt it as a whole with the given passphrase
+    pub fn encode_encrypted(&self, passphrase: &str) -> Vec<u8> {
+        let data = Zeroizing::new(self.encode());
+        let encrypted = EncryptedStore {
+            encrypted_store: HEXLOWER
+                .encode(&encrypt_with_password(&data, passphrase)),
+        };
+        toml::to_vec(&encrypted)
+            .expect("Serializing of encrypted store shouldn't fail")
+    }
 }
 
-/// Generate a new secret key from the seed.
+/// A store that is encrypted as a whole, hiding its aliases, addresses and
+/// viewing keys in addition to its secret keys
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct EncryptedStore {
+    /// Hex encoded encryption salt followed by the encrypted TOML store
+    encrypted_store: String,
+}
+
+/// Generate a new secret key from the seed. Fails for the schemes whose keys
+/// can't be derived.
 pub fn derive_hd_secret_key(
     scheme: SchemeType,
     seed: &[u8],
     derivation_path: DerivationPath,
-) -> common::SecretKey {
-    match scheme {
+) -> Result<common::SecretKey, DerivationPathError> {
+    let sk = match scheme {
         SchemeType::Ed25519 => {
             let indexes = derivation_path
                 .path()
@@ -723,13 +772,11 @@ pub fn derive_hd_secret_key(
                 .try_to_sk()
                 .unwrap()
         }
-        SchemeType::Common => {
-            panic!(
-                "Cannot generate common signing scheme. Must convert from \
-                 alternative scheme."
-            )
+        SchemeType::Secp256r1 | SchemeType::Common => {
+            return Err(DerivationPathError::UnsupportedScheme(scheme));
         }
-    }
+    };
+    Ok(sk)
 }
 
 impl Display for AddressVpType {
@@ -803,7 +850,8 @@ mod test_wallet {
             DerivationPath::from_path_str(SCHEME, DERIVATION_PATH)
                 .expect("Derivation path construction cannot fail");
 
-        let sk = derive_hd_secret_key(SCHEME, seed.as_bytes(), derivation_path);
+        let sk = derive_hd_secret_key(SCHEME, seed.as_bytes(), derivation_path)
+            .expect("Secret key derivation cannot fail");
 
         assert_eq!(&sk.to_string()[2..], SK_EXPECTED);
     }
@@ -833,13 +881,15 @@ mod test_wallet {
             DerivationPath::from_path_str(SCHEME, DERIVATION_PATH_HARDENED)
                 .expect("Derivation path construction cannot fail");
 
-        let sk = derive_hd_secret_key(SCHEME, seed.as_bytes(), derivation_path);
+        let sk = derive_hd_secret_key(SCHEME, seed.as_bytes(), derivation_path)
+            .expect("Secret key derivation cannot fail");
 
         let sk_hard = derive_hd_secret_key(
             SCHEME,
             seed.as_bytes(),
             derivation_path_hardened,
-        );
+        )
+        .expect("Secret key derivation cannot fail");
 
         // check that indexes are promoted to hardened
         assert_eq!(&sk.to_string(), &sk_hard.to_string());
@@ -859,7 +909,8 @@ mod test_wallet {
                 .as_slice(),
             DerivationPath::from_path_str(scheme, derivation_path)
                 .expect("Derivation path construction cannot fail"),
-        );
+        )
+        .expect("Secret key derivation cannot fail");
         let sk_expected = if priv_key.starts_with("xprv") {
             // this is an extended private key encoded in base58
             let xprv =
@@ -871,6 +922,23 @@ mod test_wallet {
         assert_eq!(&sk.to_string()[2..], sk_expected);
     }
 
+    #[test]
+    fn gen_sk_from_seed_unsupported_scheme() {
+        const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+        for scheme in [SchemeType::Secp256r1, SchemeType::Common] {
+            let result = derive_hd_secret_key(
+                scheme,
+                HEXLOWER.decode(SEED.as_bytes()).unwrap().as_slice(),
+                DerivationPath::from_path_str(scheme, "m/44'/877'/0'/0/0")
+                    .unwrap(),
+            );
+            assert!(matches!(
+                result,
+                Err(DerivationPathError::UnsupportedScheme(s)) if s == scheme
+            ));
+        }
+    }
+
     #[test]
     fn gen_sk_from_seed_secp256k1() {
         const SCHEME: SchemeType = SchemeType::Secp256k1;
@@ -922,4 +990,37 @@ mod test_wallet {
             do_test_gen_sk_from_seed_and_derivation_path(SCHEME, SEED, "m/0'/2147483647'/1'/2147483646'/2'", "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d");
         }
     }
+
+    #[test]
+    fn encrypted_store_roundtrip() {
+        use namada_core::types::address::testing::established_address_1;
+
+        use crate::wallet::fs::FsWalletUtils;
+
+        let mut store = Store::default();
+        let address = established_address_1();
+        store.insert_address::<FsWalletUtils>(
+            "secret-alias".into(),
+            address.clone(),
+            false,
+        );
+
+        let plain = store.encode();
+        assert!(!Store::is_encrypted(&plain));
+
+        let encrypted = store.encode_encrypted("passphrase");
+        assert!(Store::is_encrypted(&encrypted));
+        let encrypted_str = String::from_utf8(encrypted.clone()).unwrap();
+        assert!(!encrypted_str.contains("secret-alias"));
+        assert!(!encrypted_str.contains(&address.encode()));
+
+        let decrypted =
+            Store::decode_encrypted(encrypted.clone(), "passphrase").unwrap();
+        assert_eq!(decrypted.find_address("secret-alias"), Some(&address));
+
+        assert!(matches!(
+            Store::decode_encrypted(encrypted, "wrong passphrase"),
+            Err(LoadStoreError::Decrypt(_))
+        ));
+    }
 }
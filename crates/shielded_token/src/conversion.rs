@@ -10,10 +10,11 @@ use namada_storage::{StorageRead, StorageWrite};
 use namada_trans_token::storage_key::{balance_key, minted_balance_key};
 use namada_trans_token::{read_denom, Amount, DenominatedAmount};
 
+use crate::storage::prune_reward_rate_history;
 use crate::storage_key::{
     masp_kd_gain_key, masp_kp_gain_key, masp_last_inflation_key,
     masp_last_locked_ratio_key, masp_locked_ratio_target_key,
-    masp_max_reward_rate_key,
+    masp_max_reward_rate_key, masp_reward_rate_history_key,
 };
 
 /// Compute the MASP rewards by applying the PD-controller to the genesis
@@ -167,6 +168,18 @@ where
 
     wl_storage.write(&masp_last_locked_ratio_key(addr), locked_ratio)?;
 
+    // Keep track of the reward rate handed out to the shielded holdings of
+    // this token in the current epoch
+    let reward_rate = Dec::try_from(noterized_inflation)
+        .ok()
+        .zip(Dec::try_from(precision).ok())
+        .and_then(|(inflation, precision)| inflation.trunc_div(&precision))
+        .unwrap_or_default();
+    let epoch = wl_storage.storage.block.epoch;
+    wl_storage
+        .write(&masp_reward_rate_history_key(addr, epoch), reward_rate)?;
+    prune_reward_rate_history(wl_storage, addr, epoch)?;
+
     Ok((noterized_inflation, precision))
 }
 
@@ -519,6 +532,7 @@ mod tests {
                 min_duration: DurationSecs(3600),
             },
             max_expected_time_per_block: DurationSecs(3600),
+            tx_expiration_window: DurationSecs(86400),
             max_proposal_bytes: Default::default(),
             max_block_gas: 100,
             vp_whitelist: vec![],
@@ -584,6 +598,15 @@ mod tests {
             println!();
             println!();
         }
+
+        // The reward rate of every token must have been recorded
+        let epoch = s.storage.block.epoch;
+        for token_addr in tokens().keys() {
+            let reward_rate: Option<Dec> = s
+                .read(&masp_reward_rate_history_key(token_addr, epoch))
+                .unwrap();
+            assert!(reward_rate.is_some());
+        }
     }
 
     pub fn tokens() -> HashMap<Address, (&'static str, Denomination)> {
@@ -1,12 +1,21 @@
+use std::collections::BTreeMap;
+
+use masp_primitives::merkle_tree::CommitmentTree;
+use masp_primitives::sapling::Node;
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
+use namada_core::types::storage::{DbKeySeg, Epoch, KeySeg};
 use namada_core::types::token;
 use namada_core::types::token::Amount;
 use namada_storage as storage;
-use namada_storage::{StorageRead, StorageWrite};
+use namada_storage::{ResultExt, StorageRead, StorageWrite};
 
 use crate::storage_key::*;
 
+/// The number of past epochs for which the MASP reward rate history is
+/// retained. The rate of an older epoch is pruned when a new one is recorded.
+pub const REWARD_RATE_HISTORY_RETENTION_EPOCHS: u64 = 1_000;
+
 /// Initialize parameters for the token in storage during the genesis block.
 pub fn write_params<S>(
     params: &token::Parameters,
@@ -30,3 +39,106 @@ where
     storage.write(&masp_kd_gain_key(address), kd_gain_nom)?;
     Ok(())
 }
+
+/// Read the number of note commitments in the MASP note commitment tree.
+pub fn read_note_commitment_count<S>(storage: &S) -> storage::Result<u64>
+where
+    S: StorageRead,
+{
+    let tree: Option<CommitmentTree<Node>> =
+        storage.read(&masp_commitment_tree_key())?;
+    Ok(tree.map_or(0, |tree| tree.size() as u64))
+}
+
+/// Read the number of MASP nullifiers revealed in each epoch.
+pub fn read_nullifier_counts<S>(
+    storage: &S,
+) -> storage::Result<BTreeMap<Epoch, u64>>
+where
+    S: StorageRead,
+{
+    let mut counts = BTreeMap::new();
+    for entry in
+        storage::iter_prefix::<u64>(storage, &masp_nullifier_count_prefix())?
+    {
+        let (key, count) = entry?;
+        if let Some(epoch) = is_masp_nullifier_count_key(&key) {
+            counts.insert(epoch, count);
+        }
+    }
+    Ok(counts)
+}
+
+/// Read the MASP reward rate of the given token for each epoch in which it
+/// was computed.
+pub fn read_reward_rate_history<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<BTreeMap<Epoch, Dec>>
+where
+    S: StorageRead,
+{
+    let mut history = BTreeMap::new();
+    for entry in storage::iter_prefix::<Dec>(
+        storage,
+        &masp_reward_rate_history_prefix(token),
+    )? {
+        let (key, rate) = entry?;
+        if let Some(DbKeySeg::StringSeg(epoch)) = key.last() {
+            let epoch = Epoch::parse(epoch.clone()).into_storage_result()?;
+            history.insert(epoch, rate);
+        }
+    }
+    Ok(history)
+}
+
+/// Remove the MASP reward rate of the given token for the epoch that has just
+/// fallen out of the retention window
+pub fn prune_reward_rate_history<S>(
+    storage: &mut S,
+    token: &Address,
+    current_epoch: Epoch,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if let Some(epoch) =
+        current_epoch.checked_sub(REWARD_RATE_HISTORY_RETENTION_EPOCHS)
+    {
+        storage.delete(&masp_reward_rate_history_key(token, epoch))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address;
+    use namada_state::testing::TestWlStorage;
+
+    use super::*;
+
+    /// Test that the reward rate history is pruned past the retention window
+    #[test]
+    fn test_prune_reward_rate_history() {
+        let mut storage = TestWlStorage::default();
+        let token = address::nam();
+        let retention = REWARD_RATE_HISTORY_RETENTION_EPOCHS;
+        for epoch in 0..retention + 2 {
+            storage
+                .write(
+                    &masp_reward_rate_history_key(&token, Epoch(epoch)),
+                    Dec::one(),
+                )
+                .unwrap();
+            prune_reward_rate_history(&mut storage, &token, Epoch(epoch))
+                .unwrap();
+        }
+
+        let history = read_reward_rate_history(&storage, &token).unwrap();
+        assert_eq!(history.len() as u64, retention);
+        assert!(!history.contains_key(&Epoch(0)));
+        assert!(!history.contains_key(&Epoch(1)));
+        assert!(history.contains_key(&Epoch(2)));
+        assert!(history.contains_key(&Epoch(retention + 1)));
+    }
+}
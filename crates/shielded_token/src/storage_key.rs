@@ -4,13 +4,15 @@ use masp_primitives::bls12_381::Scalar;
 use masp_primitives::sapling::Nullifier;
 use namada_core::types::address::{self, Address};
 use namada_core::types::hash::Hash;
-use namada_core::types::storage::{self, DbKeySeg, KeySeg};
+use namada_core::types::storage::{self, DbKeySeg, Epoch, KeySeg};
 use namada_trans_token::storage_key::key_of_token;
 
 /// Key segment prefix for pinned shielded transactions
 pub const PIN_KEY_PREFIX: &str = "pin-";
 /// Key segment prefix for the nullifiers
 pub const MASP_NULLIFIERS_KEY: &str = "nullifiers";
+/// Key segment prefix for the number of nullifiers revealed per epoch
+pub const MASP_NULLIFIER_COUNT_KEY: &str = "nullifier_count";
 /// Key segment prefix for the note commitment merkle tree
 pub const MASP_NOTE_COMMITMENT_TREE_KEY: &str = "commitment_tree";
 /// Key segment prefix for the note commitment anchor
@@ -30,6 +32,8 @@ pub const MASP_KD_GAIN_KEY: &str = "derivative_gain";
 pub const MASP_LOCKED_RATIO_TARGET_KEY: &str = "locked_ratio_target";
 /// The key for the max reward rate for a given asset
 pub const MASP_MAX_REWARD_RATE_KEY: &str = "max_reward_rate";
+/// The key prefix for the per epoch reward rates of a given asset
+pub const MASP_REWARD_RATE_HISTORY_KEY: &str = "reward_rate_history";
 
 /// Obtain the nominal proportional key for the given token
 pub fn masp_kp_gain_key(token_addr: &Address) -> storage::Key {
@@ -77,6 +81,12 @@ pub fn is_masp_allowed_key(key: &storage::Key) -> bool {
             DbKeySeg::StringSeg(key),
             DbKeySeg::StringSeg(_nullifier),
         ] if *addr == address::MASP && key == MASP_NULLIFIERS_KEY => true,
+
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(key),
+            DbKeySeg::StringSeg(_epoch),
+        ] if *addr == address::MASP && key == MASP_NULLIFIER_COUNT_KEY => true,
         _ => false,
     }
 }
@@ -98,6 +108,21 @@ pub fn is_masp_nullifier_key(key: &storage::Key) -> bool {
         ] if *addr == address::MASP && prefix == MASP_NULLIFIERS_KEY)
 }
 
+/// Check if the given storage key is a masp nullifier count key and return
+/// the epoch it refers to
+pub fn is_masp_nullifier_count_key(key: &storage::Key) -> Option<Epoch> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(epoch),
+        ] if *addr == address::MASP && prefix == MASP_NULLIFIER_COUNT_KEY => {
+            Epoch::parse(epoch.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
 /// Obtain the storage key for the last locked ratio of a token
 pub fn masp_last_locked_ratio_key(token_address: &Address) -> storage::Key {
     key_of_token(
@@ -116,6 +141,25 @@ pub fn masp_last_inflation_key(token_address: &Address) -> storage::Key {
     )
 }
 
+/// Obtain the storage key prefix for the reward rate history of a token
+pub fn masp_reward_rate_history_prefix(token_address: &Address) -> storage::Key {
+    key_of_token(
+        token_address,
+        MASP_REWARD_RATE_HISTORY_KEY,
+        "cannot obtain storage key for the reward rate history",
+    )
+}
+
+/// Obtain the storage key for the reward rate of a token at the given epoch
+pub fn masp_reward_rate_history_key(
+    token_address: &Address,
+    epoch: Epoch,
+) -> storage::Key {
+    masp_reward_rate_history_prefix(token_address)
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get a key for a masp pin
 pub fn masp_pin_tx_key(key: &str) -> storage::Key {
     storage::Key::from(address::MASP.to_db_key())
@@ -132,6 +176,20 @@ pub fn masp_nullifier_key(nullifier: &Nullifier) -> storage::Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key prefix for the number of masp nullifiers revealed per epoch
+pub fn masp_nullifier_count_prefix() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_NULLIFIER_COUNT_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the number of masp nullifiers revealed in the given epoch
+pub fn masp_nullifier_count_key(epoch: Epoch) -> storage::Key {
+    masp_nullifier_count_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the key for the masp commitment tree
 pub fn masp_commitment_tree_key() -> storage::Key {
     storage::Key::from(address::MASP.to_db_key())
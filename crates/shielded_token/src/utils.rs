@@ -7,21 +7,32 @@ use namada_core::types::storage::IndexedTx;
 use namada_storage::{Error, Result, StorageRead, StorageWrite};
 
 use crate::storage_key::{
-    masp_commitment_tree_key, masp_nullifier_key, masp_pin_tx_key,
+    masp_commitment_tree_key, masp_nullifier_count_key, masp_nullifier_key,
+    masp_pin_tx_key,
 };
 
-// Writes the nullifiers of the provided masp transaction to storage
+// Writes the nullifiers of the provided masp transaction to storage and
+// accounts for them in the current epoch's nullifier count
 fn reveal_nullifiers(
-    ctx: &mut impl StorageWrite,
+    ctx: &mut (impl StorageRead + StorageWrite),
     transaction: &Transaction,
 ) -> Result<()> {
-    for description in transaction
+    let shielded_spends = transaction
         .sapling_bundle()
-        .map_or(&vec![], |description| &description.shielded_spends)
-    {
+        .map_or(&vec![], |description| &description.shielded_spends);
+    for description in shielded_spends {
         ctx.write(&masp_nullifier_key(&description.nullifier), ())?;
     }
 
+    if !shielded_spends.is_empty() {
+        let count_key = masp_nullifier_count_key(ctx.get_block_epoch()?);
+        let count: u64 = ctx.read(&count_key)?.unwrap_or_default();
+        let count = count
+            .checked_add(shielded_spends.len() as u64)
+            .ok_or(Error::SimpleMessage("Nullifier count overflow"))?;
+        ctx.write(&count_key, count)?;
+    }
+
     Ok(())
 }
 
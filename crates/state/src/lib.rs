@@ -8,6 +8,7 @@ use std::cmp::Ordering;
 use std::format;
 
 use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
+use namada_core::ledger::replay_protection;
 use namada_core::tendermint::merkle::proof::ProofOps;
 use namada_core::types::address::{
     Address, EstablishedAddressGen, InternalAddress,
@@ -48,6 +49,10 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// it has 2 blocks delay on validator set update.
 pub const EPOCH_SWITCH_BLOCKS_DELAY: u32 = 2;
 
+/// The minimum number of the last block heights whose diffs are retained when
+/// the diffs are pruned, as a rollback needs the diffs of the last two heights.
+pub const MIN_DIFFS_RETAINED_HEIGHTS: u64 = 2;
+
 /// The ledger's state
 #[derive(Debug)]
 pub struct State<D, H>
@@ -104,6 +109,13 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// How many of the last block heights have their diffs retained when the
+    /// older ones are pruned. When `None`, the diffs of all the heights are
+    /// kept in archive mode.
+    pub diffs_retained_heights: Option<u64>,
+    /// The height below which the diffs have been pruned, if any. The values
+    /// at these heights can no longer be read.
+    pub diffs_pruning_height: Option<BlockHeight>,
 }
 
 /// Last committed block
@@ -161,6 +173,14 @@ pub enum Error {
     BorshCodingError(std::io::Error),
     #[error("Merkle tree at the height {height} is not stored")]
     NoMerkleTree { height: BlockHeight },
+    #[error(
+        "The state at the height {height} has been pruned, the oldest height \
+         that can be queried is {oldest_height}"
+    )]
+    PrunedHeight {
+        height: BlockHeight,
+        oldest_height: BlockHeight,
+    },
     #[error("Code hash error: {0}")]
     InvalidCodeHash(HashError),
     #[error("DB error: {0}")]
@@ -209,6 +229,8 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            diffs_retained_heights: None,
+            diffs_pruning_height: None,
         }
     }
 
@@ -251,6 +273,7 @@ where
             self.tx_queue = tx_queue;
             self.ethereum_height = ethereum_height;
             self.eth_events_queue = eth_events_queue;
+            self.diffs_pruning_height = self.db.read_diffs_pruning_height()?;
             tracing::debug!("Loaded storage from DB");
         } else {
             tracing::info!("No state could be found");
@@ -326,9 +349,56 @@ where
             self.prune_merkle_tree_stores(&mut batch)?;
         }
         self.db.exec_batch(batch)?;
+        self.prune_diffs()?;
+        Ok(())
+    }
+
+    /// Prune the diffs of the heights older than the retained ones, if enabled.
+    /// The diffs are pruned up to the start of an epoch, so that the Merkle
+    /// trees of the retained heights can still be rebuilt from the snapshot
+    /// at the start of their epoch.
+    fn prune_diffs(&mut self) -> Result<()> {
+        let Some(retained_heights) = self.diffs_retained_heights else {
+            return Ok(());
+        };
+        let retained_heights = retained_heights.max(MIN_DIFFS_RETAINED_HEIGHTS);
+        let last_height = self.get_last_block_height();
+        if last_height.0 <= retained_heights {
+            return Ok(());
+        }
+        let oldest_height = BlockHeight(last_height.0 - retained_heights + 1);
+        let pruning_height = self
+            .block
+            .pred_epochs
+            .get_epoch(oldest_height)
+            .and_then(|epoch| {
+                self.block.pred_epochs.get_start_height_of_epoch(epoch)
+            })
+            .unwrap_or(oldest_height);
+        if self
+            .diffs_pruning_height
+            .map_or(true, |pruned| pruned < pruning_height)
+        {
+            tracing::debug!("Pruning the diffs below height {pruning_height}");
+            self.db.prune_diffs(pruning_height)?;
+            self.diffs_pruning_height = Some(pruning_height);
+        }
         Ok(())
     }
 
+    /// Check that the diffs at the given height haven't been pruned
+    fn check_height_not_pruned(&self, height: BlockHeight) -> Result<()> {
+        match self.diffs_pruning_height {
+            Some(oldest_height) if height < oldest_height => {
+                Err(Error::PrunedHeight {
+                    height,
+                    oldest_height,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Find the root hash of the merkle tree
     pub fn merkle_root(&self) -> MerkleRoot {
         self.block.tree.root()
@@ -372,6 +442,7 @@ where
         if height == BlockHeight(0) || height >= self.get_last_block_height() {
             self.read(key)
         } else {
+            self.check_height_not_pruned(height)?;
             match self.db.read_subspace_val_with_height(
                 key,
                 height,
@@ -547,6 +618,7 @@ where
         } else {
             height
         };
+        self.check_height_not_pruned(height)?;
 
         let epoch = self
             .block
@@ -966,6 +1038,10 @@ where
             }
             _ => BlockHeight(1),
         };
+        let oldest_height = match self.diffs_pruning_height {
+            Some(pruning_height) => oldest_height.max(pruning_height),
+            None => oldest_height,
+        };
         self.block
             .pred_epochs
             .get_epoch(oldest_height)
@@ -1021,8 +1097,9 @@ where
         &mut self,
         batch: &mut D::WriteBatch,
         key: &Key,
+        value: impl AsRef<[u8]>,
     ) -> Result<()> {
-        self.db.write_replay_protection_entry(batch, key)?;
+        self.db.write_replay_protection_entry(batch, key, value)?;
         Ok(())
     }
 
@@ -1036,14 +1113,79 @@ where
         Ok(())
     }
 
-    /// Iterate the replay protection storage from the last block
+    /// Iterate the replay protection storage from the last block, together
+    /// with the expiration of the txs, if known
     pub fn iter_replay_protection(
         &self,
-    ) -> Box<dyn Iterator<Item = Hash> + '_> {
-        Box::new(self.db.iter_replay_protection().map(|(raw_key, _, _)| {
-            raw_key.parse().expect("Failed hash conversion")
+    ) -> Box<dyn Iterator<Item = (Hash, Option<DateTimeUtc>)> + '_> {
+        Box::new(self.db.iter_replay_protection().map(|(raw_key, value, _)| {
+            let hash = raw_key.parse().expect("Failed hash conversion");
+            (hash, DateTimeUtc::try_from_slice(&value).ok())
         }))
     }
+
+    /// Iterate the replay protection entries of the txs that expired before
+    /// the given time, ordered by expiration
+    pub fn iter_expired_replay_protection(
+        &self,
+        time: DateTimeUtc,
+    ) -> Box<dyn Iterator<Item = (Hash, DateTimeUtc)> + '_> {
+        Box::new(
+            self.db
+                .iter_replay_protection_prefix(
+                    &replay_protection::expiry_prefix(),
+                )
+                .map(|(raw_key, _, _)| {
+                    replay_protection::parse_expiry_key(&raw_key)
+                        .expect("Failed expiry key conversion")
+                })
+                .take_while(move |(_, expiration)| *expiration < time)
+                .map(|(expiration, hash)| (hash, expiration)),
+        )
+    }
+
+    /// Give the provided expiration to the replay protection entries written
+    /// before their expiration was tracked, indexing them for pruning.
+    /// Returns the number of migrated entries.
+    ///
+    /// The legacy entries don't carry the expiration of their tx, so the
+    /// provided one must be late enough for any of these txs to have
+    /// expired by then.
+    pub fn migrate_replay_protection(
+        &mut self,
+        expiration: DateTimeUtc,
+    ) -> Result<u64> {
+        let legacy_hashes: Vec<Hash> = self
+            .db
+            .iter_replay_protection_prefix(&replay_protection::all_prefix())
+            .filter_map(|(raw_key, value, _)| {
+                value
+                    .is_empty()
+                    .then(|| raw_key.parse().expect("Failed hash conversion"))
+            })
+            .collect();
+        if legacy_hashes.is_empty() {
+            return Ok(0);
+        }
+
+        let mut batch = D::batch();
+        let value = expiration.serialize_to_vec();
+        for hash in &legacy_hashes {
+            self.db.write_replay_protection_entry(
+                &mut batch,
+                &replay_protection::all_key(hash),
+                &value,
+            )?;
+            self.db.write_replay_protection_entry(
+                &mut batch,
+                &replay_protection::expiry_key(&expiration, hash),
+                b"",
+            )?;
+        }
+        self.db.exec_batch(batch)?;
+
+        Ok(legacy_hashes.len() as u64)
+    }
 }
 
 impl From<MerkleTreeError> for Error {
@@ -1104,6 +1246,8 @@ pub mod testing {
                 ethereum_height: None,
                 eth_events_queue: EthEventsQueue::default(),
                 storage_read_past_height_limit: Some(1000),
+                diffs_retained_heights: None,
+                diffs_pruning_height: None,
             }
         }
     }
@@ -1208,6 +1352,7 @@ mod tests {
                 max_block_gas: 20_000_000,
                 epoch_duration: epoch_duration.clone(),
                 max_expected_time_per_block: Duration::seconds(max_expected_time_per_block).into(),
+                tx_expiration_window: Duration::seconds(86400).into(),
                 vp_whitelist: vec![],
                 tx_whitelist: vec![],
                 implicit_vp_code_hash: Some(Hash::zero()),
@@ -1342,4 +1487,51 @@ mod tests {
             assert_eq!(wl_storage.storage.block.epoch, epoch_before.next());
         }
     }
+
+    /// Test that the legacy replay protection entries get the given
+    /// expiration, while the other ones are kept as they are
+    #[test]
+    fn test_migrate_replay_protection() {
+        let mut storage = TestStorage::default();
+        let legacy = Hash::sha256("legacy".as_bytes());
+        let tracked = Hash::sha256("tracked".as_bytes());
+        let now = DateTimeUtc::now();
+        let tracked_expiration = now + Duration::seconds(10);
+        let mut batch = mockdb::MockDB::batch();
+        storage
+            .write_replay_protection_entry(
+                &mut batch,
+                &replay_protection::all_key(&legacy),
+                b"",
+            )
+            .unwrap();
+        storage
+            .write_replay_protection_entry(
+                &mut batch,
+                &replay_protection::all_key(&tracked),
+                tracked_expiration.serialize_to_vec(),
+            )
+            .unwrap();
+        storage
+            .write_replay_protection_entry(
+                &mut batch,
+                &replay_protection::expiry_key(&tracked_expiration, &tracked),
+                b"",
+            )
+            .unwrap();
+
+        let expiration = now + replay_protection::LEGACY_EXPIRATION_BOUND;
+        assert_eq!(storage.migrate_replay_protection(expiration).unwrap(), 1);
+        // Nothing is left to migrate
+        assert_eq!(storage.migrate_replay_protection(expiration).unwrap(), 0);
+
+        let expired: Vec<_> = storage
+            .iter_expired_replay_protection(expiration + Duration::seconds(1))
+            .collect();
+        assert_eq!(
+            expired,
+            vec![(tracked, tracked_expiration), (legacy, expiration)]
+        );
+        assert!(storage.has_replay_protection_entry(&legacy).unwrap());
+    }
 }
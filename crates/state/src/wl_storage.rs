@@ -101,8 +101,13 @@ pub trait WriteLogAndStorage {
     /// borrow checker)
     fn split_borrow(&mut self) -> (&mut WriteLog, &State<Self::D, Self::H>);
 
-    /// Write the provided tx hash to storage.
-    fn write_tx_hash(&mut self, hash: Hash) -> write_log::Result<()>;
+    /// Write the provided tx hash to storage, with the expiration of the tx
+    /// if known.
+    fn write_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: Option<DateTimeUtc>,
+    ) -> write_log::Result<()>;
 }
 
 impl<D, H> WriteLogAndStorage for WlStorage<D, H>
@@ -129,8 +134,12 @@ where
         (&mut self.write_log, &self.storage)
     }
 
-    fn write_tx_hash(&mut self, hash: Hash) -> write_log::Result<()> {
-        self.write_log.write_tx_hash(hash)
+    fn write_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: Option<DateTimeUtc>,
+    ) -> write_log::Result<()> {
+        self.write_log.write_tx_hash(hash, expiration)
     }
 }
 
@@ -158,8 +167,12 @@ where
         (&mut self.write_log, (self.storage))
     }
 
-    fn write_tx_hash(&mut self, hash: Hash) -> write_log::Result<()> {
-        self.write_log.write_tx_hash(hash)
+    fn write_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: Option<DateTimeUtc>,
+    ) -> write_log::Result<()> {
+        self.write_log.write_tx_hash(hash, expiration)
     }
 }
 
@@ -4,6 +4,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use itertools::Itertools;
+use namada_core::borsh::BorshSerializeExt;
 use namada_core::ledger::replay_protection;
 use namada_core::types::address::{
     Address, EstablishedAddressGen, InternalAddress,
@@ -11,6 +12,8 @@ use namada_core::types::address::{
 use namada_core::types::hash::{Hash, StorageHasher};
 use namada_core::types::ibc::IbcEvent;
 use namada_core::types::storage;
+use namada_core::types::time::DateTimeUtc;
+use namada_core::types::token::TransferLegEvent;
 use namada_gas::{MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE};
 use namada_trans_token::storage_key::{
     is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
@@ -70,12 +73,14 @@ pub enum StorageModification {
 #[derive(Debug, Clone)]
 /// A replay protection storage modification
 enum ReProtStorageModification {
-    /// Write an entry
-    Write,
+    /// Write an entry, with the expiration of the tx if known
+    Write(Option<DateTimeUtc>),
     /// Delete an entry
     Delete,
-    /// Finalize an entry
-    Finalize,
+    /// Finalize an entry, with the expiration of the tx if known
+    Finalize(Option<DateTimeUtc>),
+    /// Prune an expired entry
+    Prune(DateTimeUtc),
 }
 
 /// The write log storage
@@ -99,6 +104,9 @@ pub struct WriteLog {
     tx_precommit_write_log: HashMap<storage::Key, StorageModification>,
     /// The IBC events for the current transaction
     ibc_events: BTreeSet<IbcEvent>,
+    /// The events of the applied legs of a multi-transfer in the current
+    /// transaction
+    transfer_leg_events: Vec<TransferLegEvent>,
     /// Storage modifications for the replay protection storage, always
     /// committed regardless of the result of the transaction
     replay_protection: HashMap<Hash, ReProtStorageModification>,
@@ -128,6 +136,7 @@ impl Default for WriteLog {
             tx_write_log: HashMap::with_capacity(100),
             tx_precommit_write_log: HashMap::with_capacity(100),
             ibc_events: BTreeSet::new(),
+            transfer_leg_events: Vec::new(),
             replay_protection: HashMap::with_capacity(1_000),
         }
     }
@@ -381,6 +390,14 @@ impl WriteLog {
         len as u64 * MEMORY_ACCESS_GAS_PER_BYTE
     }
 
+    /// Set the event of an applied leg of a multi-transfer and return the gas
+    /// cost.
+    pub fn emit_transfer_leg_event(&mut self, event: TransferLegEvent) -> u64 {
+        let len = event.serialize_to_vec().len();
+        self.transfer_leg_events.push(event);
+        len as u64 * MEMORY_ACCESS_GAS_PER_BYTE
+    }
+
     /// Get the storage keys changed and accounts keys initialized in the
     /// current transaction. The account keys point to the validity predicates
     /// of the newly created accounts. The keys in the precommit are not
@@ -445,6 +462,16 @@ impl WriteLog {
         &self.ibc_events
     }
 
+    /// Take the events of the multi-transfer legs of the current transaction
+    pub fn take_transfer_leg_events(&mut self) -> Vec<TransferLegEvent> {
+        std::mem::take(&mut self.transfer_leg_events)
+    }
+
+    /// Get the events of the multi-transfer legs of the current transaction
+    pub fn get_transfer_leg_events(&self) -> &[TransferLegEvent] {
+        &self.transfer_leg_events
+    }
+
     /// Add the entire content of the tx write log to the precommit one. The tx
     /// log gets reset in the process.
     pub fn precommit_tx(&mut self) {
@@ -474,6 +501,7 @@ impl WriteLog {
 
         self.block_write_log.extend(tx_precommit_write_log);
         self.take_ibc_events();
+        self.take_transfer_leg_events();
     }
 
     /// Drop the current transaction's write log and precommit when it's
@@ -527,12 +555,15 @@ impl WriteLog {
 
         for (hash, entry) in self.replay_protection.iter() {
             match entry {
-                ReProtStorageModification::Write => storage
+                ReProtStorageModification::Write(expiration) => storage
                     .write_replay_protection_entry(
                         batch,
                         // Can only write tx hashes to the previous block, no
                         // further
                         &replay_protection::last_key(hash),
+                        expiration
+                            .map(|expiration| expiration.serialize_to_vec())
+                            .unwrap_or_default(),
                     )
                     .map_err(Error::StorageError)?,
                 ReProtStorageModification::Delete => storage
@@ -543,13 +574,29 @@ impl WriteLog {
                         &replay_protection::last_key(hash),
                     )
                     .map_err(Error::StorageError)?,
-                ReProtStorageModification::Finalize => {
+                ReProtStorageModification::Finalize(expiration) => {
                     storage
                         .write_replay_protection_entry(
                             batch,
                             &replay_protection::all_key(hash),
+                            expiration
+                                .map(|expiration| expiration.serialize_to_vec())
+                                .unwrap_or_default(),
                         )
                         .map_err(Error::StorageError)?;
+                    // Only the entries with a known expiration are indexed
+                    // for pruning, the others are kept forever
+                    if let Some(expiration) = expiration {
+                        storage
+                            .write_replay_protection_entry(
+                                batch,
+                                &replay_protection::expiry_key(
+                                    expiration, hash,
+                                ),
+                                b"",
+                            )
+                            .map_err(Error::StorageError)?;
+                    }
                     storage
                         .delete_replay_protection_entry(
                             batch,
@@ -557,6 +604,20 @@ impl WriteLog {
                         )
                         .map_err(Error::StorageError)?
                 }
+                ReProtStorageModification::Prune(expiration) => {
+                    storage
+                        .delete_replay_protection_entry(
+                            batch,
+                            &replay_protection::all_key(hash),
+                        )
+                        .map_err(Error::StorageError)?;
+                    storage
+                        .delete_replay_protection_entry(
+                            batch,
+                            &replay_protection::expiry_key(expiration, hash),
+                        )
+                        .map_err(Error::StorageError)?
+                }
             }
         }
 
@@ -662,16 +723,25 @@ impl WriteLog {
     /// Check if the given tx hash has already been processed. Returns `None` if
     /// the key is not known.
     pub fn has_replay_protection_entry(&self, hash: &Hash) -> Option<bool> {
-        self.replay_protection
-            .get(hash)
-            .map(|action| !matches!(action, ReProtStorageModification::Delete))
+        self.replay_protection.get(hash).map(|action| {
+            !matches!(
+                action,
+                ReProtStorageModification::Delete
+                    | ReProtStorageModification::Prune(_)
+            )
+        })
     }
 
-    /// Write the transaction hash
-    pub(crate) fn write_tx_hash(&mut self, hash: Hash) -> Result<()> {
+    /// Write the transaction hash, with the expiration of the transaction if
+    /// known. An entry without expiration is never pruned.
+    pub(crate) fn write_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: Option<DateTimeUtc>,
+    ) -> Result<()> {
         if self
             .replay_protection
-            .insert(hash, ReProtStorageModification::Write)
+            .insert(hash, ReProtStorageModification::Write(expiration))
             .is_some()
         {
             // Cannot write an hash if other requests have already been
@@ -692,7 +762,7 @@ impl WriteLog {
         {
             None => Ok(()),
             // Allow overwriting a previous finalize request
-            Some(ReProtStorageModification::Finalize) => Ok(()),
+            Some(ReProtStorageModification::Finalize(_)) => Ok(()),
             Some(_) =>
             // Cannot delete an hash that still has to be written to
             // storage or has already been deleted
@@ -708,10 +778,14 @@ impl WriteLog {
     /// Move the transaction hash of the previous block to the list of all
     /// blocks. This functions should be called at the beginning of the block
     /// processing, before any other replay protection operation is done
-    pub fn finalize_tx_hash(&mut self, hash: Hash) -> Result<()> {
+    pub fn finalize_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: Option<DateTimeUtc>,
+    ) -> Result<()> {
         if self
             .replay_protection
-            .insert(hash, ReProtStorageModification::Finalize)
+            .insert(hash, ReProtStorageModification::Finalize(expiration))
             .is_some()
         {
             // Cannot finalize an hash if other requests have already been
@@ -723,12 +797,36 @@ impl WriteLog {
 
         Ok(())
     }
+
+    /// Remove the hash of an expired transaction from the list of all blocks.
+    /// Like [`WriteLog::finalize_tx_hash`], this function should be called at
+    /// the beginning of the block processing
+    pub fn prune_tx_hash(
+        &mut self,
+        hash: Hash,
+        expiration: DateTimeUtc,
+    ) -> Result<()> {
+        if self
+            .replay_protection
+            .insert(hash, ReProtStorageModification::Prune(expiration))
+            .is_some()
+        {
+            // Cannot prune an hash if other requests have already been
+            // committed for the same hash
+            return Err(Error::ReplayProtection(format!(
+                "Requested a prune on hash {hash} over a previous request"
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
     use namada_core::types::hash::Hash;
+    use namada_core::types::time::DurationSecs;
     use namada_core::types::{address, storage};
     use pretty_assertions::assert_eq;
     use proptest::prelude::*;
@@ -957,16 +1055,17 @@ mod tests {
         let mut storage = crate::testing::TestStorage::default();
         let mut write_log = WriteLog::default();
         let mut batch = crate::testing::TestStorage::batch();
+        let expiration = DateTimeUtc::now();
 
         // write some replay protection keys
         write_log
-            .write_tx_hash(Hash::sha256("tx1".as_bytes()))
+            .write_tx_hash(Hash::sha256("tx1".as_bytes()), None)
             .unwrap();
         write_log
-            .write_tx_hash(Hash::sha256("tx2".as_bytes()))
+            .write_tx_hash(Hash::sha256("tx2".as_bytes()), None)
             .unwrap();
         write_log
-            .write_tx_hash(Hash::sha256("tx3".as_bytes()))
+            .write_tx_hash(Hash::sha256("tx3".as_bytes()), None)
             .unwrap();
 
         // commit a block
@@ -985,13 +1084,13 @@ mod tests {
 
         // write some replay protection keys
         write_log
-            .write_tx_hash(Hash::sha256("tx4".as_bytes()))
+            .write_tx_hash(Hash::sha256("tx4".as_bytes()), None)
             .unwrap();
         write_log
-            .write_tx_hash(Hash::sha256("tx5".as_bytes()))
+            .write_tx_hash(Hash::sha256("tx5".as_bytes()), None)
             .unwrap();
         write_log
-            .write_tx_hash(Hash::sha256("tx6".as_bytes()))
+            .write_tx_hash(Hash::sha256("tx6".as_bytes()), None)
             .unwrap();
 
         // delete previous hash
@@ -1002,7 +1101,7 @@ mod tests {
         // finalize previous hashes
         for tx in ["tx2", "tx3"] {
             write_log
-                .finalize_tx_hash(Hash::sha256(tx.as_bytes()))
+                .finalize_tx_hash(Hash::sha256(tx.as_bytes()), Some(expiration))
                 .unwrap();
         }
 
@@ -1030,6 +1129,11 @@ mod tests {
             .delete_tx_hash(Hash::sha256("tx2".as_bytes()))
             .unwrap();
 
+        // finalize a hash without a known expiration
+        write_log
+            .finalize_tx_hash(Hash::sha256("tx4".as_bytes()), None)
+            .unwrap();
+
         // commit a block
         write_log
             .commit_block(&mut storage, &mut batch)
@@ -1041,6 +1145,48 @@ mod tests {
                 .has_replay_protection_entry(&Hash::sha256("tx2".as_bytes()))
                 .expect("read failed")
         );
+
+        // the finalized hashes are indexed by their expiration, if known
+        let expired: Vec<_> = storage
+            .iter_expired_replay_protection(expiration + DurationSecs(1))
+            .map(|(hash, _)| hash)
+            .collect();
+        assert_eq!(expired.len(), 2);
+        assert!(
+            storage
+                .iter_expired_replay_protection(expiration)
+                .next()
+                .is_none()
+        );
+
+        // prune an expired hash
+        write_log
+            .prune_tx_hash(Hash::sha256("tx2".as_bytes()), expiration)
+            .unwrap();
+
+        // commit a block
+        write_log
+            .commit_block(&mut storage, &mut batch)
+            .expect("commit failed");
+
+        assert!(write_log.replay_protection.is_empty());
+        assert!(
+            !storage
+                .has_replay_protection_entry(&Hash::sha256("tx2".as_bytes()))
+                .expect("read failed")
+        );
+        for tx in ["tx3", "tx4"] {
+            assert!(
+                storage
+                    .has_replay_protection_entry(&Hash::sha256(tx.as_bytes()))
+                    .expect("read failed")
+            );
+        }
+        let expired: Vec<_> = storage
+            .iter_expired_replay_protection(expiration + DurationSecs(1))
+            .map(|(hash, _)| hash)
+            .collect();
+        assert_eq!(expired, vec![Hash::sha256("tx3".as_bytes())]);
     }
 
     prop_compose! {
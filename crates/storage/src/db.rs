@@ -227,6 +227,16 @@ pub trait DB: Debug {
         pruned_epoch: Epoch,
     ) -> Result<()>;
 
+    /// Read the height below which the diffs of the account subspaces have
+    /// been pruned, if any
+    fn read_diffs_pruning_height(&self) -> Result<Option<BlockHeight>>;
+
+    /// Prune the diffs of the account subspaces at the heights below the given
+    /// one, after which the values at these heights can no longer be read. The
+    /// height is persisted right away, but the diffs may be deleted in the
+    /// background.
+    fn prune_diffs(&mut self, pruning_height: BlockHeight) -> Result<()>;
+
     /// Read the signed nonce of Bridge Pool
     fn read_bridge_pool_signed_nonce(
         &self,
@@ -234,11 +244,12 @@ pub trait DB: Debug {
         last_height: BlockHeight,
     ) -> Result<Option<ethereum_events::Uint>>;
 
-    /// Write a replay protection entry
+    /// Write a replay protection entry with the given value
     fn write_replay_protection_entry(
         &mut self,
         batch: &mut Self::WriteBatch,
         key: &Key,
+        value: impl AsRef<[u8]>,
     ) -> Result<()>;
 
     /// Delete a replay protection entry
@@ -281,6 +292,13 @@ pub trait DBIter<'iter> {
 
     /// Read replay protection storage from the last block
     fn iter_replay_protection(&'iter self) -> Self::PrefixIter;
+
+    /// Read replay protection storage under the given prefix, ordered by the
+    /// keys. The prefix is stripped from the returned keys.
+    fn iter_replay_protection_prefix(
+        &'iter self,
+        prefix: &Key,
+    ) -> Self::PrefixIter;
 }
 
 /// Atomic batch write.
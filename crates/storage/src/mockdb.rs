@@ -587,6 +587,35 @@ impl DB for MockDB {
         Ok(())
     }
 
+    fn read_diffs_pruning_height(&self) -> Result<Option<BlockHeight>> {
+        match self.0.borrow().get("diffs_pruning_height") {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn prune_diffs(&mut self, pruning_height: BlockHeight) -> Result<()> {
+        let mut db = self.0.borrow_mut();
+        db.insert(
+            "diffs_pruning_height".to_owned(),
+            types::encode(&pruning_height),
+        );
+        let pruning_height = pruning_height.raw();
+        db.retain(|key, _| {
+            let mut segments = key.split(KEY_SEGMENT_SEPARATOR);
+            match (segments.next(), segments.next()) {
+                (Some(height), Some("old" | "new")) => {
+                    height.len() != pruning_height.len()
+                        || height >= pruning_height.as_str()
+                }
+                _ => true,
+            }
+        });
+        Ok(())
+    }
+
     fn read_bridge_pool_signed_nonce(
         &self,
         _height: BlockHeight,
@@ -599,17 +628,17 @@ impl DB for MockDB {
         &mut self,
         _batch: &mut Self::WriteBatch,
         key: &Key,
+        value: impl AsRef<[u8]>,
     ) -> Result<()> {
         let key = Key::parse("replay_protection")
             .map_err(Error::KeyError)?
             .join(key);
 
-        match self.0.borrow_mut().insert(key.to_string(), vec![]) {
-            Some(_) => Err(Error::DBError(format!(
-                "Replay protection key {key} already in storage"
-            ))),
-            None => Ok(()),
-        }
+        self.0
+            .borrow_mut()
+            .insert(key.to_string(), value.as_ref().to_owned());
+
+        Ok(())
     }
 
     fn delete_replay_protection_entry(
@@ -700,8 +729,14 @@ impl<'iter> DBIter<'iter> for MockDB {
     }
 
     fn iter_replay_protection(&'iter self) -> Self::PrefixIter {
-        let stripped_prefix =
-            format!("replay_protection/{}/", replay_protection::last_prefix());
+        self.iter_replay_protection_prefix(&replay_protection::last_prefix())
+    }
+
+    fn iter_replay_protection_prefix(
+        &'iter self,
+        prefix: &Key,
+    ) -> Self::PrefixIter {
+        let stripped_prefix = format!("replay_protection/{prefix}/");
         let prefix = stripped_prefix.clone();
         let iter = self.0.borrow().clone().into_iter();
         MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
@@ -1556,12 +1556,15 @@ fn propose_funding(
     let christel = find_address(test_b, CHRISTEL)?;
 
     let pgf_funding = PgfFunding {
-        continuous: vec![PGFTarget::Ibc(PGFIbcTarget {
-            amount: Amount::from_u64(10),
-            target: bertha.to_string(),
-            port_id: src_port_id.clone(),
-            channel_id: src_channel_id.clone(),
-        })],
+        continuous: vec![
+            PGFTarget::Ibc(PGFIbcTarget {
+                amount: Amount::from_u64(10),
+                target: bertha.to_string(),
+                port_id: src_port_id.clone(),
+                channel_id: src_channel_id.clone(),
+            })
+            .into(),
+        ],
         retro: vec![PGFTarget::Ibc(PGFIbcTarget {
             amount: Amount::from_u64(5),
             target: christel.to_string(),
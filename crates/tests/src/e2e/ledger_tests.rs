@@ -2321,10 +2321,13 @@ fn pgf_governance_proposal() -> Result<()> {
     let christel = find_address(&test, CHRISTEL)?;
 
     let pgf_funding = PgfFunding {
-        continuous: vec![PGFTarget::Internal(PGFInternalTarget {
-            amount: token::Amount::from_u64(10),
-            target: bertha.clone(),
-        })],
+        continuous: vec![
+            PGFTarget::Internal(PGFInternalTarget {
+                amount: token::Amount::from_u64(10),
+                target: bertha.clone(),
+            })
+            .into(),
+        ],
         retro: vec![PGFTarget::Internal(PGFInternalTarget {
             amount: token::Amount::from_u64(5),
             target: christel,
@@ -1,10 +1,18 @@
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use color_eyre::eyre::Result;
 use color_eyre::owo_colors::OwoColorize;
-use namada_apps::node::ledger::shell::testing::client::run;
+use namada_apps::client::tx::sign;
+use namada_apps::node::ledger::shell::testing::client::{run, sdk_context};
+use namada_apps::node::ledger::shell::testing::node::MockNode;
 use namada_apps::node::ledger::shell::testing::utils::{Bin, CapturedOutput};
+use namada_sdk::args::TxBuilder;
 use namada_sdk::masp::fs::FsShieldedUtils;
+use namada_sdk::masp::ShieldedMultiTransferBuilder;
+use namada_sdk::types::masp::{ExtendedSpendingKey, PaymentAddress};
+use namada_sdk::types::token::DenominatedAmount;
+use namada_sdk::Namada;
 use test_log::test;
 
 use super::setup;
@@ -1335,3 +1343,173 @@ fn cross_epoch_tx() -> Result<()> {
 
     Ok(())
 }
+
+/// In this test we verify that a single MASP transaction can combine the notes
+/// of several spending keys to pay several outputs in different tokens, with
+/// the change going to the first spending key or to the given change address.
+#[test]
+fn masp_shielded_multi_transfer() -> Result<()> {
+    // This address doesn't matter for tests. But an argument is required.
+    let validator_one_rpc = "127.0.0.1:26567";
+    // Download the shielded pool parameters before starting node
+    let _ = FsShieldedUtils::new(PathBuf::new());
+    let (mut node, _services) = setup::setup()?;
+    _ = node.next_epoch();
+
+    // 1. Shield 20 BTC and 10 ETH to PA(A) and 20 BTC to PA(B)
+    for (target, token, amount) in [
+        (AA_PAYMENT_ADDRESS, BTC, "20"),
+        (AA_PAYMENT_ADDRESS, ETH, "10"),
+        (AB_PAYMENT_ADDRESS, BTC, "20"),
+    ] {
+        run(
+            &node,
+            Bin::Client,
+            vec![
+                "transfer",
+                "--source",
+                ALBERT,
+                "--target",
+                target,
+                "--token",
+                token,
+                "--amount",
+                amount,
+                "--node",
+                validator_one_rpc,
+            ],
+        )?;
+        node.assert_success();
+    }
+
+    // 2. Attempt to pay more BTC than SK(A) and SK(B) hold together
+    let err = shielded_multi_transfer(
+        &node,
+        &[A_SPENDING_KEY, B_SPENDING_KEY],
+        &[(AC_PAYMENT_ADDRESS, BTC, "50")],
+        None,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Insufficient funds"));
+
+    // 3. Pay 30 BTC and 5 ETH to PA(C) out of SK(A) and SK(B). The notes of
+    // the duplicate key must only be spent once, and the change must go back
+    // to SK(A)
+    shielded_multi_transfer(
+        &node,
+        &[A_SPENDING_KEY, A_SPENDING_KEY, B_SPENDING_KEY],
+        &[
+            (AC_PAYMENT_ADDRESS, BTC, "30"),
+            (AC_PAYMENT_ADDRESS, ETH, "5"),
+        ],
+        None,
+    )?;
+    node.assert_success();
+    for (owner, token, expected) in [
+        (AA_VIEWING_KEY, BTC, "btc: 10"),
+        (AA_VIEWING_KEY, ETH, "eth: 5"),
+        (AB_VIEWING_KEY, BTC, "No shielded btc balance found"),
+        (AC_VIEWING_KEY, BTC, "btc: 30"),
+        (AC_VIEWING_KEY, ETH, "eth: 5"),
+    ] {
+        let captured = CapturedOutput::of(|| {
+            run(
+                &node,
+                Bin::Client,
+                vec![
+                    "balance",
+                    "--owner",
+                    owner,
+                    "--token",
+                    token,
+                    "--node",
+                    validator_one_rpc,
+                ],
+            )
+        });
+        assert!(captured.result.is_ok());
+        assert!(captured.contains(expected));
+    }
+
+    // 4. Pay 1 BTC to PA(B) out of SK(A), sending the change to PA(C)
+    shielded_multi_transfer(
+        &node,
+        &[A_SPENDING_KEY],
+        &[(AB_PAYMENT_ADDRESS, BTC, "1")],
+        Some(AC_PAYMENT_ADDRESS),
+    )?;
+    node.assert_success();
+    for (owner, token, expected) in [
+        (AA_VIEWING_KEY, BTC, "No shielded btc balance found"),
+        (AA_VIEWING_KEY, ETH, "eth: 5"),
+        (AB_VIEWING_KEY, BTC, "btc: 1"),
+        (AC_VIEWING_KEY, BTC, "btc: 39"),
+    ] {
+        let captured = CapturedOutput::of(|| {
+            run(
+                &node,
+                Bin::Client,
+                vec![
+                    "balance",
+                    "--owner",
+                    owner,
+                    "--token",
+                    token,
+                    "--node",
+                    validator_one_rpc,
+                ],
+            )
+        });
+        assert!(captured.result.is_ok());
+        assert!(captured.contains(expected));
+    }
+
+    Ok(())
+}
+
+/// Build, sign and submit a shielded multi-transfer funded by the given
+/// spending keys and paying the given (payment address, token, amount)
+/// outputs. The fees are paid by Christel.
+fn shielded_multi_transfer(
+    node: &MockNode,
+    spending_keys: &[&str],
+    outputs: &[(&str, &str, &str)],
+    change_address: Option<&str>,
+) -> std::result::Result<(), namada_sdk::error::Error> {
+    let namada = sdk_context(node).expect("unable to load the client context");
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut shielded = ShieldedMultiTransferBuilder::new();
+        for key in spending_keys {
+            shielded = shielded
+                .spending_key(ExtendedSpendingKey::from_str(key).unwrap());
+        }
+        for (target, token, amount) in outputs {
+            let token = namada
+                .wallet()
+                .await
+                .find_address(token)
+                .unwrap()
+                .into_owned();
+            shielded = shielded.output(
+                PaymentAddress::from_str(target).unwrap(),
+                token,
+                DenominatedAmount::from_str(amount).unwrap(),
+            );
+        }
+        if let Some(change_address) = change_address {
+            shielded = shielded.change_address(
+                PaymentAddress::from_str(change_address).unwrap(),
+            );
+        }
+        let gas_payer =
+            namada.wallet().await.find_public_key(CHRISTEL_KEY).unwrap();
+        let mut args = namada
+            .new_shielded_multi_transfer(shielded)
+            .wrapper_fee_payer(gas_payer);
+        let (mut tx, signing_data, _epoch) = args.build(&namada).await?;
+        sign(&namada, &mut tx, &args.tx, signing_data).await?;
+        namada.submit(tx, &args.tx).await?;
+        Ok(())
+    })
+}
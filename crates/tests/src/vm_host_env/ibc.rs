@@ -46,6 +46,7 @@ use namada::ibc::core::connection::types::{
 pub use namada::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, ConnectionId, PortId, Sequence,
 };
+use namada::ibc::ica;
 use namada::ibc::primitives::proto::{Any, Protobuf};
 use namada::ibc::primitives::Timestamp;
 use namada::ledger::gas::VpGasMeter;
@@ -680,6 +681,16 @@ pub fn msg_packet_ack(packet: Packet) -> MsgAcknowledgement {
     }
 }
 
+pub fn msg_packet_ack_with_error(packet: Packet) -> MsgAcknowledgement {
+    MsgAcknowledgement {
+        packet,
+        acknowledgement: transfer_ack_with_error().into(),
+        proof_acked_on_b: dummy_proof(),
+        proof_height_on_b: dummy_proof_height(),
+        signer: "test".to_string().into(),
+    }
+}
+
 pub fn received_packet(
     port_id: PortId,
     channel_id: ChannelId,
@@ -713,6 +724,63 @@ pub fn received_packet(
     }
 }
 
+pub fn set_packet_memo(packet: &mut Packet, memo: String) {
+    let mut data: PacketData =
+        serde_json::from_slice(&packet.data).expect("invalid packet data");
+    data.memo = memo.into();
+    packet.data = serde_json::to_vec(&data).unwrap();
+}
+
+/// Make the packet sent from this chain in the current block
+pub fn sent_packet(
+    port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    data: &PacketData,
+    timeout: Duration,
+) -> Packet {
+    let counterparty = dummy_channel_counterparty();
+    let timestamp = (host_timestamp() + timeout).unwrap();
+    Packet {
+        seq_on_a: sequence,
+        port_id_on_a: port_id,
+        chan_id_on_a: channel_id,
+        port_id_on_b: counterparty.port_id().clone(),
+        chan_id_on_b: counterparty.channel_id().unwrap().clone(),
+        data: serde_json::to_vec(data).unwrap(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: timestamp,
+    }
+}
+
+pub fn host_timestamp() -> Timestamp {
+    let time = tx_host_env::with(|env| {
+        env.wl_storage
+            .storage
+            .get_block_header(None)
+            .unwrap()
+            .0
+            .unwrap()
+            .time
+    });
+    TmTime::try_from(time).unwrap().into()
+}
+
+/// Make the consensus state of the counterparty chain with the given
+/// timestamp
+pub fn consensus_state_with_timestamp(
+    client_id: &ClientId,
+    timestamp: Timestamp,
+) -> (Key, Vec<u8>) {
+    let header = MockHeader {
+        height: Height::new(0, 1).unwrap(),
+        timestamp,
+    };
+    let key = consensus_state_key(client_id, header.height);
+    let bytes = Protobuf::<Any>::encode_vec(MockConsensusState::new(header));
+    (key, bytes)
+}
+
 pub fn msg_timeout(packet: Packet, next_sequence_recv: Sequence) -> MsgTimeout {
     MsgTimeout {
         packet,
@@ -779,3 +847,55 @@ pub fn transfer_ack_with_error() -> AcknowledgementStatus {
         .expect("Empty message"),
     )
 }
+
+pub fn ack_success() -> AcknowledgementStatus {
+    AcknowledgementStatus::success(ack_success_b64())
+}
+
+pub fn ica_controller_port_id() -> PortId {
+    let owner = address::testing::established_address_1();
+    ica::controller_port_id(&owner).expect("invalid port ID")
+}
+
+pub fn msg_ica_channel_open_try(conn_id: ConnectionId) -> MsgChannelOpenTry {
+    let counterparty = dummy_channel_counterparty();
+    let counterparty_conn_id = dummy_connection_counterparty()
+        .connection_id()
+        .cloned()
+        .unwrap();
+    let metadata = ica::Metadata::new(&counterparty_conn_id, &conn_id);
+    #[allow(deprecated)]
+    MsgChannelOpenTry {
+        port_id_on_b: ica::host_port_id(),
+        connection_hops_on_b: vec![conn_id],
+        port_id_on_a: ica_controller_port_id(),
+        chan_id_on_a: counterparty.channel_id().cloned().unwrap(),
+        version_supported_on_a: metadata.to_version(),
+        proof_chan_end_on_a: dummy_proof(),
+        proof_height_on_a: dummy_proof_height(),
+        ordering: Order::Ordered,
+        signer: "test".to_string().into(),
+        version_proposal: ChanVersion::default(),
+    }
+}
+
+pub fn ica_received_packet(
+    channel_id: ChannelId,
+    sequence: Sequence,
+    txs: &[ica::HostTx],
+) -> Packet {
+    let counterparty = dummy_channel_counterparty();
+    let timestamp = (Timestamp::now() + Duration::from_secs(100)).unwrap();
+    let data =
+        ica::InterchainAccountPacketData::with_host_txs(txs, String::new());
+    Packet {
+        seq_on_a: sequence,
+        port_id_on_a: ica_controller_port_id(),
+        chan_id_on_a: counterparty.channel_id().unwrap().clone(),
+        port_id_on_b: ica::host_port_id(),
+        chan_id_on_b: channel_id,
+        data: serde_json::to_vec(&data).unwrap(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: timestamp,
+    }
+}
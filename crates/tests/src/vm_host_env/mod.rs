@@ -20,24 +20,34 @@ mod tests {
 
     use std::collections::BTreeSet;
     use std::panic;
+    use std::time::Duration;
 
     use borsh_ext::BorshSerializeExt;
     use itertools::Itertools;
     use namada::account::pks_handle;
+    use namada::governance::storage::keys::get_proposal_execution_key;
+    use namada::ibc::apps::transfer::types::packet::PacketData;
+    use namada::ibc::apps::transfer::types::PrefixedCoin;
     use namada::ibc::context::transfer_mod::testing::DummyTransferModule;
+    use namada::ibc::forward::{
+        self, ForwardMetadata, InFlightPacket, IBC_FORWARD,
+    };
     use namada::ibc::primitives::Msg;
-    use namada::ibc::Error as IbcActionError;
+    use namada::ibc::rate_limit::{self, RateLimit};
+    use namada::ibc::{ica, Error as IbcActionError};
+    use namada::ledger::gas::{Gas, GasMetering};
     use namada::ledger::ibc::storage as ibc_storage;
     use namada::ledger::native_vp::ibc::{
         get_dummy_header as tm_dummy_header, Error as IbcError,
     };
+    use namada::ledger::parameters::storage::get_max_block_gas_key;
     use namada::ledger::tx_env::TxEnv;
     use namada::token::{self, Amount};
     use namada::tx::Tx;
     use namada::types::hash::Hash;
     use namada::types::key::*;
     use namada::types::storage::{self, BlockHash, BlockHeight, Key, KeySeg};
-    use namada::types::time::DateTimeUtc;
+    use namada::types::time::{DateTimeUtc, DurationSecs};
     use namada::types::{address, key};
     use namada_test_utils::TestWasms;
     use namada_tx_prelude::address::InternalAddress;
@@ -207,6 +217,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tx_emit_multi_transfer_events() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let source = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let legs: Vec<_> = (1..=3)
+            .map(|amount| token::TransferLeg {
+                source: source.clone(),
+                target: target.clone(),
+                token: address::nam(),
+                amount: Amount::native_whole(amount).native_denominated(),
+            })
+            .collect();
+        let events: Vec<_> = legs
+            .into_iter()
+            .enumerate()
+            .map(|(index, leg)| token::TransferLegEvent {
+                index: index as u64,
+                leg,
+            })
+            .collect();
+        for event in &events {
+            tx::ctx().emit_transfer_leg_event(event).unwrap();
+        }
+        assert_eq!(
+            tx_host_env::with(|env| {
+                env.wl_storage.write_log.get_transfer_leg_events().to_vec()
+            }),
+            events,
+            "Each leg of a multi-transfer should emit an event"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_tx_init_account_with_invalid_vp() {
@@ -489,7 +534,7 @@ mod tests {
                         &None,
                         1,
                         None,
-                        || Ok(())
+                        |_| Ok(())
                     )
                     .is_ok()
             );
@@ -505,7 +550,7 @@ mod tests {
                         &None,
                         1,
                         None,
-                        || Ok(())
+                        |_| Ok(())
                     )
                     .is_err()
             );
@@ -1844,4 +1889,759 @@ mod tests {
         let result = ibc::validate_multitoken_vp_from_tx(&env, &tx, &escrow);
         assert!(result.expect("token validation failed unexpectedly"));
     }
+
+    #[test]
+    fn test_ibc_rate_limit_outflow() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // Set the initial state before starting transactions
+        let (token, sender) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+        // The sender has enough balance to send the token twice
+        let balance_key = token::storage_key::balance_key(&token, &sender);
+        let init_bal = Amount::from_uint(200, ibc::ANY_DENOMINATION).unwrap();
+        writes.insert(balance_key, init_bal.serialize_to_vec());
+        // The outflow quota is the same as the amount to be sent
+        let limit = RateLimit {
+            inflow: None,
+            outflow: Some(
+                Amount::from_uint(100, ibc::ANY_DENOMINATION).unwrap(),
+            ),
+            window: 2,
+        };
+        writes.insert(
+            ibc_storage::rate_limit_key(&channel_id, &token),
+            limit.serialize_to_vec(),
+        );
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Start a transaction to send a packet
+        let msg = ibc::msg_transfer(
+            port_id.clone(),
+            channel_id.clone(),
+            token.to_string(),
+            &sender,
+        );
+        let mut tx_data = vec![];
+        msg.clone()
+            .to_any()
+            .encode(&mut tx_data)
+            .expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // send the token and a packet with the data
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("sending a token failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // Check the usage
+        tx_host_env::set(env);
+        let usage = tx_host_env::with(|env| {
+            rate_limit::rate_limit_usage(&env.wl_storage, &channel_id, &token)
+                .expect("read error")
+                .expect("the rate limit should exist")
+        });
+        assert_eq!(usage.outflow, limit.outflow.unwrap());
+        assert_eq!(usage.inflow, Amount::zero());
+
+        // Commit
+        let mut env = tx_host_env::take();
+        env.commit_tx_and_block();
+        // for the next block
+        env.wl_storage
+            .storage
+            .begin_block(BlockHash::default(), BlockHeight(2))
+            .unwrap();
+        env.wl_storage
+            .storage
+            .set_header(tm_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+
+        // Sending the token again exceeds the quota in the same window
+        let result = tx_host_env::ibc::ibc_actions(tx::ctx()).execute(&tx_data);
+        assert!(matches!(
+            result.expect_err("sending a token succeeded unexpectedly"),
+            IbcActionError::RateLimit(_),
+        ));
+    }
+
+    #[test]
+    fn test_ibc_rate_limit_inflow() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+        // The inflow quota is less than the amount to be received
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let ibc_token = ibc::ibc_token(&denom);
+        let limit = RateLimit {
+            inflow: Some(Amount::from_u64(50)),
+            outflow: None,
+            window: 1,
+        };
+        writes.insert(
+            ibc_storage::rate_limit_key(&channel_id, &ibc_token),
+            limit.serialize_to_vec(),
+        );
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        // packet
+        let sequence = ibc::Sequence::from(1);
+        let packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            sequence,
+            token.to_string(),
+            &receiver,
+        );
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // Receive the packet, but the token over the quota is rejected with
+        // the error acknowledgement
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving the packet failed");
+
+        // Check if the transaction is valid
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // Check if only the ack and the receipt are added, i.e. no token is
+        // minted and no flow is recorded
+        tx_host_env::set(env);
+        let ack_key = ibc_storage::ack_key(&port_id, &channel_id, sequence);
+        let receipt_key =
+            ibc_storage::receipt_key(&port_id, &channel_id, sequence);
+        let changed_keys = tx_host_env::with(|env| {
+            env.wl_storage
+                .write_log
+                .verifiers_and_changed_keys(&BTreeSet::new())
+                .1
+        });
+        let expected_changed_keys = BTreeSet::from([ack_key, receipt_key]);
+        assert_eq!(changed_keys, expected_changed_keys);
+        let key = ibc::balance_key_with_ibc_prefix(denom, &receiver);
+        let balance: Option<Amount> = tx_host_env::with(|env| {
+            env.wl_storage.read(&key).expect("read error")
+        });
+        assert_eq!(balance, None);
+    }
+
+    #[test]
+    fn test_ibc_rate_limit_update() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // Set the initial state before starting transactions
+        let (token, _) = ibc::init_storage();
+        let channel_id = ibc::ChannelId::new(0);
+        let limit = RateLimit {
+            inflow: Some(Amount::from_u64(100)),
+            outflow: Some(Amount::from_u64(100)),
+            window: 1,
+        };
+
+        // Update the rate limit without a governance proposal
+        rate_limit::write_rate_limit(
+            tx::ctx(),
+            &channel_id,
+            &token,
+            limit.clone(),
+        )
+        .expect("write failed");
+        let proposal_id = 0_u64;
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(proposal_id.serialize_to_vec());
+
+        // The IBC VP should reject it
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(matches!(
+            result.expect_err("validation succeeded unexpectedly"),
+            IbcError::RateLimit(_),
+        ));
+
+        // Update the rate limit with an accepted proposal
+        tx_host_env::set(env);
+        tx_host_env::with(|env| {
+            let key = get_proposal_execution_key(proposal_id);
+            env.wl_storage
+                .storage
+                .write(&key, Vec::<u8>::new())
+                .expect("write error");
+        });
+
+        // The IBC VP should accept it
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+    }
+
+    #[test]
+    fn test_ibc_forward_and_retry_refund() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let (client_id, _, refund_packet, in_flight) = forward_and_fail(None);
+        let port_id = ibc::PortId::transfer();
+        let channel_id = ibc::ChannelId::new(0);
+
+        // Time out the refund packet
+        timeout_refund_packet(&client_id, refund_packet);
+
+        // Check if the refund has been retried
+        tx_host_env::with(|env| {
+            let refunded = forward::read_in_flight(
+                &env.wl_storage,
+                &port_id,
+                &channel_id,
+                ibc::Sequence::from(1),
+            )
+            .expect("read error");
+            assert_eq!(refunded, None);
+            let retried = forward::read_in_flight(
+                &env.wl_storage,
+                &port_id,
+                &channel_id,
+                ibc::Sequence::from(2),
+            )
+            .expect("read error");
+            assert_eq!(
+                retried,
+                Some(InFlightPacket {
+                    refund_attempts: 2,
+                    ..in_flight
+                })
+            );
+            let commitment_key = ibc_storage::commitment_key(
+                &port_id,
+                &channel_id,
+                ibc::Sequence::from(2),
+            );
+            assert!(env.wl_storage.has_key(&commitment_key).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_ibc_forward_refund_to_fallback() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let fallback = address::testing::gen_established_address();
+        let (client_id, denom, refund_packet, _) =
+            forward_and_fail(Some(fallback.clone()));
+        let port_id = ibc::PortId::transfer();
+        let channel_id = ibc::ChannelId::new(0);
+
+        // Time out the refund packet
+        timeout_refund_packet(&client_id, refund_packet);
+
+        // Check if the token has been sent to the fallback address without
+        // retrying the refund
+        tx_host_env::with(|env| {
+            let retried = forward::read_in_flight(
+                &env.wl_storage,
+                &port_id,
+                &channel_id,
+                ibc::Sequence::from(2),
+            )
+            .expect("read error");
+            assert_eq!(retried, None);
+        });
+        let key = ibc::balance_key_with_ibc_prefix(denom, &fallback);
+        let balance: Option<Amount> = tx_host_env::with(|env| {
+            env.wl_storage.read(&key).expect("read error")
+        });
+        assert_eq!(balance, Some(Amount::from_u64(100)));
+    }
+
+    #[test]
+    fn test_ibc_forward_with_unexpected_balance_change() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let (token, _, port_id, channel_id, next_channel_id) =
+            prepare_forward_channels();
+
+        // Receive the token to be forwarded
+        let metadata = ForwardMetadata {
+            receiver: "receiver-on-next-chain".to_string(),
+            port: port_id.to_string(),
+            channel: next_channel_id.to_string(),
+            timeout: None,
+            fallback: None,
+            next: None,
+        };
+        let mut packet = ibc::received_packet(
+            port_id,
+            channel_id,
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &IBC_FORWARD,
+        );
+        ibc::set_packet_memo(&mut packet, metadata.to_memo());
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("executing the IBC message failed");
+
+        // Credit the forward address besides the forwarding
+        let key = token::storage_key::balance_key(&token, &IBC_FORWARD);
+        tx::ctx()
+            .write(&key, Amount::from_u64(100))
+            .expect("write failed");
+
+        // The IBC VP should reject it
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(matches!(
+            result.expect_err("validation succeeded unexpectedly"),
+            IbcError::StateChange(_),
+        ));
+    }
+
+    #[test]
+    fn test_ica_host_register_and_receive_txs() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // Set the initial state before starting transactions
+        ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        // Only the allowed transaction can be executed by the interchain
+        // account
+        let allowed_code_hash = Hash::sha256(b"tx_allowed");
+        writes.insert(
+            ibc_storage::ica_allowlist_key(),
+            vec![allowed_code_hash].serialize_to_vec(),
+        );
+        writes
+            .insert(get_max_block_gas_key(), 20_000_000_u64.serialize_to_vec());
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        // Register an interchain account with ChannelOpenTry
+        let msg = ibc::msg_ica_channel_open_try(conn_id.clone());
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+        let controller_port_id = ibc::ica_controller_port_id();
+        let account = ibc_storage::ica_account(&conn_id, &controller_port_id);
+        let key = ibc_storage::ica_account_key(&controller_port_id, &conn_id);
+        let registered: Option<String> = tx_host_env::with(|env| {
+            env.wl_storage.read(&key).expect("read error")
+        });
+        assert_eq!(registered, Some(account.to_string()));
+        commit_and_begin_block(3);
+
+        // Open the channel with ChannelOpenConfirm
+        let port_id = ica::host_port_id();
+        let channel_id = ibc::ChannelId::new(0);
+        let msg =
+            ibc::msg_channel_open_confirm(port_id.clone(), channel_id.clone());
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+        commit_and_begin_block(4);
+
+        // The transaction not in the allowlist is rejected with the error
+        // acknowledgement
+        let success_ack =
+            Hash::sha256(Vec::<u8>::from(ibc::ack_success())).to_vec();
+        let not_allowed_tx = ica::HostTx {
+            code_hash: Hash::sha256(b"tx_not_allowed"),
+            data: vec![],
+            gas_limit: 1_000,
+        };
+        let sequence = ibc::Sequence::from(1);
+        let packet = ibc::ica_received_packet(
+            channel_id.clone(),
+            sequence,
+            &[not_allowed_tx],
+        );
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+        let ack_key = ibc_storage::ack_key(&port_id, &channel_id, sequence);
+        let pending_key =
+            ibc_storage::ica_pending_tx_key(&channel_id, sequence);
+        tx_host_env::with(|env| {
+            let ack = env.wl_storage.read_bytes(&ack_key).expect("read error");
+            assert_ne!(ack, Some(success_ack.clone()));
+            assert!(!env.wl_storage.has_key(&pending_key).expect("read error"));
+        });
+        commit_and_begin_block(5);
+
+        // The allowed transaction is queued and its gas limit is charged
+        let allowed_tx = ica::HostTx {
+            code_hash: allowed_code_hash,
+            data: vec![1, 2, 3],
+            gas_limit: 1_000,
+        };
+        let sequence = ibc::Sequence::from(2);
+        let packet = ibc::ica_received_packet(
+            channel_id.clone(),
+            sequence,
+            &[allowed_tx.clone()],
+        );
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let gas_pre =
+            tx_host_env::with(|env| env.gas_meter.get_tx_consumed_gas());
+        execute_and_validate_ibc_tx(tx_data);
+        let gas_post =
+            tx_host_env::with(|env| env.gas_meter.get_tx_consumed_gas());
+        assert!(
+            u64::from(gas_post) - u64::from(gas_pre)
+                >= u64::from(Gas::from_whole_units(allowed_tx.gas_limit))
+        );
+        let ack_key = ibc_storage::ack_key(&port_id, &channel_id, sequence);
+        let pending_key =
+            ibc_storage::ica_pending_tx_key(&channel_id, sequence);
+        tx_host_env::with(|env| {
+            let ack = env.wl_storage.read_bytes(&ack_key).expect("read error");
+            assert_eq!(ack, Some(success_ack.clone()));
+            let pending: Option<ica::PendingHostTxs> =
+                env.wl_storage.read(&pending_key).expect("read error");
+            assert_eq!(
+                pending,
+                Some(ica::PendingHostTxs {
+                    channel_id: channel_id.to_string(),
+                    sequence: 2,
+                    account: account.clone(),
+                    txs: vec![allowed_tx.clone()],
+                })
+            );
+        });
+        commit_and_begin_block(6);
+
+        // The transactions over the limit per block are rejected. The queued
+        // transaction hasn't been executed without finalizing the block.
+        let sequence = ibc::Sequence::from(3);
+        let packet = ibc::ica_received_packet(
+            channel_id.clone(),
+            sequence,
+            &vec![allowed_tx; ica::MAX_HOST_TXS_PER_BLOCK],
+        );
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+        let ack_key = ibc_storage::ack_key(&port_id, &channel_id, sequence);
+        let pending_key =
+            ibc_storage::ica_pending_tx_key(&channel_id, sequence);
+        tx_host_env::with(|env| {
+            let ack = env.wl_storage.read_bytes(&ack_key).expect("read error");
+            assert_ne!(ack, Some(success_ack));
+            assert!(!env.wl_storage.has_key(&pending_key).expect("read error"));
+        });
+    }
+
+    /// Receive a token to forward it from the channel 0 to the channel 1, fail
+    /// the forwarded packet and refund the token to the original sender.
+    /// Returns the client ID, the denom of the received token, the refund
+    /// packet and the in-flight packet of the refund.
+    fn forward_and_fail(
+        fallback: Option<Address>,
+    ) -> (ibc::ClientId, String, ibc::Packet, InFlightPacket) {
+        let (token, client_id, port_id, channel_id, next_channel_id) =
+            prepare_forward_channels();
+
+        // Receive the token to be forwarded
+        let timeout = Duration::from_secs(60);
+        let metadata = ForwardMetadata {
+            receiver: "receiver-on-next-chain".to_string(),
+            port: port_id.to_string(),
+            channel: next_channel_id.to_string(),
+            timeout: Some(timeout.as_nanos() as u64),
+            fallback: fallback.as_ref().map(|addr| addr.to_string()),
+            next: None,
+        };
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &IBC_FORWARD,
+        );
+        ibc::set_packet_memo(&mut packet, metadata.to_memo());
+        let data: PacketData = serde_json::from_slice(&packet.data).unwrap();
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+
+        // Check if the token has been forwarded
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let success_ack =
+            Hash::sha256(Vec::<u8>::from(ibc::ack_success())).to_vec();
+        let expected_in_flight = InFlightPacket {
+            refund_port_id: port_id.to_string(),
+            refund_channel_id: channel_id.to_string(),
+            original_sender: data.sender.to_string(),
+            original_sequence: 1,
+            timeout: timeout.as_nanos() as u64,
+            fallback,
+            refund_attempts: 0,
+        };
+        tx_host_env::with(|env| {
+            let ack = env.wl_storage.read_bytes(&ack_key).expect("read error");
+            assert_eq!(ack, Some(success_ack));
+            let in_flight = forward::read_in_flight(
+                &env.wl_storage,
+                &port_id,
+                &next_channel_id,
+                ibc::Sequence::from(1),
+            )
+            .expect("read error");
+            assert_eq!(in_flight, Some(expected_in_flight.clone()));
+        });
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let escrow = Address::Internal(InternalAddress::Ibc);
+        let escrow_key =
+            ibc::balance_key_with_ibc_prefix(denom.clone(), &escrow);
+        let escrowed: Option<Amount> = tx_host_env::with(|env| {
+            env.wl_storage.read(&escrow_key).expect("read error")
+        });
+        assert_eq!(escrowed, Some(Amount::from_u64(100)));
+        let forwarded_data = PacketData {
+            token: PrefixedCoin {
+                denom: denom.parse().expect("invalid denom"),
+                amount: data.token.amount,
+            },
+            sender: IBC_FORWARD.to_string().into(),
+            receiver: metadata.receiver.clone().into(),
+            memo: String::new().into(),
+        };
+        let forwarded_packet = ibc::sent_packet(
+            port_id.clone(),
+            next_channel_id.clone(),
+            ibc::Sequence::from(1),
+            &forwarded_data,
+            timeout,
+        );
+        commit_and_begin_block(3);
+
+        // The forwarded packet fails on the next chain
+        let msg = ibc::msg_packet_ack_with_error(forwarded_packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+
+        // Check if the refund packet has been sent and recorded
+        let expected_in_flight = InFlightPacket {
+            refund_attempts: 1,
+            ..expected_in_flight
+        };
+        tx_host_env::with(|env| {
+            let in_flight = forward::read_in_flight(
+                &env.wl_storage,
+                &port_id,
+                &next_channel_id,
+                ibc::Sequence::from(1),
+            )
+            .expect("read error");
+            assert_eq!(in_flight, None);
+            let in_flight = forward::read_in_flight(
+                &env.wl_storage,
+                &port_id,
+                &channel_id,
+                ibc::Sequence::from(1),
+            )
+            .expect("read error");
+            assert_eq!(in_flight, Some(expected_in_flight.clone()));
+        });
+        let escrowed: Option<Amount> = tx_host_env::with(|env| {
+            env.wl_storage.read(&escrow_key).expect("read error")
+        });
+        assert_eq!(escrowed, Some(Amount::zero()));
+        let refund_data = PacketData {
+            receiver: data.sender.clone(),
+            ..forwarded_data
+        };
+        let refund_packet = ibc::sent_packet(
+            port_id,
+            channel_id,
+            ibc::Sequence::from(1),
+            &refund_data,
+            timeout,
+        );
+        commit_and_begin_block(4);
+
+        (client_id, denom, refund_packet, expected_in_flight)
+    }
+
+    /// Set the initial state to forward a token from the channel 0 to the
+    /// channel 1. Returns the token, the client ID, the port ID and the IDs
+    /// of both channels.
+    fn prepare_forward_channels() -> (
+        Address,
+        ibc::ClientId,
+        ibc::PortId,
+        ibc::ChannelId,
+        ibc::ChannelId,
+    ) {
+        let (token, _) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        // The channel to forward the token
+        let next_channel_id = ibc::ChannelId::new(1);
+        let channel =
+            channel_writes[&ibc::channel_key(&port_id, &channel_id)].clone();
+        writes.insert(ibc::channel_key(&port_id, &next_channel_id), channel);
+        writes.extend(channel_writes);
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+        (token, client_id, port_id, channel_id, next_channel_id)
+    }
+
+    /// Time out the refund packet after the counterparty chain has passed its
+    /// timeout
+    fn timeout_refund_packet(client_id: &ibc::ClientId, packet: ibc::Packet) {
+        let timestamp =
+            (ibc::host_timestamp() + Duration::from_secs(3600)).unwrap();
+        let (key, val) =
+            ibc::consensus_state_with_timestamp(client_id, timestamp);
+        tx_host_env::with(|env| {
+            env.wl_storage
+                .storage
+                .write(&key, &val)
+                .expect("write error");
+            let mut header = tm_dummy_header();
+            header.time = header.time + DurationSecs(7200);
+            env.wl_storage.storage.set_header(header).unwrap();
+        });
+
+        let msg = ibc::msg_timeout(packet, ibc::Sequence::from(1));
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        execute_and_validate_ibc_tx(tx_data);
+    }
+
+    /// Execute the IBC message and validate the transaction with the IBC VP
+    fn execute_and_validate_ibc_tx(tx_data: Vec<u8>) {
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("executing the IBC message failed");
+
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        tx_host_env::set(env);
+    }
+
+    /// Commit the current block and begin the next block
+    fn commit_and_begin_block(height: u64) {
+        tx_host_env::with(|env| {
+            env.commit_tx_and_block();
+            env.wl_storage
+                .storage
+                .begin_block(BlockHash::default(), BlockHeight(height))
+                .unwrap();
+            env.wl_storage
+                .storage
+                .set_header(tm_dummy_header())
+                .unwrap();
+        });
+    }
 }
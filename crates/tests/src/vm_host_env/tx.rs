@@ -233,6 +233,7 @@ impl TestTxEnv {
             &self.tx,
             &mut self.vp_wasm_cache,
             &mut self.tx_wasm_cache,
+            &mut None,
         )
         .and(Ok(()))
     }
@@ -356,6 +357,7 @@ mod native_tx_host_env {
                                 tx,
                             }: &mut TestTxEnv| {
 
+                            let mut trace = None;
                             let tx_env = vm::host_env::testing::tx_env(
                                 &wl_storage.storage,
                                 &mut wl_storage.write_log,
@@ -366,6 +368,7 @@ mod native_tx_host_env {
                                 tx,
                                 tx_index,
                                 result_buffer,
+                                &mut trace,
                                 vp_wasm_cache,
                                 tx_wasm_cache,
                             );
@@ -398,6 +401,7 @@ mod native_tx_host_env {
                                 tx,
                             }: &mut TestTxEnv| {
 
+                            let mut trace = None;
                             let tx_env = vm::host_env::testing::tx_env(
                                 &wl_storage.storage,
                                 &mut wl_storage.write_log,
@@ -408,6 +412,7 @@ mod native_tx_host_env {
                                 tx,
                                 tx_index,
                                 result_buffer,
+                                &mut trace,
                                 vp_wasm_cache,
                                 tx_wasm_cache,
                             );
@@ -440,6 +445,7 @@ mod native_tx_host_env {
                                 tx,
                             }: &mut TestTxEnv| {
 
+                            let mut trace = None;
                             let tx_env = vm::host_env::testing::tx_env(
                                 &wl_storage.storage,
                                 &mut wl_storage.write_log,
@@ -450,6 +456,7 @@ mod native_tx_host_env {
                                 tx,
                                 tx_index,
                                 result_buffer,
+                                &mut trace,
                                 vp_wasm_cache,
                                 tx_wasm_cache,
                             );
@@ -499,6 +506,7 @@ mod native_tx_host_env {
         result_ptr: u64
     ));
     native_host_fn!(tx_emit_ibc_event(event_ptr: u64, event_len: u64));
+    native_host_fn!(tx_emit_transfer_leg_event(event_ptr: u64, event_len: u64));
     native_host_fn!(tx_get_ibc_events(event_type_ptr: u64, event_type_len: u64) -> i64);
     native_host_fn!(tx_get_chain_id(result_ptr: u64));
     native_host_fn!(tx_get_block_height() -> u64);
@@ -272,6 +272,7 @@ mod native_vp_host_env {
                                 vp_cache_dir: _,
                             }: &mut TestVpEnv| {
 
+                            let mut trace = None;
                             let env = vm::host_env::testing::vp_env(
                                 addr,
                                 &wl_storage.storage,
@@ -285,6 +286,7 @@ mod native_vp_host_env {
                                 result_buffer,
                                 keys_changed,
                                 eval_runner,
+                                &mut trace,
                                 vp_wasm_cache,
                             );
 
@@ -317,6 +319,7 @@ mod native_vp_host_env {
                                 vp_cache_dir: _,
                             }: &mut TestVpEnv| {
 
+                            let mut trace = None;
                             let env = vm::host_env::testing::vp_env(
                                 addr,
                                 &wl_storage.storage,
@@ -330,6 +333,7 @@ mod native_vp_host_env {
                                 result_buffer,
                                 keys_changed,
                                 eval_runner,
+                                &mut trace,
                                 vp_wasm_cache,
                             );
 
@@ -10,6 +10,8 @@ pub mod pgf;
 pub mod pos;
 /// transaction protocols made by validators
 pub mod protocol;
+/// execution traces of txs and vps
+pub mod trace;
 /// wrapper txs with encrypted payloads
 pub mod wrapper;
 
@@ -26,7 +28,8 @@ use namada_core::types::ethereum_structs::EthBridgeEvent;
 use namada_core::types::hash::Hash;
 use namada_core::types::ibc::IbcEvent;
 use namada_core::types::storage;
-use namada_gas::{Gas, VpsGas};
+use namada_core::types::token::TransferLegEvent;
+use namada_gas::{Gas, GasProfile, VpsGas};
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 use serde::{Deserialize, Serialize};
@@ -34,6 +37,7 @@ use sha2::{Digest, Sha256};
 pub use wrapper::*;
 
 use crate::data::protocol::ProtocolTx;
+use crate::data::trace::ExecutionTrace;
 
 /// The different result codes that the ledger may send back to a client
 /// indicating the status of their submitted tx.
@@ -84,6 +88,9 @@ pub enum ResultCode {
     TooLarge = 14,
     /// Decrypted tx is expired
     ExpiredDecryptedTx = 15,
+    /// Wrapper tx is missing an expiration or its expiration is beyond the
+    /// tx expiration window
+    InvalidExpiration = 16,
     // =========================================================================
     // WARN: These codes shouldn't be changed between version!
 }
@@ -100,7 +107,7 @@ impl ResultCode {
             InvalidTx | InvalidSig | InvalidOrder | ExtraTxs
             | Undecryptable | AllocationError | ReplayTx | InvalidChainId
             | ExpiredTx | TxGasLimit | FeeError | InvalidVoteExtension
-            | TooLarge => false,
+            | TooLarge | InvalidExpiration => false,
         }
     }
 
@@ -185,6 +192,16 @@ pub struct TxResult {
     pub ibc_events: BTreeSet<IbcEvent>,
     /// Ethereum bridge events emitted by the transaction
     pub eth_bridge_events: BTreeSet<EthBridgeEvent>,
+    /// Events of the applied legs of a multi-transfer
+    pub transfer_leg_events: Vec<TransferLegEvent>,
+    /// Execution trace of the transaction and its VPs, only recorded on
+    /// request when dry running the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<ExecutionTrace>,
+    /// Breakdown of the gas used by the transaction, only recorded when dry
+    /// running the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfile>,
 }
 
 impl TxResult {
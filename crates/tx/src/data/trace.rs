@@ -0,0 +1,306 @@
+//! Execution traces of transactions and validity predicates, recorded on
+//! request when dry running a transaction.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use data_encoding::HEXUPPER;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::hash::Hash;
+use namada_core::types::storage;
+use namada_gas::Gas;
+use serde::{Deserialize, Serialize};
+
+/// A host function call made from a transaction or a validity predicate
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub enum HostCall {
+    /// Check the presence of a key
+    HasKey {
+        /// The checked key
+        key: storage::Key,
+        /// Whether the key is present
+        present: bool,
+    },
+    /// Read the value of a key
+    Read {
+        /// The read key
+        key: storage::Key,
+        /// The value, if any
+        value: Option<Vec<u8>>,
+    },
+    /// Write a value to a key
+    Write {
+        /// The written key
+        key: storage::Key,
+        /// The new value
+        value: Vec<u8>,
+    },
+    /// Write a value to a key of the temporary storage
+    WriteTemp {
+        /// The written key
+        key: storage::Key,
+        /// The new value
+        value: Vec<u8>,
+    },
+    /// Delete a key
+    Delete {
+        /// The deleted key
+        key: storage::Key,
+    },
+    /// Iterate over the keys with the given prefix
+    IterPrefix {
+        /// The iterated prefix
+        prefix: storage::Key,
+    },
+    /// Insert a verifier whose VP must be triggered
+    InsertVerifier {
+        /// The inserted verifier
+        address: Address,
+    },
+    /// Initialize a new established account
+    InitAccount {
+        /// The address of the new account
+        address: Address,
+    },
+    /// Check the presence of a key in the prior state
+    HasKeyPre {
+        /// The checked key
+        key: storage::Key,
+        /// Whether the key is present
+        present: bool,
+    },
+    /// Check the presence of a key in the posterior state
+    HasKeyPost {
+        /// The checked key
+        key: storage::Key,
+        /// Whether the key is present
+        present: bool,
+    },
+    /// Read the value of a key in the prior state
+    ReadPre {
+        /// The read key
+        key: storage::Key,
+        /// The value, if any
+        value: Option<Vec<u8>>,
+    },
+    /// Read the value of a key in the posterior state
+    ReadPost {
+        /// The read key
+        key: storage::Key,
+        /// The value, if any
+        value: Option<Vec<u8>>,
+    },
+    /// Read the value of a key in the temporary storage
+    ReadTemp {
+        /// The read key
+        key: storage::Key,
+        /// The value, if any
+        value: Option<Vec<u8>>,
+    },
+    /// Iterate over the keys with the given prefix in the prior state
+    IterPrefixPre {
+        /// The iterated prefix
+        prefix: storage::Key,
+    },
+    /// Iterate over the keys with the given prefix in the posterior state
+    IterPrefixPost {
+        /// The iterated prefix
+        prefix: storage::Key,
+    },
+    /// Evaluate another validity predicate
+    Eval {
+        /// The hash of the evaluated VP's code
+        vp_code_hash: Hash,
+        /// Whether the evaluated VP accepted
+        accepted: bool,
+    },
+    /// Log a message
+    Log {
+        /// The logged message
+        message: String,
+    },
+}
+
+/// A host function call along with the gas used by the transaction or the
+/// validity predicate once the call returned
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct TraceEntry {
+    /// The host function call
+    pub call: HostCall,
+    /// The total gas used so far
+    pub gas_used: Gas,
+}
+
+/// The decision of a validity predicate
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub enum VpDecision {
+    /// The VP accepted the transaction
+    Accepted,
+    /// The VP rejected the transaction
+    Rejected,
+    /// The VP found an invalid signature
+    InvalidSignature,
+    /// The VP failed to run
+    Error(String),
+}
+
+/// The trace of a validity predicate run. Native VPs don't go through the
+/// host environment, so only their decision is recorded.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct VpTrace {
+    /// The host calls made by the VP, in order
+    pub calls: Vec<TraceEntry>,
+    /// The decision of the VP
+    pub decision: VpDecision,
+}
+
+/// The trace of a transaction's execution and of the VPs it triggered
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct ExecutionTrace {
+    /// The host calls made by the transaction, in order
+    pub tx: Vec<TraceEntry>,
+    /// The traces of the triggered VPs
+    pub vps: BTreeMap<Address, VpTrace>,
+}
+
+/// Format an optional storage value
+fn fmt_value(value: &Option<Vec<u8>>) -> String {
+    match value {
+        Some(value) => HEXUPPER.encode(value),
+        None => "<none>".to_string(),
+    }
+}
+
+impl Display for HostCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostCall::HasKey { key, present } => {
+                write!(f, "has_key {key} -> {present}")
+            }
+            HostCall::Read { key, value } => {
+                write!(f, "read {key} -> {}", fmt_value(value))
+            }
+            HostCall::Write { key, value } => {
+                write!(f, "write {key} <- {}", HEXUPPER.encode(value))
+            }
+            HostCall::WriteTemp { key, value } => {
+                write!(f, "write_temp {key} <- {}", HEXUPPER.encode(value))
+            }
+            HostCall::Delete { key } => write!(f, "delete {key}"),
+            HostCall::IterPrefix { prefix } => {
+                write!(f, "iter_prefix {prefix}")
+            }
+            HostCall::InsertVerifier { address } => {
+                write!(f, "insert_verifier {address}")
+            }
+            HostCall::InitAccount { address } => {
+                write!(f, "init_account -> {address}")
+            }
+            HostCall::HasKeyPre { key, present } => {
+                write!(f, "has_key_pre {key} -> {present}")
+            }
+            HostCall::HasKeyPost { key, present } => {
+                write!(f, "has_key_post {key} -> {present}")
+            }
+            HostCall::ReadPre { key, value } => {
+                write!(f, "read_pre {key} -> {}", fmt_value(value))
+            }
+            HostCall::ReadPost { key, value } => {
+                write!(f, "read_post {key} -> {}", fmt_value(value))
+            }
+            HostCall::ReadTemp { key, value } => {
+                write!(f, "read_temp {key} -> {}", fmt_value(value))
+            }
+            HostCall::IterPrefixPre { prefix } => {
+                write!(f, "iter_prefix_pre {prefix}")
+            }
+            HostCall::IterPrefixPost { prefix } => {
+                write!(f, "iter_prefix_post {prefix}")
+            }
+            HostCall::Eval {
+                vp_code_hash,
+                accepted,
+            } => write!(f, "eval {vp_code_hash} -> {accepted}"),
+            HostCall::Log { message } => write!(f, "log {message:?}"),
+        }
+    }
+}
+
+impl Display for VpDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VpDecision::Accepted => write!(f, "accepted"),
+            VpDecision::Rejected => write!(f, "rejected"),
+            VpDecision::InvalidSignature => {
+                write!(f, "rejected (invalid signature)")
+            }
+            VpDecision::Error(err) => write!(f, "failed: {err}"),
+        }
+    }
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[gas {}] {}", self.gas_used, self.call)
+    }
+}
+
+impl Display for ExecutionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Transaction:")?;
+        for entry in &self.tx {
+            writeln!(f, "  {entry}")?;
+        }
+        for (address, vp) in &self.vps {
+            writeln!(f, "VP of {address}: {}", vp.decision)?;
+            for entry in &vp.calls {
+                writeln!(f, "  {entry}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -461,7 +461,9 @@ impl Signature {
         consume_verify_sig_gas: &mut F,
     ) -> std::result::Result<u8, VerifySigError>
     where
-        F: FnMut() -> std::result::Result<(), namada_gas::Error>,
+        F: FnMut(
+            &common::PublicKey,
+        ) -> std::result::Result<(), namada_gas::Error>,
     {
         // Records whether there are any successful verifications
         let mut verifications = 0;
@@ -473,7 +475,7 @@ impl Signature {
                     if let Some(pk) =
                         public_keys_index_map.get_public_key_from_index(*idx)
                     {
-                        consume_verify_sig_gas()?;
+                        consume_verify_sig_gas(&pk)?;
                         common::SigScheme::verify_signature(
                             &pk,
                             &self.get_raw_hash(),
@@ -494,7 +496,7 @@ impl Signature {
                     if let Some(map_idx) =
                         public_keys_index_map.get_index_from_public_key(pk)
                     {
-                        consume_verify_sig_gas()?;
+                        consume_verify_sig_gas(pk)?;
                         common::SigScheme::verify_signature(
                             pk,
                             &self.get_raw_hash(),
@@ -1187,7 +1189,9 @@ impl Tx {
         mut consume_verify_sig_gas: F,
     ) -> std::result::Result<Vec<&Signature>, VerifySigError>
     where
-        F: FnMut() -> std::result::Result<(), namada_gas::Error>,
+        F: FnMut(
+            &common::PublicKey,
+        ) -> std::result::Result<(), namada_gas::Error>,
     {
         let max_signatures = max_signatures.unwrap_or(u8::MAX);
         // Records the public key indices used in successful signatures
@@ -1260,7 +1264,7 @@ impl Tx {
             &None,
             1,
             None,
-            || Ok(()),
+            |_| Ok(()),
         )
         .map(|x| *x.first().unwrap())
         .map_err(|_| VerifySigError::InvalidWrapperSignature)
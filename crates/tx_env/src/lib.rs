@@ -5,6 +5,7 @@ use namada_core::borsh::BorshSerialize;
 use namada_core::types::address::Address;
 use namada_core::types::ibc::IbcEvent;
 use namada_core::types::storage;
+use namada_core::types::token::TransferLegEvent;
 use namada_storage::{Result, StorageRead, StorageWrite};
 
 /// Transaction host functions
@@ -51,6 +52,13 @@ pub trait TxEnv: StorageRead + StorageWrite {
     /// Emit an IBC event. On multiple calls, these emitted event will be added.
     fn emit_ibc_event(&mut self, event: &IbcEvent) -> Result<()>;
 
+    /// Emit the event of an applied leg of a multi-transfer. On multiple
+    /// calls, these emitted event will be added.
+    fn emit_transfer_leg_event(
+        &mut self,
+        event: &TransferLegEvent,
+    ) -> Result<()>;
+
     /// Request to charge the provided amount of gas for the current transaction
     fn charge_gas(&mut self, used_gas: u64) -> Result<()>;
 
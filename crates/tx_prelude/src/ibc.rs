@@ -8,7 +8,8 @@ pub use namada_core::types::ibc::{IbcEvent, IbcShieldedTransfer};
 use namada_core::types::token::DenominatedAmount;
 pub use namada_ibc::storage::is_ibc_key;
 pub use namada_ibc::{
-    IbcActions, IbcCommonContext, IbcStorageContext, ProofSpec, TransferModule,
+    IbcActions, IbcCommonContext, IbcStorageContext, IcaControllerModule,
+    IcaHostModule, ProofSpec, TransferModule,
 };
 use namada_token::denom_to_amount;
 use namada_tx_env::TxEnv;
@@ -20,8 +21,12 @@ use crate::{Ctx, Error};
 pub fn ibc_actions(ctx: &mut Ctx) -> IbcActions<Ctx> {
     let ctx = Rc::new(RefCell::new(ctx.clone()));
     let mut actions = IbcActions::new(ctx.clone());
-    let module = TransferModule::new(ctx);
+    let module = TransferModule::new(ctx.clone());
     actions.add_transfer_module(module.module_id(), module);
+    let module = IcaHostModule::new(ctx.clone());
+    actions.add_ica_host_module(module.module_id(), module);
+    let module = IcaControllerModule::new(ctx);
+    actions.add_ica_controller_module(module.module_id(), module);
     actions
 }
 
@@ -83,6 +88,10 @@ impl IbcStorageContext for Ctx {
         burn(self, target, token, denom_to_amount(amount, token, self)?)
     }
 
+    fn charge_gas(&mut self, used_gas: u64) -> Result<(), Error> {
+        <Ctx as TxEnv>::charge_gas(self, used_gas)
+    }
+
     fn log_string(&self, message: String) {
         super::log_string(message);
     }
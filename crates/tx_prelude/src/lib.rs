@@ -351,6 +351,20 @@ impl TxEnv for Ctx {
         Ok(())
     }
 
+    fn emit_transfer_leg_event(
+        &mut self,
+        event: &token::TransferLegEvent,
+    ) -> Result<(), Error> {
+        let event = borsh::to_vec(event).unwrap();
+        unsafe {
+            namada_tx_emit_transfer_leg_event(
+                event.as_ptr() as _,
+                event.len() as _,
+            )
+        };
+        Ok(())
+    }
+
     fn charge_gas(&mut self, used_gas: u64) -> Result<(), Error> {
         unsafe { namada_tx_charge_gas(used_gas) };
         Ok(())
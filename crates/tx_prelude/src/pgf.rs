@@ -1,3 +1,6 @@
+use namada_governance::pgf::storage::batch::{
+    ChallengePgfBatchData, SubmitPgfBatchData,
+};
 use namada_tx::data::pgf::UpdateStewardCommission;
 
 use super::*;
@@ -20,3 +23,25 @@ pub fn remove_steward(ctx: &mut Ctx, data: &Address) -> EnvResult<()> {
 
     Ok(())
 }
+
+pub fn submit_pgf_batch(
+    ctx: &mut Ctx,
+    data: SubmitPgfBatchData,
+) -> EnvResult<u64> {
+    ctx.insert_verifier(&data.steward)?;
+    let batch_id = namada_governance::pgf::storage::submit_batch(ctx, data)?;
+
+    Ok(batch_id)
+}
+
+pub fn challenge_pgf_batch(
+    ctx: &mut Ctx,
+    data: ChallengePgfBatchData,
+    content: Vec<u8>,
+) -> EnvResult<u64> {
+    ctx.insert_verifier(&data.challenger)?;
+    let proposal_id =
+        namada_governance::pgf::storage::challenge_batch(ctx, data, content)?;
+
+    Ok(proposal_id)
+}
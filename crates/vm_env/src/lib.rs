@@ -82,6 +82,12 @@ pub mod tx {
         // Emit an IBC event
         pub fn namada_tx_emit_ibc_event(event_ptr: u64, event_len: u64);
 
+        // Emit the event of an applied leg of a multi-transfer
+        pub fn namada_tx_emit_transfer_leg_event(
+            event_ptr: u64,
+            event_len: u64,
+        );
+
         // Get IBC events
         pub fn namada_tx_get_ibc_events(
             event_type_ptr: u64,
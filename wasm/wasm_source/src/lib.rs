@@ -4,6 +4,10 @@ pub mod tx_become_validator;
 pub mod tx_bond;
 #[cfg(feature = "tx_bridge_pool")]
 pub mod tx_bridge_pool;
+#[cfg(feature = "tx_bridge_pool_update")]
+pub mod tx_bridge_pool_update;
+#[cfg(feature = "tx_challenge_pgf_batch")]
+pub mod tx_challenge_pgf_batch;
 #[cfg(feature = "tx_change_consensus_key")]
 pub mod tx_change_consensus_key;
 #[cfg(feature = "tx_change_validator_commission")]
@@ -20,6 +24,8 @@ pub mod tx_ibc;
 pub mod tx_init_account;
 #[cfg(feature = "tx_init_proposal")]
 pub mod tx_init_proposal;
+#[cfg(feature = "tx_multi_transfer")]
+pub mod tx_multi_transfer;
 #[cfg(feature = "tx_reactivate_validator")]
 pub mod tx_reactivate_validator;
 #[cfg(feature = "tx_redelegate")]
@@ -28,6 +34,8 @@ pub mod tx_redelegate;
 pub mod tx_resign_steward;
 #[cfg(feature = "tx_reveal_pk")]
 pub mod tx_reveal_pk;
+#[cfg(feature = "tx_submit_pgf_batch")]
+pub mod tx_submit_pgf_batch;
 #[cfg(feature = "tx_transfer")]
 pub mod tx_transfer;
 #[cfg(feature = "tx_unbond")]
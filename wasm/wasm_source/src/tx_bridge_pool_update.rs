@@ -0,0 +1,61 @@
+//! A tx for cancelling a transfer pending in the Ethereum bridge
+//! pool, or for bumping its gas fees.
+use eth_bridge_pool::{GasFee, PendingTransfer, PendingTransferUpdate};
+use namada_tx_prelude::eth_bridge_pool::{
+    get_cancelled_key, get_gas_fee_bump_key, get_key_from_hash,
+    BRIDGE_POOL_ADDRESS,
+};
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1038546)]
+fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let update =
+        PendingTransferUpdate::try_from_slice(&data[..]).map_err(|e| {
+            Error::wrap("Error deserializing PendingTransferUpdate", e)
+        })?;
+    let pending_key = get_key_from_hash(update.transfer_hash());
+    let transfer: PendingTransfer = ctx
+        .read(&pending_key)?
+        .ok_or_err_msg("The transfer is not in the bridge pool")?;
+    match update {
+        PendingTransferUpdate::Cancel { transfer_hash } => {
+            log_string("Received transfer to cancel.");
+            // the escrowed assets are refunded by the protocol, once
+            // the transfer can no longer be relayed
+            ctx.delete(&pending_key)?;
+            ctx.write(&get_cancelled_key(&transfer_hash), &transfer)
+                .wrap_err("Could not cancel the transfer")?;
+            ctx.insert_verifier(&transfer.transfer.sender)?;
+        }
+        PendingTransferUpdate::BumpGasFee {
+            transfer_hash,
+            amount,
+        } => {
+            log_string("Received gas fee bump of a pending transfer.");
+            let GasFee {
+                token: ref fee_token_addr,
+                ref payer,
+                ..
+            } = transfer.gas_fee;
+            token::undenominated_transfer(
+                ctx,
+                payer,
+                &BRIDGE_POOL_ADDRESS,
+                fee_token_addr,
+                amount,
+            )?;
+            let bump_key = get_gas_fee_bump_key(&transfer_hash);
+            let bump: token::Amount = ctx.read(&bump_key)?.unwrap_or_default();
+            let bump = bump
+                .checked_add(amount)
+                .ok_or_err_msg("Gas fee bump overflowed")?;
+            ctx.write(&bump_key, bump)
+                .wrap_err("Could not bump the gas fees of the transfer")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,164 @@
+//! A tx for a validator to escalate a steward batch of pgf payments to a
+//! governance proposal
+
+use namada_tx_prelude::governance::pgf::storage::batch::ChallengePgfBatchData;
+use namada_tx_prelude::*;
+
+#[transaction(gas = 969395)]
+fn apply_tx(ctx: &mut Ctx, tx: Tx) -> TxResult {
+    let data = tx.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data = ChallengePgfBatchData::try_from_slice(&data[..])
+        .wrap_err("failed to decode a ChallengePgfBatchData")?;
+
+    // Get the content of the proposal from the referred to section
+    let content = tx
+        .get_section(&tx_data.content)
+        .ok_or_err_msg("Missing proposal content")
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?
+        .extra_data()
+        .ok_or_err_msg("Missing full proposal content")
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?;
+
+    let proposal_id = pgf::challenge_pgf_batch(ctx, tx_data, content)?;
+    debug_log!(
+        "Escalated steward payment batch to proposal {}",
+        proposal_id
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::governance::parameters::GovernanceParameters;
+    use namada::governance::pgf::parameters::PgfParameters;
+    use namada::governance::pgf::storage::batch::{
+        PgfBatchStatus, SubmitPgfBatchData,
+    };
+    use namada::governance::pgf::storage::{get_batch, submit_batch};
+    use namada::governance::pgf::MAX_PGF_ACTIONS;
+    use namada::governance::storage::proposal::{PGFInternalTarget, PGFTarget};
+    use namada::ledger::pgf::{PgfVp, ADDRESS as PGF};
+    use namada::ledger::pos::OwnedPosParams;
+    use namada::proof_of_stake::types::GenesisValidator;
+    use namada::types::dec::Dec;
+    use namada::types::storage::Epoch;
+    use namada_tests::log::test;
+    use namada_tests::native_vp::pos::init_pos;
+    use namada_tests::native_vp::TestNativeVpEnv;
+    use namada_tests::tx::*;
+    use namada_tx_prelude::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+    use namada_tx_prelude::chain::ChainId;
+    use namada_tx_prelude::key::testing::keypair_1;
+    use namada_tx_prelude::key::RefTo;
+    use namada_tx_prelude::{token, BorshSerializeExt};
+
+    use super::*;
+
+    /// Test that a validator can escalate a steward batch of
+    /// `MAX_PGF_ACTIONS` payments to a governance proposal and that the PGF
+    /// VP accepts it
+    #[test]
+    fn test_tx_challenge_pgf_batch() {
+        let steward = established_address_1();
+        let challenger = established_address_3();
+
+        let genesis_validators = [GenesisValidator {
+            address: challenger.clone(),
+            tokens: token::Amount::native_whole(1_000_000),
+            consensus_key: key::testing::keypair_1().ref_to(),
+            protocol_key: key::testing::keypair_2().ref_to(),
+            eth_hot_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            eth_cold_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            commission_rate: Dec::new(5, 2).unwrap(),
+            max_commission_rate_change: Dec::new(1, 2).unwrap(),
+            metadata: Default::default(),
+        }];
+        init_pos(
+            &genesis_validators[..],
+            &OwnedPosParams::default(),
+            Epoch(0),
+        );
+
+        let batch_id = tx_host_env::with(|tx_env| {
+            let native_token = tx_env.wl_storage.storage.native_token.clone();
+            tx_env.spawn_accounts([&steward]);
+            tx_env.credit_tokens(
+                &challenger,
+                &native_token,
+                token::Amount::native_whole(1_000),
+            );
+            PgfParameters {
+                stewards: [steward.clone()].into(),
+                ..Default::default()
+            }
+            .init_storage(&mut tx_env.wl_storage)
+            .unwrap();
+            GovernanceParameters::default()
+                .init_storage(&mut tx_env.wl_storage)
+                .unwrap();
+            let payment = PGFTarget::Internal(PGFInternalTarget {
+                target: established_address_2(),
+                amount: token::Amount::native_whole(1),
+            });
+            let batch_id = submit_batch(
+                &mut tx_env.wl_storage,
+                SubmitPgfBatchData {
+                    steward: steward.clone(),
+                    payments: vec![payment; MAX_PGF_ACTIONS],
+                },
+            )
+            .unwrap();
+            tx_env.commit_genesis();
+            batch_id
+        });
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        let (_, content) = tx.add_extra_section(b"content".to_vec(), None);
+        let data = ChallengePgfBatchData {
+            batch_id,
+            challenger: challenger.clone(),
+            content,
+        };
+        tx.add_code(vec![], None)
+            .add_serialized_data(data.serialize_to_vec())
+            .sign_wrapper(keypair_1());
+
+        apply_tx(ctx(), tx).unwrap();
+
+        let batch = get_batch(ctx(), batch_id).unwrap().unwrap();
+        assert_eq!(batch.payments.len(), MAX_PGF_ACTIONS);
+        assert_eq!(
+            batch.status,
+            PgfBatchStatus::Escalated {
+                challenger,
+                proposal_id: 0,
+            }
+        );
+
+        let tx_env = tx_host_env::take();
+        let vp_env = TestNativeVpEnv::from_tx_env(tx_env, PGF);
+        let result = vp_env.validate_tx(|ctx| PgfVp { ctx });
+        assert!(
+            result.expect("Validation of valid changes must not fail!"),
+            "PGF Validity predicate must accept this transaction"
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! A tx for a batch of transparent token transfers applied atomically.
+//! This tx uses `token::MultiTransfer` wrapped inside `SignedTxData`
+//! as its input as declared in `namada` crate.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1703358)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let multi = token::MultiTransfer::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::MultiTransfer")?;
+    debug_log!("apply_tx called with multi-transfer: {:#?}", multi);
+
+    if multi.legs.is_empty() {
+        return Err(Error::new_const("The multi-transfer has no legs"));
+    }
+    for (index, leg) in multi.legs.into_iter().enumerate() {
+        token::transfer(ctx, &leg.source, &leg.target, &leg.token, leg.amount)?;
+        ctx.emit_transfer_leg_event(&token::TransferLegEvent {
+            index: index as u64,
+            leg,
+        })?;
+    }
+    Ok(())
+}
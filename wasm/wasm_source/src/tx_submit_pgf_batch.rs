@@ -0,0 +1,105 @@
+//! A tx for a steward to submit a batch of pgf payments
+
+use namada_tx_prelude::governance::pgf::storage::batch::SubmitPgfBatchData;
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1222239)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let batch = SubmitPgfBatchData::try_from_slice(&data[..])
+        .wrap_err("failed to decode a SubmitPgfBatchData")?;
+
+    let batch_id = pgf::submit_pgf_batch(ctx, batch)?;
+    debug_log!("Submitted steward payment batch {}", batch_id);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::governance::pgf::parameters::PgfParameters;
+    use namada::governance::pgf::storage::get_batch;
+    use namada::governance::pgf::MAX_PGF_ACTIONS;
+    use namada::governance::storage::proposal::{PGFInternalTarget, PGFTarget};
+    use namada::ledger::pgf::{PgfVp, ADDRESS as PGF};
+    use namada_tests::log::test;
+    use namada_tests::native_vp::TestNativeVpEnv;
+    use namada_tests::tx::*;
+    use namada_tx_prelude::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_tx_prelude::chain::ChainId;
+    use namada_tx_prelude::key::testing::keypair_1;
+    use namada_tx_prelude::{token, BorshSerializeExt};
+
+    use super::*;
+
+    fn init_pgf(steward: &Address) {
+        tx_host_env::init();
+        tx_host_env::with(|tx_env| {
+            tx_env.spawn_accounts([steward]);
+            PgfParameters {
+                stewards: [steward.clone()].into(),
+                ..Default::default()
+            }
+            .init_storage(&mut tx_env.wl_storage)
+            .unwrap();
+            tx_env.commit_genesis();
+        });
+    }
+
+    fn submit_batch_tx(steward: &Address, len: usize) -> Tx {
+        let payment = PGFTarget::Internal(PGFInternalTarget {
+            target: established_address_2(),
+            amount: token::Amount::native_whole(1),
+        });
+        let data = SubmitPgfBatchData {
+            steward: steward.clone(),
+            payments: vec![payment; len],
+        };
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(data.serialize_to_vec())
+            .sign_wrapper(keypair_1());
+        tx
+    }
+
+    /// Test that a steward can submit a batch of `MAX_PGF_ACTIONS` payments
+    /// and that the PGF VP accepts it
+    #[test]
+    fn test_tx_submit_pgf_batch() {
+        let steward = established_address_1();
+        init_pgf(&steward);
+
+        apply_tx(ctx(), submit_batch_tx(&steward, MAX_PGF_ACTIONS)).unwrap();
+
+        let batch = get_batch(ctx(), 0).unwrap().unwrap();
+        assert_eq!(batch.steward, steward);
+        assert_eq!(batch.payments.len(), MAX_PGF_ACTIONS);
+
+        let tx_env = tx_host_env::take();
+        let vp_env = TestNativeVpEnv::from_tx_env(tx_env, PGF);
+        let result = vp_env.validate_tx(|ctx| PgfVp { ctx });
+        assert!(
+            result.expect("Validation of valid changes must not fail!"),
+            "PGF Validity predicate must accept this transaction"
+        );
+    }
+
+    /// Test that a batch of more than `MAX_PGF_ACTIONS` payments can't be
+    /// submitted
+    #[test]
+    fn test_tx_submit_pgf_batch_too_large() {
+        let steward = established_address_1();
+        init_pgf(&steward);
+
+        let result =
+            apply_tx(ctx(), submit_batch_tx(&steward, MAX_PGF_ACTIONS + 1));
+        assert!(result.is_err());
+        assert!(get_batch(ctx(), 0).unwrap().is_none());
+    }
+}